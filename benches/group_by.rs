@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use modav_core::repr::{
+    col_sheet::{Aggregation, ColumnSheet},
+    Config, Data, HeaderStrategy, Sheet, TypesStrategy,
+};
+
+const SIZES: [usize; 2] = [10_000, 100_000];
+
+fn csv(rows: usize) -> String {
+    let mut csv = String::from("region,units,price\n");
+    for idx in 0..rows {
+        csv.push_str(&format!(
+            "region {},{},{}.5\n",
+            idx % 53,
+            idx % 97,
+            idx % 11
+        ));
+    }
+
+    csv
+}
+
+fn config() -> Config<&'static str> {
+    Config::new("group_by.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+}
+
+/// Sums, counts and averages by going through the rows of a [`Sheet`], as
+/// done without a columnar layout.
+fn group_rows(sheet: &Sheet) -> usize {
+    let mut groups: HashMap<&Data, (i64, usize, f64)> = HashMap::new();
+
+    for row in sheet.iter_rows() {
+        let cells = row
+            .iter_cells()
+            .map(|cell| cell.get_data())
+            .collect::<Vec<_>>();
+        let group = groups.entry(cells[0]).or_default();
+
+        if let Data::Integer(units) = cells[1] {
+            group.0 += i64::from(*units);
+            group.1 += 1;
+        }
+        if let Data::Float(price) = cells[2] {
+            group.2 += f64::from(*price);
+        }
+    }
+
+    groups.len()
+}
+
+fn bench_group_by(c: &mut Criterion) {
+    let mut group = c.benchmark_group("group_by");
+    let aggs = [
+        (1, Aggregation::Sum),
+        (1, Aggregation::Count),
+        (2, Aggregation::Mean),
+    ];
+
+    for size in SIZES {
+        let csv = csv(size);
+        let columns = ColumnSheet::from_reader(csv.as_bytes(), config()).unwrap();
+        let rows = Sheet::from_reader(csv.as_bytes(), config()).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("columnar", size), &columns, |b, sheet| {
+            b.iter(|| sheet.group_by(black_box(0), &aggs).unwrap().height())
+        });
+
+        group.bench_with_input(BenchmarkId::new("rows", size), &rows, |b, sheet| {
+            b.iter(|| group_rows(black_box(sheet)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_group_by);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use modav_core::repr::{
+    sheet::{LineGraphAxisLabelStrategy, LineLabelStrategy, RowSelector, ScaleOptions},
+    ColumnHeader, ColumnType, Data, Sheet,
+};
+
+/// Widths and heights of the sheets charted.
+const SIZES: [(usize, usize); 2] = [(200, 5_000), (2_000, 1_000)];
+
+fn sheet(width: usize, height: usize) -> Sheet {
+    let mut headers = vec![ColumnHeader::new("label".into(), ColumnType::Text)];
+    headers.extend(
+        (1..width).map(|col| ColumnHeader::new(format!("column {}", col), ColumnType::Integer)),
+    );
+
+    let rows = (0..height)
+        .map(|row| {
+            let mut cells = vec![Data::Text(format!("row {}", row))];
+            cells.extend((1..width).map(|col| Data::Integer(((row * 31 + col) % 997) as i32)));
+            cells
+        })
+        .collect();
+
+    Sheet::from_rows(rows, headers, 0).unwrap()
+}
+
+fn bench_line_graph(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_line_graph");
+    group.sample_size(10);
+
+    for (width, height) in SIZES {
+        let sheet = sheet(width, height);
+        let id = format!("{}x{}", width, height);
+
+        group.bench_with_input(BenchmarkId::new("from_cell", &id), &sheet, |b, sheet| {
+            b.iter(|| {
+                black_box(sheet)
+                    .create_line_graph_labelled(
+                        LineGraphAxisLabelStrategy::Headers { y: "Value".into() },
+                        LineLabelStrategy::FromCell(0),
+                        RowSelector::default(),
+                        HashSet::new(),
+                    )
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Widths and heights of the wide but short sheets charted transposed.
+const WIDE_SIZES: [(usize, usize); 2] = [(5_000, 20), (20_000, 50)];
+
+fn bench_line_graph_transposed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_line_graph_transposed");
+    group.sample_size(10);
+
+    for (width, height) in WIDE_SIZES {
+        let sheet = sheet(width, height);
+        let id = format!("{}x{}", width, height);
+
+        group.bench_with_input(BenchmarkId::new("direct", &id), &sheet, |b, sheet| {
+            b.iter(|| {
+                black_box(sheet)
+                    .create_line_graph_transposed(
+                        LineGraphAxisLabelStrategy::Headers { y: "Value".into() },
+                        LineLabelStrategy::FromCell(0),
+                        RowSelector::default(),
+                        HashSet::new(),
+                        ScaleOptions::default(),
+                    )
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_line_graph, bench_line_graph_transposed);
+criterion_main!(benches);
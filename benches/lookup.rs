@@ -0,0 +1,55 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use modav_core::repr::{ColumnHeader, ColumnType, Data, Sheet};
+
+const SIZE: usize = 1_000_000;
+/// Scanning is quadratic, so it only looks up a sample of the rows
+const SCANNED: usize = 100;
+
+fn sheet(rows: usize) -> Sheet {
+    let rows = (0..rows)
+        .map(|idx| vec![Data::Integer(idx as i32), Data::Integer((idx % 97) as i32)])
+        .collect();
+    let headers = vec![
+        ColumnHeader::new("id".into(), ColumnType::Integer),
+        ColumnHeader::new("value".into(), ColumnType::Integer),
+    ];
+
+    Sheet::from_rows(rows, headers, 0).unwrap()
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sheet_row_lookup");
+    group.sample_size(10);
+
+    let mut sheet = sheet(SIZE);
+    group.bench_with_input(BenchmarkId::new("in_order", SIZE), &sheet, |b, sheet| {
+        b.iter(|| {
+            (0..sheet.height())
+                .filter_map(|id| sheet.get_row_by_id(black_box(id)))
+                .count()
+        })
+    });
+
+    // Sorting by value sets ids and positions apart
+    sheet.sort_rows(1).unwrap();
+    group.bench_with_input(BenchmarkId::new("reordered", SIZE), &sheet, |b, sheet| {
+        b.iter(|| {
+            (0..sheet.height())
+                .filter_map(|id| sheet.get_row_by_id(black_box(id)))
+                .count()
+        })
+    });
+
+    group.bench_with_input(BenchmarkId::new("scan", SCANNED), &sheet, |b, sheet| {
+        b.iter(|| {
+            (0..SCANNED)
+                .filter_map(|id| sheet.iter_rows().find(|row| row.id() == black_box(id)))
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use modav_core::repr::{col_sheet::ColumnSheet, Config, HeaderStrategy, TypesStrategy};
+
+const SIZES: [usize; 2] = [10_000, 100_000];
+
+fn sheet(rows: usize) -> ColumnSheet {
+    let mut csv = String::from("a,b,c,d,e,f,g,h\n");
+    for idx in 0..rows {
+        csv.push_str(&format!(
+            "{},{}.5,label {},{},{},{}.25,{},x{}\n",
+            idx,
+            idx % 97,
+            idx % 31,
+            idx % 2 == 0,
+            idx * 3,
+            idx % 11,
+            idx % 5,
+            idx % 7
+        ));
+    }
+
+    let config = Config::new("rows.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+
+    ColumnSheet::from_reader(csv.as_bytes(), config).unwrap()
+}
+
+fn bench_rows(c: &mut Criterion) {
+    let mut group = c.benchmark_group("column_sheet_rows");
+    group.sample_size(10);
+
+    for size in SIZES {
+        let sheet = sheet(size);
+
+        // iter_rows is expected to beat fetching each row with get_row
+        group.bench_with_input(BenchmarkId::new("get_row", size), &sheet, |b, sheet| {
+            b.iter(|| {
+                (0..sheet.height())
+                    .filter_map(|row| sheet.get_row(black_box(row)))
+                    .map(|row| row.len())
+                    .sum::<usize>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("iter_rows", size), &sheet, |b, sheet| {
+            b.iter(|| {
+                black_box(sheet)
+                    .iter_rows()
+                    .map(|row| row.len())
+                    .sum::<usize>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_rows);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use modav_core::{models::ScaleKind, prelude::Scale, repr::Data};
+
+const SIZES: [usize; 2] = [10_000, 1_000_000];
+
+fn integers(size: usize) -> Vec<Data> {
+    // A cheap linear congruential generator keeps the points reproducible
+    let mut state: u32 = 17;
+    (0..size)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            Data::Integer((state >> 8) as i32 % 50_000)
+        })
+        .collect()
+}
+
+fn bench_scales(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scale_new");
+    group.sample_size(10);
+
+    for size in SIZES {
+        let ints = integers(size);
+        let mut sorted = ints.clone();
+        sorted.sort();
+        let floats = ints
+            .iter()
+            .map(|point| match point {
+                Data::Integer(num) => Data::Float(*num as f32 / 8.0),
+                _ => Data::None,
+            })
+            .collect::<Vec<Data>>();
+        let texts = ints
+            .iter()
+            .map(|point| Data::Text(format!("label {}", point)))
+            .collect::<Vec<Data>>();
+
+        let cases = [
+            ("integer", &ints, ScaleKind::Integer),
+            ("integer_sorted", &sorted, ScaleKind::Integer),
+            ("float", &floats, ScaleKind::Float),
+            ("categorical", &texts, ScaleKind::Categorical),
+        ];
+
+        for (name, points, kind) in cases {
+            group.bench_with_input(BenchmarkId::new(name, size), points, |b, points| {
+                b.iter(|| Scale::new(black_box(points.clone()), kind))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_positions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scale_positions");
+    group.sample_size(10);
+
+    for size in SIZES {
+        let ints = integers(size);
+        let texts = ints
+            .iter()
+            .map(|point| Data::Text(format!("label {}", point)))
+            .collect::<Vec<Data>>();
+
+        let cases = [
+            ("integer", &ints, ScaleKind::Integer),
+            ("categorical", &texts, ScaleKind::Categorical),
+        ];
+
+        for (name, points, kind) in cases {
+            let scale = Scale::new(points.clone(), kind);
+            // Index categories before timing the lookups
+            scale.position_of(&points[0]);
+
+            group.bench_with_input(BenchmarkId::new(name, size), points, |b, points| {
+                b.iter(|| scale.positions(black_box(points).iter()))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_scales, bench_positions);
+criterion_main!(benches);
@@ -1,2 +1,8 @@
+#[macro_use]
+mod trace;
+
 pub mod models;
+pub mod prelude;
 pub mod repr;
+#[cfg(feature = "samples")]
+pub mod samples;
@@ -1,9 +1,21 @@
 pub mod bar;
+pub mod box_plot;
 pub mod common;
+#[cfg(feature = "serde")]
+pub mod export;
+pub mod heatmap;
 pub mod line;
 pub mod stacked_bar;
+#[cfg(feature = "vega")]
+pub mod vega;
 
 pub use bar::*;
+pub use box_plot::*;
 pub use common::*;
+#[cfg(feature = "serde")]
+pub use export::*;
+pub use heatmap::*;
 pub use line::*;
 pub use stacked_bar::*;
+#[cfg(feature = "vega")]
+pub use vega::*;
@@ -1,11 +1,25 @@
-use std::fmt::{self, Debug};
-
-use super::{Point, Scale};
-
-#[derive(Clone, Debug, PartialEq)]
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+};
+
+use super::{
+    duplicate_labels, fit_annotation, Annotation, LabelTransform, Point, Scale, ScaleBuilder,
+    ScaleKind,
+};
+use crate::repr::Data;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bar {
+    #[cfg_attr(feature = "serde", serde(rename = "label"))]
     pub label: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "point"))]
     pub point: Point,
+    /// The weight of the bar, such as the number of values averaged into
+    /// it. Weights are left out of the scales of a chart.
+    #[cfg_attr(feature = "serde", serde(rename = "weight", default))]
+    pub weight: Option<Data>,
 }
 
 impl Bar {
@@ -13,6 +27,7 @@ impl Bar {
         Self {
             point: point.into(),
             label: Some(label.into()),
+            weight: None,
         }
     }
 
@@ -20,6 +35,7 @@ impl Bar {
         Self {
             point: point.into(),
             label: None,
+            weight: None,
         }
     }
 
@@ -27,15 +43,146 @@ impl Bar {
         self.label = Some(label);
         self
     }
+
+    pub fn weight(mut self, weight: impl Into<Data>) -> Self {
+        self.weight = Some(weight.into());
+        self
+    }
+}
+
+/// Determines the order of the bars kept by [`TopN`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TopNOrdering {
+    /// Largest value first
+    #[default]
+    ByValue,
+    /// Ordered by the x value of the bar
+    ByLabel,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Keeps the `n` bars with the largest values, merging the rest into a
+/// single bar labelled `other_label`, whose value is their sum. Bars sharing
+/// an x value are summed before they are ranked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopN {
+    pub n: usize,
+    pub other_label: String,
+    pub ordering: TopNOrdering,
+}
+
+impl TopN {
+    pub fn new(n: usize, other_label: impl Into<String>) -> Self {
+        Self {
+            n,
+            other_label: other_label.into(),
+            ordering: TopNOrdering::default(),
+        }
+    }
+
+    pub fn ordering(mut self, ordering: TopNOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Buckets `bars`, which are expected to have numeric values on the y
+    /// axis. Bars sharing an x value are first merged into one, keeping the
+    /// label of the first. The other bar is always last, and is only added if
+    /// any bar was merged into it.
+    ///
+    /// Returns an error if a sum overflows the type of its values, or if the
+    /// other bar is added while a bar already has `other_label` as its x
+    /// value.
+    pub(crate) fn apply(&self, bars: Vec<Bar>) -> Result<Vec<Bar>, BarChartError> {
+        let mut positions: HashMap<Data, usize> = HashMap::new();
+        let mut merged: Vec<Bar> = Vec::with_capacity(bars.len());
+
+        for bar in bars {
+            match positions.get(&bar.point.x) {
+                Some(&pos) => {
+                    let first = &mut merged[pos];
+                    first.point.y = add(&first.point.y, &bar.point.y)?;
+                }
+                None => {
+                    positions.insert(bar.point.x.clone(), merged.len());
+                    merged.push(bar);
+                }
+            }
+        }
+
+        let mut bars = merged;
+        bars.sort_by(|a, b| b.point.y.cmp(&a.point.y));
+
+        let rest = if bars.len() > self.n {
+            bars.split_off(self.n)
+        } else {
+            Vec::new()
+        };
+
+        if let TopNOrdering::ByLabel = self.ordering {
+            bars.sort_by(|a, b| a.point.x.cmp(&b.point.x));
+        }
+
+        if rest.is_empty() {
+            return Ok(bars);
+        }
+
+        let other = Data::Text(self.other_label.clone());
+        if positions.contains_key(&other) {
+            return Err(BarChartError::OtherLabelTaken(self.other_label.clone()));
+        }
+
+        let sum = rest
+            .iter()
+            .try_fold(Data::None, |acc, bar| add(&acc, &bar.point.y))?;
+
+        bars.push(Bar::new(self.other_label.clone(), (other, sum)));
+
+        Ok(bars)
+    }
+}
+
+/// Returns the sum of two values of the same numeric type, where empty values
+/// count as zero.
+fn add(x: &Data, y: &Data) -> Result<Data, BarChartError> {
+    let sum = match (x, y) {
+        (x, Data::None) => Some(x.clone()),
+        (Data::None, y) => Some(y.clone()),
+        (Data::Integer(x), Data::Integer(y)) => x.checked_add(*y).map(Data::Integer),
+        (Data::Number(x), Data::Number(y)) => x.checked_add(*y).map(Data::Number),
+        (Data::Float(x), Data::Float(y)) => Some(Data::Float(x + y)),
+        _ => None,
+    };
+
+    sum.ok_or_else(|| BarChartError::InvalidSum(x.to_string(), y.to_string()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BarChart {
+    #[cfg_attr(feature = "serde", serde(rename = "bars"))]
     pub bars: Vec<Bar>,
+    #[cfg_attr(feature = "serde", serde(rename = "x_label"))]
     pub x_label: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "y_label"))]
     pub y_label: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "x_scale"))]
     pub x_scale: Scale,
+    #[cfg_attr(feature = "serde", serde(rename = "y_scale"))]
     pub y_scale: Scale,
+    /// The part of the y scale to show, leaving outliers off it. Every
+    /// point lies on `y_scale`, though not necessarily on the view. See
+    /// [`ScaleOptions::clamp_percentiles`](crate::repr::ScaleOptions::clamp_percentiles)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "y_view", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub y_view: Option<Scale>,
+    /// Marks over the graph. See [`BarChart::annotate`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "annotations", default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub annotations: Vec<Annotation>,
 }
 
 #[allow(dead_code)]
@@ -50,12 +197,125 @@ impl BarChart {
             bars,
             x_label: None,
             y_label: None,
+            y_view: None,
+            annotations: Vec::new(),
         })
     }
 
+    /// Returns the y scale to show, which is `y_view` if set and `y_scale`
+    /// otherwise.
+    pub fn shown_y_scale(&self) -> &Scale {
+        self.y_view.as_ref().unwrap_or(&self.y_scale)
+    }
+
+    /// Adds `annotation` to the chart. Returns an error if a value of the
+    /// annotation is not on the scale of its axis, unless `extend` is true
+    /// and the scale extends to it. See [`Scale::extend_to`].
+    pub fn annotate(&mut self, annotation: Annotation, extend: bool) -> Result<(), BarChartError> {
+        fit_annotation(
+            &annotation,
+            &mut self.x_scale,
+            &mut self.y_scale,
+            self.y_view.as_mut(),
+            extend,
+        )
+        .map_err(|(axis, value)| BarChartError::OutOfRange(axis.to_string(), value.to_string()))?;
+        self.annotations.push(annotation);
+
+        Ok(())
+    }
+
+    /// Returns the chart with `annotation` added. See [`BarChart::annotate`].
+    pub fn with_annotation(
+        mut self,
+        annotation: Annotation,
+        extend: bool,
+    ) -> Result<Self, BarChartError> {
+        self.annotate(annotation, extend)?;
+        Ok(self)
+    }
+
+    /// Returns true if the chart has no bars, as when every row of a sheet
+    /// is excluded. The scales of an empty chart are empty.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// Returns a new bar chart of `points`, with scales of `x_kind` and
+    /// `y_kind` built while the points are read rather than after they are
+    /// collected. The bar at each position is labelled by the label at that
+    /// position in `labels`, and bars without one are unlabelled.
+    ///
+    /// The scales are those [`Scale::new`] builds over all the points. See
+    /// [`ScaleBuilder`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{models::{BarChart, Point, ScaleKind}, repr::Data};
+    ///
+    /// let points = [
+    ///     Point::new(Data::Text("jan".into()), Data::Integer(12)),
+    ///     Point::new(Data::Text("feb".into()), Data::Integer(-4)),
+    /// ];
+    /// let labels = [Some("first".to_string())];
+    ///
+    /// let chart =
+    ///     BarChart::from_points(points, ScaleKind::Categorical, ScaleKind::Integer, labels).unwrap();
+    ///
+    /// assert_eq!(chart.bars[0].label.as_deref(), Some("first"));
+    /// assert_eq!(chart.bars[1].label, None);
+    /// assert_eq!(chart.x_scale.length, 2);
+    /// ```
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point>,
+        x_kind: ScaleKind,
+        y_kind: ScaleKind,
+        labels: impl IntoIterator<Item = Option<String>>,
+    ) -> Result<Self, BarChartError> {
+        let mut labels = labels.into_iter();
+        let bars = points.into_iter().map(|point| Bar {
+            label: labels.next().flatten(),
+            point,
+            weight: None,
+        });
+
+        Self::with_kinds(bars, x_kind, y_kind)
+    }
+
+    /// Returns a new bar chart of `bars`, with scales of `x_kind` and
+    /// `y_kind` built while the bars are read.
+    pub(crate) fn with_kinds(
+        bars: impl IntoIterator<Item = Bar>,
+        x_kind: ScaleKind,
+        y_kind: ScaleKind,
+    ) -> Result<Self, BarChartError> {
+        let mut x_scale = ScaleBuilder::new(x_kind);
+        let mut y_scale = ScaleBuilder::new(y_kind);
+
+        let bars = bars
+            .into_iter()
+            .inspect(|bar| {
+                x_scale.push(bar.point.x.clone());
+                y_scale.push(bar.point.y.clone());
+            })
+            .collect();
+
+        Self::new(bars, x_scale.build(), y_scale.build())
+    }
+
+    /// Checks that every point of the chart is on the scales, as
+    /// [`BarChart::new`] does.
+    #[cfg(feature = "serde")]
+    pub(crate) fn assert_scales(&self) -> Result<(), BarChartError> {
+        Self::assert_x_scale(&self.x_scale, &self.bars)?;
+        Self::assert_y_scale(&self.y_scale, &self.bars)
+    }
+
     fn assert_x_scale(scale: &Scale, bars: &[Bar]) -> Result<(), BarChartError> {
+        let contains = scale.contains_fn();
         for x in bars.iter().map(|bar| &bar.point.x) {
-            if !scale.contains(x) {
+            if !contains(x) {
                 return Err(BarChartError::OutOfRange("X".into(), x.to_string()));
             }
         }
@@ -64,8 +324,9 @@ impl BarChart {
     }
 
     fn assert_y_scale(scale: &Scale, bars: &[Bar]) -> Result<(), BarChartError> {
+        let contains = scale.contains_fn();
         for y in bars.iter().map(|bar| &bar.point.y) {
-            if !scale.contains(y) {
+            if !contains(y) {
                 return Err(BarChartError::OutOfRange("Y".into(), y.to_string()));
             }
         }
@@ -78,15 +339,97 @@ impl BarChart {
         self
     }
 
+    /// Applies `transform` to the label of every bar.
+    ///
+    /// Returns the labels shared by more than one bar after the transform.
+    pub fn transform_labels(&mut self, transform: &LabelTransform) -> Vec<String> {
+        for bar in self.bars.iter_mut() {
+            if let Some(label) = bar.label.as_mut() {
+                *label = transform.apply(label);
+            }
+        }
+
+        self.duplicate_labels()
+    }
+
+    /// Returns the labels shared by more than one bar, in order of first
+    /// appearance.
+    pub fn duplicate_labels(&self) -> Vec<String> {
+        duplicate_labels(self.bars.iter().filter_map(|bar| bar.label.as_ref()))
+    }
+
     pub fn y_label(mut self, label: impl Into<String>) -> Self {
         self.y_label = Some(label.into());
         self
     }
+
+    /// Returns the sum of the numeric weights of the bars. Bars without a
+    /// numeric weight are ignored.
+    pub fn total_weight(&self) -> f64 {
+        self.bars
+            .iter()
+            .filter_map(|bar| bar.weight.as_ref()?.as_f64())
+            .sum()
+    }
+
+    /// Returns the weight of each bar divided by the largest absolute
+    /// weight, in the order of the bars. Non-negative weights are scaled to
+    /// `0.0..=1.0`. Bars without a numeric weight have [`None`], and if every
+    /// weight is zero, so are the scaled weights.
+    pub fn normalize_weights(&self) -> Vec<Option<f64>> {
+        let weights: Vec<Option<f64>> = self
+            .bars
+            .iter()
+            .map(|bar| bar.weight.as_ref()?.as_f64())
+            .collect();
+        let largest = weights
+            .iter()
+            .flatten()
+            .fold(0.0_f64, |largest, weight| largest.max(weight.abs()));
+
+        weights
+            .into_iter()
+            .map(|weight| {
+                weight.map(|weight| match largest > 0.0 {
+                    true => weight / largest,
+                    false => 0.0,
+                })
+            })
+            .collect()
+    }
+
+    /// Moves the bar at `from` to `to`, shifting the bars in between. No move
+    /// occurs if either index is out of range.
+    ///
+    /// If `reorder_scale` is true and the x scale is categorical, the x scale
+    /// is reordered to follow the bars. See [`BarChart::reorder_x_scale`].
+    pub fn move_bar(&mut self, from: usize, to: usize, reorder_scale: bool) {
+        if from < self.bars.len() && to < self.bars.len() {
+            let bar = self.bars.remove(from);
+            self.bars.insert(to, bar);
+        }
+
+        if reorder_scale {
+            self.reorder_x_scale();
+        }
+    }
+
+    /// Reorders a categorical x scale so that its points follow the x values
+    /// of the bars, with points not used by any bar last. Other scales are
+    /// left unchanged.
+    pub fn reorder_x_scale(&mut self) {
+        self.x_scale
+            .reorder_categories(self.bars.iter().map(|bar| &bar.point.x));
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BarChartError {
     OutOfRange(String, String),
+    /// Two values merged into one bar cannot be summed within their type
+    InvalidSum(String, String),
+    /// The label of the other bar of a [`TopN`] is already an x value
+    OtherLabelTaken(String),
 }
 
 impl fmt::Display for BarChartError {
@@ -99,6 +442,12 @@ impl fmt::Display for BarChartError {
                     val, sc
                 )
             }
+            BarChartError::InvalidSum(x, y) => {
+                write!(f, "Cannot sum the bar values {} and {}", x, y)
+            }
+            BarChartError::OtherLabelTaken(label) => {
+                write!(f, "A bar is already labelled {}", label)
+            }
         }
     }
 }
@@ -107,7 +456,7 @@ impl std::error::Error for BarChartError {}
 
 #[cfg(test)]
 mod barchart_tests {
-    use super::super::ScaleKind;
+    use super::super::{Axis, ScaleKind};
     use super::*;
     use crate::repr::Data;
 
@@ -180,4 +529,179 @@ mod barchart_tests {
             Err(e) => assert_eq!(e, expected),
         }
     }
+
+    #[test]
+    fn test_move_bar() {
+        let bars = ["a", "b", "c"]
+            .into_iter()
+            .zip(1..)
+            .map(|(x, y)| Bar::new(x, (Data::Text(x.into()), Data::Integer(y))))
+            .collect();
+        let x_scale = Scale::new(["a", "b", "c", "d"], ScaleKind::Categorical);
+        let y_scale = Scale::new(0..5, ScaleKind::Integer);
+        let mut chart = BarChart::new(bars, x_scale, y_scale).unwrap();
+        let labels = |chart: &BarChart| {
+            chart
+                .bars
+                .iter()
+                .map(|bar| bar.label.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        chart.move_bar(2, 0, false);
+        assert_eq!(labels(&chart), vec!["c", "a", "b"]);
+        assert_eq!(
+            chart.x_scale.points(),
+            vec!["a", "b", "c", "d"]
+                .into_iter()
+                .map(Data::from)
+                .collect::<Vec<_>>()
+        );
+
+        chart.move_bar(0, 1, true);
+        assert_eq!(labels(&chart), vec!["a", "c", "b"]);
+        assert_eq!(
+            chart.x_scale.points(),
+            vec!["a", "c", "b", "d"]
+                .into_iter()
+                .map(Data::from)
+                .collect::<Vec<_>>()
+        );
+
+        chart.x_scale.invert();
+        chart.move_bar(2, 0, true);
+        assert_eq!(labels(&chart), vec!["b", "a", "c"]);
+        assert_eq!(
+            chart.x_scale.points(),
+            vec!["b", "a", "c", "d"]
+                .into_iter()
+                .map(Data::from)
+                .collect::<Vec<_>>()
+        );
+
+        chart.move_bar(0, 3, true);
+        assert_eq!(labels(&chart), vec!["b", "a", "c"]);
+        assert!(chart.x_scale.is_descending());
+    }
+
+    #[test]
+    fn test_weights() {
+        let bars = vec![
+            Bar::from_point((Data::Integer(1), Data::Integer(1))).weight(4),
+            Bar::from_point((Data::Integer(2), Data::Integer(2))),
+            Bar::from_point((Data::Integer(3), Data::Integer(3))).weight(Data::Float(-8.0)),
+            Bar::from_point((Data::Integer(4), Data::Integer(4))).weight("heavy"),
+        ];
+        let x_scale = Scale::new(0..5, ScaleKind::Integer);
+        let y_scale = Scale::new(0..5, ScaleKind::Integer);
+        let mut chart = BarChart::new(bars, x_scale, y_scale).unwrap();
+
+        assert_eq!(chart.total_weight(), -4.0);
+        assert_eq!(
+            chart.normalize_weights(),
+            vec![Some(0.5), None, Some(-1.0), None]
+        );
+
+        for bar in chart.bars.iter_mut() {
+            bar.weight = bar.weight.take().map(|_| Data::Integer(0));
+        }
+        assert_eq!(
+            chart.normalize_weights(),
+            vec![Some(0.0), None, Some(0.0), Some(0.0)]
+        );
+    }
+
+    #[test]
+    fn test_annotations() {
+        let points = [(1, 4), (2, 12), (3, 7)].map(|(x, y)| Point::new(x.into(), y.into()));
+        let chart =
+            BarChart::from_points(points, ScaleKind::Integer, ScaleKind::Integer, []).unwrap();
+        assert!(!chart.y_scale.contains(&Data::Integer(25)));
+        let target = Annotation::HLine {
+            y: Data::Integer(25),
+            label: Some("Target".into()),
+        };
+
+        let mut fixed = chart.clone();
+        assert_eq!(
+            fixed.annotate(target.clone(), false),
+            Err(BarChartError::OutOfRange("Y".into(), "25".into()))
+        );
+        assert_eq!(fixed, chart);
+
+        let extended = chart.clone().with_annotation(target.clone(), true).unwrap();
+        assert_eq!(extended.annotations, vec![target.clone()]);
+        assert!(extended.y_scale.contains(&Data::Integer(25)));
+        assert_eq!(extended.x_scale, chart.x_scale);
+
+        // Bands need both ends on the scale of their axis
+        let band = Annotation::Band {
+            from: Data::Integer(1),
+            to: Data::Float(2.5),
+            axis: Axis::Y,
+        };
+        let mut fixed = extended.clone();
+        assert!(fixed.annotate(band, true).is_err());
+        assert_eq!(fixed, extended);
+
+        // A clamped chart shows annotations past its view
+        let mut clamped = chart.clone();
+        clamped.y_view = Some(Scale::new(vec![4, 7], ScaleKind::Integer));
+        let outlier = Annotation::HLine {
+            y: Data::Integer(12),
+            label: None,
+        };
+        clamped.annotate(outlier, false).unwrap();
+        assert_eq!(clamped.y_scale, chart.y_scale);
+        assert!(clamped.shown_y_scale().contains(&Data::Integer(12)));
+        assert!(clamped.shown_y_scale().contains(&Data::Integer(4)));
+
+        let clamped = clamped.with_annotation(target, true).unwrap();
+        assert!(clamped.y_scale.contains(&Data::Integer(25)));
+        assert!(clamped.shown_y_scale().contains(&Data::Integer(25)));
+    }
+
+    #[test]
+    fn test_from_points() {
+        let xs = ["one", "two", "one", "three"];
+        let ys = [4, -2, 7, 4];
+        let points: Vec<Point> = xs
+            .into_iter()
+            .zip(ys)
+            .map(|(x, y)| Point::new(Data::Text(x.into()), Data::Integer(y)))
+            .collect();
+        let labels = [Some("a".to_string()), None, Some("c".to_string())];
+
+        let chart = BarChart::from_points(
+            points.clone(),
+            ScaleKind::Categorical,
+            ScaleKind::Integer,
+            labels,
+        )
+        .unwrap();
+
+        let bar_labels: Vec<Option<&str>> =
+            chart.bars.iter().map(|bar| bar.label.as_deref()).collect();
+        assert_eq!(bar_labels, vec![Some("a"), None, Some("c"), None]);
+        assert!(chart.bars.iter().map(|bar| &bar.point).eq(points.iter()));
+        assert_eq!(
+            chart.x_scale,
+            Scale::new(
+                points.iter().map(|point| point.x.clone()),
+                ScaleKind::Categorical
+            )
+        );
+        assert_eq!(
+            chart.y_scale,
+            Scale::new(
+                points.iter().map(|point| point.y.clone()),
+                ScaleKind::Integer
+            )
+        );
+
+        let chart =
+            BarChart::from_points(points, ScaleKind::Integer, ScaleKind::Integer, []).unwrap();
+        assert_eq!(chart.x_scale.kind, ScaleKind::Categorical);
+        assert!(chart.bars.iter().all(|bar| bar.label.is_none()));
+    }
 }
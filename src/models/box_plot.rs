@@ -0,0 +1,176 @@
+use std::fmt::{self, Debug};
+
+use super::{quantile, Scale};
+use crate::repr::Data;
+
+/// The summary of a group of values within a [`BoxPlot`].
+///
+/// All values are [`Data::Float`]. `min` and `max` are the ends of the
+/// whiskers, the smallest and largest values which are not outliers.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoxItem {
+    pub label: Option<String>,
+    pub min: Data,
+    pub q1: Data,
+    pub median: Data,
+    pub q3: Data,
+    pub max: Data,
+    /// Values more than 1.5 times the interquartile range below the first
+    /// quartile or above the third quartile.
+    pub outliers: Vec<Data>,
+}
+
+impl BoxItem {
+    /// Summarises `values`, which need not be sorted. Returns [`None`] if
+    /// `values` is empty.
+    ///
+    /// Quartiles are interpolated linearly between the closest values, so
+    /// groups with fewer than 4 values still produce a box, although with
+    /// degenerate quartiles. A single value produces a box where every
+    /// quartile is that value.
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut values = values.into_iter().collect::<Vec<f64>>();
+
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_by(|x, y| x.total_cmp(y));
+
+        let q1 = quantile(&values, 0.25);
+        let median = quantile(&values, 0.5);
+        let q3 = quantile(&values, 0.75);
+
+        let iqr = q3 - q1;
+        let lower = q1 - 1.5 * iqr;
+        let upper = q3 + 1.5 * iqr;
+
+        let (inner, outliers): (Vec<f64>, Vec<f64>) = values
+            .into_iter()
+            .partition(|value| lower <= *value && *value <= upper);
+
+        // The quartiles always lie within the fences so inner is never empty
+        let min = inner.first().copied().unwrap_or(q1);
+        let max = inner.last().copied().unwrap_or(q3);
+
+        Some(Self {
+            label: None,
+            min: Data::Float(min as f32),
+            q1: Data::Float(q1 as f32),
+            median: Data::Float(median as f32),
+            q3: Data::Float(q3 as f32),
+            max: Data::Float(max as f32),
+            outliers: outliers
+                .into_iter()
+                .map(|value| Data::Float(value as f32))
+                .collect(),
+        })
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Returns all the values which should be drawn for this item.
+    fn values(&self) -> impl Iterator<Item = &Data> {
+        [&self.min, &self.q1, &self.median, &self.q3, &self.max]
+            .into_iter()
+            .chain(self.outliers.iter())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoxPlot {
+    pub items: Vec<BoxItem>,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+    pub y_scale: Scale,
+}
+
+impl BoxPlot {
+    pub fn new(items: Vec<BoxItem>, y_scale: Scale) -> Result<Self, BoxPlotError> {
+        Self::assert_y_scale(&y_scale, &items)?;
+
+        Ok(Self {
+            items,
+            y_scale,
+            x_label: None,
+            y_label: None,
+        })
+    }
+
+    fn assert_y_scale(scale: &Scale, items: &[BoxItem]) -> Result<(), BoxPlotError> {
+        for value in items.iter().flat_map(|item| item.values()) {
+            if !scale.contains(value) {
+                return Err(BoxPlotError::OutOfRange(value.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn x_label(mut self, label: impl Into<String>) -> Self {
+        self.x_label = Some(label.into());
+        self
+    }
+
+    pub fn y_label(mut self, label: impl Into<String>) -> Self {
+        self.y_label = Some(label.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoxPlotError {
+    OutOfRange(String),
+}
+
+impl fmt::Display for BoxPlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoxPlotError::OutOfRange(val) => {
+                write!(f, "The value {} is out of range of the y axis", val)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoxPlotError {}
+
+#[cfg(test)]
+mod box_plot_tests {
+    use super::*;
+
+    #[test]
+    fn test_box_item() {
+        // Sorted: 1, 2, 3, 4, 5, 6, 7, 8, 30
+        let item = BoxItem::new([4.0, 2.0, 30.0, 1.0, 5.0, 3.0, 8.0, 7.0, 6.0]).unwrap();
+
+        assert_eq!(item.min, Data::Float(1.0));
+        assert_eq!(item.q1, Data::Float(3.0));
+        assert_eq!(item.median, Data::Float(5.0));
+        assert_eq!(item.q3, Data::Float(7.0));
+        assert_eq!(item.max, Data::Float(8.0));
+        assert_eq!(item.outliers, vec![Data::Float(30.0)]);
+
+        let item = BoxItem::new([2.0]).unwrap();
+        assert_eq!(item.min, Data::Float(2.0));
+        assert_eq!(item.median, Data::Float(2.0));
+        assert_eq!(item.max, Data::Float(2.0));
+        assert!(item.outliers.is_empty());
+
+        assert!(BoxItem::new([]).is_none());
+    }
+
+    #[test]
+    fn test_faulty_box_plot() {
+        let item = BoxItem::new([1.0, 2.0, 3.0]).unwrap();
+        let scale = Scale::from(vec![1.0, 2.0]);
+
+        assert_eq!(
+            BoxPlot::new(vec![item], scale),
+            Err(BoxPlotError::OutOfRange("2.5".into()))
+        );
+    }
+}
@@ -1,9 +1,17 @@
 use crate::repr::{ColumnType, Data};
-use std::{collections::HashSet, fmt::Debug};
-
-#[derive(Debug, Clone, PartialEq)]
+use once_cell::sync::OnceCell;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point<X = Data, Y = Data> {
+    #[cfg_attr(feature = "serde", serde(rename = "x"))]
     pub x: X,
+    #[cfg_attr(feature = "serde", serde(rename = "y"))]
     pub y: Y,
 }
 
@@ -19,15 +27,115 @@ impl<X, Y> From<(X, Y)> for Point<X, Y> {
     }
 }
 
+/// The kinds of charts supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChartKind {
+    /// A [`LineGraph`](super::LineGraph)
+    #[cfg_attr(feature = "serde", serde(rename = "Line"))]
+    Line,
+    /// A [`BarChart`](super::BarChart)
+    #[cfg_attr(feature = "serde", serde(rename = "Bar"))]
+    Bar,
+    /// A [`StackedBarChart`](super::StackedBarChart)
+    #[cfg_attr(feature = "serde", serde(rename = "StackedBar"))]
+    StackedBar,
+}
+
+/// A normalization applied to the labels of a chart.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LabelTransform {
+    /// Labels are left as-is
+    #[default]
+    None,
+    /// Leading and trailing whitespace is removed
+    Trim,
+    /// Labels are converted to lowercase
+    Lowercase,
+    /// The first letter of each word is capitalized and all others are made
+    /// lowercase
+    Titlecase,
+    /// Labels are transformed by the provided function
+    Custom(fn(&str) -> String),
+}
+
+impl LabelTransform {
+    /// Returns the result of applying this transform to `label`.
+    pub fn apply(&self, label: &str) -> String {
+        match self {
+            Self::None => label.to_owned(),
+            Self::Trim => label.trim().to_owned(),
+            Self::Lowercase => label.to_lowercase(),
+            Self::Titlecase => {
+                let mut output = String::with_capacity(label.len());
+                let mut is_start = true;
+
+                for ch in label.chars() {
+                    if is_start {
+                        output.extend(ch.to_uppercase());
+                    } else {
+                        output.extend(ch.to_lowercase());
+                    }
+                    is_start = ch.is_whitespace();
+                }
+
+                output
+            }
+            Self::Custom(transform) => transform(label),
+        }
+    }
+}
+
+/// Custom transforms are equal when they hold the same function, compared by
+/// address.
+impl PartialEq for LabelTransform {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(x), Self::Custom(y)) => *x as usize == *y as usize,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Returns the labels which occur more than once, in order of first
+/// appearance.
+pub(crate) fn duplicate_labels<'a>(labels: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+
+    for label in labels {
+        if !seen.insert(label) && !duplicates.contains(label) {
+            duplicates.push(label.clone());
+        }
+    }
+
+    duplicates
+}
+
+/// Returns the `q` quantile of the sorted, non-empty `values`, interpolating
+/// linearly between the closest values.
+pub(crate) fn quantile(values: &[f64], q: f64) -> f64 {
+    let pos = q * (values.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+
+    values[lower] + (pos - lower as f64) * (values[upper] - values[lower])
+}
+
 /// Determines how points on the scale are handled
 ///
 ///
-/// Points on a [`ScaleKind::Categorical`] are treated categorically with all duplicates removed and in an arbitary order. Points on other [`ScaleKind`] are treated numerically as a range
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) enum ScaleKind {
+/// Points on a [`ScaleKind::Categorical`] are treated categorically with all duplicates removed, in the order they were first seen. Points on other [`ScaleKind`] are treated numerically as a range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScaleKind {
+    #[cfg_attr(feature = "serde", serde(rename = "Number"))]
     Number,
+    #[cfg_attr(feature = "serde", serde(rename = "Integer"))]
     Integer,
+    #[cfg_attr(feature = "serde", serde(rename = "Float"))]
     Float,
+    #[cfg_attr(feature = "serde", serde(rename = "Categorical"))]
     Categorical,
 }
 
@@ -42,30 +150,335 @@ impl From<ColumnType> for ScaleKind {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// English month names, in order.
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// English weekday names, in order.
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// A known order of the values of a categorical [`Scale`], such as months,
+/// used rather than the order values were first seen or sorted in. See
+/// [`Scale::set_order`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CategoricalOrder {
+    /// No known order
+    #[default]
+    None,
+    /// English month names from January to December, in full or as their
+    /// first three letters, in any case, such as `JAN` or `january`
+    Months,
+    /// English weekday names from Monday to Sunday, in full or as their
+    /// first three letters, in any case
+    Weekdays,
+    /// The given values, in order, matched exactly against the text of each
+    /// value
+    Custom(Vec<String>),
+}
+
+impl CategoricalOrder {
+    /// Returns the position of `value` within the order, if it is part of
+    /// the order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{models::CategoricalOrder, repr::Data};
+    ///
+    /// assert_eq!(CategoricalOrder::Months.position(&Data::from("Mar")), Some(2));
+    /// assert_eq!(CategoricalOrder::Weekdays.position(&Data::from("SUNDAY")), Some(6));
+    /// assert_eq!(CategoricalOrder::Weekdays.position(&Data::from("Someday")), None);
+    /// ```
+    pub fn position(&self, value: &Data) -> Option<usize> {
+        let named = |names: &[&str]| {
+            let Data::Text(text) = value else {
+                return None;
+            };
+            let text = text.trim().to_lowercase();
+
+            names
+                .iter()
+                .position(|name| text == *name || text == name[..3])
+        };
+
+        match self {
+            Self::None => None,
+            Self::Months => named(&MONTHS),
+            Self::Weekdays => named(&WEEKDAYS),
+            Self::Custom(values) => {
+                let text = value.to_string();
+                values.iter().position(|other| other == &text)
+            }
+        }
+    }
+
+    /// Returns true if every value of `values`, other than empty ones, is
+    /// part of the order, and at least one is.
+    fn orders<'a>(&self, values: impl IntoIterator<Item = &'a Data>) -> bool {
+        let mut values = values.into_iter().filter(|value| *value != &Data::None);
+        let Some(first) = values.next() else {
+            return false;
+        };
+
+        self.position(first).is_some() && values.all(|value| self.position(value).is_some())
+    }
+}
+
+/// Floats are compared and hashed bitwise, matching [`Data`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum ScaleValues {
     /// Both ends are inclusive
+    #[cfg_attr(feature = "serde", serde(rename = "Number"))]
     Number {
+        #[cfg_attr(feature = "serde", serde(rename = "start"))]
         start: isize,
+        #[cfg_attr(feature = "serde", serde(rename = "end"))]
         end: isize,
+        #[cfg_attr(feature = "serde", serde(rename = "step"))]
         step: isize,
     },
     /// Both ends are inclusive
+    #[cfg_attr(feature = "serde", serde(rename = "Integer"))]
     Integer {
+        #[cfg_attr(feature = "serde", serde(rename = "start"))]
         start: i32,
+        #[cfg_attr(feature = "serde", serde(rename = "end"))]
         end: i32,
+        #[cfg_attr(feature = "serde", serde(rename = "step"))]
         step: i32,
     },
     /// Both ends are inclusive
+    #[cfg_attr(feature = "serde", serde(rename = "Float"))]
     Float {
+        #[cfg_attr(feature = "serde", serde(rename = "start"))]
         start: f32,
+        #[cfg_attr(feature = "serde", serde(rename = "end"))]
         end: f32,
+        #[cfg_attr(feature = "serde", serde(rename = "step"))]
         step: f32,
     },
+    #[cfg_attr(feature = "serde", serde(rename = "Categorical"))]
     Categorical(Vec<Data>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl PartialEq for ScaleValues {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Number { start, end, step },
+                Self::Number {
+                    start: s,
+                    end: e,
+                    step: st,
+                },
+            ) => start == s && end == e && step == st,
+            (
+                Self::Integer { start, end, step },
+                Self::Integer {
+                    start: s,
+                    end: e,
+                    step: st,
+                },
+            ) => start == s && end == e && step == st,
+            (
+                Self::Float { start, end, step },
+                Self::Float {
+                    start: s,
+                    end: e,
+                    step: st,
+                },
+            ) => {
+                start.to_bits() == s.to_bits()
+                    && end.to_bits() == e.to_bits()
+                    && step.to_bits() == st.to_bits()
+            }
+            (Self::Categorical(x), Self::Categorical(y)) => x == y,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ScaleValues {}
+
+impl Hash for ScaleValues {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Self::Number { start, end, step } => (start, end, step).hash(state),
+            Self::Integer { start, end, step } => (start, end, step).hash(state),
+            Self::Float { start, end, step } => {
+                (start.to_bits(), end.to_bits(), step.to_bits()).hash(state)
+            }
+            Self::Categorical(values) => values.hash(state),
+        }
+    }
+}
+
+/// The lazily built positions of the values of a categorical [`Scale`].
+///
+/// Scales invalidate the index whenever their values are reordered. The
+/// index is ignored when comparing or hashing scales.
+#[derive(Debug, Clone, Default)]
+struct CategoryIndex(OnceCell<HashMap<Data, usize>>);
+
+impl CategoryIndex {
+    /// Returns the position of `value` within `values`, indexing `values` if
+    /// there is no index.
+    fn get(&self, values: &[Data], value: &Data) -> Option<usize> {
+        let index = self.0.get_or_init(|| {
+            let mut index = HashMap::with_capacity(values.len());
+            for (pos, value) in values.iter().enumerate() {
+                index.entry(value.clone()).or_insert(pos);
+            }
+            index
+        });
+
+        index.get(value).copied()
+    }
+
+    fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+impl PartialEq for CategoryIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for CategoryIndex {}
+
+impl Hash for CategoryIndex {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+fn _assert_sync<T: Send + Sync>() {}
+
+fn _assert_charts_sync() {
+    _assert_sync::<Scale>();
+    _assert_sync::<super::LineGraph>();
+    _assert_sync::<super::BarChart>();
+    _assert_sync::<super::StackedBarChart>();
+}
+
+/// An axis of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Axis::X => write!(f, "X"),
+            Axis::Y => write!(f, "Y"),
+        }
+    }
+}
+
+/// A mark over a chart giving context to its values, such as a target or an
+/// acceptable range. Each value is placed on the scale of its axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Annotation {
+    /// A horizontal line across the chart at `y`
+    HLine { y: Data, label: Option<String> },
+    /// A vertical line across the chart at `x`
+    VLine { x: Data },
+    /// A shaded band from `from` to `to` along `axis`
+    Band { from: Data, to: Data, axis: Axis },
+}
+
+impl Annotation {
+    /// Returns the values of the annotation with the axis each is on.
+    fn values(&self) -> Vec<(Axis, &Data)> {
+        match self {
+            Annotation::HLine { y, .. } => vec![(Axis::Y, y)],
+            Annotation::VLine { x } => vec![(Axis::X, x)],
+            Annotation::Band { from, to, axis } => vec![(*axis, from), (*axis, to)],
+        }
+    }
+}
+
+/// Checks that the values of `annotation` are on `x_scale` and `y_scale`,
+/// extending the scales to them if `extend` is true. See
+/// [`Scale::extend_to`].
+///
+/// Once every value is on its scale, `y_view` is extended to the values on
+/// the y axis, so that the annotation is shown on a clamped chart.
+///
+/// Returns the axis and value of the first value which is not, leaving the
+/// scales and `y_view` unchanged.
+pub(crate) fn fit_annotation<'a>(
+    annotation: &'a Annotation,
+    x_scale: &mut Scale,
+    y_scale: &mut Scale,
+    y_view: Option<&mut Scale>,
+    extend: bool,
+) -> Result<(), (Axis, &'a Data)> {
+    let (mut x_extended, mut y_extended) = (None::<Scale>, None::<Scale>);
+
+    for (axis, value) in annotation.values() {
+        let (scale, extended) = match axis {
+            Axis::X => (&*x_scale, &mut x_extended),
+            Axis::Y => (&*y_scale, &mut y_extended),
+        };
+        let current = extended.as_ref().unwrap_or(scale);
+        if current.contains(value) {
+            continue;
+        }
+
+        let mut wider = current.clone();
+        if !extend || !wider.extend_to(value) {
+            return Err((axis, value));
+        }
+        *extended = Some(wider);
+    }
+
+    if let Some(scale) = x_extended {
+        *x_scale = scale;
+    }
+    if let Some(scale) = y_extended {
+        *y_scale = scale;
+    }
+    if let Some(view) = y_view {
+        for (axis, value) in annotation.values() {
+            if axis == Axis::Y {
+                view.extend_to(value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// Representation of [`Scale`] points on an Axis.
 pub enum AxisPoints {
     /// Categorical points with no concept of negatives and positives.
@@ -78,31 +491,90 @@ pub enum AxisPoints {
     },
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Options for the points returned by [`Scale::axis_points_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AxisOptions {
+    /// Generates every whole value of integer scales, rather than the ticks
+    /// of [`Scale::points`]. Ignored for categorical and floating point
+    /// scales.
+    pub sequential: bool,
+    /// Extends numeric scales to include zero. See [`Scale::include_zero`].
+    pub include_zero: bool,
+    /// The largest number of points returned. Points are thinned evenly,
+    /// keeping zero where present.
+    pub max_ticks: Option<usize>,
+}
+
+/// The values along an axis of a chart.
+///
+/// Categorical scales cache the positions of their values the first time a
+/// value is looked up. The cache is left out of comparisons and hashing, so
+/// scales, and the charts holding them, can be used as the keys of maps and
+/// sets even though clippy reports them as mutable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scale {
     /// The type of scale
+    #[cfg_attr(feature = "serde", serde(rename = "kind"))]
     pub(crate) kind: ScaleKind,
     /// The values within the scale
+    #[cfg_attr(feature = "serde", serde(rename = "values"))]
     values: ScaleValues,
     /// The number of points on the scale.
     ///
-    /// For non-categorical data this is at most one more than the number of
-    /// points used to generate the scale
+    /// For non-categorical data this is the number of ticks generated, at
+    /// most twice the number of distinct points used to generate the scale
+    #[cfg_attr(feature = "serde", serde(rename = "length"))]
     pub length: usize,
+    /// Whether the points on the scale are in descending order
+    #[cfg_attr(feature = "serde", serde(rename = "descending"))]
+    descending: bool,
+    /// The known order of the values of a categorical scale
+    #[cfg_attr(feature = "serde", serde(rename = "order", default))]
+    order: CategoricalOrder,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    index: CategoryIndex,
 }
 
 impl Scale {
     /// Returns a new scale of the specified type from the given points.
     /// If the scale type specified cannot be created from the points, a [`ScaleKind::Categorical`] is
     /// created instead.
-    pub(crate) fn new(points: impl IntoIterator<Item = impl Into<Data>>, kind: ScaleKind) -> Self {
+    ///
+    /// Points consisting only of [`Data::None`] are treated as empty. A
+    /// non-categorical scale built from no points is empty, with no ticks.
+    /// See [`Scale::is_empty`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{models::{Scale, ScaleKind}, repr::Data, samples};
+    ///
+    /// let sheet = samples::air();
+    /// let values = sheet
+    ///     .iter_rows()
+    ///     .map(|row| row.get_cell_by_index(1).unwrap().get_data().clone());
+    ///
+    /// let scale = Scale::new(values, ScaleKind::Integer);
+    /// assert_eq!(scale.kind(), ScaleKind::Integer);
+    /// assert!(scale.contains(&Data::Integer(340)));
+    ///
+    /// let scale = Scale::new(sheet.get_headers().iter().map(|hdr| hdr.label.clone()), ScaleKind::Integer);
+    /// assert_eq!(scale.kind(), ScaleKind::Categorical);
+    /// ```
+    pub fn new(points: impl IntoIterator<Item = impl Into<Data>>, kind: ScaleKind) -> Self {
+        let _span = trace_span!("scale_new", ?kind);
+
         let points = points.into_iter().map(Into::into);
         match kind {
             ScaleKind::Categorical => {
+                let points = points.collect::<Vec<Data>>();
+                let mut seen = HashSet::with_capacity(points.len());
                 let mut values = Vec::default();
 
                 for point in points {
-                    if !values.iter().any(|pnt| pnt == &point) {
+                    if !seen.contains(&point) {
+                        seen.insert(point.clone());
                         values.push(point);
                     }
                 }
@@ -114,24 +586,15 @@ impl Scale {
                     kind,
                     values,
                     length,
+                    descending: false,
+                    index: CategoryIndex::default(),
+                    order: CategoricalOrder::default(),
                 }
             }
             ScaleKind::Integer => {
-                let mut valid = HashSet::new();
-                let mut invalid = HashSet::new();
-
-                for point in points {
-                    match point {
-                        Data::Integer(num) => {
-                            valid.insert(num);
-                        }
-                        other => {
-                            invalid.insert(other);
-                        }
-                    };
-                }
+                let points = points.collect::<Vec<Data>>();
 
-                if valid.is_empty() && invalid.is_empty() {
+                if points.iter().all(|point| point == &Data::None) {
                     Self {
                         kind,
                         values: ScaleValues::Integer {
@@ -139,41 +602,24 @@ impl Scale {
                             end: 0,
                             step: 0,
                         },
-                        length: 1,
-                    }
-                } else if !invalid.is_empty() {
-                    for point in valid.into_iter() {
-                        invalid.insert(point.into());
-                    }
-
-                    let invalid = invalid.into_iter().collect::<Vec<Data>>();
-                    let length = invalid.len();
-
-                    Self {
-                        kind: ScaleKind::Categorical,
-                        values: ScaleValues::Categorical(invalid),
-                        length,
+                        length: 0,
+                        descending: false,
+                        index: CategoryIndex::default(),
+                        order: CategoricalOrder::default(),
                     }
+                } else if points.iter().all(|point| matches!(point, Data::Integer(_))) {
+                    Self::from_i32(points.into_iter().filter_map(|point| match point {
+                        Data::Integer(num) => Some(num),
+                        _ => None,
+                    }))
                 } else {
-                    Self::from_i32(valid.into_iter())
+                    Self::new(points, ScaleKind::Categorical)
                 }
             }
             ScaleKind::Number => {
-                let mut valid = HashSet::new();
-                let mut invalid = HashSet::new();
-
-                for point in points {
-                    match point {
-                        Data::Number(num) => {
-                            valid.insert(num);
-                        }
-                        other => {
-                            invalid.insert(other);
-                        }
-                    }
-                }
+                let points = points.collect::<Vec<Data>>();
 
-                if valid.is_empty() && invalid.is_empty() {
+                if points.iter().all(|point| point == &Data::None) {
                     Self {
                         kind,
                         values: ScaleValues::Number {
@@ -181,44 +627,24 @@ impl Scale {
                             end: 0,
                             step: 0,
                         },
-                        length: 1,
-                    }
-                } else if !invalid.is_empty() {
-                    for point in valid.into_iter() {
-                        invalid.insert(point.into());
-                    }
-
-                    let invalid = invalid.into_iter().collect::<Vec<Data>>();
-                    let length = invalid.len();
-
-                    Self {
-                        kind: ScaleKind::Categorical,
-                        values: ScaleValues::Categorical(invalid),
-                        length,
+                        length: 0,
+                        descending: false,
+                        index: CategoryIndex::default(),
+                        order: CategoricalOrder::default(),
                     }
+                } else if points.iter().all(|point| matches!(point, Data::Number(_))) {
+                    Self::from_isize(points.into_iter().filter_map(|point| match point {
+                        Data::Number(num) => Some(num),
+                        _ => None,
+                    }))
                 } else {
-                    Self::from_isize(valid.into_iter())
+                    Self::new(points, ScaleKind::Categorical)
                 }
             }
             ScaleKind::Float => {
-                // f32 doesn't implement Hash or Eq
-                let mut valid: Vec<f32> = Vec::new();
-                let mut invalid = HashSet::new();
+                let points = points.collect::<Vec<Data>>();
 
-                for point in points {
-                    match point {
-                        Data::Float(float) => {
-                            if !valid.iter().any(|x| *x == float) {
-                                valid.push(float);
-                            }
-                        }
-                        other => {
-                            invalid.insert(other);
-                        }
-                    }
-                }
-
-                if valid.is_empty() && invalid.is_empty() {
+                if points.iter().all(|point| point == &Data::None) {
                     Self {
                         kind,
                         values: ScaleValues::Float {
@@ -226,23 +652,18 @@ impl Scale {
                             end: 0.0,
                             step: 0.0,
                         },
-                        length: 1,
-                    }
-                } else if !invalid.is_empty() {
-                    for point in valid.into_iter() {
-                        invalid.insert(point.into());
-                    }
-
-                    let invalid = invalid.into_iter().collect::<Vec<Data>>();
-                    let length = invalid.len();
-
-                    Self {
-                        kind: ScaleKind::Categorical,
-                        values: ScaleValues::Categorical(invalid),
-                        length,
+                        length: 0,
+                        descending: false,
+                        index: CategoryIndex::default(),
+                        order: CategoricalOrder::default(),
                     }
+                } else if points.iter().all(|point| matches!(point, Data::Float(_))) {
+                    Self::from_f32(points.into_iter().filter_map(|point| match point {
+                        Data::Float(float) => Some(float),
+                        _ => None,
+                    }))
                 } else {
-                    Self::from_f32(valid.into_iter())
+                    Self::new(points, ScaleKind::Categorical)
                 }
             }
         }
@@ -250,44 +671,139 @@ impl Scale {
 
     /// Returns the points on the scale.
     ///
-    /// Categorical scales return all points used to generate the scale.
+    /// Categorical scales return all points used to generate the scale, with
+    /// duplicates removed, in the order they were first seen. This order is
+    /// stable across constructions from the same points.
     ///
-    /// Non-Categorical scales return a ordered generated range, guaranteed to contain all initial points.
+    /// Non-Categorical scales return an ordered generated range of
+    /// [`Scale::length`] evenly spaced ticks. The first tick is the smallest
+    /// initial point. The last tick is the largest initial point, or the
+    /// closest tick above it if the step does not divide the range evenly.
+    /// A scale whose initial points are all equal has a single tick, and an
+    /// empty scale has none.
+    ///
+    /// Points are reversed if the scale is descending.
     pub fn points(&self) -> Vec<Data> {
+        let mut points = self.ascending_points();
+
+        if self.descending {
+            points.reverse();
+        }
+
+        points
+    }
+
+    /// Returns true if the scale has no points, as when built from no
+    /// points or only [`Data::None`]. An empty scale contains no values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{repr::Data, models::{Scale, ScaleKind}};
+    ///
+    /// let scale = Scale::new(vec![Data::None, Data::None], ScaleKind::Integer);
+    /// assert!(scale.is_empty());
+    /// assert!(scale.points().is_empty());
+    /// assert!(!scale.contains(&Data::Integer(0)));
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    fn ascending_points(&self) -> Vec<Data> {
         match &self.values {
             ScaleValues::Categorical(values) => values.clone(),
-            ScaleValues::Number { start, step, .. } => {
-                let mut output = Vec::default();
-                let n = self.length as isize;
+            _ => (0..self.length)
+                .filter_map(|idx| self.ascending_point(idx))
+                .collect(),
+        }
+    }
 
-                for i in 0..n {
-                    let curr = *start + (i * step);
-                    output.push(Data::Number(curr));
-                }
+    /// Returns the point at `idx` of the ascending points.
+    fn ascending_point(&self, idx: usize) -> Option<Data> {
+        if idx >= self.length {
+            return None;
+        }
 
-                output
+        let point = match &self.values {
+            ScaleValues::Categorical(values) => values.get(idx)?.clone(),
+            ScaleValues::Number { start, step, .. } => {
+                Data::Number(integer_tick(*start, *step, idx))
             }
             ScaleValues::Integer { start, step, .. } => {
-                let mut output = Vec::default();
-                let n = self.length as i32;
+                Data::Integer(integer_tick(*start, *step, idx))
+            }
+            ScaleValues::Float { start, end, step } => {
+                Data::Float(float_tick(*start, *end, *step, idx, self.length))
+            }
+        };
 
-                for i in 0..n {
-                    let curr = *start + (i * step);
-                    output.push(Data::Integer(curr));
-                }
+        Some(point)
+    }
 
-                output
-            }
-            ScaleValues::Float { start, step, .. } => {
-                let mut output = Vec::default();
-                let n = self.length as isize;
+    /// Returns the point at `idx` of [`Scale::points`].
+    fn point(&self, idx: usize) -> Option<Data> {
+        if self.descending {
+            self.ascending_point(self.length.checked_sub(idx + 1)?)
+        } else {
+            self.ascending_point(idx)
+        }
+    }
 
-                for i in 0..n {
-                    let curr = *start + ((i as f32) * step);
-                    output.push(Data::Float(curr));
-                }
+    /// Returns the first of [`Scale::points`], if any. This is the largest
+    /// tick of a descending scale.
+    pub fn start(&self) -> Option<Data> {
+        self.point(0)
+    }
 
-                output
+    /// Returns the last of [`Scale::points`], if any. This is the smallest
+    /// tick of a descending scale.
+    pub fn end(&self) -> Option<Data> {
+        self.point(self.length.checked_sub(1)?)
+    }
+
+    /// Returns the distance between successive ticks of a non-categorical
+    /// scale, regardless of its direction.
+    pub fn step(&self) -> Option<Data> {
+        match &self.values {
+            ScaleValues::Number { step, .. } => Some(Data::Number(*step)),
+            ScaleValues::Integer { step, .. } => Some(Data::Integer(*step)),
+            ScaleValues::Float { step, .. } => Some(Data::Float(*step)),
+            ScaleValues::Categorical(_) => None,
+        }
+    }
+
+    /// Returns about `desired` evenly spaced points of [`Scale::points`],
+    /// always including the first and last, for labelling an axis.
+    ///
+    /// Every point is returned if there are no more than `desired`. A
+    /// `desired` of 1 returns only the first point, and 0 returns none.
+    /// Categorical scales are sampled the same way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{repr::Data, models::Scale};
+    ///
+    /// let scale = Scale::from((0..=100).collect::<Vec<i32>>());
+    /// let ticks = scale.tick_positions(3);
+    /// assert_eq!(ticks.first(), scale.start().as_ref());
+    /// assert_eq!(ticks.last(), scale.end().as_ref());
+    /// assert_eq!(ticks.len(), 3);
+    /// ```
+    pub fn tick_positions(&self, desired: usize) -> Vec<Data> {
+        match desired {
+            0 => Vec::new(),
+            1 => self.start().into_iter().collect(),
+            desired if desired >= self.length => self.points(),
+            desired => {
+                // More points than ticks keeps the rounded positions apart
+                let last = (self.length - 1) as f64;
+                let gaps = (desired - 1) as f64;
+
+                (0..desired)
+                    .filter_map(|tick| self.point((tick as f64 * last / gaps).round() as usize))
+                    .collect()
             }
         }
     }
@@ -305,7 +821,11 @@ impl Scale {
     ///
     /// ```
     pub fn ranged(&self) -> Vec<Data> {
-        match &self.values {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        let mut ranged: Vec<Data> = match &self.values {
             ScaleValues::Integer { start, end, .. } => {
                 let range = *start..=*end;
                 range.map(From::from).collect()
@@ -314,8 +834,14 @@ impl Scale {
                 let range = *start..=*end;
                 range.map(From::from).collect()
             }
-            _ => self.points(),
+            _ => self.ascending_points(),
+        };
+
+        if self.descending {
+            ranged.reverse();
         }
+
+        ranged
     }
 
     /// Returns true if the scale contains the given [`Data`].
@@ -323,6 +849,10 @@ impl Scale {
     /// For non-categorical scales, true is returned if a valid data value falls
     /// the range min(Scale::points), max(Scale::points).
     ///
+    /// `NaN` and infinities are left out of the range of a float scale, but
+    /// are always contained by one, so charts of points with such values
+    /// remain valid.
+    ///
     /// # Example
     ///
     /// ```
@@ -339,58 +869,329 @@ impl Scale {
     ///
     /// ```
     pub fn contains(&self, value: &Data) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
         match (&self.values, value) {
             (ScaleValues::Categorical(values), data) => values.contains(data),
             (ScaleValues::Number { start, step, .. }, Data::Number(num)) => {
-                let end = start + (*step * (self.length - 1) as isize);
+                let end = integer_tick(*start, *step, self.length - 1);
                 start <= num && num <= &end
             }
             (ScaleValues::Integer { start, step, .. }, Data::Integer(num)) => {
-                let end = start + (*step * (self.length - 1) as i32);
+                let end = integer_tick(*start, *step, self.length - 1);
                 start <= num && num <= &end
             }
-            (ScaleValues::Float { start, step, .. }, Data::Float(num)) => {
-                let end = start + (*step * (self.length - 1) as f32);
+            (ScaleValues::Float { .. }, Data::Float(num)) if !num.is_finite() => true,
+            (ScaleValues::Float { start, step, end }, Data::Float(num)) => {
+                let end = float_tick(*start, *end, *step, self.length - 1, self.length);
                 start <= num && num <= &end
             }
             _ => false,
         }
     }
 
+    /// Returns a check of whether values are on the scale, as with
+    /// [`Scale::contains`], for testing many values at once. Categorical
+    /// values are looked up by hash rather than searched.
+    pub(crate) fn contains_fn(&self) -> impl Fn(&Data) -> bool + '_ {
+        let categories: Option<HashSet<&Data>> = match &self.values {
+            ScaleValues::Categorical(values) => Some(values.iter().collect()),
+            _ => None,
+        };
+
+        move |value| match &categories {
+            Some(categories) => categories.contains(value),
+            None => self.contains(value),
+        }
+    }
+
+    /// Returns the position of `value` within [`Scale::points`], for mapping
+    /// values onto an axis.
+    ///
+    /// Categorical values are looked up by hash, through an index built the
+    /// first time a position is requested. Non-categorical values are placed
+    /// arithmetically at the tick at or below them, going from the start of
+    /// the scale. Values not contained by the scale, including `NaN` and
+    /// infinities, have no position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{repr::Data, models::Scale};
+    ///
+    /// let scale = Scale::from(vec![0, 10, 20]);
+    /// assert_eq!(scale.position_of(&Data::Integer(10)), Some(1));
+    /// assert_eq!(scale.position_of(&Data::Integer(19)), Some(1));
+    /// assert_eq!(scale.position_of(&Data::Integer(21)), None);
+    /// ```
+    pub fn position_of(&self, value: &Data) -> Option<usize> {
+        let last = self.length.checked_sub(1)?;
+
+        let position = match (&self.values, value) {
+            (ScaleValues::Categorical(values), value) => self.index.get(values, value)?,
+            (ScaleValues::Number { start, step, .. }, Data::Number(num)) => {
+                integer_position(*start, *step, last, *num)?
+            }
+            (ScaleValues::Integer { start, step, .. }, Data::Integer(num)) => {
+                integer_position(*start, *step, last, *num)?
+            }
+            (ScaleValues::Float { start, end, step }, Data::Float(num)) => {
+                float_position(*start, *end, *step, self.length, *num)?
+            }
+            _ => return None,
+        };
+
+        match self.descending {
+            true => Some(last - position),
+            false => Some(position),
+        }
+    }
+
+    /// Returns the position of each of `values` within [`Scale::points`], as
+    /// with [`Scale::position_of`].
+    pub fn positions<'a>(&self, values: impl Iterator<Item = &'a Data>) -> Vec<Option<usize>> {
+        values.map(|value| self.position_of(value)).collect()
+    }
+
     /// Returns the points on the scale as a [`AxisPoints`].
     ///
     /// For non-categorical, non-floating point scales, points are generated
-    /// sequentially if `sequential` is true.
+    /// sequentially if `sequential` is true. See [`Scale::axis_points_with`].
     ///
-    /// Points for non-categorical scales are guaranteed to be in order.
+    /// Points for non-categorical scales are guaranteed to be in order. When
+    /// not sequential, they are the ticks of [`Scale::points`], so they start
+    /// at the smallest initial point and end at or just above the largest.
+    /// On a descending scale, both the positive and negative points are
+    /// reversed.
     pub fn axis_points(&self, sequential: bool) -> AxisPoints {
-        match &self.values {
-            ScaleValues::Categorical(vals) => AxisPoints::Categorical(vals.clone()),
-            ScaleValues::Number { start, end, step } => {
-                let mut pos = vec![];
-                let mut neg = vec![];
-
-                if sequential {
-                    for i in *start..=*end {
-                        if i < 0 {
-                            neg.push(i.into());
-                        } else {
-                            pos.push(i.into());
-                        }
-                    }
-                } else {
-                    let n = self.length as isize;
+        self.axis_points_with(AxisOptions {
+            sequential,
+            ..Default::default()
+        })
+    }
 
-                    for i in 0..n {
-                        let curr = *start + (i * step);
-                        if curr < 0 {
-                            neg.push(curr.into());
-                        } else {
-                            pos.push(curr.into());
-                        }
-                    }
+    /// Returns the points on the scale as a [`AxisPoints`], generated as
+    /// described by `opts`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{models::{AxisOptions, AxisPoints, Scale}, repr::Data};
+    ///
+    /// let scale = Scale::from(vec![5, 10, 15]);
+    /// let opts = AxisOptions {
+    ///     include_zero: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let AxisPoints::Numeric { positives, negatives } = scale.axis_points_with(opts) else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(positives, [0, 5, 10, 15].map(Data::Integer));
+    /// assert!(negatives.is_empty());
+    /// ```
+    pub fn axis_points_with(&self, opts: AxisOptions) -> AxisPoints {
+        let mut points = if opts.include_zero {
+            let mut scale = self.clone();
+            scale.include_zero();
+            scale.ascending_axis_points(opts.sequential)
+        } else {
+            self.ascending_axis_points(opts.sequential)
+        };
+
+        if let Some(max_ticks) = opts.max_ticks {
+            points = match points {
+                AxisPoints::Categorical(values) => {
+                    AxisPoints::Categorical(thin_points(values, max_ticks))
                 }
-
+                AxisPoints::Numeric {
+                    positives,
+                    mut negatives,
+                } => {
+                    negatives.extend(positives);
+                    let mut points = thin_points(negatives, max_ticks);
+                    let positives = points.split_off(
+                        points
+                            .iter()
+                            .position(|point| !point.is_negative())
+                            .unwrap_or(points.len()),
+                    );
+
+                    AxisPoints::Numeric {
+                        positives,
+                        negatives: points,
+                    }
+                }
+            };
+        }
+
+        if self.descending {
+            match &mut points {
+                AxisPoints::Categorical(values) => values.reverse(),
+                AxisPoints::Numeric {
+                    positives,
+                    negatives,
+                } => {
+                    positives.reverse();
+                    negatives.reverse();
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Extends a numeric scale to include zero, recomputing its ticks as if
+    /// zero were one more initial point. Scales already including zero,
+    /// categorical scales and scales of only NaN are unchanged.
+    pub fn include_zero(&mut self) {
+        let distinct = self.length + 1;
+
+        match &mut self.values {
+            ScaleValues::Integer { start, end, step } if *start > 0 || *end < 0 => {
+                let (min, max) = ((*start).min(0), (*end).max(0));
+                let (new_step, length) =
+                    integer_steps(min as i128, max as i128, distinct, i32::MAX as i128);
+
+                *start = min;
+                *end = max;
+                *step = new_step as i32;
+                self.length = length;
+            }
+            ScaleValues::Number { start, end, step } if *start > 0 || *end < 0 => {
+                let (min, max) = ((*start).min(0), (*end).max(0));
+                let (new_step, length) =
+                    integer_steps(min as i128, max as i128, distinct, isize::MAX as i128);
+
+                *start = min;
+                *end = max;
+                *step = new_step as isize;
+                self.length = length;
+            }
+            ScaleValues::Float { start, end, step } if *start > 0.0 || *end < 0.0 => {
+                let (min, max) = (start.min(0.0), end.max(0.0));
+                let (new_step, length) = float_steps(min, max, distinct);
+
+                *start = min;
+                *end = max;
+                *step = new_step;
+                self.length = length;
+            }
+            _ => {}
+        }
+    }
+
+    /// Extends the scale to contain `value`. The ticks of a numeric scale are
+    /// recomputed as if `value` were one more initial point, while a
+    /// categorical scale gains `value` as its last category.
+    ///
+    /// Returns false, leaving the scale unchanged, if `value` cannot be on
+    /// the scale, such as a value of another type than a numeric scale.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{repr::Data, models::Scale};
+    ///
+    /// let mut scale = Scale::from(vec![0, 10, 20]);
+    /// assert!(scale.extend_to(&Data::Integer(35)));
+    /// assert!(scale.contains(&Data::Integer(35)));
+    ///
+    /// assert!(!scale.extend_to(&Data::Float(40.0)));
+    /// ```
+    pub fn extend_to(&mut self, value: &Data) -> bool {
+        if self.contains(value) {
+            return true;
+        }
+
+        let empty = self.is_empty();
+        let distinct = self.length + 1;
+
+        match (&mut self.values, value) {
+            (ScaleValues::Categorical(values), value) => {
+                values.push(value.clone());
+                self.length = values.len();
+                self.index.invalidate();
+            }
+            (ScaleValues::Integer { start, end, step }, Data::Integer(value)) => {
+                let (min, max) = match empty {
+                    true => (*value, *value),
+                    false => ((*start).min(*value), (*end).max(*value)),
+                };
+                let (new_step, length) =
+                    integer_steps(min as i128, max as i128, distinct, i32::MAX as i128);
+
+                *start = min;
+                *end = max;
+                *step = new_step as i32;
+                self.length = length;
+            }
+            (ScaleValues::Number { start, end, step }, Data::Number(value)) => {
+                let (min, max) = match empty {
+                    true => (*value, *value),
+                    false => ((*start).min(*value), (*end).max(*value)),
+                };
+                let (new_step, length) =
+                    integer_steps(min as i128, max as i128, distinct, isize::MAX as i128);
+
+                *start = min;
+                *end = max;
+                *step = new_step as isize;
+                self.length = length;
+            }
+            (ScaleValues::Float { start, end, step }, Data::Float(value)) if value.is_finite() => {
+                let (min, max) = match empty {
+                    true => (*value, *value),
+                    false => (start.min(*value), end.max(*value)),
+                };
+                let (new_step, length) = float_steps(min, max, distinct);
+
+                *start = min;
+                *end = max;
+                *step = new_step;
+                self.length = length;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn ascending_axis_points(&self, sequential: bool) -> AxisPoints {
+        if self.is_empty() && !self.is_categorical() {
+            return AxisPoints::Numeric {
+                positives: vec![],
+                negatives: vec![],
+            };
+        }
+
+        match &self.values {
+            ScaleValues::Categorical(vals) => AxisPoints::Categorical(vals.clone()),
+            ScaleValues::Number { start, end, step } => {
+                let mut pos = vec![];
+                let mut neg = vec![];
+
+                if sequential {
+                    for i in *start..=*end {
+                        if i < 0 {
+                            neg.push(i.into());
+                        } else {
+                            pos.push(i.into());
+                        }
+                    }
+                } else {
+                    for i in 0..self.length {
+                        let curr = integer_tick(*start, *step, i);
+                        if curr < 0 {
+                            neg.push(curr.into());
+                        } else {
+                            pos.push(curr.into());
+                        }
+                    }
+                }
+
                 AxisPoints::Numeric {
                     positives: pos,
                     negatives: neg,
@@ -409,10 +1210,8 @@ impl Scale {
                         }
                     }
                 } else {
-                    let n = self.length as i32;
-
-                    for i in 0..n {
-                        let curr = *start + (i * step);
+                    for i in 0..self.length {
+                        let curr = integer_tick(*start, *step, i);
                         if curr < 0 {
                             neg.push(curr.into());
                         } else {
@@ -426,14 +1225,12 @@ impl Scale {
                     negatives: neg,
                 }
             }
-            ScaleValues::Float { start, step, .. } => {
+            ScaleValues::Float { start, end, step } => {
                 let mut pos = vec![];
                 let mut neg = vec![];
 
-                let n = self.length;
-
-                for i in 0..n {
-                    let curr = *start + ((i as f32) * step);
+                for i in 0..self.length {
+                    let curr = float_tick(*start, *end, *step, i, self.length);
                     if curr < 0.0 {
                         neg.push(curr.into());
                     } else {
@@ -449,6 +1246,23 @@ impl Scale {
         }
     }
 
+    /// Reverses the direction of the scale. Points on a descending scale are
+    /// returned from largest to smallest, or in reverse order for
+    /// categorical scales. [`Scale::contains`] is unaffected.
+    pub fn invert(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    /// Returns true if the points on the scale are in descending order.
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Returns the type of the scale
+    pub fn kind(&self) -> ScaleKind {
+        self.kind
+    }
+
     /// Returns true if the scale is categorical
     pub fn is_categorical(&self) -> bool {
         self.kind == ScaleKind::Categorical
@@ -456,155 +1270,126 @@ impl Scale {
 
     /// Assumes points is not empty
     fn from_i32(points: impl Iterator<Item = i32>) -> Self {
-        let deduped = points.collect::<HashSet<i32>>();
-
-        let mut min = None;
-        let mut max = None;
-
-        for num in deduped.iter() {
-            let num = *num;
-
-            if let Some(prev) = min {
-                if num < prev {
-                    min = Some(num);
-                }
-            } else {
-                min = Some(num);
-            }
+        let mut deduped = points.collect::<Vec<i32>>();
 
-            if let Some(prev) = max {
-                if num > prev {
-                    max = Some(num);
-                }
-            } else {
-                max = Some(num);
-            }
+        // Sorting once gives the extremes and the distinct count without
+        // hashing every point. Already sorted points skip the sort.
+        if deduped.windows(2).any(|pair| pair[0] > pair[1]) {
+            deduped.sort_unstable();
         }
+        deduped.dedup();
 
-        let mut length = deduped.len();
-
-        let min = min.unwrap();
-        let max = max.unwrap();
-        let mut step = (max - min) / length as i32;
-
-        if step * (length as i32) != max - min {
-            step += 1;
-        }
+        Self::integer(deduped[0], deduped[deduped.len() - 1], deduped.len())
+    }
 
-        if ((length - 1) as i32) * step + min < max {
-            length += 1;
-        }
+    /// Returns an integer scale from `min` to `max` of `distinct` points.
+    fn integer(min: i32, max: i32, distinct: usize) -> Self {
+        let (step, length) = integer_steps(min as i128, max as i128, distinct, i32::MAX as i128);
 
         Self {
             kind: ScaleKind::Integer,
             length,
+            descending: false,
+            index: CategoryIndex::default(),
+            order: CategoricalOrder::default(),
             values: ScaleValues::Integer {
                 start: min,
                 end: max,
-                step,
+                step: step as i32,
             },
         }
     }
 
     /// Assumes points is not empty
     fn from_isize(points: impl Iterator<Item = isize>) -> Self {
-        let deduped = points.collect::<HashSet<isize>>();
-
-        let mut min = None;
-        let mut max = None;
-
-        for num in deduped.iter() {
-            let num = *num;
-
-            if let Some(prev) = min {
-                if num < prev {
-                    min = Some(num);
-                }
-            } else {
-                min = Some(num);
-            }
+        let mut deduped = points.collect::<Vec<isize>>();
 
-            if let Some(prev) = max {
-                if num > prev {
-                    max = Some(num);
-                }
-            } else {
-                max = Some(num);
-            }
+        // Sorting once gives the extremes and the distinct count without
+        // hashing every point. Already sorted points skip the sort.
+        if deduped.windows(2).any(|pair| pair[0] > pair[1]) {
+            deduped.sort_unstable();
         }
+        deduped.dedup();
 
-        let mut length = deduped.len();
-
-        let min = min.unwrap();
-        let max = max.unwrap();
-        let mut step = (max - min) / length as isize;
-
-        if step * (length as isize) != max - min {
-            step += 1;
-        }
+        Self::number(deduped[0], deduped[deduped.len() - 1], deduped.len())
+    }
 
-        if ((length - 1) as isize) * step + min < max {
-            length += 1;
-        }
+    /// Returns a number scale from `min` to `max` of `distinct` points.
+    fn number(min: isize, max: isize, distinct: usize) -> Self {
+        let (step, length) = integer_steps(min as i128, max as i128, distinct, isize::MAX as i128);
 
         Self {
             kind: ScaleKind::Number,
             length,
+            descending: false,
+            index: CategoryIndex::default(),
+            order: CategoricalOrder::default(),
             values: ScaleValues::Number {
                 start: min,
                 end: max,
-                step,
+                step: step as isize,
             },
         }
     }
 
+    /// Returns the scale of `kind` over points from `min` to `max` with
+    /// `distinct` distinct values, as [`Scale::new`] would give over the
+    /// points themselves, such as from the meta of a column.
+    ///
+    /// Returns None unless `kind` is [`ScaleKind::Integer`] or
+    /// [`ScaleKind::Number`] and `min` and `max` are of that type.
+    pub(crate) fn from_summary(
+        kind: ScaleKind,
+        min: &Data,
+        max: &Data,
+        distinct: usize,
+    ) -> Option<Self> {
+        match (kind, min, max) {
+            (ScaleKind::Integer, Data::Integer(min), Data::Integer(max)) => {
+                Some(Self::integer(*min, *max, distinct))
+            }
+            (ScaleKind::Number, Data::Number(min), Data::Number(max)) => {
+                Some(Self::number(*min, *max, distinct))
+            }
+            _ => None,
+        }
+    }
+
     fn from_f32(points: impl Iterator<Item = f32>) -> Self {
         let mut min = None;
         let mut max = None;
-        let mut seen = Vec::default();
+        let mut seen = HashSet::with_capacity(points.size_hint().0);
+        // NaN is never equal to another NaN, so each is distinct.
+        let mut nans = 0;
 
         for point in points {
-            if !seen.iter().any(|pnt| *pnt == point) {
-                seen.push(point);
-            }
-
-            // I'm not quite certain how the < and > would work around NaN,
-            if let Some(prev) = min {
-                if point < prev {
-                    min = Some(point);
-                }
+            if point.is_nan() {
+                nans += 1;
             } else {
-                min = Some(point);
-            }
-
-            if let Some(prev) = max {
-                if point > prev {
-                    max = Some(point);
+                // 0.0 and -0.0 are equal but have different bit patterns
+                let bits = if point == 0.0 { 0 } else { point.to_bits() };
+                seen.insert(bits);
+
+                // Infinities are distinct points but would leave no range
+                if point.is_finite() {
+                    min = Some(min.map_or(point, |prev: f32| prev.min(point)));
+                    max = Some(max.map_or(point, |prev: f32| prev.max(point)));
                 }
-            } else {
-                max = Some(point);
             }
         }
 
-        let min = min.unwrap();
-        let max = max.unwrap();
-        println!("{min}: {max}");
-
-        let mut length = seen.len();
-
-        let mut step = (max - min) / length as f32;
+        // Only non-finite points give a single NaN tick
+        let min = min.unwrap_or(f32::NAN);
+        let max = max.unwrap_or(f32::NAN);
 
-        if step * (length as f32) != max - min {
-            step += 1.0
-        }
-
-        if ((length - 1) as f32) * step + min < max {
-            length += 1;
-        }
+        let (step, length) = float_steps(min, max, seen.len() + nans);
 
         Self {
             kind: ScaleKind::Float,
             length,
+            descending: false,
+            index: CategoryIndex::default(),
+            order: CategoricalOrder::default(),
             values: ScaleValues::Float {
                 start: min,
                 end: max,
@@ -613,13 +1398,365 @@ impl Scale {
         }
     }
 
+    /// Sorts the values of a categorical scale by their attached order, if
+    /// any, or by value. See [`Scale::set_order`].
+    ///
+    /// Other scales are left unchanged.
     pub fn sort(&mut self) {
         if let ScaleValues::Categorical(values) = &mut self.values {
-            values.sort();
+            match self.order {
+                CategoricalOrder::None => values.sort(),
+                ref order => {
+                    values.sort_by_key(|value| order.position(value).unwrap_or(usize::MAX))
+                }
+            }
+            self.index.invalidate();
+        }
+    }
+
+    /// Attaches `order` to a categorical scale and sorts its values by it,
+    /// if every value other than empty ones is part of the order. Empty
+    /// values follow the rest. Returns true if `order` was attached.
+    ///
+    /// [`CategoricalOrder::None`] detaches any order, leaving the values as
+    /// they are. Other scales are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{models::{CategoricalOrder, Scale, ScaleKind}, repr::Data};
+    ///
+    /// let mut scale = Scale::new(["MAR", "JAN", "feb"], ScaleKind::Categorical);
+    /// assert!(scale.set_order(CategoricalOrder::Months));
+    ///
+    /// let points = vec![Data::from("JAN"), Data::from("feb"), Data::from("MAR")];
+    /// assert_eq!(scale.points(), points);
+    /// ```
+    pub fn set_order(&mut self, order: CategoricalOrder) -> bool {
+        let ScaleValues::Categorical(values) = &self.values else {
+            return false;
+        };
+
+        if order == CategoricalOrder::None {
+            self.order = order;
+            return true;
+        }
+
+        if !order.orders(values.iter()) {
+            return false;
+        }
+
+        self.order = order;
+        self.sort();
+        true
+    }
+
+    /// Returns the known order of the values of the scale, if attached. See
+    /// [`Scale::set_order`].
+    pub fn categorical_order(&self) -> &CategoricalOrder {
+        &self.order
+    }
+
+    /// Reorders the values of a categorical scale so that [`Scale::points`]
+    /// lists those in `order` first, in that order. Values of the scale not in
+    /// `order` follow in their previous order, and values of `order` not on the
+    /// scale are ignored. The direction of the scale is kept.
+    ///
+    /// Other scales are left unchanged.
+    pub fn reorder_categories<'a>(&mut self, order: impl IntoIterator<Item = &'a Data>) {
+        let descending = self.descending;
+
+        if let ScaleValues::Categorical(values) = &mut self.values {
+            if descending {
+                values.reverse();
+            }
+
+            let mut rest = std::mem::take(values);
+            for value in order {
+                if let Some(pos) = rest.iter().position(|other| other == value) {
+                    values.push(rest.remove(pos));
+                }
+            }
+            values.append(&mut rest);
+            self.index.invalidate();
+
+            if descending {
+                values.reverse();
+            }
+        }
+    }
+}
+
+/// Builds a [`Scale`] from points pushed one at a time, such as while
+/// streaming rows, giving the same scale as [`Scale::new`] over all the
+/// points.
+///
+/// Only the distinct points are kept, so a scale of many repeated values
+/// never holds them all at once.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::{models::{Scale, ScaleBuilder, ScaleKind}, repr::Data};
+///
+/// let mut builder = ScaleBuilder::new(ScaleKind::Integer);
+/// builder.push(Data::Integer(4));
+/// builder.extend([Data::Integer(-2), Data::Integer(4)]);
+///
+/// let points = [Data::Integer(4), Data::Integer(-2), Data::Integer(4)];
+/// assert_eq!(builder.build(), Scale::new(points, ScaleKind::Integer));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScaleBuilder {
+    kind: ScaleKind,
+    /// The distinct points, in the order first seen
+    distinct: Vec<Data>,
+    seen: HashSet<Data>,
+    /// Whether every point is empty
+    all_empty: bool,
+    /// Whether every point is of the type of `kind`
+    all_kind: bool,
+    /// NaN points are each a distinct point on a float scale
+    nans: usize,
+}
+
+impl ScaleBuilder {
+    /// Returns a builder of a scale of `kind`. As with [`Scale::new`], a
+    /// [`ScaleKind::Categorical`] scale is built instead if the points do not
+    /// make a scale of `kind`.
+    pub fn new(kind: ScaleKind) -> Self {
+        Self {
+            kind,
+            distinct: Vec::new(),
+            seen: HashSet::new(),
+            all_empty: true,
+            all_kind: true,
+            nans: 0,
+        }
+    }
+
+    /// Adds `point` to the scale.
+    pub fn push(&mut self, point: impl Into<Data>) {
+        let point = point.into();
+
+        self.all_empty &= point == Data::None;
+        self.all_kind &= matches!(
+            (self.kind, &point),
+            (ScaleKind::Integer, Data::Integer(_))
+                | (ScaleKind::Number, Data::Number(_))
+                | (ScaleKind::Float, Data::Float(_))
+        );
+        if matches!(point, Data::Float(float) if float.is_nan()) {
+            self.nans += 1;
+        }
+
+        if !self.seen.contains(&point) {
+            self.seen.insert(point.clone());
+            self.distinct.push(point);
+        }
+    }
+
+    /// Returns the scale of the points pushed.
+    pub fn build(self) -> Scale {
+        let categorical = |distinct: Vec<Data>| Scale {
+            kind: ScaleKind::Categorical,
+            length: distinct.len(),
+            values: ScaleValues::Categorical(distinct),
+            descending: false,
+            index: CategoryIndex::default(),
+            order: CategoricalOrder::default(),
+        };
+
+        match self.kind {
+            ScaleKind::Categorical => categorical(self.distinct),
+            kind if self.all_empty => Scale::new(Vec::<Data>::new(), kind),
+            _ if !self.all_kind => categorical(self.distinct),
+            ScaleKind::Integer => {
+                Scale::from_i32(self.distinct.into_iter().filter_map(|point| match point {
+                    Data::Integer(num) => Some(num),
+                    _ => None,
+                }))
+            }
+            ScaleKind::Number => {
+                Scale::from_isize(self.distinct.into_iter().filter_map(|point| match point {
+                    Data::Number(num) => Some(num),
+                    _ => None,
+                }))
+            }
+            ScaleKind::Float => {
+                let floats = self.distinct.into_iter().filter_map(|point| match point {
+                    Data::Float(float) if !float.is_nan() => Some(float),
+                    _ => None,
+                });
+
+                Scale::from_f32(floats.chain(std::iter::repeat(f32::NAN).take(self.nans)))
+            }
         }
     }
 }
 
+impl<D: Into<Data>> Extend<D> for ScaleBuilder {
+    fn extend<T: IntoIterator<Item = D>>(&mut self, points: T) {
+        points.into_iter().for_each(|point| self.push(point));
+    }
+}
+
+/// The integer types of integer scales.
+trait ScaleInt: Copy + TryFrom<i128> {
+    const MAX: Self;
+
+    fn widen(self) -> i128;
+}
+
+impl ScaleInt for i32 {
+    const MAX: Self = i32::MAX;
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+}
+
+impl ScaleInt for isize {
+    const MAX: Self = isize::MAX;
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+}
+
+/// Keeps every nth of `points` so at most `max_ticks` remain, with the
+/// stride aligned to keep zero if present.
+fn thin_points(points: Vec<Data>, max_ticks: usize) -> Vec<Data> {
+    let max_ticks = max_ticks.max(1);
+
+    if points.len() <= max_ticks {
+        return points;
+    }
+
+    let stride = (points.len() + max_ticks - 1) / max_ticks;
+    let offset = points
+        .iter()
+        .position(|point| point.as_f64() == Some(0.0))
+        .map_or(0, |zero| zero % stride);
+
+    points
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| idx % stride == offset)
+        .map(|(_, point)| point)
+        .collect()
+}
+
+/// Returns the tick at `idx` of an integer scale. A last tick overshooting
+/// the largest value of the type is clamped to it.
+fn integer_tick<T: ScaleInt>(start: T, step: T, idx: usize) -> T {
+    let tick = start.widen() + (idx as i128) * step.widen();
+
+    T::try_from(tick).unwrap_or(T::MAX)
+}
+
+/// Returns the tick at `idx` of a floating point scale with `length` ticks.
+/// Rounding may leave the last tick just short of `end`, so it is never less
+/// than `end`.
+fn float_tick(start: f32, end: f32, step: f32, idx: usize, length: usize) -> f32 {
+    let tick = start + (idx as f32) * step;
+
+    if idx + 1 == length {
+        tick.max(end)
+    } else {
+        tick
+    }
+}
+
+/// Returns the position of the tick at or below `value` on an integer scale
+/// whose last tick is at `last`, if the scale contains `value`.
+fn integer_position<T: ScaleInt>(start: T, step: T, last: usize, value: T) -> Option<usize> {
+    let offset = value.widen() - start.widen();
+    if offset < 0 || value.widen() > integer_tick(start, step, last).widen() {
+        return None;
+    }
+
+    match step.widen() {
+        0 => Some(0),
+        step => Some(((offset / step) as usize).min(last)),
+    }
+}
+
+/// Returns the position of the tick at or below `value` on a floating point
+/// scale with `length` ticks, if the scale contains `value`.
+///
+/// The division may round either way, so the position is corrected against
+/// the ticks themselves, and values exactly on a tick land on it.
+fn float_position(start: f32, end: f32, step: f32, length: usize, value: f32) -> Option<usize> {
+    let last = length - 1;
+    let tick = |idx: usize| float_tick(start, end, step, idx, length);
+
+    if !(value.is_finite() && start <= value && value <= tick(last)) {
+        return None;
+    }
+
+    if step <= 0.0 {
+        return Some(0);
+    }
+
+    let mut position = (((value - start) / step).floor() as usize).min(last);
+    while position > 0 && tick(position) > value {
+        position -= 1;
+    }
+    while position < last && tick(position + 1) <= value {
+        position += 1;
+    }
+
+    Some(position)
+}
+
+/// Returns the step and the number of ticks of an integer scale from `min`
+/// to `max`, generated from `distinct` points.
+///
+/// The range is split into one interval fewer than the distinct points. Up
+/// to twice as many intervals are used if that lets the ticks end exactly on
+/// `max`, otherwise the last tick overshoots `max` by as little as possible.
+/// The step is at most `max_step`.
+fn integer_steps(min: i128, max: i128, distinct: usize, max_step: i128) -> (i128, usize) {
+    let div_ceil = |num: i128, den: i128| num / den + i128::from(num % den != 0);
+    let range = max - min;
+
+    if range <= 0 {
+        return (0, 1);
+    }
+
+    let intervals = ((distinct.max(2) - 1) as i128).max(div_ceil(range, max_step));
+
+    if let Some(exact) = (intervals..=(intervals * 2).min(range)).find(|k| range % k == 0) {
+        return (range / exact, exact as usize + 1);
+    }
+
+    let step = div_ceil(range, intervals);
+
+    (step, div_ceil(range, step) as usize + 1)
+}
+
+/// Returns the step and the number of ticks of a floating point scale from
+/// `min` to `max`, generated from `distinct` points.
+///
+/// Whole `min` and `max` are stepped as in [`integer_steps`]. Otherwise the
+/// range is split evenly into one interval fewer than the distinct points.
+fn float_steps(min: f32, max: f32, distinct: usize) -> (f32, usize) {
+    // Floats are exact integers up to 2^24
+    let whole = |value: f32| value.fract() == 0.0 && value.abs() <= 16_777_216.0;
+    let range = max - min;
+
+    if range.is_nan() || range <= 0.0 {
+        (0.0, 1)
+    } else if whole(min) && whole(max) {
+        let (step, length) = integer_steps(min as i128, max as i128, distinct, i128::MAX);
+        (step as f32, length)
+    } else {
+        let intervals = distinct.max(2) - 1;
+        (range / intervals as f32, intervals + 1)
+    }
+}
+
 impl From<Vec<i32>> for Scale {
     fn from(value: Vec<i32>) -> Self {
         Self::new(value, ScaleKind::Integer)
@@ -641,6 +1778,7 @@ impl From<Vec<f32>> for Scale {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{arbitrary::any, collection, prelude::*, proptest};
 
     fn create_point<X, Y>(x: X, y: Y) -> Point<X, Y> {
         Point::new(x, y)
@@ -701,17 +1839,25 @@ mod tests {
         let pnts: Vec<isize> = vec![1, 12, 12, 6, 4, 1, 25];
         let scale = Scale::new(pnts, ScaleKind::Number);
 
-        assert_eq!(scale.length, 6);
+        assert_eq!(scale.length, 5);
+        assert_eq!(
+            scale.points(),
+            [1, 7, 13, 19, 25].map(Data::Number).to_vec()
+        );
         assert!(scale.contains(&Data::Number(25)));
-        assert!(scale.contains(&Data::Number(26)));
-        assert!(!scale.contains(&Data::Number(30)));
+        assert!(!scale.contains(&Data::Number(26)));
         assert!(!scale.contains(&Data::Integer(25)));
 
         let pnts: Vec<f32> = vec![1.0, 3.0, 2.0, 2.0, 3.0, 4.0, 1.0, 5.0];
         let scale = Scale::new(pnts, ScaleKind::Float);
 
-        assert_eq!(scale.length, 6);
+        assert_eq!(scale.length, 5);
+        assert_eq!(
+            scale.points(),
+            [1.0, 2.0, 3.0, 4.0, 5.0].map(Data::Float).to_vec()
+        );
         assert!(!scale.contains(&Data::Float(0.99)));
+        assert!(!scale.contains(&Data::Float(5.01)));
 
         let pnts: Vec<isize> = vec![1, 12, 12, 6, 4, 1, 25];
         let mut scale = Scale::new(pnts, ScaleKind::Categorical);
@@ -752,40 +1898,231 @@ mod tests {
         assert!(scale.contains(&Data::Integer(44)));
         assert!(scale.contains(&Data::None));
         assert!(scale.contains(&Data::Text("Test".into())));
+
+        let contains = scale.contains_fn();
+        for value in [
+            Data::Text("44".into()),
+            Data::Integer(44),
+            Data::None,
+            Data::Float(44.0),
+        ] {
+            assert_eq!(contains(&value), scale.contains(&value));
+        }
     }
 
     #[test]
-    fn test_scale_pos_neg() {
-        let pnts = vec![-1, -8, -3];
-        let scale = Scale::new(pnts, ScaleKind::Integer);
-
-        assert_eq!(scale.length, 4);
+    fn test_tick_positions() {
+        let scale = Scale::from((0..=100).collect::<Vec<i32>>());
+        assert_eq!(scale.step(), Some(Data::Integer(1)));
+        assert_eq!(scale.start(), Some(Data::Integer(0)));
+        assert_eq!(scale.end(), Some(Data::Integer(100)));
         assert_eq!(
-            scale.points(),
-            vec![
-                Data::Integer(-8),
-                Data::Integer(-5),
-                Data::Integer(-2),
-                Data::Integer(1),
+            scale.tick_positions(6),
+            [0, 20, 40, 60, 80, 100].map(Data::Integer)
+        );
+        assert_eq!(scale.tick_positions(4), [0, 33, 67, 100].map(Data::Integer));
+        assert_eq!(scale.tick_positions(1), vec![Data::Integer(0)]);
+        assert!(scale.tick_positions(0).is_empty());
+        assert_eq!(scale.tick_positions(scale.length), scale.points());
+        assert_eq!(scale.tick_positions(500), scale.points());
+
+        let mut inverted = scale.clone();
+        inverted.invert();
+        assert_eq!(inverted.start(), Some(Data::Integer(100)));
+        assert_eq!(inverted.end(), Some(Data::Integer(0)));
+        assert_eq!(inverted.step(), Some(Data::Integer(1)));
+        assert_eq!(inverted.tick_positions(3), [100, 50, 0].map(Data::Integer));
+
+        let scale = Scale::new([0.5, 0.25, 2.0, 1.0], ScaleKind::Float);
+        let ticks = scale.tick_positions(3);
+        assert_eq!(ticks.len(), 3);
+        assert_eq!(ticks.first(), scale.start().as_ref());
+        assert_eq!(ticks.last(), scale.end().as_ref());
+        assert!(matches!(scale.step(), Some(Data::Float(step)) if step > 0.0));
+        assert!(ticks.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let months = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL"];
+        let scale = Scale::new(months, ScaleKind::Categorical);
+        assert_eq!(scale.step(), None);
+        assert_eq!(scale.end(), Some(Data::Text("JUL".into())));
+        assert_eq!(
+            scale.tick_positions(4),
+            ["JAN", "MAR", "MAY", "JUL"].map(|month| Data::Text(month.into()))
+        );
+        assert_eq!(scale.tick_positions(7).len(), 7);
+        for desired in 2..7 {
+            let ticks = scale.tick_positions(desired);
+            assert_eq!(ticks.len(), desired);
+            assert_eq!(ticks.iter().collect::<HashSet<_>>().len(), desired);
+        }
+
+        let empty = Scale::new(Vec::<Data>::new(), ScaleKind::Categorical);
+        assert_eq!(empty.start(), None);
+        assert_eq!(empty.end(), None);
+        assert!(empty.tick_positions(3).is_empty());
+        assert!(empty.tick_positions(1).is_empty());
+    }
+
+    #[test]
+    fn test_categorical_order() {
+        let text = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| Data::Text(value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut scale = Scale::new(
+            text(&["APR", "AUG", "dec", "February", "Jan", "MARCH"]),
+            ScaleKind::Categorical,
+        );
+        scale.sort();
+        assert_eq!(
+            scale.points(),
+            text(&["APR", "AUG", "February", "Jan", "MARCH", "dec"])
+        );
+        assert!(scale.set_order(CategoricalOrder::Months));
+        assert_eq!(
+            scale.points(),
+            text(&["Jan", "February", "MARCH", "APR", "AUG", "dec"])
+        );
+        assert_eq!(scale.position_of(&Data::from("APR")), Some(3));
+
+        // Sorting keeps to the attached order, in either direction
+        scale.reorder_categories(&text(&["dec"]));
+        scale.sort();
+        assert_eq!(scale.points()[0], Data::from("Jan"));
+        scale.invert();
+        assert_eq!(scale.points()[0], Data::from("dec"));
+        assert!(scale.set_order(CategoricalOrder::None));
+        scale.sort();
+        assert_eq!(scale.points()[0], Data::from("dec"));
+
+        // Empty values follow the rest
+        let mut days = Scale::new(
+            vec![
+                Data::from("Sun"),
+                Data::None,
+                Data::from("wed"),
+                Data::from("MON"),
+            ],
+            ScaleKind::Categorical,
+        );
+        assert!(days.set_order(CategoricalOrder::Weekdays));
+        assert_eq!(
+            days.points(),
+            vec![
+                Data::from("MON"),
+                Data::from("wed"),
+                Data::from("Sun"),
+                Data::None
             ]
         );
-        assert!(scale.contains(&Data::Integer(0)));
 
+        // Orders are only attached if every value is part of them
+        let mut scale = Scale::new(text(&["Jan", "Feb", "Total"]), ScaleKind::Categorical);
+        assert!(!scale.set_order(CategoricalOrder::Months));
+        assert_eq!(scale.categorical_order(), &CategoricalOrder::None);
+        assert_eq!(scale.points(), text(&["Jan", "Feb", "Total"]));
+        let empty = Scale::new(Vec::<Data>::new(), ScaleKind::Categorical);
+        assert!(!empty.clone().set_order(CategoricalOrder::Months));
+        assert!(!Scale::from(vec![1, 2]).set_order(CategoricalOrder::Months));
+
+        let order = CategoricalOrder::Custom(vec!["low".into(), "mid".into(), "high".into()]);
+        let mut scale = Scale::new(text(&["high", "low", "mid"]), ScaleKind::Categorical);
+        assert!(scale.set_order(order.clone()));
+        assert_eq!(scale.points(), text(&["low", "mid", "high"]));
+        assert_eq!(order.position(&Data::from("Low")), None);
+    }
+
+    #[test]
+    fn test_position_of() {
+        let scale = Scale::from(vec![0, 10, 20, 26]);
+        let ticks = scale.points();
+        assert_eq!(ticks, [0, 9, 18, 27].map(Data::Integer));
+        for (pos, tick) in ticks.iter().enumerate() {
+            assert_eq!(scale.position_of(tick), Some(pos));
+        }
+        assert_eq!(scale.position_of(&Data::Integer(8)), Some(0));
+        assert_eq!(scale.position_of(&Data::Integer(26)), Some(2));
+        assert_eq!(scale.position_of(&Data::Integer(28)), None);
+        assert_eq!(scale.position_of(&Data::Integer(-1)), None);
+        assert_eq!(scale.position_of(&Data::Number(9)), None);
+
+        let mut inverted = scale.clone();
+        inverted.invert();
+        assert_eq!(inverted.position_of(&Data::Integer(27)), Some(0));
+        assert_eq!(inverted.position_of(&Data::Integer(0)), Some(3));
+        assert_eq!(inverted.position_of(&Data::Integer(10)), Some(2));
+
+        let single = Scale::from(vec![isize::MAX, isize::MAX]);
+        assert_eq!(single.position_of(&Data::Number(isize::MAX)), Some(0));
+        assert_eq!(single.position_of(&Data::Number(0)), None);
+
+        let scale = Scale::new([0.1, 0.7, 0.3, 0.5], ScaleKind::Float);
+        let ticks = scale.points();
+        for (pos, tick) in ticks.iter().enumerate() {
+            assert_eq!(scale.position_of(tick), Some(pos));
+        }
+        assert_eq!(scale.position_of(&Data::Float(0.69)), Some(2));
+        assert_eq!(scale.position_of(&Data::Float(0.71)), None);
+        assert_eq!(scale.position_of(&Data::Float(f32::NAN)), None);
+        assert_eq!(scale.position_of(&Data::Float(f32::INFINITY)), None);
+
+        let months = ["JAN", "FEB", "MAR"].map(|month| Data::Text(month.into()));
+        let mut scale = Scale::new(months.clone(), ScaleKind::Categorical);
+        assert_eq!(
+            scale.positions(months.iter()),
+            vec![Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(scale.position_of(&Data::Text("APR".into())), None);
+
+        scale.sort();
+        assert_eq!(
+            scale.positions(months.iter()),
+            vec![Some(1), Some(0), Some(2)]
+        );
+        scale.reorder_categories(&[months[2].clone()]);
+        assert_eq!(
+            scale.positions(months.iter()),
+            vec![Some(2), Some(1), Some(0)]
+        );
+        scale.invert();
+        assert_eq!(
+            scale.positions(months.iter()),
+            vec![Some(0), Some(1), Some(2)]
+        );
+
+        let empty = Scale::new(Vec::<Data>::new(), ScaleKind::Categorical);
+        assert_eq!(empty.position_of(&Data::None), None);
+    }
+
+    #[test]
+    fn test_scale_pos_neg() {
+        // A range of 7 cannot be split evenly into 2 to 4 intervals, so the
+        // last tick overshoots the max by as little as possible
+        let pnts = vec![-1, -8, -3];
+        let scale = Scale::new(pnts, ScaleKind::Integer);
+
+        assert_eq!(scale.length, 3);
+        assert_eq!(
+            scale.points(),
+            vec![Data::Integer(-8), Data::Integer(-4), Data::Integer(0),]
+        );
+        assert!(scale.contains(&Data::Integer(-1)));
+        assert!(!scale.contains(&Data::Integer(1)));
+
+        // A range of 7 split into 7 intervals ends exactly on the max
         let pnts = vec![-2, 0, 1, 2, 5];
         let scale = Scale::new(pnts, ScaleKind::Integer);
 
-        assert_eq!(scale.length, 5);
+        assert_eq!(scale.length, 8);
         assert_eq!(
             scale.points(),
-            vec![
-                Data::Integer(-2),
-                Data::Integer(0),
-                Data::Integer(2),
-                Data::Integer(4),
-                Data::Integer(6),
-            ]
+            (-2..=5).map(Data::Integer).collect::<Vec<_>>()
         );
-        assert!(scale.contains(&Data::Integer(6)));
+        assert!(scale.contains(&Data::Integer(5)));
+        assert!(!scale.contains(&Data::Integer(6)));
         assert!(!scale.contains(&Data::Integer(-3)));
 
         let pnts = vec![-3, -10, -1, 2, -5];
@@ -815,13 +2152,183 @@ mod tests {
         assert!(!scale.contains(&Data::Integer(2)));
     }
 
+    #[test]
+    fn test_scale_zero_range() {
+        let scale = Scale::new(vec![7, 7, 7], ScaleKind::Integer);
+        assert_eq!(scale.length, 1);
+        assert_eq!(scale.points(), vec![Data::Integer(7)]);
+
+        let scale = Scale::new(vec![-3_isize, -3], ScaleKind::Number);
+        assert_eq!(scale.points(), vec![Data::Number(-3)]);
+
+        let scale = Scale::new(vec![0.5_f32, 0.5], ScaleKind::Float);
+        assert_eq!(scale.points(), vec![Data::Float(0.5)]);
+        assert_eq!(
+            scale.axis_points(false),
+            AxisPoints::Numeric {
+                positives: vec![Data::Float(0.5)],
+                negatives: vec![],
+            }
+        );
+
+        let scale = Scale::new(vec![0.5_f32, 2.0, 1.25], ScaleKind::Float);
+        assert_eq!(scale.points(), [0.5, 1.25, 2.0].map(Data::Float).to_vec());
+    }
+
     #[test]
     fn test_scale_none() {
         let scale = Scale::new(Vec::<Data>::new(), ScaleKind::Integer);
 
-        assert_eq!(scale.length, 1);
-        assert_eq!(scale.points(), vec![Data::Integer(0)]);
-        assert!(scale.contains(&Data::Integer(0)));
+        assert!(scale.is_empty());
+        assert_eq!(scale.length, 0);
+        assert!(scale.points().is_empty());
+        assert!(scale.ranged().is_empty());
+        assert!(!scale.contains(&Data::Integer(0)));
+        assert_eq!(scale.start(), None);
+        assert_eq!(scale.position_of(&Data::Integer(0)), None);
+        assert_eq!(
+            scale.axis_points(true),
+            AxisPoints::Numeric {
+                positives: vec![],
+                negatives: vec![],
+            }
+        );
+
+        let scale = Scale::new(Vec::<Data>::new(), ScaleKind::Categorical);
+        assert!(scale.is_empty());
+        assert_eq!(scale.axis_points(false), AxisPoints::Categorical(vec![]));
+    }
+
+    #[test]
+    fn test_scale_only_none() {
+        let pnts = vec![Data::None, Data::None];
+        let scale = Scale::new(pnts.clone(), ScaleKind::Integer);
+
+        assert!(!scale.is_categorical());
+        assert!(scale.is_empty());
+        assert!(scale.points().is_empty());
+
+        let mut scale = Scale::new(pnts.clone(), ScaleKind::Number);
+        assert!(scale.is_empty());
+        scale.include_zero();
+        assert!(scale.is_empty());
+
+        let scale = Scale::new(pnts, ScaleKind::Float);
+        assert!(scale.is_empty());
+        assert!(!scale.contains(&Data::Float(f32::NAN)));
+        assert_eq!(scale.tick_positions(3), vec![]);
+    }
+
+    #[test]
+    fn test_scale_stable_order() {
+        let pnts = vec![
+            Data::Integer(44),
+            Data::Text("Test".into()),
+            Data::None,
+            Data::Integer(4),
+            Data::Text("Other".into()),
+            Data::Integer(44),
+            Data::Boolean(true),
+        ];
+        let expected = vec![
+            Data::Integer(44),
+            Data::Text("Test".into()),
+            Data::None,
+            Data::Integer(4),
+            Data::Text("Other".into()),
+            Data::Boolean(true),
+        ];
+
+        for kind in [ScaleKind::Integer, ScaleKind::Number, ScaleKind::Float] {
+            for _ in 0..32 {
+                let scale = Scale::new(pnts.clone(), kind);
+
+                assert!(scale.is_categorical());
+                assert_eq!(scale.length, 6);
+                assert_eq!(scale.points(), expected);
+            }
+        }
+
+        let pnts = vec![Data::Integer(3), Data::None, Data::Integer(1)];
+        let scale = Scale::new(pnts, ScaleKind::Integer);
+
+        assert!(scale.is_categorical());
+        assert_eq!(
+            scale.points(),
+            vec![Data::Integer(3), Data::None, Data::Integer(1)]
+        );
+    }
+
+    #[test]
+    fn test_scale_descending() {
+        let mut scale = Scale::new(vec![-2, 0, 3], ScaleKind::Integer);
+        let points = scale.points();
+        let ranged = scale.ranged();
+
+        scale.invert();
+        assert!(scale.is_descending());
+        assert_eq!(scale.points(), points.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(scale.ranged(), ranged.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(
+            scale.axis_points(true),
+            AxisPoints::Numeric {
+                positives: vec![3.into(), 2.into(), 1.into(), 0.into()],
+                negatives: vec![(-1).into(), (-2).into()],
+            }
+        );
+        assert!(scale.contains(&Data::Integer(-2)));
+        assert!(scale.contains(&Data::Integer(3)));
+
+        let mut scale = Scale::new(vec![-2_isize, 0, 3], ScaleKind::Number);
+        let points = scale.points();
+        scale.invert();
+        assert_eq!(scale.points(), points.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(
+            scale.ranged(),
+            (-2_isize..=3).rev().map(Data::from).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            scale.axis_points(true),
+            AxisPoints::Numeric {
+                positives: (0_isize..=3).rev().map(Data::from).collect(),
+                negatives: vec![Data::Number(-1), Data::Number(-2)],
+            }
+        );
+        assert!(scale.contains(&Data::Number(1)));
+
+        let mut scale = Scale::new(vec![-1.0_f32, 2.0], ScaleKind::Float);
+        let points = scale.points();
+        scale.invert();
+        assert_eq!(
+            scale.points(),
+            points.iter().cloned().rev().collect::<Vec<_>>()
+        );
+        assert_eq!(scale.ranged(), scale.points());
+        match scale.axis_points(false) {
+            AxisPoints::Numeric {
+                positives,
+                negatives,
+            } => {
+                assert!(positives.windows(2).all(|pair| pair[0] > pair[1]));
+                assert!(negatives.windows(2).all(|pair| pair[0] > pair[1]));
+            }
+            _ => panic!("Expected numeric axis points"),
+        }
+        assert!(scale.contains(&Data::Float(0.5)));
+
+        let mut scale = Scale::new(vec!["a", "b", "c"], ScaleKind::Categorical);
+        scale.invert();
+        assert_eq!(scale.points(), vec!["c".into(), "b".into(), "a".into()]);
+        assert_eq!(scale.ranged(), scale.points());
+        assert_eq!(
+            scale.axis_points(false),
+            AxisPoints::Categorical(vec!["c".into(), "b".into(), "a".into()])
+        );
+        assert!(scale.contains(&Data::from("a")));
+
+        scale.invert();
+        assert!(!scale.is_descending());
+        assert_eq!(scale.points(), vec!["a".into(), "b".into(), "c".into()]);
     }
 
     #[test]
@@ -950,12 +2457,13 @@ mod tests {
         let pnts: Vec<isize> = vec![1, 2, -9, 10];
         let scale = Scale::from(pnts);
 
-        assert_eq!(scale.length, 5);
+        // 19 has no divisor between 3 and 6, so the last tick overshoots
+        assert_eq!(scale.length, 4);
         assert_eq!(
             scale.axis_points(false),
             AxisPoints::Numeric {
-                positives: vec![Data::Number(1), Data::Number(6), Data::Number(11),],
-                negatives: vec![Data::Number(-9), Data::Number(-4),],
+                positives: vec![Data::Number(5), Data::Number(12)],
+                negatives: vec![Data::Number(-9), Data::Number(-2)],
             }
         );
 
@@ -975,4 +2483,472 @@ mod tests {
             }
         );
     }
+
+    /// A plain scale construction, kept to check the fast paths give the
+    /// same results.
+    fn reference_scale(points: Vec<Data>, kind: ScaleKind) -> (usize, ScaleValues) {
+        fn steps(min: i128, max: i128, distinct: usize) -> (usize, i128) {
+            let range = max - min;
+
+            if range == 0 {
+                return (1, 0);
+            }
+
+            let intervals = distinct.max(2) as i128 - 1;
+            let exact = (intervals..=intervals * 2)
+                .filter(|k| *k <= range)
+                .find(|k| range % k == 0);
+
+            match exact {
+                Some(k) => (k as usize + 1, range / k),
+                None => {
+                    let mut step = range / intervals;
+                    if step * intervals < range {
+                        step += 1;
+                    }
+                    let mut length = 1;
+                    while min + (length as i128 - 1) * step < max {
+                        length += 1;
+                    }
+                    (length, step)
+                }
+            }
+        }
+
+        fn range(distinct: Vec<i128>) -> (usize, i128, i128, i128) {
+            let min = *distinct.iter().min().unwrap();
+            let max = *distinct.iter().max().unwrap();
+            let (length, step) = steps(min, max, distinct.len());
+
+            (length, min, max, step)
+        }
+
+        let categorical = |points: Vec<Data>| {
+            let mut values: Vec<Data> = Vec::default();
+            for point in points {
+                if !values.iter().any(|pnt| pnt == &point) {
+                    values.push(point);
+                }
+            }
+            (values.len(), ScaleValues::Categorical(values))
+        };
+
+        if points.iter().all(|point| point == &Data::None) {
+            let values = match kind {
+                ScaleKind::Categorical => return categorical(points),
+                ScaleKind::Integer => ScaleValues::Integer {
+                    start: 0,
+                    end: 0,
+                    step: 0,
+                },
+                ScaleKind::Number => ScaleValues::Number {
+                    start: 0,
+                    end: 0,
+                    step: 0,
+                },
+                ScaleKind::Float => ScaleValues::Float {
+                    start: 0.0,
+                    end: 0.0,
+                    step: 0.0,
+                },
+            };
+
+            return (0, values);
+        }
+
+        match kind {
+            ScaleKind::Categorical => categorical(points),
+            ScaleKind::Integer => {
+                let nums = points
+                    .iter()
+                    .map(|point| match point {
+                        Data::Integer(num) => Some(*num),
+                        _ => None,
+                    })
+                    .collect::<Option<HashSet<i32>>>();
+                match nums {
+                    Some(nums) if !nums.is_empty() => {
+                        let (length, start, end, step) =
+                            range(nums.into_iter().map(i128::from).collect());
+                        let (start, end, step) = (start as i32, end as i32, step as i32);
+                        (length, ScaleValues::Integer { start, end, step })
+                    }
+                    _ => categorical(points),
+                }
+            }
+            ScaleKind::Number => {
+                let nums = points
+                    .iter()
+                    .map(|point| match point {
+                        Data::Number(num) => Some(*num),
+                        _ => None,
+                    })
+                    .collect::<Option<HashSet<isize>>>();
+                match nums {
+                    Some(nums) if !nums.is_empty() => {
+                        let (length, start, end, step) =
+                            range(nums.into_iter().map(|num| num as i128).collect());
+                        let (start, end, step) = (start as isize, end as isize, step as isize);
+                        (length, ScaleValues::Number { start, end, step })
+                    }
+                    _ => categorical(points),
+                }
+            }
+            ScaleKind::Float => {
+                let floats = points
+                    .iter()
+                    .map(|point| match point {
+                        Data::Float(num) => Some(*num),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<f32>>>();
+                match floats {
+                    Some(floats) if !floats.is_empty() => {
+                        let mut seen: Vec<f32> = Vec::default();
+                        for point in floats {
+                            if !seen.contains(&point) {
+                                seen.push(point);
+                            }
+                        }
+
+                        let numbers = seen.iter().filter(|pnt| pnt.is_finite());
+                        let min = numbers.clone().copied().fold(f32::NAN, f32::min);
+                        let max = numbers.copied().fold(f32::NAN, f32::max);
+
+                        let (length, step) = if min.is_nan() || min == max {
+                            (1, 0.0)
+                        } else if min.fract() == 0.0 && max.fract() == 0.0 {
+                            let (length, step) = steps(min as i128, max as i128, seen.len());
+                            (length, step as f32)
+                        } else {
+                            (seen.len(), (max - min) / (seen.len() - 1) as f32)
+                        };
+
+                        (
+                            length,
+                            ScaleValues::Float {
+                                start: min,
+                                end: max,
+                                step,
+                            },
+                        )
+                    }
+                    _ => categorical(points),
+                }
+            }
+        }
+    }
+
+    fn assert_reference(points: Vec<Data>, kind: ScaleKind) {
+        let scale = Scale::new(points.clone(), kind);
+        let (length, values) = reference_scale(points, kind);
+
+        assert_eq!(scale.length, length);
+        assert_eq!(scale.values, values);
+    }
+
+    fn assert_builder(points: Vec<Data>, kind: ScaleKind) {
+        let mut builder = ScaleBuilder::new(kind);
+        builder.extend(points.iter().cloned());
+
+        assert_eq!(builder.build(), Scale::new(points, kind));
+    }
+
+    fn data_points() -> impl Strategy<Value = Vec<Data>> {
+        let point = prop_oneof![
+            any::<i32>().prop_map(|num| Data::Integer(num % 1000)),
+            any::<bool>().prop_map(Data::Boolean),
+            "[a-e]{1,2}".prop_map(Data::Text),
+            Just(Data::None),
+        ];
+
+        collection::vec(point, 0..200)
+    }
+
+    #[test]
+    fn test_axis_points_include_zero() {
+        let ints = |values: &[i32]| values.iter().map(|val| Data::Integer(*val)).collect();
+        let with_zero = AxisOptions {
+            include_zero: true,
+            ..Default::default()
+        };
+
+        let positive = Scale::from(vec![5, 10, 15]);
+        assert_eq!(
+            positive.axis_points(false),
+            AxisPoints::Numeric {
+                positives: ints(&[5, 10, 15]),
+                negatives: vec![]
+            }
+        );
+        assert_eq!(
+            positive.axis_points_with(with_zero),
+            AxisPoints::Numeric {
+                positives: ints(&[0, 5, 10, 15]),
+                negatives: vec![]
+            }
+        );
+
+        let negative = Scale::from(vec![-15, -10, -5]);
+        assert_eq!(
+            negative.axis_points(false),
+            AxisPoints::Numeric {
+                positives: vec![],
+                negatives: ints(&[-15, -10, -5])
+            }
+        );
+        assert_eq!(
+            negative.axis_points_with(with_zero),
+            AxisPoints::Numeric {
+                positives: ints(&[0]),
+                negatives: ints(&[-15, -10, -5])
+            }
+        );
+
+        // Scales spanning zero are unchanged
+        let spanning = Scale::from(vec![-4, 2, 6]);
+        assert_eq!(
+            spanning.axis_points_with(with_zero),
+            spanning.axis_points(false)
+        );
+        assert_eq!(
+            spanning.axis_points(false),
+            AxisPoints::Numeric {
+                positives: ints(&[1, 6]),
+                negatives: ints(&[-4])
+            }
+        );
+
+        let float = Scale::from(vec![2.0, 4.0, 6.0]);
+        assert_eq!(
+            float.axis_points_with(with_zero),
+            AxisPoints::Numeric {
+                positives: vec![0.0, 2.0, 4.0, 6.0]
+                    .into_iter()
+                    .map(From::from)
+                    .collect(),
+                negatives: vec![]
+            }
+        );
+
+        let mut descending = positive.clone();
+        descending.invert();
+        assert_eq!(
+            descending.axis_points_with(with_zero),
+            AxisPoints::Numeric {
+                positives: ints(&[15, 10, 5, 0]),
+                negatives: vec![]
+            }
+        );
+
+        let mut scale = positive;
+        scale.include_zero();
+        assert_eq!(scale.length, 4);
+        assert!(scale.contains(&Data::Integer(0)));
+
+        let mut scale = Scale::new(vec!["a", "b"], ScaleKind::Categorical);
+        scale.include_zero();
+        assert_eq!(scale.length, 2);
+    }
+
+    #[test]
+    fn test_extend_to() {
+        let mut scale = Scale::new(vec![0, 10, 20], ScaleKind::Integer);
+        assert!(scale.extend_to(&Data::Integer(10)));
+        assert_eq!(scale, Scale::new(vec![0, 10, 20], ScaleKind::Integer));
+
+        assert!(scale.extend_to(&Data::Integer(-35)));
+        assert!(scale.contains(&Data::Integer(-35)));
+        assert!(scale.contains(&Data::Integer(20)));
+        assert!(!scale.extend_to(&Data::Number(40)));
+        assert!(!scale.contains(&Data::Number(40)));
+
+        let mut scale = Scale::new(vec![0.5, 1.5], ScaleKind::Float);
+        assert!(scale.extend_to(&Data::Float(4.25)));
+        assert!(scale.contains(&Data::Float(4.25)));
+        assert!(!scale.extend_to(&Data::Integer(5)));
+
+        let mut scale = Scale::new(vec![Data::None], ScaleKind::Number);
+        assert!(scale.extend_to(&Data::Number(7)));
+        assert_eq!(scale.points(), vec![Data::Number(7)]);
+
+        let mut scale = Scale::new(vec!["a", "b"], ScaleKind::Categorical);
+        assert!(scale.extend_to(&Data::from("c")));
+        assert_eq!(scale.length, 3);
+        assert_eq!(scale.position_of(&Data::from("c")), Some(2));
+    }
+
+    #[test]
+    fn test_axis_points_max_ticks() {
+        let ints = |values: &[i32]| values.iter().map(|val| Data::Integer(*val)).collect();
+        let thinned = |max_ticks| AxisOptions {
+            sequential: true,
+            max_ticks: Some(max_ticks),
+            ..Default::default()
+        };
+
+        let scale = Scale::from((0..=10).collect::<Vec<i32>>());
+        assert_eq!(
+            scale.axis_points_with(thinned(4)),
+            AxisPoints::Numeric {
+                positives: ints(&[0, 3, 6, 9]),
+                negatives: vec![]
+            }
+        );
+        assert_eq!(scale.axis_points_with(thinned(20)), scale.axis_points(true));
+
+        // Thinning keeps zero
+        let scale = Scale::from((-5..=5).collect::<Vec<i32>>());
+        assert_eq!(
+            scale.axis_points_with(thinned(4)),
+            AxisPoints::Numeric {
+                positives: ints(&[0, 3]),
+                negatives: ints(&[-3])
+            }
+        );
+
+        let scale = Scale::new(vec!["a", "b", "c", "d", "e"], ScaleKind::Categorical);
+        assert_eq!(
+            scale.axis_points_with(thinned(2)),
+            AxisPoints::Categorical(vec!["a".into(), "d".into()])
+        );
+    }
+
+    #[test]
+    fn test_scale_signed_zero() {
+        assert_reference(
+            vec![Data::Float(0.0), Data::Float(-0.0), Data::Float(1.5)],
+            ScaleKind::Float,
+        );
+        assert_reference(
+            vec![
+                Data::Float(f32::NAN),
+                Data::Float(2.0),
+                Data::Float(f32::NAN),
+            ],
+            ScaleKind::Float,
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_scale_integer_reference(points in collection::vec(-500..500i32, 1..500)) {
+            let points = points.into_iter().map(Data::Integer).collect();
+            assert_reference(points, ScaleKind::Integer);
+        }
+
+        #[test]
+        fn test_scale_sorted_reference(mut points in collection::vec(any::<i32>().prop_map(|num| num / 4), 1..500)) {
+            points.sort();
+            let points = points.into_iter().map(Data::Integer).collect();
+            assert_reference(points, ScaleKind::Integer);
+        }
+
+        #[test]
+        fn test_scale_number_reference(points in collection::vec(-10_000..10_000isize, 1..500)) {
+            let points = points.into_iter().map(Data::Number).collect();
+            assert_reference(points, ScaleKind::Number);
+        }
+
+        #[test]
+        fn test_scale_float_reference(points in collection::vec(-100..100i32, 1..500)) {
+            let points = points
+                .into_iter()
+                .map(|num| Data::Float(num as f32 / 4.0))
+                .collect();
+            assert_reference(points, ScaleKind::Float);
+        }
+
+        #[test]
+        fn test_scale_ticks(points in collection::vec(any::<i32>(), 1..200)) {
+            let min = *points.iter().min().unwrap();
+            let max = *points.iter().max().unwrap();
+            let scale = Scale::from(points);
+            let ticks = scale.points();
+
+            prop_assert_eq!(ticks.len(), scale.length);
+            prop_assert_eq!(&ticks[0], &Data::Integer(min));
+
+            let last = match ticks[ticks.len() - 1] {
+                Data::Integer(last) => last,
+                _ => unreachable!(),
+            };
+            let step = match scale.values {
+                ScaleValues::Integer { step, .. } => step,
+                _ => unreachable!(),
+            };
+            prop_assert!(last >= max);
+            prop_assert!((last as i64) - (max as i64) < step.max(1) as i64);
+        }
+
+        #[test]
+        fn test_scale_builder(points in data_points()) {
+            for kind in [ScaleKind::Integer, ScaleKind::Number, ScaleKind::Float, ScaleKind::Categorical] {
+                assert_builder(points.clone(), kind);
+            }
+        }
+
+        #[test]
+        fn test_scale_builder_numeric(
+            ints in collection::vec(prop_oneof![(-500..500i32).prop_map(Some), Just(None)], 0..300),
+            floats in collection::vec(prop_oneof![
+                (-100..100i32).prop_map(|num| Some(num as f32 / 4.0)),
+                Just(Some(f32::NAN)),
+                Just(Some(-0.0)),
+                Just(None),
+            ], 0..300),
+        ) {
+            let data = |point: Option<Data>| point.unwrap_or(Data::None);
+
+            let points: Vec<Data> = ints.iter().map(|num| data(num.map(Data::Integer))).collect();
+            assert_builder(points, ScaleKind::Integer);
+
+            let points: Vec<Data> = ints
+                .iter()
+                .map(|num| data(num.map(|num| Data::Number(num as isize * 1000))))
+                .collect();
+            assert_builder(points, ScaleKind::Number);
+
+            let points: Vec<Data> = floats.iter().map(|num| data(num.map(Data::Float))).collect();
+            assert_builder(points, ScaleKind::Float);
+        }
+
+        #[test]
+        fn test_scale_positions(
+            ints in collection::vec(-10_000..10_000i32, 1..100),
+            floats in collection::vec(-1000..1000i32, 1..100),
+            divisor in 1..64i32,
+        ) {
+            let floats: Vec<f32> = floats.into_iter().map(|num| num as f32 / divisor as f32).collect();
+
+            for scale in [Scale::from(ints), Scale::from(floats)] {
+                let ticks = scale.points();
+                for (pos, tick) in ticks.iter().enumerate() {
+                    prop_assert_eq!(scale.position_of(tick), Some(pos));
+                }
+
+                // Values between ticks land on the tick below them
+                for pair in ticks.windows(2).enumerate() {
+                    let (pos, pair) = pair;
+                    let between = match (&pair[0], &pair[1]) {
+                        (Data::Integer(a), Data::Integer(b)) => Data::Integer(b - 1).max(Data::Integer(*a)),
+                        (Data::Float(a), Data::Float(b)) => Data::Float(a + (b - a) / 2.0),
+                        _ => unreachable!(),
+                    };
+                    prop_assert_eq!(scale.position_of(&between), Some(pos));
+                }
+
+                let mut inverted = scale.clone();
+                inverted.invert();
+                let positions = inverted.positions(inverted.points().iter());
+                prop_assert!(positions.into_iter().eq((0..scale.length).map(Some)));
+            }
+        }
+
+        #[test]
+        fn test_scale_categorical_reference(points in data_points()) {
+            assert_reference(points.clone(), ScaleKind::Categorical);
+            assert_reference(points, ScaleKind::Integer);
+        }
+    }
 }
@@ -0,0 +1,451 @@
+//! A versioned JSON format for persisting charts, enabled by the `serde`
+//! feature.
+//!
+//! Every chart is written within an envelope recording the version of the
+//! format and the kind of chart:
+//!
+//! ```json
+//! { "version": 1, "kind": "Line", "chart": { "lines": [], ... } }
+//! ```
+//!
+//! Reading ignores unknown fields, so files written by newer versions can be
+//! read as long as the version is supported. Fields and variants are named
+//! explicitly, so renaming them in code does not change the format. Fields
+//! added to charts must have a default, and the golden files within
+//! `dummies/models`, which are never regenerated, must continue to load.
+
+use std::{error, fmt};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{BarChart, ChartKind, LineGraph, StackedBarChart};
+
+/// The version of the format written by this crate.
+pub const MODEL_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    version: u32,
+    kind: ChartKind,
+    chart: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+    kind: ChartKind,
+}
+
+#[derive(Deserialize)]
+struct Body<T> {
+    chart: T,
+}
+
+/// Charts which can be written to and read from the versioned JSON format.
+pub trait ModelJson: Serialize + DeserializeOwned {
+    /// The kind recorded within the envelope.
+    const KIND: ChartKind;
+
+    /// Writes the chart, including any sections removed from it, as JSON.
+    fn to_model_json(&self) -> Result<String, ModelError> {
+        let envelope = Envelope {
+            version: MODEL_VERSION,
+            kind: Self::KIND,
+            chart: self,
+        };
+
+        serde_json::to_string(&envelope).map_err(ModelError::Json)
+    }
+
+    /// Reads a chart written by [`ModelJson::to_model_json`].
+    ///
+    /// Returns an error if the JSON was written by an unsupported version of
+    /// the format, holds a different kind of chart, or holds a chart which
+    /// fails [`ModelJson::validate`].
+    fn from_model_json(json: &str) -> Result<Self, ModelError> {
+        let header: Header = serde_json::from_str(json).map_err(ModelError::Json)?;
+
+        if header.version == 0 || header.version > MODEL_VERSION {
+            return Err(ModelError::UnsupportedVersion(header.version));
+        }
+
+        if header.kind != Self::KIND {
+            return Err(ModelError::WrongKind {
+                expected: Self::KIND,
+                found: header.kind,
+            });
+        }
+
+        let body: Body<Self> = serde_json::from_str(json).map_err(ModelError::Json)?;
+        body.chart.validate()?;
+
+        Ok(body.chart)
+    }
+
+    /// Checks the chart as its constructor would, returning an error if a
+    /// point is not on the scale of its axis.
+    fn validate(&self) -> Result<(), ModelError>;
+}
+
+impl ModelJson for LineGraph {
+    const KIND: ChartKind = ChartKind::Line;
+
+    fn validate(&self) -> Result<(), ModelError> {
+        self.assert_scales()
+            .map_err(|error| ModelError::Invalid(error.to_string()))
+    }
+}
+
+impl ModelJson for BarChart {
+    const KIND: ChartKind = ChartKind::Bar;
+
+    fn validate(&self) -> Result<(), ModelError> {
+        self.assert_scales()
+            .map_err(|error| ModelError::Invalid(error.to_string()))
+    }
+}
+
+impl ModelJson for StackedBarChart {
+    const KIND: ChartKind = ChartKind::StackedBar;
+
+    fn validate(&self) -> Result<(), ModelError> {
+        self.assert_scales()
+            .map_err(|error| ModelError::Invalid(error.to_string()))
+    }
+}
+
+#[derive(Debug)]
+pub enum ModelError {
+    /// The JSON was malformed or did not match the format
+    Json(serde_json::Error),
+    /// The version of the format is not supported
+    UnsupportedVersion(u32),
+    /// The JSON holds a different kind of chart
+    WrongKind {
+        expected: ChartKind,
+        found: ChartKind,
+    },
+    /// The chart was read, but a point is not on the scale of its axis
+    Invalid(String),
+}
+
+impl fmt::Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(error) => error.fmt(f),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported chart model version {}", version)
+            }
+            Self::WrongKind { expected, found } => {
+                write!(
+                    f,
+                    "Expected a {:?} chart model, but had {:?}",
+                    expected, found
+                )
+            }
+            Self::Invalid(reason) => write!(f, "Invalid chart model: {}", reason),
+        }
+    }
+}
+
+impl error::Error for ModelError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{
+        models::{Bar, LabelTransform, Line, Point, ScaleKind},
+        repr::{
+            BarChartAxisLabelStrategy, BarChartBarLabels, Config, Data, HeaderStrategy,
+            LineGraphAxisLabelStrategy, LineLabelStrategy, RowSelector, ScaleOptions, Sheet,
+            StackedBarChartAxisLabelStrategy, TypesStrategy,
+        },
+    };
+
+    fn load(path: &str) -> Sheet {
+        let config = Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+
+        Sheet::with_config(config).unwrap()
+    }
+
+    fn line_graph() -> LineGraph {
+        load("./dummies/csv/air.csv")
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::Headers {
+                    y: "Passengers".into(),
+                },
+                LineLabelStrategy::FromCell(0),
+                RowSelector::default(),
+                HashSet::new(),
+            )
+            .unwrap()
+    }
+
+    fn bar_chart() -> BarChart {
+        load("./dummies/csv/air.csv")
+            .create_bar_chart(
+                0,
+                1,
+                BarChartBarLabels::FromColumn(0),
+                BarChartAxisLabelStrategy::Headers,
+                RowSelector::default(),
+            )
+            .unwrap()
+    }
+
+    fn stacked_bar_chart() -> StackedBarChart {
+        let mut chart = load("./dummies/csv/stacked_neg.csv")
+            .create_stacked_bar_chart(
+                0,
+                [1, 2, 3, 4],
+                StackedBarChartAxisLabelStrategy::Header("Sales".into()),
+                RowSelector::default(),
+            )
+            .unwrap();
+        chart.transform_labels(&LabelTransform::Lowercase);
+        chart.remove_section(1, "coffee");
+        chart
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let line = line_graph();
+        let json = line.to_model_json().unwrap();
+        assert_eq!(LineGraph::from_model_json(&json).unwrap(), line);
+
+        let mut bar = bar_chart();
+        let json = bar.to_model_json().unwrap();
+        assert_eq!(BarChart::from_model_json(&json).unwrap(), bar);
+        bar.bars[2].weight = Some(Data::Float(0.5));
+        let json = bar.to_model_json().unwrap();
+        assert_eq!(BarChart::from_model_json(&json).unwrap(), bar);
+
+        let stacked = stacked_bar_chart();
+        let json = stacked.to_model_json().unwrap();
+        let mut read = StackedBarChart::from_model_json(&json).unwrap();
+        assert_eq!(read, stacked);
+
+        // Removed sections survive, so they can be re-added
+        assert_eq!(read.bars[1].point.y, Data::Integer(9));
+        read.add_section(1, "coffee");
+        assert_eq!(read.bars[1].point.y, Data::Integer(19));
+    }
+
+    #[test]
+    fn test_clamped_round_trip() {
+        let options = || ScaleOptions::new().clamp_percentiles(5.0, 95.0);
+
+        let line = load("./dummies/csv/air.csv")
+            .create_line_graph_labelled_with(
+                LineGraphAxisLabelStrategy::Headers {
+                    y: "Passengers".into(),
+                },
+                LineLabelStrategy::FromCell(0),
+                RowSelector::default(),
+                HashSet::new(),
+                options(),
+            )
+            .unwrap();
+        let view = line.y_view.as_ref().unwrap();
+        assert!(line
+            .lines
+            .iter()
+            .flat_map(|line| line.points.iter())
+            .any(|point| !view.contains(&point.y)));
+
+        let json = line.to_model_json().unwrap();
+        assert_eq!(LineGraph::from_model_json(&json).unwrap(), line);
+
+        let bar = load("./dummies/csv/air.csv")
+            .create_bar_chart_with(
+                0,
+                1,
+                BarChartBarLabels::FromColumn(0),
+                BarChartAxisLabelStrategy::Headers,
+                RowSelector::default(),
+                options(),
+            )
+            .unwrap();
+        assert!(bar.y_view.is_some());
+
+        let json = bar.to_model_json().unwrap();
+        assert_eq!(BarChart::from_model_json(&json).unwrap(), bar);
+    }
+
+    /// The golden files were written by version 1 of the format and are never
+    /// regenerated, so their expected contents are spelled out here rather
+    /// than computed by the current conversions.
+    #[test]
+    fn test_golden_files() {
+        let read =
+            |name: &str| std::fs::read_to_string(format!("./dummies/models/{}", name)).unwrap();
+        let text = |value: &str| Data::Text(value.into());
+        let months = [
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ];
+
+        let line = LineGraph::from_model_json(&read("line_v1.json")).unwrap();
+        let passengers = [
+            [340, 360, 417],
+            [318, 342, 391],
+            [362, 406, 419],
+            [348, 396, 461],
+            [363, 420, 472],
+            [435, 472, 535],
+            [491, 548, 622],
+            [505, 559, 606],
+            [404, 463, 508],
+            [359, 407, 461],
+            [310, 362, 390],
+            [337, 405, 432],
+        ];
+        let lines = months
+            .iter()
+            .zip(passengers)
+            .map(|(month, ys)| {
+                Line::new(["1958", "1959", "1960"].map(text).into_iter().zip(ys)).label(*month)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(line.lines, lines);
+        assert_eq!(line.x_label, "Month");
+        assert_eq!(line.y_label, "Passengers");
+        assert_eq!(
+            line.x_scale.points(),
+            vec![text("1958"), text("1959"), text("1960")]
+        );
+        assert_eq!(line.y_scale.kind(), ScaleKind::Integer);
+        assert_eq!(line.y_scale.start(), Some(Data::Integer(310)));
+        assert_eq!(line.y_scale.step(), Some(Data::Integer(10)));
+        assert_eq!(line.y_scale.length, 33);
+        assert!(!line.y_scale.is_descending());
+        assert!(line.annotations.is_empty());
+
+        let bar = BarChart::from_model_json(&read("bar_v1.json")).unwrap();
+        let ys = [340, 318, 362, 348, 363, 435, 491, 505, 404, 359, 310, 337];
+        let bars = months
+            .iter()
+            .zip(ys)
+            .map(|(month, y)| Bar::new(*month, Point::new(text(month), Data::Integer(y))))
+            .collect::<Vec<_>>();
+        assert_eq!(bar.bars, bars);
+        assert_eq!(bar.x_label.as_deref(), Some("Month"));
+        assert_eq!(bar.y_label.as_deref(), Some("1958"));
+        assert_eq!(
+            bar.x_scale.points(),
+            months.iter().map(|month| text(month)).collect::<Vec<_>>()
+        );
+        assert_eq!(bar.y_scale.kind(), ScaleKind::Integer);
+        assert_eq!(bar.y_scale.start(), Some(Data::Integer(310)));
+        assert_eq!(bar.y_scale.step(), Some(Data::Integer(17)));
+        assert_eq!(bar.y_scale.length, 13);
+
+        let stacked = StackedBarChart::from_model_json(&read("stacked_bar_v1.json")).unwrap();
+        let bars = stacked
+            .bars
+            .iter()
+            .map(|bar| (bar.point.clone(), bar.is_negative, bar.group))
+            .collect::<Vec<_>>();
+        let expected = [
+            ("Monday", 0, false, 0),
+            ("Tuesday", 9, false, 1),
+            ("Tuesday", -10, true, 1),
+            ("Wednesday", 14, false, 2),
+            ("Thursday", -18, true, 3),
+            ("Friday", 9, false, 4),
+            ("Friday", -15, true, 4),
+            ("Saturday", 11, false, 5),
+            ("Sunday", 22, false, 6),
+        ]
+        .map(|(x, y, negative, group)| (Point::new(text(x), Data::Integer(y)), negative, group));
+        assert_eq!(bars, expected);
+        assert_eq!(
+            stacked.labels,
+            HashSet::from(["ice cream", "chocolate", "coffee", "soda"].map(String::from))
+        );
+        assert_eq!(stacked.x_axis.as_deref(), Some("Day of Week"));
+        assert_eq!(stacked.y_axis.as_deref(), Some("Sales"));
+        assert_eq!(stacked.y_scale.start(), Some(Data::Integer(-18)));
+        assert_eq!(stacked.y_scale.step(), Some(Data::Integer(5)));
+        assert_eq!(stacked.y_scale.length, 9);
+
+        // Exact section values postdate version 1, so are read as missing
+        let tuesday = &stacked.bars[1];
+        assert_eq!(tuesday.fraction("soda"), Some(0.15789473684210525));
+        assert_eq!(tuesday.section_exact("soda"), None);
+
+        // The removed section is kept, so it can be re-added
+        let mut stacked = stacked;
+        stacked.add_section(1, "coffee");
+        assert_eq!(stacked.bars[1].point.y, Data::Integer(19));
+    }
+
+    #[test]
+    fn test_envelope() {
+        let json = bar_chart().to_model_json().unwrap();
+
+        assert!(matches!(
+            LineGraph::from_model_json(&json),
+            Err(ModelError::WrongKind {
+                expected: ChartKind::Line,
+                found: ChartKind::Bar
+            })
+        ));
+
+        let future = json.replacen("\"version\":1", "\"version\":2", 1);
+        assert!(matches!(
+            BarChart::from_model_json(&future),
+            Err(ModelError::UnsupportedVersion(2))
+        ));
+
+        // Unknown fields are ignored
+        let extra = json.replacen("{", "{\"written_by\":\"modav 9.0\",", 2);
+        assert_eq!(BarChart::from_model_json(&extra).unwrap(), bar_chart());
+
+        assert!(matches!(
+            BarChart::from_model_json("{\"version\":1}"),
+            Err(ModelError::Json(_))
+        ));
+    }
+
+    /// Charts read from a file are checked as their constructors would check
+    /// them, so a file edited by hand cannot hold points off their scales.
+    #[test]
+    fn test_tampered_files() {
+        let read =
+            |name: &str| std::fs::read_to_string(format!("./dummies/models/{}", name)).unwrap();
+
+        let bar = read("bar_v1.json").replacen("\"Integer\": 340", "\"Integer\": 9000", 1);
+        assert!(matches!(
+            BarChart::from_model_json(&bar),
+            Err(ModelError::Invalid(_))
+        ));
+
+        let line = read("line_v1.json").replacen("\"Text\": \"1958\"", "\"Text\": \"1957\"", 1);
+        assert!(matches!(
+            LineGraph::from_model_json(&line),
+            Err(ModelError::Invalid(_))
+        ));
+
+        let stacked = read("stacked_bar_v1.json").replacen(
+            "\"Text\": \"Monday\"",
+            "\"Text\": \"Someday\"",
+            1,
+        );
+        assert!(matches!(
+            StackedBarChart::from_model_json(&stacked),
+            Err(ModelError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::{self, Debug};
+
+use super::Scale;
+use crate::repr::Data;
+
+/// A grid of values, with labelled rows and columns.
+///
+/// Values are stored in row-major order. Empty cells are kept as
+/// [`Data::None`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HeatMap {
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
+    values: Vec<Data>,
+    /// The scale of the color axis
+    pub scale: Scale,
+}
+
+impl HeatMap {
+    pub fn new(
+        row_labels: Vec<String>,
+        col_labels: Vec<String>,
+        values: Vec<Data>,
+        scale: Scale,
+    ) -> Result<Self, HeatMapError> {
+        if row_labels.len() * col_labels.len() != values.len() {
+            return Err(HeatMapError::InvalidDimensions(format!(
+                "Expected {} values for {} rows and {} columns, but had {}",
+                row_labels.len() * col_labels.len(),
+                row_labels.len(),
+                col_labels.len(),
+                values.len()
+            )));
+        }
+
+        Self::assert_scale(&scale, &values)?;
+
+        Ok(Self {
+            row_labels,
+            col_labels,
+            values,
+            scale,
+        })
+    }
+
+    fn assert_scale(scale: &Scale, values: &[Data]) -> Result<(), HeatMapError> {
+        for value in values.iter().filter(|value| **value != Data::None) {
+            if !scale.contains(value) {
+                return Err(HeatMapError::OutOfRange(value.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value at row `row` and column `col`, if any.
+    pub fn get(&self, row: usize, col: usize) -> Option<&Data> {
+        if row >= self.height() || col >= self.width() {
+            return None;
+        }
+
+        self.values.get(row * self.width() + col)
+    }
+
+    /// Returns the values of the [`HeatMap`] in row-major order.
+    pub fn values(&self) -> &[Data] {
+        &self.values
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.col_labels.len()
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.row_labels.len()
+    }
+
+    /// Swaps the rows and columns of the [`HeatMap`].
+    pub fn transpose(&mut self) {
+        let (width, height) = (self.width(), self.height());
+
+        let mut values = Vec::with_capacity(self.values.len());
+        for col in 0..width {
+            for row in 0..height {
+                values.push(std::mem::take(&mut self.values[row * width + col]));
+            }
+        }
+
+        self.values = values;
+        std::mem::swap(&mut self.row_labels, &mut self.col_labels);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeatMapError {
+    InvalidDimensions(String),
+    OutOfRange(String),
+}
+
+impl fmt::Display for HeatMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeatMapError::InvalidDimensions(s) => write!(f, "Invalid dimensions: {}", s),
+            HeatMapError::OutOfRange(val) => {
+                write!(f, "The value {} is out of range of the scale", val)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeatMapError {}
+
+#[cfg(test)]
+mod heatmap_tests {
+    use super::*;
+
+    fn create_heatmap() -> HeatMap {
+        let rows = vec!["a".to_string(), "b".to_string()];
+        let cols = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+        let values = vec![
+            Data::Integer(1),
+            Data::Integer(2),
+            Data::Integer(3),
+            Data::Integer(4),
+            Data::None,
+            Data::Integer(6),
+        ];
+        let scale = Scale::from(vec![1, 2, 3, 4, 6]);
+
+        HeatMap::new(rows, cols, values, scale).unwrap()
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let mut heatmap = create_heatmap();
+
+        assert_eq!(heatmap.get(0, 2), Some(&Data::Integer(3)));
+        assert_eq!(heatmap.get(1, 1), Some(&Data::None));
+        assert_eq!(heatmap.get(2, 0), None);
+        assert_eq!(heatmap.get(0, 3), None);
+
+        heatmap.transpose();
+
+        assert_eq!(heatmap.width(), 2);
+        assert_eq!(heatmap.height(), 3);
+        assert_eq!(heatmap.row_labels, vec!["x", "y", "z"]);
+        assert_eq!(heatmap.col_labels, vec!["a", "b"]);
+        assert_eq!(heatmap.get(2, 0), Some(&Data::Integer(3)));
+        assert_eq!(heatmap.get(1, 1), Some(&Data::None));
+        assert_eq!(heatmap.get(2, 1), Some(&Data::Integer(6)));
+
+        heatmap.transpose();
+        assert_eq!(heatmap, create_heatmap());
+    }
+
+    #[test]
+    fn test_faulty_heatmap() {
+        let rows = vec!["a".to_string()];
+        let cols = vec!["x".to_string(), "y".to_string()];
+
+        let res = HeatMap::new(
+            rows.clone(),
+            cols.clone(),
+            vec![Data::Integer(1)],
+            Scale::from(vec![1]),
+        );
+        assert!(matches!(res, Err(HeatMapError::InvalidDimensions(_))));
+
+        let res = HeatMap::new(
+            rows,
+            cols,
+            vec![Data::Integer(1), Data::Integer(5)],
+            Scale::from(vec![1, 2]),
+        );
+        assert_eq!(res, Err(HeatMapError::OutOfRange("5".into())));
+    }
+}
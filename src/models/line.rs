@@ -1,12 +1,18 @@
 use crate::repr::Data;
-use std::fmt::Debug;
-pub use utils::*;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
 
-use super::{Point, Scale};
+use super::{
+    duplicate_labels, fit_annotation, Annotation, LabelTransform, Point, Scale, ScaleBuilder,
+    ScaleKind,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Line {
+    #[cfg_attr(feature = "serde", serde(rename = "points"))]
     pub points: Vec<Point<Data, Data>>,
+    #[cfg_attr(feature = "serde", serde(rename = "label"))]
     pub label: Option<String>,
 }
 
@@ -32,15 +38,47 @@ impl Line {
         self.label = Some(label.into());
         self
     }
+
+    /// Keeps only the points for which `pred` returns true, without
+    /// reallocating.
+    pub fn retain_points(&mut self, pred: impl FnMut(&Point<Data, Data>) -> bool) {
+        self.points.retain(pred);
+    }
+
+    /// Returns a pointer to the allocation holding the points of the line.
+    #[cfg(test)]
+    pub(crate) fn points_ptr(&self) -> *const Point<Data, Data> {
+        self.points.as_ptr()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineGraph {
+    #[cfg_attr(feature = "serde", serde(rename = "lines"))]
     pub lines: Vec<Line>,
+    #[cfg_attr(feature = "serde", serde(rename = "x_label"))]
     pub x_label: String,
+    #[cfg_attr(feature = "serde", serde(rename = "y_label"))]
     pub y_label: String,
+    #[cfg_attr(feature = "serde", serde(rename = "x_scale"))]
     pub x_scale: Scale,
+    #[cfg_attr(feature = "serde", serde(rename = "y_scale"))]
     pub y_scale: Scale,
+    /// The part of the y scale to show, leaving outliers off it. Every
+    /// point lies on `y_scale`, though not necessarily on the view. See
+    /// [`ScaleOptions::clamp_percentiles`](crate::repr::ScaleOptions::clamp_percentiles)
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "y_view", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub y_view: Option<Scale>,
+    /// Marks over the graph. See [`LineGraph::annotate`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "annotations", default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub annotations: Vec<Annotation>,
 }
 
 #[allow(dead_code)]
@@ -66,16 +104,191 @@ impl LineGraph {
             y_label,
             x_scale,
             y_scale,
+            y_view: None,
+            annotations: Vec::new(),
         })
     }
 
+    /// Returns the y scale to show, which is `y_view` if set and `y_scale`
+    /// otherwise.
+    pub fn shown_y_scale(&self) -> &Scale {
+        self.y_view.as_ref().unwrap_or(&self.y_scale)
+    }
+
+    /// Returns a new line graph of `lines`, with scales of `x_kind` and
+    /// `y_kind` built while the lines are read rather than after they are
+    /// collected.
+    ///
+    /// The scales are those [`Scale::new`] builds over the points of all the
+    /// lines. See [`ScaleBuilder`].
+    pub fn from_lines_with_kinds(
+        lines: impl IntoIterator<Item = Line>,
+        x_label: Option<String>,
+        y_label: Option<String>,
+        x_kind: ScaleKind,
+        y_kind: ScaleKind,
+    ) -> Result<Self, LineGraphError> {
+        let mut x_scale = ScaleBuilder::new(x_kind);
+        let mut y_scale = ScaleBuilder::new(y_kind);
+
+        let lines = lines
+            .into_iter()
+            .inspect(|line| {
+                for point in line.points.iter() {
+                    x_scale.push(point.x.clone());
+                    y_scale.push(point.y.clone());
+                }
+            })
+            .collect();
+
+        Self::new(lines, x_label, y_label, x_scale.build(), y_scale.build())
+    }
+
+    /// Adds `annotation` to the graph. Returns an error if a value of the
+    /// annotation is not on the scale of its axis, unless `extend` is true
+    /// and the scale extends to it. See [`Scale::extend_to`].
+    pub fn annotate(&mut self, annotation: Annotation, extend: bool) -> Result<(), LineGraphError> {
+        fit_annotation(
+            &annotation,
+            &mut self.x_scale,
+            &mut self.y_scale,
+            self.y_view.as_mut(),
+            extend,
+        )
+        .map_err(|(axis, value)| LineGraphError::OutOfRange {
+            axis: axis.to_string(),
+            line: None,
+            value: value.to_string(),
+        })?;
+        self.annotations.push(annotation);
+
+        Ok(())
+    }
+
+    /// Returns the graph with `annotation` added. See [`LineGraph::annotate`].
+    pub fn with_annotation(
+        mut self,
+        annotation: Annotation,
+        extend: bool,
+    ) -> Result<Self, LineGraphError> {
+        self.annotate(annotation, extend)?;
+        Ok(self)
+    }
+
+    /// Returns true if no line of the graph has any points.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.points.is_empty())
+    }
+
+    /// Applies `transform` to the label of every line.
+    ///
+    /// Returns the labels shared by more than one line after the transform.
+    pub fn transform_labels(&mut self, transform: &LabelTransform) -> Vec<String> {
+        for line in self.lines.iter_mut() {
+            if let Some(label) = line.label.as_mut() {
+                *label = transform.apply(label);
+            }
+        }
+
+        self.duplicate_labels()
+    }
+
+    /// Returns the labels shared by more than one line, in order of first
+    /// appearance.
+    pub fn duplicate_labels(&self) -> Vec<String> {
+        duplicate_labels(self.lines.iter().filter_map(|line| line.label.as_ref()))
+    }
+
+    /// Applies `f` to every point of every line in place, reusing the existing
+    /// allocations. `f` receives the index of the line along with the point.
+    ///
+    /// A scale which no longer contains every point is rebuilt from the new
+    /// points with its kind and direction kept. Other scales are left unchanged.
+    /// Rebuilding the y scale clears `y_view`, as the clamp it was built from
+    /// no longer applies, so that the whole rebuilt scale is shown.
+    pub fn map_points_in_place(&mut self, mut f: impl FnMut(usize, &mut Point<Data, Data>)) {
+        for (idx, line) in self.lines.iter_mut().enumerate() {
+            for point in line.points.iter_mut() {
+                f(idx, point);
+            }
+        }
+
+        if Self::assert_x_scale(&self.x_scale, &self.lines).is_err() {
+            let values = self
+                .lines
+                .iter()
+                .flat_map(|line| line.points.iter().map(|point| point.x.clone()));
+            let descending = self.x_scale.is_descending();
+            self.x_scale = Scale::new(values, self.x_scale.kind);
+            if descending {
+                self.x_scale.invert();
+            }
+        }
+
+        if Self::assert_y_scale(&self.y_scale, &self.lines).is_err() {
+            let values = self
+                .lines
+                .iter()
+                .flat_map(|line| line.points.iter().map(|point| point.y.clone()));
+            let descending = self.y_scale.is_descending();
+            self.y_scale = Scale::new(values, self.y_scale.kind);
+            if descending {
+                self.y_scale.invert();
+            }
+            self.y_view = None;
+        }
+    }
+
+    /// Keeps only the lines for which `pred` returns true. The scales are
+    /// left unchanged.
+    pub fn retain_lines(&mut self, pred: impl FnMut(&Line) -> bool) {
+        self.lines.retain(pred);
+    }
+
+    /// Sorts the lines by their labels, with unlabelled lines last. The sort
+    /// is stable and the scales are left unchanged.
+    pub fn sort_lines_by_label(&mut self) {
+        self.lines
+            .sort_by(|a, b| match (a.label.as_ref(), b.label.as_ref()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+    }
+
+    /// Sorts the lines with the comparator `f`. The sort is stable and the
+    /// scales are left unchanged.
+    pub fn sort_lines_by(&mut self, f: impl FnMut(&Line, &Line) -> Ordering) {
+        self.lines.sort_by(f);
+    }
+
+    /// Moves the line at `from` to `to`, shifting the lines in between. No
+    /// move occurs if either index is out of range.
+    pub fn move_line(&mut self, from: usize, to: usize) {
+        if from < self.lines.len() && to < self.lines.len() {
+            let line = self.lines.remove(from);
+            self.lines.insert(to, line);
+        }
+    }
+
+    /// Checks that every point of the chart is on the scales, as
+    /// [`LineGraph::new`] does.
+    #[cfg(feature = "serde")]
+    pub(crate) fn assert_scales(&self) -> Result<(), LineGraphError> {
+        Self::assert_x_scale(&self.x_scale, &self.lines)?;
+        Self::assert_y_scale(&self.y_scale, &self.lines)
+    }
+
     fn assert_x_scale(scale: &Scale, lines: &[Line]) -> Result<(), LineGraphError> {
-        for x in lines
-            .iter()
-            .flat_map(|line| line.points.iter().map(|point| &point.x))
-        {
-            if !scale.contains(x) {
-                return Err(LineGraphError::OutOfRange("X".into(), x.to_string()));
+        let contains = scale.contains_fn();
+        for line in lines {
+            if let Some(point) = line.points.iter().find(|point| !contains(&point.x)) {
+                return Err(LineGraphError::OutOfRange {
+                    axis: "X".into(),
+                    line: line.label.clone(),
+                    value: point.x.to_string(),
+                });
             }
         }
 
@@ -83,12 +296,14 @@ impl LineGraph {
     }
 
     fn assert_y_scale(scale: &Scale, lines: &[Line]) -> Result<(), LineGraphError> {
-        for y in lines
-            .iter()
-            .flat_map(|line| line.points.iter().map(|point| &point.y))
-        {
-            if !scale.contains(y) {
-                return Err(LineGraphError::OutOfRange("Y".into(), y.to_string()));
+        let contains = scale.contains_fn();
+        for line in lines {
+            if let Some(point) = line.points.iter().find(|point| !contains(&point.y)) {
+                return Err(LineGraphError::OutOfRange {
+                    axis: "Y".into(),
+                    line: line.label.clone(),
+                    value: point.y.to_string(),
+                });
             }
         }
 
@@ -96,38 +311,53 @@ impl LineGraph {
     }
 }
 
-pub mod utils {
-    use std::fmt;
-
-    #[derive(Debug, Clone, PartialEq)]
-    pub enum LineGraphError {
-        OutOfRange(String, String),
-        ScaleLengthError(String),
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineGraphError {
+    /// A point of a line does not fall within the scale of an axis.
+    OutOfRange {
+        /// The axis whose scale does not contain the point
+        axis: String,
+        /// The label of the line containing the point, if any
+        line: Option<String>,
+        /// The offending value
+        value: String,
+    },
+    ScaleLengthError(String),
+}
 
-    impl fmt::Display for LineGraphError {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            match self {
-                LineGraphError::ScaleLengthError(sc) => {
-                    write!(f, "The {} axis has smaller scale than expected", sc)
-                }
-                LineGraphError::OutOfRange(sc, val) => {
-                    write!(
-                        f,
-                        "The point with value {} on the {} axis is out of range",
-                        val, sc
-                    )
+impl fmt::Display for LineGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineGraphError::ScaleLengthError(sc) => {
+                write!(f, "The {} axis has smaller scale than expected", sc)
+            }
+            LineGraphError::OutOfRange { axis, line, value } => {
+                write!(
+                    f,
+                    "The point with value {} on the {} axis is out of range",
+                    value, axis
+                )?;
+
+                match line {
+                    Some(line) => write!(f, " in line {}", line),
+                    None => Ok(()),
                 }
             }
         }
     }
+}
+
+impl std::error::Error for LineGraphError {}
 
-    impl std::error::Error for LineGraphError {}
+/// Kept so that paths through `line::utils` continue to work.
+#[doc(hidden)]
+pub mod utils {
+    pub use super::LineGraphError;
 }
 
 #[cfg(test)]
 mod line_tests {
-    use super::super::common::ScaleKind;
+    use super::super::common::{Axis, ScaleKind};
     use super::*;
 
     fn create_point<X, Y>(x: X, y: Y) -> Point<X, Y> {
@@ -210,6 +440,90 @@ mod line_tests {
         LineGraph::new(vec![l1, l2], None, None, x_scale, y_scale)
     }
 
+    #[test]
+    fn test_in_place() {
+        let l1 = Line::new(vec![("a", 1.0), ("b", 4.0), ("c", 1.0)]).label("First");
+        let l2 = Line::new(vec![("a", 2.0), ("b", 2.0), ("c", 8.0)]).label("Second");
+        let x_scale = Scale::new(vec!["a", "b", "c"], ScaleKind::Categorical);
+        let y_scale = Scale::new(vec![1.0, 2.0, 4.0, 8.0], ScaleKind::Float);
+
+        let mut graph = LineGraph::new(vec![l1, l2], None, None, x_scale, y_scale).unwrap();
+        graph.y_view = Some(Scale::new(vec![1.0, 4.0], ScaleKind::Float));
+        let x_scale = graph.x_scale.clone();
+
+        let ptrs = graph
+            .lines
+            .iter()
+            .map(|line| line.points_ptr())
+            .collect::<Vec<_>>();
+        let x_ptr = |graph: &LineGraph| match &graph.lines[0].points[1].x {
+            Data::Text(x) => x.as_ptr(),
+            _ => panic!("Expected text"),
+        };
+        let x = x_ptr(&graph);
+
+        // Moving average over the neighbours of each point
+        let smoothed = graph
+            .lines
+            .iter()
+            .map(|line| {
+                let ys = line
+                    .points
+                    .iter()
+                    .map(|point| match point.y {
+                        Data::Float(y) => y,
+                        _ => panic!("Expected float"),
+                    })
+                    .collect::<Vec<f32>>();
+
+                (0..ys.len())
+                    .map(|i| {
+                        let window = &ys[i.saturating_sub(1)..(i + 2).min(ys.len())];
+                        window.iter().sum::<f32>() / window.len() as f32
+                    })
+                    .collect::<Vec<f32>>()
+            })
+            .collect::<Vec<Vec<f32>>>();
+
+        let mut positions = vec![0; smoothed.len()];
+        graph.map_points_in_place(|line, point| {
+            point.y = Data::Float(smoothed[line][positions[line]]);
+            positions[line] += 1;
+        });
+
+        assert_eq!(graph.lines[0].points[0].y, Data::Float(2.5));
+        assert_eq!(graph.lines[1].points[1].y, Data::Float(4.0));
+        assert_eq!(x_ptr(&graph), x);
+        assert_eq!(
+            graph
+                .lines
+                .iter()
+                .map(|line| line.points_ptr())
+                .collect::<Vec<_>>(),
+            ptrs
+        );
+        assert_eq!(graph.x_scale, x_scale);
+        assert!(graph.y_scale.contains(&Data::Float(5.0)));
+        assert!(graph.y_view.is_some());
+
+        // Rebuilding the y scale clears the view
+        graph.map_points_in_place(|_, point| {
+            if point.y == Data::Float(4.0) {
+                point.y = Data::Float(16.0);
+            }
+        });
+        assert!(graph.y_scale.contains(&Data::Float(16.0)));
+        assert_eq!(graph.y_view, None);
+
+        graph.lines[1].retain_points(|point| point.x != Data::from("b"));
+        assert_eq!(graph.lines[1].points.len(), 2);
+        assert_eq!(graph.lines[1].points_ptr(), ptrs[1]);
+
+        graph.retain_lines(|line| line.label.as_deref() == Some("Second"));
+        assert_eq!(graph.lines.len(), 1);
+        assert_eq!(graph.lines[0].points_ptr(), ptrs[1]);
+    }
+
     #[test]
     fn test_line_line() {
         let pts = vec!["one", "two", "three"];
@@ -240,10 +554,156 @@ mod line_tests {
 
     #[test]
     fn test_faulty_graph() {
-        let expected = LineGraphError::OutOfRange(String::from("X"), String::from("20"));
+        let expected = LineGraphError::OutOfRange {
+            axis: String::from("X"),
+            line: None,
+            value: String::from("20"),
+        };
         match faulty_graph1() {
             Ok(_) => panic!("Should not reach this test case"),
             Err(e) => assert_eq!(e, expected),
         }
+
+        let line = Line::new(vec![(0, 5), (1, 50)]).label("Faulty");
+        let x_scale = Scale::new(0..2, ScaleKind::Integer);
+        let y_scale = Scale::new(0..10, ScaleKind::Integer);
+
+        let expected = LineGraphError::OutOfRange {
+            axis: String::from("Y"),
+            line: Some(String::from("Faulty")),
+            value: String::from("50"),
+        };
+        match LineGraph::new(vec![line], None, None, x_scale, y_scale) {
+            Ok(_) => panic!("Should not reach this test case"),
+            Err(e) => assert_eq!(e, expected),
+        }
+    }
+
+    #[test]
+    fn test_reorder_lines() {
+        let mut graph = create_graph();
+        graph.lines.push(Line::new(vec![(1, "one")]));
+        graph
+            .lines
+            .push(Line::new(vec![(2, "two")]).label("Castellano"));
+        let legend = |graph: &LineGraph| {
+            graph
+                .lines
+                .iter()
+                .map(|line| line.label.clone())
+                .collect::<Vec<_>>()
+        };
+        let x_points = graph.x_scale.points();
+
+        graph.sort_lines_by_label();
+        assert_eq!(
+            legend(&graph),
+            vec![
+                Some("Castellano".into()),
+                Some("Deutsch".into()),
+                Some("English".into()),
+                None
+            ]
+        );
+
+        graph.move_line(3, 0);
+        assert_eq!(graph.lines[0].label, None);
+        assert_eq!(graph.lines[1].label.as_deref(), Some("Castellano"));
+
+        graph.move_line(0, 4);
+        assert_eq!(graph.lines[0].label, None);
+
+        graph.sort_lines_by(|a, b| b.points.len().cmp(&a.points.len()));
+        assert_eq!(
+            legend(&graph),
+            vec![
+                Some("Deutsch".into()),
+                Some("English".into()),
+                None,
+                Some("Castellano".into())
+            ]
+        );
+        assert_eq!(graph.x_scale.points(), x_points);
+    }
+
+    #[test]
+    fn test_from_lines_with_kinds() {
+        let lines = vec![
+            create_line_from_new(vec![(10, "one"), (20, "two")], "Deutsch"),
+            create_line_from_points(vec!["one", "three", "four"], "English"),
+        ];
+        let points = || lines.iter().flat_map(|line| line.points.iter());
+
+        let graph = LineGraph::from_lines_with_kinds(
+            lines.clone(),
+            Some("Number".into()),
+            None,
+            ScaleKind::Number,
+            ScaleKind::Categorical,
+        )
+        .unwrap();
+
+        assert_eq!(graph.lines, lines);
+        assert_eq!(graph.x_label, "Number");
+        assert_eq!(graph.y_label, "");
+        assert_eq!(
+            graph.x_scale,
+            Scale::new(points().map(|point| point.x.clone()), ScaleKind::Number)
+        );
+        assert_eq!(
+            graph.y_scale,
+            Scale::new(
+                points().map(|point| point.y.clone()),
+                ScaleKind::Categorical
+            )
+        );
+    }
+
+    #[test]
+    fn test_annotations() {
+        let mut graph = create_graph();
+        let deadline = Annotation::VLine {
+            x: Data::Number(75),
+        };
+
+        match graph.annotate(deadline.clone(), false) {
+            Err(LineGraphError::OutOfRange { axis, line, value }) => {
+                assert_eq!((axis.as_str(), line, value.as_str()), ("X", None, "75"))
+            }
+            res => panic!("Expected an out of range error, got {:?}", res),
+        }
+        assert!(graph.annotations.is_empty());
+
+        graph.annotate(deadline, true).unwrap();
+        assert!(graph.x_scale.contains(&Data::Number(75)));
+        assert!(graph.x_scale.contains(&Data::Number(0)));
+
+        // Extending a categorical scale adds a category
+        let hline = Annotation::HLine {
+            y: Data::from("six"),
+            label: None,
+        };
+        let graph = graph.with_annotation(hline, true).unwrap();
+        assert_eq!(graph.y_scale.points().last(), Some(&Data::from("six")));
+        assert_eq!(graph.annotations.len(), 2);
+
+        // A clamped graph shows annotations past its view
+        let line = Line::new(vec![("a", 1.0), ("b", 2.0), ("c", 40.0)]);
+        let x_scale = Scale::new(vec!["a", "b", "c"], ScaleKind::Categorical);
+        let y_scale = Scale::new(vec![1.0, 40.0], ScaleKind::Float);
+        let mut clamped = LineGraph::new(vec![line], None, None, x_scale, y_scale).unwrap();
+        clamped.y_view = Some(Scale::new(vec![1.0, 2.0], ScaleKind::Float));
+        let band = Annotation::Band {
+            from: Data::Float(1.5),
+            to: Data::Float(30.0),
+            axis: Axis::Y,
+        };
+        clamped.annotate(band, false).unwrap();
+        assert_eq!(
+            clamped.y_scale,
+            Scale::new(vec![1.0, 40.0], ScaleKind::Float)
+        );
+        assert!(clamped.shown_y_scale().contains(&Data::Float(30.0)));
+        assert!(clamped.shown_y_scale().contains(&Data::Float(1.0)));
     }
 }
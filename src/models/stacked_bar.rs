@@ -1,34 +1,65 @@
 use std::{
     collections::{HashMap, HashSet},
     fmt::{self, Debug},
+    hash::{Hash, Hasher},
 };
 
-use super::{Point, Scale};
+use super::{
+    duplicate_labels, fit_annotation, Annotation, LabelTransform, Line, LineGraph, LineGraphError,
+    Point, Scale,
+};
 use crate::repr::Data;
 
-#[derive(Clone, Debug, PartialEq)]
+/// Fractions are compared and hashed bitwise, matching [`Data`]. The exact
+/// values of sections are left out of both, as bars read from version 1 of
+/// the model format do not have them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackedBar {
     /// The (x, y) points for the bar
+    #[cfg_attr(feature = "serde", serde(rename = "point"))]
     pub point: Point,
     /// The percentage makeup  of the bar. For all
     /// k, v in `fractions` v1 + v2 + v3 + .. = 1.0
+    ///
+    /// Each fraction is computed from the value of its section. See
+    /// [`StackedBar::section_exact`] for the values themselves.
+    #[cfg_attr(feature = "serde", serde(rename = "fractions"))]
     pub fractions: HashMap<String, f64>,
     /// Is true of all points within the bar are negative
+    #[cfg_attr(feature = "serde", serde(rename = "is_negative"))]
     pub is_negative: bool,
+    /// The position of the row the bar was created from. The positive and
+    /// negative bars created from the same row share a group
+    #[cfg_attr(feature = "serde", serde(rename = "group"))]
+    pub group: usize,
     /// The full value of the stacked bar
+    #[cfg_attr(feature = "serde", serde(rename = "true_y"))]
     true_y: Data,
+    /// The value each section contributes to the bar
+    #[cfg_attr(feature = "serde", serde(rename = "sections", default))]
+    sections: HashMap<String, Data>,
     /// Keeps track of sections removed from the bar
+    #[cfg_attr(feature = "serde", serde(rename = "removed_sections"))]
     removed_sections: HashSet<String>,
 }
 
 impl StackedBar {
-    pub(crate) fn new(point: Point, fractions: HashMap<String, f64>, is_negative: bool) -> Self {
+    /// Returns a new bar of `point`, made up of the values of `sections`.
+    pub(crate) fn new(point: Point, sections: HashMap<String, Data>, is_negative: bool) -> Self {
         let true_y = point.y.clone();
+        let fractions = sections
+            .iter()
+            .map(|(label, value)| (label.clone(), section_fraction(value, &true_y)))
+            .collect();
+
         Self {
             point,
             fractions,
             is_negative,
+            group: 0,
             true_y,
+            sections,
             removed_sections: HashSet::new(),
         }
     }
@@ -40,11 +71,19 @@ impl StackedBar {
             point,
             fractions: HashMap::default(),
             is_negative,
+            group: 0,
             true_y,
+            sections: HashMap::default(),
             removed_sections: HashSet::new(),
         }
     }
 
+    /// Sets the group of the bar.
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+
     pub fn restore(&mut self) {
         self.point.y = self.true_y.clone();
     }
@@ -57,6 +96,41 @@ impl StackedBar {
         &self.point
     }
 
+    /// Returns the value `section` contributes to the full bar, exactly as
+    /// it was charted. Unlike multiplying a fraction by the total, this
+    /// never goes through floating point arithmetic.
+    pub fn section_exact(&self, section: &str) -> Option<&Data> {
+        self.sections.get(section)
+    }
+
+    /// Returns true if `section` has been removed from the bar.
+    #[cfg(feature = "vega")]
+    pub(super) fn is_removed(&self, section: &str) -> bool {
+        self.removed_sections.contains(section)
+    }
+
+    /// Returns the value `section` contributes to the full bar, worked out
+    /// from its fraction if the exact value is unknown. See
+    /// [`StackedBar::section_exact`].
+    fn section_value(&self, section: &str) -> Option<Data> {
+        if let Some(value) = self.sections.get(section) {
+            return Some(value.clone());
+        }
+
+        let fraction = self.fractions.get(section)?;
+        let total = self.true_y.as_f64()?;
+        Some(Data::Float((total * fraction) as f32))
+    }
+
+    /// Returns the fraction of the full bar made up by `section`, computed
+    /// from its value.
+    pub fn fraction(&self, section: &str) -> Option<f64> {
+        match self.sections.get(section) {
+            Some(value) => Some(section_fraction(value, &self.true_y)),
+            None => self.fractions.get(section).copied(),
+        }
+    }
+
     /// Returns true if the point is empty. For a Stacked bar chart, an empty point
     /// is defined as one which has a y data value of 0 or 0.0
     pub(crate) fn is_empty(&self) -> bool {
@@ -68,33 +142,48 @@ impl StackedBar {
         }
     }
 
+    /// Applies `transform` to the section labels of the bar. The fractions of
+    /// sections which end up with the same label are combined.
+    fn transform_sections(&mut self, transform: &LabelTransform) {
+        let mut fractions = HashMap::with_capacity(self.fractions.len());
+
+        for (section, fraction) in self.fractions.drain() {
+            *fractions.entry(transform.apply(&section)).or_insert(0.0) += fraction;
+        }
+
+        // Sections whose combined value overflows keep only their fraction
+        let mut sections: HashMap<String, Option<Data>> =
+            HashMap::with_capacity(self.sections.len());
+        for (section, value) in self.sections.drain() {
+            let combined = match sections.remove(&transform.apply(&section)) {
+                Some(prev) => prev.and_then(|prev| checked_add(&prev, &value)),
+                None => Some(value),
+            };
+            sections.insert(transform.apply(&section), combined);
+        }
+
+        self.fractions = fractions;
+        self.sections = sections
+            .into_iter()
+            .filter_map(|(section, value)| Some((section, value?)))
+            .collect();
+        self.removed_sections = self
+            .removed_sections
+            .drain()
+            .map(|section| transform.apply(&section))
+            .collect();
+    }
+
     /// Effectively removes the contribution of specified section from the
     /// stacked bar if it exists
     pub fn remove_section(&mut self, section: impl Into<String>) {
         let section = section.into();
 
-        if self.removed_sections.contains(&section) {
+        if self.removed_sections.contains(&section) || !self.fractions.contains_key(&section) {
             return;
         }
 
-        let fraction = self.fractions.get(&section);
-
-        let Some(fraction) = fraction else { return };
-
-        let contribution = match self.true_y {
-            Data::Number(n) => (n as f64) * fraction,
-            Data::Integer(i) => (i as f64) * fraction,
-            Data::Float(f) => (f as f64) * fraction,
-            _ => 0.0,
-        };
-
-        match self.point.y {
-            Data::Number(n) => self.point.y = Data::Number(((n as f64) - contribution) as isize),
-            Data::Integer(i) => self.point.y = Data::Integer(((i as f64) - contribution) as i32),
-            Data::Float(f) => self.point.y = Data::Float(((f as f64) - contribution) as f32),
-            _ => {}
-        };
-
+        self.point.y = self.shifted(&section, false);
         self.removed_sections.insert(section);
     }
 
@@ -103,40 +192,158 @@ impl StackedBar {
     pub fn add_section(&mut self, section: impl Into<String>) {
         let section = section.into();
 
-        if !self.removed_sections.contains(&section) {
+        if !self.removed_sections.contains(&section) || !self.fractions.contains_key(&section) {
             return;
         }
 
-        let fraction = self.fractions.get(&section);
+        self.point.y = self.shifted(&section, true);
+        self.removed_sections.remove(&section);
+    }
 
-        let Some(fraction) = fraction else { return };
+    /// Returns the y value of the bar with the contribution of `section`
+    /// added, or taken away. The exact value of the section is used where
+    /// known, and its fraction of the full bar otherwise.
+    fn shifted(&self, section: &str, add: bool) -> Data {
+        let exact = self.sections.get(section).and_then(|value| match add {
+            true => checked_add(&self.point.y, value),
+            false => checked_sub(&self.point.y, value),
+        });
+        if let Some(exact) = exact {
+            return exact;
+        }
 
-        let contribution = match self.true_y {
-            Data::Number(n) => (n as f64) * fraction,
-            Data::Integer(i) => (i as f64) * fraction,
-            Data::Float(f) => (f as f64) * fraction,
-            _ => 0.0,
-        };
+        let fraction = self.fractions.get(section).copied().unwrap_or_default();
+        let contribution = self.true_y.as_f64().unwrap_or_default() * fraction;
+        let contribution = if add { contribution } else { -contribution };
 
         match self.point.y {
-            Data::Number(n) => self.point.y = Data::Number(((n as f64) + contribution) as isize),
-            Data::Integer(i) => self.point.y = Data::Integer(((i as f64) + contribution) as i32),
-            Data::Float(f) => self.point.y = Data::Float(((f as f64) + contribution) as f32),
-            _ => {}
+            Data::Number(n) => Data::Number(((n as f64) + contribution) as isize),
+            Data::Integer(i) => Data::Integer(((i as f64) + contribution) as i32),
+            Data::Float(f) => Data::Float(((f as f64) + contribution) as f32),
+            ref y => y.clone(),
         }
+    }
+}
 
-        self.removed_sections.remove(&section);
+/// Returns the fraction of `total` made up by `value`. A total of zero has
+/// fractions of 0.0, and a fraction is never negative zero.
+pub(crate) fn section_fraction(value: &Data, total: &Data) -> f64 {
+    let (Some(value), Some(total)) = (value.as_f64(), total.as_f64()) else {
+        return 0.0;
+    };
+
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    let fraction = value / total;
+    if fraction == 0.0 {
+        0.0
+    } else {
+        fraction
+    }
+}
+
+/// Returns the sum of `a` and `b` if they are the same numeric variant and
+/// the sum does not overflow.
+fn checked_add(a: &Data, b: &Data) -> Option<Data> {
+    match (a, b) {
+        (Data::Integer(a), Data::Integer(b)) => a.checked_add(*b).map(Data::Integer),
+        (Data::Number(a), Data::Number(b)) => a.checked_add(*b).map(Data::Number),
+        (Data::Float(a), Data::Float(b)) => Some(Data::Float(a + b)),
+        _ => None,
+    }
+}
+
+/// Returns `a` less `b` if they are the same numeric variant and the
+/// difference does not overflow.
+fn checked_sub(a: &Data, b: &Data) -> Option<Data> {
+    match (a, b) {
+        (Data::Integer(a), Data::Integer(b)) => a.checked_sub(*b).map(Data::Integer),
+        (Data::Number(a), Data::Number(b)) => a.checked_sub(*b).map(Data::Number),
+        (Data::Float(a), Data::Float(b)) => Some(Data::Float(a - b)),
+        _ => None,
+    }
+}
+
+impl PartialEq for StackedBar {
+    fn eq(&self, other: &Self) -> bool {
+        self.point == other.point
+            && self.is_negative == other.is_negative
+            && self.group == other.group
+            && self.true_y == other.true_y
+            && self.removed_sections == other.removed_sections
+            && self.fractions.len() == other.fractions.len()
+            && self.fractions.iter().all(|(label, fraction)| {
+                other
+                    .fractions
+                    .get(label)
+                    .map_or(false, |other| fraction.to_bits() == other.to_bits())
+            })
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl Eq for StackedBar {}
+
+impl Hash for StackedBar {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.point.hash(state);
+        self.is_negative.hash(state);
+        self.group.hash(state);
+        self.true_y.hash(state);
+
+        let mut fractions = self
+            .fractions
+            .iter()
+            .map(|(label, fraction)| (label, fraction.to_bits()))
+            .collect::<Vec<_>>();
+        fractions.sort();
+        fractions.hash(state);
+
+        sorted(&self.removed_sections).hash(state);
+    }
+}
+
+/// Returns the labels in `set` in sorted order, so they can be hashed.
+fn sorted(set: &HashSet<String>) -> Vec<&String> {
+    let mut labels = set.iter().collect::<Vec<&String>>();
+    labels.sort();
+    labels
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackedBarChart {
+    #[cfg_attr(feature = "serde", serde(rename = "bars"))]
     pub bars: Vec<StackedBar>,
+    #[cfg_attr(feature = "serde", serde(rename = "x_axis"))]
     pub x_axis: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "y_axis"))]
     pub y_axis: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "labels"))]
     pub labels: HashSet<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "x_scale"))]
     pub x_scale: Scale,
+    #[cfg_attr(feature = "serde", serde(rename = "y_scale"))]
     pub y_scale: Scale,
+    /// Marks over the graph. See [`StackedBarChart::annotate`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "annotations", default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub annotations: Vec<Annotation>,
+}
+
+impl Hash for StackedBarChart {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bars.hash(state);
+        self.x_axis.hash(state);
+        self.y_axis.hash(state);
+        sorted(&self.labels).hash(state);
+        self.x_scale.hash(state);
+        self.y_scale.hash(state);
+        self.annotations.hash(state);
+    }
 }
 
 #[allow(dead_code)]
@@ -157,9 +364,56 @@ impl StackedBarChart {
             x_axis: None,
             y_axis: None,
             labels,
+            annotations: Vec::new(),
         })
     }
 
+    /// Adds `annotation` to the chart. Returns an error if a value of the
+    /// annotation is not on the scale of its axis, unless `extend` is true
+    /// and the scale extends to it. See [`Scale::extend_to`].
+    pub fn annotate(
+        &mut self,
+        annotation: Annotation,
+        extend: bool,
+    ) -> Result<(), StackedBarChartError> {
+        fit_annotation(
+            &annotation,
+            &mut self.x_scale,
+            &mut self.y_scale,
+            None,
+            extend,
+        )
+        .map_err(|(axis, value)| {
+            StackedBarChartError::OutOfRange(axis.to_string(), value.to_string())
+        })?;
+        self.annotations.push(annotation);
+
+        Ok(())
+    }
+
+    /// Returns the chart with `annotation` added. See [`StackedBarChart::annotate`].
+    pub fn with_annotation(
+        mut self,
+        annotation: Annotation,
+        extend: bool,
+    ) -> Result<Self, StackedBarChartError> {
+        self.annotate(annotation, extend)?;
+        Ok(self)
+    }
+
+    /// Returns true if the chart has no bars.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// Checks that every point of the chart is on the scales, as
+    /// [`StackedBarChart::new`] does.
+    #[cfg(feature = "serde")]
+    pub(crate) fn assert_scales(&self) -> Result<(), StackedBarChartError> {
+        Self::assert_x_scale(&self.x_scale, &self.bars)?;
+        Self::assert_y_scale(&self.y_scale, &self.bars)
+    }
+
     fn assert_x_scale(scale: &Scale, bars: &[StackedBar]) -> Result<(), StackedBarChartError> {
         for x in bars.iter().map(|bar| &bar.point.x) {
             if !scale.contains(x) {
@@ -196,6 +450,24 @@ impl StackedBarChart {
         self
     }
 
+    /// Applies `transform` to every section label of the chart.
+    ///
+    /// Returns the transformed labels which more than one section now shares.
+    /// The contributions of such sections are combined.
+    pub fn transform_labels(&mut self, transform: &LabelTransform) -> Vec<String> {
+        let mut labels: Vec<&String> = self.labels.iter().collect();
+        labels.sort();
+        let transformed: Vec<String> = labels.into_iter().map(|l| transform.apply(l)).collect();
+        let duplicates = duplicate_labels(transformed.iter());
+
+        self.labels = transformed.into_iter().collect();
+        self.bars
+            .iter_mut()
+            .for_each(|bar| bar.transform_sections(transform));
+
+        duplicates
+    }
+
     /// Returns true any negative bar is not completely empty. For a Stacked bar chart, an empty point
     /// is defined as one which has a y data value of 0 or 0.0
     pub fn has_true_negatives(&self) -> bool {
@@ -212,6 +484,44 @@ impl StackedBarChart {
             .any(|bar| !bar.is_negative && !bar.is_empty())
     }
 
+    /// Returns the positive and negative bar of each group, in order of first
+    /// appearance within [`StackedBarChart::bars`].
+    pub fn pairs(&self) -> Vec<(Option<&StackedBar>, Option<&StackedBar>)> {
+        let mut groups: Vec<usize> = Vec::new();
+        let mut pairs: Vec<(Option<&StackedBar>, Option<&StackedBar>)> = Vec::new();
+
+        for bar in self.bars.iter() {
+            let idx = match groups.iter().position(|group| *group == bar.group) {
+                Some(idx) => idx,
+                None => {
+                    groups.push(bar.group);
+                    pairs.push((None, None));
+                    pairs.len() - 1
+                }
+            };
+
+            if bar.is_negative {
+                pairs[idx].1 = Some(bar);
+            } else {
+                pairs[idx].0 = Some(bar);
+            }
+        }
+
+        pairs
+    }
+
+    /// Returns the positive and negative bar of `group`, if any.
+    pub fn pair(&self, group: usize) -> (Option<&StackedBar>, Option<&StackedBar>) {
+        let mut bars = self.bars.iter().filter(|bar| bar.group == group);
+        let positive = bars.clone().find(|bar| !bar.is_negative);
+        let negative = bars.find(|bar| bar.is_negative);
+
+        (positive, negative)
+    }
+
+    /// Removes `section` from the bar at position `bar` of
+    /// [`StackedBarChart::bars`]. See [`StackedBarChart::pair`] for finding
+    /// the bars of a group.
     pub fn remove_section(&mut self, bar: usize, section: impl Into<String>) {
         if let Some(bar) = self.bars.get_mut(bar) {
             bar.remove_section(section);
@@ -225,6 +535,8 @@ impl StackedBarChart {
         });
     }
 
+    /// Re-adds `section` to the bar at position `bar` of
+    /// [`StackedBarChart::bars`].
     pub fn add_section(&mut self, bar: usize, section: impl Into<String>) {
         if let Some(bar) = self.bars.get_mut(bar) {
             bar.add_section(section);
@@ -237,6 +549,64 @@ impl StackedBarChart {
             bar.add_section(section.clone());
         });
     }
+
+    /// Returns the value of `section` in each group with it, against the x
+    /// value of the group, in the order of [`StackedBarChart::pairs`]. The
+    /// values are those charted, whether or not the section is removed from
+    /// the bars.
+    pub fn section_series(&self, label: &str) -> Vec<Point> {
+        self.pairs()
+            .into_iter()
+            .filter_map(|(positive, negative)| {
+                let bar = positive
+                    .into_iter()
+                    .chain(negative)
+                    .find(|bar| bar.fractions.contains_key(label))?;
+
+                Some(Point::new(bar.point.x.clone(), bar.section_value(label)?))
+            })
+            .collect()
+    }
+
+    /// Returns a scale over the values of `section` across every bar, of
+    /// the same kind as [`StackedBarChart::y_scale`] where possible.
+    /// Returns [`None`] if no bar has the section.
+    pub fn section_scale(&self, label: &str) -> Option<Scale> {
+        let values = self
+            .section_series(label)
+            .into_iter()
+            .map(|point| point.y)
+            .collect::<Vec<Data>>();
+
+        (!values.is_empty()).then(|| Scale::new(values, self.y_scale.kind()))
+    }
+
+    /// Returns a line graph of the same data, with a line for each section
+    /// in order of label. See [`StackedBarChart::section_series`].
+    ///
+    /// The x scale and axis labels are those of the chart, while the y scale
+    /// covers the values of every section.
+    pub fn to_line_graph(&self) -> Result<LineGraph, LineGraphError> {
+        let lines = sorted(&self.labels)
+            .into_iter()
+            .map(|label| Line::from_points(self.section_series(label)).label(label.clone()))
+            .collect::<Vec<Line>>();
+
+        let y_scale = Scale::new(
+            lines
+                .iter()
+                .flat_map(|line| line.points.iter().map(|point| point.y.clone())),
+            self.y_scale.kind(),
+        );
+
+        LineGraph::new(
+            lines,
+            self.x_axis.clone(),
+            self.y_axis.clone(),
+            self.x_scale.clone(),
+            y_scale,
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -263,6 +633,7 @@ impl std::error::Error for StackedBarChartError {}
 #[cfg(test)]
 mod stacked_barchart_tests {
     use crate::models::ScaleKind;
+    use proptest::{arbitrary::any, collection, prelude::*, proptest};
 
     use super::*;
 
@@ -271,63 +642,63 @@ mod stacked_barchart_tests {
 
         let pnt = Point::new(Data::Text("One".into()), Data::Integer(19));
 
-        let fractions = HashMap::from([
-            (String::from("Soda"), 3.0 / 19.0),
-            (String::from("Cream"), 3.0 / 19.0),
-            (String::from("Coffee"), 5.0 / 19.0),
-            (String::from("Choco"), 8.0 / 19.0),
+        let sections = HashMap::from([
+            (String::from("Soda"), Data::Integer(3)),
+            (String::from("Cream"), Data::Integer(3)),
+            (String::from("Coffee"), Data::Integer(5)),
+            (String::from("Choco"), Data::Integer(8)),
         ]);
 
-        let bar = StackedBar::new(pnt, fractions, false);
+        let bar = StackedBar::new(pnt, sections, false);
 
         bars.push(bar);
 
         let pnt = Point::new(Data::Text("Two".into()), Data::Integer(19));
 
-        let fractions = HashMap::from([
-            (String::from("Soda"), 3.0 / 19.0),
-            (String::from("Cream"), 6.0 / 19.0),
-            (String::from("Coffee"), 10.0 / 19.0),
-            (String::from("Choco"), 0.0 / 19.0),
+        let sections = HashMap::from([
+            (String::from("Soda"), Data::Integer(3)),
+            (String::from("Cream"), Data::Integer(6)),
+            (String::from("Coffee"), Data::Integer(10)),
+            (String::from("Choco"), Data::Integer(0)),
         ]);
 
-        let bar = StackedBar::new(pnt, fractions, false);
+        let bar = StackedBar::new(pnt, sections, false);
         bars.push(bar);
 
         let pnt = Point::new(Data::Text("Three".into()), Data::Integer(14));
 
-        let fractions = HashMap::from([
-            (String::from("Soda"), 6.0 / 14.0),
-            (String::from("Cream"), 0.0 / 14.0),
-            (String::from("Coffee"), 8.0 / 14.0),
-            (String::from("Choco"), 0.0 / 14.0),
+        let sections = HashMap::from([
+            (String::from("Soda"), Data::Integer(6)),
+            (String::from("Cream"), Data::Integer(0)),
+            (String::from("Coffee"), Data::Integer(8)),
+            (String::from("Choco"), Data::Integer(0)),
         ]);
 
-        let bar = StackedBar::new(pnt, fractions, false);
+        let bar = StackedBar::new(pnt, sections, false);
         bars.push(bar);
 
         let pnt = Point::new(Data::Text("Four".into()), Data::Integer(16));
 
-        let fractions = HashMap::from([
-            (String::from("Soda"), 3.0 / 16.0),
-            (String::from("Cream"), 0.0 / 16.0),
-            (String::from("Coffee"), 7.0 / 16.0),
-            (String::from("Choco"), 6.0 / 16.0),
+        let sections = HashMap::from([
+            (String::from("Soda"), Data::Integer(3)),
+            (String::from("Cream"), Data::Integer(0)),
+            (String::from("Coffee"), Data::Integer(7)),
+            (String::from("Choco"), Data::Integer(6)),
         ]);
 
-        let bar = StackedBar::new(pnt, fractions, false);
+        let bar = StackedBar::new(pnt, sections, false);
         bars.push(bar);
 
         let pnt = Point::new(Data::Text("Five".into()), Data::Integer(19));
 
-        let fractions = HashMap::from([
-            (String::from("Soda"), 9.0 / 19.0),
-            (String::from("Cream"), 0.0 / 19.0),
-            (String::from("Coffee"), 10.0 / 19.0),
-            (String::from("Choco"), 0.0 / 19.0),
+        let sections = HashMap::from([
+            (String::from("Soda"), Data::Integer(9)),
+            (String::from("Cream"), Data::Integer(0)),
+            (String::from("Coffee"), Data::Integer(10)),
+            (String::from("Choco"), Data::Integer(0)),
         ]);
 
-        let bar = StackedBar::new(pnt, fractions, false);
+        let bar = StackedBar::new(pnt, sections, false);
         bars.push(bar);
 
         let x_scale = {
@@ -410,4 +781,95 @@ mod stacked_barchart_tests {
             Err(e) => assert_eq!(e, expected),
         }
     }
+
+    /// Returns a bar of `values`, labelled by their positions.
+    fn sectioned(values: &[Data], total: Data) -> StackedBar {
+        let sections = values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| (idx.to_string(), value.clone()))
+            .collect();
+
+        StackedBar::new(Point::new(Data::Integer(0), total), sections, false)
+    }
+
+    #[test]
+    fn test_section_exact() {
+        let large = 2_isize.pow(53) + 1;
+        let values = [Data::Number(large), Data::Number(1)];
+        let mut bar = sectioned(&values, Data::Number(large + 1));
+
+        assert_eq!(bar.section_exact("0"), Some(&Data::Number(large)));
+        assert_eq!(bar.section_exact("2"), None);
+
+        bar.remove_section("1");
+        assert_eq!(bar.point.y, Data::Number(large));
+        bar.remove_section("0");
+        assert_eq!(bar.point.y, Data::Number(0));
+        bar.add_section("0");
+        bar.add_section("1");
+        assert_eq!(bar.point.y, Data::Number(large + 1));
+    }
+
+    #[test]
+    fn test_section_equality() {
+        let large = 2_isize.pow(54);
+        let even = sectioned(
+            &[Data::Number(large), Data::Number(large)],
+            Data::Number(2 * large),
+        );
+        let uneven = sectioned(
+            &[Data::Number(large + 1), Data::Number(large - 1)],
+            Data::Number(2 * large),
+        );
+
+        // The fractions of both bars round to a half, so only the exact
+        // values tell them apart
+        assert_eq!(even.fractions, uneven.fractions);
+        assert_eq!(even, uneven);
+        assert_ne!(even.section_exact("0"), uneven.section_exact("0"));
+
+        let mut unknown = even.clone();
+        unknown.sections.clear();
+        assert_eq!(unknown, even);
+        assert_eq!(unknown, uneven);
+        assert_eq!(HashSet::from([even, uneven, unknown]).len(), 1);
+    }
+
+    #[test]
+    fn test_section_fraction() {
+        let fraction = section_fraction(&Data::Float(-0.0), &Data::Float(4.0));
+        assert!(fraction.is_sign_positive());
+        assert_eq!(fraction, 0.0);
+
+        let bar = sectioned(&[Data::Integer(0), Data::Integer(0)], Data::Integer(0));
+        assert_eq!(bar.fraction("0"), Some(0.0));
+        assert!(bar.fractions.values().all(|fraction| *fraction == 0.0));
+    }
+
+    proptest! {
+        #[test]
+        fn test_section_reconstruction(
+            values in collection::vec(any::<i32>(), 1..8),
+            negative in any::<bool>(),
+        ) {
+            let values: Vec<isize> = values
+                .into_iter()
+                .map(|value| (value as isize).abs() * if negative { -1 } else { 1 })
+                .collect();
+            let total: isize = values.iter().sum();
+            let data: Vec<Data> = values.iter().copied().map(Data::Number).collect();
+            let bar = sectioned(&data, Data::Number(total));
+
+            for (idx, value) in values.iter().enumerate() {
+                let label = idx.to_string();
+                prop_assert_eq!(bar.section_exact(&label), Some(&Data::Number(*value)));
+
+                let exact = *value as f64;
+                let rebuilt = bar.fraction(&label).unwrap() * total as f64;
+                let ulp = f64::from_bits(exact.abs().to_bits() + 1) - exact.abs();
+                prop_assert!((rebuilt - exact).abs() <= ulp, "{} rebuilt as {}", exact, rebuilt);
+            }
+        }
+    }
 }
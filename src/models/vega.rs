@@ -0,0 +1,417 @@
+//! Export of charts to [Vega-Lite](https://vega.github.io/vega-lite/) specs,
+//! enabled by the `vega` feature, for use with existing web tooling.
+//!
+//! Each spec holds its data inline. Categorical scales are encoded as
+//! nominal fields sorted in the order of their points, and other scales as
+//! quantitative fields over the domain of the scale.
+
+use serde_json::{json, Map, Number, Value};
+
+use super::{BarChart, LineGraph, Scale, ScaleKind, StackedBar, StackedBarChart};
+use crate::repr::Data;
+
+/// The Vega-Lite schema the specs are written against.
+pub const VEGA_LITE_SCHEMA: &str = "https://vega.github.io/schema/vega-lite/v5.json";
+
+impl LineGraph {
+    /// Returns a Vega-Lite spec of the graph, with a point for each point of
+    /// every line. Lines are told apart by their position, in the `line`
+    /// field, and coloured by their labels where any line has one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::models::{Line, LineGraph, ScaleKind};
+    ///
+    /// let line = Line::new([(1, 4), (2, 7)]).label("Sales");
+    /// let graph = LineGraph::from_lines_with_kinds(
+    ///     vec![line],
+    ///     Some("Week".into()),
+    ///     None,
+    ///     ScaleKind::Integer,
+    ///     ScaleKind::Integer,
+    /// )
+    /// .unwrap();
+    ///
+    /// let spec = graph.to_vega_lite();
+    /// assert_eq!(spec["mark"]["type"], "line");
+    /// assert_eq!(spec["data"]["values"][1]["y"], 7);
+    /// assert_eq!(spec["encoding"]["x"]["title"], "Week");
+    /// ```
+    pub fn to_vega_lite(&self) -> Value {
+        let values = self
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, line)| {
+                line.points.iter().map(move |point| {
+                    json!({
+                        "line": idx,
+                        "label": line.label,
+                        "x": data_value(&point.x),
+                        "y": data_value(&point.y),
+                    })
+                })
+            })
+            .collect();
+
+        let mut encoding = Map::new();
+        encoding.insert("x".into(), encode("x", &self.x_scale, &self.x_label));
+        encoding.insert("y".into(), encode("y", self.shown_y_scale(), &self.y_label));
+        encoding.insert("detail".into(), json!({"field": "line", "type": "nominal"}));
+        if self.lines.iter().any(|line| line.label.is_some()) {
+            encoding.insert(
+                "color".into(),
+                json!({"field": "label", "type": "nominal", "title": null}),
+            );
+        }
+
+        spec(values, json!({"type": "line", "point": true}), encoding)
+    }
+}
+
+impl BarChart {
+    /// Returns a Vega-Lite spec of the chart, with a bar for each bar of the
+    /// chart. Labels of bars are shown as tooltips where any bar has one.
+    pub fn to_vega_lite(&self) -> Value {
+        let values = self
+            .bars
+            .iter()
+            .map(|bar| {
+                json!({
+                    "label": bar.label,
+                    "x": data_value(&bar.point.x),
+                    "y": data_value(&bar.point.y),
+                })
+            })
+            .collect();
+
+        let mut encoding = Map::new();
+        let x_label = self.x_label.clone().unwrap_or_default();
+        let y_label = self.y_label.clone().unwrap_or_default();
+        encoding.insert("x".into(), encode("x", &self.x_scale, &x_label));
+        encoding.insert("y".into(), encode("y", self.shown_y_scale(), &y_label));
+        if self.bars.iter().any(|bar| bar.label.is_some()) {
+            encoding.insert(
+                "tooltip".into(),
+                json!([{"field": "label", "type": "nominal"}]),
+            );
+        }
+
+        spec(values, json!({"type": "bar"}), encoding)
+    }
+}
+
+impl StackedBarChart {
+    /// Returns a Vega-Lite spec of the chart, with a bar segment for each
+    /// section of every bar, coloured by section. Removed sections are left
+    /// out.
+    ///
+    /// Segments are stacked by the exact value of their sections, or, if the
+    /// value of any section is only known as a fraction of its bar, stacked
+    /// by fraction and normalized to the full height of the chart.
+    pub fn to_vega_lite(&self) -> Value {
+        let sections = |bar: &StackedBar| {
+            let mut labels = bar
+                .fractions
+                .keys()
+                .filter(|label| !bar.is_removed(label))
+                .cloned()
+                .collect::<Vec<_>>();
+            labels.sort();
+            labels
+        };
+        let exact = self.bars.iter().all(|bar| {
+            sections(bar)
+                .iter()
+                .all(|label| bar.section_exact(label).is_some())
+        });
+
+        let values = self
+            .bars
+            .iter()
+            .flat_map(|bar| {
+                sections(bar).into_iter().map(move |label| {
+                    let y = match exact {
+                        true => bar.section_exact(&label).map(data_value),
+                        false => bar.fraction(&label).map(float_value),
+                    };
+
+                    json!({
+                        "x": data_value(&bar.point.x),
+                        "section": label,
+                        "y": y.unwrap_or(Value::Null),
+                    })
+                })
+            })
+            .collect();
+
+        let mut encoding = Map::new();
+        let x_label = self.x_axis.clone().unwrap_or_default();
+        let y_label = self.y_axis.clone().unwrap_or_default();
+        encoding.insert("x".into(), encode("x", &self.x_scale, &x_label));
+        let y = match exact {
+            true => encode("y", &self.y_scale, &y_label),
+            false => {
+                let mut y = json!({"field": "y", "type": "quantitative", "stack": "normalize"});
+                if !y_label.is_empty() {
+                    y["title"] = y_label.into();
+                }
+                y
+            }
+        };
+        encoding.insert("y".into(), y);
+        encoding.insert(
+            "color".into(),
+            json!({"field": "section", "type": "nominal", "title": null}),
+        );
+
+        spec(values, json!({"type": "bar"}), encoding)
+    }
+}
+
+/// Returns a spec of `values` drawn with `mark` and `encoding`.
+fn spec(values: Vec<Value>, mark: Value, encoding: Map<String, Value>) -> Value {
+    json!({
+        "$schema": VEGA_LITE_SCHEMA,
+        "data": {"values": values},
+        "mark": mark,
+        "encoding": encoding,
+    })
+}
+
+/// Returns the encoding of `field` on an axis with `scale`, titled `title`
+/// unless it is empty.
+fn encode(field: &str, scale: &Scale, title: &str) -> Value {
+    let mut encoding = json!({"field": field});
+
+    match scale.kind() {
+        ScaleKind::Categorical => {
+            encoding["type"] = "nominal".into();
+            encoding["sort"] = scale.points().iter().map(data_value).collect();
+        }
+        ScaleKind::Number | ScaleKind::Integer | ScaleKind::Float => {
+            encoding["type"] = "quantitative".into();
+
+            let mut points = scale.points();
+            if scale.is_descending() {
+                points.reverse();
+            }
+            if let (Some(min), Some(max)) = (points.first(), points.last()) {
+                encoding["scale"] = json!({
+                    "domain": [data_value(min), data_value(max)],
+                    "reverse": scale.is_descending(),
+                });
+            }
+        }
+    }
+
+    if !title.is_empty() {
+        encoding["title"] = title.into();
+    }
+
+    encoding
+}
+
+/// Returns `data` as a JSON value. Floats are written as they are
+/// displayed, and non-finite floats as null.
+fn data_value(data: &Data) -> Value {
+    match data {
+        Data::Text(text) => Value::String(text.clone()),
+        Data::Integer(int) => (*int).into(),
+        Data::Number(num) => (*num as i64).into(),
+        Data::Float(float) => float
+            .to_string()
+            .parse::<f64>()
+            .ok()
+            .map_or(Value::Null, float_value),
+        Data::Boolean(bool) => (*bool).into(),
+        Data::None => Value::Null,
+    }
+}
+
+fn float_value(float: f64) -> Value {
+    Number::from_f64(float).map_or(Value::Null, Value::Number)
+}
+
+#[cfg(all(test, feature = "csv"))]
+mod vega_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{
+        models::LabelTransform,
+        repr::{
+            BarChartAxisLabelStrategy, BarChartBarLabels, Config, HeaderStrategy,
+            LineGraphAxisLabelStrategy, LineLabelStrategy, RowSelector, Sheet,
+            StackedBarChartAxisLabelStrategy, TypesStrategy,
+        },
+    };
+
+    fn load(path: &str) -> Sheet {
+        let config = Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+
+        Sheet::with_config(config).unwrap()
+    }
+
+    fn line_graph() -> LineGraph {
+        load("./dummies/csv/air.csv")
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::Headers {
+                    y: "Passengers".into(),
+                },
+                LineLabelStrategy::FromCell(0),
+                RowSelector::default(),
+                HashSet::new(),
+            )
+            .unwrap()
+    }
+
+    fn bar_chart() -> BarChart {
+        load("./dummies/csv/air.csv")
+            .create_bar_chart(
+                0,
+                1,
+                BarChartBarLabels::FromColumn(0),
+                BarChartAxisLabelStrategy::Headers,
+                RowSelector::default(),
+            )
+            .unwrap()
+    }
+
+    fn stacked_bar_chart() -> StackedBarChart {
+        let mut chart = load("./dummies/csv/stacked_neg.csv")
+            .create_stacked_bar_chart(
+                0,
+                [1, 2, 3, 4],
+                StackedBarChartAxisLabelStrategy::Header("Sales".into()),
+                RowSelector::default(),
+            )
+            .unwrap();
+        chart.transform_labels(&LabelTransform::Lowercase);
+        chart.remove_section(1, "coffee");
+        chart
+    }
+
+    fn read(name: &str) -> Value {
+        let path = format!("./dummies/models/{name}");
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Checks the parts of the Vega-Lite schema the specs rely on.
+    fn validate(spec: &Value) {
+        assert_eq!(spec["$schema"], VEGA_LITE_SCHEMA);
+
+        let values = spec["data"]["values"].as_array().unwrap();
+        assert!(!values.is_empty());
+        assert!(values.iter().all(Value::is_object));
+
+        let mark = spec["mark"]["type"].as_str().unwrap();
+        assert!(["bar", "line"].contains(&mark));
+
+        let encoding = spec["encoding"].as_object().unwrap();
+        assert!(encoding.contains_key("x") && encoding.contains_key("y"));
+
+        for (channel, definition) in encoding {
+            let definitions = match definition {
+                Value::Array(definitions) => definitions.iter().collect(),
+                definition => vec![definition],
+            };
+
+            for definition in definitions {
+                let field = definition["field"].as_str().unwrap();
+                let kind = definition["type"].as_str().unwrap();
+                assert!(
+                    ["quantitative", "nominal", "ordinal", "temporal"].contains(&kind),
+                    "{channel} has type {kind}"
+                );
+                assert!(values.iter().all(|value| value.get(field).is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_line() {
+        let spec = line_graph().to_vega_lite();
+        validate(&spec);
+
+        assert_eq!(spec["mark"]["type"], "line");
+        assert_eq!(spec["encoding"]["x"]["type"], "nominal");
+        assert_eq!(spec["encoding"]["y"]["type"], "quantitative");
+        assert_eq!(spec["encoding"]["y"]["title"], "Passengers");
+        assert_eq!(spec["encoding"]["color"]["field"], "label");
+
+        assert_eq!(spec, read("vega_line.json"));
+    }
+
+    #[test]
+    fn test_bar() {
+        let spec = bar_chart().to_vega_lite();
+        validate(&spec);
+
+        assert_eq!(spec["mark"]["type"], "bar");
+        assert_eq!(spec["encoding"]["x"]["type"], "nominal");
+        assert_eq!(spec["encoding"]["y"]["type"], "quantitative");
+
+        assert_eq!(spec, read("vega_bar.json"));
+    }
+
+    #[test]
+    fn test_stacked_bar() {
+        let mut chart = stacked_bar_chart();
+        let spec = chart.to_vega_lite();
+        validate(&spec);
+
+        assert_eq!(spec["encoding"]["y"]["title"], "Sales");
+        assert!(spec["encoding"]["y"].get("stack").is_none());
+        assert!(spec["encoding"]["y"]["scale"]["domain"].is_array());
+        let values = spec["data"]["values"].as_array().unwrap();
+        assert_eq!(values.len(), 7 * 4 - 1);
+        assert_eq!(
+            values[6],
+            json!({"x": "Tuesday", "section": "chocolate", "y": -10})
+        );
+
+        assert_eq!(spec, read("vega_stacked_bar.json"));
+
+        // A section known only as a fraction stacks the chart by fraction
+        chart.bars[0].fractions.insert("tea".into(), 0.5);
+        let spec = chart.to_vega_lite();
+        validate(&spec);
+
+        assert_eq!(spec["encoding"]["y"]["stack"], "normalize");
+        assert!(spec["encoding"]["y"].get("scale").is_none());
+        let values = spec["data"]["values"].as_array().unwrap();
+        assert_eq!(values.len(), 7 * 4);
+        assert_eq!(
+            values[4],
+            json!({"x": "Monday", "section": "tea", "y": 0.5})
+        );
+    }
+
+    #[test]
+    fn test_scales() {
+        let mut scale = Scale::new([3, 1, 2], ScaleKind::Integer);
+        let encoding = encode("y", &scale, "");
+        assert_eq!(
+            encoding,
+            json!({
+                "field": "y",
+                "type": "quantitative",
+                "scale": {"domain": [1, 3], "reverse": false}
+            })
+        );
+
+        scale.invert();
+        assert_eq!(encode("y", &scale, "")["scale"]["reverse"], true);
+
+        let scale = Scale::new(["b", "a"], ScaleKind::Categorical);
+        assert_eq!(encode("x", &scale, "Kind")["sort"], json!(["b", "a"]));
+
+        assert_eq!(data_value(&Data::Float(0.1)), json!(0.1));
+        assert_eq!(data_value(&Data::Float(f32::NAN)), Value::Null);
+    }
+}
@@ -0,0 +1,39 @@
+//! Re-exports of the commonly used types of this crate.
+//!
+//! ```
+//! use modav_core::{prelude::*, samples};
+//! use std::collections::HashSet;
+//!
+//! let sheet = samples::air();
+//! assert_eq!(sheet.column_kind(1), Some(ColumnType::Integer));
+//!
+//! let chart: BarChart = sheet
+//!     .create_bar_chart_with(
+//!         0,
+//!         1,
+//!         BarChartBarLabels::None,
+//!         BarChartAxisLabelStrategy::Headers,
+//!         HashSet::new(),
+//!         ScaleOptions::new().descending(true),
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(chart.y_scale.kind(), ScaleKind::Integer);
+//! assert!(chart.y_scale.is_descending());
+//! ```
+
+pub use crate::models::{
+    Annotation, Axis, AxisOptions, AxisPoints, Bar, BarChart, BoxItem, BoxPlot, CategoricalOrder,
+    ChartKind, HeatMap, LabelTransform, Line, LineGraph, Point, Scale, ScaleKind, StackedBar,
+    StackedBarChart, TopN, TopNOrdering,
+};
+pub use crate::repr::col_sheet::ColumnSheet;
+pub use crate::repr::{
+    AutoChart, BarChartAxisLabelStrategy, BarChartBarLabels, BoolMode, Cell, ChartContext,
+    ChartSuggestion, ColumnHeader, ColumnType, Config, ConstraintViolation, Data, DiffMode,
+    DiffOptions, DiffSummary, Encoding, FromData, FromRow, HeaderStrategy,
+    LineGraphAxisLabelStrategy, LineLabelStrategy, LineTerminator, LoadReport, MissingPolicy,
+    PreviewInfo, QuoteStyle, Row, RowConstraint, RowSelector, ScaleOptions, Schema, SchemaColumn,
+    SchemaDiff, Sheet, SimplePattern, SniffResult, StackedBarChartAxisLabelStrategy, TypesStrategy,
+    Unit, Workbook, WriteOptions,
+};
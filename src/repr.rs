@@ -5,3 +5,20 @@ mod config;
 pub use config::*;
 
 pub mod col_sheet;
+
+mod workbook;
+pub use workbook::*;
+
+mod schema;
+pub use schema::*;
+
+mod sniff;
+pub use sniff::*;
+
+#[cfg(feature = "serde")]
+mod snapshot;
+#[cfg(feature = "serde")]
+pub use snapshot::{SnapshotError, SNAPSHOT_VERSION};
+
+#[cfg(test)]
+mod conformance_tests;
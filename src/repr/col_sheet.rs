@@ -1,10 +1,14 @@
-use csv::{ReaderBuilder, Trim};
 use std::{
+    cmp::Ordering,
+    collections::HashSet,
     iter::{ExactSizeIterator, Iterator},
-    path::Path,
+    ops::{Bound, RangeBounds},
     slice::{Iter, IterMut},
 };
 
+#[cfg(feature = "csv")]
+use std::{io, path::Path};
+
 #[allow(unused_imports)]
 use crate::models::{
     bar::{Bar, BarChart},
@@ -42,11 +46,31 @@ pub use arrayf64::*;
 mod arraybool;
 pub use arraybool::*;
 
+mod arrayunion;
+pub use arrayunion::*;
+
+mod group;
+pub use group::*;
+
+#[cfg(feature = "csv")]
+mod lazy;
+#[cfg(feature = "csv")]
+use lazy::LazyColumn;
+
+#[cfg(feature = "serde")]
+mod snapshot;
+
 mod col_tests;
 
 use super::config::*;
-use super::utils::{ColumnType as CT, TypesStrategy};
+#[cfg(feature = "csv")]
+use super::utils::Data;
+use super::utils::{
+    count_distinct, is_increasing, ColumnMeta, ColumnType as CT, ParseField, TypesStrategy, Unit,
+};
+use super::{Schema, SchemaColumn};
 
+#[cfg(feature = "csv")]
 const INFERENCE_LIMIT: u32 = 100;
 const I32: u8 = 0b0000_0001;
 const U32: u8 = 0b0000_0010;
@@ -62,6 +86,8 @@ const TEXT: u8 = 0b1000_0000;
 enum ColumnType {
     None,
     Infer(bool),
+    /// Like [`ColumnType::Infer`], but mixed columns become [`ArrayUnion`]s
+    InferUnion(bool),
     Type(CT),
 }
 
@@ -92,6 +118,7 @@ impl Iterator for StrategyIter {
             TypesStrategy::Provided(headers) => headers.get(idx).copied().map(ColumnType::Type),
             TypesStrategy::None => Some(ColumnType::None),
             TypesStrategy::Infer => Some(ColumnType::Infer(self.try_infer)),
+            TypesStrategy::InferWithUnion => Some(ColumnType::InferUnion(self.try_infer)),
         }
     }
 }
@@ -106,52 +133,131 @@ pub struct ColumnSheet {
     height: usize,
     /// The string which should be considered null.
     null_string: String,
+    /// The original text of the cells of each column which are written
+    /// differently from how they were read. See [`Config::preserve_original`].
+    originals: Vec<Option<ArrayText>>,
+    /// The meta of each column, if collected and no column has changed
+    /// since. See [`Config::collect_meta`].
+    meta: Vec<Option<ColumnMeta<CellValue>>>,
+    /// The number of fields each row was read with, if any row was padded
+    /// with nulls and no row or column has changed since.
+    widths: Vec<usize>,
+    /// See [`ColumnSheet::load_report`]
+    report: LoadReport,
 }
 
 impl ColumnSheet {
     /// Constructs a [`ColumnSheet`] from the provided path using the default
     /// [`Config`].
+    #[cfg(feature = "csv")]
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         Self::with_config(Config::new(path))
     }
 
     /// Constructs a [`ColumnSheet`] using a configured [`Config`].
+    #[cfg(feature = "csv")]
     pub fn with_config<P: AsRef<Path>>(config: Config<P>) -> Result<Self> {
+        let config = config.detected().map_err(CsvError::from)?;
+        let file = std::fs::File::open(config.path.as_ref()).map_err(CsvError::from)?;
+        let rdr = config.csv_reader(file);
+
+        Self::read_csv(rdr, config)
+    }
+
+    /// Constructs a [`ColumnSheet`] from CSV data in `reader`, such as an
+    /// in-memory buffer, using a configured [`Config`]. The path of `config`
+    /// is only used to describe the source.
+    #[cfg(feature = "csv")]
+    pub fn from_reader<R: io::Read, P: AsRef<Path>>(reader: R, config: Config<P>) -> Result<Self> {
+        let rdr = config.csv_reader(reader);
+
+        Self::read_csv(rdr, config)
+    }
+
+    #[cfg(feature = "csv")]
+    fn read_csv<R: io::Read, P: AsRef<Path>>(
+        mut rdr: csv::Reader<Decoder<R>>,
+        config: Config<P>,
+    ) -> Result<Self> {
+        let span = trace_span!(
+            "column_sheet_load",
+            path = %config.path.as_ref().display(),
+            rows = tracing::field::Empty,
+            cols = tracing::field::Empty
+        );
+
         let Config {
-            path,
             primary,
+            primary_set,
             trim,
-            flexible,
-            delimiter,
             label_strategy,
             type_strategy,
             null_string,
+            max_rows,
+            drop_empty_cols,
+            decimal_separator,
+            preserve_original,
+            collect_meta,
+            nan_policy,
+            type_mismatch,
+            select_columns,
+            expected_schema,
+            lazy,
+            ..
         } = config;
 
-        let trim = if trim { Trim::All } else { Trim::None };
-        let has_headers = label_strategy == HeaderStrategy::ReadLabels;
+        let selected = match &select_columns {
+            Some(select) => {
+                let headers = match label_strategy {
+                    HeaderStrategy::ReadLabels => Some(rdr.headers()?.clone()),
+                    _ => None,
+                };
+                Some(select.resolve(headers.as_ref(), trim)?)
+            }
+            None => None,
+        };
+        let primary = match (&selected, primary) {
+            (Some(selected), Some(primary)) => Some(
+                SelectColumns::primary(selected, primary, primary_set)
+                    .ok_or(Error::InvalidPrimary(primary))?,
+            ),
+            _ => primary,
+        };
+        let mut widest_record = 0;
 
-        let mut rdr = ReaderBuilder::new()
-            .has_headers(has_headers)
-            .trim(trim)
-            .delimiter(delimiter)
-            .flexible(flexible)
-            .from_path(path)?;
+        // The text of each field which may be written differently from how
+        // it was read, by column. See `may_rewrite`.
+        let mut raw_texts: Vec<Vec<(usize, String)>> = Vec::default();
+        let mut widths = Vec::default();
 
-        let (mut cols, height, types) = {
+        let (mut cols, height, mut types) = {
             let mut cols: Vec<Vec<String>> = Vec::default();
             let mut types: Vec<(u8, bool)> = Vec::default();
             let mut rows = 0;
             let mut columns = 0;
             let mut limit = 0;
 
-            for (row, record) in rdr.records().enumerate() {
-                let record = record?;
+            let max_rows = max_rows.unwrap_or(usize::MAX);
+
+            for (row, record) in rdr.records().take(max_rows).enumerate() {
+                let mut record = record?;
+                // The fields of columns not selected are never copied out
+                if let Some(selected) = &selected {
+                    widest_record = usize::max(widest_record, record.len());
+                    record = select_fields(&record, selected);
+                }
+
+                // The fields as read, kept only until the row is parsed
+                let raw = preserve_original.then(|| record.clone());
+                if preserve_original && trim {
+                    record.trim();
+                }
                 rows += 1;
                 let curr_cols = record.len();
+                widths.push(curr_cols);
 
                 for (col, record) in record.into_iter().enumerate() {
-                    let record = record.to_owned();
+                    let record = normalize_decimal(record, decimal_separator).into_owned();
 
                     let prev = types.get(col);
                     let has_prev = prev.is_some();
@@ -170,13 +276,47 @@ impl ColumnSheet {
                         types.push(col_type);
                     }
 
+                    if let Some(text) = raw.as_ref().and_then(|raw| raw.get(col)) {
+                        if raw_texts.len() <= col {
+                            raw_texts.resize_with(col + 1, Vec::new);
+                        }
+                        let texts = &mut raw_texts[col];
+
+                        let float = match &type_strategy {
+                            TypesStrategy::Provided(kinds) => kinds.get(col) == Some(&CT::Float),
+                            _ => matches!(col_type.0, F32 | F64),
+                        };
+                        // Integers read before the column was found to hold
+                        // floats are written as floats
+                        if float && !matches!(prev.0, F32 | F64) {
+                            let previous = cols.get(col).map(Vec::as_slice).unwrap_or_default();
+                            for (prev_row, value) in previous.iter().enumerate() {
+                                if value.parse::<isize>().is_err()
+                                    && value.parse::<usize>().is_err()
+                                {
+                                    continue;
+                                }
+                                if let Err(pos) =
+                                    texts.binary_search_by_key(&prev_row, |(row, _)| *row)
+                                {
+                                    texts.insert(pos, (prev_row, value.clone()));
+                                }
+                            }
+                        }
+
+                        let null = record.is_empty() || record == null_string;
+                        if !null && (text != record || may_rewrite(&record, float)) {
+                            texts.push((row, text.to_owned()));
+                        }
+                    }
+
                     match cols.get_mut(col) {
                         Some(col) => col.push(record),
                         // If this record(row) is longer than previous, construct
-                        // the a new column, fill it with the default value and
+                        // the a new column, fill it with the null value and
                         // then also push this row's value for the column.
                         None => {
-                            let mut col = vec![String::default(); row];
+                            let mut col = vec![null_string.clone(); row];
                             col.push(record);
                             cols.push(col);
                         }
@@ -187,10 +327,10 @@ impl ColumnSheet {
                     columns = curr_cols
                 } else {
                     // If a previous record(row) was longer than this one, fill
-                    // it with the default value
+                    // it with the null value
                     for missing in curr_cols..columns {
                         if let Some(missing) = cols.get_mut(missing) {
-                            missing.push(String::default())
+                            missing.push(null_string.clone())
                         }
                     }
                 }
@@ -201,43 +341,237 @@ impl ColumnSheet {
             (cols, rows, types)
         };
 
-        //cols.iter_mut()
-        //    .for_each(|col| col.resize_with(height, Default::default));
-
+        let read_labels = matches!(label_strategy, HeaderStrategy::ReadLabels);
         let mut headers = match label_strategy {
             HeaderStrategy::NoLabels => vec![None; cols.len()],
             HeaderStrategy::Provided(headers) => headers.into_iter().map(Some).collect(),
-            HeaderStrategy::ReadLabels => rdr
-                .headers()?
-                .into_iter()
-                .map(|header| {
-                    if header.is_empty() {
-                        None
-                    } else {
-                        Some(header.to_owned())
-                    }
-                })
-                .collect(),
+            HeaderStrategy::ReadLabels => {
+                let mut record = rdr.headers()?.clone();
+                if let Some(selected) = &selected {
+                    widest_record = usize::max(widest_record, record.len());
+                    record = select_fields(&record, selected);
+                }
+
+                record
+                    .into_iter()
+                    .map(|header| if trim { header.trim() } else { header })
+                    .map(|header| {
+                        if header.is_empty() {
+                            None
+                        } else {
+                            Some(header.to_owned())
+                        }
+                    })
+                    .collect()
+            }
         };
 
+        if let Some(selected) = &selected {
+            SelectColumns::check_width(selected, widest_record)?;
+        }
+
+        let mut report = LoadReport::default();
+        if height != 0 && headers.len() != cols.len() {
+            if read_labels {
+                report.header_width_mismatch = Some((headers.len(), cols.len()));
+            }
+            trace_debug!(
+                headers = headers.len(),
+                width = cols.len(),
+                "header and data widths differ"
+            );
+        }
+
         let longest = usize::max(cols.len(), headers.len());
         headers.resize_with(longest, Default::default);
-        cols.resize_with(longest, Default::default);
+        // Labels beyond the widest record get columns of nulls
+        cols.resize_with(longest, || vec![null_string.clone(); height]);
+        types.resize_with(longest, Default::default);
 
-        let columns: Vec<Box<dyn Column>> =
-            Self::create_columns(cols, headers, type_strategy, (false, types), &null_string);
-        let primary = if columns.is_empty() {
-            None
+        if let TypesStrategy::Provided(kinds) = &type_strategy {
+            resolve_mismatches(&mut cols, kinds, type_mismatch, &null_string);
+        }
+
+        let mut columns: Vec<Box<dyn Column>> = if lazy {
+            Self::create_lazy_columns(
+                cols,
+                headers,
+                type_strategy,
+                types,
+                &null_string,
+                nan_policy,
+            )
         } else {
-            Some(primary)
+            Self::create_columns(cols, headers, type_strategy, (false, types), &null_string)
         };
+        if nan_policy == NanPolicy::Null && !lazy {
+            columns
+                .iter_mut()
+                .for_each(|column| null_nan(column.as_mut()));
+        }
+        let primary = primary.filter(|_| !columns.is_empty());
 
-        Ok(Self {
+        debug_assert!(
+            columns.iter().all(|column| column.len() == height),
+            "Columns should all have the same height"
+        );
+
+        let originals = Self::originals(&columns, raw_texts, decimal_separator);
+
+        let mut sheet = Self {
             columns,
             primary,
             height,
             null_string,
-        })
+            originals,
+            meta: Vec::new(),
+            widths: Vec::new(),
+            report,
+        };
+
+        if widths.iter().any(|width| *width < sheet.width()) {
+            sheet.widths = widths;
+        }
+
+        if drop_empty_cols {
+            sheet.drop_null_cols(1.0);
+        }
+
+        if let Some(expected) = &expected_schema {
+            let diff = expected.diff(&sheet.schema());
+            if !diff.is_compatible() {
+                return Err(Error::SchemaMismatch(Box::new(diff)));
+            }
+        }
+
+        if collect_meta {
+            sheet.meta = sheet
+                .columns
+                .iter()
+                .map(|column| Some(column_meta(column.as_ref())))
+                .collect();
+        }
+
+        #[cfg(feature = "trace")]
+        for (col, column) in sheet.columns.iter().enumerate() {
+            trace_debug!(col, label = column.label(), kind = %column.kind(), "inferred column kind");
+        }
+        span.record("rows", sheet.height);
+        span.record("cols", sheet.width());
+        sheet.report.replaced = rdr.get_ref().replaced();
+        trace_debug!(replaced = sheet.report.replaced, "loaded column sheet");
+
+        Ok(sheet)
+    }
+
+    /// Returns the original text of the cells of `columns` which are written
+    /// differently from how they were read, given the text of each cell which
+    /// may be, by column and row.
+    #[cfg(feature = "csv")]
+    fn originals(
+        columns: &[Box<dyn Column>],
+        texts: Vec<Vec<(usize, String)>>,
+        decimal_separator: char,
+    ) -> Vec<Option<ArrayText>> {
+        let default = WriteOptions::default();
+        let mut texts = texts.into_iter();
+
+        columns
+            .iter()
+            .map(|column| {
+                let mut kept = texts
+                    .next()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(row, text)| {
+                        let Some(cell) = column.data_ref(*row) else {
+                            return false;
+                        };
+                        let float = matches!(cell, CellRef::F32(_) | CellRef::F64(_));
+
+                        (decimal_separator == '.' || !float) && write_field(cell, &default) != *text
+                    })
+                    .peekable();
+                kept.peek()?;
+
+                let mut cells = vec![None; column.len()];
+                for (row, text) in kept {
+                    cells[row] = Some(text);
+                }
+
+                Some(ArrayText::from_iterator_option(cells.into_iter()))
+            })
+            .collect()
+    }
+
+    /// Returns the text the cell at `col`, `row` was read from, if it is
+    /// written differently from its value. Cells only keep their text when
+    /// read with [`Config::preserve_original`], and lose it once modified
+    /// through the [`ColumnSheet`]. Mutable access to a column discards the
+    /// text of all its cells.
+    pub fn original_text(&self, col: usize, row: usize) -> Option<&str> {
+        match self.originals.get(col)?.as_ref()?.data_ref(row)? {
+            CellRef::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn clear_original(&mut self, col: usize, row: usize) {
+        if let Some(Some(original)) = self.originals.get_mut(col) {
+            original.clear(row);
+        }
+    }
+
+    /// Writes the [`ColumnSheet`] as CSV to `writer` using the default
+    /// [`WriteOptions`].
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: io::Write>(&self, writer: W) -> Result<()> {
+        self.write_csv_with(writer, &WriteOptions::default())
+    }
+
+    /// Writes the [`ColumnSheet`] as CSV to `writer` using `options`. Missing
+    /// headers are written as empty fields.
+    ///
+    /// Cells with original text are written as they were read, except for
+    /// floats written with a decimal separator other than `.`. See
+    /// [`Config::preserve_original`].
+    #[cfg(feature = "csv")]
+    pub fn write_csv_with<W: io::Write>(&self, writer: W, options: &WriteOptions) -> Result<()> {
+        let mut wtr = options.csv_writer(writer);
+
+        if options.headers {
+            wtr.write_record(self.headers().map(|header| header.header.unwrap_or("")))?;
+        }
+
+        for row in 0..self.height() {
+            let cells = self.get_row(row).ok_or(Error::InvalidRow(row))?;
+            let width = if options.trim_padding {
+                self.original_width(row).unwrap_or_default()
+            } else {
+                cells.len()
+            };
+
+            wtr.write_record(
+                cells
+                    .into_iter()
+                    .take(width)
+                    .enumerate()
+                    .map(|(col, cell)| {
+                        let float = matches!(cell, CellRef::F32(_) | CellRef::F64(_));
+
+                        match self.original_text(col, row) {
+                            Some(text) if !float || options.decimal_separator == '.' => {
+                                text.to_owned()
+                            }
+                            _ => write_field(cell, options),
+                        }
+                    }),
+            )?;
+        }
+
+        wtr.flush().map_err(CsvError::from)?;
+
+        Ok(())
     }
 
     /// Constructs columns from inputs. Expects the length of `cols` and
@@ -268,6 +602,36 @@ impl ColumnSheet {
             .collect()
     }
 
+    /// Constructs columns from inputs like [`ColumnSheet::create_columns`],
+    /// leaving their values to be parsed on first access.
+    #[cfg(feature = "csv")]
+    fn create_lazy_columns(
+        cols: Vec<Vec<String>>,
+        headers: Vec<Option<String>>,
+        type_strategy: TypesStrategy,
+        inferance: Vec<(u8, bool)>,
+        null: &str,
+        nan_policy: NanPolicy,
+    ) -> Vec<Box<dyn Column>> {
+        let strategies = StrategyIter::new(type_strategy, false);
+
+        cols.into_iter()
+            .zip(headers)
+            .zip(strategies)
+            .zip(inferance)
+            .map(|(((col, header), kind), inference)| {
+                boxed(LazyColumn::new(
+                    col,
+                    header,
+                    kind,
+                    inference,
+                    null,
+                    nan_policy == NanPolicy::Null,
+                ))
+            })
+            .collect()
+    }
+
     /// Returns an iterator over the columns of the [`ColumnSheet`].
     pub fn iter(&self) -> Iter<'_, Box<dyn Column>> {
         self.columns.iter()
@@ -275,6 +639,11 @@ impl ColumnSheet {
 
     /// Returns an iterator that allows modifying each column
     pub fn iter_mut(&mut self) -> IterMut<'_, Box<dyn Column>> {
+        self.meta.clear();
+        self.widths.clear();
+        self.originals
+            .iter_mut()
+            .for_each(|original| *original = None);
         self.columns.iter_mut()
     }
 
@@ -286,6 +655,8 @@ impl ColumnSheet {
     /// Overwrites the cell at `col`, `row` with `value` if parsing to the
     /// valid column type succeeds.
     pub fn set_cell(&mut self, value: impl AsRef<str>, col: usize, row: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if col >= self.width() {
             return Err(Error::InvalidColumn(col));
         }
@@ -304,6 +675,8 @@ impl ColumnSheet {
             return Err(Error::InvalidCellInput { col, row });
         }
 
+        self.clear_original(col, row);
+
         Ok(())
     }
 
@@ -323,8 +696,97 @@ impl ColumnSheet {
         Some(output)
     }
 
+    /// Returns an owned copy of the row at index `row` within the
+    /// [`ColumnSheet`] if any. Unlike [`ColumnSheet::get_row`], the copy does
+    /// not borrow the [`ColumnSheet`].
+    pub fn get_row_owned(&self, row: usize) -> Option<Vec<CellValue>> {
+        self.get_row(row)
+            .map(|cells| cells.into_iter().map(CellValue::from).collect())
+    }
+
+    /// Returns the smallest and largest values of the column at `col`, if
+    /// any. See [`Column::bounds`].
+    ///
+    /// Returns an error if `col` is out of range.
+    pub fn col_bounds(&self, col: usize) -> Result<Option<(CellRef<'_>, CellRef<'_>)>> {
+        if let Some(meta) = self.col_meta(col) {
+            return Ok(meta
+                .bounds()
+                .map(|(min, max)| (min.as_cell_ref(), max.as_cell_ref())));
+        }
+
+        self.columns
+            .get(col)
+            .map(|column| column.bounds())
+            .ok_or(Error::InvalidColumn(col))
+    }
+
+    /// Returns the smallest and largest values of the row at `row`, if any.
+    /// See [`Column::bounds`].
+    ///
+    /// Returns an error if `row` is out of range.
+    pub fn row_bounds(&self, row: usize) -> Result<Option<(CellRef<'_>, CellRef<'_>)>> {
+        self.get_row(row).map(bounds).ok_or(Error::InvalidRow(row))
+    }
+
+    /// Returns the smallest and largest values across the columns at `cols`,
+    /// if any, such as for the shared scale of a chart of several columns.
+    /// See [`Column::bounds`].
+    ///
+    /// Returns an error if any of `cols` is out of range.
+    pub fn bounds_of(&self, cols: &[usize]) -> Result<Option<(CellRef<'_>, CellRef<'_>)>> {
+        let mut cells = Vec::with_capacity(cols.len() * 2);
+        for col in cols {
+            if let Some((min, max)) = self.col_bounds(*col)? {
+                cells.extend([min, max]);
+            }
+        }
+
+        Ok(bounds(cells))
+    }
+
+    /// Returns an iterator over the rows of the [`ColumnSheet`]. Each row is
+    /// the same as that returned by [`ColumnSheet::get_row`].
+    ///
+    /// Cells are fetched from each column in blocks of rows, which is faster
+    /// than calling [`ColumnSheet::get_row`] for every row.
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = Vec<CellRef<'_>>> {
+        self.rows_range(..)
+    }
+
+    /// Returns an iterator over the rows within `range`. Any part of `range`
+    /// past the last row is ignored. See [`ColumnSheet::iter_rows`].
+    pub fn rows_range(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> impl ExactSizeIterator<Item = Vec<CellRef<'_>>> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.height,
+        };
+        let end = end.min(self.height);
+
+        Rows {
+            columns: &self.columns,
+            next: start.min(end),
+            end,
+            block_start: start,
+            blocks: vec![Vec::with_capacity(ROW_BLOCK); self.width()],
+        }
+    }
+
     /// Time Complexity: `O(width * log(k) + width)`
     fn sort_col_helper(&mut self, cell: usize, rev: bool) {
+        self.meta.clear();
+        self.widths.clear();
+        let _span = trace_span!("sort_col", cell, rev);
+
         if cell >= self.height {
             return;
         }
@@ -352,6 +814,7 @@ impl ColumnSheet {
                 self.primary = Some(pos)
             }
             self.columns.swap(pos, *elem);
+            self.originals.swap(pos, *elem);
         }
 
         //self.columns.sort_by(|a, b| {
@@ -393,6 +856,10 @@ impl ColumnSheet {
 
     /// Time Complexity: `O(height * (1 + log(k) +  width)`
     fn sort_row_helper(&mut self, cell: usize, rev: bool) {
+        self.meta.clear();
+        self.widths.clear();
+        let _span = trace_span!("sort_row", cell, rev);
+
         if cell >= self.width() {
             return;
         }
@@ -416,6 +883,10 @@ impl ColumnSheet {
         self.columns
             .iter_mut()
             .for_each(|column| column.apply_index_swap(&indices));
+        self.originals
+            .iter_mut()
+            .flatten()
+            .for_each(|original| original.apply_index_swap(&indices));
     }
 
     /// Sorts the rows of the [`ColumnSheet`] using the primary column. If no
@@ -450,16 +921,119 @@ impl ColumnSheet {
 
     /// Returns an iterator over the headers of the [`ColumnSheet`].
     pub fn headers(&self) -> impl ExactSizeIterator<Item = ColumnHeader<'_>> {
-        self.columns.iter().map(|col| {
-            let header = ColumnHeader {
-                header: col.label(),
-                kind: col.kind(),
-            };
-            header
+        let mut seen = HashSet::new();
+        let duplicates: HashSet<&str> = self
+            .columns
+            .iter()
+            .filter_map(|col| col.label())
+            .filter(|label| !seen.insert(*label))
+            .collect();
+
+        (0..self.width()).map(move |idx| {
+            let is_unique = self.columns[idx]
+                .label()
+                .map_or(true, |label| !duplicates.contains(label));
+            self.header_unchecked(idx, is_unique)
         })
     }
 
+    /// Returns true if both sheets have the same height and the same
+    /// columns, in the same order.
+    ///
+    /// Columns are the same when their labels, types and values match.
+    /// Floats are compared by their bits, as with [`Data`],
+    /// so `NaN`s are equal. The primary column, units, null string and
+    /// original text are ignored.
+    pub fn logical_eq(&self, other: &Self) -> bool {
+        if self.width() != other.width() || self.height != other.height {
+            return false;
+        }
+
+        self.columns
+            .iter()
+            .zip(other.columns.iter())
+            .all(|(own, other)| {
+                own.label() == other.label()
+                    && own.kind() == other.kind()
+                    && (0..self.height).all(|row| match (own.data_ref(row), other.data_ref(row)) {
+                        (Some(own), Some(other)) => own.cmp(&other) == Ordering::Equal,
+                        (own, other) => own.is_none() && other.is_none(),
+                    })
+            })
+    }
+
+    /// Returns the header of the column at `idx`, if any.
+    pub fn header_at(&self, idx: usize) -> Option<ColumnHeader<'_>> {
+        if idx >= self.width() {
+            return None;
+        }
+
+        let is_unique = match self.columns[idx].label() {
+            Some(label) => self.find_col(label, Some(idx)).is_none(),
+            None => true,
+        };
+
+        Some(self.header_unchecked(idx, is_unique))
+    }
+
+    /// Assumes `idx` is a valid column index.
+    fn header_unchecked(&self, idx: usize, is_unique: bool) -> ColumnHeader<'_> {
+        let col = &self.columns[idx];
+
+        ColumnHeader {
+            header: col.label(),
+            kind: col.provisional_kind().unwrap_or_else(|| col.kind()),
+            idx,
+            is_primary: self.primary == Some(idx),
+            null_count: col.null_count(),
+            height: col.len(),
+            kind_hint: col.kind_hint(),
+            unit: col.unit(),
+            is_unique,
+        }
+    }
+
+    /// Returns the position of the first column labelled `label`, other than
+    /// the column at `skip`.
+    fn find_col(&self, label: &str, skip: Option<usize>) -> Option<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .position(|(idx, col)| Some(idx) != skip && col.label() == Some(label))
+    }
+
+    /// Sets the unit of the values within the column at `col`. A [`None`]
+    /// clears any previous unit.
+    ///
+    /// Returns `Err` if `col` is invalid.
+    pub fn set_col_unit(&mut self, col: usize, unit: Option<Unit>) -> Result<()> {
+        let column = self.columns.get_mut(col).ok_or(Error::InvalidColumn(col))?;
+        column.set_unit(unit);
+
+        Ok(())
+    }
+
+    /// Records `kind` as the intended type of the text column at `col`,
+    /// without converting it. A [`None`] clears any previous hint.
+    ///
+    /// Returns `Err` if `col` is invalid or not a [`DataType::Text`] column.
+    pub fn set_col_kind_hint(&mut self, col: usize, kind: Option<CT>) -> Result<()> {
+        let column = self.columns.get_mut(col).ok_or(Error::InvalidColumn(col))?;
+
+        if column.set_kind_hint(kind) {
+            Ok(())
+        } else {
+            Err(Error::InvalidKindHint {
+                col,
+                kind: column.kind(),
+            })
+        }
+    }
+
     /// Sets the header of the column at `col` to `header`.
+    ///
+    /// Other columns may share the label. See
+    /// [`ColumnSheet::set_col_header_unique`].
     pub fn set_col_header(&mut self, col: usize, header: impl Into<String>) -> Result<()> {
         if col >= self.width() {
             return Err(Error::InvalidColumn(col));
@@ -472,6 +1046,48 @@ impl ColumnSheet {
         Ok(())
     }
 
+    /// Sets the header of the column at `col` to `header`, unless another
+    /// column already has that label.
+    ///
+    /// Returns `Err` if `col` is invalid or the label is taken.
+    pub fn set_col_header_unique(&mut self, col: usize, header: impl Into<String>) -> Result<()> {
+        if col >= self.width() {
+            return Err(Error::InvalidColumn(col));
+        }
+
+        let label = header.into();
+        if let Some(existing_col) = self.find_col(&label, Some(col)) {
+            return Err(Error::DuplicateHeader {
+                label,
+                existing_col,
+            });
+        }
+
+        self.set_col_header(col, label)
+    }
+
+    /// Renames the first column labelled `from` to `to`, unless another
+    /// column already has the label `to`.
+    ///
+    /// Returns `Err` if no column is labelled `from` or the label is taken.
+    pub fn rename_col(&mut self, from: &str, to: &str) -> Result<()> {
+        let col = self
+            .find_col(from, None)
+            .ok_or_else(|| Error::UnknownHeader(from.to_owned()))?;
+
+        self.set_col_header_unique(col, to)
+    }
+
+    /// Removes the header of the column at `col`.
+    ///
+    /// Returns `Err` if `col` is invalid.
+    pub fn clear_col_header(&mut self, col: usize) -> Result<()> {
+        let column = self.columns.get_mut(col).ok_or(Error::InvalidColumn(col))?;
+        column.clear_header();
+
+        Ok(())
+    }
+
     /// Returns the width of the [`ColumnSheet`].
     ///
     /// This is essentially the same as the number of [`Column`]s in the [`ColumnSheet`].
@@ -479,6 +1095,32 @@ impl ColumnSheet {
         self.columns.len()
     }
 
+    /// Returns how the [`ColumnSheet`] was read from its file. See
+    /// [`LoadReport`].
+    pub fn load_report(&self) -> LoadReport {
+        self.report
+    }
+
+    /// Returns the label, type and nullability of each column of the
+    /// [`ColumnSheet`]. The types are those of [`Sheet`](crate::repr::Sheet)
+    /// columns holding the same values. See [`Schema`].
+    ///
+    /// Columns read lazily are parsed to find their types.
+    pub fn schema(&self) -> Schema {
+        let columns = self
+            .headers()
+            .map(|header| {
+                SchemaColumn::new(
+                    header.header.unwrap_or_default(),
+                    self.columns[header.idx].kind().into(),
+                    header.null_count > 0,
+                )
+            })
+            .collect();
+
+        Schema::new(columns)
+    }
+
     /// Returns the height of the [`ColumnSheet`].
     ///
     /// The height is defined as the number of cells within a [`Column`]. As such,
@@ -490,6 +1132,38 @@ impl ColumnSheet {
         self.height
     }
 
+    /// Returns the number of fields `row` was read with before it was padded
+    /// with nulls to the width of the [`ColumnSheet`], or the width if it was
+    /// not padded.
+    ///
+    /// Padding is only tracked until a row or column of the [`ColumnSheet`]
+    /// changes.
+    pub fn original_width(&self, row: usize) -> Option<usize> {
+        if row >= self.height {
+            return None;
+        }
+
+        let width = self.width();
+        Some(
+            self.widths
+                .get(row)
+                .map_or(width, |original| width.min(*original)),
+        )
+    }
+
+    /// Returns the positions, as `(row, column)`, of the cells synthesized to
+    /// pad short rows to the width of the [`ColumnSheet`]. See
+    /// [`ColumnSheet::original_width`].
+    pub fn synthesized_cells(&self) -> Vec<(usize, usize)> {
+        let width = self.width();
+
+        self.widths
+            .iter()
+            .enumerate()
+            .flat_map(|(row, original)| (*original..width).map(move |col| (row, col)))
+            .collect()
+    }
+
     /// Sets the primary column of the [`ColumnSheet`].
     ///
     /// If `primary` is invalid, no change is made.
@@ -526,8 +1200,104 @@ impl ColumnSheet {
         self.columns.get(idx).map(|boxed| boxed.as_ref())
     }
 
+    /// Returns the meta of the column at `col`, if it was collected while
+    /// loading the [`ColumnSheet`] and no column has changed since. See
+    /// [`Config::collect_meta`].
+    pub fn col_meta(&self, col: usize) -> Option<&ColumnMeta<CellValue>> {
+        self.meta.get(col)?.as_ref()
+    }
+
+    /// Returns an iterator over every cell with its row and column, in
+    /// column-major order.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, CellRef<'_>)> {
+        let height = self.height;
+
+        self.columns
+            .iter()
+            .enumerate()
+            .flat_map(move |(col, column)| {
+                (0..height).filter_map(move |row| Some((row, col, column.data_ref(row)?)))
+            })
+    }
+
+    /// Returns an iterator over the cells of the column at `col` with the row
+    /// of each cell. The iterator is empty if `col` is out of range.
+    pub fn enumerate_col(&self, col: usize) -> impl Iterator<Item = (usize, CellRef<'_>)> {
+        let column = self.columns.get(col);
+
+        (0..self.height).filter_map(move |row| Some((row, column?.data_ref(row)?)))
+    }
+
+    /// Returns the values of the column at `col` converted to [`Data`] for
+    /// chart building.
+    ///
+    /// `U32` and `USize` values are charted as [`Data::Number`], which may
+    /// not hold them on every target. The first value which does not fit is
+    /// an error naming its cell, unless `overflow` is
+    /// [`UnsignedOverflow::WidenToFloat`], in which case the whole column is
+    /// charted as [`Data::Float`] and the rows which lost precision are
+    /// reported in [`ChartValues::lossy`]. `F64` values narrow to
+    /// [`Data::Float`] and are reported the same way.
+    pub fn chart_values(&self, col: usize, overflow: UnsignedOverflow) -> Result<ChartValues> {
+        let column = self.get_col(col).ok_or(Error::InvalidColumn(col))?;
+        let kind = column.kind();
+
+        let mut cells = Vec::with_capacity(column.len());
+        column.data_refs(0..column.len(), &mut cells);
+
+        let overflowed = cells.iter().position(|cell| chart_data(*cell).is_none());
+        let widen = match (overflowed, overflow) {
+            (None, _) => false,
+            (Some(row), UnsignedOverflow::Reject) => {
+                return Err(Error::UnrepresentableValue { col, row, kind })
+            }
+            (Some(_), UnsignedOverflow::WidenToFloat) => true,
+        };
+
+        let mut values = Vec::with_capacity(cells.len());
+        let mut lossy = Vec::default();
+        for (row, cell) in cells.into_iter().enumerate() {
+            let (value, lost) = if widen {
+                widened_chart_data(cell)
+            } else {
+                chart_data(cell).expect("Chart conversion: Overflow was checked")
+            };
+
+            if lost {
+                lossy.push(row);
+            }
+            values.push(value);
+        }
+
+        if !lossy.is_empty() {
+            trace_debug!(col, rows = lossy.len(), "charted values lost precision");
+        }
+
+        let kind = match kind {
+            _ if widen => ScaleKind::Float,
+            DataType::I32 => ScaleKind::Integer,
+            DataType::U32 | DataType::ISize | DataType::USize => ScaleKind::Number,
+            DataType::F32 | DataType::F64 => ScaleKind::Float,
+            DataType::Bool | DataType::Text | DataType::Union => ScaleKind::Categorical,
+        };
+
+        Ok(ChartValues {
+            kind,
+            values,
+            lossy,
+        })
+    }
+
     /// Returns an exclusive reference to the column at `idx` if any.
+    ///
+    /// The original text of the cells of the column is discarded. See
+    /// [`ColumnSheet::original_text`].
     pub fn get_col_mut(&mut self, idx: usize) -> Option<&mut Box<dyn Column>> {
+        self.meta.clear();
+        self.widths.clear();
+        if let Some(original) = self.originals.get_mut(idx) {
+            *original = None;
+        }
         self.columns.get_mut(idx)
     }
 
@@ -543,10 +1313,32 @@ impl ColumnSheet {
         self.width() == 0
     }
 
+    /// Returns true if the [`ColumnSheet`] has at least one row.
+    ///
+    /// This is the same as `!self.is_empty()`.
+    pub fn has_rows(&self) -> bool {
+        self.height > 0
+    }
+
+    /// Returns true if at least one cell of the [`ColumnSheet`] is not null.
+    pub fn has_data(&self) -> bool {
+        self.columns.iter().any(|col| {
+            (0..col.len()).any(|idx| !matches!(col.data_ref(idx), None | Some(CellRef::None)))
+        })
+    }
+
+    /// Returns true if every cell of the [`ColumnSheet`] is null. A
+    /// [`ColumnSheet`] with no rows is also blank.
+    pub fn is_blank(&self) -> bool {
+        !self.has_data()
+    }
+
     /// Appends a column to the back of the [`ColumnSheet`]
     ///
     /// Returns `Err` if `column` has a different width than `Self`.
     pub fn push_col(&mut self, column: Box<dyn Column>) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         self.insert_col(column, self.width())
     }
 
@@ -558,12 +1350,16 @@ impl ColumnSheet {
         I: AsRef<str>,
         R: ExactSizeIterator<Item = I>,
     {
+        self.meta.clear();
+        self.widths.clear();
         self.insert_row(row, self.height)
     }
 
     /// Duplicates  the [`Column`] at `col`. The duplicate column is inserted at
     /// `col`, shifting all [`Column`]s after to the right.
     pub fn duplicate_col(&mut self, idx: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.width() {
             return Err(Error::InvalidColumn(idx));
         }
@@ -576,6 +1372,8 @@ impl ColumnSheet {
 
     /// Removes and returns the last [`Column`] from the [`ColumnSheet`].
     pub fn pop_col(&mut self) -> Result<Box<dyn Column>> {
+        self.meta.clear();
+        self.widths.clear();
         if self.width() == 0 {
             return Err(Error::InvalidColumn(0));
         }
@@ -585,6 +1383,8 @@ impl ColumnSheet {
 
     /// Removes the last row from the [`ColumnSheet`].
     pub fn pop_row(&mut self) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if self.is_empty() {
             return Err(Error::InvalidRow(0));
         }
@@ -596,11 +1396,14 @@ impl ColumnSheet {
     ///
     /// Returns `Err` if `idx` >= `self.width`  
     pub fn remove_col(&mut self, idx: usize) -> Result<Box<dyn Column>> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.width() {
             return Err(Error::InvalidColumn(idx));
         }
 
         let removed = self.columns.remove(idx);
+        self.originals.remove(idx);
 
         let Some(primary) = self.primary else {
             return Ok(removed);
@@ -615,9 +1418,38 @@ impl ColumnSheet {
         Ok(removed)
     }
 
+    /// Removes every [`Column`] whose fraction of [`None`] values is at least
+    /// `threshold`, returning the indices of the removed columns in
+    /// ascending order. See [`ColumnSheet::remove_col`] for how the primary
+    /// column is affected.
+    ///
+    /// A `threshold` of `1.0` only removes columns where every value is
+    /// [`None`]. The columns of a [`ColumnSheet`] without rows are all
+    /// considered empty.
+    pub fn drop_null_cols(&mut self, threshold: f64) -> Vec<usize> {
+        let height = self.height;
+
+        let removed = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, col)| height == 0 || col.null_count() as f64 / height as f64 >= threshold)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<usize>>();
+
+        for idx in removed.iter().rev() {
+            let _ = self.remove_col(*idx);
+        }
+
+        removed
+    }
+
     /// Removes all [`Column`]s within the [`ColumnSheet`].
     pub fn remove_all_cols(&mut self) {
+        self.meta.clear();
+        self.widths.clear();
         self.columns.clear();
+        self.originals.clear();
         self.height = 0;
         self.primary = None;
     }
@@ -626,6 +1458,8 @@ impl ColumnSheet {
     ///
     /// Returns `Err` if `idx` >= `self.height`  
     pub fn remove_row(&mut self, idx: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.height {
             return Err(Error::InvalidRow(idx));
         }
@@ -633,6 +1467,10 @@ impl ColumnSheet {
         self.columns
             .iter_mut()
             .for_each(|column| column.remove(idx));
+        self.originals
+            .iter_mut()
+            .flatten()
+            .for_each(|original| original.remove(idx));
 
         self.height -= 1;
 
@@ -643,7 +1481,13 @@ impl ColumnSheet {
     ///
     /// All [`Column`]s in are left empty.
     pub fn remove_all_rows(&mut self) {
+        self.meta.clear();
+        self.widths.clear();
         self.columns.iter_mut().for_each(|col| col.remove_all());
+        self.originals
+            .iter_mut()
+            .flatten()
+            .for_each(|original| original.remove_all());
         self.height = 0;
     }
 
@@ -652,6 +1496,8 @@ impl ColumnSheet {
     /// Returns `Err` if `idx` > `self.width`  
     /// Returns `Err` if `column` has a different width than `Self`.
     pub fn insert_col(&mut self, column: Box<dyn Column>, idx: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         let other = column.len();
         let own = self.height;
 
@@ -664,6 +1510,7 @@ impl ColumnSheet {
         }
 
         self.columns.insert(idx, column);
+        self.originals.insert(idx, None);
 
         if self.width() == 1 {
             self.primary = Some(0);
@@ -683,6 +1530,10 @@ impl ColumnSheet {
 
     /// Inserts a row at `idx` shifting all values after down
     ///
+    /// Columns without rows, such as those read from a header-only CSV, keep
+    /// their headers and take their types from the first row inserted. A
+    /// [`ColumnSheet`] without columns creates a column for each value.
+    ///
     /// Returns `Err` if `idx` > `self.height()`  
     /// Returns `Err` if `row` has a different width than `Self`.
     pub fn insert_row<I, R>(&mut self, row: R, idx: usize) -> Result<()>
@@ -690,6 +1541,8 @@ impl ColumnSheet {
         I: AsRef<str>,
         R: ExactSizeIterator<Item = I>,
     {
+        self.meta.clear();
+        self.widths.clear();
         let own = self.width();
         let other = row.len();
 
@@ -701,7 +1554,7 @@ impl ColumnSheet {
             return Err(Error::InvalidInsertion(idx));
         }
 
-        if self.is_empty() {
+        if self.true_is_empty() {
             let cols = row
                 .map(|value| vec![value.as_ref().to_owned()])
                 .collect::<Vec<Vec<String>>>();
@@ -715,15 +1568,28 @@ impl ColumnSheet {
             );
 
             self.columns = columns;
+            self.originals = vec![None; len];
 
             if len != 0 {
                 self.primary = Some(0);
             }
+        } else if self.is_empty() {
+            for (column, value) in self.columns.iter_mut().zip(row) {
+                *column =
+                    Self::with_first_value(column.as_ref(), value.as_ref(), &self.null_string);
+            }
+            self.originals
+                .iter_mut()
+                .for_each(|original| *original = None);
         } else {
             self.columns
                 .iter_mut()
                 .zip(row)
                 .for_each(|(column, value)| column.insert(value.as_ref(), idx, &self.null_string));
+            self.originals
+                .iter_mut()
+                .flatten()
+                .for_each(|original| original.insert("", idx, ""));
         }
 
         self.height += 1;
@@ -731,10 +1597,43 @@ impl ColumnSheet {
         Ok(())
     }
 
+    /// Returns a copy of `column`, which has no values, holding only `value`.
+    ///
+    /// The header, unit and kind hint of `column` are kept. A text column
+    /// holds no type information without values, so it takes the type
+    /// inferred from `value`, as does a column whose type cannot hold
+    /// `value`. A null `value` keeps the type of `column`.
+    fn with_first_value(column: &dyn Column, value: &str, null: &str) -> Box<dyn Column> {
+        let kind = column.kind();
+        let is_null = value.is_empty() || value == null;
+
+        let mut first = column.convert_col(kind);
+        first.push(value, null);
+
+        let held = matches!(first.data_ref(0), Some(cell) if cell != CellRef::None);
+        if !is_null && (kind == DataType::Text || !held) {
+            first = Self::create_columns(
+                vec![vec![value.to_owned()]],
+                vec![column.label().map(str::to_owned)],
+                TypesStrategy::Infer,
+                (true, vec![(0, false)]),
+                null,
+            )
+            .remove(0);
+        }
+
+        first.set_unit(column.unit().cloned());
+        first.set_kind_hint(column.kind_hint());
+
+        first
+    }
+
     /// Swaps the columns at `x` with those at `y`.
     ///
     /// Values are left unchanged if any one of the indices are invalid
     pub fn swap_cols(&mut self, x: usize, y: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if x >= self.width() {
             return Err(Error::InvalidColumn(x));
         }
@@ -744,6 +1643,7 @@ impl ColumnSheet {
         }
 
         self.columns.swap(x, y);
+        self.originals.swap(x, y);
 
         if let Some(primary) = self.primary {
             if x == primary {
@@ -760,6 +1660,8 @@ impl ColumnSheet {
     ///
     /// Values are left unchanged if any one of the indices are invalid
     pub fn swap_rows(&mut self, x: usize, y: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         let height = self.height;
 
         if x >= height {
@@ -771,12 +1673,18 @@ impl ColumnSheet {
         }
 
         self.columns.iter_mut().for_each(|col| col.swap(x, y));
+        self.originals
+            .iter_mut()
+            .flatten()
+            .for_each(|original| original.swap(x, y));
 
         Ok(())
     }
 
     /// Replaces all values within the [`Column`] at `idx` with [`None`].
     pub fn clear_col(&mut self, idx: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.width() {
             return Err(Error::InvalidColumn(idx));
         }
@@ -784,23 +1692,32 @@ impl ColumnSheet {
         if let Some(col) = self.columns.get_mut(idx) {
             col.clear_all();
         }
+        self.originals[idx] = None;
 
         Ok(())
     }
 
     /// Replaces all values within the row at `idx` with [`None`].
     pub fn clear_row(&mut self, idx: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.height() {
             return Err(Error::InvalidRow(idx));
         }
 
         self.columns.iter_mut().for_each(|column| column.clear(idx));
+        self.originals
+            .iter_mut()
+            .flatten()
+            .for_each(|original| original.clear(idx));
 
         Ok(())
     }
 
     /// Replaces the value of the cell in `col` column at `row` row with [`None`].
     pub fn clear_cell(&mut self, col: usize, row: usize) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if col >= self.width() {
             return Err(Error::InvalidColumn(col));
         }
@@ -809,47 +1726,324 @@ impl ColumnSheet {
             return Err(Error::InvalidRow(row));
         }
 
-        if let Some(col) = self.columns.get_mut(col) {
-            col.clear(row);
+        if let Some(column) = self.columns.get_mut(col) {
+            column.clear(row);
         }
+        self.clear_original(col, row);
 
         Ok(())
     }
 
+    /// Replaces every non-null value in the `col` column for which `pred`
+    /// returns true with the result of `new`, returning the number of
+    /// modified cells. A [`None`] from `new` clears the cell.
+    ///
+    /// The values are accessed as `T` directly. Returns an error if the column
+    /// does not hold `T` values. See [`ColumnValue`].
+    pub fn update_col_where<T: ColumnValue>(
+        &mut self,
+        col: usize,
+        pred: impl Fn(&T) -> bool,
+        new: impl Fn(&T) -> Option<T>,
+    ) -> Result<usize> {
+        self.meta.clear();
+        self.widths.clear();
+        let column = self.columns.get_mut(col).ok_or(Error::InvalidColumn(col))?;
+        let found = column.kind();
+        let column =
+            column
+                .as_any_mut()
+                .downcast_mut::<T::Column>()
+                .ok_or(Error::InvalidColumnKind {
+                    col,
+                    expected: T::KIND,
+                    found,
+                })?;
+        self.originals[col] = None;
+
+        let mut count = 0;
+        for cell in T::cells_mut(column) {
+            if let Some(value) = cell.as_ref().filter(|value| pred(value)) {
+                *cell = new(value);
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
     /// Converts the [`Column`] at `idx`index to a `to` type column.
     ///
     /// Unlike [`ColumnSheet::convert_col`], this does not check for [`DataType`]
     /// compatibility which could lead to loss of information and inaccuracies.
     pub fn convert_col_unchecked(&mut self, idx: usize, to: DataType) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.width() {
             return Err(Error::InvalidColumn(idx));
         }
 
         let from = &self.columns[idx];
-        let new = from.convert_col(to);
+        let mut new = from.convert_col(to);
+        new.set_unit(from.unit().cloned());
 
         self.columns.push(new);
         self.columns.swap_remove(idx);
+        self.originals[idx] = None;
 
         Ok(())
     }
 
     /// Converts the [`Column`] at `idx`index to a `to` type column.
     ///
-    /// Returns an error if [`Column::kind`] is incompatible with `to`.
+    /// Returns an error if [`Column::kind`] is incompatible with `to`. A
+    /// [`DataType::Union`] column is converted by
+    /// [`ArrayUnion::coerce_to`], so an error is also returned if any of
+    /// its values cannot be widened to `to`.
     pub fn convert_col(&mut self, idx: usize, to: DataType) -> Result<()> {
+        self.meta.clear();
+        self.widths.clear();
         if idx >= self.width() {
             return Err(Error::InvalidColumn(idx));
         }
 
         let from = &self.columns[idx];
-        let from = from.kind();
+        let kind = from.kind();
+
+        if !DataType::can_convert(kind, to) {
+            return Err(Error::InvalidColConversion {
+                col: idx,
+                from: kind,
+                to,
+            });
+        }
+
+        let Some(union) = from.as_any().downcast_ref::<ArrayUnion>() else {
+            return self.convert_col_unchecked(idx, to);
+        };
+
+        let mut new = union.coerce_to(to).ok_or(Error::InvalidColConversion {
+            col: idx,
+            from: kind,
+            to,
+        })?;
+        new.set_unit(from.unit().cloned());
+
+        self.columns.push(new);
+        self.columns.swap_remove(idx);
+        self.originals[idx] = None;
+
+        Ok(())
+    }
+
+    /// Returns a new [`ColumnSheet`] with the rows and columns swapped.
+    ///
+    /// If `first_col_as_headers` is true, the values of the first column
+    /// become the headers, while the headers of the other columns become the
+    /// first column, so transposing twice restores the original. Otherwise
+    /// every column becomes a row and the headers are dropped.
+    ///
+    /// Each new column keeps the type of its row if every transposed column
+    /// has the same type, and is inferred from its values otherwise. Null
+    /// cells stay null. A unit shared by every transposed column applies to
+    /// each new column.
+    ///
+    /// Returns `Err` if `first_col_as_headers` is true but there are no
+    /// columns.
+    pub fn transpose(&self, first_col_as_headers: bool) -> Result<ColumnSheet> {
+        let _span = trace_span!(
+            "column_sheet_transpose",
+            rows = self.height,
+            cols = self.width()
+        );
+
+        let null = self.null_string.as_str();
+        let options = WriteOptions::new().null_string(null);
+        let text = |cell: Option<CellRef<'_>>| match cell {
+            None | Some(CellRef::None) => None,
+            Some(cell) => Some(write_field(cell, &options)),
+        };
+
+        let mut columns: Vec<Box<dyn Column>> = Vec::with_capacity(self.height + 1);
 
-        if DataType::can_convert(from, to) {
-            self.convert_col_unchecked(idx, to)
+        let (sources, headers) = if first_col_as_headers {
+            let first = self.columns.first().ok_or(Error::InvalidColumn(0))?;
+            let sources = &self.columns[1..];
+
+            let mut labels = ArrayText::from_iterator_option(
+                sources.iter().map(|col| col.label().map(str::to_owned)),
+            );
+            if let Some(label) = first.label() {
+                labels.set_header(label);
+            }
+            columns.push(boxed(labels));
+
+            let headers = (0..self.height)
+                .map(|row| text(first.data_ref(row)))
+                .collect::<Vec<Option<String>>>();
+
+            (sources, headers)
         } else {
-            Err(Error::InvalidColConversion { col: idx, from, to })
+            (self.columns.as_slice(), vec![None; self.height])
+        };
+
+        let kind = sources
+            .first()
+            .map(|col| col.kind())
+            .filter(|kind| sources.iter().all(|col| col.kind() == *kind));
+        let unit = sources
+            .first()
+            .and_then(|col| col.unit())
+            .filter(|unit| sources.iter().all(|col| col.unit() == Some(*unit)))
+            .cloned();
+
+        for (row, header) in headers.into_iter().enumerate() {
+            let values = sources
+                .iter()
+                .map(|col| text(col.data_ref(row)).unwrap_or_else(|| null.to_owned()))
+                .collect::<Vec<String>>();
+
+            let mut column = match kind.and_then(|kind| parse_as(&values, kind, null)) {
+                Some(column) => column,
+                None => Self::create_columns(
+                    vec![values],
+                    vec![None],
+                    TypesStrategy::Infer,
+                    (true, vec![(0, false)]),
+                    null,
+                )
+                .remove(0),
+            };
+
+            if let Some(header) = header {
+                column.set_header(header);
+            }
+            column.set_unit(unit.clone());
+            columns.push(column);
+        }
+
+        let width = columns.len();
+
+        Ok(Self {
+            columns,
+            primary: (width != 0).then_some(0),
+            height: sources.len(),
+            null_string: self.null_string.clone(),
+            originals: vec![None; width],
+            meta: Vec::new(),
+            widths: Vec::new(),
+            report: LoadReport::default(),
+        })
+    }
+
+    /// Groups the rows by the values of the `key_col` column, returning a
+    /// [`ColumnSheet`] with one row for each distinct value, in order of
+    /// first appearance. Null values form a group of their own.
+    ///
+    /// The first column holds the values of `key_col`, followed by a column
+    /// for each of `aggs`, combining the non-null values of its column within
+    /// each group. These are labelled with the aggregation and the label of
+    /// the column, such as `sum(Sales)`, or its index if it has no label.
+    ///
+    /// Returns an error if any column is out of range or an aggregation is
+    /// not supported for the type of its column. Every type can be counted,
+    /// only numeric columns summed or averaged, and all but
+    /// [`DataType::Union`] columns ordered.
+    pub fn group_by(&self, key_col: usize, aggs: &[(usize, Aggregation)]) -> Result<ColumnSheet> {
+        let _span = trace_span!(
+            "column_sheet_group_by",
+            rows = self.height,
+            aggs = aggs.len()
+        );
+
+        let key = self
+            .columns
+            .get(key_col)
+            .ok_or(Error::InvalidColumn(key_col))?;
+        let groups = Groups::of(key.as_ref());
+
+        let mut columns = Vec::with_capacity(aggs.len() + 1);
+        columns.push(groups.keys(key.as_ref()));
+
+        for &(col, aggregation) in aggs {
+            let column = self.columns.get(col).ok_or(Error::InvalidColumn(col))?;
+            let mut aggregated = groups.aggregate(column.as_ref(), col, aggregation)?;
+
+            let label = column
+                .label()
+                .map_or_else(|| col.to_string(), str::to_owned);
+            aggregated.set_header(format!("{aggregation}({label})"));
+            columns.push(aggregated);
         }
+
+        let width = columns.len();
+
+        Ok(Self {
+            columns,
+            primary: Some(0),
+            height: groups.len(),
+            null_string: self.null_string.clone(),
+            originals: vec![None; width],
+            meta: Vec::new(),
+            widths: Vec::new(),
+            report: LoadReport::default(),
+        })
+    }
+}
+
+/// Gathers the meta of `column` from its cells.
+#[cfg_attr(not(feature = "csv"), allow(dead_code))]
+fn column_meta(column: &dyn Column) -> ColumnMeta<CellValue> {
+    let values = (0..column.len())
+        .filter_map(|row| column.data_ref(row))
+        .filter(|cell| *cell != CellRef::None);
+
+    ColumnMeta::new(
+        column
+            .bounds()
+            .map(|(min, max)| (CellValue::from(min), CellValue::from(max))),
+        column.null_count(),
+        count_distinct(values.clone().map(|cell| cell.key())),
+        is_increasing(values),
+    )
+}
+
+/// Returns how `cell` is written as a CSV field with `options`.
+fn write_field(cell: CellRef<'_>, options: &WriteOptions) -> String {
+    match cell {
+        CellRef::I32(value) => value.to_string(),
+        CellRef::U32(value) => value.to_string(),
+        CellRef::ISize(value) => value.to_string(),
+        CellRef::USize(value) => value.to_string(),
+        CellRef::Bool(value) => value.to_string(),
+        CellRef::F32(value) => options.format_float(value),
+        CellRef::F64(value) => options.format_float(value),
+        CellRef::Text(value) => value.to_owned(),
+        CellRef::None => options.null_string.clone(),
+    }
+}
+
+/// Returns whether a field of `text` may be written as other text once read,
+/// so that [`Config::preserve_original`] keeps its text. Integers are also
+/// written differently when read as floats, as they are if `float` is true.
+#[cfg(feature = "csv")]
+fn may_rewrite(text: &str, float: bool) -> bool {
+    let options = WriteOptions::default();
+
+    if let Ok(value) = text.parse::<isize>() {
+        return float || value.to_string() != text;
+    }
+
+    if let Ok(value) = text.parse::<usize>() {
+        return float || value.to_string() != text;
+    }
+
+    match f64::parse_field(text) {
+        Some(value) => {
+            options.format_float(value) != text
+                || f32::parse_field(text).map_or(false, |value| options.format_float(value) != text)
+        }
+        None => false,
     }
 }
 
@@ -877,11 +2071,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (USIZE, false);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -901,11 +2095,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (ISIZE, prev.1);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -929,11 +2123,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (USIZE, false);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -953,11 +2147,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (USIZE, false);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -969,11 +2163,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (ISIZE, prev.1 || value < 0);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -989,11 +2183,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
                 return (USIZE, false);
             }
 
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -1004,11 +2198,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
             if value.parse::<usize>().is_ok() {
                 return (USIZE, false);
             }
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -1016,11 +2210,11 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
         }
 
         F32 => {
-            if value.parse::<f32>().is_ok() {
+            if f32::parse_field(value).is_some() {
                 return (F32, false);
             }
 
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -1028,7 +2222,7 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
         }
 
         F64 => {
-            if value.parse::<f64>().is_ok() {
+            if f64::parse_field(value).is_some() {
                 return (F64, false);
             }
 
@@ -1047,6 +2241,80 @@ fn infered_type(prev: (u8, bool), value: &str, null: &str) -> (u8, bool) {
     }
 }
 
+/// Returns the [`DataType`] corresponding to the type bits of [`infered_type`].
+fn infered_data_type(bits: u8) -> DataType {
+    match bits {
+        I32 => DataType::I32,
+        U32 => DataType::U32,
+        ISIZE => DataType::ISize,
+        USIZE => DataType::USize,
+        F32 => DataType::F32,
+        F64 => DataType::F64,
+        BOOL => DataType::Bool,
+        _ => DataType::Text,
+    }
+}
+
+/// Returns the type each of `cells` is read as when a column of mixed values
+/// is split by type, along with the types present in order of first
+/// appearance. Each cell is a value and its type bits from [`infered_type`].
+///
+/// The integers are read as the narrowest type holding all of them, as are
+/// the floats, so numbers are not split by their size. Integers which no
+/// single integer type holds are read as [`DataType::F64`]s, along with the
+/// other floats.
+fn split_kinds<S: AsRef<str>>(cells: &[Option<(S, u8)>]) -> (Vec<DataType>, Vec<Option<DataType>>) {
+    const INTEGERS: [u8; 4] = [I32, U32, ISIZE, USIZE];
+
+    // The narrowest type holding every value whose own type is in `family`
+    let widen = |family: &[u8]| {
+        cells
+            .iter()
+            .flatten()
+            .filter(|(_, bits)| family.contains(bits))
+            .fold((0, false), |prev, (value, _)| {
+                infered_type(prev, value.as_ref(), "")
+            })
+            .0
+    };
+
+    // Integers too wide for any integer type are read with the floats,
+    // as f64s to keep as many of their digits as possible
+    let (integer, float) = match widen(&INTEGERS) {
+        F32 | F64 => (F64, F64),
+        integer => (integer, widen(&[F32, F64])),
+    };
+
+    let kinds = cells
+        .iter()
+        .map(|cell| {
+            cell.as_ref().map(|(_, bits)| match *bits {
+                I32 | U32 | ISIZE | USIZE => infered_data_type(integer),
+                F32 | F64 => infered_data_type(float),
+                bits => infered_data_type(bits),
+            })
+        })
+        .collect::<Vec<Option<DataType>>>();
+
+    let mut order: Vec<DataType> = Vec::default();
+    for kind in kinds.iter().flatten() {
+        if !order.contains(kind) {
+            order.push(*kind);
+        }
+    }
+
+    (order, kinds)
+}
+
+/// Sheets are equal when they have the same headers, height and values.
+/// See [`ColumnSheet::logical_eq`].
+impl PartialEq for ColumnSheet {
+    fn eq(&self, other: &Self) -> bool {
+        self.logical_eq(other)
+    }
+}
+
+#[cfg(feature = "csv")]
 impl<P: AsRef<Path>> TryFrom<Config<P>> for ColumnSheet {
     type Error = Error;
 
@@ -1062,7 +2330,18 @@ fn parse_column(
     inferance: (u8, bool),
     null: &str,
 ) -> Box<dyn Column> {
+    let union = matches!(strategy, ColumnType::InferUnion(_));
+
     let text = |col: Vec<String>, header: Option<String>| {
+        if union {
+            if let Some(mut array) = ArrayUnion::parse_mixed(&col, null) {
+                if let Some(header) = header {
+                    array.set_header(header);
+                }
+                return boxed(array);
+            }
+        }
+
         let mut array = ArrayText::parse_str(&col, null);
         if let Some(header) = header {
             array.set_header(header);
@@ -1070,6 +2349,11 @@ fn parse_column(
         boxed(array)
     };
 
+    let strategy = match strategy {
+        ColumnType::InferUnion(try_infer) => ColumnType::Infer(try_infer),
+        strategy => strategy,
+    };
+
     match strategy {
         ColumnType::None => text(col, header),
 
@@ -1189,7 +2473,9 @@ fn parse_column(
             text(col, header)
         }
 
-        ColumnType::Type(CT::None) | ColumnType::Type(CT::Text) => text(col, header),
+        ColumnType::Type(CT::None) | ColumnType::Type(CT::Mixed) | ColumnType::Type(CT::Text) => {
+            text(col, header)
+        }
 
         ColumnType::Type(CT::Integer) => {
             if let Some(mut array) = ArrayI32::parse_str(&col, null) {
@@ -1255,22 +2541,185 @@ fn parse_column(
 
             text(col, header)
         }
+
+        ColumnType::InferUnion(_) => unreachable!("Union inference is mapped to inference above"),
+    }
+}
+
+/// Returns true if `value` can be read into a column of `kind`.
+#[cfg(feature = "csv")]
+fn conforms(value: &str, kind: CT, null: &str) -> bool {
+    match kind {
+        CT::Integer => {
+            parse_helper::<i32>(value, null).is_ok() || parse_helper::<u32>(value, null).is_ok()
+        }
+        CT::Number => {
+            parse_helper::<isize>(value, null).is_ok() || parse_helper::<usize>(value, null).is_ok()
+        }
+        CT::Float => parse_helper::<f64>(value, null).is_ok(),
+        CT::Boolean => parse_helper::<bool>(value, null).is_ok(),
+        CT::Text | CT::None | CT::Mixed => true,
     }
 }
 
+/// Replaces or casts the fields of `cols` which cannot be read into the
+/// column types in `kinds`, following `policy`. Under
+/// [`TypeMismatchPolicy::Fail`] the fields are left as is, so their columns
+/// are read as text.
+#[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+#[cfg(feature = "csv")]
+fn resolve_mismatches(
+    cols: &mut [Vec<String>],
+    kinds: &[CT],
+    policy: TypeMismatchPolicy,
+    null: &str,
+) {
+    if policy == TypeMismatchPolicy::Fail {
+        return;
+    }
+
+    for (col, (values, kind)) in cols.iter_mut().zip(kinds).enumerate() {
+        for (row, value) in values.iter_mut().enumerate() {
+            if conforms(value, *kind, null) {
+                continue;
+            }
+
+            let coerced = match policy {
+                TypeMismatchPolicy::Coerce => Data::from_csv_field(value, None).coerce(kind),
+                _ => None,
+            };
+            trace_debug!(
+                row,
+                col,
+                coerced = coerced.is_some(),
+                "cell does not match its column type"
+            );
+            *value = match coerced {
+                Some(Data::None) | None => null.to_owned(),
+                Some(data) => data.to_string(),
+            };
+        }
+    }
+}
+
+/// Replaces the `NaN` floats within `column` with nulls.
+#[cfg(feature = "csv")]
+fn null_nan(column: &mut dyn Column) {
+    let column = column.as_any_mut();
+
+    if let Some(column) = column.downcast_mut::<ArrayF32>() {
+        column
+            .iter_mut()
+            .filter(|cell| matches!(cell, Some(value) if value.is_nan()))
+            .for_each(|cell| *cell = None);
+    } else if let Some(column) = column.downcast_mut::<ArrayF64>() {
+        column
+            .iter_mut()
+            .filter(|cell| matches!(cell, Some(value) if value.is_nan()))
+            .for_each(|cell| *cell = None);
+    } else if let Some(column) = column.downcast_mut::<ArrayUnion>() {
+        column
+            .iter_mut()
+            .filter(|cell| match cell {
+                Some(UnionValue::F32(value)) => value.is_nan(),
+                Some(UnionValue::F64(value)) => value.is_nan(),
+                _ => false,
+            })
+            .for_each(|cell| *cell = None);
+    }
+}
+
+/// Parses `values` into a column of `kind`, if every value can be parsed.
+fn parse_as(values: &Vec<String>, kind: DataType, null: &str) -> Option<Box<dyn Column>> {
+    let column = match kind {
+        DataType::Text => boxed(ArrayText::parse_str(values, null)),
+        DataType::I32 => boxed(ArrayI32::parse_str(values, null)?),
+        DataType::U32 => boxed(ArrayU32::parse_str(values, null)?),
+        DataType::ISize => boxed(ArrayISize::parse_str(values, null)?),
+        DataType::USize => boxed(ArrayUSize::parse_str(values, null)?),
+        DataType::F32 => boxed(ArrayF32::parse_str(values, null)?),
+        DataType::F64 => boxed(ArrayF64::parse_str(values, null)?),
+        DataType::Bool => boxed(ArrayBool::parse_str(values, null)?),
+        DataType::Union => boxed(ArrayUnion::parse_str(values, null)),
+    };
+
+    Some(column)
+}
+
 fn boxed<T: Column>(value: T) -> Box<dyn Column> {
     Box::new(value)
 }
 
+/// The number of rows fetched from each column at once by [`Rows`].
+const ROW_BLOCK: usize = 64;
+
+/// Iterator over the rows of a [`ColumnSheet`], fetching the cells of each
+/// column in blocks of [`ROW_BLOCK`] rows.
+struct Rows<'a> {
+    columns: &'a [Box<dyn Column>],
+    next: usize,
+    end: usize,
+    /// The row of the first cell within each block
+    block_start: usize,
+    /// The current block of cells of each column
+    blocks: Vec<Vec<CellRef<'a>>>,
+}
+
+impl<'a> Rows<'a> {
+    fn fetch_block(&mut self) {
+        let block_end = self.end.min(self.next + ROW_BLOCK);
+        self.block_start = self.next;
+
+        for (column, block) in self.columns.iter().zip(self.blocks.iter_mut()) {
+            block.clear();
+            column.data_refs(self.next..block_end, block);
+        }
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Vec<CellRef<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        let fetched = self.blocks.first().map_or(0, Vec::len);
+        if self.next >= self.block_start + fetched {
+            self.fetch_block();
+        }
+
+        let offset = self.next - self.block_start;
+        self.next += 1;
+
+        Some(
+            self.blocks
+                .iter()
+                .map(|block| block.get(offset).copied().unwrap_or(CellRef::None))
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Rows<'a> {}
+
 mod error {
     #[allow(unused_imports)]
     use super::*;
-    use csv::Error as CSVError;
+    use crate::repr::SchemaDiff;
+    #[cfg(feature = "serde")]
+    use crate::repr::SnapshotError;
     use std::{error, fmt};
 
     #[derive(Debug)]
     pub enum Error {
-        CSV(CSVError),
+        CSV(CsvError),
         InvalidColumn(usize),
         InvalidRow(usize),
         InvalidPrimary(usize),
@@ -1292,14 +2741,74 @@ mod error {
             from: DataType,
             to: DataType,
         },
+        InvalidKindHint {
+            col: usize,
+            kind: DataType,
+        },
+        InvalidColumnKind {
+            col: usize,
+            expected: DataType,
+            found: DataType,
+        },
+        /// The columns selected to be read are invalid
+        SelectColumns(SelectColumnsError),
+        /// Another column already has the label
+        DuplicateHeader {
+            label: String,
+            existing_col: usize,
+        },
+        /// No column has the label
+        UnknownHeader(String),
+        /// A value which cannot be charted without overflowing
+        UnrepresentableValue {
+            col: usize,
+            row: usize,
+            kind: DataType,
+        },
+        /// The sheet read is not compatible with the schema expected. See
+        /// [`Config::expect_schema`]
+        SchemaMismatch(Box<SchemaDiff>),
+        /// The aggregation is not supported for the type of the column
+        InvalidAggregation {
+            col: usize,
+            aggregation: Aggregation,
+            kind: DataType,
+        },
+        /// The sum of a group of the column does not fit its type
+        AggregationOverflow {
+            col: usize,
+        },
+        /// Error from reading or writing a binary snapshot of the sheet
+        #[cfg(feature = "serde")]
+        Snapshot(SnapshotError),
     }
 
-    impl From<CSVError> for Error {
-        fn from(value: CSVError) -> Self {
+    impl From<CsvError> for Error {
+        fn from(value: CsvError) -> Self {
             Self::CSV(value)
         }
     }
 
+    #[cfg(feature = "csv")]
+    impl From<csv::Error> for Error {
+        fn from(value: csv::Error) -> Self {
+            Self::CSV(value.into())
+        }
+    }
+
+    impl From<SelectColumnsError> for Error {
+        fn from(value: SelectColumnsError) -> Self {
+            Self::SelectColumns(value)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl From<SnapshotError> for Error {
+        fn from(value: SnapshotError) -> Self {
+            Self::Snapshot(value)
+        }
+    }
+
     impl fmt::Display for Error {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -1325,16 +2834,64 @@ mod error {
                         "Invalid column conversion from {from} to {to} at column {col}"
                     )
                 }
+                Self::InvalidKindHint { col, kind } => {
+                    write!(f, "Cannot set a kind hint on column {col} of type {kind}")
+                }
+                Self::InvalidColumnKind {
+                    col,
+                    expected,
+                    found,
+                } => {
+                    write!(
+                        f,
+                        "Expected column {col} of type {expected}, but had {found}"
+                    )
+                }
+                Self::SelectColumns(error) => error.fmt(f),
+                Self::DuplicateHeader {
+                    label,
+                    existing_col,
+                } => {
+                    write!(
+                        f,
+                        "The header `{label}` is already used by column {existing_col}"
+                    )
+                }
+                Self::UnknownHeader(label) => write!(f, "No column has the header `{label}`"),
+                Self::SchemaMismatch(diff) => write!(f, "Schema mismatch: {diff}"),
+                Self::InvalidAggregation {
+                    col,
+                    aggregation,
+                    kind,
+                } => {
+                    write!(
+                        f,
+                        "Cannot aggregate column {col} of type {kind} with {aggregation}"
+                    )
+                }
+                Self::AggregationOverflow { col } => {
+                    write!(f, "The sum of a group of column {col} overflows")
+                }
+                Self::UnrepresentableValue { col, row, kind } => {
+                    write!(
+                        f,
+                        "Cannot chart the {kind} value at column: {col}, row: {row} without overflow"
+                    )
+                }
+                #[cfg(feature = "serde")]
+                Self::Snapshot(error) => error.fmt(f),
             }
         }
     }
 
     impl error::Error for Error {
         fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-            if let Self::CSV(error) = self {
-                error.source()
-            } else {
-                None
+            match self {
+                Self::CSV(error) => error.source(),
+                Self::SelectColumns(error) => Some(error),
+                #[cfg(feature = "serde")]
+                Self::Snapshot(error) => Some(error),
+                _ => None,
             }
         }
     }
@@ -1362,5 +2919,6 @@ fn index_sort_swap(indices: &mut [usize]) {
 mod arrays {
     pub use super::{
         ArrayBool, ArrayF32, ArrayF64, ArrayI32, ArrayISize, ArrayText, ArrayU32, ArrayUSize,
+        ArrayUnion, UnionValue,
     };
 }
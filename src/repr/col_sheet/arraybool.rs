@@ -1,9 +1,14 @@
+use std::ops::Range;
+
 use super::{arrays::*, parse_helper, parse_unchecked, utils::*, Iter, IterMut};
+use crate::repr::Unit;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ArrayBool {
     header: Option<String>,
     cells: Vec<Option<bool>>,
+    nulls: NullCount,
+    unit: Option<Unit>,
 }
 
 impl ArrayBool {
@@ -43,6 +48,7 @@ impl ArrayBool {
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, Option<bool>> {
+        self.nulls.invalidate();
         self.cells.iter_mut()
     }
 
@@ -55,19 +61,21 @@ impl ArrayBool {
         }
 
         Some(Self {
-            header: None,
             cells,
+            ..Default::default()
         })
     }
 }
 
 impl Sealed for ArrayBool {
     fn push(&mut self, value: &str, null: &str) {
+        self.nulls.invalidate();
         let parsed = parse_unchecked::<bool>(value, null);
         self.cells.push(parsed)
     }
 
     fn remove(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if idx >= self.len() {
             return;
         }
@@ -75,6 +83,7 @@ impl Sealed for ArrayBool {
     }
 
     fn insert(&mut self, value: &str, idx: usize, null: &str) {
+        self.nulls.invalidate();
         if idx > self.len() {
             return;
         }
@@ -90,7 +99,16 @@ impl Sealed for ArrayBool {
         }
     }
 
+    fn set_unit(&mut self, unit: Option<Unit>) {
+        self.unit = unit;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
     fn remove_all(&mut self) {
+        self.nulls.invalidate();
         self.cells.clear()
     }
 }
@@ -108,11 +126,24 @@ impl Column for ArrayBool {
         self.cells.len()
     }
 
+    fn null_count(&self) -> usize {
+        self.nulls.get(&self.cells)
+    }
+
+    fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
     fn set_header(&mut self, header: String) {
         self.header = Some(header);
     }
 
+    fn clear_header(&mut self) {
+        self.header = None;
+    }
+
     fn set_position(&mut self, value: &str, idx: usize, null: &str) -> bool {
+        self.nulls.invalidate();
         let Ok(parsed) = parse_helper::<bool>(value, null) else {
             return false;
         };
@@ -143,13 +174,19 @@ impl Column for ArrayBool {
         }
     }
 
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        cell_refs(&self.cells, rows, out, |value| CellRef::Bool(*value))
+    }
+
     fn clear(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if let Some(cell) = self.cells.get_mut(idx) {
             cell.take();
         }
     }
 
     fn clear_all(&mut self) {
+        self.nulls.invalidate();
         let len = self.cells.len();
 
         self.cells = vec![None; len];
@@ -164,6 +201,16 @@ impl Column for ArrayBool {
 
         match to {
             DataType::Bool => Box::new(self.clone()),
+            DataType::Union => {
+                let mut array =
+                    ArrayUnion::from_iterator_option(iter.map(|value| value.map(UnionValue::Bool)));
+
+                if let Some(header) = self.header.as_ref() {
+                    array.set_header(header.clone());
+                }
+
+                Box::new(array)
+            }
             DataType::U32 => {
                 let mut array = ArrayU32::from_iterator_option(
                     iter.map(|value| value.map(|value| value as u32)),
@@ -1,9 +1,14 @@
+use std::ops::Range;
+
 use super::{arrays::*, parse_helper, parse_unchecked, utils::*, Iter, IterMut};
+use crate::repr::Unit;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ArrayI32 {
     header: Option<String>,
     cells: Vec<Option<i32>>,
+    nulls: NullCount,
+    unit: Option<Unit>,
 }
 
 impl ArrayI32 {
@@ -43,6 +48,7 @@ impl ArrayI32 {
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, Option<i32>> {
+        self.nulls.invalidate();
         self.cells.iter_mut()
     }
 
@@ -55,19 +61,21 @@ impl ArrayI32 {
         }
 
         Some(Self {
-            header: None,
             cells,
+            ..Default::default()
         })
     }
 }
 
 impl Sealed for ArrayI32 {
     fn push(&mut self, value: &str, null: &str) {
+        self.nulls.invalidate();
         let parsed = parse_unchecked(value, null);
         self.cells.push(parsed)
     }
 
     fn remove(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if idx >= self.len() {
             return;
         }
@@ -75,6 +83,7 @@ impl Sealed for ArrayI32 {
     }
 
     fn insert(&mut self, value: &str, idx: usize, null: &str) {
+        self.nulls.invalidate();
         if idx > self.len() {
             return;
         }
@@ -90,7 +99,16 @@ impl Sealed for ArrayI32 {
         }
     }
 
+    fn set_unit(&mut self, unit: Option<Unit>) {
+        self.unit = unit;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
     fn remove_all(&mut self) {
+        self.nulls.invalidate();
         self.cells.clear()
     }
 }
@@ -112,11 +130,24 @@ impl Column for ArrayI32 {
         self.cells.len()
     }
 
+    fn null_count(&self) -> usize {
+        self.nulls.get(&self.cells)
+    }
+
+    fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
     fn set_header(&mut self, header: String) {
         self.header = Some(header);
     }
 
+    fn clear_header(&mut self) {
+        self.header = None;
+    }
+
     fn set_position(&mut self, value: &str, idx: usize, null: &str) -> bool {
+        self.nulls.invalidate();
         let Ok(parsed) = parse_helper::<i32>(value, null) else {
             return false;
         };
@@ -147,13 +178,19 @@ impl Column for ArrayI32 {
         }
     }
 
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        cell_refs(&self.cells, rows, out, |value| CellRef::I32(*value))
+    }
+
     fn clear(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if let Some(cell) = self.cells.get_mut(idx) {
             cell.take();
         }
     }
 
     fn clear_all(&mut self) {
+        self.nulls.invalidate();
         let len = self.cells.len();
 
         self.cells = vec![None; len];
@@ -164,6 +201,16 @@ impl Column for ArrayI32 {
 
         match to {
             DataType::I32 => Box::new(self.clone()),
+            DataType::Union => {
+                let mut array =
+                    ArrayUnion::from_iterator_option(iter.map(|value| value.map(UnionValue::I32)));
+
+                if let Some(header) = self.header.as_ref() {
+                    array.set_header(header.clone());
+                }
+
+                Box::new(array)
+            }
             DataType::U32 => {
                 let mut array = ArrayU32::from_iterator_option(
                     iter.map(|value| value.map(|value| value as u32)),
@@ -1,9 +1,15 @@
-use super::{arrays::*, parse_helper, utils::*, Iter, IterMut};
+use std::ops::Range;
+
+use super::{arrays::*, infered_type, parse_helper, split_kinds, utils::*, Iter, IterMut};
+use crate::repr::{sheet::utils::ParseField, ColumnType, Unit};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ArrayText {
     header: Option<String>,
     cells: Vec<Option<String>>,
+    nulls: NullCount,
+    unit: Option<Unit>,
+    kind_hint: Option<ColumnType>,
 }
 
 impl ArrayText {
@@ -47,6 +53,7 @@ impl ArrayText {
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, Option<String>> {
+        self.nulls.invalidate();
         self.cells.iter_mut()
     }
 
@@ -60,19 +67,65 @@ impl ArrayText {
         }
 
         Self {
-            header: None,
             cells,
+            ..Default::default()
         }
     }
+
+    /// Splits a column of mixed values into one typed [`Column`] per type
+    /// present, in order of first appearance.
+    ///
+    /// The integers of the column are read as the narrowest type holding
+    /// all of them, as are its floats, so a column of numbers is not split
+    /// by their size. Integers which no single integer type holds are read
+    /// as [`DataType::F64`]s, along with the other floats. Every returned
+    /// column has the same length as `self`, with [`None`] wherever the cell
+    /// held a value of another type. Values which parse to no narrower type
+    /// are kept in a [`DataType::Text`] column.
+    pub fn split_by_type(&self) -> Vec<(DataType, Box<dyn Column>)> {
+        let parsed = self
+            .cells
+            .iter()
+            .map(|cell| {
+                cell.as_ref()
+                    .map(|value| (value.as_str(), infered_type((0, false), value, "").0))
+            })
+            .collect::<Vec<Option<(&str, u8)>>>();
+
+        let (order, kinds) = split_kinds(&parsed);
+
+        order
+            .into_iter()
+            .map(|kind| {
+                let mut array = Self::from_iterator_option(self.cells.iter().zip(&kinds).map(
+                    |(cell, cell_kind)| {
+                        if cell_kind == &Some(kind) {
+                            cell.clone()
+                        } else {
+                            None
+                        }
+                    },
+                ));
+
+                if let Some(header) = self.header.as_ref() {
+                    array.set_header(header.clone());
+                }
+
+                (kind, array.convert_col(kind))
+            })
+            .collect()
+    }
 }
 
 impl Sealed for ArrayText {
     fn push(&mut self, value: &str, null: &str) {
+        self.nulls.invalidate();
         let parsed = parse_helper(value, null).unwrap_or_default();
         self.cells.push(parsed)
     }
 
     fn remove(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if idx >= self.len() {
             return;
         }
@@ -80,6 +133,7 @@ impl Sealed for ArrayText {
     }
 
     fn insert(&mut self, value: &str, idx: usize, null: &str) {
+        self.nulls.invalidate();
         if idx > self.len() {
             return;
         }
@@ -94,7 +148,21 @@ impl Sealed for ArrayText {
         }
     }
 
+    fn set_kind_hint(&mut self, kind: Option<ColumnType>) -> bool {
+        self.kind_hint = kind;
+        true
+    }
+
+    fn set_unit(&mut self, unit: Option<Unit>) {
+        self.unit = unit;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
     fn remove_all(&mut self) {
+        self.nulls.invalidate();
         self.cells.clear()
     }
 }
@@ -104,6 +172,18 @@ impl Column for ArrayText {
         self.cells.len()
     }
 
+    fn null_count(&self) -> usize {
+        self.nulls.get(&self.cells)
+    }
+
+    fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    fn kind_hint(&self) -> Option<ColumnType> {
+        self.kind_hint
+    }
+
     fn kind(&self) -> DataType {
         DataType::Text
     }
@@ -116,7 +196,12 @@ impl Column for ArrayText {
         self.set_header(header);
     }
 
+    fn clear_header(&mut self) {
+        self.header = None;
+    }
+
     fn set_position(&mut self, value: &str, idx: usize, null: &str) -> bool {
+        self.nulls.invalidate();
         let Ok(parsed) = parse_helper::<String>(value, null) else {
             return false;
         };
@@ -147,13 +232,19 @@ impl Column for ArrayText {
         }
     }
 
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        cell_refs(&self.cells, rows, out, |value| CellRef::Text(value))
+    }
+
     fn clear(&mut self, idx: usize) {
+        self.nulls.invalidate();
         if let Some(cell) = self.cells.get_mut(idx) {
             cell.take();
         }
     }
 
     fn clear_all(&mut self) {
+        self.nulls.invalidate();
         let len = self.cells.len();
 
         self.cells = vec![None; len];
@@ -168,6 +259,17 @@ impl Column for ArrayText {
 
         match to {
             DataType::Text => Box::new(self.clone()),
+            DataType::Union => {
+                let mut array = ArrayUnion::from_iterator_option(
+                    iter.map(|value| value.as_deref().map(UnionValue::parse)),
+                );
+
+                if let Some(header) = self.header.as_ref() {
+                    array.set_header(header.clone());
+                }
+
+                Box::new(array)
+            }
             DataType::U32 => {
                 let mut array = ArrayU32::from_iterator_option(
                     iter.map(|value| value.as_ref().and_then(|value| value.parse::<u32>().ok())),
@@ -203,7 +305,7 @@ impl Column for ArrayText {
             }
             DataType::F32 => {
                 let mut array = ArrayF32::from_iterator_option(
-                    iter.map(|value| value.as_ref().and_then(|value| value.parse::<f32>().ok())),
+                    iter.map(|value| value.as_deref().and_then(f32::parse_field)),
                 );
 
                 if let Some(header) = self.header.as_ref() {
@@ -214,7 +316,7 @@ impl Column for ArrayText {
             }
             DataType::F64 => {
                 let mut array = ArrayF64::from_iterator_option(
-                    iter.map(|value| value.as_ref().and_then(|value| value.parse::<f64>().ok())),
+                    iter.map(|value| value.as_deref().and_then(f64::parse_field)),
                 );
 
                 if let Some(header) = self.header.as_ref() {
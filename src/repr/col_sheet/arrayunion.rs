@@ -0,0 +1,431 @@
+use std::{fmt, ops::Range};
+
+use super::{
+    arrays::*, infered_data_type, infered_type, parse_helper, split_kinds, utils::*, Iter, IterMut,
+    TEXT,
+};
+use crate::repr::{sheet::error::Error as SheetError, Data, Unit};
+
+/// A single value within an [`ArrayUnion`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnionValue {
+    I32(i32),
+    U32(u32),
+    ISize(isize),
+    USize(usize),
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    Text(String),
+}
+
+impl UnionValue {
+    /// Parses `value` as the narrowest type it fits, following the same order
+    /// as type inference. Values which fit no other type are kept as text.
+    pub fn parse(value: &str) -> Self {
+        let kind = infered_data_type(infered_type((0, false), value, "").0);
+
+        let parsed = match kind {
+            DataType::I32 => value.parse().ok().map(Self::I32),
+            DataType::U32 => value.parse().ok().map(Self::U32),
+            DataType::ISize => value.parse().ok().map(Self::ISize),
+            DataType::USize => value.parse().ok().map(Self::USize),
+            DataType::Bool => value.parse().ok().map(Self::Bool),
+            DataType::F32 => value.parse().ok().map(Self::F32),
+            DataType::F64 => value.parse().ok().map(Self::F64),
+            DataType::Text | DataType::Union => None,
+        };
+
+        parsed.unwrap_or_else(|| Self::Text(value.to_string()))
+    }
+
+    /// Returns the type of the value.
+    pub fn kind(&self) -> DataType {
+        match self {
+            Self::I32(_) => DataType::I32,
+            Self::U32(_) => DataType::U32,
+            Self::ISize(_) => DataType::ISize,
+            Self::USize(_) => DataType::USize,
+            Self::Bool(_) => DataType::Bool,
+            Self::F32(_) => DataType::F32,
+            Self::F64(_) => DataType::F64,
+            Self::Text(_) => DataType::Text,
+        }
+    }
+
+    fn as_cell_ref(&self) -> CellRef<'_> {
+        match self {
+            Self::I32(value) => CellRef::I32(*value),
+            Self::U32(value) => CellRef::U32(*value),
+            Self::ISize(value) => CellRef::ISize(*value),
+            Self::USize(value) => CellRef::USize(*value),
+            Self::Bool(value) => CellRef::Bool(*value),
+            Self::F32(value) => CellRef::F32(*value),
+            Self::F64(value) => CellRef::F64(*value),
+            Self::Text(value) => CellRef::Text(value),
+        }
+    }
+}
+
+/// [`Data::None`] becomes [`None`], the null of an [`ArrayUnion`].
+impl From<Data> for Option<UnionValue> {
+    fn from(value: Data) -> Self {
+        match value {
+            Data::Text(value) => Some(UnionValue::Text(value)),
+            Data::Integer(value) => Some(UnionValue::I32(value)),
+            Data::Float(value) => Some(UnionValue::F32(value)),
+            Data::Number(value) => Some(UnionValue::ISize(value)),
+            Data::Boolean(value) => Some(UnionValue::Bool(value)),
+            Data::None => None,
+        }
+    }
+}
+
+/// `U32` and `USize` values widen to [`Data::Number`] and `F64` values narrow
+/// to [`Data::Float`]. Values which would not survive the conversion exactly
+/// return an [`SheetError::ConversionError`].
+impl TryFrom<UnionValue> for Data {
+    type Error = SheetError;
+
+    fn try_from(value: UnionValue) -> Result<Self, Self::Error> {
+        cell_data(value.as_cell_ref()).ok_or_else(|| {
+            SheetError::ConversionError(format!(
+                "{} value {} does not fit in Data",
+                value.kind(),
+                value
+            ))
+        })
+    }
+}
+
+impl fmt::Display for UnionValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::I32(value) => value.fmt(f),
+            Self::U32(value) => value.fmt(f),
+            Self::ISize(value) => value.fmt(f),
+            Self::USize(value) => value.fmt(f),
+            Self::Bool(value) => value.fmt(f),
+            Self::F32(value) => value.fmt(f),
+            Self::F64(value) => value.fmt(f),
+            Self::Text(value) => value.fmt(f),
+        }
+    }
+}
+
+/// A column whose cells may each hold a value of a different type.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ArrayUnion {
+    header: Option<String>,
+    cells: Vec<Option<UnionValue>>,
+    nulls: NullCount,
+    unit: Option<Unit>,
+}
+
+impl ArrayUnion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_iterator(values: impl Iterator<Item = UnionValue>) -> Self {
+        Self {
+            cells: values.map(Some).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn from_iterator_option(values: impl Iterator<Item = Option<UnionValue>>) -> Self {
+        Self {
+            cells: values.collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_header(&mut self, header: String) -> &mut Self {
+        self.header = Some(header);
+        self
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&UnionValue> {
+        self.cells.get(idx)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut UnionValue> {
+        self.cells.get_mut(idx)?.as_mut()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Option<UnionValue>> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Option<UnionValue>> {
+        self.nulls.invalidate();
+        self.cells.iter_mut()
+    }
+
+    /// Parses each value individually. See [`UnionValue::parse`].
+    pub fn parse_str(values: &[String], null: &str) -> Self {
+        let cells = values
+            .iter()
+            .map(|value| parse_helper::<String>(value, null).unwrap_or_default())
+            .map(|value| value.as_deref().map(UnionValue::parse))
+            .collect();
+
+        Self {
+            cells,
+            ..Default::default()
+        }
+    }
+
+    /// Parses `values` like [`ArrayUnion::parse_str`], returning [`None`] if
+    /// the values do not have more than one type between them.
+    pub(super) fn parse_mixed(values: &[String], null: &str) -> Option<Self> {
+        let array = Self::parse_str(values, null);
+
+        if array.kinds().len() > 1 {
+            Some(array)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the types of the values within the column, in order of first
+    /// appearance.
+    pub fn kinds(&self) -> Vec<DataType> {
+        let mut kinds = Vec::default();
+
+        for kind in self.cells.iter().flatten().map(UnionValue::kind) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        kinds
+    }
+
+    /// Splits the column into one typed [`Column`] per type present, in
+    /// order of first appearance.
+    ///
+    /// Numbers are widened within their family as by
+    /// [`ArrayText::split_by_type`], so a column of numbers is not split by
+    /// their size. Every returned column has the same length as `self`, with
+    /// [`None`] wherever the cell held a value of another type.
+    pub fn split_by_type(&self) -> Vec<(DataType, Box<dyn Column>)> {
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| {
+                cell.as_ref().map(|value| match value {
+                    UnionValue::Text(value) => (value.clone(), TEXT),
+                    value => {
+                        let value = value.to_string();
+                        let bits = infered_type((0, false), &value, "").0;
+                        (value, bits)
+                    }
+                })
+            })
+            .collect::<Vec<Option<(String, u8)>>>();
+
+        let (order, kinds) = split_kinds(&cells);
+
+        order
+            .into_iter()
+            .map(|kind| {
+                let cells = cells
+                    .iter()
+                    .zip(&kinds)
+                    .map(|(cell, cell_kind)| match cell {
+                        Some((value, _)) if cell_kind == &Some(kind) => Some(value.clone()),
+                        _ => None,
+                    });
+
+                (kind, self.text(cells).convert_col(kind))
+            })
+            .collect()
+    }
+
+    /// Converts the column to a column of `to`, widening each value within
+    /// its family as [`DataType::can_convert`] allows, such as `I32`s and
+    /// `ISize`s to [`DataType::ISize`]. Any number can be widened to
+    /// [`DataType::F64`], though integers beyond 2^53 lose precision.
+    ///
+    /// Every value converts to [`DataType::Text`]. For other types, returns
+    /// [`None`] if any value cannot be widened to `to`, such as a text value
+    /// or an `F32` when `to` is an integer type.
+    pub fn coerce_to(&self, to: DataType) -> Option<Box<dyn Column>> {
+        match to {
+            DataType::Union | DataType::Text => return Some(self.convert_col(to)),
+            _ => {}
+        }
+
+        let widens = self
+            .cells
+            .iter()
+            .flatten()
+            .all(|value| match (value.kind(), to) {
+                (kind @ (DataType::Bool | DataType::Text), to) => DataType::can_convert(kind, to),
+                (_, DataType::F64) => true,
+                (kind, to) => DataType::can_convert(kind, to),
+            });
+
+        if widens {
+            Some(self.convert_col(to))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an [`ArrayText`] of `cells` with the header of `self`.
+    fn text(&self, cells: impl Iterator<Item = Option<String>>) -> ArrayText {
+        let mut array = ArrayText::from_iterator_option(cells);
+
+        if let Some(header) = self.header.as_ref() {
+            array.set_header(header.clone());
+        }
+
+        array
+    }
+}
+
+impl Sealed for ArrayUnion {
+    fn push(&mut self, value: &str, null: &str) {
+        self.nulls.invalidate();
+        let parsed = parse_helper::<String>(value, null).unwrap_or_default();
+        self.cells.push(parsed.as_deref().map(UnionValue::parse))
+    }
+
+    fn remove(&mut self, idx: usize) {
+        self.nulls.invalidate();
+        if idx >= self.len() {
+            return;
+        }
+        self.cells.remove(idx);
+    }
+
+    fn insert(&mut self, value: &str, idx: usize, null: &str) {
+        self.nulls.invalidate();
+        if idx > self.len() {
+            return;
+        }
+
+        let parsed = parse_helper::<String>(value, null).unwrap_or_default();
+
+        self.cells
+            .insert(idx, parsed.as_deref().map(UnionValue::parse));
+    }
+
+    fn apply_index_swap(&mut self, indices: &[usize]) {
+        for (pos, elem) in indices.iter().enumerate() {
+            self.cells.swap(pos, *elem);
+        }
+    }
+
+    fn set_unit(&mut self, unit: Option<Unit>) {
+        self.unit = unit;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn remove_all(&mut self) {
+        self.nulls.invalidate();
+        self.cells.clear()
+    }
+}
+
+impl Column for ArrayUnion {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn label(&self) -> Option<&str> {
+        self.header.as_deref()
+    }
+
+    fn kind(&self) -> DataType {
+        DataType::Union
+    }
+
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.get(&self.cells)
+    }
+
+    fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+
+    fn set_header(&mut self, header: String) {
+        self.header = Some(header);
+    }
+
+    fn clear_header(&mut self) {
+        self.header = None;
+    }
+
+    fn set_position(&mut self, value: &str, idx: usize, null: &str) -> bool {
+        self.nulls.invalidate();
+        // Always successful
+        let parsed = parse_helper::<String>(value, null).unwrap_or_default();
+
+        if let Some(prev) = self.cells.get_mut(idx) {
+            *prev = parsed.as_deref().map(UnionValue::parse);
+        }
+
+        true
+    }
+
+    fn swap(&mut self, x: usize, y: usize) {
+        if x >= self.len() || y >= self.len() {
+            return;
+        }
+
+        self.cells.swap(x, y);
+    }
+
+    fn data_ref(&self, idx: usize) -> Option<CellRef<'_>> {
+        match self.cells.get(idx)? {
+            Some(value) => Some(value.as_cell_ref()),
+            None => Some(CellRef::None),
+        }
+    }
+
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        cell_refs(&self.cells, rows, out, UnionValue::as_cell_ref)
+    }
+
+    fn clear(&mut self, idx: usize) {
+        self.nulls.invalidate();
+        if let Some(cell) = self.cells.get_mut(idx) {
+            cell.take();
+        }
+    }
+
+    fn clear_all(&mut self) {
+        self.nulls.invalidate();
+        let len = self.cells.len();
+
+        self.cells = vec![None; len];
+    }
+
+    /// Converts through the text of each value, so values which cannot be
+    /// parsed as `to` become [`None`].
+    fn convert_col(&self, to: DataType) -> Box<dyn Column> {
+        match to {
+            DataType::Union => Box::new(self.clone()),
+            to => {
+                let cells = self
+                    .cells
+                    .iter()
+                    .map(|cell| cell.as_ref().map(ToString::to_string));
+
+                self.text(cells).convert_col(to)
+            }
+        }
+    }
+}
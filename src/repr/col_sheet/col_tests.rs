@@ -1,9 +1,14 @@
 #![cfg(test)]
 use super::{
-    index_sort_swap, ArrayI32, ArrayText, CellRef, Column, ColumnHeader, ColumnSheet, Config,
-    DataType, HeaderStrategy, TypesStrategy,
+    index_sort_swap, Aggregation, ArrayI32, ArrayText, ArrayU32, ArrayUSize, ArrayUnion, CellRef,
+    CellValue, Column, ColumnHeader, ColumnSheet, Config, DataType, Error, HeaderStrategy,
+    LineTerminator, SelectColumns, TypeMismatchPolicy, TypesStrategy, UnionValue,
+    UnrepresentableCell, UnsignedOverflow, WriteOptions,
+};
+use crate::{
+    models::ScaleKind,
+    repr::{ColumnType, Data, Sheet, Unit},
 };
-use crate::repr::ColumnType;
 use proptest::{arbitrary::any, collection, proptest, strategy::Strategy};
 
 const OVERKILL_PROPTEST: bool = false;
@@ -107,22 +112,13 @@ fn headerless() {
     let sht = ColumnSheet::with_config(builder).unwrap();
     let headers = sht.headers();
     let expected = [
-        ColumnHeader {
-            kind: DataType::Text,
-            header: None,
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: None,
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: None,
-        },
+        (DataType::Text, None),
+        (DataType::I32, None),
+        (DataType::I32, None),
     ];
 
     for (expected, actual) in expected.into_iter().zip(headers) {
-        assert_eq!(expected, actual);
+        assert_eq!(expected, (actual.kind, actual.header));
     }
 }
 
@@ -176,7 +172,14 @@ fn test_empty() {
         empty.headers().next().unwrap(),
         ColumnHeader {
             kind: DataType::I32,
-            header: None
+            header: None,
+            idx: 0,
+            is_primary: false,
+            null_count: 0,
+            height: 1,
+            kind_hint: None,
+            unit: None,
+            is_unique: true,
         }
     );
 
@@ -223,6 +226,28 @@ fn test_empty() {
     assert!(empty.headers().next().is_some());
 }
 
+#[test]
+fn test_blank() {
+    let empty = create_empty();
+    assert!(!empty.has_rows());
+    assert!(!empty.has_data());
+    assert!(empty.is_blank());
+
+    let mut sht = create_air_csv();
+    assert!(sht.has_rows());
+    assert!(sht.has_data());
+    assert!(!sht.is_blank());
+
+    for col in 0..sht.width() {
+        sht.clear_col(col).unwrap();
+    }
+
+    assert!(sht.has_rows());
+    assert!(!sht.is_empty());
+    assert!(!sht.has_data());
+    assert!(sht.is_blank());
+}
+
 #[test]
 fn test_primary_key() {
     let mut sht = create_air_csv();
@@ -279,6 +304,13 @@ fn test_primary_key() {
     let mut sht = create_air_csv();
     sht.clear_primary();
     assert_eq!(None, sht.get_primary());
+
+    // Read without a primary
+    let config = Config::new("./dummies/csv/air.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .primary(None);
+    let sht = ColumnSheet::with_config(config).unwrap();
+    assert_eq!(None, sht.get_primary());
 }
 
 #[test]
@@ -520,55 +552,1651 @@ fn test_headers() {
     let mut sht = create_air_csv();
     let actuals = sht.headers();
     let headers = [
-        ColumnHeader {
-            kind: DataType::Text,
-            header: Some("Month"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("1958"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("1959"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("1960"),
-        },
+        (DataType::Text, Some("Month")),
+        (DataType::I32, Some("1958")),
+        (DataType::I32, Some("1959")),
+        (DataType::I32, Some("1960")),
     ];
 
     assert_eq!(4, actuals.len());
 
     for (expected, actual) in headers.into_iter().zip(actuals) {
-        assert_eq!(expected, actual);
+        assert_eq!(expected, (actual.kind, actual.header));
     }
 
     assert!(sht.set_col_header(100, "Failure").is_err());
     assert!(sht.set_col_header(1, "Success").is_ok());
 
     let headers = [
-        ColumnHeader {
+        (DataType::Text, Some("Month")),
+        (DataType::I32, Some("Success")),
+        (DataType::I32, Some("1959")),
+        (DataType::I32, Some("1960")),
+    ];
+
+    for (expected, actual) in headers.into_iter().zip(sht.headers()) {
+        assert_eq!(expected, (actual.kind, actual.header));
+    }
+}
+
+#[test]
+fn test_header_view() {
+    let mut sht = create_air_csv();
+    let height = sht.height();
+
+    assert!(sht.header_at(4).is_none());
+    assert_eq!(
+        sht.header_at(0),
+        Some(ColumnHeader {
             kind: DataType::Text,
             header: Some("Month"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("Success"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("1959"),
-        },
-        ColumnHeader {
-            kind: DataType::I32,
-            header: Some("1960"),
-        },
+            idx: 0,
+            is_primary: true,
+            null_count: 0,
+            height,
+            kind_hint: None,
+            unit: None,
+            is_unique: true,
+        })
+    );
+
+    let nulls = |sht: &ColumnSheet| sht.headers().map(|hdr| hdr.null_count).collect::<Vec<_>>();
+    assert_eq!(nulls(&sht), vec![0, 0, 0, 0]);
+
+    sht.clear_cell(1, 0).unwrap();
+    assert_eq!(nulls(&sht), vec![0, 1, 0, 0]);
+
+    sht.push_row(vec!["DEC", "", "", "1"].into_iter()).unwrap();
+    assert_eq!(nulls(&sht), vec![0, 2, 1, 0]);
+    assert_eq!(sht.header_at(1).unwrap().height, height + 1);
+
+    sht.remove_row(0).unwrap();
+    assert_eq!(nulls(&sht), vec![0, 1, 1, 0]);
+
+    sht.set_cell("5", 1, height - 1).unwrap();
+    assert_eq!(nulls(&sht), vec![0, 0, 1, 0]);
+
+    sht.clear_row(0).unwrap();
+    assert_eq!(nulls(&sht), vec![1, 1, 2, 1]);
+
+    sht.clear_col(3).unwrap();
+    assert_eq!(nulls(&sht), vec![1, 1, 2, height]);
+
+    sht.swap_cols(2, 3).unwrap();
+    assert_eq!(nulls(&sht), vec![1, 1, height, 2]);
+
+    sht.set_primary(1).unwrap();
+    assert!(!sht.header_at(0).unwrap().is_primary);
+    assert!(sht.header_at(1).unwrap().is_primary);
+
+    let mut column = ArrayI32::from_iterator([1, 2, 3].into_iter());
+    assert_eq!(column.null_count(), 0);
+    column.iter_mut().for_each(|cell| *cell = None);
+    assert_eq!(column.null_count(), 3);
+}
+
+#[test]
+fn test_kind_hint() {
+    let mut sht = create_air_csv();
+
+    assert!(sht.set_col_kind_hint(1, Some(ColumnType::Float)).is_err());
+    assert!(sht.set_col_kind_hint(9, Some(ColumnType::Float)).is_err());
+
+    sht.set_col_kind_hint(0, Some(ColumnType::Integer)).unwrap();
+    assert_eq!(
+        sht.header_at(0).unwrap().kind_hint,
+        Some(ColumnType::Integer)
+    );
+    assert_eq!(sht.header_at(0).unwrap().kind, DataType::Text);
+
+    sht.swap_cols(0, 2).unwrap();
+    assert_eq!(
+        sht.header_at(2).unwrap().kind_hint,
+        Some(ColumnType::Integer)
+    );
+
+    sht.set_col_kind_hint(2, None).unwrap();
+    assert_eq!(sht.header_at(2).unwrap().kind_hint, None);
+}
+
+#[test]
+fn test_col_unit() {
+    let mut sht = create_air_csv();
+
+    assert!(sht.set_col_unit(9, Some(Unit::Percent)).is_err());
+    sht.set_col_unit(1, Some(Unit::Percent)).unwrap();
+    assert_eq!(sht.header_at(1).unwrap().unit, Some(&Unit::Percent));
+    assert_eq!(sht.header_at(2).unwrap().unit, None);
+
+    sht.swap_cols(1, 3).unwrap();
+    assert_eq!(sht.header_at(3).unwrap().unit, Some(&Unit::Percent));
+
+    sht.convert_col(3, DataType::F64).unwrap();
+    assert_eq!(sht.header_at(3).unwrap().kind, DataType::F64);
+    assert_eq!(sht.header_at(3).unwrap().unit, Some(&Unit::Percent));
+
+    sht.set_col_unit(3, None).unwrap();
+    assert_eq!(sht.header_at(3).unwrap().unit, None);
+}
+
+#[test]
+fn test_ragged_null_padding() {
+    let config = |path, labels| {
+        Config::new(path)
+            .flexible(true)
+            .null_string("na")
+            .types(TypesStrategy::Infer)
+            .labels(labels)
+    };
+
+    let heights = |sht: &ColumnSheet| sht.iter().map(|col| col.len()).collect::<Vec<usize>>();
+    let kinds = |sht: &ColumnSheet| sht.headers().map(|hdr| hdr.kind).collect::<Vec<_>>();
+    let nulls = |sht: &ColumnSheet| sht.headers().map(|hdr| hdr.null_count).collect::<Vec<_>>();
+
+    // Short rows followed by a longer one
+    let sht = ColumnSheet::with_config(config(
+        "./dummies/csv/ragged_grow.csv",
+        HeaderStrategy::ReadLabels,
+    ))
+    .unwrap();
+    assert_eq!(sht.height(), 3);
+    assert_eq!(heights(&sht), vec![3, 3, 3]);
+    assert_eq!(kinds(&sht), vec![DataType::I32; 3]);
+    assert_eq!(nulls(&sht), vec![0, 1, 2]);
+    assert_eq!(sht.get_cell(2, 2), Some(CellRef::I32(6)));
+
+    // A long row followed by shorter ones, with more headers than values
+    let headers = ["w", "x", "y", "z"].map(String::from).to_vec();
+    let sht = ColumnSheet::with_config(config(
+        "./dummies/csv/ragged_shrink.csv",
+        HeaderStrategy::Provided(headers),
+    ))
+    .unwrap();
+    assert_eq!(sht.height(), 3);
+    // Headers without values are kept, with columns of nulls
+    assert_eq!(heights(&sht), vec![3, 3, 3, 3]);
+    assert_eq!(nulls(&sht), vec![0, 2, 2, 3]);
+    assert_eq!(
+        kinds(&sht),
+        vec![DataType::I32, DataType::I32, DataType::I32, DataType::Text]
+    );
+    assert_eq!(sht.get_cell(1, 1), Some(CellRef::None));
+}
+
+#[test]
+fn test_split_by_type() {
+    let mut mixed = ArrayText::from_iterator_option(
+        vec![
+            Some("417".to_string()),
+            Some("Nothing".to_string()),
+            None,
+            Some("-3".to_string()),
+            Some("true".to_string()),
+        ]
+        .into_iter(),
+    );
+    mixed.set_header("Mixed");
+
+    let text = mixed.convert_col(DataType::Text);
+    for idx in 0..mixed.len() {
+        assert_eq!(mixed.data_ref(idx), text.data_ref(idx));
+    }
+
+    let split = mixed.split_by_type();
+    let kinds = split
+        .iter()
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<DataType>>();
+    assert_eq!(kinds, vec![DataType::I32, DataType::Text, DataType::Bool]);
+
+    let (_, ints) = &split[0];
+    assert_eq!(ints.label(), Some("Mixed"));
+    assert_eq!(ints.len(), 5);
+    assert_eq!(ints.data_ref(0), Some(CellRef::I32(417)));
+    assert_eq!(ints.data_ref(1), Some(CellRef::None));
+    assert_eq!(ints.data_ref(2), Some(CellRef::None));
+    assert_eq!(ints.data_ref(3), Some(CellRef::I32(-3)));
+    assert_eq!(ints.data_ref(4), Some(CellRef::None));
+
+    let (_, texts) = &split[1];
+    assert_eq!(texts.data_ref(0), Some(CellRef::None));
+    assert_eq!(texts.data_ref(1), Some(CellRef::Text("Nothing")));
+
+    let (_, bools) = &split[2];
+    assert_eq!(bools.data_ref(4), Some(CellRef::Bool(true)));
+    assert_eq!(bools.data_ref(0), Some(CellRef::None));
+
+    // Numbers are widened within their family rather than split by size
+    let numbers = ArrayText::from_iterator(
+        ["1", "-5000000000", "2.5", "1e300", "3", "yes"]
+            .into_iter()
+            .map(String::from),
+    );
+    let split = numbers.split_by_type();
+    let kinds = split
+        .iter()
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<DataType>>();
+    assert_eq!(kinds, vec![DataType::ISize, DataType::F64, DataType::Text]);
+
+    let (_, ints) = &split[0];
+    assert_eq!(ints.data_ref(0), Some(CellRef::ISize(1)));
+    assert_eq!(ints.data_ref(1), Some(CellRef::ISize(-5_000_000_000)));
+    assert_eq!(ints.data_ref(4), Some(CellRef::ISize(3)));
+
+    let (_, floats) = &split[1];
+    assert_eq!(floats.data_ref(2), Some(CellRef::F64(2.5)));
+    assert_eq!(floats.data_ref(3), Some(CellRef::F64(1e300)));
+
+    // Integers no integer type holds are read with the floats
+    let numbers = ArrayText::from_iterator(
+        ["-1", "18446744073709551615", "0.5"]
+            .into_iter()
+            .map(String::from),
+    );
+    let split = numbers.split_by_type();
+    let kinds = split
+        .iter()
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<DataType>>();
+    assert_eq!(kinds, vec![DataType::F64]);
+
+    let (_, floats) = &split[0];
+    assert_eq!(floats.data_ref(0), Some(CellRef::F64(-1.0)));
+    assert_eq!(
+        floats.data_ref(1),
+        Some(CellRef::F64(18_446_744_073_709_551_615.0))
+    );
+    assert_eq!(floats.data_ref(2), Some(CellRef::F64(0.5)));
+}
+
+#[test]
+fn test_union_inference() {
+    let config = |types| {
+        Config::new("./dummies/csv/mixed.csv")
+            .trim(true)
+            .types(types)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+
+    let sht = ColumnSheet::with_config(config(TypesStrategy::Infer)).unwrap();
+    assert_eq!(sht.get_col(2).unwrap().kind(), DataType::Text);
+
+    let sht = ColumnSheet::with_config(config(TypesStrategy::InferWithUnion)).unwrap();
+    let kinds = sht.headers().map(|header| header.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            DataType::I32,
+            DataType::Text,
+            DataType::Union,
+            DataType::Text
+        ]
+    );
+
+    assert_eq!(sht.get_cell(2, 0), Some(CellRef::F32(1.5)));
+    assert_eq!(sht.get_cell(2, 1), Some(CellRef::I32(3)));
+    assert_eq!(sht.get_cell(2, 2), Some(CellRef::Bool(true)));
+
+    let union = sht
+        .get_col(2)
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ArrayUnion>()
+        .unwrap();
+    assert_eq!(
+        union.kinds(),
+        vec![DataType::F32, DataType::I32, DataType::Bool]
+    );
+
+    let split = union.split_by_type();
+    let (_, floats) = &split[0];
+    assert_eq!(floats.label(), Some("Mixed"));
+    assert_eq!(floats.data_ref(3), Some(CellRef::F32(2.5)));
+    assert_eq!(floats.data_ref(1), Some(CellRef::None));
+
+    let text = union.convert_col(DataType::Text);
+    assert_eq!(text.data_ref(2), Some(CellRef::Text("true")));
+
+    // Numbers are widened within their family, as when splitting text
+    let values = ["1", "-5000000000", "2.5", "yes"].map(String::from);
+    let union = ArrayUnion::parse_str(&values, "");
+    let split = union.split_by_type();
+    let kinds = split
+        .iter()
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<DataType>>();
+    assert_eq!(kinds, vec![DataType::ISize, DataType::F32, DataType::Text]);
+    assert_eq!(
+        kinds,
+        ArrayText::from_iterator(values.into_iter())
+            .split_by_type()
+            .iter()
+            .map(|(kind, _)| *kind)
+            .collect::<Vec<DataType>>()
+    );
+
+    let (_, ints) = &split[0];
+    assert_eq!(ints.data_ref(0), Some(CellRef::ISize(1)));
+    assert_eq!(ints.data_ref(1), Some(CellRef::ISize(-5_000_000_000)));
+    assert_eq!(ints.data_ref(2), Some(CellRef::None));
+
+    let (_, texts) = &split[2];
+    assert_eq!(texts.data_ref(3), Some(CellRef::Text("yes")));
+}
+
+#[test]
+fn test_union_coercion() {
+    let union = ArrayUnion::from_iterator_option(
+        vec![
+            Some(UnionValue::I32(-3)),
+            None,
+            Some(UnionValue::ISize(-5_000_000_000)),
+            Some(UnionValue::U32(7)),
+        ]
+        .into_iter(),
+    );
+
+    let ints = union.coerce_to(DataType::ISize).unwrap();
+    assert_eq!(ints.kind(), DataType::ISize);
+    assert_eq!(ints.data_ref(0), Some(CellRef::ISize(-3)));
+    assert_eq!(ints.data_ref(1), Some(CellRef::None));
+    assert_eq!(ints.data_ref(2), Some(CellRef::ISize(-5_000_000_000)));
+    assert_eq!(ints.data_ref(3), Some(CellRef::ISize(7)));
+
+    let floats = union.coerce_to(DataType::F64).unwrap();
+    assert_eq!(floats.data_ref(2), Some(CellRef::F64(-5_000_000_000.0)));
+
+    // Values are only widened, never narrowed
+    assert!(union.coerce_to(DataType::I32).is_none());
+    assert!(union.coerce_to(DataType::USize).is_none());
+
+    let config = Config::new("./dummies/csv/mixed.csv")
+        .trim(true)
+        .types(TypesStrategy::InferWithUnion)
+        .labels(HeaderStrategy::ReadLabels);
+    let mut sht = ColumnSheet::with_config(config).unwrap();
+
+    // The boolean cannot be read as a number
+    assert!(sht.convert_col(2, DataType::F64).is_err());
+    assert_eq!(sht.get_col(2).unwrap().kind(), DataType::Union);
+
+    sht.set_cell("7", 2, 2).unwrap();
+    assert!(sht.convert_col(2, DataType::I32).is_err());
+    sht.convert_col(2, DataType::F64).unwrap();
+    assert_eq!(sht.get_col(2).unwrap().kind(), DataType::F64);
+    assert_eq!(sht.get_cell(2, 0), Some(CellRef::F64(1.5)));
+    assert_eq!(sht.get_cell(2, 1), Some(CellRef::F64(3.0)));
+    assert_eq!(sht.get_cell(2, 2), Some(CellRef::F64(7.0)));
+}
+
+#[test]
+fn test_max_rows() {
+    let config = || {
+        Config::new("./dummies/csv/air.csv")
+            .trim(true)
+            .types(TypesStrategy::Infer)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+
+    let full = ColumnSheet::with_config(config()).unwrap();
+    let limited = ColumnSheet::with_config(config().max_rows(Some(3))).unwrap();
+
+    assert_eq!(limited.height(), 3);
+    assert_eq!(limited.width(), full.width());
+
+    let kinds = |sht: &ColumnSheet| {
+        sht.headers()
+            .map(|header| (header.kind, header.header.map(ToOwned::to_owned)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(kinds(&limited), kinds(&full));
+    assert_eq!(limited.get_cell(1, 2), full.get_cell(1, 2));
+}
+
+#[test]
+fn test_drop_null_cols() {
+    let config = || {
+        Config::new("./dummies/csv/sparse.csv")
+            .primary(1)
+            .types(TypesStrategy::Infer)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+
+    let mut sht = ColumnSheet::with_config(config()).unwrap();
+    assert_eq!(sht.width(), 5);
+
+    assert_eq!(sht.drop_null_cols(0.8), vec![3, 4]);
+    assert_eq!(sht.width(), 3);
+    assert!(sht.header_at(1).unwrap().is_primary);
+
+    assert_eq!(sht.drop_null_cols(0.75), vec![2]);
+    assert_eq!(sht.width(), 2);
+
+    let sht = ColumnSheet::with_config(config().drop_empty_cols(true)).unwrap();
+    assert_eq!(sht.width(), 3);
+    assert_eq!(sht.get_col(2).unwrap().null_count(), 3);
+}
+
+#[test]
+fn test_update_col_where() {
+    let mut sheet = create_air_csv();
+
+    let count = sheet
+        .update_col_where::<i32>(1, |value| *value < 340, |value| Some(value * 10))
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(sheet.get_cell(1, 1), Some(CellRef::I32(3180)));
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::I32(340)));
+
+    let count = sheet
+        .update_col_where::<String>(0, |month| month == "JAN", |_| None)
+        .unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(sheet.get_cell(0, 0), Some(CellRef::None));
+    assert_eq!(sheet.header_at(0).unwrap().null_count, 1);
+
+    let count = sheet
+        .update_col_where::<i32>(2, |_| false, |value| Some(*value))
+        .unwrap();
+    assert_eq!(count, 0);
+
+    assert!(matches!(
+        sheet.update_col_where::<f64>(1, |_| true, |value| Some(*value)),
+        Err(Error::InvalidColumnKind {
+            col: 1,
+            expected: DataType::F64,
+            found: DataType::I32
+        })
+    ));
+    assert!(matches!(
+        sheet.update_col_where::<i32>(4, |_| true, |value| Some(*value)),
+        Err(Error::InvalidColumn(4))
+    ));
+}
+
+#[test]
+fn test_flexible_header_width() {
+    let load = |path: &str| {
+        let config = Config::new(path)
+            .trim(true)
+            .flexible(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+        ColumnSheet::with_config(config).unwrap()
+    };
+
+    let sheet = load("./dummies/csv/wide_header.csv");
+    let labels = sheet
+        .headers()
+        .map(|header| header.header)
+        .collect::<Vec<Option<&str>>>();
+    assert_eq!(
+        labels,
+        vec![Some("Name"), Some("Age"), Some("City"), Some("Country")]
+    );
+    assert!(sheet.iter().all(|col| col.len() == 3));
+    assert_eq!(sheet.get_cell(3, 0), Some(CellRef::None));
+    assert_eq!(sheet.load_report().header_width_mismatch, Some((4, 3)));
+
+    let sheet = load("./dummies/csv/narrow_header.csv");
+    let labels = sheet
+        .headers()
+        .map(|header| header.header)
+        .collect::<Vec<Option<&str>>>();
+    assert_eq!(labels, vec![Some("Name"), Some("Age"), None, None]);
+    assert_eq!(sheet.get_cell(3, 2), Some(CellRef::Text("USA")));
+    assert_eq!(sheet.load_report().header_width_mismatch, Some((2, 4)));
+
+    let sheet = load("./dummies/csv/air.csv");
+    assert_eq!(sheet.load_report().header_width_mismatch, None);
+}
+
+#[test]
+fn test_write_csv_round_trip() {
+    let config = Config::new("./dummies/csv/infer.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = ColumnSheet::with_config(config).unwrap();
+
+    let options = WriteOptions::new()
+        .delimiter(b'\t')
+        .decimal_separator(',')
+        .terminator(LineTerminator::Crlf)
+        .null_string("-");
+    let mut buffer = Vec::new();
+    sheet.write_csv_with(&mut buffer, &options).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.starts_with("Month\tYear\tPercentage\tMixed\tExpected\tTest\r\n"));
+    assert!(written.contains("JAN\t1998\t0,24\t417\ttrue\t-\r\n"));
+
+    let config = Config::new("infer.csv")
+        .delimiter(b'\t')
+        .decimal_separator(',')
+        .null_string("-")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let read = ColumnSheet::from_reader(written.as_bytes(), config).unwrap();
+
+    assert_eq!(read.width(), sheet.width());
+    assert_eq!(read.height(), sheet.height());
+    assert!(read.headers().zip(sheet.headers()).all(|(a, b)| a == b));
+    for row in 0..sheet.height() {
+        assert_eq!(read.get_row(row), sheet.get_row(row));
+    }
+
+    let mut buffer = Vec::new();
+    sheet
+        .write_csv_with(&mut buffer, &WriteOptions::new().decimal_separator(','))
+        .unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.contains("MAR,2000,\"1,0\",419,false,Else\n"));
+}
+
+#[test]
+fn test_iter_rows() {
+    let mut csv = String::from("id,name,score,passed\n");
+    for idx in 0..150 {
+        let name = if idx % 7 == 0 {
+            "".to_string()
+        } else {
+            format!("n{}", idx)
+        };
+        csv.push_str(&format!(
+            "{},{},{}.5,{}\n",
+            idx,
+            name,
+            idx % 13,
+            idx % 2 == 0
+        ));
+    }
+    let config = Config::new("rows.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let mut sheet = ColumnSheet::from_reader(csv.as_bytes(), config).unwrap();
+
+    assert_eq!(sheet.iter_rows().len(), 150);
+    for (idx, row) in sheet.iter_rows().enumerate() {
+        assert_eq!(Some(row), sheet.get_row(idx));
+    }
+
+    let rows = sheet.rows_range(60..70).collect::<Vec<_>>();
+    assert_eq!(rows.len(), 10);
+    assert_eq!(rows[4], sheet.get_row(64).unwrap());
+    assert_eq!(sheet.rows_range(..=2).len(), 3);
+    assert_eq!(sheet.rows_range(140..500).len(), 10);
+    assert_eq!(sheet.rows_range(200..).len(), 0);
+
+    let owned = sheet.get_row_owned(7).unwrap();
+    assert_eq!(owned[1], CellValue::None);
+    sheet.set_cell("changed", 1, 7).unwrap();
+    assert_eq!(owned[0], CellValue::I32(7));
+    assert_eq!(sheet.get_cell(1, 7), Some(CellRef::Text("changed")));
+    assert_eq!(
+        owned.iter().map(CellValue::as_cell_ref).collect::<Vec<_>>(),
+        vec![
+            CellRef::I32(7),
+            CellRef::None,
+            CellRef::F32(7.5),
+            CellRef::Bool(false)
+        ]
+    );
+    assert!(sheet.get_row_owned(150).is_none());
+}
+
+#[test]
+fn test_preserve_original() {
+    let path = "./dummies/csv/originals.csv";
+    let expected = std::fs::read_to_string(path).unwrap();
+    let config = |preserve| {
+        Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .preserve_original(preserve)
+    };
+    let write = |sheet: &ColumnSheet| {
+        let mut buffer = Vec::new();
+        sheet.write_csv(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    let sheet = ColumnSheet::with_config(config(false)).unwrap();
+    assert_eq!(sheet.original_text(0, 0), None);
+    assert_ne!(write(&sheet), expected);
+
+    let mut sheet = ColumnSheet::with_config(config(true)).unwrap();
+    assert_eq!(sheet.get_cell(0, 0), Some(CellRef::I32(7)));
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::Text("alice")));
+    assert_eq!(sheet.original_text(0, 0), Some("007"));
+    assert_eq!(sheet.original_text(1, 0), Some("  alice "));
+    assert_eq!(sheet.original_text(2, 1), None);
+    assert_eq!(sheet.original_text(3, 1), Some("2"));
+    assert_eq!(write(&sheet), expected);
+
+    // Original text follows its cell when rows and columns move
+    sheet.sort_row_by_rev(0);
+    assert_eq!(sheet.original_text(0, 0), Some(" 12 "));
+    assert_eq!(sheet.original_text(0, 2), Some("007"));
+    sheet.swap_cols(0, 2).unwrap();
+    assert_eq!(sheet.original_text(2, 2), Some("007"));
+    sheet.remove_row(0).unwrap();
+    assert_eq!(sheet.original_text(2, 1), Some("007"));
+    sheet
+        .insert_row(["1", "dan", "4", "0.5"].into_iter(), 0)
+        .unwrap();
+    assert_eq!(sheet.original_text(2, 0), None);
+    assert_eq!(sheet.original_text(2, 2), Some("007"));
+
+    // Changed cells lose their text
+    sheet.set_cell("9", 2, 2).unwrap();
+    assert_eq!(sheet.original_text(2, 2), None);
+    assert_eq!(sheet.original_text(0, 2), Some("042"));
+    sheet.get_col_mut(0);
+    assert_eq!(sheet.original_text(0, 2), None);
+
+    // Integers read before the first float keep their text, while null
+    // tokens are written with the null string of the writer
+    let data = "Ratio,Note\n1,NA\n2,x\n2.50,y\n";
+    let config = Config::new("ratios.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .null_string("NA")
+        .preserve_original(true);
+    let sheet = ColumnSheet::from_reader(data.as_bytes(), config).unwrap();
+    assert_eq!(sheet.get_cell(0, 0), Some(CellRef::F32(1.0)));
+    assert_eq!(sheet.original_text(0, 0), Some("1"));
+    assert_eq!(sheet.original_text(0, 1), Some("2"));
+    assert_eq!(sheet.original_text(0, 2), Some("2.50"));
+    assert_eq!(sheet.original_text(1, 0), None);
+    assert_eq!(sheet.original_text(1, 1), None);
+
+    let mut buffer = Vec::new();
+    sheet
+        .write_csv_with(&mut buffer, &WriteOptions::new().null_string("NULL"))
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "Ratio,Note\n1,NULL\n2,x\n2.50,y\n"
+    );
+}
+
+#[test]
+fn test_push_row_header_only() {
+    let config = Config::new("./dummies/csv/header_only.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .null_string("NA")
+        .primary(1);
+    let mut sheet = ColumnSheet::with_config(config).unwrap();
+    assert_eq!((sheet.width(), sheet.height()), (4, 0));
+
+    sheet
+        .push_row(["alpha", "3", "0.5", "NA"].into_iter())
+        .unwrap();
+    assert_eq!(sheet.height(), 1);
+    assert_eq!(sheet.get_primary(), Some(1));
+    let headers = sheet.headers().collect::<Vec<ColumnHeader>>();
+    assert_eq!(
+        headers.iter().map(|hdr| hdr.header).collect::<Vec<_>>(),
+        [Some("Name"), Some("Count"), Some("Ratio"), Some("Note")]
+    );
+    assert_eq!(
+        headers.iter().map(|hdr| hdr.kind).collect::<Vec<_>>(),
+        [DataType::Text, DataType::I32, DataType::F32, DataType::Text]
+    );
+    assert_eq!(sheet.get_cell(3, 0), Some(CellRef::None));
+
+    sheet
+        .push_row(["beta", "4", "1.5", "late"].into_iter())
+        .unwrap();
+    sheet
+        .push_row(["gamma", "NA", "2", "NA"].into_iter())
+        .unwrap();
+    assert_eq!(sheet.height(), 3);
+    assert!(sheet.iter().all(|col| col.len() == 3));
+    assert_eq!(sheet.get_cell(1, 1), Some(CellRef::I32(4)));
+    assert_eq!(sheet.get_cell(1, 2), Some(CellRef::None));
+    assert_eq!(sheet.get_cell(3, 1), Some(CellRef::Text("late")));
+
+    // Typed columns keep their type when it can hold the first value
+    let config = Config::new("./dummies/csv/header_only.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(vec![
+            ColumnType::Text,
+            ColumnType::Float,
+            ColumnType::Integer,
+            ColumnType::Text,
+        ]));
+    let mut sheet = ColumnSheet::with_config(config).unwrap();
+    sheet.push_row(["7", "3", "0.5", "x"].into_iter()).unwrap();
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::F32(3.0)));
+    assert_eq!(sheet.get_cell(2, 0), Some(CellRef::F32(0.5)));
+    assert_eq!(sheet.header_at(0).unwrap().header, Some("Name"));
+
+    // Without columns, a column is created for each value
+    let mut sheet = create_empty();
+    sheet.remove_all_cols();
+    sheet.push_row(["a", "1"].into_iter()).unwrap();
+    assert_eq!((sheet.width(), sheet.height()), (2, 1));
+    assert_eq!(sheet.get_primary(), Some(0));
+}
+
+#[test]
+fn test_transpose() {
+    let sht = create_air_csv();
+    let res = sht.transpose(true).unwrap();
+
+    assert_eq!(13, res.width());
+    assert_eq!(3, res.height());
+    assert_eq!(Some(0), res.primary);
+
+    let headers = res.headers().collect::<Vec<ColumnHeader>>();
+    assert_eq!(Some("Month"), headers[0].header);
+    assert_eq!(DataType::Text, headers[0].kind);
+    assert_eq!(Some("JUN"), headers[6].header);
+    assert_eq!(DataType::I32, headers[6].kind);
+
+    assert_eq!(Some(CellRef::Text("1958")), res.get_cell(0, 0));
+    assert_eq!(Some(CellRef::I32(360)), res.get_cell(1, 1));
+    assert_eq!(Some(CellRef::I32(535)), res.get_cell(6, 2));
+
+    let res = sht.transpose(false).unwrap();
+    assert_eq!(12, res.width());
+    assert_eq!(4, res.height());
+    assert!(res.headers().all(|header| header.header.is_none()));
+    assert_eq!(Some(CellRef::Text("JAN")), res.get_cell(0, 0));
+    assert_eq!(Some(CellRef::Text("340")), res.get_cell(0, 1));
+
+    assert!(matches!(
+        create_empty().transpose(true),
+        Err(Error::InvalidColumn(0))
+    ));
+}
+
+#[test]
+fn test_transpose_flexible() {
+    let ct = vec![ColumnType::Text, ColumnType::Integer, ColumnType::Integer];
+    let builder = Config::new("./dummies/csv/transpose1.csv")
+        .trim(true)
+        .flexible(true)
+        .primary(0)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(ct));
+
+    let sht = ColumnSheet::with_config(builder).unwrap();
+    let res = sht.transpose(true).unwrap();
+
+    assert_eq!(3, res.width());
+    assert_eq!(2, res.height());
+    assert_eq!(Some(CellRef::Text("1958")), res.get_cell(0, 0));
+    assert_eq!(Some(CellRef::I32(3)), res.get_cell(2, 0));
+    assert_eq!(Some(CellRef::I32(2)), res.get_cell(1, 1));
+    assert_eq!(Some(CellRef::None), res.get_cell(2, 1));
+    assert_eq!(None, res.get_row(2));
+
+    let header = res.headers().nth(2).unwrap();
+    assert_eq!((Some("FEB"), DataType::I32), (header.header, header.kind));
+}
+
+#[test]
+fn test_transpose_headless() {
+    let builder = Config::new("./dummies/csv/headless.csv")
+        .trim(true)
+        .labels(HeaderStrategy::NoLabels)
+        .types(TypesStrategy::Infer);
+
+    let sht = ColumnSheet::with_config(builder).unwrap();
+    let res = sht.transpose(true).unwrap();
+
+    assert_eq!(3, res.width());
+    assert_eq!(3, res.height());
+    assert_eq!(Some(CellRef::None), res.get_cell(0, 2));
+
+    let headers = res
+        .headers()
+        .map(|header| (header.header, header.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (None, DataType::Text),
+            (Some("JAN"), DataType::I32),
+            (Some("Feb"), DataType::I32)
+        ],
+        headers
+    );
+}
+
+#[test]
+fn test_transpose_symmetry() {
+    fn assert_symmetric(sht: &ColumnSheet) {
+        let res = sht.transpose(true).unwrap().transpose(true).unwrap();
+
+        assert_eq!(sht.width(), res.width());
+        assert_eq!(sht.height(), res.height());
+        assert!(sht.headers().eq(res.headers()));
+        for row in 0..sht.height() {
+            assert_eq!(sht.get_row(row), res.get_row(row));
+        }
+    }
+
+    assert_symmetric(&create_air_csv());
+
+    let ct = vec![ColumnType::Text, ColumnType::Integer, ColumnType::Integer];
+    let builder = Config::new("./dummies/csv/transpose1.csv")
+        .trim(true)
+        .flexible(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(ct));
+    assert_symmetric(&ColumnSheet::with_config(builder).unwrap());
+
+    let builder = Config::new("./dummies/csv/headless.csv")
+        .trim(true)
+        .labels(HeaderStrategy::NoLabels)
+        .types(TypesStrategy::Infer);
+    assert_symmetric(&ColumnSheet::with_config(builder).unwrap());
+}
+
+#[test]
+fn test_data_union_conversions() {
+    let data = [
+        Data::Text("abc".into()),
+        Data::Integer(i32::MIN),
+        Data::Float(f32::MAX),
+        Data::Number(isize::MAX),
+        Data::Boolean(true),
+        Data::None,
+    ];
+    let values = [
+        Some(UnionValue::Text("abc".into())),
+        Some(UnionValue::I32(i32::MIN)),
+        Some(UnionValue::F32(f32::MAX)),
+        Some(UnionValue::ISize(isize::MAX)),
+        Some(UnionValue::Bool(true)),
+        None,
+    ];
+    let cells = [
+        CellRef::Text("abc"),
+        CellRef::I32(i32::MIN),
+        CellRef::F32(f32::MAX),
+        CellRef::ISize(isize::MAX),
+        CellRef::Bool(true),
+        CellRef::None,
     ];
 
-    for (expected, actual) in headers.into_iter().zip(sht.headers()) {
-        assert_eq!(expected, actual);
+    for ((data, value), cell) in data.iter().zip(values).zip(cells) {
+        assert_eq!(value, Option::<UnionValue>::from(data.clone()));
+        assert_eq!(cell, CellRef::from(data));
+        assert_eq!(data, &Data::try_from(cell).unwrap());
+        if let Some(value) = value {
+            assert_eq!(data, &Data::try_from(value).unwrap());
+        }
+    }
+
+    // Unsigned values widen to Number when they fit
+    assert_eq!(
+        Data::Number(u32::MAX as isize),
+        Data::try_from(UnionValue::U32(u32::MAX)).unwrap()
+    );
+    assert_eq!(Data::Number(0), Data::try_from(CellRef::U32(0)).unwrap());
+    assert_eq!(
+        Data::Number(isize::MAX),
+        Data::try_from(UnionValue::USize(isize::MAX as usize)).unwrap()
+    );
+    assert!(Data::try_from(UnionValue::USize(usize::MAX)).is_err());
+    assert_eq!(
+        Data::try_from(CellRef::USize(usize::MAX)),
+        Err(UnrepresentableCell(CellValue::USize(usize::MAX)))
+    );
+
+    // Doubles narrow to Float only when exact
+    assert_eq!(
+        Data::Float(0.5),
+        Data::try_from(UnionValue::F64(0.5)).unwrap()
+    );
+    assert_eq!(
+        Data::Float(f32::INFINITY),
+        Data::try_from(CellRef::F64(f64::INFINITY)).unwrap()
+    );
+    assert!(matches!(
+        Data::try_from(CellRef::F64(f64::NAN)),
+        Ok(Data::Float(value)) if value.is_nan()
+    ));
+    assert!(Data::try_from(UnionValue::F64(0.1)).is_err());
+    assert!(Data::try_from(UnionValue::F64(f64::MAX)).is_err());
+    assert_eq!(
+        Data::try_from(CellRef::F64(0.1)),
+        Err(UnrepresentableCell(CellValue::F64(0.1)))
+    );
+    assert!(Data::try_from(CellRef::F64(f64::MAX)).is_err());
+}
+
+#[test]
+fn test_nan_policy() {
+    use crate::repr::NanPolicy;
+
+    let load = |policy| {
+        let config = Config::new("./dummies/csv/nan.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .nan_policy(policy);
+        let mut sheet = ColumnSheet::with_config(config).unwrap();
+        sheet.sort_row_by(1);
+        sheet
+    };
+    let column = |sheet: &ColumnSheet, col: usize| {
+        (0..sheet.height())
+            .map(|row| sheet.get_cell(col, row).unwrap())
+            .map(CellValue::from)
+            .collect::<Vec<CellValue>>()
+    };
+
+    let sheet = load(NanPolicy::Keep);
+    assert_eq!(DataType::F32, sheet.header_at(1).unwrap().kind);
+    let values = column(&sheet, 1);
+    assert_eq!(values[..3], [-2.5, 1.5, f32::INFINITY].map(CellValue::F32));
+    assert!(matches!(values[3], CellValue::F32(value) if value.is_nan()));
+    assert_eq!(Some(CellRef::Text("b")), sheet.get_cell(0, 3));
+
+    let sheet = load(NanPolicy::Null);
+    assert_eq!(DataType::F32, sheet.header_at(1).unwrap().kind);
+    assert_eq!(
+        vec![
+            CellValue::None,
+            CellValue::F32(-2.5),
+            CellValue::F32(1.5),
+            CellValue::F32(f32::INFINITY)
+        ],
+        column(&sheet, 1)
+    );
+    assert_eq!(Some(CellRef::Text("b")), sheet.get_cell(0, 0));
+    assert_eq!(Some(CellRef::None), sheet.get_cell(2, 1));
+    assert_eq!(1, sheet.get_col(2).unwrap().null_count());
+}
+
+#[test]
+fn test_encoding() {
+    use crate::repr::Encoding;
+
+    let config = Config::new("./dummies/csv/latin1.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .encoding(Encoding::Latin1);
+    let sheet = ColumnSheet::with_config(config).unwrap();
+
+    let headers = sheet
+        .headers()
+        .map(|header| header.header)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![Some("Nom"), Some("Ville"), Some("Âge")], headers);
+    assert_eq!(Some(CellRef::Text("Françoise")), sheet.get_cell(0, 1));
+    assert_eq!(Some(CellRef::Text("Montréal")), sheet.get_cell(1, 0));
+    assert_eq!(Some(CellRef::I32(34)), sheet.get_cell(2, 0));
+
+    let data = std::fs::read("./dummies/csv/latin1.csv").unwrap();
+    let config = Config::new("latin1.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .encoding(Encoding::Latin1);
+    let from_reader = ColumnSheet::from_reader(data.as_slice(), config).unwrap();
+    assert_eq!(Some(CellRef::Text("Zürich")), from_reader.get_cell(1, 1));
+
+    assert_eq!(0, from_reader.load_report().replaced);
+
+    let config = Config::new("./dummies/csv/latin1.csv").labels(HeaderStrategy::ReadLabels);
+    assert!(ColumnSheet::with_config(config).is_err());
+
+    let config = Config::new("./dummies/csv/latin1.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .encoding(Encoding::Utf8Lossy);
+    let sheet = ColumnSheet::with_config(config).unwrap();
+    assert_eq!(5, sheet.load_report().replaced);
+    assert_eq!(Some("\u{FFFD}ge"), sheet.headers().nth(2).unwrap().header);
+}
+
+#[test]
+fn test_bounds() {
+    let config = Config::new("./dummies/csv/bounds.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = ColumnSheet::with_config(config).unwrap();
+
+    assert_eq!(
+        Some((CellRef::I32(3), CellRef::I32(7))),
+        sheet.col_bounds(1).unwrap()
+    );
+    assert_eq!(
+        Some((CellRef::I32(-9), CellRef::I32(-2))),
+        sheet.col_bounds(2).unwrap()
+    );
+    assert_eq!(None, sheet.col_bounds(3).unwrap());
+    assert_eq!(
+        Some((CellRef::F32(-4.5), CellRef::F32(1.5))),
+        sheet.col_bounds(4).unwrap()
+    );
+    assert_eq!(
+        Some((CellRef::I32(42), CellRef::I32(42))),
+        sheet.col_bounds(5).unwrap()
+    );
+
+    assert_eq!(
+        Some((CellRef::I32(-2), CellRef::Text("a"))),
+        sheet.row_bounds(0).unwrap()
+    );
+    assert_eq!(
+        Some((CellRef::I32(-9), CellRef::I32(7))),
+        sheet.bounds_of(&[1, 2, 4]).unwrap()
+    );
+    assert_eq!(None, sheet.bounds_of(&[3]).unwrap());
+
+    assert!(matches!(sheet.col_bounds(6), Err(Error::InvalidColumn(6))));
+    assert!(matches!(
+        sheet.bounds_of(&[6]),
+        Err(Error::InvalidColumn(6))
+    ));
+    assert!(matches!(sheet.row_bounds(3), Err(Error::InvalidRow(3))));
+
+    let config = Config::new("./dummies/csv/nan.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = ColumnSheet::with_config(config).unwrap();
+    assert_eq!(
+        Some((CellRef::F32(-2.5), CellRef::F32(1.5))),
+        sheet.col_bounds(1).unwrap()
+    );
+}
+
+#[test]
+fn test_chart_values_overflow() {
+    let mut sheet = create_air_csv();
+    let mut unsigned = vec![None; sheet.height()];
+    unsigned[0] = Some(u32::MAX);
+    unsigned[1] = Some(7);
+    let mut column = ArrayU32::from_iterator_option(unsigned.into_iter());
+    column.set_header("U32".into());
+    sheet.push_col(Box::new(column)).unwrap();
+
+    let mut unsigned = vec![Some(3); sheet.height()];
+    unsigned[2] = Some(usize::MAX);
+    unsigned[3] = None;
+    let mut column = ArrayUSize::from_iterator_option(unsigned.into_iter());
+    column.set_header("USize".into());
+    sheet.push_col(Box::new(column)).unwrap();
+
+    let values = sheet.chart_values(1, UnsignedOverflow::Reject).unwrap();
+    assert_eq!(values.kind, ScaleKind::Integer);
+    assert_eq!(values.values[0], Data::Integer(340));
+    assert!(values.lossy.is_empty());
+    assert!(matches!(
+        sheet.chart_values(9, UnsignedOverflow::Reject),
+        Err(Error::InvalidColumn(9))
+    ));
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        let values = sheet.chart_values(4, UnsignedOverflow::Reject).unwrap();
+        assert_eq!(values.kind, ScaleKind::Number);
+        assert_eq!(values.values[0], Data::Number(u32::MAX as isize));
+        assert_eq!(values.values[1], Data::Number(7));
+        assert_eq!(values.values[2], Data::None);
+    }
+    #[cfg(not(target_pointer_width = "64"))]
+    {
+        assert!(matches!(
+            sheet.chart_values(4, UnsignedOverflow::Reject),
+            Err(Error::UnrepresentableValue {
+                col: 4,
+                row: 0,
+                kind: DataType::U32
+            })
+        ));
+        let values = sheet
+            .chart_values(4, UnsignedOverflow::WidenToFloat)
+            .unwrap();
+        assert_eq!(values.kind, ScaleKind::Float);
+        assert_eq!(values.values[0], Data::Float(u32::MAX as f32));
+        assert_eq!(values.values[1], Data::Float(7.0));
+        assert_eq!(values.lossy, vec![0]);
+    }
+
+    let res = sheet.chart_values(5, UnsignedOverflow::Reject);
+    assert!(matches!(
+        res,
+        Err(Error::UnrepresentableValue {
+            col: 5,
+            row: 2,
+            kind: DataType::USize
+        })
+    ));
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Cannot chart the USize value at column: 5, row: 2 without overflow"
+    );
+
+    let values = sheet
+        .chart_values(5, UnsignedOverflow::WidenToFloat)
+        .unwrap();
+    assert_eq!(values.kind, ScaleKind::Float);
+    assert_eq!(values.values[0], Data::Float(3.0));
+    assert_eq!(values.values[2], Data::Float(usize::MAX as f32));
+    assert_eq!(values.values[3], Data::None);
+    assert_eq!(values.lossy, vec![2]);
+
+    sheet.convert_col(4, DataType::F64).unwrap();
+    sheet.set_cell("0.1", 4, 1).unwrap();
+    let values = sheet.chart_values(4, UnsignedOverflow::Reject).unwrap();
+    assert_eq!(values.kind, ScaleKind::Float);
+    assert_eq!(values.values[1], Data::Float(0.1));
+    assert_eq!(values.lossy, vec![0, 1]);
+}
+
+#[test]
+fn test_logical_eq() {
+    let sheet = create_air_csv();
+    assert!(sheet == create_air_csv());
+
+    let mut edited = create_air_csv();
+    let row: Vec<String> = edited
+        .get_row(4)
+        .unwrap()
+        .iter()
+        .map(|cell| Option::<String>::from(*cell).unwrap_or_default())
+        .collect();
+    edited.remove_row(4).unwrap();
+    assert!(edited != sheet);
+    edited.insert_row(row.iter(), 4).unwrap();
+    assert!(edited.logical_eq(&sheet));
+
+    edited.set_cell("0", 2, 7).unwrap();
+    assert!(edited != sheet);
+    edited.set_cell("559", 2, 7).unwrap();
+    assert!(edited == sheet);
+
+    edited.clear_cell(2, 7).unwrap();
+    assert!(edited != sheet);
+
+    let mut converted = create_air_csv();
+    converted.convert_col(1, DataType::ISize).unwrap();
+    assert!(converted != sheet);
+
+    let nan = || {
+        let mut sheet = create_air_csv();
+        sheet.convert_col(1, DataType::F64).unwrap();
+        sheet.set_cell("NaN", 1, 0).unwrap();
+        sheet
+    };
+    assert!(nan() == nan());
+}
+
+#[test]
+fn test_select_columns() {
+    let config = Config::new("./dummies/csv/air.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+
+    let select = SelectColumns::Names(vec!["Month".into(), "1959".into()]);
+    let sheet = ColumnSheet::with_config(config.clone().select_columns(select)).unwrap();
+    assert_eq!(sheet.width(), 2);
+    assert_eq!(sheet.height(), 12);
+    assert_eq!(sheet.get_primary(), Some(0));
+    let headers: Vec<_> = sheet.headers().map(|hdr| (hdr.header, hdr.kind)).collect();
+    assert_eq!(
+        headers,
+        vec![
+            (Some("Month"), DataType::Text),
+            (Some("1959"), DataType::I32)
+        ]
+    );
+    assert_eq!(sheet.get_cell(1, 7), Some(CellRef::I32(559)));
+
+    let select = SelectColumns::Indices(vec![2, 0]);
+    let sheet = ColumnSheet::with_config(config.clone().select_columns(select)).unwrap();
+    assert_eq!(sheet.get_primary(), Some(1));
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::Text("JAN")));
+
+    // The default primary column falls back to the first selected column
+    let select = SelectColumns::Names(vec!["1958".into(), "1959".into()]);
+    let sheet = ColumnSheet::with_config(config.clone().select_columns(select)).unwrap();
+    assert_eq!(sheet.get_primary(), Some(0));
+    assert_eq!(sheet.get_cell(0, 0), Some(CellRef::I32(340)));
+
+    // An explicit primary column must be selected
+    let res = ColumnSheet::with_config(
+        config
+            .primary(0)
+            .select_columns(SelectColumns::Indices(vec![3])),
+    );
+    assert!(matches!(res, Err(Error::InvalidPrimary(0))));
+}
+
+#[test]
+fn test_unique_headers() {
+    let mut sheet = create_air_csv();
+    let unique = |sheet: &ColumnSheet| sheet.headers().map(|hdr| hdr.is_unique).collect::<Vec<_>>();
+    assert_eq!(unique(&sheet), vec![true; 4]);
+
+    // Without the guard, duplicates are allowed
+    sheet.set_col_header(3, "1959").unwrap();
+    assert_eq!(unique(&sheet), vec![true, true, false, false]);
+    assert!(!sheet.header_at(2).unwrap().is_unique);
+
+    // With the guard, they are rejected
+    let res = sheet.set_col_header_unique(1, "Month");
+    assert!(matches!(
+        res,
+        Err(Error::DuplicateHeader { ref label, existing_col: 0 }) if label == "Month"
+    ));
+    assert_eq!(sheet.header_at(1).unwrap().header, Some("1958"));
+    sheet.set_col_header_unique(3, "1960").unwrap();
+    sheet.set_col_header_unique(3, "1960").unwrap();
+    assert_eq!(unique(&sheet), vec![true; 4]);
+    assert!(matches!(
+        sheet.set_col_header_unique(4, "1961"),
+        Err(Error::InvalidColumn(4))
+    ));
+
+    sheet.rename_col("1960", "Sixty").unwrap();
+    assert_eq!(sheet.header_at(3).unwrap().header, Some("Sixty"));
+    let res = sheet.rename_col("1961", "Sixty One");
+    assert!(matches!(res, Err(Error::UnknownHeader(ref label)) if label == "1961"));
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "No column has the header `1961`"
+    );
+    assert!(matches!(
+        sheet.rename_col("Sixty", "1958"),
+        Err(Error::DuplicateHeader {
+            existing_col: 1,
+            ..
+        })
+    ));
+
+    sheet.set_col_header(2, "1958").unwrap();
+    sheet.clear_col_header(1).unwrap();
+    sheet.clear_col_header(2).unwrap();
+    assert_eq!(sheet.header_at(1).unwrap().header, None);
+    assert_eq!(unique(&sheet), vec![true; 4]);
+    assert!(sheet.clear_col_header(4).is_err());
+}
+
+#[test]
+fn test_type_mismatch_policy() {
+    let config = |policy: TypeMismatchPolicy| {
+        let ct = vec![
+            ColumnType::Text,
+            ColumnType::Integer,
+            ColumnType::Integer,
+            ColumnType::Integer,
+        ];
+        Config::new("./dummies/csv/invalid1.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Provided(ct))
+            .type_mismatch(policy)
+    };
+
+    let sht = ColumnSheet::with_config(config(TypeMismatchPolicy::Fail)).unwrap();
+    assert_eq!(sht.get_col(2).unwrap().kind(), DataType::Text);
+    assert_eq!(sht.get_col(3).unwrap().kind(), DataType::Text);
+    assert_eq!(Some(CellRef::Text("\"error2\"")), sht.get_cell(2, 5));
+
+    for policy in [TypeMismatchPolicy::Null, TypeMismatchPolicy::Coerce] {
+        let sht = ColumnSheet::with_config(config(policy)).unwrap();
+        assert_eq!(sht.get_col(2).unwrap().kind(), DataType::I32);
+        assert_eq!(sht.get_col(3).unwrap().kind(), DataType::I32);
+        assert_eq!(Some(CellRef::None), sht.get_cell(2, 5));
+        assert_eq!(Some(CellRef::None), sht.get_cell(3, 2));
+        assert_eq!(Some(CellRef::None), sht.get_cell(3, 8));
+        assert_eq!(Some(CellRef::I32(559)), sht.get_cell(2, 7));
+        assert_eq!(2, sht.get_col(3).unwrap().null_count());
     }
+
+    let data = "a,b\nx,2.0\ny,true\n";
+    let config = |policy: TypeMismatchPolicy| {
+        Config::new("")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Provided(vec![
+                ColumnType::Text,
+                ColumnType::Integer,
+            ]))
+            .type_mismatch(policy)
+    };
+    let sht =
+        ColumnSheet::from_reader(data.as_bytes(), config(TypeMismatchPolicy::Coerce)).unwrap();
+    assert_eq!(Some(CellRef::I32(2)), sht.get_cell(1, 0));
+    assert_eq!(Some(CellRef::None), sht.get_cell(1, 1));
+
+    let sht = ColumnSheet::from_reader(data.as_bytes(), config(TypeMismatchPolicy::Null)).unwrap();
+    assert_eq!(Some(CellRef::None), sht.get_cell(1, 0));
+}
+
+#[test]
+fn test_iter_cells() {
+    let mut sht = create_air_csv();
+
+    let cells: Vec<_> = sht.iter_cells().collect();
+    assert_eq!(cells.len(), sht.width() * sht.height());
+    assert_eq!(cells[0], (0, 0, CellRef::Text("JAN")));
+    assert_eq!(cells[2 * 12 + 7], (7, 2, CellRef::I32(559)));
+
+    let column = ArrayI32::from_iterator(0..12);
+    sht.insert_col(Box::new(column), 1).unwrap();
+    for (row, col, cell) in sht.iter_cells() {
+        assert_eq!(sht.get_cell(col, row), Some(cell));
+    }
+    assert!(sht
+        .iter_cells()
+        .any(|cell| cell == (7, 3, CellRef::I32(559))));
+
+    let col: Vec<_> = sht.enumerate_col(1).collect();
+    assert_eq!(col.len(), sht.height());
+    assert_eq!(col[7], (7, CellRef::I32(7)));
+    assert_eq!(sht.enumerate_col(sht.width()).count(), 0);
+}
+
+#[test]
+fn test_col_meta() {
+    let config = |collect_meta: bool| {
+        Config::new("./dummies/csv/air.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .collect_meta(collect_meta)
+    };
+    let plain = ColumnSheet::with_config(config(false)).unwrap();
+    let mut sht = ColumnSheet::with_config(config(true)).unwrap();
+    assert_eq!(plain.col_meta(1), None);
+
+    let meta = sht.col_meta(1).unwrap();
+    assert_eq!(meta.min(), Some(&CellValue::I32(310)));
+    assert_eq!(meta.max(), Some(&CellValue::I32(505)));
+    assert_eq!(meta.null_count(), 0);
+    assert_eq!(meta.exact_distinct(), Some(12));
+    assert!(!meta.is_increasing());
+    assert_eq!(sht.col_meta(0).unwrap().exact_distinct(), Some(12));
+
+    for col in 0..sht.width() {
+        assert_eq!(sht.col_bounds(col).unwrap(), plain.col_bounds(col).unwrap());
+    }
+
+    sht.set_cell("600", 1, 0).unwrap();
+    assert_eq!(sht.col_meta(1), None);
+    assert_eq!(
+        sht.col_bounds(1).unwrap(),
+        Some((CellRef::I32(310), CellRef::I32(600)))
+    );
+}
+
+#[test]
+fn test_synthesized_cells() {
+    let config = Config::new("./dummies/csv/flexible.csv")
+        .trim(true)
+        .flexible(true)
+        .labels(HeaderStrategy::NoLabels)
+        .types(TypesStrategy::Infer);
+    let mut sheet = ColumnSheet::with_config(config).unwrap();
+
+    assert_eq!(sheet.original_width(0), Some(4));
+    assert_eq!(sheet.original_width(7), Some(2));
+    assert_eq!(sheet.original_width(12), None);
+    assert_eq!(
+        sheet.synthesized_cells(),
+        vec![(1, 3), (6, 3), (7, 2), (7, 3), (11, 3)]
+    );
+
+    let mut buffer = Vec::new();
+    let options = WriteOptions::new().headers(false).trim_padding(true);
+    sheet.write_csv_with(&mut buffer, &options).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.starts_with("JAN,340,360,417\nFEB,318,391\nMAR"));
+    assert!(written.contains("AUG,606\n"));
+
+    // Padding is forgotten once the shape changes
+    sheet.remove_col(3).unwrap();
+    assert!(sheet.synthesized_cells().is_empty());
+    assert_eq!(sheet.original_width(7), Some(3));
+}
+
+#[test]
+fn test_schema_drift() {
+    let config = |path: &str| {
+        Config::new(path.to_string())
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    };
+
+    let base = ColumnSheet::with_config(config("./dummies/csv/schema_base.csv"))
+        .unwrap()
+        .schema();
+    // A file has the same schema read either way
+    let rows = crate::repr::Sheet::with_config(config("./dummies/csv/schema_base.csv")).unwrap();
+    assert_eq!(base, rows.schema());
+
+    let diff = match ColumnSheet::with_config(
+        config("./dummies/csv/schema_renamed.csv").expect_schema(base.clone()),
+    ) {
+        Err(Error::SchemaMismatch(diff)) => diff,
+        Err(err) => panic!("Expected a schema mismatch, got {err}"),
+        Ok(_) => panic!("Expected a schema mismatch"),
+    };
+    assert_eq!(diff.renamed, vec![("Region".into(), "Area".into())]);
+
+    let retyped = ColumnSheet::with_config(config("./dummies/csv/schema_retyped.csv")).unwrap();
+    let diff = base.diff(&retyped.schema());
+    assert_eq!(
+        diff.retyped,
+        vec![("Units".into(), ColumnType::Integer, ColumnType::Float)]
+    );
+
+    assert!(
+        ColumnSheet::with_config(config("./dummies/csv/schema_base.csv").expect_schema(base))
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_group_by() {
+    let config = || {
+        Config::new("./dummies/csv/group.csv")
+            .types(TypesStrategy::Infer)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+    let sheet = ColumnSheet::with_config(config()).unwrap();
+    let rows = Sheet::with_config(config()).unwrap();
+
+    let aggs = [
+        (1, Aggregation::Sum),
+        (1, Aggregation::Count),
+        (2, Aggregation::Mean),
+        (1, Aggregation::Min),
+        (2, Aggregation::Max),
+        (3, Aggregation::Count),
+    ];
+    let grouped = sheet.group_by(0, &aggs).unwrap();
+
+    let labels = grouped.iter().map(|col| col.label()).collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            Some("Region"),
+            Some("sum(Units)"),
+            Some("count(Units)"),
+            Some("mean(Price)"),
+            Some("min(Units)"),
+            Some("max(Price)"),
+            Some("count(Store)"),
+        ]
+    );
+
+    // The same groups, gathered from the rows of a `Sheet`
+    let mut groups: Vec<(Data, Vec<i32>, Vec<f32>, usize)> = Vec::new();
+    for row in rows.iter_rows() {
+        let cells = row
+            .iter_cells()
+            .map(|cell| cell.get_data().clone())
+            .collect::<Vec<_>>();
+        let pos = match groups.iter().position(|group| group.0 == cells[0]) {
+            Some(pos) => pos,
+            None => {
+                groups.push((cells[0].clone(), Vec::new(), Vec::new(), 0));
+                groups.len() - 1
+            }
+        };
+
+        let group = &mut groups[pos];
+        if let Data::Integer(units) = cells[1] {
+            group.1.push(units);
+        }
+        if let Data::Float(price) = cells[2] {
+            group.2.push(price);
+        }
+        if cells[3] != Data::None {
+            group.3 += 1;
+        }
+    }
+
+    assert_eq!(grouped.height(), groups.len());
+    assert_eq!(grouped.height(), 4);
+    for (row, (key, units, prices, stores)) in groups.iter().enumerate() {
+        let cell = |col: usize| grouped.get_cell(col, row).unwrap();
+
+        assert_eq!(Data::try_from(cell(0)).unwrap(), *key);
+        assert_eq!(
+            cell(1),
+            CellRef::from(
+                (!units.is_empty())
+                    .then(|| units.iter().map(|units| *units as isize).sum::<isize>())
+            )
+        );
+        assert_eq!(cell(2), CellRef::USize(units.len()));
+        match cell(3) {
+            CellRef::F64(mean) => {
+                let expected = prices.iter().sum::<f32>() as f64 / prices.len() as f64;
+                assert!((mean - expected).abs() < 1e-6);
+            }
+            cell => assert!(prices.is_empty() && cell == CellRef::None),
+        }
+        assert_eq!(cell(4), CellRef::from(units.iter().min().copied()));
+        assert_eq!(
+            cell(5),
+            CellRef::from(prices.iter().copied().reduce(f32::max))
+        );
+        assert_eq!(cell(6), CellRef::USize(*stores));
+    }
+
+    // Null keys form a group of their own
+    assert_eq!(grouped.get_cell(0, 3), Some(CellRef::None));
+
+    assert!(matches!(
+        sheet.group_by(0, &[(3, Aggregation::Sum)]),
+        Err(Error::InvalidAggregation {
+            col: 3,
+            aggregation: Aggregation::Sum,
+            kind: DataType::Text
+        })
+    ));
+    assert!(matches!(
+        sheet.group_by(4, &[]),
+        Err(Error::InvalidColumn(4))
+    ));
+    assert!(matches!(
+        sheet.group_by(0, &[(4, Aggregation::Count)]),
+        Err(Error::InvalidColumn(4))
+    ));
+
+    // Sums which do not fit their type
+    let mut big = ColumnSheet::with_config(config()).unwrap();
+    big.push_col(Box::new(ArrayUSize::from_iterator(
+        [usize::MAX, 0, 1, 0, 0, 0, 0, 0].into_iter(),
+    )))
+    .unwrap();
+    assert!(matches!(
+        big.group_by(0, &[(4, Aggregation::Sum), (4, Aggregation::Max)]),
+        Err(Error::AggregationOverflow { col: 4 })
+    ));
+}
+
+#[test]
+fn test_lazy_columns() {
+    use super::{lazy::MATERIALIZED, NanPolicy};
+
+    let materialized = || MATERIALIZED.with(|count| count.get());
+    let air = || {
+        Config::new("./dummies/csv/air.csv")
+            .trim(true)
+            .types(TypesStrategy::Provided(vec![
+                ColumnType::Text,
+                ColumnType::Integer,
+                ColumnType::Integer,
+                ColumnType::Integer,
+            ]))
+            .labels(HeaderStrategy::ReadLabels)
+    };
+    let infer = |path: &'static str, types: TypesStrategy| {
+        move || {
+            Config::new(path)
+                .trim(true)
+                .types(types.clone())
+                .labels(HeaderStrategy::ReadLabels)
+                .nan_policy(NanPolicy::Null)
+        }
+    };
+
+    let configs: Vec<Box<dyn Fn() -> Config<&'static str>>> = vec![
+        Box::new(air),
+        Box::new(infer("./dummies/csv/infer.csv", TypesStrategy::Infer)),
+        Box::new(infer(
+            "./dummies/csv/mixed.csv",
+            TypesStrategy::InferWithUnion,
+        )),
+        Box::new(infer("./dummies/csv/nan.csv", TypesStrategy::Infer)),
+        Box::new(infer("./dummies/csv/address.csv", TypesStrategy::None)),
+    ];
+
+    for config in configs {
+        let eager = ColumnSheet::with_config(config()).unwrap();
+        let lazy = ColumnSheet::with_config(config().lazy(true)).unwrap();
+
+        assert_eq!(lazy.width(), eager.width());
+        assert_eq!(lazy.height(), eager.height());
+        for (lazy, eager) in lazy.headers().zip(eager.headers()) {
+            assert_eq!(lazy.header, eager.header);
+            assert_eq!(lazy.null_count, eager.null_count);
+        }
+
+        assert!(lazy.logical_eq(&eager));
+        assert!(lazy.headers().eq(eager.headers()));
+        assert_eq!(lazy.schema(), eager.schema());
+    }
+
+    let mut sheet = ColumnSheet::with_config(air().lazy(true)).unwrap();
+    let start = materialized();
+
+    // Headers give the provisional type without parsing
+    let kinds = sheet
+        .headers()
+        .map(|header| header.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![DataType::Text, DataType::I32, DataType::I32, DataType::I32]
+    );
+    assert_eq!(sheet.header_at(1).unwrap().null_count, 0);
+    assert_eq!(materialized(), start);
+
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::I32(340)));
+    assert_eq!(materialized(), start + 1);
+    let column = sheet.get_col(1).unwrap();
+    assert_eq!(column.kind(), DataType::I32);
+    assert!(column.as_any().downcast_ref::<ArrayI32>().is_some());
+    assert_eq!(materialized(), start + 1);
+
+    sheet.chart_values(2, UnsignedOverflow::Reject).unwrap();
+    sheet.set_cell("1", 1, 0).unwrap();
+    sheet.chart_values(2, UnsignedOverflow::Reject).unwrap();
+    assert_eq!(sheet.get_cell(1, 0), Some(CellRef::I32(1)));
+    assert_eq!(materialized(), start + 2);
 }
 
 fn test_vec() -> impl Strategy<Value = Vec<isize>> {
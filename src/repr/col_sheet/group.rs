@@ -0,0 +1,388 @@
+use std::{cmp::Ordering, collections::HashMap, fmt, hash::Hash};
+
+use super::{arrays::*, boxed, utils::*, Error, Result};
+
+/// How the values of a column within each group are combined. See
+/// [`ColumnSheet::group_by`](super::ColumnSheet::group_by).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// The sum of the values, as an [`DataType::ISize`], [`DataType::USize`]
+    /// for unsigned columns, or [`DataType::F64`] for float columns
+    Sum,
+    /// The number of values, as a [`DataType::USize`]
+    Count,
+    /// The mean of the values, as a [`DataType::F64`]
+    Mean,
+    /// The least value, with the type of the column
+    Min,
+    /// The greatest value, with the type of the column
+    Max,
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Sum => "sum",
+            Self::Count => "count",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// Typed access to the cells of a concrete [`Column`].
+trait Typed: ColumnValue + Clone {
+    fn cells(column: &Self::Column) -> &[Option<Self>];
+
+    fn collect(cells: impl Iterator<Item = Option<Self>>) -> Self::Column;
+}
+
+macro_rules! typed {
+    ($($value:ty => $column:ty;)+) => {
+        $(
+            impl Typed for $value {
+                fn cells(column: &Self::Column) -> &[Option<Self>] {
+                    column.iter().as_slice()
+                }
+
+                fn collect(cells: impl Iterator<Item = Option<Self>>) -> Self::Column {
+                    <$column>::from_iterator_option(cells)
+                }
+            }
+        )+
+    };
+}
+
+typed! {
+    i32 => ArrayI32;
+    u32 => ArrayU32;
+    isize => ArrayISize;
+    usize => ArrayUSize;
+    bool => ArrayBool;
+    f32 => ArrayF32;
+    f64 => ArrayF64;
+    String => ArrayText;
+    UnionValue => ArrayUnion;
+}
+
+/// Values which can be summed and averaged.
+trait Number: Typed + Copy {
+    const FLOAT: bool;
+    const SIGNED: bool;
+
+    fn to_i128(self) -> i128;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number {
+    ($($value:ty => $float:expr, $signed:expr;)+) => {
+        $(
+            impl Number for $value {
+                const FLOAT: bool = $float;
+                const SIGNED: bool = $signed;
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+number! {
+    i32 => false, true;
+    u32 => false, false;
+    isize => false, true;
+    usize => false, false;
+    f32 => true, true;
+    f64 => true, true;
+}
+
+/// Values which can be ordered, using the same order as [`CellRef`].
+trait Ordered: Typed {
+    fn order(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! ordered {
+    ($($value:ty => $order:expr;)+) => {
+        $(
+            impl Ordered for $value {
+                fn order(&self, other: &Self) -> Ordering {
+                    $order(self, other)
+                }
+            }
+        )+
+    };
+}
+
+ordered! {
+    i32 => Ord::cmp;
+    u32 => Ord::cmp;
+    isize => Ord::cmp;
+    usize => Ord::cmp;
+    bool => Ord::cmp;
+    f32 => f32::total_cmp;
+    f64 => f64::total_cmp;
+    String => Ord::cmp;
+}
+
+/// Returns the cells of `column`, which must hold `T` values.
+fn cells<T: Typed>(column: &dyn Column) -> &[Option<T>] {
+    column
+        .as_any()
+        .downcast_ref::<T::Column>()
+        .map(T::cells)
+        .expect("column of the matching kind")
+}
+
+/// The groups of the rows of a [`ColumnSheet`](super::ColumnSheet) with
+/// equal values in its key column.
+pub(super) struct Groups {
+    /// The group of each row, numbered in order of first appearance
+    ids: Vec<usize>,
+    /// The first row of each group
+    firsts: Vec<usize>,
+}
+
+impl Groups {
+    /// Groups the rows by the values of `key`. Null values form a group of
+    /// their own.
+    pub(super) fn of(key: &dyn Column) -> Self {
+        match key.kind() {
+            DataType::I32 => Self::from_keys(cells::<i32>(key).iter().copied()),
+            DataType::U32 => Self::from_keys(cells::<u32>(key).iter().copied()),
+            DataType::ISize => Self::from_keys(cells::<isize>(key).iter().copied()),
+            DataType::USize => Self::from_keys(cells::<usize>(key).iter().copied()),
+            DataType::Bool => Self::from_keys(cells::<bool>(key).iter().copied()),
+            DataType::F32 => {
+                Self::from_keys(cells::<f32>(key).iter().map(|cell| cell.map(f32::to_bits)))
+            }
+            DataType::F64 => {
+                Self::from_keys(cells::<f64>(key).iter().map(|cell| cell.map(f64::to_bits)))
+            }
+            DataType::Text => Self::from_keys(cells::<String>(key).iter().map(Option::as_deref)),
+            DataType::Union => {
+                let keys = (0..key.len()).map(|row| key.data_ref(row).map(|cell| cell.key()));
+                Self::from_keys(keys)
+            }
+        }
+    }
+
+    fn from_keys<K: Hash + Eq>(keys: impl Iterator<Item = K>) -> Self {
+        let mut groups = HashMap::new();
+        let mut firsts = Vec::new();
+
+        let ids = keys
+            .enumerate()
+            .map(|(row, key)| {
+                let next = groups.len();
+                let id = *groups.entry(key).or_insert(next);
+                if id == next {
+                    firsts.push(row);
+                }
+                id
+            })
+            .collect();
+
+        Self { ids, firsts }
+    }
+
+    /// Returns the number of groups.
+    pub(super) fn len(&self) -> usize {
+        self.firsts.len()
+    }
+
+    /// Returns the value of `key` for each group, keeping its label and
+    /// unit.
+    pub(super) fn keys(&self, key: &dyn Column) -> Box<dyn Column> {
+        let mut column = match key.kind() {
+            DataType::I32 => self.take::<i32>(key),
+            DataType::U32 => self.take::<u32>(key),
+            DataType::ISize => self.take::<isize>(key),
+            DataType::USize => self.take::<usize>(key),
+            DataType::Bool => self.take::<bool>(key),
+            DataType::F32 => self.take::<f32>(key),
+            DataType::F64 => self.take::<f64>(key),
+            DataType::Text => self.take::<String>(key),
+            DataType::Union => self.take::<UnionValue>(key),
+        };
+
+        if let Some(label) = key.label() {
+            column.set_header(label.to_owned());
+        }
+        column.set_unit(key.unit().cloned());
+
+        column
+    }
+
+    fn take<T: Typed>(&self, column: &dyn Column) -> Box<dyn Column> {
+        let cells = cells::<T>(column);
+        boxed(T::collect(
+            self.firsts.iter().map(|row| cells[*row].clone()),
+        ))
+    }
+
+    /// Combines the non-null values of `column`, the `col` column, within
+    /// each group.
+    ///
+    /// Returns an error if `aggregation` is not supported for the type of
+    /// `column`, or if a sum overflows.
+    pub(super) fn aggregate(
+        &self,
+        column: &dyn Column,
+        col: usize,
+        aggregation: Aggregation,
+    ) -> Result<Box<dyn Column>> {
+        let kind = column.kind();
+        let unsupported = Error::InvalidAggregation {
+            col,
+            aggregation,
+            kind,
+        };
+
+        let mut aggregated = match aggregation {
+            Aggregation::Count => match kind {
+                DataType::I32 => self.count::<i32>(column),
+                DataType::U32 => self.count::<u32>(column),
+                DataType::ISize => self.count::<isize>(column),
+                DataType::USize => self.count::<usize>(column),
+                DataType::Bool => self.count::<bool>(column),
+                DataType::F32 => self.count::<f32>(column),
+                DataType::F64 => self.count::<f64>(column),
+                DataType::Text => self.count::<String>(column),
+                DataType::Union => self.count::<UnionValue>(column),
+            },
+            Aggregation::Sum | Aggregation::Mean => {
+                let mean = aggregation == Aggregation::Mean;
+                match kind {
+                    DataType::I32 => self.sum::<i32>(column, col, mean)?,
+                    DataType::U32 => self.sum::<u32>(column, col, mean)?,
+                    DataType::ISize => self.sum::<isize>(column, col, mean)?,
+                    DataType::USize => self.sum::<usize>(column, col, mean)?,
+                    DataType::F32 => self.sum::<f32>(column, col, mean)?,
+                    DataType::F64 => self.sum::<f64>(column, col, mean)?,
+                    DataType::Bool | DataType::Text | DataType::Union => return Err(unsupported),
+                }
+            }
+            Aggregation::Min | Aggregation::Max => {
+                let wanted = if aggregation == Aggregation::Min {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                match kind {
+                    DataType::I32 => self.extreme::<i32>(column, wanted),
+                    DataType::U32 => self.extreme::<u32>(column, wanted),
+                    DataType::ISize => self.extreme::<isize>(column, wanted),
+                    DataType::USize => self.extreme::<usize>(column, wanted),
+                    DataType::Bool => self.extreme::<bool>(column, wanted),
+                    DataType::F32 => self.extreme::<f32>(column, wanted),
+                    DataType::F64 => self.extreme::<f64>(column, wanted),
+                    DataType::Text => self.extreme::<String>(column, wanted),
+                    DataType::Union => return Err(unsupported),
+                }
+            }
+        };
+
+        if aggregation != Aggregation::Count {
+            aggregated.set_unit(column.unit().cloned());
+        }
+
+        Ok(aggregated)
+    }
+
+    fn count<T: Typed>(&self, column: &dyn Column) -> Box<dyn Column> {
+        let mut counts = vec![0; self.len()];
+
+        for (id, cell) in self.ids.iter().zip(cells::<T>(column)) {
+            if cell.is_some() {
+                counts[*id] += 1;
+            }
+        }
+
+        boxed(ArrayUSize::from_iterator(counts.into_iter()))
+    }
+
+    /// Sums the values of each group, or averages them if `mean` is true.
+    /// Groups without values are null.
+    fn sum<T: Number>(
+        &self,
+        column: &dyn Column,
+        col: usize,
+        mean: bool,
+    ) -> Result<Box<dyn Column>> {
+        let cells = cells::<T>(column);
+        let mut counts = vec![0usize; self.len()];
+
+        if T::FLOAT || mean {
+            let mut sums = vec![0.0; self.len()];
+            for (id, cell) in self.ids.iter().zip(cells) {
+                if let Some(value) = cell {
+                    sums[*id] += value.to_f64();
+                    counts[*id] += 1;
+                }
+            }
+
+            let values = sums.into_iter().zip(counts).map(|(sum, count)| {
+                (count != 0).then(|| if mean { sum / count as f64 } else { sum })
+            });
+            return Ok(boxed(ArrayF64::from_iterator_option(values)));
+        }
+
+        let mut sums = vec![0i128; self.len()];
+        for (id, cell) in self.ids.iter().zip(cells) {
+            if let Some(value) = cell {
+                sums[*id] += value.to_i128();
+                counts[*id] += 1;
+            }
+        }
+        let sums = sums.into_iter().zip(counts);
+
+        let overflow = Error::AggregationOverflow { col };
+        if T::SIGNED {
+            let values = sums
+                .map(|(sum, count)| match count {
+                    0 => Ok(None),
+                    _ => isize::try_from(sum).map(Some),
+                })
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map_err(|_| overflow)?;
+            Ok(boxed(ArrayISize::from_iterator_option(values.into_iter())))
+        } else {
+            let values = sums
+                .map(|(sum, count)| match count {
+                    0 => Ok(None),
+                    _ => usize::try_from(sum).map(Some),
+                })
+                .collect::<core::result::Result<Vec<_>, _>>()
+                .map_err(|_| overflow)?;
+            Ok(boxed(ArrayUSize::from_iterator_option(values.into_iter())))
+        }
+    }
+
+    /// Keeps the value of each group which orders `wanted` against every
+    /// other, the first one among equals. Groups without values are null.
+    fn extreme<T: Ordered>(&self, column: &dyn Column, wanted: Ordering) -> Box<dyn Column> {
+        let mut extremes: Vec<Option<&T>> = vec![None; self.len()];
+
+        for (id, cell) in self.ids.iter().zip(cells::<T>(column)) {
+            let Some(value) = cell else {
+                continue;
+            };
+            let extreme = &mut extremes[*id];
+            if extreme.map_or(true, |extreme| value.order(extreme) == wanted) {
+                *extreme = Some(value);
+            }
+        }
+
+        boxed(T::collect(extremes.into_iter().map(|value| value.cloned())))
+    }
+}
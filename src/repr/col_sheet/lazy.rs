@@ -0,0 +1,238 @@
+use std::{cell::Cell, fmt, ops::Range};
+
+use once_cell::unsync::OnceCell;
+
+use super::{infered_data_type, null_nan, parse_column, utils::*, ColumnType as Strategy, CT};
+use crate::repr::Unit;
+
+#[cfg(test)]
+thread_local! {
+    /// The number of columns parsed by [`LazyColumn`]s on this thread.
+    pub(super) static MATERIALIZED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A column read from a file whose values are only parsed on first access,
+/// after which the parsed column is kept. See
+/// [`Config::lazy`](crate::repr::Config::lazy).
+///
+/// The column stands in for the column it parses to, so downcasting it gives
+/// the parsed column. Its label, length and, unless `NaN`s are read as
+/// nulls, null count are known without parsing.
+pub(super) struct LazyColumn {
+    /// The fields of the column, taken once parsed
+    raw: Cell<Vec<String>>,
+    header: Option<String>,
+    strategy: Strategy,
+    inference: (u8, bool),
+    null: String,
+    /// Whether `NaN` floats are read as nulls
+    nan_null: bool,
+    len: usize,
+    nulls: usize,
+    parsed: OnceCell<Box<dyn Column>>,
+}
+
+impl LazyColumn {
+    pub(super) fn new(
+        raw: Vec<String>,
+        header: Option<String>,
+        strategy: Strategy,
+        inference: (u8, bool),
+        null: &str,
+        nan_null: bool,
+    ) -> Self {
+        let nulls = raw
+            .iter()
+            .filter(|value| value.is_empty() || *value == null)
+            .count();
+
+        Self {
+            len: raw.len(),
+            raw: Cell::new(raw),
+            header,
+            strategy,
+            inference,
+            null: null.to_owned(),
+            nan_null,
+            nulls,
+            parsed: OnceCell::new(),
+        }
+    }
+
+    /// Returns the parsed column, parsing it on the first call.
+    fn column(&self) -> &dyn Column {
+        self.parsed
+            .get_or_init(|| {
+                let mut column = parse_column(
+                    self.raw.take(),
+                    self.header.clone(),
+                    self.strategy,
+                    self.inference,
+                    &self.null,
+                );
+                if self.nan_null {
+                    null_nan(column.as_mut());
+                }
+
+                #[cfg(test)]
+                MATERIALIZED.with(|count| count.set(count.get() + 1));
+
+                column
+            })
+            .as_ref()
+    }
+
+    fn column_mut(&mut self) -> &mut dyn Column {
+        self.column();
+        self.parsed
+            .get_mut()
+            .expect("Column should be parsed")
+            .as_mut()
+    }
+
+    /// Returns the type the column is most likely parsed to, judging by the
+    /// fields its type was inferred from.
+    fn provisional(&self) -> DataType {
+        match self.strategy {
+            Strategy::None => DataType::Text,
+            Strategy::Infer(_) | Strategy::InferUnion(_) => infered_data_type(self.inference.0),
+            Strategy::Type(CT::Integer) => DataType::I32,
+            Strategy::Type(CT::Number) => DataType::ISize,
+            Strategy::Type(CT::Float) => DataType::F32,
+            Strategy::Type(CT::Boolean) => DataType::Bool,
+            Strategy::Type(CT::Text | CT::Mixed | CT::None) => DataType::Text,
+        }
+    }
+}
+
+impl fmt::Debug for LazyColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.parsed.get() {
+            Some(column) => column.fmt(f),
+            None => f
+                .debug_struct("LazyColumn")
+                .field("header", &self.header)
+                .field("len", &self.len)
+                .field("provisional", &self.provisional())
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl Sealed for LazyColumn {
+    fn push(&mut self, value: &str, null: &str) {
+        self.column_mut().push(value, null)
+    }
+
+    fn remove(&mut self, idx: usize) {
+        self.column_mut().remove(idx)
+    }
+
+    fn remove_all(&mut self) {
+        self.column_mut().remove_all()
+    }
+
+    fn insert(&mut self, value: &str, idx: usize, null: &str) {
+        self.column_mut().insert(value, idx, null)
+    }
+
+    fn apply_index_swap(&mut self, indices: &[usize]) {
+        self.column_mut().apply_index_swap(indices)
+    }
+
+    fn set_kind_hint(&mut self, kind: Option<CT>) -> bool {
+        self.column_mut().set_kind_hint(kind)
+    }
+
+    fn set_unit(&mut self, unit: Option<Unit>) {
+        self.column_mut().set_unit(unit)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self.column_mut().as_any_mut()
+    }
+
+    fn provisional_kind(&self) -> Option<DataType> {
+        match self.parsed.get() {
+            Some(_) => None,
+            None => Some(self.provisional()),
+        }
+    }
+}
+
+impl Column for LazyColumn {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.column().as_any()
+    }
+
+    fn label(&self) -> Option<&str> {
+        match self.parsed.get() {
+            Some(column) => column.label(),
+            None => self.header.as_deref(),
+        }
+    }
+
+    fn kind(&self) -> DataType {
+        self.column().kind()
+    }
+
+    fn data_ref(&self, idx: usize) -> Option<CellRef<'_>> {
+        self.column().data_ref(idx)
+    }
+
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        self.column().data_refs(rows, out)
+    }
+
+    fn bounds(&self) -> Option<(CellRef<'_>, CellRef<'_>)> {
+        self.column().bounds()
+    }
+
+    fn len(&self) -> usize {
+        self.parsed.get().map_or(self.len, |column| column.len())
+    }
+
+    fn null_count(&self) -> usize {
+        match self.parsed.get() {
+            Some(column) => column.null_count(),
+            None if self.nan_null => self.column().null_count(),
+            None => self.nulls,
+        }
+    }
+
+    fn kind_hint(&self) -> Option<CT> {
+        self.parsed.get().and_then(|column| column.kind_hint())
+    }
+
+    fn unit(&self) -> Option<&Unit> {
+        self.parsed.get().and_then(|column| column.unit())
+    }
+
+    fn clear(&mut self, idx: usize) {
+        self.column_mut().clear(idx)
+    }
+
+    fn clear_all(&mut self) {
+        self.column_mut().clear_all()
+    }
+
+    fn set_header(&mut self, header: String) {
+        self.column_mut().set_header(header)
+    }
+
+    fn clear_header(&mut self) {
+        self.column_mut().clear_header()
+    }
+
+    fn set_position(&mut self, value: &str, idx: usize, null: &str) -> bool {
+        self.column_mut().set_position(value, idx, null)
+    }
+
+    fn swap(&mut self, x: usize, y: usize) {
+        self.column_mut().swap(x, y)
+    }
+
+    fn convert_col(&self, to: DataType) -> Box<dyn Column> {
+        self.column().convert_col(to)
+    }
+}
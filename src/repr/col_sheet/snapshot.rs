@@ -0,0 +1,374 @@
+//! Binary snapshots of a [`ColumnSheet`], written in the format described by
+//! [`snapshot`](crate::repr::snapshot).
+//!
+//! Columns are written as buffers rather than cell by cell. Each column has a
+//! validity bitmap with a bit set for every cell holding a value, alongside
+//! its values as little endian bytes, with the slots of empty cells zeroed.
+//! Text columns hold their concatenated text and the offset at which each
+//! cell ends, while union columns hold only the values of non-empty cells.
+
+use serde::{Deserialize, Serialize};
+
+use super::{arrays::*, utils::*, ColumnSheet, Error, Result};
+use crate::repr::{
+    snapshot::{self, Bytes, SnapshotError, SnapshotKind},
+    ColumnType, LoadReport, Unit,
+};
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    columns: Vec<ColumnBuffers>,
+    primary: Option<usize>,
+    height: usize,
+    null_string: String,
+    originals: Vec<Option<ColumnBuffers>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ColumnBuffers {
+    header: Option<String>,
+    unit: Option<Unit>,
+    kind_hint: Option<ColumnType>,
+    validity: Bytes,
+    values: Values,
+}
+
+#[derive(Serialize, Deserialize)]
+enum Values {
+    I32(Bytes),
+    U32(Bytes),
+    ISize(Bytes),
+    USize(Bytes),
+    /// A bitmap of the values
+    Bool(Bytes),
+    F32(Bytes),
+    F64(Bytes),
+    Text {
+        offsets: Bytes,
+        text: Bytes,
+    },
+    Union(Vec<UnionValue>),
+}
+
+/// Values written with a fixed number of bytes. Pointer sized integers are
+/// always written with 64 bits.
+trait Fixed: Copy + Default {
+    const WIDTH: usize;
+
+    fn write(self, out: &mut Vec<u8>);
+
+    fn read(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! fixed {
+    ($($value:ty as $wide:ty;)+) => {
+        $(
+            impl Fixed for $value {
+                const WIDTH: usize = std::mem::size_of::<$wide>();
+
+                fn write(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&(self as $wide).to_le_bytes());
+                }
+
+                fn read(bytes: &[u8]) -> Option<Self> {
+                    let value = <$wide>::from_le_bytes(bytes.try_into().ok()?);
+                    value.try_into().ok()
+                }
+            }
+        )+
+    };
+}
+
+fixed! {
+    i32 as i32;
+    u32 as u32;
+    isize as i64;
+    usize as u64;
+    f32 as f32;
+    f64 as f64;
+    u64 as u64;
+}
+
+fn bitmap_len(len: usize) -> usize {
+    (len + 7) / 8
+}
+
+fn bitmap(bits: impl ExactSizeIterator<Item = bool>) -> Bytes {
+    let mut bytes = vec![0; bitmap_len(bits.len())];
+    for (idx, bit) in bits.enumerate() {
+        if bit {
+            bytes[idx / 8] |= 1 << (idx % 8);
+        }
+    }
+
+    Bytes(bytes)
+}
+
+fn bit(bitmap: &[u8], idx: usize) -> bool {
+    bitmap[idx / 8] & (1 << (idx % 8)) != 0
+}
+
+fn validity<T>(cells: &[Option<T>]) -> Bytes {
+    bitmap(cells.iter().map(Option::is_some))
+}
+
+fn write_fixed<T: Fixed>(cells: &[Option<T>]) -> Bytes {
+    let mut bytes = Vec::with_capacity(cells.len() * T::WIDTH);
+    for cell in cells {
+        cell.unwrap_or_default().write(&mut bytes);
+    }
+
+    Bytes(bytes)
+}
+
+fn read_values<T: Fixed>(values: &[u8], height: usize) -> Option<Vec<T>> {
+    if values.len() != height * T::WIDTH {
+        return None;
+    }
+
+    values.chunks_exact(T::WIDTH).map(T::read).collect()
+}
+
+fn read_fixed<T: Fixed>(validity: &[u8], values: &[u8], height: usize) -> Option<Vec<Option<T>>> {
+    let values = read_values(values, height)?;
+
+    Some(
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| bit(validity, idx).then_some(value))
+            .collect(),
+    )
+}
+
+fn write_column(column: &dyn Column) -> ColumnBuffers {
+    let column_any = column.as_any();
+
+    let (validity, values) = if let Some(col) = column_any.downcast_ref::<ArrayI32>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::I32(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayU32>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::U32(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayISize>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::ISize(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayUSize>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::USize(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayF32>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::F32(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayF64>() {
+        let cells = col.iter().as_slice();
+        (validity(cells), Values::F64(write_fixed(cells)))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayBool>() {
+        let cells = col.iter().as_slice();
+        let values = bitmap(cells.iter().map(|cell| cell.unwrap_or_default()));
+        (validity(cells), Values::Bool(values))
+    } else if let Some(col) = column_any.downcast_ref::<ArrayUnion>() {
+        let cells = col.iter().as_slice();
+        let values = cells.iter().flatten().cloned().collect();
+        (validity(cells), Values::Union(values))
+    } else {
+        let col = column_any
+            .downcast_ref::<ArrayText>()
+            .expect("Columns are one of the crate's arrays");
+        let cells = col.iter().as_slice();
+        let mut offsets = Vec::with_capacity(cells.len() * u64::WIDTH);
+        let mut text = Vec::new();
+        for cell in cells {
+            text.extend_from_slice(cell.as_deref().unwrap_or_default().as_bytes());
+            (text.len() as u64).write(&mut offsets);
+        }
+        let values = Values::Text {
+            offsets: Bytes(offsets),
+            text: Bytes(text),
+        };
+        (validity(cells), values)
+    };
+
+    ColumnBuffers {
+        header: column.label().map(ToOwned::to_owned),
+        unit: column.unit().cloned(),
+        kind_hint: column.kind_hint(),
+        validity,
+        values,
+    }
+}
+
+fn read_text(validity: &[u8], offsets: &[u8], text: &[u8], height: usize) -> Option<ArrayText> {
+    let offsets = read_values::<u64>(offsets, height)?;
+    let text = std::str::from_utf8(text).ok()?;
+
+    let mut start = 0;
+    let mut cells = Vec::with_capacity(height);
+    for (idx, end) in offsets.into_iter().enumerate() {
+        let end = usize::try_from(end).ok()?;
+        let value = text.get(start..end)?;
+        cells.push(bit(validity, idx).then(|| value.to_owned()));
+        start = end;
+    }
+
+    (start == text.len()).then(|| ArrayText::from_iterator_option(cells.into_iter()))
+}
+
+fn read_original(buffers: ColumnBuffers, height: usize) -> Option<ArrayText> {
+    let validity = &buffers.validity.0;
+    if validity.len() != bitmap_len(height) {
+        return None;
+    }
+
+    match buffers.values {
+        Values::Text { offsets, text } => read_text(validity, &offsets.0, &text.0, height),
+        _ => None,
+    }
+}
+
+fn read_column(buffers: ColumnBuffers, height: usize) -> Option<Box<dyn Column>> {
+    let validity = &buffers.validity.0;
+    if validity.len() != bitmap_len(height) {
+        return None;
+    }
+
+    let mut column: Box<dyn Column> = match buffers.values {
+        Values::I32(values) => Box::new(ArrayI32::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::U32(values) => Box::new(ArrayU32::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::ISize(values) => Box::new(ArrayISize::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::USize(values) => Box::new(ArrayUSize::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::F32(values) => Box::new(ArrayF32::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::F64(values) => Box::new(ArrayF64::from_iterator_option(
+            read_fixed(validity, &values.0, height)?.into_iter(),
+        )),
+        Values::Bool(values) => {
+            if values.0.len() != validity.len() {
+                return None;
+            }
+            let cells = (0..height).map(|idx| bit(validity, idx).then(|| bit(&values.0, idx)));
+            Box::new(ArrayBool::from_iterator_option(cells))
+        }
+        Values::Union(values) => {
+            if values.len() != (0..height).filter(|idx| bit(validity, *idx)).count() {
+                return None;
+            }
+            let mut values = values.into_iter();
+            let cells = (0..height).map(|idx| match bit(validity, idx) {
+                true => values.next(),
+                false => None,
+            });
+            Box::new(ArrayUnion::from_iterator_option(cells))
+        }
+        Values::Text { offsets, text } => {
+            Box::new(read_text(validity, &offsets.0, &text.0, height)?)
+        }
+    };
+
+    if let Some(header) = buffers.header {
+        column.set_header(header);
+    }
+    column.set_unit(buffers.unit);
+    if buffers.kind_hint.is_some() && !column.set_kind_hint(buffers.kind_hint) {
+        return None;
+    }
+
+    Some(column)
+}
+
+impl ColumnSheet {
+    /// Returns a binary snapshot of the sheet, suited to caching it on disk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::repr::{col_sheet::ColumnSheet, Config};
+    ///
+    /// let sheet = ColumnSheet::with_config(Config::new("./dummies/csv/air.csv")).unwrap();
+    /// let read = ColumnSheet::from_bytes(&sheet.to_bytes().unwrap()).unwrap();
+    ///
+    /// assert!(read.iter_rows().eq(sheet.iter_rows()));
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let snapshot = Snapshot {
+            columns: self
+                .columns
+                .iter()
+                .map(|column| write_column(column.as_ref()))
+                .collect(),
+            primary: self.primary,
+            height: self.height,
+            null_string: self.null_string.clone(),
+            originals: self
+                .originals
+                .iter()
+                .map(|original| original.as_ref().map(|original| write_column(original)))
+                .collect(),
+        };
+
+        snapshot::encode(SnapshotKind::ColumnSheet, &snapshot).map_err(Error::Snapshot)
+    }
+
+    /// Reads a sheet from a snapshot written by [`ColumnSheet::to_bytes`].
+    ///
+    /// Returns an [`Error::Snapshot`] if the snapshot is corrupt, was written
+    /// by another version of the format or holds a [`Sheet`](crate::repr::Sheet).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let snapshot: Snapshot =
+            snapshot::decode(SnapshotKind::ColumnSheet, bytes).map_err(Error::Snapshot)?;
+        let height = snapshot.height;
+
+        let malformed = |col: usize| {
+            Error::Snapshot(SnapshotError::Malformed(format!(
+                "column {} does not match its buffers",
+                col
+            )))
+        };
+
+        let columns = snapshot
+            .columns
+            .into_iter()
+            .enumerate()
+            .map(|(col, buffers)| read_column(buffers, height).ok_or_else(|| malformed(col)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let originals = snapshot
+            .originals
+            .into_iter()
+            .enumerate()
+            .map(|(col, buffers)| match buffers {
+                None => Ok(None),
+                Some(buffers) => read_original(buffers, height)
+                    .map(Some)
+                    .ok_or_else(|| malformed(col)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if originals.len() != columns.len() {
+            return Err(malformed(originals.len().min(columns.len())));
+        }
+
+        if let Some(primary) = snapshot.primary.filter(|primary| *primary >= columns.len()) {
+            return Err(Error::InvalidPrimary(primary));
+        }
+
+        Ok(Self {
+            columns,
+            primary: snapshot.primary,
+            height,
+            null_string: snapshot.null_string,
+            originals,
+            meta: Vec::new(),
+            widths: Vec::new(),
+            report: LoadReport::default(),
+        })
+    }
+}
@@ -1,8 +1,17 @@
 use std::{
     any::Any,
+    cell::Cell,
     cmp::{Eq, Ord, Ordering, PartialOrd},
     fmt::{Debug, Display},
-    str::FromStr,
+    mem,
+    ops::Range,
+    slice::IterMut,
+};
+
+use super::arrays::*;
+use crate::{
+    models::ScaleKind,
+    repr::{sheet::utils::ParseField, ColumnType, Data, Unit},
 };
 
 pub(super) use private::Sealed;
@@ -19,15 +28,27 @@ pub enum DataType {
     F64,
     #[default]
     Text,
+    /// Each cell holds a value of any of the other types
+    Union,
 }
 
 impl DataType {
     /// Returns true if a lossless conversion can be made.
+    ///
+    /// A [`DataType::Union`] can be converted to a numeric type, though the
+    /// conversion only succeeds if every value widens to it. See
+    /// [`ArrayUnion::coerce_to`](super::ArrayUnion::coerce_to).
     pub fn can_convert(from: Self, to: Self) -> bool {
         match (from, to) {
             (Self::Text, Self::Text) => true,
             (_, Self::Text) => true,
+            (_, Self::Union) => true,
             (Self::Text, _) => false,
+            (
+                Self::Union,
+                Self::I32 | Self::U32 | Self::ISize | Self::USize | Self::F32 | Self::F64,
+            ) => true,
+            (Self::Union, _) => false,
 
             (Self::U32, Self::U32) => true,
             (Self::U32, Self::USize) => true,
@@ -59,6 +80,20 @@ impl DataType {
     }
 }
 
+/// The type of a [`Sheet`](crate::repr::Sheet) column holding the same values.
+impl From<DataType> for ColumnType {
+    fn from(value: DataType) -> Self {
+        match value {
+            DataType::I32 => Self::Integer,
+            DataType::U32 | DataType::ISize | DataType::USize => Self::Number,
+            DataType::F32 | DataType::F64 => Self::Float,
+            DataType::Bool => Self::Boolean,
+            DataType::Text => Self::Text,
+            DataType::Union => Self::Mixed,
+        }
+    }
+}
+
 impl Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -79,6 +114,27 @@ pub trait Column: Sealed + Debug + Any {
     /// A [`None`] value is returned if `idx` is out of range.
     fn data_ref(&self, idx: usize) -> Option<CellRef<'_>>;
 
+    /// Appends references to the data at each index of `rows` to `out`,
+    /// skipping indices out of range.
+    ///
+    /// Fetching a block of rows at once needs a single dynamic call, rather
+    /// than one for each row.
+    fn data_refs<'a>(&'a self, rows: Range<usize>, out: &mut Vec<CellRef<'a>>) {
+        out.extend(rows.filter_map(|idx| self.data_ref(idx)));
+    }
+
+    /// Returns the smallest and largest values within the [`Column`], if
+    /// any, using the ordering of [`CellRef`].
+    ///
+    /// Nulls are ignored, as are `NaN` and infinite floats, which are left out
+    /// of the bounds of a float [`Scale`](crate::models::Scale) too.
+    fn bounds(&self) -> Option<(CellRef<'_>, CellRef<'_>)> {
+        let mut cells = Vec::with_capacity(self.len());
+        self.data_refs(0..self.len(), &mut cells);
+
+        bounds(cells)
+    }
+
     /// Returns the length of the [`Column`].
     fn len(&self) -> usize;
 
@@ -89,6 +145,20 @@ pub trait Column: Sealed + Debug + Any {
         self.len() == 0
     }
 
+    /// Returns the number of null values within the [`Column`].
+    ///
+    /// The count is cached until the [`Column`] is next mutated.
+    fn null_count(&self) -> usize;
+
+    /// Returns the [`ColumnType`] the [`Column`] is intended to be converted
+    /// to, if any. Only [`DataType::Text`] columns can hold a hint.
+    fn kind_hint(&self) -> Option<ColumnType> {
+        None
+    }
+
+    /// Returns the unit of the values within the [`Column`], if any.
+    fn unit(&self) -> Option<&Unit>;
+
     /// Discards the value at `idx` leaving a [`None`] in its place.
     fn clear(&mut self, idx: usize);
 
@@ -98,6 +168,9 @@ pub trait Column: Sealed + Debug + Any {
     /// Sets the header label for the [`Column`].
     fn set_header(&mut self, header: String);
 
+    /// Removes the header label of the [`Column`].
+    fn clear_header(&mut self);
+
     /// Overwrites the value at `idx` with successfully parsed `value`.
     ///
     /// If `value` matches `null`, a [`None`] is written at `idx`.
@@ -115,14 +188,104 @@ pub trait Column: Sealed + Debug + Any {
     fn convert_col(&self, to: DataType) -> Box<dyn Column>;
 }
 
+/// Types of the values held by a concrete [`Column`], allowing typed access to
+/// its cells.
+pub trait ColumnValue: Sized + 'static {
+    /// The [`DataType`] of columns holding this type.
+    const KIND: DataType;
+
+    /// The concrete column holding this type.
+    type Column: Column;
+
+    /// Returns an iterator over the cells of `column`, which may be modified.
+    fn cells_mut(column: &mut Self::Column) -> IterMut<'_, Option<Self>>;
+}
+
+macro_rules! column_value {
+    ($($value:ty => $column:ty, $kind:expr;)+) => {
+        $(
+            impl ColumnValue for $value {
+                const KIND: DataType = $kind;
+                type Column = $column;
+
+                fn cells_mut(column: &mut Self::Column) -> IterMut<'_, Option<Self>> {
+                    column.iter_mut()
+                }
+            }
+        )+
+    };
+}
+
+column_value! {
+    i32 => ArrayI32, DataType::I32;
+    u32 => ArrayU32, DataType::U32;
+    isize => ArrayISize, DataType::ISize;
+    usize => ArrayUSize, DataType::USize;
+    bool => ArrayBool, DataType::Bool;
+    f32 => ArrayF32, DataType::F32;
+    f64 => ArrayF64, DataType::F64;
+    String => ArrayText, DataType::Text;
+    UnionValue => ArrayUnion, DataType::Union;
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ColumnHeader<'a> {
     pub header: Option<&'a str>,
+    /// The type of the column. For a column read lazily and not yet parsed,
+    /// the type inferred from its first values. See
+    /// [`Config::lazy`](crate::repr::Config::lazy)
     pub kind: DataType,
+    /// The position of the column within the [`ColumnSheet`](super::ColumnSheet)
+    pub idx: usize,
+    /// Whether the column is the primary column
+    pub is_primary: bool,
+    /// The number of null cells within the column
+    pub null_count: usize,
+    /// The number of cells within the column
+    pub height: usize,
+    /// The intended type of the column. See [`Column::kind_hint`]
+    pub kind_hint: Option<ColumnType>,
+    /// The unit of the values within the column
+    pub unit: Option<&'a Unit>,
+    /// Whether no other column has the same label. Columns without a label
+    /// are unique
+    pub is_unique: bool,
+}
+
+/// The lazily computed number of nulls within a [`Column`].
+///
+/// Columns invalidate the count whenever they are mutated. The count is
+/// ignored when comparing columns.
+#[derive(Debug, Clone, Default)]
+pub(super) struct NullCount(Cell<Option<usize>>);
+
+impl NullCount {
+    /// Returns the cached count, counting the nulls in `cells` if there is
+    /// none.
+    pub(super) fn get<T>(&self, cells: &[Option<T>]) -> usize {
+        match self.0.get() {
+            Some(count) => count,
+            None => {
+                let count = cells.iter().filter(|cell| cell.is_none()).count();
+                self.0.set(Some(count));
+                count
+            }
+        }
+    }
+
+    pub(super) fn invalidate(&mut self) {
+        *self.0.get_mut() = None;
+    }
+}
+
+impl PartialEq for NullCount {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 /// Reference to the data within a [`Column`]'s cell.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CellRef<'a> {
     I32(i32),
     U32(u32),
@@ -136,98 +299,87 @@ pub enum CellRef<'a> {
 }
 
 impl<'a> CellRef<'a> {
-    pub(super) fn cmp(&self, b: &Self) -> Ordering {
-        match (self, b) {
-            (CellRef::None, CellRef::None) => Ordering::Equal,
-            (CellRef::None, _) => Ordering::Less,
-            (_, CellRef::None) => Ordering::Greater,
-
-            (CellRef::I32(x), CellRef::I32(y)) => x.cmp(y),
-            (CellRef::I32(x), CellRef::U32(y)) => {
-                if *x < 0 {
-                    Ordering::Less
-                } else {
-                    (*x as u32).cmp(y)
-                }
-            }
-            (CellRef::U32(x), CellRef::I32(y)) => {
-                if *y < 0 {
-                    Ordering::Less
-                } else {
-                    (*y as u32).cmp(x)
-                }
-            }
-            (CellRef::I32(x), CellRef::ISize(y)) => (*x as isize).cmp(y),
-            (CellRef::ISize(x), CellRef::I32(y)) => (*y as isize).cmp(x),
-            (CellRef::I32(x), CellRef::USize(y)) => {
-                if *x < 0 {
-                    Ordering::Less
-                } else {
-                    (*x as usize).cmp(y)
-                }
-            }
-            (CellRef::USize(x), CellRef::I32(y)) => {
-                if *y < 0 {
-                    Ordering::Less
-                } else {
-                    (*y as usize).cmp(x)
-                }
-            }
-            (CellRef::I32(x), CellRef::F32(y)) => (*x as f64).total_cmp(&(*y as f64)),
-            (CellRef::F32(x), CellRef::I32(y)) => (*y as f64).total_cmp(&(*x as f64)),
-            (CellRef::I32(x), CellRef::F64(y)) => (*x as f64).total_cmp(y),
-            (CellRef::F64(x), CellRef::I32(y)) => (*y as f64).total_cmp(x),
-
-            (CellRef::U32(x), CellRef::U32(y)) => x.cmp(y),
-            (CellRef::U32(x), CellRef::USize(y)) => (*x as usize).cmp(y),
-            (CellRef::USize(x), CellRef::U32(y)) => (*y as usize).cmp(x),
-            (CellRef::U32(x), CellRef::ISize(y)) => (*x as isize).cmp(y),
-            (CellRef::ISize(x), CellRef::U32(y)) => (*y as isize).cmp(x),
-            (CellRef::U32(x), CellRef::F32(y)) => (*x as f64).total_cmp(&(*y as f64)),
-            (CellRef::F32(x), CellRef::U32(y)) => (*y as f64).total_cmp(&(*x as f64)),
-            (CellRef::U32(x), CellRef::F64(y)) => (*x as f64).total_cmp(y),
-            (CellRef::F64(x), CellRef::U32(y)) => (*y as f64).total_cmp(x),
-
-            (CellRef::ISize(x), CellRef::ISize(y)) => x.cmp(y),
-            (CellRef::ISize(x), CellRef::USize(y)) => {
-                if *x < 0 {
-                    Ordering::Less
-                } else {
-                    (*x as usize).cmp(y)
-                }
-            }
-            (CellRef::USize(x), CellRef::ISize(y)) => {
-                if *y < 0 {
-                    Ordering::Less
-                } else {
-                    (*y as usize).cmp(x)
-                }
-            }
-            (CellRef::ISize(x), CellRef::F32(y)) => (*x as f64).total_cmp(&(*y as f64)),
-            (CellRef::F32(x), CellRef::ISize(y)) => (*y as f64).total_cmp(&(*x as f64)),
-            (CellRef::ISize(x), CellRef::F64(y)) => (*x as f64).total_cmp(y),
-            (CellRef::F64(x), CellRef::ISize(y)) => (*y as f64).total_cmp(x),
-
-            (CellRef::USize(x), CellRef::USize(y)) => x.cmp(y),
-            (CellRef::USize(x), CellRef::F32(y)) => (*x as f64).total_cmp(&(*y as f64)),
-            (CellRef::F32(x), CellRef::USize(y)) => (*y as f64).total_cmp(&(*x as f64)),
-            (CellRef::USize(x), CellRef::F64(y)) => (*x as f64).total_cmp(y),
-            (CellRef::F64(x), CellRef::USize(y)) => (*y as f64).total_cmp(x),
+    /// Returns a key which is equal for two cells exactly when they compare
+    /// equal, such as for counting distinct cells.
+    pub(crate) fn key(&self) -> (mem::Discriminant<Self>, u64, &'a str) {
+        let bits = match *self {
+            CellRef::I32(value) => value as u64,
+            CellRef::U32(value) => value as u64,
+            CellRef::ISize(value) => value as u64,
+            CellRef::USize(value) => value as u64,
+            CellRef::Bool(value) => value as u64,
+            CellRef::F32(value) => value.to_bits() as u64,
+            CellRef::F64(value) => value.to_bits(),
+            CellRef::Text(_) | CellRef::None => 0,
+        };
+        let text = match *self {
+            CellRef::Text(text) => text,
+            _ => "",
+        };
+
+        (mem::discriminant(self), bits, text)
+    }
 
+    /// Compares two [`CellRef`]s using the total order below:
+    ///
+    /// `None` < `Bool` < numeric values < `Text`
+    ///
+    /// Numeric values are compared by value with each other. Numerically
+    /// equal values of different variants are ordered as `I32` < `U32` <
+    /// `ISize` < `USize` < `F32` < `F64`. Floats are compared using
+    /// [`f64::total_cmp`].
+    ///
+    /// This matches the ordering of `Data` used by `Sheet`.
+    pub(super) fn cmp(&self, b: &Self) -> Ordering {
+        let ord = match (self, b) {
+            (CellRef::Text(x), CellRef::Text(y)) => x.cmp(y),
             (CellRef::Bool(x), CellRef::Bool(y)) => x.cmp(y),
-            (CellRef::Bool(_), _) => Ordering::Less,
-            (_, CellRef::Bool(_)) => Ordering::Greater,
-
-            (CellRef::F32(x), CellRef::F32(y)) => x.total_cmp(y),
-            (CellRef::F32(x), CellRef::F64(y)) => (*x as f64).total_cmp(y),
-            (CellRef::F64(x), CellRef::F32(y)) => (*y as f64).total_cmp(x),
+            (x, y) => match (x.as_i128(), y.as_i128()) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                _ => match (x.as_f64(), y.as_f64()) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    _ => Ordering::Equal,
+                },
+            },
+        };
+
+        ord.then_with(|| self.order_rank().cmp(&b.order_rank()))
+    }
 
-            (CellRef::F64(x), CellRef::F64(y)) => x.total_cmp(y),
+    /// The position of the variant within the total order of [`CellRef`].
+    fn order_rank(&self) -> u8 {
+        match self {
+            CellRef::None => 0,
+            CellRef::Bool(_) => 1,
+            CellRef::I32(_) => 2,
+            CellRef::U32(_) => 3,
+            CellRef::ISize(_) => 4,
+            CellRef::USize(_) => 5,
+            CellRef::F32(_) => 6,
+            CellRef::F64(_) => 7,
+            CellRef::Text(_) => 8,
+        }
+    }
 
-            (CellRef::Text(x), CellRef::Text(y)) => x.cmp(y),
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            CellRef::I32(value) => Some(*value as i128),
+            CellRef::U32(value) => Some(*value as i128),
+            CellRef::ISize(value) => Some(*value as i128),
+            CellRef::USize(value) => Some(*value as i128),
+            _ => None,
+        }
+    }
 
-            (CellRef::Text(_), _) => Ordering::Greater,
-            (_, CellRef::Text(_)) => Ordering::Less,
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            CellRef::I32(value) => Some(*value as f64),
+            CellRef::U32(value) => Some(*value as f64),
+            CellRef::ISize(value) => Some(*value as f64),
+            CellRef::USize(value) => Some(*value as f64),
+            CellRef::F32(value) => Some(*value as f64),
+            CellRef::F64(value) => Some(*value),
+            _ => None,
         }
     }
 }
@@ -248,6 +400,209 @@ impl<'a> From<CellRef<'a>> for Option<String> {
     }
 }
 
+/// Appends references to the `cells` at each index of `rows` to `out`,
+/// skipping indices out of range. See [`Column::data_refs`].
+pub(super) fn cell_refs<'a, T>(
+    cells: &'a [Option<T>],
+    rows: Range<usize>,
+    out: &mut Vec<CellRef<'a>>,
+    to_ref: impl Fn(&'a T) -> CellRef<'a>,
+) {
+    let end = rows.end.min(cells.len());
+    let start = rows.start.min(end);
+
+    out.extend(cells[start..end].iter().map(|cell| match cell {
+        Some(value) => to_ref(value),
+        None => CellRef::None,
+    }));
+}
+
+/// An owned copy of the data within a [`Column`]'s cell. See [`CellRef`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    I32(i32),
+    U32(u32),
+    ISize(isize),
+    USize(usize),
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    Text(String),
+    None,
+}
+
+impl CellValue {
+    /// Returns a reference to the data.
+    pub fn as_cell_ref(&self) -> CellRef<'_> {
+        match self {
+            Self::I32(value) => CellRef::I32(*value),
+            Self::U32(value) => CellRef::U32(*value),
+            Self::ISize(value) => CellRef::ISize(*value),
+            Self::USize(value) => CellRef::USize(*value),
+            Self::Bool(value) => CellRef::Bool(*value),
+            Self::F32(value) => CellRef::F32(*value),
+            Self::F64(value) => CellRef::F64(*value),
+            Self::Text(value) => CellRef::Text(value),
+            Self::None => CellRef::None,
+        }
+    }
+}
+
+impl<'a> From<CellRef<'a>> for CellValue {
+    fn from(value: CellRef<'a>) -> Self {
+        match value {
+            CellRef::I32(value) => Self::I32(value),
+            CellRef::U32(value) => Self::U32(value),
+            CellRef::ISize(value) => Self::ISize(value),
+            CellRef::USize(value) => Self::USize(value),
+            CellRef::Bool(value) => Self::Bool(value),
+            CellRef::F32(value) => Self::F32(value),
+            CellRef::F64(value) => Self::F64(value),
+            CellRef::Text(value) => Self::Text(value.to_owned()),
+            CellRef::None => Self::None,
+        }
+    }
+}
+
+/// Converts `value` to the [`Data`] holding it without loss, if there is one.
+///
+/// `U32` and `USize` values widen to [`Data::Number`] when they fit in an
+/// `isize`, and `F64` values narrow to [`Data::Float`] when an `f32` holds
+/// them exactly.
+pub(crate) fn cell_data(value: CellRef<'_>) -> Option<Data> {
+    let data = match value {
+        CellRef::I32(value) => Data::Integer(value),
+        CellRef::U32(value) => Data::Number(isize::try_from(value).ok()?),
+        CellRef::ISize(value) => Data::Number(value),
+        CellRef::USize(value) => Data::Number(isize::try_from(value).ok()?),
+        CellRef::Bool(value) => Data::Boolean(value),
+        CellRef::F32(value) => Data::Float(value),
+        CellRef::F64(value) => {
+            let narrowed = value as f32;
+            if f64::from(narrowed) != value && !value.is_nan() {
+                return None;
+            }
+            Data::Float(narrowed)
+        }
+        CellRef::Text(value) => Data::Text(value.to_owned()),
+        CellRef::None => Data::None,
+    };
+
+    Some(data)
+}
+
+/// A cell value with no lossless [`Data`] representation, such as a `USize`
+/// above `isize::MAX` or an `F64` no `f32` holds exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnrepresentableCell(pub CellValue);
+
+impl Display for UnrepresentableCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = Option::<String>::from(self.0.as_cell_ref()).unwrap_or_default();
+        write!(f, "Cell value {} does not fit in Data", value)
+    }
+}
+
+impl std::error::Error for UnrepresentableCell {}
+
+/// Converts the value to the [`Data`] holding it without loss. See
+/// [`UnrepresentableCell`].
+impl<'a> TryFrom<CellRef<'a>> for Data {
+    type Error = UnrepresentableCell;
+
+    fn try_from(value: CellRef<'a>) -> Result<Self, Self::Error> {
+        cell_data(value).ok_or_else(|| UnrepresentableCell(value.into()))
+    }
+}
+
+/// How chart building handles unsigned values too large for a
+/// [`Data::Number`]. See [`ColumnSheet::chart_values`](super::ColumnSheet::chart_values).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnsignedOverflow {
+    /// Values which do not fit are an error
+    #[default]
+    Reject,
+    /// A column with a value which does not fit is charted as floats
+    WidenToFloat,
+}
+
+/// The values of a column converted to [`Data`] for chart building.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartValues {
+    /// The kind of scale the values belong on
+    pub kind: ScaleKind,
+    /// The value of each row, in order
+    pub values: Vec<Data>,
+    /// The rows whose value could not be charted exactly
+    pub lossy: Vec<usize>,
+}
+
+/// Converts `value` to [`Data`] for chart building, returning [`None`] for
+/// unsigned values which do not fit in an `isize`.
+///
+/// Unlike [`cell_data`], `F64` values always narrow to [`Data::Float`]. The
+/// returned flag is true if the value was not kept exactly.
+pub(crate) fn chart_data(value: CellRef<'_>) -> Option<(Data, bool)> {
+    let data = match value {
+        CellRef::U32(value) => Data::Number(isize::try_from(value).ok()?),
+        CellRef::USize(value) => Data::Number(isize::try_from(value).ok()?),
+        CellRef::F64(value) => {
+            let narrowed = value as f32;
+            let lossy = f64::from(narrowed) != value && !value.is_nan();
+            return Some((Data::Float(narrowed), lossy));
+        }
+        value => cell_data(value)?,
+    };
+
+    Some((data, false))
+}
+
+/// Converts `value` to a [`Data::Float`], flagging whether it was kept
+/// exactly. Values other than unsigned integers are converted as in
+/// [`chart_data`].
+pub(crate) fn widened_chart_data(value: CellRef<'_>) -> (Data, bool) {
+    let unsigned = match value {
+        CellRef::U32(value) => u128::from(value),
+        CellRef::USize(value) => value as u128,
+        value => return chart_data(value).unwrap_or((Data::None, true)),
+    };
+
+    let widened = unsigned as f32;
+    (Data::Float(widened), widened as u128 != unsigned)
+}
+
+impl<'a> From<&'a Data> for CellRef<'a> {
+    fn from(value: &'a Data) -> Self {
+        match value {
+            Data::Text(value) => Self::Text(value),
+            Data::Integer(value) => Self::I32(*value),
+            Data::Float(value) => Self::F32(*value),
+            Data::Number(value) => Self::ISize(*value),
+            Data::Boolean(value) => Self::Bool(*value),
+            Data::None => Self::None,
+        }
+    }
+}
+
+/// Returns the smallest and largest of `cells`, ignoring nulls and non-finite
+/// floats. See [`Column::bounds`].
+pub(crate) fn bounds<'a>(
+    cells: impl IntoIterator<Item = CellRef<'a>>,
+) -> Option<(CellRef<'a>, CellRef<'a>)> {
+    cells
+        .into_iter()
+        .filter(|cell| match cell {
+            CellRef::None => false,
+            CellRef::F32(value) => value.is_finite(),
+            CellRef::F64(value) => value.is_finite(),
+            _ => true,
+        })
+        .fold(None, |acc, cell| match acc {
+            None => Some((cell, cell)),
+            Some((min, max)) => Some((min.min(cell), max.max(cell))),
+        })
+}
+
 impl<'a> PartialOrd for CellRef<'a> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(Ord::cmp(self, other))
@@ -385,25 +740,25 @@ impl<'a> From<Option<i32>> for CellRef<'a> {
 /// Parses `input` into given type, taking note of both empty and null strings.
 ///
 /// On error, `()` is returned.
-pub(super) fn parse_helper<T: FromStr>(input: &str, null: &str) -> Result<Option<T>, ()> {
+pub(super) fn parse_helper<T: ParseField>(input: &str, null: &str) -> Result<Option<T>, ()> {
     if input.is_empty() || input == null {
         return Ok(None);
     }
 
-    input.parse::<T>().map_err(|_err| {}).map(Some)
+    T::parse_field(input).ok_or(()).map(Some)
 }
 
 /// Discards the error from `parse_helper`.
 ///
 /// Logs any parsing failures
-pub(super) fn parse_unchecked<T: FromStr>(input: &str, null: &str) -> Option<T> {
+pub(super) fn parse_unchecked<T: ParseField>(input: &str, null: &str) -> Option<T> {
     parse_helper(input, null).ok()?
 }
 
 mod private {
     #![allow(unused_imports)]
     use super::super::ColumnSheet;
-    use super::Column;
+    use super::{Column, ColumnType, Unit};
     /// Methods within this trait are kept private to ensure all invariants on
     /// [`ColumnSheet`] are maintained.
     pub trait Sealed {
@@ -429,5 +784,24 @@ mod private {
         /// Applies the provided swap indices to self, sorting the contents of
         /// self as a result.
         fn apply_index_swap(&mut self, indices: &[usize]);
+
+        /// Records the intended type of the column, returning false if the
+        /// column cannot hold a hint.
+        fn set_kind_hint(&mut self, _kind: Option<ColumnType>) -> bool {
+            false
+        }
+
+        /// Sets the unit of the values within the column.
+        fn set_unit(&mut self, unit: Option<Unit>);
+
+        /// Returns the column as a mutable [`Any`](std::any::Any), for
+        /// downcasting to a concrete column.
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+
+        /// Returns the type the column is expected to have if its values are
+        /// yet to be parsed. See [`Config::lazy`](crate::repr::Config::lazy).
+        fn provisional_kind(&self) -> Option<super::DataType> {
+            None
+        }
     }
 }
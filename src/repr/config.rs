@@ -1,9 +1,16 @@
-use std::{fmt, path::Path};
+use std::{borrow::Cow, error, fmt, io, path::Path, str::FromStr};
 
-use super::utils::TypesStrategy;
+use super::{
+    utils::{ColumnType, TypesStrategy},
+    Schema, SchemaCache, SniffResult,
+};
 
 const NULL: &str = "<null>";
 
+/// The number of bytes sampled by [`Config::auto_detect`].
+#[cfg(feature = "csv")]
+const SNIFF_BYTES: usize = 16 * 1024;
+
 /// Determines how headers read
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum HeaderStrategy {
@@ -16,6 +23,19 @@ pub enum HeaderStrategy {
     Provided(Vec<String>),
 }
 
+impl HeaderStrategy {
+    /// The name of the strategy, as accepted by its [`FromStr`]
+    /// implementation. [`HeaderStrategy::Provided`] has no name, as it cannot
+    /// be parsed.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Provided(_) => None,
+            Self::ReadLabels => Some("read"),
+            Self::NoLabels => Some("none"),
+        }
+    }
+}
+
 impl fmt::Display for HeaderStrategy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -30,16 +50,142 @@ impl fmt::Display for HeaderStrategy {
     }
 }
 
+impl FromStr for HeaderStrategy {
+    type Err = ParseHeaderError;
+
+    /// Parses `read` and `none` as their respective strategies, ignoring case
+    /// and surrounding whitespace. See [`HeaderStrategy::name`].
+    ///
+    /// [`HeaderStrategy::Provided`] cannot be parsed from a string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "read" => Ok(Self::ReadLabels),
+            "none" => Ok(Self::NoLabels),
+            _ => Err(ParseHeaderError(s.trim().to_owned())),
+        }
+    }
+}
+
+/// Error returned when parsing a [`HeaderStrategy`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHeaderError(String);
+
+impl fmt::Display for ParseHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid header strategy `{}`. Expected one of `read` or `none`",
+            self.0
+        )
+    }
+}
+
+impl error::Error for ParseHeaderError {}
+
+/// Error returned when parsing a [`ColumnType`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTypeError {
+    token: String,
+    position: Option<usize>,
+}
+
+impl ParseTypeError {
+    pub(crate) fn new(token: impl Into<String>, position: Option<usize>) -> Self {
+        Self {
+            token: token.into(),
+            position,
+        }
+    }
+
+    /// Returns the token which failed to parse.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Returns the zero-based position of the failed token within a type list,
+    /// if it was parsed as part of one.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid column type `{}`", self.token)?;
+
+        if let Some(position) = self.position {
+            write!(f, " at position {}", position)?;
+        }
+
+        write!(
+            f,
+            ". Expected one of `text`, `integer`, `number`, `float`, `boolean`, `none` or `mixed`"
+        )
+    }
+}
+
+impl error::Error for ParseTypeError {}
+
+/// Parses a comma separated list of [`ColumnType`]s, such as
+/// `text,int,int,float`.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::repr::{parse_type_list, ColumnType};
+///
+/// let types = parse_type_list("text, int,float").unwrap();
+/// assert_eq!(types, vec![ColumnType::Text, ColumnType::Integer, ColumnType::Float]);
+///
+/// let error = parse_type_list("text,integr").unwrap_err();
+/// assert_eq!(error.token(), "integr");
+/// assert_eq!(error.position(), Some(1));
+/// ```
+pub fn parse_type_list(s: &str) -> Result<Vec<ColumnType>, ParseTypeError> {
+    s.split(',')
+        .enumerate()
+        .map(|(position, token)| {
+            token
+                .parse::<ColumnType>()
+                .map_err(|_| ParseTypeError::new(token.trim(), Some(position)))
+        })
+        .collect()
+}
+
+/// Options for reading a CSV file. The same [`Config`] is used to construct
+/// both a `Sheet` and a `ColumnSheet`.
+///
+/// Records are split by the `csv` crate, so both agree with it: `\n` and
+/// `\r\n` both end a record, the final newline is optional, empty lines are
+/// skipped and quoted fields may hold delimiters, newlines and escaped
+/// quotes. A line of only delimiters is a record of empty fields. The width
+/// is that of the widest record, including the header record.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config<P: AsRef<Path>> {
     pub(super) path: P,
-    pub(super) primary: usize,
+    pub(super) primary: Option<usize>,
+    /// Whether the primary column was set rather than left as the default
+    pub(super) primary_set: bool,
     pub(super) trim: bool,
     pub(super) label_strategy: HeaderStrategy,
     pub(super) flexible: bool,
     pub(super) type_strategy: TypesStrategy,
     pub(super) delimiter: u8,
+    pub(super) quote: u8,
+    pub(super) auto_detect: bool,
     pub(super) null_string: String,
+    pub(super) max_rows: Option<usize>,
+    pub(super) drop_empty_cols: bool,
+    pub(super) decimal_separator: char,
+    pub(super) preserve_original: bool,
+    pub(super) collect_meta: bool,
+    pub(super) nan_policy: NanPolicy,
+    pub(super) type_mismatch: TypeMismatchPolicy,
+    pub(super) encoding: Encoding,
+    pub(super) select_columns: Option<SelectColumns>,
+    pub(super) expected_schema: Option<Schema>,
+    pub(super) schema_cache: Option<SchemaCache>,
+    pub(super) lazy: bool,
 }
 
 impl<P: AsRef<Path>> Config<P> {
@@ -47,19 +193,39 @@ impl<P: AsRef<Path>> Config<P> {
     pub fn new(path: P) -> Self {
         Self {
             path,
-            primary: 0,
+            primary: Some(0),
+            primary_set: false,
             trim: false,
             label_strategy: HeaderStrategy::NoLabels,
             flexible: false,
             type_strategy: TypesStrategy::None,
             delimiter: b',',
+            quote: b'"',
+            auto_detect: false,
             null_string: NULL.to_string(),
+            max_rows: None,
+            drop_empty_cols: false,
+            decimal_separator: '.',
+            preserve_original: false,
+            collect_meta: false,
+            nan_policy: NanPolicy::Keep,
+            type_mismatch: TypeMismatchPolicy::Fail,
+            encoding: Encoding::Utf8,
+            select_columns: None,
+            expected_schema: None,
+            schema_cache: None,
+            lazy: false,
         }
     }
 
-    /// Sets the primary column.
-    pub fn primary(self, primary: usize) -> Self {
-        Self { primary, ..self }
+    /// Sets the primary column. Defaults to the first column, and [`None`]
+    /// reads a sheet without a primary column.
+    pub fn primary(self, primary: impl Into<Option<usize>>) -> Self {
+        Self {
+            primary: primary.into(),
+            primary_set: true,
+            ..self
+        }
     }
 
     /// Whether fields are trimmed of leading and trailing whitespaces or not.
@@ -94,9 +260,742 @@ impl<P: AsRef<Path>> Config<P> {
         self
     }
 
-    /// The string to be considered as a null field.
+    /// The quote character to use when parsing CSV.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Detects the delimiter, quote character and whether the first record
+    /// is a header from the start of the file when it is read, overriding
+    /// [`Config::delimiter`], [`Config::quote`] and [`Config::labels`]. See
+    /// [`Config::sniff`].
+    ///
+    /// Only used when reading from a path.
+    pub fn auto_detect(mut self, auto_detect: bool) -> Self {
+        self.auto_detect = auto_detect;
+        self
+    }
+
+    /// The string to be considered as a null field. Both [`Sheet`] and
+    /// [`ColumnSheet`] read fields matching it as [`Data::None`], so by
+    /// default a field of `<null>` is read as missing rather than as text.
+    ///
+    /// [`Sheet`]: crate::repr::Sheet
+    /// [`ColumnSheet`]: crate::repr::col_sheet::ColumnSheet
+    /// [`Data::None`]: crate::repr::Data::None
+    pub fn null_string(mut self, null_string: impl Into<String>) -> Self {
+        self.null_string = null_string.into();
+        self
+    }
+
+    /// The maximum number of records to read, not counting a header row.
+    /// Types are only inferred from the records read. [`None`] reads every
+    /// record.
+    pub fn max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Whether columns with only empty cells are dropped after loading.
+    pub fn drop_empty_cols(mut self, drop_empty_cols: bool) -> Self {
+        self.drop_empty_cols = drop_empty_cols;
+        self
+    }
+
+    /// The character separating the integer and fractional parts of floats,
+    /// such as `,` for many European locales. Fields using it are read as
+    /// floats.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Keeps the original text of fields whose parsed value is written
+    /// differently, such as `007` read as the integer `7` or a field trimmed
+    /// of whitespace. The original text is written back by the CSV writers.
+    ///
+    /// Floats read with a decimal separator other than `.` do not keep their
+    /// text, as the separator they are written with is chosen when writing.
+    /// Nor do fields read as empty cells, such as the null string, which are
+    /// written with [`WriteOptions::null_string`].
+    pub fn preserve_original(self, preserve_original: bool) -> Self {
+        Self {
+            preserve_original,
+            ..self
+        }
+    }
+
+    /// Gathers a [`ColumnMeta`](super::utils::ColumnMeta) for each column
+    /// once it is loaded, reading its values again. The bounds, statistics
+    /// and charts of a sheet reuse the meta until its column changes.
+    pub fn collect_meta(self, collect_meta: bool) -> Self {
+        Self {
+            collect_meta,
+            ..self
+        }
+    }
+
+    /// How float fields which parse as `NaN`, such as `NaN` or `nan`, are read.
+    pub fn nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    /// How fields which do not match a type given by
+    /// [`TypesStrategy::Provided`] are read.
+    pub fn type_mismatch(mut self, policy: TypeMismatchPolicy) -> Self {
+        self.type_mismatch = policy;
+        self
+    }
+
+    /// The character encoding of the input. Input which is not UTF-8 is
+    /// transcoded before it is parsed.
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Reads only the columns chosen by `select`, in the order chosen. The
+    /// fields of other columns are skipped as each record is read.
+    ///
+    /// Every other option refers to the selected columns: provided types and
+    /// labels are given for each selected column in order, and the primary
+    /// column is a position within the file. It becomes the position of that
+    /// column among the selected columns. A primary column set with
+    /// [`Config::primary`] must be selected, while the default primary column
+    /// falls back to the first selected column when it is not.
+    pub fn select_columns(mut self, select: SelectColumns) -> Self {
+        self.select_columns = Some(select);
+        self
+    }
+
+    /// Fails the load if the sheet read is not compatible with `schema`,
+    /// with the differences in the error. See [`SchemaDiff::is_compatible`].
+    ///
+    /// [`SchemaDiff::is_compatible`]: super::SchemaDiff::is_compatible
+    pub fn expect_schema(mut self, schema: Schema) -> Self {
+        self.expected_schema = Some(schema);
+        self
+    }
+
+    /// Uses the types of `cache` instead of inferring them when the file
+    /// still has the fingerprint of the cache, as when a file is opened
+    /// again. Types are inferred as usual if the file changed or its cells
+    /// no longer match the cached types.
+    ///
+    /// Only used by a `Sheet` read from a file with [`TypesStrategy::Infer`]
+    /// or [`TypesStrategy::InferWithUnion`]. See
+    /// [`Sheet::schema_fingerprint`](super::Sheet::schema_fingerprint).
+    pub fn with_schema_cache(mut self, cache: SchemaCache) -> Self {
+        self.schema_cache = Some(cache);
+        self
+    }
+
+    /// Leaves the values of each column of a `ColumnSheet` unparsed until
+    /// the column is first accessed, such as through
+    /// [`ColumnSheet::get_col`](super::col_sheet::ColumnSheet::get_col) or a
+    /// chart, keeping the parsed column from then on. Until then, its header
+    /// gives the type inferred from its first values, which parsing may
+    /// settle otherwise.
+    ///
+    /// Suits exploring files with many columns when only a few are needed.
+    /// Keeping original text, collecting meta, dropping empty columns and
+    /// expecting a schema parse every column as it is read. Not used by a
+    /// `Sheet`.
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Detects the dialect of the file from at most its first `sample_bytes`
+    /// bytes, decoded with [`Config::encoding`]. See [`sniff_sample`].
+    ///
+    /// [`sniff_sample`]: super::sniff_sample
+    pub fn sniff(&self, sample_bytes: usize) -> io::Result<SniffResult> {
+        use io::Read;
+
+        let mut file = std::fs::File::open(self.path.as_ref())?;
+        let mut sample = Vec::with_capacity(sample_bytes);
+        (&mut file)
+            .take(sample_bytes as u64 + 1)
+            .read_to_end(&mut sample)?;
+
+        // Leave out the record cut short by the end of the sample
+        if sample.len() > sample_bytes {
+            sample.truncate(sample_bytes);
+            let end = sample.iter().rposition(|byte| *byte == b'\n');
+            sample.truncate(end.map_or(0, |end| end + 1));
+        }
+
+        let sample = match self.encoding {
+            Encoding::Latin1 => sample.iter().copied().map(char::from).collect(),
+            Encoding::Utf8 | Encoding::Utf8Lossy => String::from_utf8_lossy(&sample).into_owned(),
+        };
+
+        Ok(super::sniff_sample(&sample))
+    }
+
+    /// Uses the dialect of `sniffed`, such as one returned by
+    /// [`Config::sniff`]. Provided labels are kept.
+    pub fn sniffed(mut self, sniffed: &SniffResult) -> Self {
+        self.delimiter = sniffed.delimiter;
+        self.quote = sniffed.quote;
+        if !matches!(self.label_strategy, HeaderStrategy::Provided(_)) {
+            self.label_strategy = if sniffed.has_header {
+                HeaderStrategy::ReadLabels
+            } else {
+                HeaderStrategy::NoLabels
+            };
+        }
+        self
+    }
+
+    /// Returns the config with the dialect of its file applied if
+    /// [`Config::auto_detect`] is set.
+    #[cfg(feature = "csv")]
+    pub(super) fn detected(self) -> io::Result<Self> {
+        if !self.auto_detect {
+            return Ok(self);
+        }
+
+        let sniffed = self.sniff(SNIFF_BYTES)?;
+        Ok(self.sniffed(&sniffed))
+    }
+
+    /// Returns a [`csv::Reader`] of `reader` configured with the parsing
+    /// options, decoding the input with [`Config::encoding`].
+    #[cfg(feature = "csv")]
+    pub(super) fn csv_reader<R: io::Read>(&self, reader: R) -> csv::Reader<Decoder<R>> {
+        self.csv_builder()
+            .from_reader(Decoder::new(reader, self.encoding))
+    }
+
+    /// Returns a [`csv::ReaderBuilder`] configured with the parsing options.
+    ///
+    /// Fields are left untrimmed when the original text is preserved, so the
+    /// readers trim them instead.
+    #[cfg(feature = "csv")]
+    pub(super) fn csv_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .has_headers(self.label_strategy == HeaderStrategy::ReadLabels)
+            .trim(if self.trim && !self.preserve_original {
+                csv::Trim::All
+            } else {
+                csv::Trim::None
+            })
+            .flexible(self.flexible)
+            .delimiter(self.delimiter)
+            .quote(self.quote);
+        builder
+    }
+}
+
+/// The columns read from a CSV file. See [`Config::select_columns`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectColumns {
+    /// The positions of the columns within the file
+    Indices(Vec<usize>),
+    /// The labels of the columns within the header record. Needs
+    /// [`HeaderStrategy::ReadLabels`]
+    Names(Vec<String>),
+}
+
+impl SelectColumns {
+    /// Returns the positions of the selected columns within each record, in
+    /// the order selected. `headers` is the header record, if read.
+    #[cfg(feature = "csv")]
+    pub(super) fn resolve(
+        &self,
+        headers: Option<&csv::StringRecord>,
+        trim: bool,
+    ) -> Result<Vec<usize>, SelectColumnsError> {
+        let indices = match self {
+            Self::Indices(indices) => indices.clone(),
+            Self::Names(names) => {
+                let headers = headers.ok_or(SelectColumnsError::LabelsNotRead)?;
+                names
+                    .iter()
+                    .map(|name| {
+                        headers
+                            .iter()
+                            .position(|header| if trim { header.trim() } else { header } == name)
+                            .ok_or_else(|| SelectColumnsError::UnknownLabel(name.clone()))
+                    })
+                    .collect::<Result<Vec<usize>, SelectColumnsError>>()?
+            }
+        };
+
+        for (pos, idx) in indices.iter().enumerate() {
+            if indices[..pos].contains(idx) {
+                return Err(SelectColumnsError::Duplicate(*idx));
+            }
+        }
+
+        Ok(indices)
+    }
+
+    /// Returns the position of `primary` among the `selected` columns. If
+    /// it is not selected, the first selected column is used instead unless
+    /// the primary was `set` explicitly.
+    #[cfg(feature = "csv")]
+    pub(super) fn primary(selected: &[usize], primary: usize, set: bool) -> Option<usize> {
+        match selected.iter().position(|idx| *idx == primary) {
+            Some(pos) => Some(pos),
+            None if !set => Some(0),
+            None => None,
+        }
+    }
+
+    /// Returns an error if any of the `selected` columns is beyond `width`,
+    /// the width of the widest record read.
+    #[cfg(feature = "csv")]
+    pub(super) fn check_width(selected: &[usize], width: usize) -> Result<(), SelectColumnsError> {
+        match selected.iter().find(|idx| **idx >= width) {
+            Some(idx) => Err(SelectColumnsError::OutOfRange(*idx)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the fields of `record` at each of `selected`, with empty fields
+/// for those beyond its end.
+#[cfg(feature = "csv")]
+pub(super) fn select_fields(record: &csv::StringRecord, selected: &[usize]) -> csv::StringRecord {
+    selected
+        .iter()
+        .map(|idx| record.get(*idx).unwrap_or_default())
+        .collect()
+}
+
+/// Error returned when the columns chosen by [`Config::select_columns`]
+/// cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectColumnsError {
+    /// Columns were selected by name without reading labels
+    LabelsNotRead,
+    /// No column has the label
+    UnknownLabel(String),
+    /// The position is beyond the widest record
+    OutOfRange(usize),
+    /// The column at the position was selected more than once
+    Duplicate(usize),
+}
+
+impl fmt::Display for SelectColumnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LabelsNotRead => write!(
+                f,
+                "Columns can only be selected by name when labels are read"
+            ),
+            Self::UnknownLabel(label) => write!(f, "No column labelled `{}`", label),
+            Self::OutOfRange(idx) => write!(f, "Selected column {} is out of range", idx),
+            Self::Duplicate(idx) => write!(f, "Column {} is selected more than once", idx),
+        }
+    }
+}
+
+impl error::Error for SelectColumnsError {}
+
+/// Error from reading or writing CSV, including failing to open the file
+/// read.
+///
+/// The error of the underlying CSV parser is kept opaque, so the parser is
+/// not part of the public API.
+#[derive(Debug)]
+pub struct CsvError(Box<dyn error::Error + Send + Sync>);
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(value: io::Error) -> Self {
+        Self(Box::new(value))
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<csv::Error> for CsvError {
+    fn from(value: csv::Error) -> Self {
+        Self(Box::new(value))
+    }
+}
+
+/// How `NaN` floats are handled when reading a CSV file.
+///
+/// Kept `NaN`s are ordered using [`f32::total_cmp`] by both sheets, so `NaN`
+/// sorts after every other float. Like infinities, they are left out of the
+/// bounds of a float [`Scale`](crate::models::Scale) and leave a stacked bar
+/// without a total. Infinities are read as floats under either policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NanPolicy {
+    /// `NaN` fields are read as floats
+    #[default]
+    Keep,
+    /// `NaN` fields in float columns are read as empty cells
+    Null,
+}
+
+/// How fields which do not match their column's type are read when types
+/// are given by [`TypesStrategy::Provided`].
+///
+/// Each field replaced or cast is logged with the `trace` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeMismatchPolicy {
+    /// A `Sheet` fails to load, while a `ColumnSheet` reads the column as
+    /// text
+    #[default]
+    Fail,
+    /// Fields are cast to the type of their column where no information is
+    /// lost, such as the text `7` into an integer column or an integer into a
+    /// float column. Fields which cannot be cast are read as empty cells
+    Coerce,
+    /// Fields are read as empty cells
+    Null,
+}
+
+/// The character encoding of CSV input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// UTF-8. Invalid input is an error
+    #[default]
+    Utf8,
+    /// UTF-8, with each invalid sequence replaced by `U+FFFD`. The number of
+    /// sequences replaced is reported by [`LoadReport::replaced`], and logged
+    /// with the `trace` feature.
+    Utf8Lossy,
+    /// ISO-8859-1, where every byte is the character of the same code point
+    Latin1,
+}
+
+/// Information about how a file was read into a sheet. It describes the
+/// file as read, and is not updated as the sheet changes. Sheets not read
+/// from a file have the default report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoadReport {
+    /// The number of invalid sequences replaced when reading with
+    /// [`Encoding::Utf8Lossy`].
+    pub replaced: usize,
+    /// The number of labels in the header record and the width of the
+    /// widest data record, if [`HeaderStrategy::ReadLabels`] read a header
+    /// record of a different width than the data. The sheet is as wide as
+    /// the wider of the two, padding labels or cells as needed.
+    pub header_width_mismatch: Option<(usize, usize)>,
+}
+
+/// Information about a file read as a preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewInfo {
+    /// Whether the file had more records than were read.
+    pub truncated: bool,
+    /// The total size of the file in bytes.
+    pub file_size: u64,
+    /// The number of invalid sequences replaced when reading with
+    /// [`Encoding::Utf8Lossy`].
+    pub replaced: usize,
+}
+
+/// The number of bytes a [`Decoder`] reads at a time.
+#[cfg(feature = "csv")]
+const DECODE_CHUNK: usize = 8 * 1024;
+
+/// Transcodes input in an [`Encoding`] to UTF-8 as it is read.
+#[cfg(feature = "csv")]
+pub(super) struct Decoder<R> {
+    inner: R,
+    encoding: Encoding,
+    /// Bytes read but not yet decoded, such as an incomplete sequence.
+    pending: Vec<u8>,
+    decoded: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    replaced: usize,
+}
+
+#[cfg(feature = "csv")]
+impl<R: io::Read> Decoder<R> {
+    fn new(inner: R, encoding: Encoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            pending: Vec::new(),
+            decoded: Vec::new(),
+            pos: 0,
+            eof: false,
+            replaced: 0,
+        }
+    }
+
+    /// The number of invalid sequences replaced so far.
+    pub(super) fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// Reads and decodes the next chunk of input.
+    fn fill(&mut self) -> io::Result<()> {
+        let mut chunk = [0; DECODE_CHUNK];
+        let read = self.inner.read(&mut chunk)?;
+        self.eof = read == 0;
+        self.decoded.clear();
+        self.pos = 0;
+
+        match self.encoding {
+            Encoding::Latin1 => {
+                for byte in &chunk[..read] {
+                    let mut buf = [0; 2];
+                    let encoded = char::from(*byte).encode_utf8(&mut buf);
+                    self.decoded.extend_from_slice(encoded.as_bytes());
+                }
+            }
+            Encoding::Utf8 | Encoding::Utf8Lossy => {
+                self.pending.extend_from_slice(&chunk[..read]);
+                self.decode_lossy();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the pending bytes, leaving an incomplete sequence at their end
+    /// unless the input has ended.
+    fn decode_lossy(&mut self) {
+        let mut start = 0;
+
+        while start < self.pending.len() {
+            let err = match std::str::from_utf8(&self.pending[start..]) {
+                Ok(valid) => {
+                    self.decoded.extend_from_slice(valid.as_bytes());
+                    start = self.pending.len();
+                    break;
+                }
+                Err(err) => err,
+            };
+
+            let valid = start + err.valid_up_to();
+            self.decoded.extend_from_slice(&self.pending[start..valid]);
+
+            match err.error_len() {
+                Some(len) => start = valid + len,
+                None if self.eof => start = self.pending.len(),
+                None => {
+                    start = valid;
+                    break;
+                }
+            }
+
+            self.decoded
+                .extend_from_slice(char::REPLACEMENT_CHARACTER.to_string().as_bytes());
+            self.replaced += 1;
+        }
+
+        self.pending.drain(..start);
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<R: io::Read> io::Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.encoding == Encoding::Utf8 {
+            return self.inner.read(buf);
+        }
+
+        while self.pos == self.decoded.len() {
+            if self.eof {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+
+        let available = &self.decoded[self.pos..];
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.pos += len;
+
+        Ok(len)
+    }
+}
+
+/// Replaces the decimal `separator` within `field` with a `.` if the result is
+/// a float. Other fields are returned as-is.
+pub(super) fn normalize_decimal(field: &str, separator: char) -> Cow<'_, str> {
+    if separator == '.' || field.contains('.') || field.matches(separator).count() != 1 {
+        return Cow::Borrowed(field);
+    }
+
+    let normalized = field.replacen(separator, ".", 1);
+    if normalized.parse::<f64>().is_ok() {
+        Cow::Owned(normalized)
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Determines which fields are quoted when writing CSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// Only fields which need quotes, such as those containing the
+    /// delimiter, are quoted
+    #[default]
+    Necessary,
+    /// Every field is quoted
+    Always,
+    /// Every field which is not a number is quoted
+    NonNumeric,
+    /// No field is quoted
+    Never,
+}
+
+impl QuoteStyle {
+    #[cfg(feature = "csv")]
+    fn to_csv(self) -> csv::QuoteStyle {
+        match self {
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+/// The line ending written after each CSV record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineTerminator {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+/// Options for writing a CSV file. The same [`WriteOptions`] are used to
+/// write both a `Sheet` and a `ColumnSheet`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WriteOptions {
+    pub(super) delimiter: u8,
+    pub(super) quote_style: QuoteStyle,
+    pub(super) terminator: LineTerminator,
+    pub(super) decimal_separator: char,
+    pub(super) null_string: String,
+    pub(super) headers: bool,
+    pub(super) trim_padding: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote_style: QuoteStyle::default(),
+            terminator: LineTerminator::default(),
+            decimal_separator: '.',
+            null_string: String::new(),
+            headers: true,
+            trim_padding: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Returns the default [`WriteOptions`]: comma delimited with `\n` line
+    /// endings, `.` as the decimal separator, empty fields for nulls and a
+    /// header row.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The field delimiter.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Which fields are quoted.
+    ///
+    /// Fields containing the delimiter are still quoted with
+    /// [`QuoteStyle::Never`] if the decimal separator is the delimiter.
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// The line ending written after each record.
+    pub fn terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// The character separating the integer and fractional parts of floats.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// The field written for empty cells.
     pub fn null_string(mut self, null_string: impl Into<String>) -> Self {
         self.null_string = null_string.into();
         self
     }
+
+    /// Whether a header row is written.
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Whether the cells synthesized to pad short rows, such as those read
+    /// with [`Config::flexible`], are left out, writing each row only up to
+    /// its original width. Defaults to false.
+    pub fn trim_padding(mut self, trim: bool) -> Self {
+        self.trim_padding = trim;
+        self
+    }
+
+    /// Returns a [`csv::Writer`] over `writer` configured with the options.
+    #[cfg(feature = "csv")]
+    pub(super) fn csv_writer<W: std::io::Write>(&self, writer: W) -> csv::Writer<W> {
+        let separator_is_delimiter =
+            self.decimal_separator.is_ascii() && self.decimal_separator as u8 == self.delimiter;
+        let quote_style = match self.quote_style {
+            QuoteStyle::Never if separator_is_delimiter => QuoteStyle::Necessary,
+            style => style,
+        };
+        let terminator = match self.terminator {
+            LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+            LineTerminator::Crlf => csv::Terminator::CRLF,
+        };
+
+        csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote_style(quote_style.to_csv())
+            .terminator(terminator)
+            .has_headers(false)
+            .flexible(self.trim_padding)
+            .from_writer(writer)
+    }
+
+    /// Formats a float with the decimal separator. Whole floats keep a
+    /// fractional part so they are not read back as integers.
+    pub(super) fn format_float(&self, float: impl fmt::Display) -> String {
+        let mut formatted = float.to_string();
+
+        if formatted.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            formatted.push_str(".0");
+        }
+
+        if self.decimal_separator == '.' {
+            formatted
+        } else {
+            formatted.replacen('.', &self.decimal_separator.to_string(), 1)
+        }
+    }
 }
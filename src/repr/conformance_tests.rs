@@ -0,0 +1,212 @@
+//! Checks that a [`Sheet`] and a [`ColumnSheet`] read from the same CSV agree
+//! on their shape and every cell.
+
+use super::{
+    col_sheet::{CellRef, ColumnSheet},
+    sheet::utils::ParseField,
+    Config, Data, HeaderStrategy, Sheet, TypesStrategy,
+};
+
+const FIXTURES: [&str; 8] = [
+    "header_only.csv",
+    "crlf.csv",
+    "no_trailing_newline.csv",
+    "quoted.csv",
+    "empty_trailing_fields.csv",
+    "blank_lines.csv",
+    "ragged.csv",
+    "padded.csv",
+];
+
+/// The headers and cells of a sheet, with empty cells as [`None`].
+#[derive(Debug, PartialEq)]
+struct Shape {
+    width: usize,
+    height: usize,
+    headers: Vec<String>,
+    cells: Vec<Vec<Option<String>>>,
+}
+
+fn sheet_shape(sheet: &Sheet) -> Shape {
+    let cells = sheet
+        .iter_rows()
+        .map(|row| {
+            (0..sheet.width())
+                .map(
+                    |col| match row.get_cell_by_index(col).map(|cell| cell.get_data()) {
+                        Some(Data::None) | None => None,
+                        Some(data) => Some(data.to_string()),
+                    },
+                )
+                .collect()
+        })
+        .collect();
+
+    Shape {
+        width: sheet.width(),
+        height: sheet.height(),
+        headers: sheet
+            .get_headers()
+            .iter()
+            .map(|header| header.label.clone())
+            .collect(),
+        cells,
+    }
+}
+
+fn column_sheet_shape(sheet: &ColumnSheet) -> Shape {
+    let cells = sheet
+        .iter_rows()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| match cell {
+                    CellRef::None => None,
+                    CellRef::I32(value) => Some(value.to_string()),
+                    CellRef::U32(value) => Some(value.to_string()),
+                    CellRef::ISize(value) => Some(value.to_string()),
+                    CellRef::USize(value) => Some(value.to_string()),
+                    CellRef::Bool(value) => Some(value.to_string()),
+                    CellRef::F32(value) => Some(value.to_string()),
+                    CellRef::F64(value) => Some(value.to_string()),
+                    CellRef::Text(value) => Some(value.to_owned()),
+                })
+                .collect()
+        })
+        .collect();
+
+    Shape {
+        width: sheet.width(),
+        height: sheet.height(),
+        headers: sheet
+            .headers()
+            .map(|header| header.header.unwrap_or_default().to_owned())
+            .collect(),
+        cells,
+    }
+}
+
+fn assert_conforms(name: &str, config: impl Fn(String) -> Config<String>) {
+    let path = format!("./dummies/csv/conformance/{}", name);
+
+    let sheet = Sheet::with_config(config(path.clone()))
+        .unwrap_or_else(|err| panic!("{}: Sheet failed to load: {}", name, err));
+    let column_sheet = ColumnSheet::with_config(config(path))
+        .unwrap_or_else(|err| panic!("{}: ColumnSheet failed to load: {}", name, err));
+
+    assert_eq!(
+        sheet_shape(&sheet),
+        column_sheet_shape(&column_sheet),
+        "{} was read differently",
+        name
+    );
+}
+
+#[test]
+fn test_conformance() {
+    for name in FIXTURES {
+        for labels in [HeaderStrategy::ReadLabels, HeaderStrategy::NoLabels] {
+            for trim in [true, false] {
+                assert_conforms(name, |path| {
+                    Config::new(path)
+                        .labels(labels.clone())
+                        .types(TypesStrategy::None)
+                        .flexible(true)
+                        .trim(trim)
+                });
+            }
+
+            assert_conforms(name, |path| {
+                Config::new(path)
+                    .labels(labels.clone())
+                    .types(TypesStrategy::Infer)
+                    .flexible(true)
+                    .trim(true)
+            });
+        }
+    }
+}
+
+#[test]
+fn test_conformance_numbers() {
+    // A `Sheet` parses every value, while a `ColumnSheet` keeps the text of
+    // columns not inferred as numeric. They only agree on how numbers are
+    // written when every column is typed.
+    assert_conforms("numbers.csv", |path| {
+        Config::new(path)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .trim(true)
+    });
+}
+
+#[test]
+fn test_conformance_numeric_syntax() {
+    let config = || {
+        Config::new("./dummies/csv/conformance/numeric_syntax.csv".to_string())
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    };
+    assert_conforms("numeric_syntax.csv", |_| config());
+
+    let sheet = Sheet::with_config(config()).unwrap();
+    let column_sheet = ColumnSheet::with_config(config()).unwrap();
+    assert_eq!(sheet.schema(), column_sheet.schema());
+
+    assert_eq!(
+        column_sheet.get_cell(2, 0),
+        Some(CellRef::F32(f32::INFINITY))
+    );
+    assert_eq!(
+        column_sheet.get_cell(2, 1),
+        Some(CellRef::F32(f32::NEG_INFINITY))
+    );
+
+    // Too large a literal is not read as an infinity
+    assert_eq!(f32::parse_field("1e40"), None);
+    assert_eq!(f32::parse_field("-1e40"), None);
+    assert_eq!(f64::parse_field("1e40"), Some(1e40));
+    assert_eq!(f32::parse_field("-Infinity"), Some(f32::NEG_INFINITY));
+}
+
+#[test]
+fn test_conformance_shapes() {
+    let load = |name: &str| {
+        let config = Config::new(format!("./dummies/csv/conformance/{}", name))
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::None)
+            .flexible(true);
+        column_sheet_shape(&ColumnSheet::with_config(config).unwrap())
+    };
+
+    // Line endings and a missing final newline do not change the records
+    let crlf = load("crlf.csv");
+    assert_eq!((crlf.width, crlf.height), (3, 2));
+    assert_eq!(crlf.cells[1][2].as_deref(), Some("y"));
+    let shape = load("no_trailing_newline.csv");
+    assert_eq!((shape.width, shape.height), (2, 2));
+    assert_eq!(shape.cells[1][1].as_deref(), Some("2"));
+
+    // Quoted fields keep embedded delimiters, newlines and escaped quotes
+    let quoted = load("quoted.csv");
+    assert_eq!(quoted.height, 3);
+    assert_eq!(quoted.cells[0][0].as_deref(), Some("alpha, beta"));
+    assert_eq!(quoted.cells[0][1].as_deref(), Some("line one\nline two"));
+    assert_eq!(quoted.cells[1][0].as_deref(), Some("say \"hi\""));
+    assert_eq!(quoted.cells[1][1], None);
+    assert_eq!(quoted.cells[2][1].as_deref(), Some("one\r\ntwo"));
+
+    // Empty lines are skipped, but a record of empty fields is a row
+    assert_eq!(load("blank_lines.csv").height, 2);
+    let trailing = load("empty_trailing_fields.csv");
+    assert_eq!((trailing.width, trailing.height), (3, 3));
+    assert_eq!(trailing.cells[2], vec![None, None, None]);
+
+    // Only a header record gives columns without rows
+    let header_only = load("header_only.csv");
+    assert_eq!((header_only.width, header_only.height), (2, 0));
+
+    let ragged = load("ragged.csv");
+    assert_eq!((ragged.width, ragged.height), (4, 3));
+    assert_eq!(ragged.headers[3], "");
+    assert_eq!(ragged.cells[2], vec![Some("7\n8".into()), None, None, None]);
+}
@@ -0,0 +1,389 @@
+//! The shape of a sheet: the label, type and nullability of each of its
+//! columns, used to detect when files which should look alike drift apart
+//! and to skip inferring the types of a file read again.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+    time::SystemTime,
+};
+
+use super::ColumnType;
+
+/// A column of a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaColumn {
+    /// The label of the column, empty if it has none
+    pub label: String,
+    /// The type of the column
+    pub kind: ColumnType,
+    /// Whether any cell of the column is empty
+    pub nullable: bool,
+}
+
+impl SchemaColumn {
+    pub fn new(label: impl Into<String>, kind: ColumnType, nullable: bool) -> Self {
+        Self {
+            label: label.into(),
+            kind,
+            nullable,
+        }
+    }
+}
+
+/// The columns of a [`Sheet`](super::Sheet) or
+/// [`ColumnSheet`](super::col_sheet::ColumnSheet), in order. See
+/// [`Sheet::schema`](super::Sheet::schema).
+///
+/// The types of a `ColumnSheet` are given as the [`ColumnType`] of a `Sheet`
+/// holding the same values, so the schemas of a file read either way
+/// compare equal.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::repr::{ColumnType, Schema, SchemaColumn};
+///
+/// let last_week = Schema::new(vec![
+///     SchemaColumn::new("Region", ColumnType::Text, false),
+///     SchemaColumn::new("Sales", ColumnType::Integer, false),
+/// ]);
+/// let this_week = Schema::new(vec![
+///     SchemaColumn::new("Region", ColumnType::Text, false),
+///     SchemaColumn::new("Sales", ColumnType::Float, true),
+/// ]);
+///
+/// let diff = last_week.diff(&this_week);
+/// assert!(!diff.is_compatible());
+/// assert_eq!(
+///     diff.retyped,
+///     vec![("Sales".to_string(), ColumnType::Integer, ColumnType::Float)]
+/// );
+/// assert_eq!(diff.became_nullable, vec!["Sales".to_string()]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schema {
+    columns: Vec<SchemaColumn>,
+}
+
+impl Schema {
+    pub fn new(columns: Vec<SchemaColumn>) -> Self {
+        Self { columns }
+    }
+
+    /// Returns the columns of the schema, in order.
+    pub fn columns(&self) -> &[SchemaColumn] {
+        &self.columns
+    }
+
+    /// Returns the number of columns in the schema.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns how `other` differs from this schema.
+    ///
+    /// Columns are matched by label, the first of a repeated label matching
+    /// first. A column of this schema without a match is guessed to be
+    /// renamed if the column of `other` at the same position is also without
+    /// a match and has the same type. Any other columns without a match are
+    /// removed from, or added to, `other`.
+    pub fn diff(&self, other: &Schema) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+        let mut matched = vec![false; other.columns.len()];
+        let mut unmatched = Vec::new();
+
+        for (idx, column) in self.columns.iter().enumerate() {
+            let found = other
+                .columns
+                .iter()
+                .enumerate()
+                .position(|(pos, col)| !matched[pos] && col.label == column.label);
+
+            let Some(pos) = found else {
+                unmatched.push(idx);
+                continue;
+            };
+            matched[pos] = true;
+
+            let col = &other.columns[pos];
+            if pos != idx {
+                diff.moved.push((column.label.clone(), idx, pos));
+            }
+            if col.kind != column.kind {
+                diff.retyped
+                    .push((column.label.clone(), column.kind, col.kind));
+            }
+            if col.nullable && !column.nullable {
+                diff.became_nullable.push(column.label.clone());
+            }
+        }
+
+        for idx in unmatched {
+            let column = &self.columns[idx];
+            match other.columns.get(idx) {
+                Some(col) if !matched[idx] && col.kind == column.kind => {
+                    matched[idx] = true;
+                    diff.renamed.push((column.label.clone(), col.label.clone()));
+                }
+                _ => diff.removed.push(column.clone()),
+            }
+        }
+
+        diff.added = other
+            .columns
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(col, _)| col.clone())
+            .collect();
+
+        diff
+    }
+}
+
+/// How one [`Schema`] differs from another. See [`Schema::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchemaDiff {
+    /// The columns only in the other schema
+    pub added: Vec<SchemaColumn>,
+    /// The columns only in the original schema
+    pub removed: Vec<SchemaColumn>,
+    /// The columns guessed to be renamed, as their original and new labels
+    pub renamed: Vec<(String, String)>,
+    /// The columns at a different position, as their label and original and
+    /// new positions
+    pub moved: Vec<(String, usize, usize)>,
+    /// The columns whose type changed, as their label and original and new
+    /// types
+    pub retyped: Vec<(String, ColumnType, ColumnType)>,
+    /// The labels of the columns which had no empty cells but now do
+    pub became_nullable: Vec<String>,
+}
+
+impl SchemaDiff {
+    /// Returns true if the schemas are alike.
+    pub fn is_empty(&self) -> bool {
+        self.is_compatible() && self.became_nullable.is_empty()
+    }
+
+    /// Returns true if sheets of the schemas can be concatenated without
+    /// losing their types, that is if only the nullability of columns
+    /// changed. See [`Sheet::concat`](super::Sheet::concat).
+    pub fn is_compatible(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.moved.is_empty()
+            && self.retyped.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        let changes = self
+            .added
+            .iter()
+            .map(|col| format!("added `{}`", col.label))
+            .chain(
+                self.removed
+                    .iter()
+                    .map(|col| format!("removed `{}`", col.label)),
+            )
+            .chain(
+                self.renamed
+                    .iter()
+                    .map(|(from, to)| format!("renamed `{from}` to `{to}`")),
+            )
+            .chain(
+                self.moved
+                    .iter()
+                    .map(|(label, from, to)| format!("moved `{label}` from {from} to {to}")),
+            )
+            .chain(self.retyped.iter().map(|(label, from, to)| {
+                format!("retyped `{label}` from {} to {}", from.name(), to.name())
+            }))
+            .chain(
+                self.became_nullable
+                    .iter()
+                    .map(|label| format!("`{label}` became nullable")),
+            )
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", changes.join("; "))
+    }
+}
+
+/// The number of bytes hashed from each end of a file. See [`Fingerprint`].
+const FINGERPRINT_BYTES: u64 = 4096;
+
+/// A cheap summary of a file, used to tell whether it changed since a
+/// [`SchemaCache`] was taken: its size, modification time and a hash of its
+/// first and last few kilobytes.
+///
+/// Edits which keep all of these alike go unnoticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingerprint {
+    size: u64,
+    modified: Option<SystemTime>,
+    head: u64,
+    tail: u64,
+}
+
+impl Fingerprint {
+    /// Returns the fingerprint of the file at `path`.
+    pub fn of(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+        let size = metadata.len();
+
+        let mut buffer = Vec::with_capacity(FINGERPRINT_BYTES as usize);
+        (&mut file)
+            .take(FINGERPRINT_BYTES)
+            .read_to_end(&mut buffer)?;
+        let head = fnv1a(&buffer);
+
+        buffer.clear();
+        file.seek(SeekFrom::Start(size.saturating_sub(FINGERPRINT_BYTES)))?;
+        file.take(FINGERPRINT_BYTES).read_to_end(&mut buffer)?;
+        let tail = fnv1a(&buffer);
+
+        Ok(Self {
+            size,
+            modified: metadata.modified().ok(),
+            head,
+            tail,
+        })
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// The 64 bit FNV-1a hash of `bytes`, which is stable across runs and
+/// platforms so persisted fingerprints stay comparable.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The [`Schema`] of a [`Sheet`](super::Sheet) alongside the [`Fingerprint`]
+/// of the file it was read from. See
+/// [`Sheet::schema_fingerprint`](super::Sheet::schema_fingerprint).
+///
+/// Given to [`Config::with_schema_cache`](super::Config::with_schema_cache),
+/// the types of the schema are used instead of inferring them again while
+/// the file is unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaCache {
+    schema: Schema,
+    fingerprint: Fingerprint,
+}
+
+impl SchemaCache {
+    pub fn new(schema: Schema, fingerprint: Fingerprint) -> Self {
+        Self {
+            schema,
+            fingerprint,
+        }
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
+    }
+
+    /// Returns the types of the schema if the file at `path` still has the
+    /// fingerprint of the cache.
+    #[cfg(feature = "csv")]
+    pub(crate) fn kinds_for(&self, path: &Path) -> Option<Vec<ColumnType>> {
+        let fingerprint = Fingerprint::of(path).ok()?;
+
+        (fingerprint == self.fingerprint)
+            .then(|| self.schema.columns.iter().map(|col| col.kind).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(columns: &[(&str, ColumnType)]) -> Schema {
+        Schema::new(
+            columns
+                .iter()
+                .map(|(label, kind)| SchemaColumn::new(*label, *kind, false))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_schema_diff() {
+        let base = schema(&[
+            ("Month", ColumnType::Text),
+            ("Sales", ColumnType::Integer),
+            ("Region", ColumnType::Text),
+        ]);
+
+        assert!(base.diff(&base).is_empty());
+        assert_eq!(base.diff(&base).to_string(), "no changes");
+
+        let other = schema(&[
+            ("Month", ColumnType::Text),
+            ("Revenue", ColumnType::Integer),
+            ("Stores", ColumnType::Integer),
+        ]);
+        let diff = base.diff(&other);
+        assert_eq!(diff.renamed, vec![("Sales".into(), "Revenue".into())]);
+        assert_eq!(
+            diff.removed,
+            vec![SchemaColumn::new("Region", ColumnType::Text, false)]
+        );
+        assert_eq!(
+            diff.added,
+            vec![SchemaColumn::new("Stores", ColumnType::Integer, false)]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "added `Stores`; removed `Region`; renamed `Sales` to `Revenue`"
+        );
+
+        // Reordered columns are matched by label
+        let reordered = schema(&[
+            ("Region", ColumnType::Text),
+            ("Month", ColumnType::Text),
+            ("Sales", ColumnType::Integer),
+        ]);
+        let diff = base.diff(&reordered);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.moved.len(), 3);
+        assert!(!diff.is_compatible());
+
+        let mut nullable = base.clone();
+        nullable.columns[2].nullable = true;
+        let diff = base.diff(&nullable);
+        assert!(diff.is_compatible());
+        assert!(!diff.is_empty());
+        assert!(nullable.diff(&base).is_empty());
+    }
+}
@@ -1,26 +1,48 @@
 use std::{
+    borrow::Cow,
     collections::{HashMap, HashSet},
-    path::Path,
     slice::{Iter, IterMut},
 };
 
-use csv::Trim;
-
 use crate::models::{
+    self,
     bar::{Bar, BarChart},
+    box_plot::{BoxItem, BoxPlot},
+    heatmap::HeatMap,
     line::{Line, LineGraph},
     stacked_bar::{StackedBar, StackedBarChart},
-    Point, Scale, ScaleKind,
+    CategoricalOrder, ChartKind, LabelTransform, Point, Scale, ScaleBuilder, ScaleKind,
 };
 
+#[cfg(feature = "csv")]
+use std::{io, path::Path};
+
 use super::config::*;
+#[cfg(feature = "csv")]
+use super::{Fingerprint, SchemaCache};
+use super::{Schema, SchemaColumn};
 pub mod error;
 pub use error::*;
 pub mod utils;
 pub use utils::*;
+pub mod mapping;
+pub use mapping::*;
+pub mod suggest;
+pub use suggest::*;
+pub mod view;
+pub use view::*;
+pub mod chart;
+pub use chart::*;
+pub mod constraint;
+pub use constraint::*;
+mod transposed;
+use transposed::Transposed;
+#[cfg(feature = "serde")]
+mod snapshot;
 mod tests;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell {
     id: usize,
     data: Data,
@@ -32,6 +54,16 @@ impl Cell {
         Cell { id, data }
     }
 
+    /// Creates a new [`Cell`] from any value convertible to [`Data`].
+    pub fn from_data(id: usize, data: impl Into<Data>) -> Self {
+        Self::new(id, data.into())
+    }
+
+    /// Returns the id of the cell, cited by errors. See [`Row::id`].
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
     pub fn get_data(&self) -> &Data {
         &self.data
     }
@@ -61,22 +93,61 @@ impl Cell {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Row {
     id: usize,
     cells: Vec<Cell>,
-    primary: usize,
+    primary: Option<usize>,
     id_counter: usize,
+    /// The number of trailing cells filled in with [`Data::None`] to pad the
+    /// row to the width of its sheet.
+    #[cfg_attr(feature = "serde", serde(default))]
+    padding: usize,
 }
 
 impl Row {
-    pub fn new(record: csv::StringRecord, id: usize, primary_index: usize) -> Self {
+    /// Creates a new [`Row`] by parsing each of `fields`, such as the fields
+    /// of a CSV record. Empty fields are [`Data::None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::repr::{Data, Row};
+    ///
+    /// let row = Row::new(["3", "", "1.5"], 0, 0);
+    ///
+    /// assert_eq!(row.get_cell_by_index(0).unwrap().get_data(), &Data::Integer(3));
+    /// assert_eq!(row.get_cell_by_index(1).unwrap().get_data(), &Data::None);
+    /// ```
+    pub fn new(
+        fields: impl IntoIterator<Item = impl AsRef<str>>,
+        id: usize,
+        primary_index: impl Into<Option<usize>>,
+    ) -> Self {
+        Self::parse(fields, id, primary_index.into(), "", '.')
+    }
+
+    /// Creates a new [`Row`] from `fields`, treating fields matching `null` as
+    /// [`Data::None`].
+    fn parse(
+        fields: impl IntoIterator<Item = impl AsRef<str>>,
+        id: usize,
+        primary_index: Option<usize>,
+        null: &str,
+        decimal_separator: char,
+    ) -> Self {
         let mut counter: usize = 0;
         let cells: Vec<Cell> = {
             let mut cells = vec![];
 
-            record.iter().for_each(|val| {
-                let data: Data = val.to_string().into();
+            fields.into_iter().for_each(|val| {
+                let val = val.as_ref();
+                let data = if val == null {
+                    Data::None
+                } else {
+                    Data::from_csv_field(&normalize_decimal(val, decimal_separator), None)
+                };
                 let cell = Cell::new(counter, data);
                 cells.push(cell);
                 counter += 1;
@@ -89,19 +160,58 @@ impl Row {
             cells,
             primary: primary_index,
             id_counter: counter,
+            padding: 0,
+        }
+    }
+
+    /// Creates a new [`Row`] directly from `cells` without any parsing.
+    ///
+    /// Each cell is given an id matching its position within the row.
+    pub fn from_data(cells: Vec<Data>, id: usize, primary: impl Into<Option<usize>>) -> Self {
+        let cells: Vec<Cell> = cells
+            .into_iter()
+            .enumerate()
+            .map(|(id, data)| Cell::new(id, data))
+            .collect();
+        let id_counter = cells.len();
+
+        Row {
+            id,
+            cells,
+            primary: primary.into(),
+            id_counter,
+            padding: 0,
         }
     }
 
-    fn width(&self) -> usize {
-        self.cells.len()
+    /// Returns the id of the row, cited by errors.
+    ///
+    /// A [`Sheet`] gives each row and cell an id equal to its position when
+    /// it is constructed, so [`Row::get_cell_by_id`] and
+    /// [`Row::get_cell_by_index`] agree. Edits may set ids and positions
+    /// apart: sorting reorders rows and removing a column keeps the ids of
+    /// the cells after it. See [`Sheet::renumber`].
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the number of cells the row had before it was padded with
+    /// [`Data::None`] to the width of its sheet, such as the number of fields
+    /// of a short record read with [`Config::flexible`].
+    ///
+    /// Cells past the original width are synthesized. Appending a column
+    /// after them makes them part of the row.
+    pub fn original_width(&self) -> usize {
+        self.cells.len() - self.padding
     }
 
     fn is_key_valid(&self, key: usize) -> bool {
         self.cells.len() > key
     }
 
+    /// Returns an error if the row has a primary key without a cell.
     pub fn is_primary_key_valid(&self) -> Result<()> {
-        if !self.is_key_valid(self.primary) {
+        if self.primary.map_or(false, |key| !self.is_key_valid(key)) {
             return Err(Error::InvalidPrimaryKey(format!(
                 "Primary key is invalid for row with id: {}",
                 self.id
@@ -152,7 +262,7 @@ impl Row {
 
     pub fn set_primary_key(&mut self, new_primary: usize) -> Result<()> {
         if new_primary < self.cells.len() {
-            self.primary = new_primary;
+            self.primary = Some(new_primary);
             Ok(())
         } else {
             Err(Error::InvalidPrimaryKey(
@@ -173,22 +283,51 @@ impl Row {
         self.cells.iter_mut()
     }
 
-    pub fn get_primary_key(&self) -> usize {
+    /// Returns the position of the primary cell of the row, if it has one.
+    pub fn get_primary_key(&self) -> Option<usize> {
         self.primary
     }
 
     pub fn get_primary_cell(&self) -> Option<&Cell> {
-        self.cells.get(self.primary)
+        self.cells.get(self.primary?)
     }
 
+    /// Returns the cell with the id `id`, if any. See [`Row::id`].
     pub fn get_cell_by_id(&self, id: usize) -> Option<&Cell> {
-        self.cells.iter().find(|cl| cl.id == id)
+        // Ids match positions unless a column was removed
+        match self.cells.get(id) {
+            Some(cell) if cell.id == id => Some(cell),
+            _ => self.cells.iter().find(|cl| cl.id == id),
+        }
     }
 
     pub fn get_cell_by_index(&self, index: usize) -> Option<&Cell> {
         self.cells.get(index)
     }
 
+    /// Removes the cell at `index`, shifting the primary key to match. See
+    /// [`Sheet::remove_col`].
+    fn remove_cell(&mut self, index: usize) -> Option<Cell> {
+        if index >= self.cells.len() {
+            return None;
+        }
+
+        if index >= self.original_width() {
+            self.padding -= 1;
+        }
+        let cell = self.cells.remove(index);
+        self.primary = shift_primary(self.primary, index, self.cells.len());
+
+        Some(cell)
+    }
+
+    /// Appends a new cell holding `data`.
+    fn push_cell(&mut self, data: Data) {
+        self.cells.push(Cell::new(self.id_counter, data));
+        self.id_counter += 1;
+        self.padding = 0;
+    }
+
     /// Fill the row with empty cells up to a given length
     fn balance_cells(&mut self, len: usize) {
         let ln = self.cells.len();
@@ -197,87 +336,85 @@ impl Row {
             return;
         }
 
+        let padding = self.padding + (len - ln);
         for _ in 0..(len - ln) {
-            let empty = Cell::new(self.id_counter, Data::None);
-            self.cells.push(empty);
-            self.id_counter += 1;
+            self.push_cell(Data::None);
         }
+        self.padding = padding;
     }
 
-    ///  Returns a Line whose points have x values from the vector provided
-    ///  and y values as the data in each corresponding cell in this row.
+    ///  Returns a Line whose points pair each x value in `columns` with the
+    ///  data in the cell of its column in this row.
     ///
     ///  Intended for use in creating LineGraphs.
     ///
-    ///  Any unpaired x or y values are ignored
+    ///  Any unpaired x or y values are ignored. Empty y values are handled
+    ///  following `policy`.
     fn create_line(
         &self,
-        label: &LineLabelStrategy,
-        x_values: &[Data],
-        exclude: &HashSet<usize>,
-        idx: usize,
-    ) -> Line {
-        let points: Vec<Point> = match label {
-            LineLabelStrategy::FromCell(idx) => {
-                let points = x_values
-                    .iter()
-                    .zip(self.cells.iter())
-                    .enumerate()
-                    .filter(|(id, _)| id != idx && !exclude.contains(id))
-                    .map(|(_, (x, cell))| Point::new(x.clone(), cell.data.clone()))
-                    .collect();
-
-                points
+        columns: &[(usize, Data)],
+        headers: &[ColumnHeader],
+        policy: MissingPolicy,
+    ) -> Result<Line> {
+        let mut points = Vec::with_capacity(columns.len());
+        for (col, x) in columns {
+            let Some(cell) = self.cells.get(*col) else {
+                continue;
+            };
+            if let Some(y) = self.charted(cell, headers, *col, policy)? {
+                points.push(Point::new(x.clone(), y));
             }
+        }
 
-            _ => x_values
-                .iter()
-                .zip(self.cells.iter())
-                .enumerate()
-                .filter(|(id, _)| !exclude.contains(id))
-                .map(|(_, (x, cell))| Point::new(x.clone(), cell.data.clone()))
-                .collect(),
-        };
+        Ok(Line::from_points(points))
+    }
 
-        match label {
-            LineLabelStrategy::None => Line::from_points(points),
-            LineLabelStrategy::Provided(labels) => match labels.get(idx).cloned() {
-                Some(label) => Line::from_points(points).label(label),
-                None => Line::from_points(points),
-            },
-            LineLabelStrategy::FromCell(idx) => {
-                let label = self.cells.get(*idx).map(|cell| cell.data.to_string());
-                match label {
-                    Some(label) => Line::from_points(points).label(label),
-                    None => Line::from_points(points),
-                }
-            }
-        }
+    /// Returns the value charted for `cell`, of the column at `col`, following
+    /// `policy`, or [`None`] if the cell is left out.
+    fn charted(
+        &self,
+        cell: &Cell,
+        headers: &[ColumnHeader],
+        col: usize,
+        policy: MissingPolicy,
+    ) -> Result<Option<Data>> {
+        charted_value(&cell.data, headers, self.id, col, policy)
     }
 
+    /// Returns the positive and negative stacked bars of the row, or [`None`]
+    /// if its x value is left out following `policy`.
     fn create_stacked_bar_chart(
         &self,
         x_col: usize,
         cols: &[usize],
         labels: &[String],
-    ) -> Result<[(StackedBar, Data); 2]> {
+        headers: &[ColumnHeader],
+        policy: MissingPolicy,
+    ) -> Result<Option<[(StackedBar, Data); 2]>> {
         let x = self
             .cells
             .get(x_col)
-            .cloned()
-            .expect("Row create stacked bar: Validations failed.")
-            .data;
+            .expect("Row create stacked bar: Validations failed.");
+        let Some(x) = self.charted(x, headers, x_col, policy)? else {
+            return Ok(None);
+        };
 
         let mut pos = Vec::with_capacity(cols.len());
         let mut neg = Vec::with_capacity(cols.len());
 
         for (col, label) in cols.iter().zip(labels) {
-            let data = self
+            let cell = self
                 .cells
                 .get(*col)
-                .cloned()
-                .expect("Row create stacked bar: Validations failed")
-                .data;
+                .expect("Row create stacked bar: Validations failed");
+            let Some(data) = self.charted(cell, headers, *col, policy)? else {
+                continue;
+            };
+
+            // Empty cells, including NaN read as empty, add nothing to a bar
+            if data == Data::None {
+                continue;
+            }
 
             if data.is_negative() {
                 neg.push((label.clone(), data));
@@ -312,86 +449,249 @@ impl Row {
                 _ => Data::None,
             });
 
-        let pos_fractions = pos
-            .into_iter()
-            .map(|(label, data)| {
-                let fraction = match (&pos_total, data) {
-                    (Data::Integer(t), Data::Integer(i)) => {
-                        if *t == 0 {
-                            0.0
-                        } else {
-                            (i as f64) / (*t as f64)
-                        }
-                    }
-                    (Data::Number(t), Data::Number(n)) => {
-                        if *t == 0 {
-                            0.0
-                        } else {
-                            (n as f64) / (*t as f64)
-                        }
-                    }
-                    (Data::Float(t), Data::Float(f)) => {
-                        if *t == 0.0 {
-                            0.0
-                        } else {
-                            (f as f64) / (*t as f64)
-                        }
-                    }
-                    _ => panic!("Row create stacked bar: So many validations failed"),
-                };
-                (label, fraction)
-            })
-            .collect::<HashMap<String, f64>>();
+        // A total which is NaN or infinite has no meaningful fractions, so the
+        // bar is left without a total instead.
+        let finite = |total: Data| match total {
+            Data::Float(fl) if !fl.is_finite() => Data::None,
+            total => total,
+        };
+        let pos_total = finite(pos_total);
+        let neg_total = finite(neg_total);
 
-        let neg_fractions = neg
-            .into_iter()
-            .map(|(label, data)| {
-                let fraction = match (&neg_total, data) {
-                    (Data::Integer(t), Data::Integer(i)) => {
-                        if *t == 0 {
-                            0.0
-                        } else {
-                            (i as f64) / (*t as f64)
-                        }
-                    }
-                    (Data::Number(t), Data::Number(n)) => {
-                        if *t == 0 {
-                            0.0
-                        } else {
-                            (n as f64) / (*t as f64)
-                        }
-                    }
-                    (Data::Float(t), Data::Float(f)) => {
-                        if *t == 0.0 {
-                            0.0
-                        } else {
-                            (f as f64) / (*t as f64)
-                        }
-                    }
-                    _ => {
-                        panic!("Row create stacked bar: So many validations failed")
-                    }
-                };
-                (label, fraction)
-            })
-            .collect::<HashMap<String, f64>>();
+        // Bars without a total have no sections
+        let pos_sections = match pos_total {
+            Data::None => HashMap::new(),
+            _ => sections(pos),
+        };
+        let neg_sections = match neg_total {
+            Data::None => HashMap::new(),
+            _ => sections(neg),
+        };
 
         let pos_pnt = Point::new(x.clone(), pos_total.clone());
-        let pos_bar = StackedBar::new(pos_pnt, pos_fractions, false);
+        let pos_bar = StackedBar::new(pos_pnt, pos_sections, false);
 
         let neg_pnt = Point::new(x, neg_total.clone());
-        let neg_bar = StackedBar::new(neg_pnt, neg_fractions, true);
+        let neg_bar = StackedBar::new(neg_pnt, neg_sections, true);
+
+        Ok(Some([(pos_bar, pos_total), (neg_bar, neg_total)]))
+    }
+}
+
+/// Returns the sections of a stacked bar from its labelled values. The values
+/// of sections sharing a label are summed.
+fn sections(values: Vec<(String, Data)>) -> HashMap<String, Data> {
+    let mut sections: HashMap<String, Data> = HashMap::with_capacity(values.len());
+
+    for (label, value) in values {
+        match sections.remove(&label) {
+            Some(prev) => {
+                let sum = prev.checked_add(&value).unwrap_or_else(|| {
+                    Data::Float(
+                        (prev.as_f64().unwrap_or_default() + value.as_f64().unwrap_or_default())
+                            as f32,
+                    )
+                });
+                sections.insert(label, sum)
+            }
+            None => sections.insert(label, value),
+        };
+    }
+
+    sections
+}
+
+#[cfg(test)]
+thread_local! {
+    /// The number of times the column kinds of a sheet were inferred on this
+    /// thread, so tests can tell when inference is skipped.
+    pub(super) static INFERENCES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
 
-        Ok([(pos_bar, pos_total), (neg_bar, neg_total)])
+/// Returns the smallest and largest of `values`, ignoring empty cells and
+/// non-finite floats. See [`Sheet::col_bounds`].
+fn bounds<'a>(values: impl Iterator<Item = &'a Data>) -> Option<(Data, Data)> {
+    values
+        .filter(|data| match data {
+            Data::None => false,
+            Data::Float(fl) => fl.is_finite(),
+            _ => true,
+        })
+        .fold(None, |acc, data| match acc {
+            None => Some((data, data)),
+            Some((min, max)) => Some((min.min(data), max.max(data))),
+        })
+        .map(|(min, max)| (min.clone(), max.clone()))
+}
+
+/// Returns the data `row` is sorted by when sorting on the column at `col`.
+/// A missing cell sorts as [`Data::None`], so rows shortened after
+/// validation cannot panic a sort.
+fn sort_key(row: &Row, col: usize) -> &Data {
+    row.cells.get(col).map_or(&Data::None, Cell::get_data)
+}
+
+/// Returns the primary key after removing the column at `removed`, leaving
+/// `width` columns. Keys at or after `removed` shift to the left, unless they
+/// are already the first column, and no columns leave no key.
+fn shift_primary(primary: Option<usize>, removed: usize, width: usize) -> Option<usize> {
+    let primary = primary?;
+
+    if width == 0 {
+        None
+    } else if removed <= primary && primary != 0 {
+        Some(primary - 1)
+    } else {
+        Some(primary)
+    }
+}
+
+/// Returns the difference between `before` and `after` as chosen by `mode`,
+/// as data of `kind`. Empty or non-numeric values, integer overflow and
+/// percent changes from zero have no difference.
+fn difference(before: &Data, after: &Data, mode: DiffMode, kind: ScaleKind) -> Option<Data> {
+    match (mode, kind, before, after) {
+        (DiffMode::Delta, ScaleKind::Integer, Data::Integer(before), Data::Integer(after)) => {
+            after.checked_sub(*before).map(Data::Integer)
+        }
+        (DiffMode::Delta, ScaleKind::Number, Data::Number(before), Data::Number(after)) => {
+            after.checked_sub(*before).map(Data::Number)
+        }
+        (DiffMode::Delta, ..) => Some(Data::Float((after.as_f64()? - before.as_f64()?) as f32)),
+        (DiffMode::PercentChange, ..) => {
+            let before = before.as_f64().filter(|before| *before != 0.0)?;
+            let change = (after.as_f64()? - before) / before.abs() * 100.0;
+
+            Some(Data::Float(change as f32))
+        }
+    }
+}
+
+/// Returns the type of a column of `acc` after a cell of `kind`. Empty cells
+/// are ignored, while cells which disagree make the column
+/// [`ColumnType::Mixed`].
+fn merge_kinds(acc: ColumnType, kind: ColumnType) -> ColumnType {
+    match (acc, kind) {
+        (acc, ColumnType::None) => acc,
+        (ColumnType::None, kind) => kind,
+        (acc, kind) if acc == kind => acc,
+        _ => ColumnType::Mixed,
     }
 }
 
+/// The text a cell was read from, kept alongside the value it was parsed
+/// into. See [`Config::preserve_original`].
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Original {
+    text: String,
+    data: Data,
+}
+
+/// The positions of the rows of a [`Sheet`] by id, kept only while the id of
+/// some row differs from its position.
+///
+/// Sheets rebuild the index whenever their rows are reordered or
+/// renumbered, and clear it when handing out mutable rows, falling back to a
+/// linear search until the next rebuild.
+#[derive(Debug, Clone, Default)]
+struct RowIndex(Option<HashMap<usize, usize>>);
+
+impl RowIndex {
+    /// Returns the position of the row with the id `id` within `rows`.
+    fn get(&self, rows: &[Row], id: usize) -> Option<usize> {
+        match &self.0 {
+            Some(index) => index.get(&id).copied(),
+            None => rows.iter().position(|row| row.id == id),
+        }
+    }
+
+    /// Indexes `rows`, unless the id of every row matches its position.
+    fn rebuild(&mut self, rows: &[Row]) {
+        if rows.iter().enumerate().all(|(pos, row)| row.id == pos) {
+            self.0 = None;
+            return;
+        }
+
+        let mut index = HashMap::with_capacity(rows.len());
+        for (pos, row) in rows.iter().enumerate() {
+            index.entry(row.id).or_insert(pos);
+        }
+        self.0 = Some(index);
+    }
+
+    /// Indexes the last row of `rows`, just pushed.
+    fn push(&mut self, rows: &[Row]) {
+        let Some(row) = rows.last() else {
+            return;
+        };
+        let pos = rows.len() - 1;
+
+        match &mut self.0 {
+            Some(index) => {
+                index.entry(row.id).or_insert(pos);
+            }
+            None if row.id != pos => self.rebuild(rows),
+            None => {}
+        }
+    }
+
+    fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+fn _assert_sync<T: Send + Sync>() {}
+
+fn _assert_sheet_sync() {
+    _assert_sync::<Sheet>();
+}
+
+/// Sheets are equal when their rows, including the ids of rows and cells,
+/// headers, primary key and original text are equal. The counter used to
+/// assign new row ids is ignored. See [`Sheet::logical_eq`] to compare
+/// contents alone.
+#[derive(Debug, Clone)]
 pub struct Sheet {
     rows: Vec<Row>,
     headers: Vec<ColumnHeader>,
     id_counter: usize,
-    primary_key: usize,
+    /// The primary column. Is None if the sheet has no columns or its primary
+    /// column was cleared.
+    primary_key: Option<usize>,
+    /// The original text of cells written differently from how they were
+    /// read, keyed by the ids of their row and cell.
+    originals: HashMap<(usize, usize), Original>,
+    /// The meta of each column, if collected and its column is unchanged
+    /// since. See [`Config::collect_meta`].
+    meta: Vec<Option<ColumnMeta>>,
+    /// The position of each row by id, while the id of some row differs
+    /// from its position. See [`Sheet::get_row_by_id`].
+    row_index: RowIndex,
+    /// See [`Sheet::add_row_constraint`]
+    constraints: RowConstraints,
+    /// See [`Sheet::load_report`]
+    report: LoadReport,
+}
+
+/// Rows holding the same cells are equal whether or not any were
+/// synthesized. See [`Row::original_width`].
+impl PartialEq for Row {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.cells == other.cells
+            && self.primary == other.primary
+            && self.id_counter == other.id_counter
+    }
+}
+
+impl PartialEq for Sheet {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+            && self.headers == other.headers
+            && self.primary_key == other.primary_key
+            && self.originals == other.originals
+    }
 }
 
 #[allow(dead_code)]
@@ -421,6 +721,7 @@ impl Sheet {
     /// Creates a new [`Sheet`] with the provided `path`.
     ///
     /// The default [`Config`] is used.
+    #[cfg(feature = "csv")]
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let builder = Config::new(path);
 
@@ -428,48 +729,206 @@ impl Sheet {
     }
 
     /// Create a new [`Sheet`] given a [`Config`].
+    #[cfg(feature = "csv")]
     pub fn with_config<P: AsRef<Path>>(config: Config<P>) -> Result<Self> {
+        Self::read(config).map(|(sheet, ..)| sheet)
+    }
+
+    /// Reads at most `n` records of the file at `path`, inferring the types
+    /// of its columns from them.
+    ///
+    /// The default [`Config`] is otherwise used. See
+    /// [`Sheet::preview_with_config`].
+    #[cfg(feature = "csv")]
+    pub fn preview<P: AsRef<Path>>(path: P, n: usize) -> Result<(Self, PreviewInfo)> {
+        let config = Config::new(path).types(TypesStrategy::Infer);
+
+        Self::preview_with_config(config, n)
+    }
+
+    /// Reads at most `n` records given a [`Config`], overriding its
+    /// [`Config::max_rows`].
+    #[cfg(feature = "csv")]
+    pub fn preview_with_config<P: AsRef<Path>>(
+        config: Config<P>,
+        n: usize,
+    ) -> Result<(Self, PreviewInfo)> {
+        let file_size = std::fs::metadata(config.path.as_ref())
+            .map_err(|err| Error::from(CsvError::from(err)).in_file(config.path.as_ref()))?
+            .len();
+        let (sheet, truncated) = Self::read(config.max_rows(Some(n)))?;
+        let replaced = sheet.report.replaced;
+
+        Ok((
+            sheet,
+            PreviewInfo {
+                truncated,
+                file_size,
+                replaced,
+            },
+        ))
+    }
+
+    /// Reads a [`Sheet`] from CSV data in `reader`, such as an in-memory
+    /// buffer, given a [`Config`]. The path of `config` is only used to
+    /// describe the source.
+    #[cfg(feature = "csv")]
+    pub fn from_reader<R: io::Read, P: AsRef<Path>>(reader: R, config: Config<P>) -> Result<Self> {
+        let rdr = config.csv_reader(reader);
+
+        Self::read_csv(rdr, config, None).map(|(sheet, ..)| sheet)
+    }
+
+    /// Reads a [`Sheet`] given a [`Config`], also returning whether records
+    /// were left unread because of [`Config::max_rows`].
+    #[cfg(feature = "csv")]
+    fn read<P: AsRef<Path>>(config: Config<P>) -> Result<(Self, bool)> {
+        let path = config.path.as_ref().to_owned();
+        let config = config
+            .detected()
+            .map_err(|err| Error::from(CsvError::from(err)).in_file(&path))?;
+        let file = std::fs::File::open(config.path.as_ref())
+            .map_err(|err| Error::from(CsvError::from(err)).in_file(config.path.as_ref()))?;
+        let rdr = config.csv_reader(file);
+
+        let cached_kinds = match (&config.schema_cache, &config.type_strategy) {
+            (Some(cache), TypesStrategy::Infer | TypesStrategy::InferWithUnion) => {
+                cache.kinds_for(config.path.as_ref())
+            }
+            _ => None,
+        };
+
+        Self::read_csv(rdr, config, cached_kinds)
+    }
+
+    /// Reads a [`Sheet`] from `rdr`, adding the path of `config` to any
+    /// error. See [`Error::WithContext`].
+    ///
+    /// The column types are taken from `cached_kinds`, if given, instead of
+    /// being inferred. See [`Config::with_schema_cache`].
+    #[cfg(feature = "csv")]
+    fn read_csv<R: io::Read, P: AsRef<Path>>(
+        rdr: csv::Reader<Decoder<R>>,
+        config: Config<P>,
+        cached_kinds: Option<Vec<ColumnType>>,
+    ) -> Result<(Self, bool)> {
+        let path = config.path.as_ref().to_path_buf();
+
+        Self::parse_csv(rdr, config, cached_kinds).map_err(|err| err.in_file(&path))
+    }
+
+    #[cfg(feature = "csv")]
+    fn parse_csv<R: io::Read, P: AsRef<Path>>(
+        mut rdr: csv::Reader<Decoder<R>>,
+        config: Config<P>,
+        cached_kinds: Option<Vec<ColumnType>>,
+    ) -> Result<(Self, bool)> {
+        let span = trace_span!(
+            "sheet_load",
+            path = %config.path.as_ref().display(),
+            rows = tracing::field::Empty,
+            cols = tracing::field::Empty
+        );
+
         let Config {
-            path,
-            flexible,
             trim,
-            delimiter,
+            flexible,
             label_strategy,
             type_strategy,
             primary,
+            primary_set,
+            null_string,
+            max_rows,
+            drop_empty_cols,
+            decimal_separator,
+            preserve_original,
+            collect_meta,
+            nan_policy,
+            type_mismatch,
+            select_columns,
+            expected_schema,
             ..
         } = config;
 
-        let mut counter: usize = 0;
-        let mut longest_row = 0;
-
-        let has_headers = match label_strategy {
-            HeaderStrategy::ReadLabels => true,
-            HeaderStrategy::NoLabels => false,
-            HeaderStrategy::Provided(_) => false,
-        };
-
-        let trim = {
-            if trim {
-                Trim::All
-            } else {
-                Trim::None
+        let selected = match &select_columns {
+            Some(select) => {
+                let headers = match label_strategy {
+                    HeaderStrategy::ReadLabels => Some(rdr.headers().map_err(located)?.clone()),
+                    _ => None,
+                };
+                Some(select.resolve(headers.as_ref(), trim)?)
             }
+            None => None,
+        };
+        let primary = match (&selected, primary) {
+            (Some(selected), Some(primary)) => Some(
+                SelectColumns::primary(selected, primary, primary_set).ok_or_else(|| {
+                    Error::InvalidPrimaryKey(format!(
+                        "The primary column {} is not selected",
+                        primary
+                    ))
+                })?,
+            ),
+            _ => primary,
         };
 
-        let mut rdr = csv::ReaderBuilder::new()
-            .has_headers(has_headers)
-            .trim(trim)
-            .flexible(flexible)
-            .delimiter(delimiter)
-            .from_path(path)?;
+        let mut counter: usize = 0;
+        let mut longest_row = 0;
+        let mut widest_record = 0;
+        let mut originals = HashMap::new();
+        let mut lines = Vec::new();
+
+        let mut truncated = false;
 
         let mut rows: Vec<Row> = {
             let mut rows = vec![];
 
             for record in rdr.records() {
-                let record = record?;
-                let row = Row::new(record, counter, primary);
+                if max_rows == Some(counter) {
+                    truncated = true;
+                    break;
+                }
+
+                let mut record = record.map_err(located)?;
+                lines.push(record.position().map(|pos| pos.line()));
+                if let Some(selected) = &selected {
+                    widest_record = usize::max(widest_record, record.len());
+                    record = select_fields(&record, selected);
+                }
+
+                let raw = if preserve_original {
+                    let raw = record.clone();
+                    if trim {
+                        record.trim();
+                    }
+                    Some(raw)
+                } else {
+                    None
+                };
+
+                let mut row =
+                    Row::parse(&record, counter, primary, &null_string, decimal_separator);
+                if nan_policy == NanPolicy::Null {
+                    row.cells
+                        .iter_mut()
+                        .filter(|cell| matches!(cell.data, Data::Float(fl) if fl.is_nan()))
+                        .for_each(|cell| cell.data = Data::None);
+                }
+
+                for (cell, text) in raw.iter().flat_map(|raw| row.cells.iter().zip(raw.iter())) {
+                    let float = matches!(cell.data, Data::Float(_));
+                    if cell.data != Data::None
+                        && (decimal_separator == '.' || !float)
+                        && write_field(&cell.data, &WriteOptions::default()) != text
+                    {
+                        let original = Original {
+                            text: text.to_owned(),
+                            data: cell.data.clone(),
+                        };
+                        originals.insert((row.id, cell.id), original);
+                    }
+                }
+
                 if row.id_counter > longest_row {
                     longest_row = row.id_counter;
                 }
@@ -479,29 +938,70 @@ impl Sheet {
             rows
         };
 
+        // The header record counts towards the width, so labels beyond the
+        // widest data record are kept.
+        let header_record = match &label_strategy {
+            HeaderStrategy::ReadLabels => {
+                let mut headers = rdr.headers().map_err(located)?.clone();
+                if let Some(selected) = &selected {
+                    widest_record = usize::max(widest_record, headers.len());
+                    headers = select_fields(&headers, selected);
+                }
+
+                Some(
+                    headers
+                        .iter()
+                        .map(|curr| if trim { curr.trim() } else { curr }.to_string())
+                        .collect::<Vec<String>>(),
+                )
+            }
+            _ => None,
+        };
+
+        if let Some(selected) = &selected {
+            SelectColumns::check_width(selected, widest_record)?;
+        }
+
+        let mut report = LoadReport::default();
+        if let Some(header_record) = &header_record {
+            if header_record.len() != longest_row && counter != 0 {
+                report.header_width_mismatch = Some((header_record.len(), longest_row));
+                trace_debug!(
+                    headers = header_record.len(),
+                    width = longest_row,
+                    "header and data widths differ"
+                );
+            }
+
+            longest_row = usize::max(longest_row, header_record.len());
+        }
+
         if flexible {
             rows.iter_mut()
                 .for_each(|row| row.balance_cells(longest_row));
         }
 
-        let types = match &type_strategy {
-            TypesStrategy::Provided(ct) => Sheet::balance_vector(ct.to_owned(), longest_row),
-            TypesStrategy::Infer => Sheet::balance_vector(Vec::<ColumnType>::new(), longest_row),
-            TypesStrategy::None => Sheet::balance_vector(Vec::<ColumnType>::new(), longest_row),
+        // Cached types are only used for a file of the same width
+        let cached_kinds = cached_kinds.filter(|kinds| kinds.len() == longest_row);
+        if cached_kinds.is_some() {
+            trace_debug!("reusing cached column kinds");
+        }
+
+        let types = match (&type_strategy, cached_kinds.as_ref()) {
+            (TypesStrategy::Provided(ct), _) => Sheet::balance_vector(ct.to_owned(), longest_row),
+            (_, Some(kinds)) => kinds.clone(),
+            (TypesStrategy::Infer | TypesStrategy::InferWithUnion | TypesStrategy::None, None) => {
+                Sheet::balance_vector(Vec::<ColumnType>::new(), longest_row)
+            }
         };
 
         let labels = match &label_strategy {
             HeaderStrategy::Provided(ch) => Sheet::balance_vector(ch.to_owned(), longest_row),
             HeaderStrategy::NoLabels => Sheet::balance_vector(Vec::<String>::new(), longest_row),
-            HeaderStrategy::ReadLabels => {
-                let labels: Vec<String> = rdr
-                    .headers()?
-                    .clone()
-                    .into_iter()
-                    .map(|curr| curr.to_string())
-                    .collect();
-                Sheet::balance_vector(labels, longest_row)
-            }
+            HeaderStrategy::ReadLabels => Sheet::balance_vector(
+                header_record.expect("Header record should have been read"),
+                longest_row,
+            ),
         };
 
         let headers: Vec<ColumnHeader> = labels
@@ -512,76 +1012,1540 @@ impl Sheet {
 
         let mut sh = Sheet {
             rows,
+            primary_key: primary.filter(|_| !headers.is_empty()),
             headers,
             id_counter: counter,
-            primary_key: primary,
+            originals,
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: RowConstraints::default(),
+            report,
         };
 
-        if type_strategy == TypesStrategy::Infer {
+        if matches!(
+            type_strategy,
+            TypesStrategy::Infer | TypesStrategy::InferWithUnion
+        ) && cached_kinds.is_none()
+        {
             Sheet::infer_col_kinds(&mut sh, longest_row);
         }
 
-        sh.validate()?;
+        if matches!(type_strategy, TypesStrategy::Provided(_)) {
+            sh.resolve_mismatches(type_mismatch);
+        }
 
-        Ok(sh)
-    }
+        if drop_empty_cols {
+            sh.drop_null_cols(1.0);
+            sh.renumber();
+        }
 
-    /// Returns the width of the [`Sheet`].
-    pub fn width(&self) -> usize {
-        self.rows.first().map(|row| row.width()).unwrap_or(0)
-    }
+        let mut validated = sh.validate();
+        if validated.is_err() && cached_kinds.is_some() {
+            trace_debug!("cached column kinds do not match, inferring them");
+            let width = sh.width();
+            Sheet::infer_col_kinds(&mut sh, width);
+            validated = sh.validate();
+        }
 
-    /// Returns the height of the [`Sheet`].
-    ///
-    /// A [`Sheet`]'s height is equivalent to the number of rows present within
-    /// it
-    pub fn height(&self) -> usize {
-        self.rows.len()
-    }
+        if let Err(err) = validated {
+            let line = sh
+                .rows
+                .iter()
+                .zip(&lines)
+                .find(|(row, _)| {
+                    row.is_primary_key_valid()
+                        .and_then(|_| row.validate_all_cols(&sh.headers))
+                        .is_err()
+                })
+                .and_then(|(_, line)| *line);
 
-    pub fn get_row_by_index(&self, index: usize) -> Option<&Row> {
-        self.rows.get(index)
-    }
+            return Err(err.at_line(line));
+        }
 
-    pub fn get_row_by_id(&self, id: usize) -> Option<&Row> {
-        self.rows.iter().find(|row| row.id == id)
-    }
+        if let Some(expected) = &expected_schema {
+            let diff = expected.diff(&sh.schema());
+            if !diff.is_compatible() {
+                return Err(Error::SchemaMismatch(Box::new(diff)));
+            }
+        }
 
-    pub fn is_empty(&self) -> bool {
-        self.rows.iter().all(|row| row.is_empty())
-    }
+        if collect_meta {
+            sh.meta = (0..sh.width())
+                .map(|col| Some(sh.compute_meta(col)))
+                .collect();
+        }
 
-    /// Could be expensive
-    pub fn validate(&self) -> Result<()> {
-        // Validating could be expensive
-        Self::is_primary_valid(self)?;
-        Self::validate_all_cols(self)?;
+        span.record("rows", sh.height());
+        span.record("cols", sh.width());
+        sh.report.replaced = rdr.get_ref().replaced();
+        trace_debug!(truncated, replaced = sh.report.replaced, "loaded sheet");
 
-        Ok(())
+        Ok((sh, truncated))
     }
 
-    /// Checks if the type for each column cell is as expected
-    fn validate_all_cols(sh: &Sheet) -> Result<()> {
-        let hrs = &sh.headers;
+    /// Replaces or casts the cells which do not match the type of their
+    /// column, following `policy`. See [`Config::type_mismatch`].
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    fn resolve_mismatches(&mut self, policy: TypeMismatchPolicy) {
+        if policy == TypeMismatchPolicy::Fail {
+            return;
+        }
 
-        sh.iter_rows()
+        let headers = &self.headers;
+        for row in self.rows.iter_mut() {
+            for (col, cell) in row.cells.iter_mut().enumerate() {
+                let Some(header) = headers.get(col) else {
+                    continue;
+                };
+                if header.crosscheck_type(&cell.data) {
+                    continue;
+                }
+
+                let coerced = match policy {
+                    TypeMismatchPolicy::Coerce => cell.data.coerce(&header.kind),
+                    _ => None,
+                };
+                trace_debug!(
+                    row = row.id,
+                    col,
+                    coerced = coerced.is_some(),
+                    "cell does not match its column type"
+                );
+                cell.data = coerced.unwrap_or(Data::None);
+            }
+        }
+    }
+
+    /// Writes the [`Sheet`] as CSV to `writer` using the default
+    /// [`WriteOptions`].
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: io::Write>(&self, writer: W) -> Result<()> {
+        self.write_csv_with(writer, &WriteOptions::default())
+    }
+
+    /// Writes the [`Sheet`] as CSV to `writer` using `options`.
+    ///
+    /// Cells with original text are written as they were read, except for
+    /// floats written with a decimal separator other than `.`. See
+    /// [`Config::preserve_original`].
+    #[cfg(feature = "csv")]
+    pub fn write_csv_with<W: io::Write>(&self, writer: W, options: &WriteOptions) -> Result<()> {
+        let mut wtr = options.csv_writer(writer);
+
+        if options.headers {
+            wtr.write_record(self.headers.iter().map(|header| header.label.as_str()))?;
+        }
+
+        for row in self.rows.iter() {
+            let width = if options.trim_padding {
+                row.original_width()
+            } else {
+                row.cells.len()
+            };
+
+            wtr.write_record(row.cells[..width].iter().map(
+                |cell| match self.original(row, cell) {
+                    Some(text) if !matches!(cell.data, Data::Float(_)) => text.to_owned(),
+                    Some(text) if options.decimal_separator == '.' => text.to_owned(),
+                    _ => write_field(&cell.data, options),
+                },
+            ))?;
+        }
+
+        wtr.flush().map_err(CsvError::from)?;
+
+        Ok(())
+    }
+
+    /// Creates a new [`Sheet`] from in-memory `rows` and `headers`.
+    ///
+    /// Rows shorter than the widest row or `headers` are padded with
+    /// [`Data::None`]. Missing headers are filled in with a label-less
+    /// [`ColumnType::None`] header. Returns an error if any cell does not match
+    /// the type of its column or if `primary` is invalid. A sheet without
+    /// columns has no primary key.
+    pub fn from_rows(
+        rows: Vec<Vec<Data>>,
+        headers: Vec<ColumnHeader>,
+        primary: impl Into<Option<usize>>,
+    ) -> Result<Self> {
+        let width = rows
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0)
+            .max(headers.len());
+        let primary = primary.into().filter(|_| width > 0);
+
+        let rows: Vec<Row> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(id, cells)| {
+                let mut row = Row::from_data(cells, id, primary);
+                row.balance_cells(width);
+                row
+            })
+            .collect();
+
+        let sh = Sheet {
+            id_counter: rows.len(),
+            rows,
+            headers: Self::balance_vector(headers, width),
+            primary_key: primary,
+            originals: HashMap::new(),
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: RowConstraints::default(),
+            report: LoadReport::default(),
+        };
+
+        sh.validate()?;
+
+        Ok(sh)
+    }
+
+    /// Returns the [`Schema`] of the [`Sheet`] alongside the [`Fingerprint`]
+    /// of the file at `path`, which it should have been read from. See
+    /// [`Config::with_schema_cache`].
+    #[cfg(feature = "csv")]
+    pub fn schema_fingerprint(&self, path: impl AsRef<Path>) -> Result<SchemaCache> {
+        let path = path.as_ref();
+        let fingerprint =
+            Fingerprint::of(path).map_err(|err| Error::from(CsvError::from(err)).in_file(path))?;
+
+        Ok(SchemaCache::new(self.schema(), fingerprint))
+    }
+
+    /// Returns how the [`Sheet`] was read from its file. See [`LoadReport`].
+    pub fn load_report(&self) -> LoadReport {
+        self.report
+    }
+
+    /// Returns the label, type and nullability of each column of the
+    /// [`Sheet`]. A column is nullable if any of its cells is empty.
+    pub fn schema(&self) -> Schema {
+        let columns = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(col, header)| {
+                let nullable = self
+                    .rows
+                    .iter()
+                    .any(|row| row.cells.get(col).map_or(true, Cell::is_empty));
+                SchemaColumn::new(header.label.clone(), header.kind, nullable)
+            })
+            .collect();
+
+        Schema::new(columns)
+    }
+
+    /// Returns the positions, as `(row, column)`, of the cells synthesized to
+    /// pad short rows to the width of the [`Sheet`]. See
+    /// [`Row::original_width`].
+    pub fn synthesized_cells(&self) -> Vec<(usize, usize)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, row)| {
+                (row.original_width()..row.cells.len()).map(move |col| (idx, col))
+            })
+            .collect()
+    }
+
+    /// Returns the width of the [`Sheet`].
+    ///
+    /// This is the number of headers, so a [`Sheet`] read from a file with
+    /// only a header record has a width but no height.
+    pub fn width(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Returns the text the cell at `row`, `col` was read from, if it is
+    /// written differently from its value. Cells only keep their text when
+    /// read with [`Config::preserve_original`], and lose it once their value
+    /// changes.
+    pub fn original_text(&self, row: usize, col: usize) -> Option<&str> {
+        let row = self.rows.get(row)?;
+        let cell = row.cells.get(col)?;
+
+        self.original(row, cell)
+    }
+
+    fn original(&self, row: &Row, cell: &Cell) -> Option<&str> {
+        self.originals
+            .get(&(row.id, cell.id))
+            .filter(|original| original.data == cell.data)
+            .map(|original| original.text.as_str())
+    }
+
+    /// Returns a copy of the sheet holding only its first `n` rows, or every
+    /// row if it has fewer. Headers, column types and the primary key are
+    /// kept.
+    pub fn head(&self, n: usize) -> Sheet {
+        self.with_rows(self.rows.iter().take(n))
+    }
+
+    /// Returns a copy of the sheet holding only its last `n` rows, or every
+    /// row if it has fewer. See [`Sheet::head`].
+    pub fn tail(&self, n: usize) -> Sheet {
+        self.with_rows(self.rows.iter().skip(self.height().saturating_sub(n)))
+    }
+
+    fn with_rows<'a>(&self, rows: impl Iterator<Item = &'a Row>) -> Sheet {
+        let rows: Vec<Row> = rows.cloned().collect();
+        let ids: HashSet<usize> = rows.iter().map(|row| row.id).collect();
+
+        let mut sheet = Sheet {
+            rows,
+            headers: self.headers.clone(),
+            id_counter: self.id_counter,
+            primary_key: self.primary_key,
+            originals: self
+                .originals
+                .iter()
+                .filter(|((row, _), _)| ids.contains(row))
+                .map(|(key, original)| (*key, original.clone()))
+                .collect(),
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: self.constraints.clone(),
+            report: LoadReport::default(),
+        };
+        sheet.renumber();
+
+        sheet
+    }
+
+    /// Sets the id of every row and cell to its position, as when the
+    /// [`Sheet`] was constructed. See [`Row::id`].
+    pub fn renumber(&mut self) {
+        let mut originals = HashMap::with_capacity(self.originals.len());
+
+        for (idx, row) in self.rows.iter_mut().enumerate() {
+            for (col, cell) in row.cells.iter_mut().enumerate() {
+                if let Some(original) = self.originals.remove(&(row.id, cell.id)) {
+                    originals.insert((idx, col), original);
+                }
+                cell.id = col;
+            }
+            row.id = idx;
+            row.id_counter = row.cells.len();
+        }
+
+        self.id_counter = self.rows.len();
+        self.originals = originals;
+        self.row_index.rebuild(&self.rows);
+    }
+
+    /// Returns the height of the [`Sheet`].
+    ///
+    /// A [`Sheet`]'s height is equivalent to the number of rows present within
+    /// it
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get_row_by_index(&self, index: usize) -> Option<&Row> {
+        self.rows.get(index)
+    }
+
+    /// Returns the row with the id `id`, if any. See [`Row::id`].
+    ///
+    /// A row whose id matches its position is found directly, and other rows
+    /// through an index by id which [`Sheet::sort_rows`] and
+    /// [`Sheet::renumber`] rebuild eagerly. Lookups never build the index
+    /// themselves: after [`Sheet::iter_rows_mut`] clears it, they fall back
+    /// to a linear search until the next rebuild.
+    pub fn get_row_by_id(&self, id: usize) -> Option<&Row> {
+        // Ids match positions until rows are reordered
+        match self.rows.get(id) {
+            Some(row) if row.id == id => Some(row),
+            _ => self.rows.get(self.row_index.get(&self.rows, id)?),
+        }
+    }
+
+    /// Returns true if the [`Sheet`] has no rows.
+    ///
+    /// This is the same as `!self.has_rows()`, mirroring
+    /// [`ColumnSheet::is_empty`](crate::repr::col_sheet::ColumnSheet::is_empty).
+    /// A [`Sheet`] with rows of only empty cells is not considered empty, see
+    /// [`Sheet::is_blank`].
+    pub fn is_empty(&self) -> bool {
+        !self.has_rows()
+    }
+
+    /// Returns true if the [`Sheet`] has at least one row.
+    pub fn has_rows(&self) -> bool {
+        !self.rows.is_empty()
+    }
+
+    /// Returns true if at least one cell of the [`Sheet`] is not empty.
+    pub fn has_data(&self) -> bool {
+        self.rows.iter().any(|row| !row.is_empty())
+    }
+
+    /// Returns true if every cell of the [`Sheet`] is empty. A [`Sheet`] with
+    /// no rows is also blank.
+    pub fn is_blank(&self) -> bool {
+        !self.has_data()
+    }
+
+    /// Checks that the [`Sheet`] has rows with data to convert and that every
+    /// cell matches the type of its column.
+    pub(super) fn validate_for_chart(&self) -> Result<()> {
+        self.assert_convertible()?;
+        self.validate()
+    }
+
+    /// Checks that the [`Sheet`] has rows with data to convert.
+    fn assert_convertible(&self) -> Result<()> {
+        if !self.has_rows() {
+            return Err(Error::NoRows);
+        }
+
+        if !self.has_data() {
+            return Err(Error::NoData);
+        }
+
+        Ok(())
+    }
+
+    /// Could be expensive
+    pub fn validate(&self) -> Result<()> {
+        // Validating could be expensive
+        let res = Self::is_primary_valid(self).and_then(|_| Self::validate_all_cols(self));
+
+        #[cfg(feature = "trace")]
+        if let Err(err) = &res {
+            trace_debug!(error = %err, "sheet validation failed");
+        }
+
+        res
+    }
+
+    /// Checks if the type for each column cell is as expected
+    fn validate_all_cols(sh: &Sheet) -> Result<()> {
+        let hrs = &sh.headers;
+
+        sh.iter_rows()
             .try_fold((), |_, curr| curr.validate_all_cols(hrs))
     }
 
-    fn validate_col(&self, col: usize) -> Result<()> {
-        let hdr = self.headers.get(col).ok_or(Error::InvalidColumnLength(
-            "Tried to access out of range column".to_string(),
-        ))?;
+    /// Checks if every cell in the column at `col` matches the type of the
+    /// column.
+    pub fn validate_col(&self, col: usize) -> Result<()> {
+        let hdr = self.headers.get(col).ok_or(Error::InvalidColumnLength(
+            "Tried to access out of range column".to_string(),
+        ))?;
+
+        self.iter_rows()
+            .try_fold((), |_, curr| curr.validate_col(hdr, col))
+    }
+
+    /// Checks if every cell in the column at `col` would be valid were the
+    /// column of type `kind`. The [`Sheet`] is left unchanged.
+    pub fn check_col_as(&self, col: usize, kind: ColumnType) -> Result<()> {
+        let hdr = self.headers.get(col).ok_or(Error::InvalidColumnLength(
+            "Tried to access out of range column".to_string(),
+        ))?;
+        let hdr = ColumnHeader::new(hdr.label.clone(), kind);
+
+        self.iter_rows()
+            .try_fold((), |_, curr| curr.validate_col(&hdr, col))
+    }
+
+    /// Returns the header of the column at `col`, if any.
+    pub fn header(&self, col: usize) -> Option<&ColumnHeader> {
+        self.headers.get(col)
+    }
+
+    /// Sets the unit of the values within the column at `col`. A [`None`]
+    /// clears any previous unit.
+    pub fn set_col_unit(&mut self, col: usize, unit: Option<Unit>) -> Result<()> {
+        let header = self
+            .headers
+            .get_mut(col)
+            .ok_or(Error::InvalidColumnLength("Column out of range".into()))?;
+        header.unit = unit;
+
+        Ok(())
+    }
+
+    /// Returns the unit shared by every column in `cols`, if any.
+    fn shared_unit(&self, cols: impl IntoIterator<Item = usize>) -> Option<&Unit> {
+        shared_unit(&self.headers, cols)
+    }
+
+    /// Returns the type of the column at `col`, if any.
+    pub fn column_kind(&self, col: usize) -> Option<ColumnType> {
+        self.header(col).map(|hdr| hdr.kind)
+    }
+
+    /// Returns the kinds of charts which can be created from the selected
+    /// `cols`.
+    ///
+    /// - A line graph is possible if all selected columns share a type, with
+    ///   every other column excluded.
+    /// - A bar chart is possible from exactly two columns, the first being the
+    ///   x column.
+    /// - A stacked bar chart is possible from at least two columns, the first
+    ///   being the x column and the rest the accumulated columns.
+    pub fn compatible_charts(&self, cols: &[usize]) -> Vec<ChartKind> {
+        let mut charts = Vec::new();
+
+        if self.is_blank() || cols.is_empty() {
+            return charts;
+        }
+
+        let exclude = (0..self.headers.len())
+            .filter(|col| !cols.contains(col))
+            .collect::<HashSet<usize>>();
+        if cols.iter().all(|col| *col < self.headers.len())
+            && self
+                .validate_to_line_graph(&LineLabelStrategy::None, &exclude)
+                .is_ok()
+        {
+            charts.push(ChartKind::Line);
+        }
+
+        if let [x_col, y_col] = cols {
+            if self
+                .validate_to_barchart(*x_col, *y_col, &BarChartBarLabels::None, &exclude)
+                .is_ok()
+            {
+                charts.push(ChartKind::Bar);
+            }
+        }
+
+        if let [x_col, acc @ ..] = cols {
+            if self
+                .validate_to_stacked_bar_chart(*x_col, acc, &exclude)
+                .is_ok()
+            {
+                charts.push(ChartKind::StackedBar);
+            }
+        }
+
+        charts
+    }
+
+    /// Returns the numeric values within the column at `col`, ignoring empty
+    /// cells.
+    fn numeric_values(&self, col: usize) -> Result<Vec<f64>> {
+        let kind = self.column_kind(col).ok_or(Error::InvalidColumnLength(
+            "Tried to access out of range column".to_string(),
+        ))?;
+
+        match kind {
+            ColumnType::Integer | ColumnType::Number | ColumnType::Float => {}
+            kind => {
+                return Err(Error::InvalidColumnType(format!(
+                    "Expected a numeric column, but had {} column",
+                    kind
+                )))
+            }
+        }
+
+        let values = self
+            .iter_rows()
+            .filter_map(|row| row.cells.get(col))
+            .filter_map(|cell| cell.data.as_f64())
+            .collect();
+
+        Ok(values)
+    }
+
+    /// Returns the quantiles `qs` of the numeric column at `col`. Empty cells
+    /// are ignored.
+    ///
+    /// Quantiles are interpolated linearly between the closest values, the
+    /// same way numpy does by default. They are returned in the type of the
+    /// column, unless they fall between two whole values of an
+    /// [`ColumnType::Integer`] or [`ColumnType::Number`] column, when they
+    /// are returned as [`Data::Float`]. Each quantile should be within
+    /// `0.0..=1.0`.
+    pub fn quantiles(&self, col: usize, qs: &[f64]) -> Result<Vec<Data>> {
+        let mut values = self.numeric_values(col)?;
+        let kind = self.column_kind(col);
+
+        if values.is_empty() {
+            return Err(Error::InvalidStatistic(
+                "Cannot compute quantiles of an empty column".into(),
+            ));
+        }
+
+        values.sort_by(|x, y| x.total_cmp(y));
+
+        qs.iter()
+            .map(|q| {
+                let value = quantile(&values, *q)?;
+                Ok(match kind {
+                    Some(ColumnType::Integer) if value.fract() == 0.0 => {
+                        Data::Integer(value as i32)
+                    }
+                    Some(ColumnType::Number) if value.fract() == 0.0 => {
+                        Data::Number(value as isize)
+                    }
+                    _ => Data::Float(value as f32),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the percentage, within `0.0..=100.0`, of values in the numeric
+    /// column at `col` which are less than or equal to `value`. Empty cells
+    /// are ignored.
+    pub fn percentile_rank(&self, col: usize, value: &Data) -> Result<f64> {
+        let value = value.as_f64().ok_or(Error::InvalidStatistic(format!(
+            "Cannot rank non-numeric value {}",
+            value
+        )))?;
+        let values = self.numeric_values(col)?;
+
+        if values.is_empty() {
+            return Err(Error::InvalidStatistic(
+                "Cannot rank within an empty column".into(),
+            ));
+        }
+
+        let below = values.iter().filter(|x| **x <= value).count();
+
+        Ok(below as f64 * 100.0 / values.len() as f64)
+    }
+
+    /// Returns a summary of each column of the sheet as a new [`Sheet`].
+    ///
+    /// The first column names the statistic of each row, and is followed by
+    /// a column for each column of the sheet, with the same label. The rows
+    /// are `count`, `nulls`, `distinct`, `min`, `max`, `mean` and `std`.
+    ///
+    /// `count`, `nulls` and `distinct` are always [`Data::Integer`]s.
+    /// Numeric columns are summarized by every statistic other than
+    /// `distinct` as [`ColumnType::Float`] columns, with `min`, `max`,
+    /// `mean` and `std` as [`Data::Float`]s. As the counts stay integers,
+    /// such a summary does not pass [`Sheet::validate`]. The statistics are
+    /// computed as `f64`s and are lossy, as [`Data::Float`] holds an `f32`.
+    /// The standard deviation is that of a sample, and is empty for fewer
+    /// than two values. `NaN` and infinite floats are left out of the
+    /// bounds, mean and standard deviation. Other columns, including
+    /// [`ColumnType::None`] and [`ColumnType::Mixed`] columns, are
+    /// summarized as [`ColumnType::Integer`] columns holding only `count`,
+    /// `nulls` and `distinct`. Statistics which do not apply are
+    /// [`Data::None`].
+    pub fn describe(&self) -> Sheet {
+        const STATISTICS: [&str; 7] = ["count", "nulls", "distinct", "min", "max", "mean", "std"];
+
+        let mut headers = vec![ColumnHeader::new("statistic".into(), ColumnType::Text)];
+        let mut columns = vec![STATISTICS
+            .iter()
+            .map(|stat| Data::Text(stat.to_string()))
+            .collect::<Vec<_>>()];
+
+        for (col, header) in self.headers.iter().enumerate() {
+            let cells = self
+                .rows
+                .iter()
+                .filter_map(|row| row.get_cell_by_index(col).map(Cell::get_data));
+            let meta = self.col_meta(col);
+            let nulls = match meta {
+                Some(meta) => meta.null_count(),
+                None => cells.clone().filter(|data| **data == Data::None).count(),
+            };
+            let count = self.height() - nulls;
+
+            let Ok(values) = self.numeric_values(col) else {
+                let distinct = match meta.and_then(ColumnMeta::exact_distinct) {
+                    Some(distinct) => distinct,
+                    None => cells
+                        .filter(|data| **data != Data::None)
+                        .collect::<HashSet<_>>()
+                        .len(),
+                };
+                let mut column = vec![Data::None; STATISTICS.len()];
+                column[0] = Data::Integer(count as i32);
+                column[1] = Data::Integer(nulls as i32);
+                column[2] = Data::Integer(distinct as i32);
+
+                headers.push(ColumnHeader::new(header.label.clone(), ColumnType::Integer));
+                columns.push(column);
+                continue;
+            };
+
+            let values: Vec<f64> = values.into_iter().filter(|x| x.is_finite()).collect();
+            let float = |value: f64| Data::Float(value as f32);
+            let (min, max) = match self.col_bounds(col) {
+                Ok(Some((min, max))) => (
+                    min.as_f64().map_or(Data::None, float),
+                    max.as_f64().map_or(Data::None, float),
+                ),
+                _ => (Data::None, Data::None),
+            };
+            let len = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / len;
+            let std = if values.len() > 1 {
+                let squares: f64 = values.iter().map(|x| (x - mean) * (x - mean)).sum();
+                float((squares / (len - 1.0)).sqrt())
+            } else {
+                Data::None
+            };
+            let mean = if values.is_empty() {
+                Data::None
+            } else {
+                float(mean)
+            };
+
+            headers.push(ColumnHeader::new(header.label.clone(), ColumnType::Float));
+            columns.push(vec![
+                Data::Integer(count as i32),
+                Data::Integer(nulls as i32),
+                Data::None,
+                min,
+                max,
+                mean,
+                std,
+            ]);
+        }
+
+        let rows: Vec<Row> = (0..STATISTICS.len())
+            .map(|idx| {
+                let cells = columns
+                    .iter_mut()
+                    .map(|column| std::mem::take(&mut column[idx]))
+                    .collect();
+                Row::from_data(cells, idx, 0)
+            })
+            .collect();
+
+        Sheet {
+            id_counter: rows.len(),
+            rows,
+            headers,
+            primary_key: Some(0),
+            originals: HashMap::new(),
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: RowConstraints::default(),
+            report: LoadReport::default(),
+        }
+    }
+
+    /// Removes the column at `col`, returning its header and data.
+    ///
+    /// If the column is before or is the primary column, the primary key is
+    /// shifted to the left, unless it is already the first column. Removing
+    /// the last column leaves the sheet without a primary key.
+    pub fn remove_col(&mut self, col: usize) -> Result<(ColumnHeader, Vec<Data>)> {
+        if col >= self.headers.len() {
+            return Err(Error::InvalidColumnLength(format!(
+                "Tried to remove out of bounds column {}",
+                col
+            )));
+        }
+
+        let data = self
+            .rows
+            .iter_mut()
+            .filter_map(|row| row.remove_cell(col))
+            .map(|cell| cell.data)
+            .collect();
+
+        let header = self.headers.remove(col);
+        self.primary_key = shift_primary(self.primary_key, col, self.headers.len());
+        self.constraints.remove_col(col);
+        if col < self.meta.len() {
+            self.meta.remove(col);
+        }
+
+        Ok((header, data))
+    }
+
+    /// Removes every column whose fraction of empty cells is at least
+    /// `threshold`, returning the indices of the removed columns in
+    /// ascending order.
+    ///
+    /// A `threshold` of `1.0` only removes columns where every cell is empty.
+    /// The columns of a [`Sheet`] without rows are all considered empty.
+    pub fn drop_null_cols(&mut self, threshold: f64) -> Vec<usize> {
+        let height = self.height();
+
+        let removed = (0..self.headers.len())
+            .filter(|col| {
+                if height == 0 {
+                    return true;
+                }
+
+                let nulls = match self.col_meta(*col) {
+                    Some(meta) => meta.null_count(),
+                    None => self
+                        .rows
+                        .iter()
+                        .filter(|row| {
+                            row.get_cell_by_index(*col)
+                                .map(Cell::is_empty)
+                                .unwrap_or(true)
+                        })
+                        .count(),
+                };
+
+                nulls as f64 / height as f64 >= threshold
+            })
+            .collect::<Vec<usize>>();
+
+        for col in removed.iter().rev() {
+            let _ = self.remove_col(*col);
+        }
+
+        removed
+    }
+
+    /// Replaces every value in the column at `col` for which `pred` returns
+    /// true with the result of `new`, returning the number of modified cells.
+    ///
+    /// The type of the column is never changed. Returns an error, leaving the
+    /// [`Sheet`] unchanged, if any new value does not match the type of the
+    /// column. [`Data::None`] matches every type.
+    pub fn update_where(
+        &mut self,
+        col: usize,
+        pred: impl Fn(&Data) -> bool,
+        new: impl Fn(&Data) -> Data,
+    ) -> Result<usize> {
+        let kind = self.column_kind(col).ok_or(Error::InvalidColumnLength(
+            "Tried to access out of range column".to_string(),
+        ))?;
+
+        let updates = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, row)| {
+                let cell = row.get_cell_by_index(col)?;
+                pred(&cell.data).then(|| (idx, Cell::new(cell.id, new(&cell.data))))
+            })
+            .collect::<Vec<(usize, Cell)>>();
+
+        updates
+            .iter()
+            .try_for_each(|(_, cell)| cell.validate_type(&kind))?;
+
+        let count = updates.len();
+        if count > 0 {
+            self.forget_meta(col);
+        }
+        for (idx, cell) in updates {
+            self.rows[idx].cells[col] = cell;
+        }
+
+        Ok(count)
+    }
+
+    /// Adds `constraint` to the rules checked against every row by
+    /// [`Sheet::check_constraints`]. Returns an error if it relates a column
+    /// out of range.
+    ///
+    /// Constraints refer to columns by position. Removing a column drops the
+    /// constraints relating it and shifts those relating later columns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::repr::{sheet::{RowConstraint, Sheet}, ColumnHeader, ColumnType, Data};
+    ///
+    /// let headers = ["Start", "End"].map(|label| ColumnHeader::new(label.into(), ColumnType::Integer));
+    /// let rows = vec![
+    ///     vec![Data::Integer(1), Data::Integer(4)],
+    ///     vec![Data::Integer(7), Data::Integer(2)],
+    /// ];
+    /// let mut sheet = Sheet::from_rows(rows, headers.to_vec(), 0).unwrap();
+    /// sheet.add_row_constraint(RowConstraint::ColumnLessEqual(0, 1)).unwrap();
+    ///
+    /// let violations = sheet.check_constraints();
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(violations[0].row, 1);
+    /// assert_eq!(violations[0].description, "`Start` (7) is greater than `End` (2)");
+    /// ```
+    pub fn add_row_constraint(&mut self, constraint: RowConstraint) -> Result<()> {
+        if let Some(col) = constraint
+            .columns()
+            .into_iter()
+            .find(|col| *col >= self.width())
+        {
+            return Err(Error::InvalidColumnLength(format!(
+                "Row constraint on out of range column {}",
+                col
+            )));
+        }
+
+        self.constraints.push(constraint);
+        Ok(())
+    }
+
+    /// Returns every violation of the constraints of the sheet, by row and
+    /// then in the order the constraints were added. See
+    /// [`Sheet::add_row_constraint`].
+    pub fn check_constraints(&self) -> Vec<ConstraintViolation> {
+        self.rows
+            .iter()
+            .flat_map(|row| self.constraints.check(row, &self.headers))
+            .collect()
+    }
+
+    /// Rejects rows pushed by [`Sheet::push_row`] and cells set by
+    /// [`Sheet::set_cell`] which leave their row breaking a constraint, with
+    /// an [`Error::ConstraintViolation`]. Rows already in the sheet are not
+    /// checked. Off by default.
+    pub fn enforce_constraints(&mut self, enforce: bool) {
+        self.constraints.enforce = enforce;
+    }
+
+    /// Returns an error if constraints are enforced and `row` breaks any.
+    fn assert_constraints(&self, row: &Row) -> Result<()> {
+        if !self.constraints.enforce {
+            return Ok(());
+        }
+
+        match self.constraints.check(row, &self.headers).next() {
+            Some(violation) => Err(Error::ConstraintViolation(violation)),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds a row of `cells` to the end of the sheet, with the next unused
+    /// row id.
+    ///
+    /// Returns an error, leaving the sheet unchanged, if the row is not as
+    /// wide as the sheet, a cell does not match the type of its column or,
+    /// when enforced, the row breaks a constraint. [`Data::None`] matches
+    /// every type.
+    pub fn push_row(&mut self, cells: Vec<Data>) -> Result<()> {
+        let row = Row::from_data(cells, self.id_counter, self.primary_key);
+        row.validate_all_cols(&self.headers)?;
+        self.assert_constraints(&row)?;
+
+        self.rows.push(row);
+        self.id_counter += 1;
+        self.meta.clear();
+        self.row_index.push(&self.rows);
+
+        Ok(())
+    }
+
+    /// Sets the cell at `row` and `col` to `data`, returning its previous
+    /// data.
+    ///
+    /// Returns an error, leaving the sheet unchanged, if either is out of
+    /// range, `data` does not match the type of the column or, when
+    /// enforced, the row would break a constraint.
+    pub fn set_cell(&mut self, row: usize, col: usize, data: Data) -> Result<Data> {
+        let current = self.rows.get(row).ok_or(Error::InvalidRow(row))?;
+        let cell = current
+            .get_cell_by_index(col)
+            .ok_or(Error::InvalidColumnLength(
+                "Tried to access out of range column".to_string(),
+            ))?;
+        let cell = Cell::new(cell.id, data);
+        cell.validate_type(&self.headers[col].kind)?;
+
+        if self.constraints.enforce {
+            let mut changed = current.clone();
+            changed.cells[col] = cell.clone();
+            self.assert_constraints(&changed)?;
+        }
+
+        let key = (current.id, cell.id);
+        self.originals.remove(&key);
+        self.forget_meta(col);
+
+        Ok(std::mem::replace(&mut self.rows[row].cells[col], cell).data)
+    }
+
+    /// Returns the smallest and largest values of the column at `col`, if
+    /// any, using the ordering of [`Data`].
+    ///
+    /// Empty cells are ignored, as are `NaN` and infinite floats, which are
+    /// left out of the bounds of a float [`Scale`] too. Returns an error if
+    /// `col` is out of range.
+    pub fn col_bounds(&self, col: usize) -> Result<Option<(Data, Data)>> {
+        if col >= self.headers.len() {
+            return Err(Error::InvalidColumnLength(
+                "Tried to access out of range column".to_string(),
+            ));
+        }
+
+        if let Some(meta) = self.col_meta(col) {
+            return Ok(meta.bounds().cloned());
+        }
+
+        Ok(bounds(self.rows.iter().filter_map(|row| {
+            row.get_cell_by_index(col).map(Cell::get_data)
+        })))
+    }
+
+    /// Returns the smallest and largest values of the row at `row`, if any.
+    /// See [`Sheet::col_bounds`].
+    ///
+    /// Returns an error if `row` is out of range.
+    pub fn row_bounds(&self, row: usize) -> Result<Option<(Data, Data)>> {
+        let row = self.rows.get(row).ok_or(Error::InvalidRow(row))?;
+
+        Ok(bounds(row.iter_cells().map(Cell::get_data)))
+    }
+
+    /// Returns the smallest and largest values across the columns at `cols`,
+    /// if any, such as for the shared scale of a chart of several columns.
+    /// See [`Sheet::col_bounds`].
+    ///
+    /// Returns an error if any of `cols` is out of range.
+    pub fn bounds_of(&self, cols: &[usize]) -> Result<Option<(Data, Data)>> {
+        let mut values = Vec::with_capacity(cols.len() * 2);
+        for col in cols {
+            if let Some((min, max)) = self.col_bounds(*col)? {
+                values.extend([min, max]);
+            }
+        }
+
+        Ok(bounds(values.iter()))
+    }
+
+    /// Splits each value of the text column at `col` on `delimiter` into a
+    /// new column for each of `new_labels`, returning the indices of the new
+    /// columns. The new columns are appended after the existing ones.
+    ///
+    /// Values with more parts than labels keep the remainder in the last
+    /// part, while those with fewer parts, and empty cells, are padded with
+    /// [`Data::None`]. The type of each new column is inferred from its parts,
+    /// as when loading a [`Sheet`] with [`TypesStrategy::Infer`].
+    ///
+    /// Returns an error if the column is not [`ColumnType::Text`] or
+    /// [`ColumnType::Mixed`], or if `delimiter` is empty. The non-text values
+    /// of a mixed column are split as they would be written to CSV.
+    ///
+    /// ```
+    /// use modav_core::repr::{ColumnHeader, ColumnType, Data, Sheet};
+    ///
+    /// let mut sheet = Sheet::from_rows(
+    ///     vec![
+    ///         vec![Data::Text("2021-Q3".into())],
+    ///         vec![Data::Text("2022".into())],
+    ///     ],
+    ///     vec![ColumnHeader::new("Period".into(), ColumnType::Text)],
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let cols = sheet.split_col(0, "-", &["Year", "Quarter"]).unwrap();
+    /// assert_eq!(cols, vec![1, 2]);
+    /// assert_eq!(sheet.column_kind(1), Some(ColumnType::Integer));
+    ///
+    /// let row = sheet.get_row_by_index(1).unwrap();
+    /// assert_eq!(row.get_cell_by_index(1).unwrap().get_data(), &Data::Integer(2022));
+    /// assert_eq!(row.get_cell_by_index(2).unwrap().get_data(), &Data::None);
+    /// ```
+    pub fn split_col(
+        &mut self,
+        col: usize,
+        delimiter: &str,
+        new_labels: &[&str],
+    ) -> Result<Vec<usize>> {
+        if delimiter.is_empty() {
+            return Err(Error::ConversionError(
+                "Cannot split a column on an empty delimiter".into(),
+            ));
+        }
 
-        self.iter_rows()
-            .try_fold((), |_, curr| curr.validate_col(hdr, col))
+        let mut parts = self
+            .text_values(col)?
+            .into_iter()
+            .map(|value| {
+                let mut parts = value
+                    .as_deref()
+                    .map(|value| value.splitn(new_labels.len(), delimiter))
+                    .into_iter()
+                    .flatten()
+                    .map(|part| Data::from_csv_field(part, None))
+                    .collect::<Vec<Data>>();
+                parts.resize(new_labels.len(), Data::None);
+                parts
+            })
+            .collect::<Vec<Vec<Data>>>();
+
+        let mut cols = Vec::with_capacity(new_labels.len());
+        for (idx, label) in new_labels.iter().enumerate() {
+            let values = parts
+                .iter_mut()
+                .map(|parts| std::mem::take(&mut parts[idx]))
+                .collect();
+            cols.push(self.push_col(label.to_string(), values));
+        }
+
+        Ok(cols)
     }
 
-    fn is_primary_valid(sh: &Sheet) -> Result<()> {
-        let len = sh.headers.len();
-        let pk = sh.primary_key;
+    /// Captures part of each value of the text column at `col` with
+    /// `pattern` into a new column labelled `label`, returning the index of
+    /// the new column. The new column is appended after the existing ones.
+    ///
+    /// Values which do not match, and empty cells, are [`Data::None`]. The
+    /// type of the new column is inferred from the captured parts.
+    ///
+    /// Returns an error if the column is not [`ColumnType::Text`] or
+    /// [`ColumnType::Mixed`]. See [`Sheet::split_col`].
+    pub fn extract_col(
+        &mut self,
+        col: usize,
+        pattern: SimplePattern,
+        label: &str,
+    ) -> Result<usize> {
+        let values = self
+            .text_values(col)?
+            .into_iter()
+            .map(
+                |value| match value.as_deref().and_then(|value| pattern.capture(value)) {
+                    Some(captured) => Data::from_csv_field(captured, None),
+                    None => Data::None,
+                },
+            )
+            .collect();
+
+        Ok(self.push_col(label.to_string(), values))
+    }
+
+    /// Replaces every occurrence of `from` with `to` in the values of the text
+    /// column at `col`, returning the number of modified cells. An empty
+    /// `from` matches nothing. Only text cells are modified, so the non-text
+    /// values of a mixed column are left as they are.
+    ///
+    /// Returns an error if the column is not [`ColumnType::Text`] or
+    /// [`ColumnType::Mixed`].
+    pub fn replace_in_col(&mut self, col: usize, from: &str, to: &str) -> Result<usize> {
+        self.text_values(col)?;
+        if from.is_empty() {
+            return Ok(0);
+        }
+
+        let mut count = 0;
+        for row in self.rows.iter_mut() {
+            if let Some(Data::Text(text)) = row.cells.get_mut(col).map(Cell::get_data_mut) {
+                if text.contains(from) {
+                    *text = text.replace(from, to);
+                    count += 1;
+                }
+            }
+        }
+
+        if count > 0 {
+            self.forget_meta(col);
+        }
+
+        Ok(count)
+    }
+
+    /// Returns a new [`Sheet`] joining each row with the rows of `other` whose
+    /// value at `other_col` equals its value at `col`, as in an inner join.
+    ///
+    /// The new sheet has the columns of this sheet followed by those of
+    /// `other` except `other_col`, and keeps the primary key of this sheet.
+    /// Rows are in the order of this sheet, then of `other` for rows with
+    /// several matches. Rows without a match, and empty keys, are left out.
+    ///
+    /// Returns an error if `col` or `other_col` is out of range.
+    ///
+    /// ```
+    /// use modav_core::repr::{ColumnHeader, ColumnType, Data, Sheet};
+    ///
+    /// let sales = Sheet::from_rows(
+    ///     vec![
+    ///         vec![Data::Text("north".into()), Data::Integer(12)],
+    ///         vec![Data::Text("south".into()), Data::Integer(7)],
+    ///         vec![Data::Text("east".into()), Data::Integer(3)],
+    ///     ],
+    ///     vec![
+    ///         ColumnHeader::new("Region".into(), ColumnType::Text),
+    ///         ColumnHeader::new("Sales".into(), ColumnType::Integer),
+    ///     ],
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// let managers = Sheet::from_rows(
+    ///     vec![
+    ///         vec![Data::Text("south".into()), Data::Text("Ama".into())],
+    ///         vec![Data::Text("north".into()), Data::Text("Kofi".into())],
+    ///     ],
+    ///     vec![
+    ///         ColumnHeader::new("Region".into(), ColumnType::Text),
+    ///         ColumnHeader::new("Manager".into(), ColumnType::Text),
+    ///     ],
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let joined = sales.join(&managers, 0, 0).unwrap();
+    /// assert_eq!(joined.width(), 3);
+    /// assert_eq!(joined.height(), 2);
+    ///
+    /// let row = joined.get_row_by_index(0).unwrap();
+    /// assert_eq!(row.get_cell_by_index(2).unwrap().get_data(), &Data::Text("Kofi".into()));
+    /// ```
+    pub fn join(&self, other: &Sheet, col: usize, other_col: usize) -> Result<Sheet> {
+        if col >= self.width() || other_col >= other.width() {
+            return Err(Error::InvalidColumnLength(
+                "Tried to join on out of range column".to_string(),
+            ));
+        }
+
+        let mut matches: HashMap<&Data, Vec<&Row>> = HashMap::new();
+        for row in other.rows.iter() {
+            match row.get_cell_by_index(other_col).map(Cell::get_data) {
+                Some(Data::None) | None => {}
+                Some(key) => matches.entry(key).or_default().push(row),
+            }
+        }
+
+        let rows = self
+            .rows
+            .iter()
+            .flat_map(|row| {
+                let key = row.get_cell_by_index(col).map(Cell::get_data);
+                let others = key.and_then(|key| matches.get(key));
+
+                others.into_iter().flatten().map(move |other| {
+                    row.cells
+                        .iter()
+                        .chain(
+                            other
+                                .cells
+                                .iter()
+                                .enumerate()
+                                .filter(|(idx, _)| *idx != other_col)
+                                .map(|(_, cell)| cell),
+                        )
+                        .map(|cell| cell.data.clone())
+                        .collect::<Vec<Data>>()
+                })
+            })
+            .collect();
+
+        let headers = self
+            .headers
+            .iter()
+            .chain(
+                other
+                    .headers
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| *idx != other_col)
+                    .map(|(_, header)| header),
+            )
+            .cloned()
+            .collect();
+
+        Sheet::from_rows(rows, headers, self.primary_key)
+    }
+
+    /// Returns a new [`Sheet`] with the rows of `other` appended after those
+    /// of this sheet.
+    ///
+    /// The new sheet keeps the headers and primary key of this sheet. A
+    /// column whose type differs between the sheets becomes
+    /// [`ColumnType::Mixed`], unless either is [`ColumnType::None`].
+    ///
+    /// Returns an error if the sheets have different widths.
+    pub fn concat(&self, other: &Sheet) -> Result<Sheet> {
+        if self.width() != other.width() {
+            return Err(Error::InvalidColumnLength(format!(
+                "Tried to concatenate a sheet of {} columns to one of {}",
+                other.width(),
+                self.width()
+            )));
+        }
+
+        let headers = self
+            .headers
+            .iter()
+            .zip(other.headers.iter())
+            .map(|(own, other)| {
+                let mut header = own.clone();
+                header.kind = merge_kinds(own.kind, other.kind);
+                header
+            })
+            .collect();
+
+        let rows = self
+            .rows
+            .iter()
+            .chain(other.rows.iter())
+            .map(|row| row.cells.iter().map(|cell| cell.data.clone()).collect())
+            .collect();
+
+        Sheet::from_rows(rows, headers, self.primary_key)
+    }
+
+    /// Returns a line graph of the differences between this sheet, the base,
+    /// and `other`, along with the rows and columns left out because only
+    /// one of the sheets has them.
+    ///
+    /// Rows are aligned by the values of their primary keys and columns by
+    /// their labels, so both sheets need a primary key. There is one line
+    /// per row of this sheet also in `other`, labelled by its key, with a
+    /// point for each numeric column of this sheet also in `other`. Each
+    /// point is the difference between the values of `other` and this sheet,
+    /// as chosen by [`DiffOptions::mode`]. Empty cells have no point.
+    ///
+    /// Differences between integer columns are integers, and between number
+    /// columns numbers. Other differences, and every percent change, are
+    /// floats.
+    ///
+    /// Returns an error if [`DiffOptions::strict`] is set and either sheet
+    /// has rows or columns the other does not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::prelude::*;
+    ///
+    /// let headers = || {
+    ///     vec![
+    ///         ColumnHeader::new("Region".into(), ColumnType::Text),
+    ///         ColumnHeader::new("Sales".into(), ColumnType::Integer),
+    ///     ]
+    /// };
+    /// let before = Sheet::from_rows(
+    ///     vec![
+    ///         vec![Data::Text("north".into()), Data::Integer(10)],
+    ///         vec![Data::Text("south".into()), Data::Integer(7)],
+    ///     ],
+    ///     headers(),
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// let after = Sheet::from_rows(
+    ///     vec![
+    ///         vec![Data::Text("south".into()), Data::Integer(4)],
+    ///         vec![Data::Text("north".into()), Data::Integer(15)],
+    ///     ],
+    ///     headers(),
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let (graph, summary) = before.diff_line_graph(&after, &DiffOptions::new()).unwrap();
+    /// assert!(summary.is_empty());
+    /// assert_eq!(graph.lines[0].label.as_deref(), Some("north"));
+    /// assert_eq!(graph.lines[0].points[0].y, Data::Integer(5));
+    /// assert_eq!(graph.lines[1].points[0].y, Data::Integer(-3));
+    /// ```
+    pub fn diff_line_graph(
+        &self,
+        other: &Sheet,
+        options: &DiffOptions,
+    ) -> Result<(LineGraph, DiffSummary)> {
+        let _span = trace_span!(
+            "create_diff_line_graph",
+            rows = self.height(),
+            cols = self.width()
+        );
+
+        let (Some(primary), Some(other_primary)) = (self.primary_key, other.primary_key) else {
+            return Err(Error::InvalidPrimaryKey(
+                "Both sheets need a primary key to be compared".into(),
+            ));
+        };
+
+        let mut summary = DiffSummary::default();
+        let mut columns = Vec::new();
+        for (col, header) in self.numeric_columns() {
+            match other
+                .numeric_columns()
+                .find(|(_, other)| other.label == header.label)
+            {
+                Some((other_col, other_header)) => {
+                    columns.push((col, other_col, header.kind, other_header.kind))
+                }
+                None => summary.base_only_cols.push(header.label.clone()),
+            }
+        }
+        summary.other_only_cols = other
+            .numeric_columns()
+            .filter(|(_, header)| {
+                !self
+                    .numeric_columns()
+                    .any(|(_, own)| own.label == header.label)
+            })
+            .map(|(_, header)| header.label.clone())
+            .collect();
+
+        let mut keys: HashMap<&Data, &Row> = HashMap::new();
+        for row in other.rows.iter() {
+            if let Some(cell) = row.get_cell_by_index(other_primary) {
+                keys.entry(&cell.data).or_insert(row);
+            }
+        }
+
+        let kind = match options.get_mode() {
+            DiffMode::PercentChange => ScaleKind::Float,
+            DiffMode::Delta => {
+                let kinds = || columns.iter().flat_map(|(.., own, other)| [*own, *other]);
+                if kinds().all(|kind| kind == ColumnType::Integer) {
+                    ScaleKind::Integer
+                } else if kinds().all(|kind| kind == ColumnType::Number) {
+                    ScaleKind::Number
+                } else {
+                    ScaleKind::Float
+                }
+            }
+        };
+
+        let mut own_keys = HashSet::new();
+        let mut lines = Vec::new();
+        for row in self.rows.iter() {
+            let key = sort_key(row, primary);
+            own_keys.insert(key);
+
+            let Some(other_row) = keys.get(key) else {
+                summary.base_only_rows.push(key.clone());
+                continue;
+            };
+
+            let points = columns.iter().filter_map(|(col, other_col, ..)| {
+                let before = &row.get_cell_by_index(*col)?.data;
+                let after = &other_row.get_cell_by_index(*other_col)?.data;
+                let y = difference(before, after, options.get_mode(), kind)?;
+                let x = Data::Text(self.headers[*col].label.clone());
+
+                Some(Point::new(x, y))
+            });
+
+            lines.push(Line::from_points(points).label(key.to_string()));
+        }
+        summary.other_only_rows = other
+            .rows
+            .iter()
+            .map(|row| sort_key(row, other_primary))
+            .filter(|key| !own_keys.contains(key))
+            .cloned()
+            .collect();
+
+        if options.is_strict() && !summary.is_empty() {
+            return Err(Error::ConversionError(format!(
+                "Diff: {} rows and {} columns are in only one of the sheets",
+                summary.base_only_rows.len() + summary.other_only_rows.len(),
+                summary.base_only_cols.len() + summary.other_only_cols.len()
+            )));
+        }
+
+        let x_scale = Scale::new(
+            columns
+                .iter()
+                .map(|(col, ..)| Data::Text(self.headers[*col].label.clone())),
+            ScaleKind::Categorical,
+        );
+        let y_scale = Scale::new(
+            lines
+                .iter()
+                .flat_map(|line| line.points.iter().map(|point| point.y.clone())),
+            kind,
+        );
+
+        let graph = LineGraph::new(
+            lines,
+            None,
+            Some(options.get_mode().to_string()),
+            x_scale,
+            y_scale,
+        )?;
+
+        Ok((graph, summary))
+    }
+
+    /// Returns the numeric columns other than the primary column, with their
+    /// positions.
+    fn numeric_columns(&self) -> impl Iterator<Item = (usize, &ColumnHeader)> {
+        self.headers
+            .iter()
+            .enumerate()
+            .filter(|(col, header)| Some(*col) != self.primary_key && header.kind.is_numeric())
+    }
+
+    /// Returns the text of each cell of the column at `col`, with empty cells
+    /// as [`None`], if it is a text or mixed column.
+    fn text_values(&self, col: usize) -> Result<Vec<Option<Cow<'_, str>>>> {
+        match self.column_kind(col) {
+            Some(ColumnType::Text) | Some(ColumnType::Mixed) => {}
+            Some(kind) => {
+                return Err(Error::InvalidColumnType(format!(
+                    "Expected a Text or Mixed column but column {} is {:?}",
+                    col, kind
+                )))
+            }
+            None => {
+                return Err(Error::InvalidColumnLength(
+                    "Tried to access out of range column".to_string(),
+                ))
+            }
+        }
+
+        let values = self
+            .rows
+            .iter()
+            .map(|row| match row.get_cell_by_index(col).map(Cell::get_data) {
+                Some(Data::Text(text)) => Some(Cow::Borrowed(text.as_str())),
+                Some(Data::None) | None => None,
+                Some(data) => Some(Cow::Owned(data.to_csv_field())),
+            })
+            .collect();
+
+        Ok(values)
+    }
+
+    /// Returns the meta of the column at `col`, if it was collected while
+    /// loading the [`Sheet`] and the column has not changed since. See
+    /// [`Config::collect_meta`].
+    pub fn col_meta(&self, col: usize) -> Option<&ColumnMeta> {
+        self.meta.get(col)?.as_ref()
+    }
+
+    /// Gathers the meta of the column at `col` from its cells.
+    fn compute_meta(&self, col: usize) -> ColumnMeta {
+        let values = self
+            .rows
+            .iter()
+            .filter_map(|row| row.get_cell_by_index(col).map(Cell::get_data));
+        let nulls = self.height() - values.clone().filter(|data| **data != Data::None).count();
+        let values = values.filter(|data| **data != Data::None);
+
+        ColumnMeta::new(
+            bounds(values.clone()),
+            nulls,
+            count_distinct(values.clone()),
+            is_increasing(values),
+        )
+    }
+
+    /// Drops the meta of the column at `col`, once its values change.
+    fn forget_meta(&mut self, col: usize) {
+        if let Some(meta) = self.meta.get_mut(col) {
+            *meta = None;
+        }
+    }
+
+    /// Appends a column labelled `label` holding `values`, one for each row,
+    /// returning its index. The type of the column is inferred from `values`.
+    fn push_col(&mut self, label: String, values: Vec<Data>) -> usize {
+        let kind = values.iter().fold(ColumnType::None, |acc, data| {
+            merge_kinds(acc, data.clone().into())
+        });
+
+        for (row, data) in self.rows.iter_mut().zip(values) {
+            row.push_cell(data);
+        }
+        self.headers.push(ColumnHeader::new(label, kind));
+
+        self.headers.len() - 1
+    }
 
-        if (len == pk && pk != 0) || (len < pk) {
+    fn is_primary_valid(sh: &Sheet) -> Result<()> {
+        if sh.primary_key.map_or(false, |pk| pk >= sh.headers.len()) {
             return Err(Error::InvalidPrimaryKey(
                 "Primary key out of column range".into(),
             ));
@@ -592,9 +2556,13 @@ impl Sheet {
             .try_fold((), |_acc, curr| curr.is_primary_key_valid())
     }
 
-    fn set_primary_key(&mut self, new_key: usize) -> Result<()> {
-        if self.rows.iter().all(|curr| curr.is_key_valid(new_key)) {
-            self.primary_key = new_key;
+    /// Sets the primary column of the sheet to the column at `new_key`.
+    ///
+    /// Returns an error if `new_key` is out of range, in which case no change
+    /// is made.
+    pub fn set_primary_key(&mut self, new_key: usize) -> Result<()> {
+        if new_key < self.width() && self.rows.iter().all(|curr| curr.is_key_valid(new_key)) {
+            self.primary_key = Some(new_key);
             self.rows
                 .iter_mut()
                 .for_each(|row| row.set_primary_key(new_key).unwrap());
@@ -605,138 +2573,214 @@ impl Sheet {
         ))
     }
 
-    pub fn get_primary_key(&self) -> usize {
+    /// Leaves the sheet without a primary column. Rows are then never
+    /// selected by a [`RowSelector::Keys`].
+    pub fn clear_primary_key(&mut self) {
+        self.primary_key = None;
+        self.rows.iter_mut().for_each(|row| row.primary = None);
+    }
+
+    /// Returns the index of the primary column of the sheet, if any.
+    ///
+    /// A sheet without columns has [`None`] as its primary column.
+    pub fn get_primary_key(&self) -> Option<usize> {
         self.primary_key
     }
 
+    /// Returns a builder of charts sharing the options of `context`. The
+    /// sheet and context are validated once, here, and reused by every chart
+    /// built. See [`ChartContext`].
+    ///
+    /// Returns an error if the sheet has no data to chart or a cell does not
+    /// match the type of its column, or if an excluded column is out of
+    /// range.
+    pub fn chart<'a>(&'a self, context: &'a ChartContext) -> Result<ChartBuilder<'a>> {
+        ChartBuilder::new(self, context)
+    }
+
+    /// Returns a view of the sheet for reordering and hiding columns without
+    /// copying it. See [`SheetView`].
+    pub fn view(&self) -> SheetView<'_> {
+        SheetView::new(self)
+    }
+
     pub fn iter_rows(&self) -> Iter<'_, Row> {
         self.rows.iter()
     }
 
+    /// Returns an iterator over the rows of the [`Sheet`] converted to `T`.
+    /// See [`FromRow`].
+    pub fn iter_as<T: FromRow>(&self) -> impl Iterator<Item = Result<T>> + '_ {
+        self.rows.iter().map(|row| T::from_row(row, &self.headers))
+    }
+
     /// Should probably call Sheet::validate after using this function
     pub fn iter_rows_mut(&mut self) -> IterMut<'_, Row> {
+        self.meta.clear();
+        self.row_index.clear();
         self.rows.iter_mut()
     }
 
+    /// Returns an iterator over the data of every cell with its row and
+    /// column, in row-major order. The coordinates are the positions of the
+    /// cell within the [`Sheet`], which may differ from the ids of its row
+    /// and cell once columns are removed or rows reordered.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &Data)> {
+        self.rows.iter().enumerate().flat_map(|(idx, row)| {
+            row.cells
+                .iter()
+                .enumerate()
+                .map(move |(col, cell)| (idx, col, &cell.data))
+        })
+    }
+
+    /// Returns an iterator over mutable references to the data of every
+    /// cell with its row and column, in row-major order. See
+    /// [`Sheet::iter_cells`].
+    ///
+    /// Cells are not checked against the types of their columns, so
+    /// [`Sheet::validate`] should be called after using this function.
+    pub fn iter_cells_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut Data)> {
+        self.meta.clear();
+        self.rows.iter_mut().enumerate().flat_map(|(idx, row)| {
+            row.cells
+                .iter_mut()
+                .enumerate()
+                .map(move |(col, cell)| (idx, col, &mut cell.data))
+        })
+    }
+
+    /// Returns an iterator over the data of the column at `col` with the
+    /// row of each cell. The iterator is empty if `col` is out of range.
+    pub fn enumerate_col(&self, col: usize) -> impl Iterator<Item = (usize, &Data)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(move |(idx, row)| Some((idx, &row.cells.get(col)?.data)))
+    }
+
     pub fn get_headers(&self) -> &Vec<ColumnHeader> {
         &self.headers
     }
 
+    /// Returns true if both sheets hold the same data under the same
+    /// headers, in the same order.
+    ///
+    /// Only the label and type of each header are compared. Row and cell
+    /// ids, the primary key and original text are ignored, so a sheet
+    /// rebuilt through edits equals one read directly.
+    pub fn logical_eq(&self, other: &Self) -> bool {
+        self.width() == other.width()
+            && self.height() == other.height()
+            && self
+                .headers
+                .iter()
+                .zip(other.headers.iter())
+                .all(|(own, other)| own.label == other.label && own.kind == other.kind)
+            && self.rows.iter().zip(other.rows.iter()).all(|(own, other)| {
+                own.cells
+                    .iter()
+                    .map(|cell| &cell.data)
+                    .eq(other.cells.iter().map(|cell| &cell.data))
+            })
+    }
+
     pub fn sort_rows(&mut self, col: usize) -> Result<()> {
+        let _span = trace_span!("sort_rows", col, rows = self.height());
+
         let ch = self
             .headers
             .get(col)
             .ok_or(Error::InvalidColumnLength("Column out of range".into()))?;
 
-        if let ColumnHeader {
-            kind: ColumnType::None,
-            ..
-        } = ch
-        {
-            return Err(Error::InvalidColumnSort(
-                "Tried to sort by an unstructured column ".into(),
-            ));
+        match ch.kind {
+            ColumnType::None => return Ok(()),
+            kind if !kind.is_sortable() => {
+                return Err(Error::InvalidColumnSort(format!(
+                    "Tried to sort by column `{}` of {} type",
+                    ch.label,
+                    kind.name()
+                )));
+            }
+            _ => {}
         }
 
         self.validate_col(col)?;
 
         let asc = |x: &Row, y: &Row| {
-            let d1 = &x.cells.get(col).unwrap().data;
-            let d2 = &y.cells.get(col).unwrap().data;
-
-            match (d1, d2) {
-                (Data::None, Data::None) => std::cmp::Ordering::Equal,
-                (Data::Text(s1), Data::Text(s2)) => s1.cmp(s2),
-                (Data::Float(f1), Data::Float(f2)) => f1.total_cmp(f2),
-                (Data::Number(n1), Data::Number(n2)) => n1.cmp(n2),
-                (Data::Integer(i1), Data::Integer(i2)) => i1.cmp(i2),
-                (Data::Boolean(b1), Data::Boolean(b2)) => b1.cmp(b2),
-                // Should never reach this case. Previous checks ensure that
-                _ => std::cmp::Ordering::Equal,
-            }
+            let d1 = sort_key(x, col);
+            let d2 = sort_key(y, col);
+
+            d1.cmp(d2)
         };
 
         self.rows.sort_by(asc);
+        self.meta.clear();
+        self.row_index.rebuild(&self.rows);
 
         Ok(())
     }
 
     pub fn sort_rows_rev(&mut self, col: usize) -> Result<()> {
+        let _span = trace_span!("sort_rows_rev", col, rows = self.height());
+
         let ch = self
             .headers
             .get(col)
             .ok_or(Error::InvalidColumnLength("Column out of range".into()))?;
 
-        if let ColumnHeader {
-            kind: ColumnType::None,
-            ..
-        } = ch
-        {
-            return Err(Error::InvalidColumnSort(
-                "Tried to sort by an unstructured column ".into(),
-            ));
+        match ch.kind {
+            ColumnType::None => return Ok(()),
+            kind if !kind.is_sortable() => {
+                return Err(Error::InvalidColumnSort(format!(
+                    "Tried to sort by column `{}` of {} type",
+                    ch.label,
+                    kind.name()
+                )));
+            }
+            _ => {}
         }
 
         self.validate_col(col)?;
 
         let desc = |x: &Row, y: &Row| {
-            let d1 = &x.cells.get(col).unwrap().data;
-            let d2 = &y.cells.get(col).unwrap().data;
-
-            match (d1, d2) {
-                (Data::None, Data::None) => std::cmp::Ordering::Equal,
-                (Data::Text(s1), Data::Text(s2)) => s2.cmp(s1),
-                (Data::Float(f1), Data::Float(f2)) => f2.total_cmp(f1),
-                (Data::Number(n1), Data::Number(n2)) => n2.cmp(n1),
-                (Data::Integer(i1), Data::Integer(i2)) => i2.cmp(i1),
-                (Data::Boolean(b1), Data::Boolean(b2)) => b2.cmp(b1),
-                // Should never reach this case. Previous checks ensure that
-                _ => std::cmp::Ordering::Equal,
-            }
+            let d1 = sort_key(x, col);
+            let d2 = sort_key(y, col);
+
+            d2.cmp(d1)
         };
 
         self.rows.sort_by(desc);
+        self.meta.clear();
+        self.row_index.rebuild(&self.rows);
 
         Ok(())
     }
 
+    /// Sets the type of each column from its cells. Empty cells are ignored,
+    /// so a column of only empty cells is [`ColumnType::None`], while one
+    /// whose cells disagree is [`ColumnType::Mixed`].
     fn infer_col_kinds(sh: &mut Self, header_len: usize) {
-        let mut is_first_iteration = true;
-        let col_kinds: Vec<ColumnType> = sh
+        #[cfg(test)]
+        INFERENCES.with(|count| count.set(count.get() + 1));
+
+        let col_kinds = sh
             .iter_rows()
-            .map(|rw| {
-                rw.iter_cells()
-                    .map(|cl| cl.get_data().clone().into())
-                    .collect::<Vec<ColumnType>>()
-            })
-            .fold(vec![None; header_len], |acc, curr| {
+            .fold(vec![ColumnType::None; header_len], |acc, row| {
                 acc.into_iter()
-                    .zip(curr)
-                    .map(|(ac, cr)| match ac {
-                        None => Some(cr),
-                        Some(ac) => match (ac, cr) {
-                            (ColumnType::None, x) => {
-                                if is_first_iteration {
-                                    is_first_iteration = false;
-                                    Some(x)
-                                } else {
-                                    Some(ColumnType::None)
-                                }
-                            }
-                            (y, ColumnType::None) => Some(y),
-                            (ac, cr) if ac == cr => Some(ac),
-                            _ => Some(ColumnType::None),
-                        },
-                    })
-                    .collect()
-            })
-            .into_iter()
-            .map(|op| op.unwrap_or_default())
-            .collect();
+                    .zip(row.iter_cells())
+                    .map(|(acc, cell)| merge_kinds(acc, cell.get_data().clone().into()))
+                    .collect::<Vec<ColumnType>>()
+            });
 
         sh.headers.iter_mut().zip(col_kinds).for_each(|(hdr, knd)| {
             hdr.kind = knd;
         });
+
+        #[cfg(feature = "trace")]
+        for (col, hdr) in sh.headers.iter().enumerate() {
+            trace_debug!(col, label = %hdr.label, kind = %hdr.kind, "inferred column kind");
+        }
     }
 
     /// initial_header: The new label for the initial header, if any
@@ -770,13 +2814,7 @@ impl Sheet {
                 let mut hrs = sheet
                     .iter_rows()
                     .fold(Vec::<ColumnHeader>::new(), |acc, curr| {
-                        let cln = match curr.get_cell_by_index(0).unwrap() {
-                            Cell {
-                                id: _,
-                                data: Data::None,
-                            } => String::new(),
-                            Cell { id: _, data: d } => d.to_string(),
-                        };
+                        let cln = curr.get_cell_by_index(0).unwrap().data.to_csv_field();
                         let hdr = ColumnHeader::new(cln, ColumnType::None);
                         let mut acc = acc;
                         acc.push(hdr);
@@ -785,7 +2823,7 @@ impl Sheet {
                 headers.push(hr);
                 headers.append(&mut hrs);
             } else {
-                let first = Cell::new(0, hr.label.into());
+                let first = Cell::new(0, Data::from_csv_field(&hr.label, None));
                 let mut rw = vec![first];
                 let mut cls: Vec<Cell> = sheet
                     .iter_rows()
@@ -812,18 +2850,34 @@ impl Sheet {
             .enumerate()
             .map(|(id, cells)| Row {
                 cells,
-                primary: 0,
+                primary: Some(0),
                 id,
                 id_counter: depth,
+                padding: 0,
             })
             .collect();
 
+        // Each new column holds a value from every old column, so only a unit
+        // shared by all of them still applies.
+        let unit = sheet.shared_unit(1..width).cloned();
+        headers[0].unit = None;
+        headers
+            .iter_mut()
+            .skip(1)
+            .for_each(|header| header.unit = unit.clone());
+
         let mut sh = Sheet {
             rows,
             headers,
             id_counter: width - 1,
-            primary_key: 0,
+            primary_key: Some(0),
+            originals: HashMap::new(),
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: RowConstraints::default(),
+            report: LoadReport::default(),
         };
+        sh.renumber();
 
         Self::infer_col_kinds(&mut sh, depth);
 
@@ -847,74 +2901,102 @@ impl Sheet {
     }
 
     fn grab_header(&self, col: usize) -> Result<&ColumnHeader> {
-        let hr = self.headers.get(col).ok_or(Error::InvalidColumnLength(
+        let hr = self.header(col).ok_or(Error::InvalidColumnLength(
             "Tried accessing an out of bounds Header".into(),
         ))?;
 
-        match hr.kind {
-            ColumnType::None => Err(Error::ConversionError(
-                "Cannot convert non uniform type column".into(),
-            )),
-            _ => Ok(hr),
+        self.assert_uniform(col)?;
+
+        Ok(hr)
+    }
+
+    /// Returns the distinct types of the non-empty cells in the column at
+    /// `col`, in the order first seen.
+    fn distinct_types(&self, col: usize) -> Vec<ColumnType> {
+        distinct_types(
+            self.iter_rows()
+                .filter_map(|row| row.cells.get(col))
+                .map(Cell::get_data),
+        )
+    }
+
+    /// Returns an error if the column at `col` is not of a single, known type.
+    fn assert_uniform(&self, col: usize) -> Result<()> {
+        match self.headers.get(col) {
+            Some(header) => assert_uniform(header, || self.distinct_types(col)),
+            None => Ok(()),
         }
     }
 
-    fn validate_to_line_graph(&self, label_strat: &LineLabelStrategy) -> Result<ScaleKind> {
-        // None type Columns
-        self.headers.iter().try_fold((), |_acc, curr| {
-            if let ColumnHeader {
-                kind: ColumnType::None,
-                ..
-            } = curr
-            {
-                Err(Error::ConversionError(
-                    "Cannot convert non uniform type column".into(),
-                ))
-            } else {
-                Ok(())
-            }
-        })?;
+    /// Returns the [`Sheet`] with the boolean columns within `cols` converted
+    /// to integer columns, with `true` as 1 and `false` as 0. The sheet is
+    /// only copied if there are boolean columns to convert. Returns an error
+    /// if there is a boolean column but no `mode`.
+    pub(super) fn charted(&self, cols: &[usize], mode: Option<BoolMode>) -> Result<Cow<'_, Sheet>> {
+        self.check_booleans(cols, mode)?;
 
-        let check_uniform_type = |acc: ColumnType, ct: ColumnType| match (&acc, &ct) {
-            (ColumnType::None, _) => Ok(ct),
-            (x, y) => {
-                if x == y {
-                    Ok(ct)
-                } else {
-                    Err(Error::ConversionError(
-                        "Cannot convert different column types".into(),
-                    ))
-                }
-            }
-        };
+        if cols
+            .iter()
+            .any(|col| self.column_kind(*col) == Some(ColumnType::Boolean))
+        {
+            let mut sheet = self.clone();
+            sheet.chart_booleans(cols);
+            Ok(Cow::Owned(sheet))
+        } else {
+            Ok(Cow::Borrowed(self))
+        }
+    }
 
-        // Uniform type columns
-        let kind = match label_strat {
-            LineLabelStrategy::FromCell(idx) => {
-                if idx >= &self.headers.len() {
-                    return Err(Error::ConversionError(
-                        "Tried to assign invalid column as label".into(),
-                    ));
+    /// Converts the boolean columns within `cols` to integer columns, with
+    /// `true` as 1 and `false` as 0.
+    fn chart_booleans(&mut self, cols: &[usize]) {
+        for col in cols.iter() {
+            let header = match self.headers.get_mut(*col) {
+                Some(header) if header.kind == ColumnType::Boolean => header,
+                _ => continue,
+            };
+
+            header.kind = ColumnType::Integer;
+            self.forget_meta(*col);
+
+            for row in self.rows.iter_mut() {
+                if let Some(cell) = row.cells.get_mut(*col) {
+                    if let Data::Boolean(value) = cell.data {
+                        cell.data = Data::Integer(value as i32);
+                    }
                 }
-
-                self.headers
-                    .iter()
-                    .map(|hrd| &hrd.kind)
-                    .enumerate()
-                    .filter(|(ind, _)| ind != idx)
-                    .try_fold(ColumnType::None, |acc, (_, ct)| {
-                        check_uniform_type(acc, *ct)
-                    })?
             }
+        }
+    }
 
-            _ => self
-                .headers
-                .iter()
-                .map(|hdr| &hdr.kind)
-                .try_fold(ColumnType::None, |acc, ct| check_uniform_type(acc, *ct))?,
-        };
+    /// Returns an error if there is a boolean column within `cols` but no
+    /// `mode`.
+    pub(super) fn check_booleans(&self, cols: &[usize], mode: Option<BoolMode>) -> Result<()> {
+        if mode.is_some() {
+            return Ok(());
+        }
+
+        match cols
+            .iter()
+            .filter_map(|col| self.headers.get(*col))
+            .find(|header| header.kind == ColumnType::Boolean)
+        {
+            Some(header) => Err(Error::ConversionError(format!(
+                "Cannot chart the boolean column `{}`. Use `ScaleOptions::bool_mode` to chart it as 1 and 0 or as counts of true",
+                header.label
+            ))),
+            None => Ok(()),
+        }
+    }
 
-        Ok(kind.into())
+    fn validate_to_line_graph(
+        &self,
+        label_strat: &LineLabelStrategy,
+        exclude: &HashSet<usize>,
+    ) -> Result<ScaleKind> {
+        validate_line_columns(&self.headers, self.height(), label_strat, exclude, |col| {
+            self.distinct_types(col)
+        })
     }
 
     fn validate_to_barchart(
@@ -922,15 +3004,31 @@ impl Sheet {
         x_col: usize,
         y_col: usize,
         bar_label: &BarChartBarLabels,
+        exclude: &HashSet<usize>,
     ) -> Result<(ScaleKind, ScaleKind)> {
-        if let BarChartBarLabels::FromColumn(idx) = bar_label {
-            if idx >= &self.headers.len() {
+        if let Some(idx) = bar_label.label_col() {
+            if idx >= self.headers.len() {
                 return Err(Error::ConversionError(
                     "Bar chart label column out of range".into(),
                 ));
             }
+
+            assert_included(exclude, idx, "Bar chart label")?;
+        }
+
+        if let BarChartBarLabels::Provided(labels) = bar_label {
+            if labels.len() > self.height() {
+                return Err(Error::ConversionError(format!(
+                    "Provided {} bar labels for {} rows",
+                    labels.len(),
+                    self.height()
+                )));
+            }
         }
 
+        assert_included(exclude, x_col, "Bar chart x")?;
+        assert_included(exclude, y_col, "Bar chart y")?;
+
         let x_type = self
             .headers
             .get(x_col)
@@ -939,11 +3037,7 @@ impl Sheet {
             ))?
             .kind;
 
-        if x_type == ColumnType::None {
-            return Err(Error::ConversionError(
-                "Cannot convert from non-uniform column".into(),
-            ));
-        }
+        self.assert_uniform(x_col)?;
 
         let y_type = self
             .headers
@@ -953,11 +3047,7 @@ impl Sheet {
             ))?
             .kind;
 
-        if y_type == ColumnType::None {
-            return Err(Error::ConversionError(
-                "Cannot convert from non-uniform column".into(),
-            ));
-        }
+        self.assert_uniform(y_col)?;
 
         Ok((x_type.into(), y_type.into()))
     }
@@ -966,10 +3056,15 @@ impl Sheet {
         &self,
         x_col: usize,
         cols: &[usize],
+        exclude: &HashSet<usize>,
     ) -> Result<(Vec<String>, ScaleKind)> {
         self.headers.get(x_col).ok_or(Error::ConversionError(
             "Stacked Bar chart: x column out of range".into(),
         ))?;
+        assert_included(exclude, x_col, "Stacked Bar chart: x")?;
+        cols.iter().try_for_each(|col| {
+            assert_included(exclude, *col, "Stacked Bar chart: Accumulating")
+        })?;
 
         let mut kind = None;
         let mut labels = Vec::with_capacity(cols.len());
@@ -983,6 +3078,8 @@ impl Sheet {
                     "Stacked Bar chart: Accumulating column, out of range".into(),
                 ))?;
 
+            self.assert_uniform(*col)?;
+
             match kind {
                 None => kind = Some(header.kind),
                 Some(prev) => {
@@ -1012,235 +3109,848 @@ impl Sheet {
 
     /// Returns a new line graph created from this csv struct
     ///
-    /// exclude_row: The positions of the rows to exclude in this transformation
+    /// exclude_row: The rows to exclude in this transformation. See
+    /// [`RowSelector`] for how exclusions interact with sorting
     /// exclude_column: The positions of columns to exclude in the
     /// transformation
+    #[deprecated(note = "use `Sheet::create_line_graph_labelled` instead")]
     pub fn create_line_graph(
         &self,
         x_label: Option<String>,
         y_label: Option<String>,
         label_strat: LineLabelStrategy,
-        exclude_row: HashSet<usize>,
+        exclude_row: impl Into<RowSelector>,
+        exclude_column: HashSet<usize>,
+    ) -> Result<LineGraph> {
+        self.validate_for_chart()?;
+        self.line_graph(
+            x_label,
+            y_label,
+            label_strat,
+            &exclude_row.into(),
+            &exclude_column,
+            &ScaleOptions::default(),
+            &self.columns(),
+        )
+    }
+
+    /// Returns a new line graph created from this csv struct, with `options`
+    /// applied to the y scale.
+    #[deprecated(note = "use `Sheet::create_line_graph_labelled_with` instead")]
+    pub fn create_line_graph_with(
+        &self,
+        x_label: Option<String>,
+        y_label: Option<String>,
+        label_strat: LineLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        exclude_column: HashSet<usize>,
+        options: ScaleOptions,
+    ) -> Result<LineGraph> {
+        self.validate_for_chart()?;
+        self.line_graph(
+            x_label,
+            y_label,
+            label_strat,
+            &exclude_row.into(),
+            &exclude_column,
+            &options,
+            &self.columns(),
+        )
+    }
+
+    /// Returns a new line graph created from this csv struct, with the axis
+    /// labels determined by `axis_labels`.
+    ///
+    /// exclude_row: The rows to exclude in this transformation. See
+    /// [`RowSelector`] for how exclusions interact with sorting
+    /// exclude_column: The positions of columns to exclude in the
+    /// transformation. Excluded columns are neither validated nor charted,
+    /// so columns of unknown or mixed types can be left out. Labelling lines
+    /// from an excluded column is an error
+    ///
+    /// There is one line per row not excluded, in the order of the rows of
+    /// the sheet. Columns sharing a label are kept apart on the x axis by
+    /// suffixing each repeat of the label with its count, such as
+    /// `Sales (2)`, rather than having their points overlap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{prelude::*, samples};
+    /// use std::collections::HashSet;
+    ///
+    /// let sheet = samples::air();
+    /// let graph = sheet
+    ///     .create_line_graph_labelled(
+    ///         LineGraphAxisLabelStrategy::Headers { y: "Passengers".into() },
+    ///         LineLabelStrategy::FromCell(0),
+    ///         RowSelector::default(),
+    ///         HashSet::new(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.lines.len(), 12);
+    /// assert_eq!(graph.lines[0].label.as_deref(), Some("JAN"));
+    /// assert_eq!(graph.x_label, "Month");
+    /// ```
+    pub fn create_line_graph_labelled(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        exclude_column: HashSet<usize>,
+    ) -> Result<LineGraph> {
+        self.create_line_graph_labelled_with(
+            axis_labels,
+            label_strat,
+            exclude_row,
+            exclude_column,
+            ScaleOptions::default(),
+        )
+    }
+
+    /// Returns a new line graph with the axis labels determined by
+    /// `axis_labels`, and `options` applied to the y scale.
+    ///
+    /// The lines follow the order of the rows of the sheet, as with
+    /// [`Sheet::create_line_graph_labelled`].
+    pub fn create_line_graph_labelled_with(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        exclude_column: HashSet<usize>,
+        options: ScaleOptions,
+    ) -> Result<LineGraph> {
+        let context = ChartContext::new()
+            .exclude_rows(exclude_row)
+            .exclude_cols(exclude_column)
+            .scale_options(options)
+            .line_axis_labels(axis_labels);
+
+        self.chart(&context)?.line_graph(label_strat)
+    }
+
+    /// Returns a new line graph of the transpose of this sheet, equal to
+    /// the one [`Sheet::create_line_graph_labelled_with`] returns from the
+    /// transposed sheet, but read from this sheet without building the
+    /// transpose.
+    ///
+    /// The transpose has a row for every column after the first, led by the
+    /// label of the column, and a column for every row, labelled by its
+    /// first cell. `label_strat`, `exclude_row` and `exclude_column` refer to
+    /// the rows and columns of the transpose, so there is one line per
+    /// column of this sheet and `LineLabelStrategy::FromCell(0)` labels each
+    /// line by its column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{prelude::*, samples};
+    /// use std::collections::HashSet;
+    ///
+    /// let sheet = samples::air();
+    /// let graph = sheet
+    ///     .create_line_graph_transposed(
+    ///         LineGraphAxisLabelStrategy::Headers { y: "Passengers".into() },
+    ///         LineLabelStrategy::FromCell(0),
+    ///         RowSelector::default(),
+    ///         HashSet::new(),
+    ///         ScaleOptions::default(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(graph.lines.len(), 3);
+    /// assert_eq!(graph.lines[0].label.as_deref(), Some("1958"));
+    /// assert_eq!(graph.lines[0].points.len(), 12);
+    /// ```
+    pub fn create_line_graph_transposed(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
         exclude_column: HashSet<usize>,
+        options: ScaleOptions,
     ) -> Result<LineGraph> {
         self.validate()?;
-        let scale_kind = self.validate_to_line_graph(&label_strat)?;
 
-        if self.is_empty() {
-            return Err(Error::ConversionError(
-                "Cannot convert an empty sheet".into(),
-            ));
-        }
+        Transposed::new(self).line_graph(
+            axis_labels,
+            label_strat,
+            &exclude_row.into(),
+            &exclude_column,
+            &options,
+        )
+    }
 
-        let x_values: Vec<Data> = self
-            .headers
-            .iter()
-            .map(|hdr| Data::Text(hdr.label.clone()))
-            .collect();
+    /// Returns the positions of every column, in order.
+    fn columns(&self) -> Vec<usize> {
+        (0..self.headers.len()).collect()
+    }
 
-        let lines: Vec<Line> = self
-            .iter_rows()
-            .enumerate()
-            .filter(|(idx, _)| !exclude_row.contains(idx))
-            .map(|(_, row)| row)
-            .enumerate()
-            .map(|(idx, rw)| rw.create_line(&label_strat, &x_values, &exclude_column, idx))
-            .collect();
+    /// Returns a new line graph whose points follow the order of `columns`,
+    /// the positions of the columns to chart.
+    pub(super) fn line_graph_labelled(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: &RowSelector,
+        exclude_column: &HashSet<usize>,
+        options: &ScaleOptions,
+        columns: &[usize],
+    ) -> Result<LineGraph> {
+        let (x_label, y_label) = line_axis_labels(
+            axis_labels,
+            &label_strat,
+            &self.headers,
+            exclude_column,
+            columns,
+        );
+
+        self.line_graph(
+            x_label,
+            y_label,
+            label_strat,
+            exclude_row,
+            exclude_column,
+            options,
+            columns,
+        )
+    }
 
-        let y_scale = {
-            let values = lines
-                .iter()
-                .flat_map(|ln| ln.points.iter().map(|pnt| pnt.y.clone()));
+    #[allow(clippy::too_many_arguments)]
+    fn line_graph(
+        &self,
+        x_label: Option<String>,
+        y_label: Option<String>,
+        label_strat: LineLabelStrategy,
+        exclude_row: &RowSelector,
+        exclude_column: &HashSet<usize>,
+        options: &ScaleOptions,
+        columns: &[usize],
+    ) -> Result<LineGraph> {
+        let _span = trace_span!(
+            "create_line_graph",
+            rows = self.height(),
+            cols = self.width()
+        );
 
-            Scale::new(values, scale_kind)
-        };
+        let scale_kind = self.validate_to_line_graph(&label_strat, exclude_column)?;
 
-        let x_scale = match label_strat {
-            LineLabelStrategy::FromCell(id) => {
-                let values = x_values.into_iter().enumerate().filter_map(|(idx, lbl)| {
-                    if idx != id && !exclude_column.contains(&idx) {
-                        Some(lbl)
-                    } else {
-                        None
-                    }
-                });
-                Scale::new(values, ScaleKind::Categorical)
-            }
-            _ => {
-                let values = x_values.into_iter().enumerate().filter_map(|(idx, lbl)| {
-                    if !exclude_column.contains(&idx) {
-                        Some(lbl)
-                    } else {
-                        None
-                    }
-                });
-                Scale::new(values, ScaleKind::Categorical)
-            }
-        };
+        // The charted columns and their x values are the same for every row
+        let line_columns: Vec<(usize, Data)> = distinct_labels(
+            columns
+                .iter()
+                .filter(|col| is_line_column(&label_strat, exclude_column, **col))
+                .filter_map(|col| Some((*col, self.headers.get(*col)?.label.as_str()))),
+        )
+        .into_iter()
+        .map(|(col, label)| (col, Data::Text(label)))
+        .collect();
+
+        let rows = self
+            .iter_rows()
+            .enumerate()
+            .filter(|(idx, row)| !exclude_row.selects(*idx, row));
+
+        let policy = options.get_missing();
+        let mut lines = Vec::new();
+        for (idx, row) in rows {
+            let line = row.create_line(&line_columns, &self.headers, policy)?;
+            let label = match &label_strat {
+                LineLabelStrategy::None => None,
+                LineLabelStrategy::Provided(labels) => labels.get(idx).cloned(),
+                LineLabelStrategy::FromCell(label_col)
+                | LineLabelStrategy::FromCellWith(label_col, _) => row
+                    .cells
+                    .get(*label_col)
+                    .map(|cell| label_strat.cell_label(&cell.data)),
+            };
 
-        let lg = LineGraph::new(lines, x_label, y_label, x_scale, y_scale)
-            .map_err(Error::LineGraphError)?;
+            lines.push(match label {
+                Some(label) => line.label(label),
+                None => line,
+            });
+        }
 
-        Ok(lg)
+        let x_values = line_columns.into_iter().map(|(_, x)| x);
+        finish_line_graph(lines, x_values, x_label, y_label, scale_kind, options)
     }
 
+    /// Returns a new bar chart of the values in `y_col` against those in
+    /// `x_col`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{prelude::*, samples};
+    ///
+    /// let chart = samples::air()
+    ///     .create_bar_chart(
+    ///         0,
+    ///         1,
+    ///         BarChartBarLabels::FromColumn(0),
+    ///         BarChartAxisLabelStrategy::Headers,
+    ///         RowSelector::default(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chart.bars.len(), 12);
+    /// assert_eq!(chart.x_label.as_deref(), Some("Month"));
+    /// assert_eq!(chart.y_label.as_deref(), Some("1958"));
+    /// ```
     pub fn create_bar_chart(
         self,
         x_col: usize,
         y_col: usize,
         bar_label: BarChartBarLabels,
         axis_labels: BarChartAxisLabelStrategy,
-        exclude_row: HashSet<usize>,
+        exclude_row: impl Into<RowSelector>,
     ) -> Result<BarChart> {
-        let (x_kind, y_kind) = self.validate_to_barchart(x_col, y_col, &bar_label)?;
+        self.create_bar_chart_with(
+            x_col,
+            y_col,
+            bar_label,
+            axis_labels,
+            exclude_row,
+            ScaleOptions::default(),
+        )
+    }
 
-        if self.is_empty() {
-            return Err(Error::ConversionError(
-                "Cannot convert an empty sheet".into(),
-            ));
+    /// Returns a new bar chart of the values in `y_col` against those in
+    /// `x_col`, with each bar weighted by the value of its row in
+    /// `weight_col`. Bars of rows with an empty weight have no weight.
+    ///
+    /// Returns an error if `weight_col` is out of range or not a numeric
+    /// column. See [`Sheet::create_bar_chart`].
+    pub fn create_bar_chart_weighted(
+        &self,
+        x_col: usize,
+        y_col: usize,
+        weight_col: usize,
+        bar_label: BarChartBarLabels,
+        axis_labels: BarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+    ) -> Result<BarChart> {
+        match self.column_kind(weight_col) {
+            Some(ColumnType::Integer | ColumnType::Number | ColumnType::Float) => {}
+            Some(kind) => {
+                return Err(Error::InvalidColumnType(format!(
+                    "Expected a numeric weight column, but had {} column",
+                    kind
+                )))
+            }
+            None => {
+                return Err(Error::InvalidColumnLength(
+                    "Tried to access out of range column".to_string(),
+                ))
+            }
         }
 
-        let x_values = self
+        let exclude_row = exclude_row.into();
+        let weights: Vec<Data> = self
             .rows
             .iter()
             .enumerate()
-            .filter(|(idx, _)| !exclude_row.contains(idx))
+            .filter(|(idx, row)| !exclude_row.selects(*idx, row))
             .map(|(_, row)| {
                 row.cells
-                    .get(x_col)
+                    .get(weight_col)
                     .expect("Bar conversion: All Rows should have the same length")
                     .data
                     .clone()
-            });
+            })
+            .collect();
+
+        let mut chart =
+            self.clone()
+                .create_bar_chart(x_col, y_col, bar_label, axis_labels, exclude_row)?;
+
+        for (bar, weight) in chart.bars.iter_mut().zip(weights) {
+            bar.weight = Some(weight).filter(|weight| *weight != Data::None);
+        }
+
+        Ok(chart)
+    }
+
+    /// Returns a new bar chart, with `options` applied to the y scale.
+    ///
+    /// Columns are excluded with [`ChartContext::exclude_cols`] and
+    /// [`Sheet::chart`].
+    ///
+    /// A boolean y column needs a [`BoolMode`]. With [`BoolMode::CountTrue`],
+    /// bars sharing an x value are merged into a single bar, keeping the
+    /// label of the first, whose value is the number of `true` values.
+    pub fn create_bar_chart_with(
+        self,
+        x_col: usize,
+        y_col: usize,
+        bar_label: BarChartBarLabels,
+        axis_labels: BarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        options: ScaleOptions,
+    ) -> Result<BarChart> {
+        let context = ChartContext::new()
+            .exclude_rows(exclude_row)
+            .scale_options(options)
+            .bar_axis_labels(axis_labels);
+
+        self.chart(&context)?.bar_chart(x_col, y_col, bar_label)
+    }
+
+    /// Returns a new bar chart from a sheet whose charted boolean columns
+    /// have already been converted.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn bar_chart(
+        &self,
+        x_col: usize,
+        y_col: usize,
+        bar_label: BarChartBarLabels,
+        axis_labels: BarChartAxisLabelStrategy,
+        exclude_row: &RowSelector,
+        exclude_column: &HashSet<usize>,
+        options: &ScaleOptions,
+        count_true: bool,
+    ) -> Result<BarChart> {
+        let (x_kind, y_kind) =
+            self.validate_to_barchart(x_col, y_col, &bar_label, exclude_column)?;
 
-        let y_values = self
+        let policy = options.get_missing();
+        let rows = self
             .rows
             .iter()
             .enumerate()
-            .filter(|(idx, _)| !exclude_row.contains(idx))
-            .map(|(_, row)| {
+            .filter(|(idx, row)| !exclude_row.selects(*idx, row));
+
+        let mut bars = Vec::new();
+        for (idx, row) in rows {
+            let cell = |col: usize| {
                 row.cells
-                    .get(y_col)
+                    .get(col)
                     .expect("Bar conversion: All Rows should have the same length")
+            };
+
+            let x = row.charted(cell(x_col), &self.headers, x_col, policy)?;
+            let y = row.charted(cell(y_col), &self.headers, y_col, policy)?;
+            let (Some(x), Some(y)) = (x, y) else {
+                continue;
+            };
+
+            let label = match &bar_label {
+                BarChartBarLabels::Provided(labels) => {
+                    labels.get(idx).filter(|label| !label.is_empty()).cloned()
+                }
+                BarChartBarLabels::FromColumn(ind) | BarChartBarLabels::FromColumnWith(ind, _) => {
+                    Some(bar_label.cell_label(&cell(*ind).data))
+                }
+                BarChartBarLabels::None => None,
+            };
+
+            let point = Point::new(x, y);
+            bars.push(match label {
+                Some(label) => Bar::new(label, point),
+                None => Bar::from_point(point),
+            });
+        }
+
+        if count_true {
+            bars = count_bars(bars);
+        }
+
+        if let Some(top_n) = options.get_top_n() {
+            if !matches!(
+                y_kind,
+                ScaleKind::Integer | ScaleKind::Number | ScaleKind::Float
+            ) {
+                return Err(Error::ConversionError(
+                    "Cannot bucket bars with non-numeric values".into(),
+                ));
+            }
+
+            bars = top_n.apply(bars)?;
+        }
+
+        let bounds = clamp_bounds(bars.iter().map(|bar| &bar.point.y), options)?;
+
+        let x_kind = match options.get_top_n() {
+            Some(_) => ScaleKind::Categorical,
+            None => x_kind,
+        };
+
+        // The meta of the column gives the y scale without reading the
+        // values again, if every value is charted as it is.
+        let y_scale = (bars.len() == self.height()
+            && !count_true
+            && options.get_top_n().is_none()
+            && bounds.is_none())
+        .then(|| self.col_meta(y_col))
+        .flatten()
+        .filter(|meta| meta.null_count() == 0)
+        .and_then(|meta| {
+            let (min, max) = meta.bounds()?;
+            Scale::from_summary(y_kind, min, max, meta.exact_distinct()?)
+        });
+
+        let mut barchart = match y_scale {
+            Some(y_scale) => {
+                let mut x_scale = ScaleBuilder::new(x_kind);
+                x_scale.extend(bars.iter().map(|bar| bar.point.x.clone()));
+                BarChart::new(bars, x_scale.build(), y_scale)?
+            }
+            None => BarChart::with_kinds(bars, x_kind, y_kind)?,
+        };
+        assert_charted(barchart.is_empty(), options)?;
+        order_categories(&mut barchart.x_scale, options);
+        if let Some(bounds) = bounds {
+            let values = barchart.bars.iter().map(|bar| &bar.point.y);
+            barchart.y_view = Some(clamped_scale(values, barchart.y_scale.kind(), bounds));
+        }
+        for scale in std::iter::once(&mut barchart.y_scale).chain(barchart.y_view.as_mut()) {
+            if options.get_include_zero() != Some(false) {
+                scale.include_zero();
+            }
+            if options.is_descending() {
+                scale.invert();
+            }
+        }
+
+        match axis_labels {
+            BarChartAxisLabelStrategy::Headers => {
+                let x = self
+                    .headers
+                    .get(x_col)
+                    .expect("Bar conversion: Invalid header access")
+                    .axis_label();
+                let y = self
+                    .headers
+                    .get(y_col)
+                    .expect("Bar conversion: Invalid header access")
+                    .axis_label();
+
+                Ok(barchart.x_label(x).y_label(y))
+            }
+            BarChartAxisLabelStrategy::Provided { x, y } => Ok(barchart.x_label(x).y_label(y)),
+            BarChartAxisLabelStrategy::None => Ok(barchart),
+        }
+    }
+
+    /// Returns a new box plot summarising the numeric column at `value_col`.
+    ///
+    /// When `group_col` is provided, one box is created for each distinct
+    /// value in that column, in the order first seen, and labelled with it.
+    /// Otherwise, a single box summarises the whole column. Empty cells in
+    /// `value_col` are ignored. See [`BoxItem::new`] for how small groups are
+    /// handled.
+    pub fn create_box_plot(&self, group_col: Option<usize>, value_col: usize) -> Result<BoxPlot> {
+        let _span = trace_span!("create_box_plot", rows = self.height(), cols = self.width());
+
+        self.numeric_values(value_col)?;
+
+        if let Some(col) = group_col {
+            self.header(col).ok_or(Error::ConversionError(
+                "Box plot: group column out of range".into(),
+            ))?;
+        }
+
+        let mut groups: Vec<(Option<String>, Vec<f64>)> = Vec::new();
+
+        for row in self.iter_rows() {
+            let value = match row.cells.get(value_col).and_then(|cell| cell.data.as_f64()) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let label = group_col.map(|col| {
+                row.cells
+                    .get(col)
+                    .expect("Box plot conversion: All Rows should have the same length")
                     .data
-                    .clone()
+                    .to_string()
             });
 
-        let points = x_values
-            .into_iter()
-            .zip(y_values)
-            .map(|(x, y)| Point::new(x, y));
+            match groups.iter_mut().find(|(lbl, _)| lbl == &label) {
+                Some((_, values)) => values.push(value),
+                None => groups.push((label, vec![value])),
+            }
+        }
+
+        if groups.is_empty() {
+            return Err(Error::ConversionError(
+                "Cannot convert an empty column".into(),
+            ));
+        }
+
+        let items = groups
+            .into_iter()
+            .filter_map(|(label, values)| {
+                let item = BoxItem::new(values)?;
+                Some(match label {
+                    Some(label) => item.label(label),
+                    None => item,
+                })
+            })
+            .collect::<Vec<BoxItem>>();
+
+        let y_scale = {
+            let values = items
+                .iter()
+                .flat_map(|item| [item.min.clone(), item.max.clone()])
+                .chain(items.iter().flat_map(|item| item.outliers.clone()));
+
+            Scale::new(values, ScaleKind::Float)
+        };
+
+        let y = self
+            .header(value_col)
+            .expect("Box plot conversion: Invalid header access")
+            .axis_label();
+        let box_plot = BoxPlot::new(items, y_scale)?.y_label(y);
+
+        match group_col.and_then(|col| self.header(col)) {
+            Some(header) => Ok(box_plot.x_label(header.axis_label())),
+            None => Ok(box_plot),
+        }
+    }
+
+    /// Returns a new heatmap with a row for each row of the sheet, labelled
+    /// from `label_col`, and a column for each of `value_cols`.
+    ///
+    /// All value columns must share a numeric type. Empty cells are kept as
+    /// [`Data::None`].
+    pub fn create_heatmap(&self, label_col: usize, value_cols: &[usize]) -> Result<HeatMap> {
+        let _span = trace_span!("create_heatmap", rows = self.height(), cols = self.width());
+
+        self.header(label_col).ok_or(Error::ConversionError(
+            "Heatmap: label column out of range".into(),
+        ))?;
+
+        let mut kind = None;
+        for col in value_cols {
+            let header = self.header(*col).ok_or(Error::ConversionError(
+                "Heatmap: value column out of range".into(),
+            ))?;
+
+            if !matches!(
+                header.kind,
+                ColumnType::Integer | ColumnType::Number | ColumnType::Float
+            ) {
+                return Err(Error::ConversionError(format!(
+                    "Heatmap: Cannot use {} column as values",
+                    header.kind
+                )));
+            }
+
+            match kind {
+                Some(prev) if prev != header.kind => {
+                    return Err(Error::ConversionError(
+                        "Heatmap: Cannot use different column types as values".into(),
+                    ))
+                }
+                _ => kind = Some(header.kind),
+            }
+        }
+
+        let row_labels = self
+            .iter_rows()
+            .map(|row| {
+                row.cells
+                    .get(label_col)
+                    .expect("Heatmap conversion: All Rows should have the same length")
+                    .data
+                    .to_string()
+            })
+            .collect();
+
+        let col_labels = value_cols
+            .iter()
+            .map(|col| self.headers[*col].label.clone())
+            .collect();
 
-        let labels: Vec<Option<String>> = match bar_label {
-            BarChartBarLabels::Provided(labels) => labels
-                .into_iter()
-                .map(|label| if label.is_empty() { None } else { Some(label) })
-                .collect(),
-            BarChartBarLabels::FromColumn(ind) => self
-                .rows
-                .iter()
-                .enumerate()
-                .filter(|(idx, _)| !exclude_row.contains(idx))
-                .map(|(_, row)| {
-                    let label = row
-                        .cells
-                        .get(ind)
-                        .expect("Bar conversion: All Rows should have the same length")
+        let values = self
+            .iter_rows()
+            .flat_map(|row| {
+                value_cols.iter().map(move |col| {
+                    row.cells
+                        .get(*col)
+                        .expect("Heatmap conversion: All Rows should have the same length")
                         .data
-                        .to_string();
-                    Some(label)
+                        .clone()
                 })
-                .collect(),
-            BarChartBarLabels::None => vec![None; self.rows.len()],
+            })
+            .collect::<Vec<Data>>();
+
+        let scale = {
+            let values = values.iter().filter(|value| **value != Data::None).cloned();
+
+            Scale::new(values, kind.unwrap_or(ColumnType::Integer).into())
         };
 
-        let labels = Self::balance_vector(labels, self.rows.len());
+        let heatmap = HeatMap::new(row_labels, col_labels, values, scale)?;
 
-        let bars = labels
-            .into_iter()
-            .zip(points)
-            .map(|(label, point)| match label {
-                Some(label) => Bar::new(label, point),
-                None => Bar::from_point(point),
+        Ok(heatmap)
+    }
+
+    /// Returns a heatmap of the Pearson correlations between each pair of the
+    /// numeric columns `cols`.
+    ///
+    /// Each pair is correlated over the rows where neither cell is empty.
+    /// Correlations are [`Data::Float`], or [`Data::None`] when undefined,
+    /// such as for a constant column.
+    pub fn correlation_matrix(&self, cols: &[usize]) -> Result<HeatMap> {
+        let _span = trace_span!(
+            "correlation_matrix",
+            rows = self.height(),
+            cols = self.width()
+        );
+
+        let columns = cols
+            .iter()
+            .map(|col| {
+                self.numeric_values(*col)?;
+
+                Ok(self
+                    .iter_rows()
+                    .map(|row| row.cells.get(*col).and_then(|cell| cell.data.as_f64()))
+                    .collect::<Vec<Option<f64>>>())
             })
-            .collect::<Vec<Bar>>();
+            .collect::<Result<Vec<_>>>()?;
 
-        let x_scale = {
-            let values = bars.iter().map(|bar| bar.point.x.clone());
+        let values = columns
+            .iter()
+            .flat_map(|xs| columns.iter().map(move |ys| pearson(xs, ys)))
+            .map(|corr| match corr {
+                Some(corr) => Data::Float(corr as f32),
+                None => Data::None,
+            })
+            .collect::<Vec<Data>>();
 
-            Scale::new(values, x_kind)
-        };
+        let labels = cols
+            .iter()
+            .map(|col| self.headers[*col].label.clone())
+            .collect::<Vec<String>>();
 
-        let y_scale = {
-            let values = bars.iter().map(|bar| bar.point.y.clone());
+        let scale = {
+            let values = values.iter().filter(|value| **value != Data::None).cloned();
 
-            Scale::new(values, y_kind)
+            Scale::new(values, ScaleKind::Float)
         };
 
-        let barchart = BarChart::new(bars, x_scale, y_scale)?;
-
-        match axis_labels {
-            BarChartAxisLabelStrategy::Headers => {
-                let x = self
-                    .headers
-                    .get(x_col)
-                    .expect("Bar conversion: Invalid header access")
-                    .label
-                    .clone();
-                let y = self
-                    .headers
-                    .get(y_col)
-                    .expect("Bar conversion: Invalid header access")
-                    .label
-                    .clone();
+        let heatmap = HeatMap::new(labels.clone(), labels, values, scale)?;
 
-                Ok(barchart.x_label(x).y_label(y))
-            }
-            BarChartAxisLabelStrategy::Provided { x, y } => Ok(barchart.x_label(x).y_label(y)),
-            BarChartAxisLabelStrategy::None => Ok(barchart),
-        }
+        Ok(heatmap)
     }
 
+    /// Returns a new stacked bar chart created from this csv struct
+    ///
+    /// exclude_row: The rows to exclude in this transformation. See
+    /// [`RowSelector`] for how exclusions interact with sorting
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{prelude::*, samples};
+    ///
+    /// let chart = samples::stacked()
+    ///     .create_stacked_bar_chart(
+    ///         0,
+    ///         [1, 2, 3, 4],
+    ///         StackedBarChartAxisLabelStrategy::Header("Sales".into()),
+    ///         RowSelector::default(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chart.bars.len(), 7);
+    /// assert_eq!(chart.labels.len(), 4);
+    /// assert_eq!(chart.x_axis.as_deref(), Some("Day of Week"));
+    /// ```
     pub fn create_stacked_bar_chart(
         self,
         x_col: usize,
         cols: impl IntoIterator<Item = usize>,
         axis_labels: StackedBarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
     ) -> Result<StackedBarChart> {
-        let cols = cols
-            .into_iter()
-            .collect::<HashSet<usize>>()
-            .into_iter()
-            .collect::<Vec<usize>>();
+        self.create_stacked_bar_chart_with(
+            x_col,
+            cols,
+            axis_labels,
+            exclude_row,
+            ScaleOptions::default(),
+        )
+    }
 
-        let (acc_labels, y_kind) = self.validate_to_stacked_bar_chart(x_col, &cols)?;
+    /// Returns a new stacked bar chart, with `options` applied to the y
+    /// scale. Only the [`BoolMode`], direction and [`MissingPolicy`] of
+    /// `options` are used.
+    ///
+    /// Columns are excluded with [`ChartContext::exclude_cols`] and
+    /// [`Sheet::chart`].
+    ///
+    /// Boolean columns are accumulated with [`BoolMode::AsBinary`]. Other
+    /// modes are rejected.
+    pub fn create_stacked_bar_chart_with(
+        self,
+        x_col: usize,
+        cols: impl IntoIterator<Item = usize>,
+        axis_labels: StackedBarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        options: ScaleOptions,
+    ) -> Result<StackedBarChart> {
+        let context = ChartContext::new()
+            .exclude_rows(exclude_row)
+            .scale_options(options)
+            .stacked_axis_labels(axis_labels);
 
-        if self.is_empty() {
-            return Err(Error::ConversionError(
-                "Cannot convert an empty sheet".into(),
-            ));
-        }
+        self.chart(&context)?.stacked_bar_chart(x_col, cols)
+    }
 
-        let x_values = self.rows.iter().map(|row| {
-            row.cells
-                .get(x_col)
-                .cloned()
-                .expect("Stacked Bar Chart conversion: Validations failed")
-                .data
-        });
+    /// Returns a new stacked bar chart from a sheet whose charted boolean
+    /// columns have already been converted.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn stacked_bar_chart(
+        &self,
+        x_col: usize,
+        cols: Vec<usize>,
+        axis_labels: StackedBarChartAxisLabelStrategy,
+        exclude_row: &RowSelector,
+        exclude_column: &HashSet<usize>,
+        options: &ScaleOptions,
+        transform: &LabelTransform,
+    ) -> Result<StackedBarChart> {
+        let (acc_labels, y_kind) =
+            self.validate_to_stacked_bar_chart(x_col, &cols, exclude_column)?;
+        let acc_labels = acc_labels
+            .iter()
+            .map(|label| transform.apply(label))
+            .collect::<Vec<String>>();
+
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(idx, row)| !exclude_row.selects(*idx, row))
+            .collect::<Vec<(usize, &Row)>>();
+
+        let mut x_values = Vec::with_capacity(rows.len());
         let mut y_values = Vec::default();
         let mut bars = Vec::default();
 
-        for row in self.rows.iter() {
-            let [pos, neg] = row.create_stacked_bar_chart(x_col, &cols, &acc_labels)?;
+        let policy = options.get_missing();
+        for (idx, row) in rows.iter() {
+            let Some([pos, neg]) =
+                row.create_stacked_bar_chart(x_col, &cols, &acc_labels, &self.headers, policy)?
+            else {
+                continue;
+            };
+            x_values.push(pos.0.point.x.clone());
 
             if pos.1 != Data::None {
-                bars.push(pos.0);
+                bars.push(pos.0.group(*idx));
                 y_values.push(pos.1);
             }
 
             if neg.1 != Data::None {
-                bars.push(neg.0);
+                bars.push(neg.0.group(*idx));
                 y_values.push(neg.1);
             }
         }
@@ -1252,14 +3962,23 @@ impl Sheet {
                 .expect("Stacked Bar Chart conversion: Validations failed")
                 .kind;
 
-            Scale::new(x_values, kind.into())
+            let mut scale = Scale::new(x_values, kind.into());
+            order_categories(&mut scale, options);
+            scale
         };
 
-        let y_scale = Scale::new(y_values, y_kind);
+        let mut y_scale = Scale::new(y_values, y_kind);
+        if options.get_include_zero() != Some(false) {
+            y_scale.include_zero();
+        }
+        if options.is_descending() {
+            y_scale.invert();
+        }
 
         let acc_labels = acc_labels.into_iter().collect();
 
         let stacked = StackedBarChart::new(bars, x_scale, y_scale, acc_labels)?;
+        assert_charted(stacked.is_empty(), options)?;
 
         match axis_labels {
             StackedBarChartAxisLabelStrategy::None => Ok(stacked),
@@ -1267,17 +3986,603 @@ impl Sheet {
                 let x_label = self
                     .headers
                     .get(x_col)
-                    .cloned()
-                    .map(|header| header.label)
-                    .unwrap_or(String::default());
+                    .map(ColumnHeader::axis_label)
+                    .unwrap_or_default();
+                let y_label = match self.shared_unit(cols) {
+                    Some(unit) => unit.annotate(&y_label),
+                    None => y_label,
+                };
 
                 Ok(stacked.x_axis(x_label).y_axis(y_label))
             }
             StackedBarChartAxisLabelStrategy::Provided { x, y } => Ok(stacked.x_axis(x).y_axis(y)),
         }
     }
+
+    /// Returns a new stacked bar chart from long-format data, where each row
+    /// holds an x value, a section and the value of that section.
+    ///
+    /// Rows are grouped by x, with the distinct values of `section_col`, in
+    /// order of first appearance, used as the sections. Values of duplicate
+    /// x and section pairs are summed, while missing pairs are treated as
+    /// zero. Rows with an empty value are ignored. The result is the same as
+    /// [`Sheet::create_stacked_bar_chart`] on the equivalent wide data.
+    pub fn create_stacked_bar_chart_long(
+        &self,
+        x_col: usize,
+        section_col: usize,
+        value_col: usize,
+        axis_labels: StackedBarChartAxisLabelStrategy,
+    ) -> Result<StackedBarChart> {
+        let _span = trace_span!(
+            "create_stacked_bar_chart_long",
+            rows = self.height(),
+            cols = self.width()
+        );
+
+        let x_header = self.header(x_col).cloned().ok_or(Error::ConversionError(
+            "Stacked Bar chart: x column out of range".into(),
+        ))?;
+        self.header(section_col).ok_or(Error::ConversionError(
+            "Stacked Bar chart: section column out of range".into(),
+        ))?;
+        let value_kind = self.column_kind(value_col).ok_or(Error::ConversionError(
+            "Stacked Bar chart: value column out of range".into(),
+        ))?;
+
+        let zero = match value_kind {
+            ColumnType::Integer => Data::Integer(0),
+            ColumnType::Number => Data::Number(0),
+            ColumnType::Float => Data::Float(0.0),
+            _ => {
+                return Err(Error::ConversionError(
+                    "Stacked Bar Chart Cannot accumulate column type".into(),
+                ))
+            }
+        };
+
+        self.assert_convertible()?;
+
+        let overflow = |id: usize| {
+            Error::ConversionError(format!(
+                "Stacked Bar Chart: sum of values overflows in row id {}",
+                id
+            ))
+        };
+
+        let mut xs: Vec<Data> = Vec::new();
+        let mut x_positions: HashMap<Data, usize> = HashMap::new();
+        let mut sections: Vec<String> = Vec::new();
+        let mut section_positions: HashMap<String, usize> = HashMap::new();
+        let mut values: HashMap<(usize, usize), Data> = HashMap::new();
+
+        for row in self.iter_rows() {
+            let get = |col: usize| {
+                row.cells
+                    .get(col)
+                    .expect("Stacked Bar Chart conversion: All Rows should have the same length")
+                    .data
+                    .clone()
+            };
+
+            let value = get(value_col);
+            if value == Data::None {
+                continue;
+            }
+
+            let x = get(x_col);
+            let x = *x_positions.entry(x).or_insert_with_key(|x| {
+                xs.push(x.clone());
+                xs.len() - 1
+            });
+
+            let section = get(section_col).to_string();
+            let section = *section_positions
+                .entry(section)
+                .or_insert_with_key(|section| {
+                    sections.push(section.clone());
+                    sections.len() - 1
+                });
+
+            let sum = match (values.remove(&(x, section)), value) {
+                (None, value) => value,
+                (Some(Data::Integer(a)), Data::Integer(b)) => {
+                    Data::Integer(a.checked_add(b).ok_or_else(|| overflow(row.id))?)
+                }
+                (Some(Data::Number(a)), Data::Number(b)) => {
+                    Data::Number(a.checked_add(b).ok_or_else(|| overflow(row.id))?)
+                }
+                (Some(Data::Float(a)), Data::Float(b)) => Data::Float(a + b),
+                (Some(_), value) => {
+                    return Err(Error::InvalidColumnType(format!(
+                        "Expected cell of {} type, but had {:?} in row id {}",
+                        value_kind, value, row.id
+                    )))
+                }
+            };
+            values.insert((x, section), sum);
+        }
+
+        let width = sections.len();
+
+        let rows = xs
+            .into_iter()
+            .enumerate()
+            .map(|(x_idx, x)| {
+                let mut row = Vec::with_capacity(width + 1);
+                row.push(x);
+                row.extend((0..width).map(|section| {
+                    values
+                        .remove(&(x_idx, section))
+                        .unwrap_or_else(|| zero.clone())
+                }));
+                row
+            })
+            .collect::<Vec<Vec<Data>>>();
+
+        let value_unit = self
+            .header(value_col)
+            .and_then(|header| header.unit.clone());
+
+        let mut headers = Vec::with_capacity(width + 1);
+        headers.push(x_header);
+        headers.extend(sections.into_iter().map(|section| ColumnHeader {
+            unit: value_unit.clone(),
+            ..ColumnHeader::new(section, value_kind)
+        }));
+
+        Sheet::from_rows(rows, headers, 0)?.create_stacked_bar_chart(
+            0,
+            1..=width,
+            axis_labels,
+            RowSelector::default(),
+        )
+    }
+}
+
+/// Returns the `q` quantile of the sorted `values`, interpolating linearly
+/// between the closest values.
+fn quantile(values: &[f64], q: f64) -> Result<f64> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(Error::InvalidStatistic(format!(
+            "Quantile {} is not within 0 and 1",
+            q
+        )));
+    }
+
+    Ok(models::quantile(values, q))
+}
+
+/// Returns the Pearson correlation between `xs` and `ys`, ignoring pairs with
+/// a missing value. Returns [`None`] if the correlation is undefined.
+fn pearson(xs: &[Option<f64>], ys: &[Option<f64>]) -> Option<f64> {
+    let pairs = xs
+        .iter()
+        .zip(ys.iter())
+        .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+        .collect::<Vec<(f64, f64)>>();
+
+    if pairs.is_empty() {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let x_mean = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let y_mean = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let (mut cov, mut x_var, mut y_var) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        cov += (x - x_mean) * (y - y_mean);
+        x_var += (x - x_mean) * (x - x_mean);
+        y_var += (y - y_mean) * (y - y_mean);
+    }
+
+    if x_var == 0.0 || y_var == 0.0 {
+        return None;
+    }
+
+    Some(cov / (x_var * y_var).sqrt())
+}
+
+/// Rejects a chart with nothing charted, as when every row is excluded,
+/// unless `options` allow empty charts.
+fn assert_charted(is_empty: bool, options: &ScaleOptions) -> Result<()> {
+    if is_empty && !options.allows_empty() {
+        return Err(Error::NoRows);
+    }
+
+    Ok(())
+}
+
+/// Orders the category scale `scale` by the categorical order of `options`,
+/// if any and if it orders every category.
+fn order_categories(scale: &mut Scale, options: &ScaleOptions) {
+    let order = options.get_categorical_order();
+    if order != &CategoricalOrder::None && !scale.set_order(order.clone()) {
+        trace_debug!(
+            ?order,
+            "categories left unordered, as not all are part of the order"
+        );
+    }
+}
+
+/// Returns the bounds the scale should be clamped to given `options`, if any.
+fn clamp_bounds<'a>(
+    values: impl Iterator<Item = &'a Data>,
+    options: &ScaleOptions,
+) -> Result<Option<(f64, f64)>> {
+    let (lower, upper) = match options.get_clamp() {
+        Some(clamp) => clamp,
+        None => return Ok(None),
+    };
+
+    if lower > upper {
+        return Err(Error::InvalidStatistic(format!(
+            "Lower percentile {} is greater than upper percentile {}",
+            lower, upper
+        )));
+    }
+
+    let mut values = values.filter_map(Data::as_f64).collect::<Vec<f64>>();
+
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    values.sort_by(|x, y| x.total_cmp(y));
+
+    let lower = quantile(&values, lower / 100.0)?;
+    let upper = quantile(&values, upper / 100.0)?;
+
+    Ok(Some((lower, upper)))
+}
+
+/// Returns a scale of `kind` over `values` clamped to `bounds`, to be shown in
+/// place of the full scale. The values themselves are left as they are.
+fn clamped_scale<'a>(
+    values: impl Iterator<Item = &'a Data>,
+    kind: ScaleKind,
+    (lower, upper): (f64, f64),
+) -> Scale {
+    Scale::new(values.map(|value| value.clone().clamp(lower, upper)), kind)
+}
+
+/// Merges bars sharing an x value into the first of them, summing their
+/// integer values. Empty values count as zero.
+fn count_bars(bars: Vec<Bar>) -> Vec<Bar> {
+    let mut positions: HashMap<Data, usize> = HashMap::new();
+    let mut counted: Vec<Bar> = Vec::new();
+
+    for bar in bars {
+        let count = match bar.point.y {
+            Data::Integer(count) => count,
+            _ => 0,
+        };
+
+        match positions.get(&bar.point.x) {
+            Some(pos) => {
+                if let Data::Integer(total) = &mut counted[*pos].point.y {
+                    *total += count;
+                }
+            }
+            None => {
+                positions.insert(bar.point.x.clone(), counted.len());
+                counted.push(Bar {
+                    label: bar.label,
+                    point: Point::new(bar.point.x, Data::Integer(count)),
+                    weight: bar.weight,
+                });
+            }
+        }
+    }
+
+    counted
+}
+
+/// Returns an error if `col`, used as the `role` column of a chart, is
+/// excluded.
+fn assert_included(exclude: &HashSet<usize>, col: usize, role: &str) -> Result<()> {
+    if exclude.contains(&col) {
+        Err(Error::ConversionError(format!(
+            "{} column {} is excluded",
+            role, col
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns true if the column at `col` contributes points to the lines of a
+/// line graph. Label columns and excluded columns do not.
+fn is_line_column(label: &LineLabelStrategy, exclude: &HashSet<usize>, col: usize) -> bool {
+    if label.label_col() == Some(col) {
+        return false;
+    }
+
+    !exclude.contains(&col)
+}
+
+/// Returns the value charted for `data`, in the row with id `row` and the
+/// column at `col`, following `policy`, or [`None`] if it is left out.
+fn charted_value(
+    data: &Data,
+    headers: &[ColumnHeader],
+    row: usize,
+    col: usize,
+    policy: MissingPolicy,
+) -> Result<Option<Data>> {
+    if data != &Data::None {
+        return Ok(Some(data.clone()));
+    }
+
+    match policy {
+        MissingPolicy::Keep => Ok(Some(Data::None)),
+        MissingPolicy::Skip => Ok(None),
+        MissingPolicy::FillZero => {
+            let zero = match headers.get(col).map(|header| header.kind) {
+                Some(ColumnType::Integer) => Data::Integer(0),
+                Some(ColumnType::Number) => Data::Number(0),
+                Some(ColumnType::Float) => Data::Float(0.0),
+                Some(ColumnType::Boolean) => Data::Boolean(false),
+                _ => Data::None,
+            };
+            Ok(Some(zero))
+        }
+        MissingPolicy::Error => Err(Error::MissingValue { row, col }),
+    }
+}
+
+/// Returns the unit shared by every column of `headers` in `cols`, if any.
+fn shared_unit(headers: &[ColumnHeader], cols: impl IntoIterator<Item = usize>) -> Option<&Unit> {
+    let mut units = cols
+        .into_iter()
+        .map(|col| headers.get(col).and_then(|header| header.unit.as_ref()));
+    let first = units.next()??;
+
+    units.all(|unit| unit == Some(first)).then_some(first)
+}
+
+/// Returns the distinct types of the non-empty `values`, in the order first
+/// seen.
+fn distinct_types<'a>(values: impl Iterator<Item = &'a Data>) -> Vec<ColumnType> {
+    let mut types = Vec::new();
+
+    for value in values {
+        let kind = ColumnType::from(value);
+
+        if kind != ColumnType::None && !types.contains(&kind) {
+            types.push(kind);
+        }
+    }
+
+    types
+}
+
+/// Returns an error if the column of `header` is not of a single, known
+/// type. `distinct_types` lists the types of a mixed column.
+fn assert_uniform(
+    header: &ColumnHeader,
+    distinct_types: impl FnOnce() -> Vec<ColumnType>,
+) -> Result<()> {
+    match header.kind {
+        ColumnType::None => Err(Error::ConversionError(format!(
+            "Cannot convert column `{}` of unknown type",
+            header.label
+        ))),
+        ColumnType::Mixed => {
+            let types = distinct_types()
+                .iter()
+                .map(|kind| kind.name().to_string())
+                .collect::<Vec<String>>();
+
+            Err(Error::ConversionError(format!(
+                "Cannot convert column `{}` of mixed types: {}",
+                header.label,
+                types.join(", ")
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the columns of `headers`, over `height` rows, can be charted
+/// as lines labelled by `label_strat`, and returns the kind of their scale.
+/// `distinct_types` lists the types of a mixed column.
+fn validate_line_columns(
+    headers: &[ColumnHeader],
+    height: usize,
+    label_strat: &LineLabelStrategy,
+    exclude: &HashSet<usize>,
+    distinct_types: impl Fn(usize) -> Vec<ColumnType>,
+) -> Result<ScaleKind> {
+    if let Some(idx) = label_strat.label_col() {
+        if idx >= headers.len() {
+            return Err(Error::ConversionError(
+                "Tried to assign invalid column as label".into(),
+            ));
+        }
+
+        assert_included(exclude, idx, "Line graph label")?;
+    }
+
+    if let LineLabelStrategy::Provided(labels) = label_strat {
+        if labels.len() > height {
+            return Err(Error::ConversionError(format!(
+                "Provided {} line labels for {} rows",
+                labels.len(),
+                height
+            )));
+        }
+
+        if labels.len() < height {
+            trace_debug!(
+                labels = labels.len(),
+                rows = height,
+                "rows without a provided line label are left unlabelled"
+            );
+        }
+    }
+
+    // Unknown and mixed type columns
+    headers
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !exclude.contains(idx))
+        .try_for_each(|(idx, header)| assert_uniform(header, || distinct_types(idx)))?;
+
+    let check_uniform_type = |acc: ColumnType, ct: ColumnType| match (&acc, &ct) {
+        (ColumnType::None, _) => Ok(ct),
+        (x, y) => {
+            if x == y {
+                Ok(ct)
+            } else {
+                Err(Error::ConversionError(
+                    "Cannot convert different column types".into(),
+                ))
+            }
+        }
+    };
+
+    // Uniform type columns
+    let kind = headers
+        .iter()
+        .map(|hdr| &hdr.kind)
+        .enumerate()
+        .filter(|(idx, _)| is_line_column(label_strat, exclude, *idx))
+        .try_fold(ColumnType::None, |acc, (_, ct)| {
+            check_uniform_type(acc, *ct)
+        })?;
+
+    Ok(kind.into())
+}
+
+/// Returns the axis labels of a line graph of `columns` given `axis_labels`.
+/// The x axis is labelled by the header of the label column, if any, and the
+/// y axis annotated with the unit shared by the value columns.
+fn line_axis_labels(
+    axis_labels: LineGraphAxisLabelStrategy,
+    label_strat: &LineLabelStrategy,
+    headers: &[ColumnHeader],
+    exclude_column: &HashSet<usize>,
+    columns: &[usize],
+) -> (Option<String>, Option<String>) {
+    match axis_labels {
+        LineGraphAxisLabelStrategy::None => (None, None),
+        LineGraphAxisLabelStrategy::Headers { y } => {
+            let label_col = label_strat.label_col();
+            let x = label_col
+                .and_then(|col| headers.get(col))
+                .map(ColumnHeader::axis_label);
+
+            let value_cols = columns
+                .iter()
+                .copied()
+                .filter(|col| Some(*col) != label_col && !exclude_column.contains(col));
+            let y = match shared_unit(headers, value_cols) {
+                Some(unit) => unit.annotate(&y),
+                None => y,
+            };
+
+            (x, Some(y))
+        }
+        LineGraphAxisLabelStrategy::Provided { x, y } => (Some(x), Some(y)),
+    }
+}
+
+/// Returns a line graph of `lines` against the x values of their columns,
+/// clamping and scaling their y scale following `options`.
+fn finish_line_graph(
+    lines: Vec<Line>,
+    x_values: impl Iterator<Item = Data>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    scale_kind: ScaleKind,
+    options: &ScaleOptions,
+) -> Result<LineGraph> {
+    let values = lines
+        .iter()
+        .flat_map(|ln| ln.points.iter().map(|pnt| pnt.y.clone()));
+    let y_scale = Scale::new(values, scale_kind);
+
+    let mut x_scale = Scale::new(x_values, ScaleKind::Categorical);
+    order_categories(&mut x_scale, options);
+
+    let mut lg =
+        LineGraph::new(lines, x_label, y_label, x_scale, y_scale).map_err(Error::LineGraphError)?;
+    assert_charted(lg.is_empty(), options)?;
+
+    let values = lg
+        .lines
+        .iter()
+        .flat_map(|ln| ln.points.iter().map(|pnt| &pnt.y));
+    if let Some(bounds) = clamp_bounds(values.clone(), options)? {
+        lg.y_view = Some(clamped_scale(values, lg.y_scale.kind(), bounds));
+    }
+    for scale in std::iter::once(&mut lg.y_scale).chain(lg.y_view.as_mut()) {
+        if options.get_include_zero() == Some(true) {
+            scale.include_zero();
+        }
+        if options.is_descending() {
+            scale.invert();
+        }
+    }
+
+    Ok(lg)
+}
+
+/// Returns each column with its label, suffixing every repeat of a label with
+/// its count, such as `Sales (2)`, so that no two columns share one. A
+/// suffixed label which is itself taken is counted further, and empty labels
+/// are suffixed as `(2)`.
+fn distinct_labels<'a>(columns: impl Iterator<Item = (usize, &'a str)>) -> Vec<(usize, String)> {
+    let columns = columns.collect::<Vec<_>>();
+    let mut taken = columns
+        .iter()
+        .map(|(_, label)| (*label).to_owned())
+        .collect::<HashSet<String>>();
+    let mut seen = HashSet::new();
+
+    columns
+        .into_iter()
+        .map(|(col, label)| {
+            if seen.insert(label) {
+                return (col, label.to_owned());
+            }
+
+            let suffix = |count: usize| format!("{label} ({count})").trim_start().to_owned();
+            let mut count = 2;
+            let mut suffixed = suffix(count);
+            while taken.contains(&suffixed) {
+                count += 1;
+                suffixed = suffix(count);
+            }
+            taken.insert(suffixed.clone());
+
+            (col, suffixed)
+        })
+        .collect()
+}
+
+/// Converts a csv error into an [`Error`] carrying the line of the record at
+/// fault, if known.
+#[cfg(feature = "csv")]
+fn located(err: csv::Error) -> Error {
+    let line = err.position().map(|pos| pos.line());
+
+    Error::from(err).at_line(line)
+}
+
+/// Returns how `data` is written as a CSV field with `options`.
+#[cfg(feature = "csv")]
+fn write_field(data: &Data, options: &WriteOptions) -> String {
+    match data {
+        Data::None => options.null_string.clone(),
+        Data::Float(float) => options.format_float(float),
+        data => data.to_string(),
+    }
 }
 
+#[cfg(feature = "csv")]
 impl<P: AsRef<Path>> TryFrom<Config<P>> for Sheet {
     type Error = Error;
 
@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use crate::models::{bar::BarChart, line::LineGraph, stacked_bar::StackedBarChart, LabelTransform};
+
+use super::{
+    error::{Error, Result},
+    utils::*,
+    Sheet,
+};
+
+/// Options shared by the charts built from a [`Sheet`], accumulated once and
+/// reused for every chart. See [`Sheet::chart`].
+///
+/// Each kind of chart has its own axis label strategy, so the strategies of
+/// every kind are kept and each chart uses its own.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::{prelude::*, samples};
+/// use std::collections::HashSet;
+///
+/// let sheet = samples::air();
+/// let context = ChartContext::new()
+///     .exclude_rows(HashSet::from([0, 1]))
+///     .label_transform(LabelTransform::Lowercase);
+/// let charts = sheet.chart(&context).unwrap();
+///
+/// let graph = charts.line_graph(LineLabelStrategy::FromCell(0)).unwrap();
+/// let chart = charts
+///     .bar_chart(0, 1, BarChartBarLabels::FromColumn(0))
+///     .unwrap();
+///
+/// assert_eq!(graph.lines.len(), 10);
+/// assert_eq!(graph.lines[0].label.as_deref(), Some("mar"));
+/// assert_eq!(chart.bars.len(), 10);
+/// ```
+#[derive(Debug, Default)]
+pub struct ChartContext {
+    exclude_row: RowSelector,
+    exclude_column: HashSet<usize>,
+    options: ScaleOptions,
+    label_transform: LabelTransform,
+    line_axis_labels: LineGraphAxisLabelStrategy,
+    bar_axis_labels: BarChartAxisLabelStrategy,
+    stacked_axis_labels: StackedBarChartAxisLabelStrategy,
+}
+
+impl ChartContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The rows to exclude from every chart. See [`RowSelector`] for how
+    /// exclusions interact with sorting.
+    pub fn exclude_rows(self, exclude_row: impl Into<RowSelector>) -> Self {
+        Self {
+            exclude_row: exclude_row.into(),
+            ..self
+        }
+    }
+
+    /// The positions of the columns to exclude from every chart. Excluded
+    /// columns are neither validated nor charted, and charting one is an
+    /// error.
+    pub fn exclude_cols(self, exclude_column: impl IntoIterator<Item = usize>) -> Self {
+        Self {
+            exclude_column: exclude_column.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// The options applied to the value scale of every chart.
+    pub fn scale_options(self, options: ScaleOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// The transform applied to the labels of every chart as they are read,
+    /// before they are stored on the chart. Stacked bar charts apply it to
+    /// the headers labelling their sections, combining sections which share
+    /// a label after it.
+    pub fn label_transform(self, label_transform: LabelTransform) -> Self {
+        Self {
+            label_transform,
+            ..self
+        }
+    }
+
+    /// How the axis labels of line graphs are generated.
+    pub fn line_axis_labels(self, line_axis_labels: LineGraphAxisLabelStrategy) -> Self {
+        Self {
+            line_axis_labels,
+            ..self
+        }
+    }
+
+    /// How the axis labels of bar charts are generated.
+    pub fn bar_axis_labels(self, bar_axis_labels: BarChartAxisLabelStrategy) -> Self {
+        Self {
+            bar_axis_labels,
+            ..self
+        }
+    }
+
+    /// How the axis labels of stacked bar charts are generated.
+    pub fn stacked_axis_labels(
+        self,
+        stacked_axis_labels: StackedBarChartAxisLabelStrategy,
+    ) -> Self {
+        Self {
+            stacked_axis_labels,
+            ..self
+        }
+    }
+
+    /// Returns the rows excluded from every chart.
+    pub fn get_exclude_rows(&self) -> &RowSelector {
+        &self.exclude_row
+    }
+
+    /// Returns the positions of the columns excluded from every chart.
+    pub fn get_exclude_cols(&self) -> &HashSet<usize> {
+        &self.exclude_column
+    }
+
+    /// Returns the options applied to the value scale of every chart.
+    pub fn get_scale_options(&self) -> &ScaleOptions {
+        &self.options
+    }
+
+    /// Returns the transform applied to the labels of every chart.
+    pub fn get_label_transform(&self) -> LabelTransform {
+        self.label_transform
+    }
+}
+
+/// Builds charts from a validated [`Sheet`] using the options of a
+/// [`ChartContext`]. Returned by [`Sheet::chart`].
+///
+/// Neither the sheet nor the context is copied between charts. The sheet is
+/// only copied while building a chart of boolean value columns.
+#[derive(Debug, Clone, Copy)]
+pub struct ChartBuilder<'a> {
+    sheet: &'a Sheet,
+    context: &'a ChartContext,
+}
+
+impl<'a> ChartBuilder<'a> {
+    pub(super) fn new(sheet: &'a Sheet, context: &'a ChartContext) -> Result<Self> {
+        if context.options.allows_empty() {
+            sheet.validate()?;
+        } else {
+            sheet.validate_for_chart()?;
+        }
+
+        if let Some(col) = context
+            .exclude_column
+            .iter()
+            .find(|col| **col >= sheet.width())
+        {
+            return Err(Error::InvalidColumnLength(format!(
+                "Tried to exclude out of range column {}",
+                col
+            )));
+        }
+
+        Ok(Self { sheet, context })
+    }
+
+    /// Returns the sheet charts are built from.
+    pub fn sheet(&self) -> &'a Sheet {
+        self.sheet
+    }
+
+    /// Returns the context of the charts built.
+    pub fn context(&self) -> &'a ChartContext {
+        self.context
+    }
+
+    /// Returns a new line graph with one line per row not excluded, labelled
+    /// by `label_strat`. See [`Sheet::create_line_graph_labelled`].
+    ///
+    /// The label transform of the context is applied to labels read from
+    /// cells or provided, unless `label_strat` has its own transform.
+    pub fn line_graph(&self, label_strat: LineLabelStrategy) -> Result<LineGraph> {
+        let context = self.context;
+        let transform = context.label_transform;
+
+        let label_strat = match label_strat {
+            LineLabelStrategy::FromCell(col) => LineLabelStrategy::FromCellWith(col, transform),
+            LineLabelStrategy::Provided(labels) => LineLabelStrategy::Provided(
+                labels.iter().map(|label| transform.apply(label)).collect(),
+            ),
+            label_strat => label_strat,
+        };
+
+        self.sheet.line_graph_labelled(
+            context.line_axis_labels.clone(),
+            label_strat,
+            &context.exclude_row,
+            &context.exclude_column,
+            &context.options,
+            &(0..self.sheet.width()).collect::<Vec<usize>>(),
+        )
+    }
+
+    /// Returns a new bar chart of the values in `y_col` against those in
+    /// `x_col`. See [`Sheet::create_bar_chart_with`].
+    ///
+    /// The label transform of the context is applied to labels read from a
+    /// column or provided, unless `bar_label` has its own transform.
+    pub fn bar_chart(
+        &self,
+        x_col: usize,
+        y_col: usize,
+        bar_label: BarChartBarLabels,
+    ) -> Result<BarChart> {
+        let _span = trace_span!(
+            "create_bar_chart",
+            rows = self.sheet.height(),
+            cols = self.sheet.width()
+        );
+
+        let context = self.context;
+        let mode = context.options.get_bool_mode();
+        let count_true = mode == Some(BoolMode::CountTrue)
+            && self.sheet.column_kind(y_col) == Some(ColumnType::Boolean);
+
+        let transform = context.label_transform;
+        let bar_label = match bar_label {
+            BarChartBarLabels::FromColumn(col) => BarChartBarLabels::FromColumnWith(col, transform),
+            BarChartBarLabels::Provided(labels) => BarChartBarLabels::Provided(
+                labels.iter().map(|label| transform.apply(label)).collect(),
+            ),
+            bar_label => bar_label,
+        };
+
+        self.sheet.charted(&[y_col], mode)?.bar_chart(
+            x_col,
+            y_col,
+            bar_label,
+            context.bar_axis_labels.clone(),
+            &context.exclude_row,
+            &context.exclude_column,
+            &context.options,
+            count_true,
+        )
+    }
+
+    /// Returns a new stacked bar chart of the columns in `cols` against
+    /// those in `x_col`. See [`Sheet::create_stacked_bar_chart_with`].
+    pub fn stacked_bar_chart(
+        &self,
+        x_col: usize,
+        cols: impl IntoIterator<Item = usize>,
+    ) -> Result<StackedBarChart> {
+        let _span = trace_span!(
+            "create_stacked_bar_chart",
+            rows = self.sheet.height(),
+            cols = self.sheet.width()
+        );
+
+        let context = self.context;
+        let cols = cols
+            .into_iter()
+            .collect::<HashSet<usize>>()
+            .into_iter()
+            .collect::<Vec<usize>>();
+
+        let mode = context.options.get_bool_mode();
+        if mode == Some(BoolMode::CountTrue)
+            && cols
+                .iter()
+                .any(|col| self.sheet.column_kind(*col) == Some(ColumnType::Boolean))
+        {
+            return Err(Error::ConversionError(
+                "Stacked Bar chart: Boolean columns can only be accumulated as 1 and 0".into(),
+            ));
+        }
+
+        self.sheet.charted(&cols, mode)?.stacked_bar_chart(
+            x_col,
+            cols,
+            context.stacked_axis_labels.clone(),
+            &context.exclude_row,
+            &context.exclude_column,
+            &context.options,
+            &context.label_transform,
+        )
+    }
+}
@@ -0,0 +1,234 @@
+use std::{fmt, sync::Arc};
+
+use super::{utils::*, Row};
+
+/// A rule relating the values of several columns in each row of a
+/// [`Sheet`](super::Sheet), such as a start coming before an end. See
+/// [`Sheet::add_row_constraint`](super::Sheet::add_row_constraint).
+///
+/// Columns are referred to by position. Rows with an empty cell in any
+/// column of a [`RowConstraint::ColumnLessEqual`] or
+/// [`RowConstraint::SumEquals`] are not checked against it.
+pub enum RowConstraint {
+    /// The value of the first column is at most that of the second. Numbers
+    /// are compared by value whatever their type, and other values by the
+    /// ordering of [`Data`], so ISO 8601 dates compare as dates.
+    ColumnLessEqual(usize, usize),
+    /// The values of the `parts` columns sum to the value of the `total`
+    /// column, give or take `tolerance`. Integers are summed exactly, while
+    /// any float among the parts makes the sum a float.
+    SumEquals {
+        parts: Vec<usize>,
+        total: usize,
+        tolerance: Data,
+    },
+    /// Rows for which the predicate returns true
+    Custom(Box<dyn Fn(&Row) -> bool + Send + Sync>),
+}
+
+impl RowConstraint {
+    /// Returns the positions of the columns the constraint relates, if known.
+    pub(super) fn columns(&self) -> Vec<usize> {
+        match self {
+            Self::ColumnLessEqual(lesser, greater) => vec![*lesser, *greater],
+            Self::SumEquals { parts, total, .. } => parts.iter().copied().chain([*total]).collect(),
+            Self::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the constraint with the columns after `col` shifted to the
+    /// left, as when removing `col`, or [`None`] if it relates `col` or its
+    /// columns are unknown.
+    fn without_col(&self, col: usize) -> Option<Self> {
+        let shift = |other: usize| match other.cmp(&col) {
+            std::cmp::Ordering::Less => Some(other),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(other - 1),
+        };
+
+        match self {
+            Self::ColumnLessEqual(lesser, greater) => {
+                Some(Self::ColumnLessEqual(shift(*lesser)?, shift(*greater)?))
+            }
+            Self::SumEquals {
+                parts,
+                total,
+                tolerance,
+            } => Some(Self::SumEquals {
+                parts: parts
+                    .iter()
+                    .map(|part| shift(*part))
+                    .collect::<Option<Vec<usize>>>()?,
+                total: shift(*total)?,
+                tolerance: tolerance.clone(),
+            }),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns a description of how `row` breaks the constraint, if it does.
+    fn violation(&self, row: &Row, headers: &[ColumnHeader]) -> Option<String> {
+        let label = |col: usize| match headers.get(col) {
+            Some(header) if !header.label.is_empty() => format!("`{}`", header.label),
+            _ => format!("column {}", col),
+        };
+        let data = |col: usize| row.get_cell_by_index(col).map(|cell| cell.get_data());
+
+        match self {
+            Self::ColumnLessEqual(lesser, greater) => {
+                let (x, y) = (data(*lesser)?, data(*greater)?);
+                if x == &Data::None || y == &Data::None {
+                    return None;
+                }
+
+                let ordered = match (x.as_i128(), y.as_i128()) {
+                    (Some(x), Some(y)) => x <= y,
+                    _ => match (x.as_f64(), y.as_f64()) {
+                        (Some(x), Some(y)) => x <= y,
+                        _ => x <= y,
+                    },
+                };
+
+                (!ordered).then(|| {
+                    format!(
+                        "{} ({}) is greater than {} ({})",
+                        label(*lesser),
+                        x,
+                        label(*greater),
+                        y
+                    )
+                })
+            }
+            Self::SumEquals {
+                parts,
+                total,
+                tolerance,
+            } => {
+                let values = parts
+                    .iter()
+                    .map(|part| data(*part))
+                    .collect::<Option<Vec<_>>>()?;
+                let expected = data(*total)?;
+                if expected == &Data::None || values.contains(&&Data::None) {
+                    return None;
+                }
+
+                let labels = parts.iter().map(|part| label(*part)).collect::<Vec<_>>();
+                let sum = values
+                    .iter()
+                    .try_fold(Data::Integer(0), |sum, value| sum.checked_add(value));
+                let Some(sum) = sum else {
+                    return Some(format!("{} cannot be summed", labels.join(" + ")));
+                };
+
+                let within = match (sum.as_i128(), expected.as_i128(), tolerance) {
+                    (
+                        Some(sum),
+                        Some(expected),
+                        Data::None | Data::Integer(_) | Data::Number(_),
+                    ) => sum.abs_diff(expected) <= tolerance.as_i128().unwrap_or(0).unsigned_abs(),
+                    _ => match (sum.as_f64(), expected.as_f64()) {
+                        (Some(sum), Some(expected)) => {
+                            (sum - expected).abs() <= tolerance.as_f64().unwrap_or(0.0).abs()
+                        }
+                        _ => false,
+                    },
+                };
+
+                (!within).then(|| {
+                    format!(
+                        "{} sum to {}, not {} ({})",
+                        labels.join(" + "),
+                        sum,
+                        label(*total),
+                        expected
+                    )
+                })
+            }
+            Self::Custom(predicate) => {
+                (!predicate(row)).then(|| "breaks a custom constraint".into())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RowConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ColumnLessEqual(lesser, greater) => f
+                .debug_tuple("ColumnLessEqual")
+                .field(lesser)
+                .field(greater)
+                .finish(),
+            Self::SumEquals {
+                parts,
+                total,
+                tolerance,
+            } => f
+                .debug_struct("SumEquals")
+                .field("parts", parts)
+                .field("total", total)
+                .field("tolerance", tolerance)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish(),
+        }
+    }
+}
+
+/// A row breaking a [`RowConstraint`]. Returned by
+/// [`Sheet::check_constraints`](super::Sheet::check_constraints).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// The id of the row. See [`Row::id`]
+    pub row: usize,
+    /// How the row breaks the constraint
+    pub description: String,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Row id {}: {}", self.row, self.description)
+    }
+}
+
+/// The row constraints of a sheet, shared by its copies.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RowConstraints {
+    rules: Vec<Arc<RowConstraint>>,
+    /// Whether rows pushed or changed must follow every rule
+    pub(super) enforce: bool,
+}
+
+impl RowConstraints {
+    pub(super) fn push(&mut self, constraint: RowConstraint) {
+        self.rules.push(Arc::new(constraint));
+    }
+
+    /// Returns the violations of every rule by `row`, in the order the rules
+    /// were added.
+    pub(super) fn check<'a>(
+        &'a self,
+        row: &'a Row,
+        headers: &'a [ColumnHeader],
+    ) -> impl Iterator<Item = ConstraintViolation> + 'a {
+        self.rules.iter().filter_map(move |rule| {
+            rule.violation(row, headers)
+                .map(|description| ConstraintViolation {
+                    row: row.id(),
+                    description,
+                })
+        })
+    }
+
+    /// Drops the rules relating the column at `col`, which was removed, and
+    /// shifts the columns after it. Custom rules are kept.
+    pub(super) fn remove_col(&mut self, col: usize) {
+        self.rules = std::mem::take(&mut self.rules)
+            .into_iter()
+            .filter_map(|rule| match rule.as_ref() {
+                RowConstraint::Custom(_) => Some(rule),
+                other => other.without_col(col).map(Arc::new),
+            })
+            .collect();
+    }
+}
@@ -1,16 +1,29 @@
-use crate::models::{bar::BarChartError, line::LineGraphError, stacked_bar::StackedBarChartError};
-use std::{error, fmt};
+use crate::models::{
+    bar::BarChartError, box_plot::BoxPlotError, heatmap::HeatMapError, line::LineGraphError,
+    stacked_bar::StackedBarChartError,
+};
+#[cfg(feature = "serde")]
+use crate::repr::SnapshotError;
+use crate::repr::{CsvError, SchemaDiff, SelectColumnsError};
+
+use super::ConstraintViolation;
+use std::{
+    error, fmt,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug)]
 pub enum Error {
     /// Invalid primary key
     InvalidPrimaryKey(String),
     /// Error from csv reader
-    CSVReaderError(csv::Error),
+    CSVReaderError(CsvError),
     /// Column type and value mismatch
     InvalidColumnType(String),
     /// Out of bounds column or uneven column number
     InvalidColumnLength(String),
+    /// Out of bounds row
+    InvalidRow(usize),
     /// Non-uniform column sorting
     InvalidColumnSort(String),
     /// Error when converting the sheet to another type
@@ -23,11 +36,123 @@ pub enum Error {
     BarChartError(BarChartError),
     /// Error from creating a new stacked barchart from sheet
     StackedBarChart(StackedBarChartError),
+    /// Invalid input to a statistical computation
+    InvalidStatistic(String),
+    /// Error from creating a new box plot from sheet
+    BoxPlotError(BoxPlotError),
+    /// Error from creating a new heatmap from sheet
+    HeatMapError(HeatMapError),
+    /// The sheet has no rows, or a chart has none left to chart after
+    /// exclusions
+    NoRows,
+    /// The sheet has rows but every cell is empty
+    NoData,
+    /// A charted cell is empty under [`MissingPolicy::Error`](crate::repr::MissingPolicy::Error)
+    MissingValue { row: usize, col: usize },
+    /// The columns selected to be read are invalid
+    SelectColumns(SelectColumnsError),
+    /// The sheet read is not compatible with the schema expected. See
+    /// [`Config::expect_schema`](crate::repr::Config::expect_schema)
+    SchemaMismatch(Box<SchemaDiff>),
+    /// Error from reading or writing a binary snapshot of the sheet
+    #[cfg(feature = "serde")]
+    Snapshot(SnapshotError),
+    /// A row breaks a constraint of the sheet. See
+    /// [`Sheet::enforce_constraints`](crate::repr::sheet::Sheet::enforce_constraints)
+    ConstraintViolation(ConstraintViolation),
+    /// An error while reading a file, with where in the file it occurred
+    WithContext {
+        /// The path of the file being read, if any
+        path: Option<PathBuf>,
+        /// The 1-based line of the record at fault, if known
+        line: Option<u64>,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the error without the context of where it occurred. See
+    /// [`Error::WithContext`].
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::WithContext { source, .. } => source.inner(),
+            error => error,
+        }
+    }
+
+    /// Returns the path of the file being read when the error occurred, if
+    /// known.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::WithContext { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the 1-based line of the record at fault, if known.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            Error::WithContext { line, .. } => *line,
+            _ => None,
+        }
+    }
+
+    /// Adds the line of the record at fault to the error, keeping any line
+    /// already known.
+    #[cfg(feature = "csv")]
+    pub(crate) fn at_line(self, line: Option<u64>) -> Self {
+        match self {
+            Error::WithContext {
+                path,
+                line: existing,
+                source,
+            } => Error::WithContext {
+                path,
+                line: existing.or(line),
+                source,
+            },
+            error if line.is_some() => Error::WithContext {
+                path: None,
+                line,
+                source: Box::new(error),
+            },
+            error => error,
+        }
+    }
+
+    /// Adds the path of the file being read to the error. An empty path
+    /// describes no file.
+    #[cfg(feature = "csv")]
+    pub(crate) fn in_file(self, file: &Path) -> Self {
+        if file.as_os_str().is_empty() {
+            return self;
+        }
+
+        match self {
+            Error::WithContext { line, source, .. } => Error::WithContext {
+                path: Some(file.to_path_buf()),
+                line,
+                source,
+            },
+            error => Error::WithContext {
+                path: Some(file.to_path_buf()),
+                line: None,
+                source: Box::new(error),
+            },
+        }
+    }
 }
 
+impl From<CsvError> for Error {
+    fn from(value: CsvError) -> Self {
+        Error::CSVReaderError(value)
+    }
+}
+
+#[cfg(feature = "csv")]
 impl From<csv::Error> for Error {
     fn from(value: csv::Error) -> Self {
-        Error::CSVReaderError(value)
+        Error::CSVReaderError(value.into())
     }
 }
 
@@ -49,6 +174,31 @@ impl From<StackedBarChartError> for Error {
     }
 }
 
+impl From<BoxPlotError> for Error {
+    fn from(value: BoxPlotError) -> Self {
+        Self::BoxPlotError(value)
+    }
+}
+
+impl From<HeatMapError> for Error {
+    fn from(value: HeatMapError) -> Self {
+        Self::HeatMapError(value)
+    }
+}
+
+impl From<SelectColumnsError> for Error {
+    fn from(value: SelectColumnsError) -> Self {
+        Self::SelectColumns(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SnapshotError> for Error {
+    fn from(value: SnapshotError) -> Self {
+        Self::Snapshot(value)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -56,6 +206,7 @@ impl fmt::Display for Error {
             Error::InvalidColumnLength(s) => {
                 write!(f, "Invalid Column Length: {}", s)
             }
+            Error::InvalidRow(row) => write!(f, "Invalid Row: row {} is out of bounds", row),
             Error::InvalidPrimaryKey(s) => {
                 write!(f, "Primary Key is invalid. {}", s)
             }
@@ -68,6 +219,29 @@ impl fmt::Display for Error {
             Error::TransposeError(s) => write!(f, "Transposing Error: {}", s),
             Error::BarChartError(bar) => bar.fmt(f),
             Error::StackedBarChart(bar) => bar.fmt(f),
+            Error::InvalidStatistic(s) => write!(f, "Invalid Statistic: {}", s),
+            Error::BoxPlotError(bp) => bp.fmt(f),
+            Error::HeatMapError(hm) => hm.fmt(f),
+            Error::NoRows => write!(f, "No Rows: The sheet has no rows"),
+            Error::NoData => write!(f, "No Data: Every cell of the sheet is empty"),
+            Error::MissingValue { row, col } => write!(
+                f,
+                "Missing Value: The cell of row id {} at column {} is empty",
+                row, col
+            ),
+            Error::SelectColumns(select) => select.fmt(f),
+            Error::SchemaMismatch(diff) => write!(f, "Schema Mismatch: {}", diff),
+            Error::ConstraintViolation(violation) => {
+                write!(f, "Constraint Violation: {}", violation)
+            }
+            #[cfg(feature = "serde")]
+            Error::Snapshot(snapshot) => snapshot.fmt(f),
+            Error::WithContext { path, line, source } => match (path, line) {
+                (Some(path), Some(line)) => write!(f, "{}:{}: {}", path.display(), line, source),
+                (Some(path), None) => write!(f, "{}: {}", path.display(), source),
+                (None, Some(line)) => write!(f, "line {}: {}", line, source),
+                (None, None) => source.fmt(f),
+            },
         }
     }
 }
@@ -77,6 +251,7 @@ impl error::Error for Error {
         match self {
             Error::CSVReaderError(e) => Some(e),
             Error::InvalidColumnLength(_) => None,
+            Error::InvalidRow(_) => None,
             Error::InvalidPrimaryKey(_) => None,
             Error::InvalidColumnType(_) => None,
             Error::InvalidColumnSort(_) => None,
@@ -85,6 +260,18 @@ impl error::Error for Error {
             Error::TransposeError(_) => None,
             Error::BarChartError(bar) => Some(bar),
             Error::StackedBarChart(bar) => Some(bar),
+            Error::InvalidStatistic(_) => None,
+            Error::BoxPlotError(bp) => Some(bp),
+            Error::HeatMapError(hm) => Some(hm),
+            Error::NoRows => None,
+            Error::NoData => None,
+            Error::MissingValue { .. } => None,
+            Error::SelectColumns(select) => Some(select),
+            Error::SchemaMismatch(_) => None,
+            Error::ConstraintViolation(_) => None,
+            #[cfg(feature = "serde")]
+            Error::Snapshot(snapshot) => Some(snapshot),
+            Error::WithContext { source, .. } => Some(source.as_ref()),
         }
     }
 }
@@ -0,0 +1,177 @@
+use super::{
+    error::{Error, Result},
+    utils::{ColumnHeader, Data},
+    Row,
+};
+
+/// Types which can be extracted from a single [`Data`] value.
+///
+/// Extraction does not convert between types, except for widening an
+/// [`Data::Integer`] into an `isize` or `f64`. Use an [`Option`] for columns
+/// which may contain [`Data::None`].
+pub trait FromData: Sized {
+    /// Returns [`None`] if `data` is not of the expected type.
+    fn from_data(data: &Data) -> Option<Self>;
+}
+
+impl FromData for String {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Text(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromData for i32 {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Integer(int) => Some(*int),
+            _ => None,
+        }
+    }
+}
+
+impl FromData for isize {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Integer(int) => Some(*int as isize),
+            Data::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+}
+
+impl FromData for f32 {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Float(float) => Some(*float),
+            _ => None,
+        }
+    }
+}
+
+impl FromData for f64 {
+    fn from_data(data: &Data) -> Option<Self> {
+        data.as_f64()
+    }
+}
+
+impl FromData for bool {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Boolean(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+}
+
+impl FromData for Data {
+    fn from_data(data: &Data) -> Option<Self> {
+        Some(data.clone())
+    }
+}
+
+impl<T: FromData> FromData for Option<T> {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::None => Some(None),
+            data => T::from_data(data).map(Some),
+        }
+    }
+}
+
+/// Types which can be created from a [`Row`] of a `Sheet`.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::{repr::{ColumnHeader, FromRow, Result, Row}, samples};
+///
+/// struct AirRow {
+///     month: String,
+///     y1958: i32,
+/// }
+///
+/// impl FromRow for AirRow {
+///     fn from_row(row: &Row, headers: &[ColumnHeader]) -> Result<Self> {
+///         Ok(Self {
+///             month: row.get_str(headers, "Month")?.to_owned(),
+///             y1958: row.get(headers, "1958")?,
+///         })
+///     }
+/// }
+///
+/// let sheet = samples::air();
+///
+/// let rows = sheet.iter_as::<AirRow>().collect::<Result<Vec<_>>>().unwrap();
+/// assert_eq!(rows[0].month, "JAN");
+/// assert_eq!(rows[0].y1958, 340);
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: &Row, headers: &[ColumnHeader]) -> Result<Self>;
+}
+
+impl Row {
+    /// Returns the data in the column labelled `label`.
+    ///
+    /// Returns an error if no header in `headers` has the label.
+    pub fn get_by_label(&self, headers: &[ColumnHeader], label: &str) -> Result<&Data> {
+        headers
+            .iter()
+            .position(|header| header.label == label)
+            .and_then(|idx| self.get_cell_by_index(idx))
+            .map(|cell| cell.get_data())
+            .ok_or(Error::ConversionError(format!(
+                "No column labelled `{}` in row with id: {}",
+                label, self.id
+            )))
+    }
+
+    /// Returns the data in the column labelled `label` as a `T`.
+    ///
+    /// Returns an error if there is no such column or its data is not a `T`.
+    /// See [`FromData`].
+    pub fn get<T: FromData>(&self, headers: &[ColumnHeader], label: &str) -> Result<T> {
+        let data = self.get_by_label(headers, label)?;
+
+        T::from_data(data).ok_or(Error::InvalidColumnType(format!(
+            "Expected {} in column `{}` of row with id: {}, but had {:?}",
+            std::any::type_name::<T>(),
+            label,
+            self.id,
+            data
+        )))
+    }
+
+    /// Returns the text in the column labelled `label`. See [`Row::get`].
+    pub fn get_str(&self, headers: &[ColumnHeader], label: &str) -> Result<&str> {
+        match self.get_by_label(headers, label)? {
+            Data::Text(text) => Ok(text),
+            data => Err(Error::InvalidColumnType(format!(
+                "Expected text in column `{}` of row with id: {}, but had {:?}",
+                label, self.id, data
+            ))),
+        }
+    }
+
+    /// Returns the integer in the column labelled `label`. See [`Row::get`].
+    pub fn get_i32(&self, headers: &[ColumnHeader], label: &str) -> Result<i32> {
+        self.get(headers, label)
+    }
+
+    /// Returns the number in the column labelled `label`. See [`Row::get`].
+    pub fn get_isize(&self, headers: &[ColumnHeader], label: &str) -> Result<isize> {
+        self.get(headers, label)
+    }
+
+    /// Returns the float in the column labelled `label`. See [`Row::get`].
+    pub fn get_f32(&self, headers: &[ColumnHeader], label: &str) -> Result<f32> {
+        self.get(headers, label)
+    }
+
+    /// Returns the boolean in the column labelled `label`. See [`Row::get`].
+    pub fn get_bool(&self, headers: &[ColumnHeader], label: &str) -> Result<bool> {
+        self.get(headers, label)
+    }
+}
@@ -0,0 +1,85 @@
+//! Binary snapshots of a [`Sheet`], written in the format described by
+//! [`snapshot`](crate::repr::snapshot).
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ColumnHeader, Error, LoadReport, Original, Result, Row, RowConstraints, RowIndex, Sheet,
+};
+use crate::repr::snapshot::{self, SnapshotKind};
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    rows: &'a [Row],
+    headers: &'a [ColumnHeader],
+    id_counter: usize,
+    primary_key: Option<usize>,
+    originals: Vec<(&'a (usize, usize), &'a Original)>,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    rows: Vec<Row>,
+    headers: Vec<ColumnHeader>,
+    id_counter: usize,
+    primary_key: Option<usize>,
+    originals: Vec<((usize, usize), Original)>,
+}
+
+impl Sheet {
+    /// Returns a binary snapshot of the sheet, suited to caching it on disk.
+    /// The same sheet always gives the same snapshot. Row constraints are
+    /// not kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use modav_core::{prelude::*, samples};
+    ///
+    /// let sheet = samples::air();
+    /// let bytes = sheet.to_bytes().unwrap();
+    ///
+    /// assert_eq!(Sheet::from_bytes(&bytes).unwrap(), sheet);
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut originals: Vec<_> = self.originals.iter().collect();
+        originals.sort_unstable_by_key(|(key, _)| *key);
+
+        let snapshot = SnapshotRef {
+            rows: &self.rows,
+            headers: &self.headers,
+            id_counter: self.id_counter,
+            primary_key: self.primary_key,
+            originals,
+        };
+
+        snapshot::encode(SnapshotKind::Sheet, &snapshot).map_err(Error::Snapshot)
+    }
+
+    /// Reads a sheet from a snapshot written by [`Sheet::to_bytes`].
+    ///
+    /// Returns an [`Error::Snapshot`] if the snapshot is corrupt, was written
+    /// by another version of the format or holds a
+    /// [`ColumnSheet`](crate::repr::col_sheet::ColumnSheet).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let snapshot: Snapshot =
+            snapshot::decode(SnapshotKind::Sheet, bytes).map_err(Error::Snapshot)?;
+
+        let mut sheet = Self {
+            rows: snapshot.rows,
+            headers: snapshot.headers,
+            id_counter: snapshot.id_counter,
+            primary_key: snapshot.primary_key,
+            originals: snapshot.originals.into_iter().collect(),
+            meta: Vec::new(),
+            row_index: RowIndex::default(),
+            constraints: RowConstraints::default(),
+            report: LoadReport::default(),
+        };
+        sheet.row_index.rebuild(&sheet.rows);
+
+        sheet.validate()?;
+
+        Ok(sheet)
+    }
+}
@@ -0,0 +1,274 @@
+use std::{collections::HashSet, fmt};
+
+use super::{
+    error::{Error, Result},
+    utils::{
+        BarChartAxisLabelStrategy, BarChartBarLabels, ColumnHeader, ColumnType,
+        LineGraphAxisLabelStrategy, LineLabelStrategy, RowSelector,
+        StackedBarChartAxisLabelStrategy,
+    },
+    Sheet,
+};
+use crate::models::{BarChart, ChartKind, LineGraph, StackedBarChart};
+
+/// The largest ratio between the mean values of two columns for the columns
+/// to be considered parts of a whole.
+const PARTS_MAGNITUDE_RATIO: f64 = 10.0;
+
+/// A chart suggested for a [`Sheet`], along with the reasoning behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartSuggestion {
+    /// The kind of chart suggested, if any chart is suitable
+    pub kind: Option<ChartKind>,
+    /// The column used for the x axis of bar charts, or for the labels of the
+    /// lines of line graphs
+    pub x_col: Option<usize>,
+    /// The columns holding the values of the chart
+    pub value_cols: Vec<usize>,
+    /// A short description of why the chart was suggested
+    pub reason: String,
+}
+
+impl ChartSuggestion {
+    fn new(
+        kind: ChartKind,
+        x_col: Option<usize>,
+        value_cols: Vec<usize>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind: Some(kind),
+            x_col,
+            value_cols,
+            reason: reason.into(),
+        }
+    }
+
+    fn none(reason: impl Into<String>) -> Self {
+        Self {
+            kind: None,
+            x_col: None,
+            value_cols: Vec::new(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ChartSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Some(kind) => write!(f, "{:?} chart: {}", kind, self.reason),
+            None => write!(f, "No chart: {}", self.reason),
+        }
+    }
+}
+
+/// Suggests a chart from the kinds of `headers` alone.
+///
+/// - Text with one numeric column suggests a bar chart.
+/// - Text with several numeric columns of the same type suggests a line
+///   graph, with a line for each row.
+/// - Only numeric columns suggests a bar chart of the first two. There are no
+///   scatter plots yet.
+///
+/// Only numeric columns of the same type as the first numeric column are
+/// used. [`Sheet::suggest_chart`] further inspects the values to suggest
+/// stacked bar charts.
+pub fn suggest_chart(headers: &[ColumnHeader], height: usize) -> ChartSuggestion {
+    if height == 0 {
+        return ChartSuggestion::none("The sheet has no rows");
+    }
+
+    let text = headers
+        .iter()
+        .position(|header| header.kind == ColumnType::Text);
+
+    let numeric_kind = headers
+        .iter()
+        .map(|header| header.kind)
+        .find(ColumnType::is_numeric);
+    let numeric = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| Some(header.kind) == numeric_kind)
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+
+    match (text, numeric.as_slice()) {
+        (_, []) => ChartSuggestion::none("There are no numeric columns"),
+        (Some(text), [numeric]) => ChartSuggestion::new(
+            ChartKind::Bar,
+            Some(text),
+            vec![*numeric],
+            "One text column and one numeric column",
+        ),
+        (Some(text), _) => ChartSuggestion::new(
+            ChartKind::Line,
+            Some(text),
+            numeric,
+            "One text column and several numeric columns of the same type",
+        ),
+        (None, [x, y, ..]) => ChartSuggestion::new(
+            ChartKind::Bar,
+            Some(*x),
+            vec![*y],
+            "Only numeric columns, so the second is plotted against the first",
+        ),
+        (None, [_]) => ChartSuggestion::none("Only a single numeric column"),
+    }
+}
+
+/// A chart created by [`Sheet::auto_chart`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutoChart {
+    Line(LineGraph),
+    Bar(BarChart),
+    StackedBar(StackedBarChart),
+}
+
+impl AutoChart {
+    pub fn kind(&self) -> ChartKind {
+        match self {
+            Self::Line(_) => ChartKind::Line,
+            Self::Bar(_) => ChartKind::Bar,
+            Self::StackedBar(_) => ChartKind::StackedBar,
+        }
+    }
+}
+
+impl Sheet {
+    /// Suggests a chart for the [`Sheet`]. See [`suggest_chart`].
+    ///
+    /// Where a line graph is suggested from the headers, a stacked bar chart
+    /// is suggested instead if the values look like parts of a whole: every
+    /// value is non-negative and the mean values of the columns are of
+    /// similar magnitude. A column holding the total of the others is left
+    /// out of the stacked bar chart.
+    pub fn suggest_chart(&self) -> ChartSuggestion {
+        let suggestion = suggest_chart(&self.headers, self.height());
+
+        match suggestion {
+            ChartSuggestion {
+                kind: Some(ChartKind::Line),
+                x_col,
+                value_cols,
+                ..
+            } => match self.parts_of_whole(&value_cols) {
+                Some(parts) => ChartSuggestion::new(
+                    ChartKind::StackedBar,
+                    x_col,
+                    parts,
+                    "One text column and several non-negative numeric columns of similar magnitude",
+                ),
+                None => ChartSuggestion::new(ChartKind::Line, x_col, value_cols, suggestion.reason),
+            },
+            suggestion => suggestion,
+        }
+    }
+
+    /// Creates the chart suggested by [`Sheet::suggest_chart`], with axis
+    /// labels taken from the headers.
+    ///
+    /// Returns [`Error::ConversionError`] if no chart is suggested.
+    pub fn auto_chart(&self) -> Result<AutoChart> {
+        let suggestion = self.suggest_chart();
+
+        match (suggestion.kind, suggestion.x_col) {
+            (Some(ChartKind::Bar), Some(x_col)) => self
+                .clone()
+                .create_bar_chart(
+                    x_col,
+                    suggestion.value_cols[0],
+                    BarChartBarLabels::None,
+                    BarChartAxisLabelStrategy::Headers,
+                    RowSelector::default(),
+                )
+                .map(AutoChart::Bar),
+            (Some(ChartKind::Line), x_col) => {
+                let label_strat = match x_col {
+                    Some(col) => LineLabelStrategy::FromCell(col),
+                    None => LineLabelStrategy::None,
+                };
+                let exclude = (0..self.headers.len())
+                    .filter(|col| Some(*col) != x_col && !suggestion.value_cols.contains(col))
+                    .collect::<HashSet<usize>>();
+
+                self.create_line_graph_labelled(
+                    LineGraphAxisLabelStrategy::Headers { y: String::new() },
+                    label_strat,
+                    RowSelector::default(),
+                    exclude,
+                )
+                .map(AutoChart::Line)
+            }
+            (Some(ChartKind::StackedBar), Some(x_col)) => self
+                .clone()
+                .create_stacked_bar_chart(
+                    x_col,
+                    suggestion.value_cols,
+                    StackedBarChartAxisLabelStrategy::Header(String::new()),
+                    RowSelector::default(),
+                )
+                .map(AutoChart::StackedBar),
+            _ => Err(Error::ConversionError(format!(
+                "No chart could be suggested. {}",
+                suggestion.reason
+            ))),
+        }
+    }
+
+    /// Returns the columns of `cols` which look like parts of a whole, without
+    /// any column holding their total.
+    fn parts_of_whole(&self, cols: &[usize]) -> Option<Vec<usize>> {
+        let columns = cols
+            .iter()
+            .map(|col| self.numeric_values(*col).ok())
+            .collect::<Option<Vec<Vec<f64>>>>()?;
+
+        if columns.iter().flatten().any(|value| *value < 0.0) {
+            return None;
+        }
+
+        // A total is the sum of every other column within each row
+        let total = (0..cols.len()).find(|idx| {
+            self.rows.iter().all(|row| {
+                let value = |col: usize| {
+                    row.get_cell_by_index(col)
+                        .and_then(|cell| cell.get_data().as_f64())
+                        .unwrap_or(0.0)
+                };
+                let others = cols
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| other != idx)
+                    .map(|(_, col)| value(*col))
+                    .sum::<f64>();
+
+                (value(cols[*idx]) - others).abs() < f64::EPSILON
+            })
+        });
+
+        let parts = cols
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| Some(*idx) != total)
+            .map(|(_, col)| *col)
+            .collect::<Vec<usize>>();
+
+        let means = columns
+            .iter()
+            .enumerate()
+            .filter(|(idx, values)| Some(*idx) != total && !values.is_empty())
+            .map(|(_, values)| values.iter().sum::<f64>() / values.len() as f64)
+            .collect::<Vec<f64>>();
+
+        let min = means.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = means.iter().copied().fold(0.0, f64::max);
+
+        if parts.len() < 2 || min <= 0.0 || max / min > PARTS_MAGNITUDE_RATIO {
+            None
+        } else {
+            Some(parts)
+        }
+    }
+}
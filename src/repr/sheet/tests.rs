@@ -2,23 +2,29 @@
 #![cfg(test)]
 use core::panic;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::usize;
 
-use crate::models::Scale;
+use crate::models::{CategoricalOrder, Scale, ScaleKind};
+use proptest::{prelude::*, proptest};
 
 use super::{
     error::*,
+    suggest_chart,
     utils::{
-        BarChartAxisLabelStrategy, BarChartBarLabels, ColumnHeader, ColumnType, Data,
-        LineLabelStrategy, StackedBarChartAxisLabelStrategy, TypesStrategy,
+        BarChartAxisLabelStrategy, BarChartBarLabels, BoolMode, ColumnHeader, ColumnType, Data,
+        DiffMode, DiffOptions, LineGraphAxisLabelStrategy, LineLabelStrategy, MissingPolicy,
+        RowSelector, ScaleOptions, SimplePattern, StackedBarChartAxisLabelStrategy, TypesStrategy,
+        Unit,
     },
-    Cell, Config, HeaderStrategy, Row, Sheet,
+    AutoChart, Cell, ChartContext, ChartSuggestion, Config, Encoding, FromRow, HeaderStrategy,
+    LineTerminator, NanPolicy, QuoteStyle, Row, RowConstraint, Schema, SchemaColumn, SelectColumns,
+    SelectColumnsError, Sheet, SheetView, TypeMismatchPolicy, WriteOptions,
 };
 
 fn create_row() -> Row {
     let sr = csv::StringRecord::from(vec!["3", "2", "1"]);
-    Row::new(sr, 4, 0)
+    Row::new(&sr, 4, 0)
 }
 
 fn create_air_csv() -> Result<Sheet> {
@@ -76,7 +82,7 @@ fn test_cell() {
 fn test_row() {
     let row = create_row();
     assert_eq!(
-            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: 0, id_counter: 3 }",
+            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: Some(0), id_counter: 3, padding: 0 }",
             format!("{:?}", row)
         )
 }
@@ -86,7 +92,7 @@ fn test_iter_cells() {
     let row = create_row();
 
     assert_eq!(
-            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: 0, id_counter: 3 }",
+            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: Some(0), id_counter: 3, padding: 0 }",
             format!("{:?}", row)
         );
 
@@ -110,7 +116,7 @@ fn test_iter_cells_mut() {
     let mut row = create_row();
 
     assert_eq!(
-            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: 0, id_counter: 3 }",
+            "Row { id: 4, cells: [Cell { id: 0, data: Integer(3) }, Cell { id: 1, data: Integer(2) }, Cell { id: 2, data: Integer(1) }], primary: Some(0), id_counter: 3, padding: 0 }",
             format!("{:?}", row)
         );
 
@@ -120,14 +126,14 @@ fn test_iter_cells_mut() {
         };
     });
 
-    assert_eq!("Row { id: 4, cells: [Cell { id: 0, data: Integer(103) }, Cell { id: 1, data: Integer(102) }, Cell { id: 2, data: Integer(101) }], primary: 0, id_counter: 3 }", 
+    assert_eq!("Row { id: 4, cells: [Cell { id: 0, data: Integer(103) }, Cell { id: 1, data: Integer(102) }, Cell { id: 2, data: Integer(101) }], primary: Some(0), id_counter: 3, padding: 0 }", 
             format!("{:?}", row));
 
     row.iter_cells_mut()
         .for_each(|cell| cell.set_data(Data::None));
 
     assert_eq!(
-            "Row { id: 4, cells: [Cell { id: 0, data: None }, Cell { id: 1, data: None }, Cell { id: 2, data: None }], primary: 0, id_counter: 3 }",
+            "Row { id: 4, cells: [Cell { id: 0, data: None }, Cell { id: 1, data: None }, Cell { id: 2, data: None }], primary: Some(0), id_counter: 3, padding: 0 }",
             format!("{:?}", row)
         )
 }
@@ -136,18 +142,18 @@ fn test_iter_cells_mut() {
 fn test_row_set_primary_key() {
     let mut row = create_row();
 
-    assert_eq!(0, row.get_primary_key());
+    assert_eq!(Some(0), row.get_primary_key());
 
     if let Err(_) = row.set_primary_key(1) {
         panic!("Something went wrong which shouldn't")
     };
-    assert_eq!(1, row.get_primary_key());
+    assert_eq!(Some(1), row.get_primary_key());
 
     if let Ok(_) = row.set_primary_key(3) {
         panic!("Something went wrong whcih shouldn't have")
     }
 
-    assert_eq!(1, row.get_primary_key())
+    assert_eq!(Some(1), row.get_primary_key())
 }
 
 #[test]
@@ -173,6 +179,102 @@ fn test_get_primary_cell() {
     )
 }
 
+#[test]
+fn test_from_data() {
+    let cell = Cell::from_data(3, 42);
+    assert_eq!(cell, Cell::new(3, Data::Integer(42)));
+
+    let row = Row::from_data(
+        vec![Data::Integer(3), Data::Integer(2), Data::Integer(1)],
+        4,
+        0,
+    );
+    assert_eq!(row, create_row());
+}
+
+fn create_in_memory() -> Sheet {
+    let rows = vec![
+        vec!["Mon".into(), 5.into(), 8.into(), 3.into()],
+        vec!["Tue".into(), 10.into(), 0.into(), 3.into()],
+        vec!["Wed".into(), 8.into(), 6.into()],
+    ];
+
+    let headers = vec![
+        ColumnHeader::new("Day".into(), ColumnType::Text),
+        ColumnHeader::new("Coffee".into(), ColumnType::Integer),
+        ColumnHeader::new("Chocolate".into(), ColumnType::Integer),
+        ColumnHeader::new("Soda".into(), ColumnType::Integer),
+    ];
+
+    Sheet::from_rows(rows, headers, 0).unwrap()
+}
+
+#[test]
+fn test_from_rows() {
+    let sht = create_in_memory();
+
+    assert_eq!(sht.width(), 4);
+    assert_eq!(sht.height(), 3);
+    assert_eq!(sht.id_counter, 3);
+    assert_eq!(
+        sht.get_row_by_index(2).unwrap().get_cell_by_index(3),
+        Some(&Cell::new(3, Data::None))
+    );
+    assert_eq!(sht.get_row_by_index(2).unwrap().id_counter, 4);
+
+    // Ragged headers
+    let sht = Sheet::from_rows(vec![vec![1.into(), 2.into()]], vec![], 1).unwrap();
+    assert_eq!(sht.get_headers().len(), 2);
+
+    // Mismatched types
+    let res = Sheet::from_rows(
+        vec![vec![1.into()], vec!["text".into()]],
+        vec![ColumnHeader::new("Int".into(), ColumnType::Integer)],
+        0,
+    );
+    assert!(matches!(res, Err(Error::InvalidColumnType(_))));
+
+    // Invalid primary
+    let res = Sheet::from_rows(vec![vec![1.into()]], vec![], 3);
+    assert!(matches!(res, Err(Error::InvalidPrimaryKey(_))));
+
+    let sht = create_in_memory();
+
+    let line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            HashSet::default(),
+            HashSet::from([3]),
+        )
+        .unwrap();
+    assert_eq!(line.lines.len(), 3);
+    assert_eq!(line.lines[1].label, Some("Tue".into()));
+
+    let bar = sht
+        .clone()
+        .create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::Headers,
+            HashSet::default(),
+        )
+        .unwrap();
+    assert_eq!(bar.bars.len(), 3);
+    assert_eq!(bar.x_label, Some("Day".into()));
+
+    let stacked = sht
+        .create_stacked_bar_chart(
+            0,
+            [1, 2],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
+        .unwrap();
+    assert_eq!(stacked.bars[0].point.y, Data::Integer(13));
+}
+
 #[test]
 fn test_sheet_builder() {
     let path: PathBuf = "./dummies/csv/air.csv".into();
@@ -200,7 +302,7 @@ fn test_sheet_builder() {
                 None => panic!("No headers when there should have been some"),
                 Some(hr) => {
                     assert_eq!(
-                        "ColumnHeader { label: \"Month\", kind: Text }",
+                        "ColumnHeader { label: \"Month\", kind: Text, unit: None }",
                         format!("{:?}", hr)
                     )
                 }
@@ -209,7 +311,7 @@ fn test_sheet_builder() {
             match hrs.get(2) {
                 None => panic!("Missing third header"),
                 Some(hr) => assert_eq!(
-                    "ColumnHeader { label: \"1959\", kind: Integer }",
+                    "ColumnHeader { label: \"1959\", kind: Integer, unit: None }",
                     format!("{:?}", hr)
                 ),
             }
@@ -225,7 +327,7 @@ fn test_sheet_builder() {
         Ok(sht) => match sht.get_headers().get(1) {
             None => panic!("No second header found"),
             Some(hr) => assert_eq!(
-                "ColumnHeader { label: \"\", kind: None }",
+                "ColumnHeader { label: \"\", kind: None, unit: None }",
                 format!("{:?}", hr)
             ),
         },
@@ -247,7 +349,7 @@ fn test_sheet_builder() {
                 None => panic!("No Header when there should be one"),
                 Some(hr) => {
                     assert_eq!(
-                        "ColumnHeader { label: \"Month\", kind: Text }",
+                        "ColumnHeader { label: \"Month\", kind: Text, unit: None }",
                         format!("{:?}", hr)
                     )
                 }
@@ -257,7 +359,7 @@ fn test_sheet_builder() {
                 None => panic!("Missing padded header"),
                 Some(hr) => {
                     assert_eq!(
-                        "ColumnHeader { label: \"\", kind: Integer }",
+                        "ColumnHeader { label: \"\", kind: Integer, unit: None }",
                         format!("{:?}", hr)
                     )
                 }
@@ -468,23 +570,69 @@ fn test_sort_panic() {
 
     let config = Config::new(path);
 
-    let res = Sheet::with_config(config);
+    let mut sh = Sheet::with_config(config).unwrap();
+    let unsorted = sh.clone();
 
-    match res {
-        Err(e) => panic!("{}", e),
-        Ok(mut sh) => match sh.sort_rows(1) {
-            Ok(_) => panic!("Test should have panicked"),
-            Err(e) => {
-                assert_eq!(
-                    format!("{}", e),
-                    "Invalid Column Sort: Tried to sort by an unstructured column "
-                )
-            }
-        },
+    // Columns of unknown type sort as all-equal
+    sh.sort_rows(1).unwrap();
+    assert_eq!(sh, unsorted);
+
+    let path: PathBuf = "./dummies/csv/infer.csv".into();
+
+    let config = Config::new(path)
+        .labels(HeaderStrategy::ReadLabels)
+        .trim(true)
+        .types(TypesStrategy::Infer);
+
+    let mut sh = Sheet::with_config(config).unwrap();
+
+    match sh.sort_rows(3) {
+        Ok(_) => panic!("Test should have panicked"),
+        Err(e) => {
+            assert_eq!(
+                format!("{}", e),
+                "Invalid Column Sort: Tried to sort by column `Mixed` of mixed type"
+            )
+        }
     }
+    assert!(sh.sort_rows_rev(3).is_err());
+}
+
+#[test]
+fn test_sort_ragged() -> Result<()> {
+    let headers = vec![
+        ColumnHeader::new("Key".into(), ColumnType::Integer),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let rows = vec![
+        vec![3.into(), 30.into()],
+        vec![1.into(), 10.into()],
+        vec![2.into(), 20.into()],
+    ];
+    let mut sheet = Sheet::from_rows(rows, headers, 0)?;
+    sheet.iter_rows_mut().nth(1).unwrap().cells.pop();
+
+    sheet.sort_rows(0)?;
+    let keys = |sheet: &Sheet| {
+        sheet
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(0).unwrap().get_data().clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(keys(&sheet), vec![1.into(), 2.into(), 3.into()]);
+
+    sheet.sort_rows_rev(0)?;
+    assert_eq!(keys(&sheet), vec![3.into(), 2.into(), 1.into()]);
+
+    assert!(sheet.sort_rows(1).is_err());
+    assert!(sheet.sort_rows_rev(1).is_err());
+    assert_eq!(keys(&sheet), vec![3.into(), 2.into(), 1.into()]);
+
+    Ok(())
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_create_line_graph() {
     let res = create_air_csv().unwrap();
 
@@ -525,9 +673,8 @@ fn test_line_scales() {
     let sht = Sheet::with_config(config).expect("Building alter csv failure");
 
     let mut line = sht
-        .create_line_graph(
-            None,
-            None,
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
             LineLabelStrategy::FromCell(0),
             HashSet::default(),
             HashSet::default(),
@@ -558,6 +705,108 @@ fn test_line_scales() {
     assert_eq!(line.y_scale, expected_y_scale);
 }
 
+#[test]
+fn test_line_graph_exclusions() {
+    let sht = create_air_csv().unwrap();
+
+    // Excluding the label column is an error
+    let res = sht.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::FromCell(0),
+        HashSet::default(),
+        HashSet::from([0, 2]),
+    );
+    assert!(matches!(res, Err(Error::ConversionError(_))));
+
+    // The label column is never charted
+    let line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            HashSet::default(),
+            HashSet::from([2]),
+        )
+        .unwrap();
+
+    let mut x_scale = line.x_scale.points();
+    x_scale.sort();
+    assert_eq!(
+        x_scale,
+        vec![Data::Text("1958".into()), Data::Text("1960".into())]
+    );
+
+    for ln in line.lines.iter() {
+        assert_eq!(ln.points.len(), 2);
+        assert!(ln.points.iter().all(|pnt| line.x_scale.contains(&pnt.x)));
+    }
+    assert_eq!(line.lines[0].label, Some("JAN".into()));
+
+    // Excluding every other column
+    let line = sht.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::FromCell(0),
+        HashSet::default(),
+        HashSet::from([1, 2, 3]),
+    );
+    assert!(matches!(line, Err(Error::NoRows)));
+
+    let line = sht
+        .create_line_graph_labelled_with(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            HashSet::default(),
+            HashSet::from([1, 2, 3]),
+            ScaleOptions::new().allow_empty(true),
+        )
+        .unwrap();
+
+    assert!(line.is_empty());
+    assert!(line.x_scale.is_empty());
+    assert!(line.lines.iter().all(|ln| ln.points.is_empty()));
+}
+
+#[test]
+fn test_line_graph_provided_labels() {
+    let sht = create_air_csv().unwrap();
+    let labels = vec!["Jan".to_string(), "Feb".into(), "Mar".into()];
+
+    let line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::Provided(labels.clone()),
+            HashSet::from([1]),
+            HashSet::from([0]),
+        )
+        .unwrap();
+
+    assert_eq!(line.lines.len(), 11);
+    assert_eq!(line.lines[0].label, Some("Jan".into()));
+    assert_eq!(line.lines[1].label, Some("Mar".into()));
+    assert!(line.lines[2..].iter().all(|ln| ln.label.is_none()));
+
+    let line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::Provided(labels),
+            RowSelector::Predicate(Box::new(|row: &Row| {
+                row.get_cell_by_index(0).unwrap().get_data() == &Data::Text("JAN".into())
+            })),
+            HashSet::from([0]),
+        )
+        .unwrap();
+    assert_eq!(line.lines[0].label, Some("Feb".into()));
+    assert_eq!(line.lines[1].label, Some("Mar".into()));
+
+    let too_many = (0..13).map(|idx| idx.to_string()).collect();
+    let res = sht.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::Provided(too_many),
+        RowSelector::default(),
+        HashSet::from([0]),
+    );
+    assert!(matches!(res, Err(Error::ConversionError(_))));
+}
+
 #[test]
 fn test_transpose() {
     match create_air_csv() {
@@ -746,7 +995,7 @@ fn test_infer_types() {
             assert_eq!(ColumnType::Float, hr2.kind);
 
             let hr3 = sh.get_headers().get(3).unwrap();
-            assert_eq!(ColumnType::None, hr3.kind);
+            assert_eq!(ColumnType::Mixed, hr3.kind);
 
             let hr5 = sh.get_headers().get(5).unwrap();
             assert_eq!(ColumnType::Text, hr5.kind);
@@ -823,13 +1072,10 @@ fn test_create_bar_chart() {
         .create_bar_chart(
             1,
             2,
-            //BarChartBarLabels::Provided(vec![String::from("One")]),
             BarChartBarLabels::Provided(vec![
                 String::from("One"),
                 String::from("Two"),
                 String::from("Three"),
-                String::from("Four"),
-                String::from("Five"),
             ]),
             BarChartAxisLabelStrategy::Provided {
                 x: "Xer".into(),
@@ -845,6 +1091,21 @@ fn test_create_bar_chart() {
         "Three"
     );
 
+    // More labels than rows
+    let barchart = res.clone().create_bar_chart(
+        1,
+        2,
+        BarChartBarLabels::Provided(vec![
+            String::from("One"),
+            String::from("Two"),
+            String::from("Three"),
+            String::from("Four"),
+        ]),
+        BarChartAxisLabelStrategy::None,
+        HashSet::default(),
+    );
+    assert!(matches!(barchart, Err(Error::ConversionError(_))));
+
     // Non uniform column test
     let barchart = res.clone().create_bar_chart(
         4,
@@ -859,7 +1120,7 @@ fn test_create_bar_chart() {
         Err(e) => {
             assert_eq!(
                 e.to_string(),
-                "Conversion Error: Cannot convert from non-uniform column"
+                "Conversion Error: Cannot convert column `Mixed` of mixed types: integer, text"
             );
         }
     }
@@ -922,7 +1183,12 @@ fn test_stacked_bar_char() {
 
     let stacked = res
         .clone()
-        .create_stacked_bar_chart(0, [1, 2, 3, 4], StackedBarChartAxisLabelStrategy::None)
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
         .unwrap();
 
     assert_eq!(stacked.x_axis, None);
@@ -943,6 +1209,7 @@ fn test_stacked_bar_char() {
             0,
             [1, 4],
             StackedBarChartAxisLabelStrategy::Header("Total".into()),
+            HashSet::new(),
         )
         .unwrap();
 
@@ -966,6 +1233,7 @@ fn test_stacked_bar_char() {
                 x: "Some X".into(),
                 y: "Some Y".into(),
             },
+            HashSet::new(),
         )
         .unwrap();
 
@@ -982,6 +1250,7 @@ fn test_stacked_bar_char() {
                 x: "Some X".into(),
                 y: "Some Y".into(),
             },
+            HashSet::new(),
         )
         .unwrap();
 
@@ -1002,7 +1271,12 @@ fn test_stacked_bar_char() {
     );
 
     let mut stacked = res
-        .create_stacked_bar_chart(0, [1, 2, 3, 4], StackedBarChartAxisLabelStrategy::None)
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
         .unwrap();
     // test multiple remove/add of the same section
     assert_eq!(stacked.bars.get(2).unwrap().point.y, 14.into());
@@ -1043,7 +1317,12 @@ fn test_stacked_bar_char() {
 
     let stacked = res
         .clone()
-        .create_stacked_bar_chart(0, [1, 2, 3, 4], StackedBarChartAxisLabelStrategy::None)
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
         .unwrap();
 
     assert!(stacked.has_true_negatives());
@@ -1058,6 +1337,38 @@ fn test_stacked_bar_char() {
     assert_eq!(stacked.bars.get(2).unwrap().point.y, Data::Integer(-10));
     assert_eq!(stacked.bars.get(4).unwrap().point.y, Data::Integer(-18));
     assert_eq!(stacked.bars.len(), 9);
+
+    // Tuesday has both a positive and a negative bar, Thursday only a negative one
+    let (pos, neg) = stacked.pair(1);
+    assert_eq!(pos.unwrap().point.y, Data::Integer(19));
+    assert_eq!(neg.unwrap().point.y, Data::Integer(-10));
+    assert_eq!(pos.unwrap().group, neg.unwrap().group);
+
+    let (pos, neg) = stacked.pair(3);
+    assert!(pos.is_none());
+    assert_eq!(neg.unwrap().point.y, Data::Integer(-18));
+
+    let pairs = stacked.pairs();
+    assert_eq!(pairs.len(), 7);
+    let groups = pairs
+        .iter()
+        .map(|(pos, neg)| pos.or(*neg).unwrap().group)
+        .collect::<Vec<usize>>();
+    assert_eq!(groups, (0..7).collect::<Vec<usize>>());
+
+    // Groups stay tied to the source rows when rows are excluded
+    let stacked = res
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::from([0, 2]),
+        )
+        .unwrap();
+    let (pos, neg) = stacked.pair(4);
+    assert_eq!(pos.unwrap().point.x, Data::Text("Friday".into()));
+    assert_eq!(neg.unwrap().point.y, Data::Integer(-15));
+    assert_eq!(stacked.pair(2), (None, None));
 }
 
 fn stacked_helper(total: &Data, fractions: &HashMap<String, f64>) -> Vec<Data> {
@@ -1071,3 +1382,4351 @@ fn stacked_helper(total: &Data, fractions: &HashMap<String, f64>) -> Vec<Data> {
         .map(|val| Data::Integer(val))
         .collect()
 }
+
+#[test]
+fn test_config_parsing() {
+    use crate::repr::parse_type_list;
+
+    let types = [
+        ColumnType::Text,
+        ColumnType::Integer,
+        ColumnType::Number,
+        ColumnType::Float,
+        ColumnType::Boolean,
+        ColumnType::None,
+    ];
+
+    for kind in types {
+        assert_eq!(kind, kind.name().parse::<ColumnType>().unwrap());
+    }
+
+    assert_eq!("Integer Column Type", ColumnType::Integer.to_string());
+
+    let aliases = [
+        ("int", ColumnType::Integer),
+        ("integer", ColumnType::Integer),
+        ("num", ColumnType::Number),
+        ("number", ColumnType::Number),
+        ("str", ColumnType::Text),
+        ("text", ColumnType::Text),
+        ("bool", ColumnType::Boolean),
+        ("boolean", ColumnType::Boolean),
+        ("float", ColumnType::Float),
+        ("none", ColumnType::None),
+        (" INT ", ColumnType::Integer),
+    ];
+
+    for (alias, kind) in aliases {
+        assert_eq!(kind, alias.parse::<ColumnType>().unwrap());
+    }
+
+    assert!("integr".parse::<ColumnType>().is_err());
+
+    assert_eq!(
+        vec![
+            ColumnType::Text,
+            ColumnType::Integer,
+            ColumnType::Integer,
+            ColumnType::Float
+        ],
+        parse_type_list("text,int,int,float").unwrap()
+    );
+
+    let error = parse_type_list("text, int,flaot").unwrap_err();
+    assert_eq!(error.token(), "flaot");
+    assert_eq!(error.position(), Some(2));
+    assert!(error.to_string().contains("`flaot` at position 2"));
+
+    let strategies = [
+        TypesStrategy::Infer,
+        TypesStrategy::InferWithUnion,
+        TypesStrategy::None,
+        TypesStrategy::Provided(vec![ColumnType::Text, ColumnType::Boolean]),
+        TypesStrategy::Provided(vec![ColumnType::None]),
+        TypesStrategy::Provided(vec![]),
+    ];
+
+    for strategy in strategies {
+        assert_eq!(strategy, strategy.name().parse::<TypesStrategy>().unwrap());
+    }
+    assert_eq!(TypesStrategy::Infer.to_string(), "Infer types");
+
+    assert_eq!(
+        TypesStrategy::Provided(vec![ColumnType::Text, ColumnType::Number]),
+        "str,num".parse::<TypesStrategy>().unwrap()
+    );
+    assert!("str,,num".parse::<TypesStrategy>().is_err());
+    assert_eq!(
+        TypesStrategy::Provided(vec![ColumnType::Integer]),
+        " Provided: int".parse::<TypesStrategy>().unwrap()
+    );
+    assert!("provided:int,flaot".parse::<TypesStrategy>().is_err());
+
+    for strategy in [HeaderStrategy::ReadLabels, HeaderStrategy::NoLabels] {
+        assert_eq!(
+            strategy,
+            strategy.name().unwrap().parse::<HeaderStrategy>().unwrap()
+        );
+    }
+    assert_eq!(HeaderStrategy::Provided(vec![]).name(), None);
+    assert_eq!(HeaderStrategy::ReadLabels.to_string(), "Read Header Labels");
+
+    assert_eq!(
+        HeaderStrategy::ReadLabels,
+        " Read".parse::<HeaderStrategy>().unwrap()
+    );
+    assert!("provided".parse::<HeaderStrategy>().is_err());
+}
+
+#[test]
+fn test_config_parity() {
+    use crate::repr::col_sheet::{CellRef, ColumnSheet};
+
+    let config = || {
+        Config::new("./dummies/csv/nulls.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .null_string("NA")
+    };
+
+    let sht = Sheet::with_config(config()).unwrap();
+    let col = ColumnSheet::with_config(config()).unwrap();
+
+    assert_eq!(sht.width(), col.width());
+    assert_eq!(sht.height(), col.height());
+
+    for (header, col_header) in sht.get_headers().iter().zip(col.headers()) {
+        assert_eq!(Some(header.label.as_str()), col_header.header);
+    }
+
+    assert_eq!(sht.get_headers()[1].kind, ColumnType::Integer);
+    assert_eq!(sht.get_headers()[2].kind, ColumnType::Boolean);
+
+    assert_eq!(
+        sht.get_row_by_index(1).unwrap().get_cell_by_index(1),
+        Some(&Cell::new(1, Data::None))
+    );
+    assert_eq!(col.get_cell(1, 1), Some(CellRef::None));
+    assert_eq!(
+        sht.get_row_by_index(2).unwrap().get_cell_by_index(2),
+        Some(&Cell::new(2, Data::None))
+    );
+    assert_eq!(col.get_cell(2, 2), Some(CellRef::None));
+
+    let config = || {
+        Config::new("./dummies/csv/air.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    };
+
+    let sht = Sheet::with_config(config()).unwrap();
+    let col = ColumnSheet::with_config(config()).unwrap();
+
+    assert_eq!(sht.width(), col.width());
+    assert_eq!(sht.height(), col.height());
+
+    // The default null string is read as missing
+    let config = Config::new("notes.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sht = Sheet::from_reader("Id,Note\n1,<null>\n2,x\n".as_bytes(), config).unwrap();
+    assert_eq!(
+        sht.get_row_by_index(0).unwrap().get_cell_by_index(1),
+        Some(&Cell::new(1, Data::None))
+    );
+}
+
+#[test]
+fn test_data_cellref_order() {
+    use crate::repr::col_sheet::CellRef;
+
+    fn to_cell_ref(data: &Data) -> CellRef<'_> {
+        match data {
+            Data::Text(text) => CellRef::Text(text),
+            Data::Integer(int) => CellRef::I32(*int),
+            Data::Number(num) => CellRef::ISize(*num),
+            Data::Float(float) => CellRef::F32(*float),
+            Data::Boolean(bool) => CellRef::Bool(*bool),
+            Data::None => CellRef::None,
+        }
+    }
+
+    let values = vec![
+        Data::Text("b".into()),
+        Data::Float(2.5),
+        Data::None,
+        Data::Integer(3),
+        Data::Number(-7),
+        Data::Boolean(true),
+        Data::Text("a".into()),
+        Data::Float(3.0),
+        Data::Boolean(false),
+        Data::Number(3),
+        Data::Integer(-100),
+        Data::Float(f32::NEG_INFINITY),
+        Data::None,
+    ];
+
+    let mut sorted = values.clone();
+    sorted.sort();
+
+    assert_eq!(
+        sorted,
+        vec![
+            Data::None,
+            Data::None,
+            Data::Boolean(false),
+            Data::Boolean(true),
+            Data::Float(f32::NEG_INFINITY),
+            Data::Integer(-100),
+            Data::Number(-7),
+            Data::Float(2.5),
+            Data::Integer(3),
+            Data::Number(3),
+            Data::Float(3.0),
+            Data::Text("a".into()),
+            Data::Text("b".into()),
+        ]
+    );
+
+    for x in values.iter() {
+        for y in values.iter() {
+            assert_eq!(
+                x.cmp(y),
+                to_cell_ref(x).cmp(&to_cell_ref(y)),
+                "{:?} and {:?} are ordered differently",
+                x,
+                y
+            );
+        }
+    }
+
+    assert!(CellRef::U32(5) > CellRef::I32(-1));
+    assert!(CellRef::I32(-1) < CellRef::U32(5));
+    assert!(CellRef::ISize(10) > CellRef::I32(2));
+    assert!(CellRef::USize(1) < CellRef::I32(2));
+}
+
+#[test]
+fn test_label_transform() {
+    use crate::models::LabelTransform;
+
+    let rows = vec![
+        vec!["Jan".into(), 1.into(), 2.into(), 3.into()],
+        vec!["JAN ".into(), 4.into(), 5.into(), 6.into()],
+        vec![" feb".into(), 7.into(), 8.into(), 9.into()],
+    ];
+    let headers = vec![
+        ColumnHeader::new("month".into(), ColumnType::Text),
+        ColumnHeader::new(" coffee".into(), ColumnType::Integer),
+        ColumnHeader::new("Coffee".into(), ColumnType::Integer),
+        ColumnHeader::new("soda ".into(), ColumnType::Integer),
+    ];
+    let sht = Sheet::from_rows(rows, headers, 0).unwrap();
+
+    let mut line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            HashSet::default(),
+            HashSet::default(),
+        )
+        .unwrap();
+
+    assert!(line.transform_labels(&LabelTransform::Trim).is_empty());
+    assert_eq!(
+        line.transform_labels(&LabelTransform::Titlecase),
+        vec![String::from("Jan")]
+    );
+    let labels = line
+        .lines
+        .iter()
+        .map(|ln| ln.label.clone().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(labels, vec!["Jan", "Jan", "Feb"]);
+
+    let mut bar = sht
+        .clone()
+        .create_bar_chart(
+            1,
+            2,
+            BarChartBarLabels::FromColumn(0),
+            BarChartAxisLabelStrategy::None,
+            HashSet::default(),
+        )
+        .unwrap();
+
+    fn shout(label: &str) -> String {
+        label.trim().to_uppercase()
+    }
+
+    assert_eq!(
+        bar.transform_labels(&LabelTransform::Custom(shout)),
+        vec![String::from("JAN")]
+    );
+    assert_eq!(bar.bars[2].label, Some("FEB".into()));
+
+    let mut stacked = sht
+        .clone()
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
+        .unwrap();
+
+    assert!(stacked.transform_labels(&LabelTransform::Trim).is_empty());
+    assert_eq!(
+        stacked.transform_labels(&LabelTransform::Lowercase),
+        vec![String::from("coffee")]
+    );
+    assert_eq!(
+        stacked.labels,
+        HashSet::from([String::from("coffee"), String::from("soda")])
+    );
+
+    let fractions = stacked.bars[0].get_fractions();
+    assert_eq!(fractions.len(), 2);
+    assert_eq!(fractions.get("coffee"), Some(&0.5));
+
+    // Transforms given with the label source apply as labels are read
+    let line = sht
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCellWith(0, LabelTransform::Titlecase),
+            HashSet::default(),
+            HashSet::default(),
+        )
+        .unwrap();
+    let labels = line
+        .lines
+        .iter()
+        .map(|ln| ln.label.clone().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(labels, vec!["Jan", "Jan ", " Feb"]);
+    assert!(line.duplicate_labels().is_empty());
+
+    let bar = sht
+        .clone()
+        .create_bar_chart(
+            1,
+            2,
+            BarChartBarLabels::FromColumnWith(0, LabelTransform::Custom(shout)),
+            BarChartAxisLabelStrategy::None,
+            HashSet::default(),
+        )
+        .unwrap();
+    assert_eq!(bar.bars[1].label, Some("JAN".into()));
+    assert_eq!(bar.duplicate_labels(), vec![String::from("JAN")]);
+
+    let context = ChartContext::new().label_transform(LabelTransform::Custom(shout));
+    let charts = sht.chart(&context).unwrap();
+
+    let line = charts.line_graph(LineLabelStrategy::FromCell(0)).unwrap();
+    assert_eq!(line.duplicate_labels(), vec![String::from("JAN")]);
+    assert_eq!(line.lines[2].label, Some("FEB".into()));
+
+    // A transform of the label source takes precedence over the context
+    let line = charts
+        .line_graph(LineLabelStrategy::FromCellWith(0, LabelTransform::Trim))
+        .unwrap();
+    assert_eq!(line.lines[1].label, Some("JAN".into()));
+    assert_eq!(line.lines[2].label, Some("feb".into()));
+
+    let bar = charts
+        .bar_chart(1, 2, BarChartBarLabels::Provided(vec!["a ".into()]))
+        .unwrap();
+    assert_eq!(bar.bars[0].label, Some("A".into()));
+
+    // Sections whose headers share a label once transformed are combined
+    let stacked = charts.stacked_bar_chart(0, [1, 2, 3]).unwrap();
+    assert_eq!(
+        stacked.labels,
+        HashSet::from([String::from("COFFEE"), String::from("SODA")])
+    );
+    let bar = &stacked.bars[0];
+    assert_eq!(bar.section_exact("COFFEE"), Some(&Data::Integer(3)));
+    assert_eq!(bar.section_exact("SODA"), Some(&Data::Integer(3)));
+    assert_eq!(bar.fraction("COFFEE"), Some(0.5));
+}
+
+#[test]
+fn test_validation_api() {
+    use crate::models::ChartKind;
+
+    let path: PathBuf = "./dummies/csv/infer.csv".into();
+
+    let config = Config::new(path)
+        .labels(HeaderStrategy::ReadLabels)
+        .trim(true)
+        .types(TypesStrategy::Infer);
+
+    let sheet = Sheet::with_config(config).unwrap();
+
+    assert_eq!(sheet.header(1).map(|hdr| hdr.label.as_str()), Some("Year"));
+    assert_eq!(sheet.header(6), None);
+    assert_eq!(sheet.column_kind(1), Some(ColumnType::Integer));
+    assert_eq!(sheet.column_kind(2), Some(ColumnType::Float));
+    assert_eq!(sheet.column_kind(3), Some(ColumnType::Mixed));
+    assert_eq!(sheet.column_kind(6), None);
+
+    assert!(sheet.validate_col(1).is_ok());
+    assert!(sheet.validate_col(6).is_err());
+
+    assert!(sheet.check_col_as(1, ColumnType::Integer).is_ok());
+    assert!(sheet.check_col_as(0, ColumnType::Integer).is_err());
+    assert!(sheet.check_col_as(3, ColumnType::Integer).is_err());
+    assert!(sheet.check_col_as(6, ColumnType::Text).is_err());
+
+    assert_eq!(
+        sheet.compatible_charts(&[0, 1]),
+        vec![ChartKind::Bar, ChartKind::StackedBar]
+    );
+    assert_eq!(sheet.compatible_charts(&[1]), vec![ChartKind::Line]);
+    assert_eq!(sheet.compatible_charts(&[0, 1, 2]), Vec::<ChartKind>::new());
+    assert_eq!(
+        sheet.compatible_charts(&[0, 5]),
+        vec![ChartKind::Line, ChartKind::Bar]
+    );
+    assert!(sheet.compatible_charts(&[0, 3]).is_empty());
+    assert!(sheet.compatible_charts(&[]).is_empty());
+    assert!(sheet.compatible_charts(&[0, 9]).is_empty());
+}
+
+#[test]
+fn test_quantiles() {
+    let rows = vec![
+        vec!["a".into(), 2.into(), 1.5.into(), "x".into()],
+        vec!["b".into(), 10.into(), Data::None, 1.into()],
+        vec!["c".into(), 2.into(), 1.5.into(), "y".into()],
+        vec!["d".into(), 1.into(), 4.0.into(), 2.into()],
+        vec!["e".into(), Data::None, Data::None, 3.into()],
+        vec!["f".into(), 3.into(), Data::None, 4.into()],
+        vec!["g".into(), 2.into(), Data::None, 5.into()],
+        vec!["h".into(), 4.into(), Data::None, 6.into()],
+    ];
+    let headers = vec![
+        ColumnHeader::new("Label".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+        ColumnHeader::new("Sparse".into(), ColumnType::Float),
+        ColumnHeader::new("Mixed".into(), ColumnType::None),
+    ];
+    let sheet = Sheet::from_rows(rows, headers, 0).unwrap();
+
+    // Values: 1, 2, 2, 2, 3, 4, 10
+    let quantiles = sheet
+        .quantiles(1, &[0.0, 0.25, 0.5, 0.75, 0.9, 1.0])
+        .unwrap();
+    assert_eq!(
+        quantiles,
+        vec![
+            Data::Integer(1),
+            Data::Integer(2),
+            Data::Integer(2),
+            Data::Float(3.5),
+            Data::Float(6.4),
+            Data::Integer(10),
+        ]
+    );
+
+    // Values: 1.5, 1.5, 4.0
+    let quantiles = sheet.quantiles(2, &[0.0, 0.25, 0.5, 0.75, 1.0]).unwrap();
+    assert_eq!(
+        quantiles,
+        vec![
+            Data::Float(1.5),
+            Data::Float(1.5),
+            Data::Float(1.5),
+            Data::Float(2.75),
+            Data::Float(4.0),
+        ]
+    );
+
+    assert!(sheet.quantiles(1, &[]).unwrap().is_empty());
+    assert!(sheet.quantiles(1, &[1.5]).is_err());
+    assert!(sheet.quantiles(0, &[0.5]).is_err());
+    assert!(sheet.quantiles(3, &[0.5]).is_err());
+    assert!(sheet.quantiles(4, &[0.5]).is_err());
+
+    assert_eq!(
+        sheet.percentile_rank(1, &Data::Integer(2)).unwrap(),
+        400.0 / 7.0
+    );
+    assert_eq!(sheet.percentile_rank(1, &Data::Float(0.5)).unwrap(), 0.0);
+    assert_eq!(sheet.percentile_rank(1, &Data::Number(10)).unwrap(), 100.0);
+    assert!(sheet.percentile_rank(1, &Data::Text("2".into())).is_err());
+    assert!(sheet.percentile_rank(0, &Data::Integer(2)).is_err());
+
+    let barchart = sheet
+        .create_bar_chart_with(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::from([4]),
+            ScaleOptions::new().clamp_percentiles(0.0, 90.0),
+        )
+        .unwrap();
+
+    // Outliers keep their values, off the clamped view but on the scale
+    let ys = barchart
+        .bars
+        .iter()
+        .map(|bar| bar.point.y.clone())
+        .collect::<Vec<Data>>();
+    assert_eq!(
+        ys,
+        vec![
+            Data::Integer(2),
+            Data::Integer(10),
+            Data::Integer(2),
+            Data::Integer(1),
+            Data::Integer(3),
+            Data::Integer(2),
+            Data::Integer(4),
+        ]
+    );
+    let view = barchart.y_view.as_ref().unwrap();
+    assert!(view.contains(&Data::Integer(6)));
+    assert!(!view.contains(&Data::Integer(10)));
+    assert!(barchart.y_scale.contains(&Data::Integer(10)));
+}
+
+#[test]
+fn test_box_plot() {
+    let rows = vec![
+        vec!["A".into(), 4.into()],
+        vec!["B".into(), 2.into()],
+        vec!["A".into(), 2.into()],
+        vec!["A".into(), 30.into()],
+        vec!["A".into(), 1.into()],
+        vec!["A".into(), 5.into()],
+        vec!["B".into(), Data::None],
+        vec!["A".into(), 3.into()],
+        vec!["A".into(), 8.into()],
+        vec!["B".into(), 4.into()],
+        vec!["A".into(), 7.into()],
+        vec!["A".into(), 6.into()],
+    ];
+    let headers = vec![
+        ColumnHeader::new("Group".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let sheet = Sheet::from_rows(rows, headers, 0).unwrap();
+
+    let plot = sheet.create_box_plot(Some(0), 1).unwrap();
+
+    assert_eq!(plot.x_label, Some("Group".into()));
+    assert_eq!(plot.y_label, Some("Value".into()));
+    assert_eq!(plot.items.len(), 2);
+
+    let a = &plot.items[0];
+    assert_eq!(a.label, Some("A".into()));
+    assert_eq!(a.min, Data::Float(1.0));
+    assert_eq!(a.q1, Data::Float(3.0));
+    assert_eq!(a.median, Data::Float(5.0));
+    assert_eq!(a.q3, Data::Float(7.0));
+    assert_eq!(a.max, Data::Float(8.0));
+    assert_eq!(a.outliers, vec![Data::Float(30.0)]);
+
+    // Fewer than 4 values
+    let b = &plot.items[1];
+    assert_eq!(b.label, Some("B".into()));
+    assert_eq!(b.min, Data::Float(2.0));
+    assert_eq!(b.q1, Data::Float(2.5));
+    assert_eq!(b.median, Data::Float(3.0));
+    assert_eq!(b.q3, Data::Float(3.5));
+    assert_eq!(b.max, Data::Float(4.0));
+    assert!(b.outliers.is_empty());
+
+    assert!(plot.y_scale.contains(&Data::Float(1.0)));
+    assert!(plot.y_scale.contains(&Data::Float(30.0)));
+
+    let plot = sheet.create_box_plot(None, 1).unwrap();
+    assert_eq!(plot.x_label, None);
+    assert_eq!(plot.items.len(), 1);
+    assert_eq!(plot.items[0].label, None);
+    // Sorted: 1, 2, 2, 3, 4, 4, 5, 6, 7, 8, 30
+    assert_eq!(plot.items[0].median, Data::Float(4.0));
+    assert_eq!(plot.items[0].outliers, vec![Data::Float(30.0)]);
+
+    assert!(sheet.create_box_plot(None, 0).is_err());
+    assert!(sheet.create_box_plot(Some(2), 1).is_err());
+}
+
+#[test]
+fn test_heatmap() {
+    let path: PathBuf = "./dummies/csv/correlation.csv".into();
+
+    let config = Config::new(path)
+        .labels(HeaderStrategy::ReadLabels)
+        .trim(true)
+        .types(TypesStrategy::Infer);
+
+    let sheet = Sheet::with_config(config).unwrap();
+
+    let heatmap = sheet.create_heatmap(0, &[1, 3]).unwrap();
+
+    assert_eq!(heatmap.height(), 6);
+    assert_eq!(heatmap.width(), 2);
+    assert_eq!(heatmap.row_labels[5], "f");
+    assert_eq!(heatmap.col_labels, vec!["X", "Z"]);
+    assert_eq!(heatmap.get(1, 1), Some(&Data::Integer(3)));
+    assert_eq!(heatmap.get(5, 0), Some(&Data::None));
+
+    assert!(sheet.create_heatmap(0, &[0, 1]).is_err());
+    assert!(sheet.create_heatmap(7, &[1]).is_err());
+
+    // Rows with a missing value are ignored pairwise, so X is correlated over
+    // the first five rows only.
+    let corr = sheet.correlation_matrix(&[1, 2, 3]).unwrap();
+
+    assert_eq!(corr.row_labels, vec!["X", "Y", "Z"]);
+    assert_eq!(corr.col_labels, vec!["X", "Y", "Z"]);
+    assert_eq!(corr.get(0, 0), Some(&Data::Float(1.0)));
+    assert_eq!(corr.get(0, 1), Some(&Data::Float(1.0)));
+    assert_eq!(corr.get(0, 2), Some(&Data::Float(-0.8)));
+    assert_eq!(corr.get(2, 0), Some(&Data::Float(-0.8)));
+
+    // Y = 2, 4, 6, 8, 10, 12 and Z = 5, 3, 4, 1, 2, 7
+    // cov = 4, var(Y) = 70, var(Z) = 104 - 22 * 22 / 6
+    let expected = 4.0 / (70.0_f64 * (104.0 - 484.0 / 6.0)).sqrt();
+    assert_eq!(corr.get(1, 2), Some(&Data::Float(expected as f32)));
+
+    assert!(sheet.correlation_matrix(&[0, 1]).is_err());
+}
+
+#[test]
+fn test_top_n() {
+    use crate::models::{BarChartError, TopN, TopNOrdering};
+
+    let rows = vec![
+        vec!["a".into(), 5.into()],
+        vec!["b".into(), 40.into()],
+        vec!["c".into(), 1.into()],
+        vec!["d".into(), 30.into()],
+        vec!["e".into(), Data::None],
+        vec!["f".into(), 7.into()],
+        vec!["g".into(), 35.into()],
+    ];
+    let headers = vec![
+        ColumnHeader::new("Name".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let sheet = Sheet::from_rows(rows, headers, 0).unwrap();
+
+    let create = |top_n: TopN| {
+        sheet.clone().create_bar_chart_with(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+            ScaleOptions::new().top_n(top_n),
+        )
+    };
+
+    let barchart = create(TopN::new(3, "Other")).unwrap();
+    let points = barchart
+        .bars
+        .iter()
+        .map(|bar| (bar.point.x.clone(), bar.point.y.clone()))
+        .collect::<Vec<(Data, Data)>>();
+
+    assert_eq!(
+        points,
+        vec![
+            ("b".into(), 40.into()),
+            ("g".into(), 35.into()),
+            ("d".into(), 30.into()),
+            ("Other".into(), 13.into()),
+        ]
+    );
+    assert_eq!(barchart.bars[3].label, Some("Other".into()));
+    assert!(barchart.x_scale.is_categorical());
+    assert_eq!(
+        barchart.x_scale.points(),
+        vec!["b".into(), "g".into(), "d".into(), "Other".into()]
+    );
+    assert!(barchart.y_scale.contains(&Data::Integer(40)));
+
+    let barchart = create(TopN::new(3, "Rest").ordering(TopNOrdering::ByLabel)).unwrap();
+    let xs = barchart
+        .bars
+        .iter()
+        .map(|bar| bar.point.x.clone())
+        .collect::<Vec<Data>>();
+    assert_eq!(xs, vec!["b".into(), "d".into(), "g".into(), "Rest".into()]);
+
+    // Nothing to merge
+    let barchart = create(TopN::new(10, "Other")).unwrap();
+    assert_eq!(barchart.bars.len(), 7);
+    assert!(!barchart.x_scale.contains(&Data::Text("Other".into())));
+
+    let res = sheet.create_bar_chart_with(
+        1,
+        0,
+        BarChartBarLabels::None,
+        BarChartAxisLabelStrategy::None,
+        HashSet::new(),
+        ScaleOptions::new().top_n(TopN::new(3, "Other")),
+    );
+    assert!(res.is_err());
+
+    // Repeated categories are summed before ranking
+    let rows = vec![
+        vec!["a".into(), 5.into()],
+        vec!["b".into(), 8.into()],
+        vec!["a".into(), 6.into()],
+        vec!["c".into(), 2.into()],
+        vec!["Other".into(), 1.into()],
+    ];
+    let headers = vec![
+        ColumnHeader::new("Name".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let sheet = Sheet::from_rows(rows, headers.clone(), 0).unwrap();
+    let create = |sheet: &Sheet, top_n: TopN| {
+        sheet.clone().create_bar_chart_with(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+            ScaleOptions::new().top_n(top_n),
+        )
+    };
+
+    let barchart = create(&sheet, TopN::new(2, "Rest")).unwrap();
+    let points = barchart
+        .bars
+        .iter()
+        .map(|bar| (bar.point.x.clone(), bar.point.y.clone()))
+        .collect::<Vec<(Data, Data)>>();
+    assert_eq!(
+        points,
+        vec![
+            ("a".into(), 11.into()),
+            ("b".into(), 8.into()),
+            ("Rest".into(), 3.into()),
+        ]
+    );
+
+    assert!(matches!(
+        create(&sheet, TopN::new(2, "Other")),
+        Err(Error::BarChartError(BarChartError::OtherLabelTaken(label))) if label == "Other"
+    ));
+    assert!(create(&sheet, TopN::new(4, "Other")).is_ok());
+
+    let rows = vec![
+        vec!["a".into(), i32::MAX.into()],
+        vec!["b".into(), i32::MAX.into()],
+        vec!["c".into(), 1.into()],
+        vec!["d".into(), 1.into()],
+    ];
+    let sheet = Sheet::from_rows(rows, headers, 0).unwrap();
+    assert!(matches!(
+        create(&sheet, TopN::new(1, "Other")),
+        Err(Error::BarChartError(BarChartError::InvalidSum(..)))
+    ));
+}
+
+#[test]
+fn test_stacked_bar_chart_long() {
+    let read = |path: &str| {
+        let config = Config::new(PathBuf::from(path))
+            .labels(HeaderStrategy::ReadLabels)
+            .trim(true)
+            .types(TypesStrategy::Infer);
+
+        Sheet::with_config(config).unwrap()
+    };
+
+    let wide = read("./dummies/csv/stacked.csv")
+        .create_stacked_bar_chart(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::Header("Total".into()),
+            HashSet::new(),
+        )
+        .unwrap();
+
+    let long = read("./dummies/csv/stacked_long.csv");
+    let stacked = long
+        .create_stacked_bar_chart_long(
+            0,
+            1,
+            2,
+            StackedBarChartAxisLabelStrategy::Provided {
+                x: "Day of Week".into(),
+                y: "Total".into(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(stacked, wide);
+
+    // Negatives
+    let headers = vec![
+        ColumnHeader::new("X".into(), ColumnType::Text),
+        ColumnHeader::new("A".into(), ColumnType::Integer),
+        ColumnHeader::new("B".into(), ColumnType::Integer),
+    ];
+    let wide = Sheet::from_rows(
+        vec![
+            vec!["one".into(), 4.into(), (-2).into()],
+            vec!["two".into(), (-3).into(), 0.into()],
+        ],
+        headers,
+        0,
+    )
+    .unwrap()
+    .create_stacked_bar_chart(
+        0,
+        [1, 2],
+        StackedBarChartAxisLabelStrategy::None,
+        HashSet::new(),
+    )
+    .unwrap();
+
+    let headers = vec![
+        ColumnHeader::new("X".into(), ColumnType::Text),
+        ColumnHeader::new("Section".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let long = Sheet::from_rows(
+        vec![
+            vec!["one".into(), "A".into(), 4.into()],
+            vec!["one".into(), "B".into(), (-3).into()],
+            vec!["two".into(), "A".into(), (-3).into()],
+            vec!["one".into(), "B".into(), 1.into()],
+            vec!["two".into(), "B".into(), Data::None],
+        ],
+        headers,
+        0,
+    )
+    .unwrap();
+    let stacked = long
+        .create_stacked_bar_chart_long(0, 1, 2, StackedBarChartAxisLabelStrategy::None)
+        .unwrap();
+
+    assert_eq!(stacked, wide);
+    assert!(stacked.has_true_negatives());
+
+    assert!(long
+        .create_stacked_bar_chart_long(0, 2, 1, StackedBarChartAxisLabelStrategy::None)
+        .is_err());
+
+    // Repeated pairs summing past the type of the column
+    let headers = vec![
+        ColumnHeader::new("X".into(), ColumnType::Text),
+        ColumnHeader::new("Section".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Integer),
+    ];
+    let long = Sheet::from_rows(
+        vec![
+            vec!["one".into(), "A".into(), i32::MAX.into()],
+            vec!["one".into(), "A".into(), 1.into()],
+        ],
+        headers,
+        0,
+    )
+    .unwrap();
+    assert!(matches!(
+        long.create_stacked_bar_chart_long(0, 1, 2, StackedBarChartAxisLabelStrategy::None),
+        Err(Error::ConversionError(_))
+    ));
+}
+
+#[test]
+fn test_mixed_columns() {
+    let path: PathBuf = "./dummies/csv/mixed.csv".into();
+
+    let config = Config::new(path)
+        .labels(HeaderStrategy::ReadLabels)
+        .trim(true)
+        .types(TypesStrategy::Infer);
+
+    let sheet = Sheet::with_config(config).unwrap();
+
+    assert_eq!(sheet.column_kind(0), Some(ColumnType::Integer));
+    assert_eq!(sheet.column_kind(1), Some(ColumnType::None));
+    assert_eq!(sheet.column_kind(2), Some(ColumnType::Mixed));
+    assert_eq!(sheet.column_kind(3), Some(ColumnType::Text));
+
+    assert!(ColumnType::Mixed.crosscheck_type(&Data::Integer(3)));
+    assert!(ColumnType::None.crosscheck_type(&Data::Boolean(true)));
+    assert!(!ColumnType::Mixed.is_sortable());
+    assert!(ColumnType::None.is_sortable());
+    assert_eq!("Mixed".parse::<ColumnType>(), Ok(ColumnType::Mixed));
+
+    let err = sheet
+        .clone()
+        .create_bar_chart(
+            3,
+            2,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Conversion Error: Cannot convert column `Mixed` of mixed types: float, integer, boolean"
+    );
+
+    let err = sheet
+        .clone()
+        .create_bar_chart(
+            3,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Conversion Error: Cannot convert column `Empty` of unknown type"
+    );
+
+    let err = sheet
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::None,
+            HashSet::new(),
+            HashSet::from([0, 1, 3]),
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("mixed types: float, integer, boolean"));
+}
+
+#[test]
+fn test_descending_scale() {
+    let sheet = create_in_memory();
+
+    let barchart = sheet
+        .clone()
+        .create_bar_chart_with(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+            ScaleOptions::new().descending(true),
+        )
+        .unwrap();
+
+    assert!(barchart.y_scale.is_descending());
+    assert!(!barchart.x_scale.is_descending());
+    let points = barchart.y_scale.points();
+    assert!(points.first() > points.last());
+
+    let line = sheet
+        .create_line_graph_labelled_with(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            HashSet::new(),
+            HashSet::new(),
+            ScaleOptions::new().descending(true),
+        )
+        .unwrap();
+
+    assert!(line.y_scale.is_descending());
+}
+
+#[test]
+fn test_chart_hashing() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let read = || {
+        let config = Config::new(PathBuf::from("./dummies/csv/stacked.csv"))
+            .labels(HeaderStrategy::ReadLabels)
+            .trim(true)
+            .types(TypesStrategy::Infer);
+
+        Sheet::with_config(config).unwrap()
+    };
+
+    let stacked = || {
+        read()
+            .create_stacked_bar_chart(
+                0,
+                [1, 2, 3, 4],
+                StackedBarChartAxisLabelStrategy::None,
+                HashSet::new(),
+            )
+            .unwrap()
+    };
+
+    let (first, second) = (stacked(), stacked());
+    assert_eq!(first, second);
+    assert_eq!(hash(&first), hash(&second));
+
+    let sheet = create_in_memory();
+    let line = |sheet: Sheet| {
+        sheet
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::None,
+                LineLabelStrategy::FromCell(0),
+                HashSet::new(),
+                HashSet::new(),
+            )
+            .unwrap()
+    };
+
+    let (first, second) = (line(sheet.clone()), line(sheet.clone()));
+    assert_eq!(first, second);
+    assert_eq!(hash(&first), hash(&second));
+
+    let bar = |sheet: Sheet| {
+        sheet
+            .create_bar_chart(
+                0,
+                1,
+                BarChartBarLabels::None,
+                BarChartAxisLabelStrategy::None,
+                HashSet::new(),
+            )
+            .unwrap()
+    };
+
+    // The category index of a scale is a cache left out of comparisons and
+    // hashing, so charts are safe to use as keys
+    #[allow(clippy::mutable_key_type)]
+    let charts = [bar(sheet.clone()), bar(sheet.clone()), bar(sheet)]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(charts.len(), 1);
+}
+
+#[test]
+fn test_float_equality() {
+    assert_eq!(Data::Float(f32::NAN), Data::Float(f32::NAN));
+    assert_ne!(Data::Float(0.0), Data::Float(-0.0));
+    assert_ne!(Data::Float(1.0), Data::Integer(1));
+}
+
+#[test]
+fn test_row_selector() {
+    let tuesday = || RowSelector::Keys(HashSet::from([Data::from("Tue")]));
+
+    let unsorted = create_in_memory();
+    let mut sorted = create_in_memory();
+    sorted.sort_rows_rev(1).unwrap();
+
+    let bars = |sheet: Sheet, selector: RowSelector| {
+        sheet
+            .create_bar_chart(
+                0,
+                1,
+                BarChartBarLabels::None,
+                BarChartAxisLabelStrategy::None,
+                selector,
+            )
+            .unwrap()
+            .bars
+            .into_iter()
+            .map(|bar| bar.point.x)
+            .collect::<HashSet<Data>>()
+    };
+
+    let expected = HashSet::from([Data::from("Mon"), Data::from("Wed")]);
+    assert_eq!(bars(unsorted.clone(), tuesday()), expected);
+    assert_eq!(bars(sorted.clone(), tuesday()), expected);
+    assert_eq!(bars(unsorted.clone(), HashSet::from([1]).into()), expected);
+    // Positions change with sorting
+    assert_ne!(bars(sorted.clone(), HashSet::from([1]).into()), expected);
+
+    let predicate = || {
+        RowSelector::Predicate(Box::new(|row| {
+            row.get_cell_by_index(1).unwrap().data > Data::Integer(9)
+        }))
+    };
+    assert_eq!(bars(sorted.clone(), predicate()), expected);
+
+    let lines = |sheet: &Sheet, selector: RowSelector| {
+        sheet
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::None,
+                LineLabelStrategy::FromCell(0),
+                selector,
+                HashSet::new(),
+            )
+            .unwrap()
+            .lines
+            .into_iter()
+            .filter_map(|line| line.label)
+            .collect::<HashSet<String>>()
+    };
+
+    let expected = HashSet::from(["Mon".to_string(), "Wed".to_string()]);
+    assert_eq!(lines(&unsorted, tuesday()), expected);
+    assert_eq!(lines(&sorted, tuesday()), expected);
+    assert_eq!(lines(&sorted, predicate()), expected);
+
+    let stacked = |sheet: Sheet, selector: RowSelector| {
+        sheet
+            .create_stacked_bar_chart(0, [1, 2], StackedBarChartAxisLabelStrategy::None, selector)
+            .unwrap()
+            .bars
+            .into_iter()
+            .map(|bar| bar.point.x)
+            .collect::<HashSet<Data>>()
+    };
+
+    let expected = HashSet::from([Data::from("Mon"), Data::from("Wed")]);
+    assert_eq!(stacked(unsorted, tuesday()), expected);
+    assert_eq!(stacked(sorted, tuesday()), expected);
+}
+
+#[test]
+fn test_blank_sheet() {
+    let headers = || {
+        vec![
+            ColumnHeader::new("Day".into(), ColumnType::Text),
+            ColumnHeader::new("Coffee".into(), ColumnType::Integer),
+        ]
+    };
+
+    let bar = |sheet: Sheet| {
+        sheet.create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            HashSet::new(),
+        )
+    };
+
+    let sheet = create_in_memory();
+    assert!(sheet.has_rows());
+    assert!(sheet.has_data());
+    assert!(!sheet.is_blank());
+    assert!(!sheet.is_empty());
+
+    let no_rows = Sheet::from_rows(Vec::new(), headers(), 0).unwrap();
+    assert!(!no_rows.has_rows());
+    assert!(!no_rows.has_data());
+    assert!(no_rows.is_blank());
+    assert!(no_rows.is_empty());
+    assert!(matches!(bar(no_rows.clone()), Err(Error::NoRows)));
+    assert!(matches!(
+        no_rows.create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::None,
+            HashSet::new(),
+            HashSet::new()
+        ),
+        Err(Error::NoRows)
+    ));
+
+    let rows = vec![vec![Data::None, Data::None]; 10];
+    let no_data = Sheet::from_rows(rows, headers(), 0).unwrap();
+    assert!(no_data.has_rows());
+    assert!(!no_data.has_data());
+    assert!(no_data.is_blank());
+    assert!(!no_data.is_empty());
+    assert!(no_data.compatible_charts(&[0, 1]).is_empty());
+    assert!(matches!(bar(no_data.clone()), Err(Error::NoData)));
+    assert!(matches!(
+        no_data.create_stacked_bar_chart(
+            0,
+            [1],
+            StackedBarChartAxisLabelStrategy::None,
+            HashSet::new()
+        ),
+        Err(Error::NoData)
+    ));
+}
+
+#[test]
+fn test_line_graph_axis_labels() {
+    let sheet = create_air_csv().unwrap();
+
+    let labels = |axis_labels: LineGraphAxisLabelStrategy, label_strat: LineLabelStrategy| {
+        let exclude = if label_strat == LineLabelStrategy::FromCell(0) {
+            HashSet::new()
+        } else {
+            HashSet::from([0])
+        };
+
+        let line = sheet
+            .create_line_graph_labelled(axis_labels, label_strat, HashSet::new(), exclude)
+            .unwrap();
+
+        (line.x_label, line.y_label)
+    };
+
+    assert_eq!(
+        labels(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0)
+        ),
+        (String::new(), String::new())
+    );
+
+    assert_eq!(
+        labels(
+            LineGraphAxisLabelStrategy::Headers {
+                y: "Passengers".into()
+            },
+            LineLabelStrategy::FromCell(0)
+        ),
+        ("Month".into(), "Passengers".into())
+    );
+
+    assert_eq!(
+        labels(
+            LineGraphAxisLabelStrategy::Headers {
+                y: "Passengers".into()
+            },
+            LineLabelStrategy::None
+        ),
+        (String::new(), "Passengers".into())
+    );
+
+    assert_eq!(
+        labels(
+            LineGraphAxisLabelStrategy::Provided {
+                x: "Year".into(),
+                y: "Passengers".into()
+            },
+            LineLabelStrategy::FromCell(0)
+        ),
+        ("Year".into(), "Passengers".into())
+    );
+}
+
+#[test]
+fn test_csv_fields() {
+    assert_eq!(Data::Float(1.0).to_csv_field(), "1.0");
+    assert_eq!(Data::Float(-0.0).to_csv_field(), "-0.0");
+    assert_eq!(Data::Float(2.5).to_csv_field(), "2.5");
+    assert_eq!(Data::None.to_csv_field(), "");
+
+    assert_eq!(
+        Data::from_csv_field("<None>", None),
+        Data::Text("<None>".into())
+    );
+    assert_eq!(Data::from("<None>"), Data::Text("<None>".into()));
+    assert_eq!(Data::from_csv_field("1", None), Data::Integer(1));
+    assert_eq!(
+        Data::from_csv_field("1", Some(ColumnType::Float)),
+        Data::Float(1.0)
+    );
+    assert_eq!(
+        Data::from_csv_field("1", Some(ColumnType::Text)),
+        Data::Text("1".into())
+    );
+    assert_eq!(
+        Data::from_csv_field("", Some(ColumnType::Integer)),
+        Data::None
+    );
+    // Falls back to inference
+    assert_eq!(
+        Data::from_csv_field("abc", Some(ColumnType::Integer)),
+        Data::Text("abc".into())
+    );
+
+    let rows = vec![
+        vec![Data::Text("<None>".into()), Data::Float(1.0)],
+        vec![Data::Text("a".into()), Data::Float(2.5)],
+    ];
+    let headers = vec![
+        ColumnHeader::new("Label".into(), ColumnType::Text),
+        ColumnHeader::new("Value".into(), ColumnType::Float),
+    ];
+    let sheet = Sheet::from_rows(rows, headers, 0).unwrap();
+
+    let transposed = Sheet::transpose(&sheet, None).unwrap();
+    assert_eq!(transposed.get_headers()[1].label, "<None>");
+    assert_eq!(
+        transposed
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(1)
+            .unwrap()
+            .data,
+        Data::Float(1.0)
+    );
+
+    let back = Sheet::transpose(&transposed, None).unwrap();
+    assert_eq!(back, sheet);
+}
+
+fn data_strategy() -> impl Strategy<Value = Data> {
+    prop_oneof![
+        Just(Data::None),
+        Just(Data::Float(f32::NAN)),
+        any::<bool>().prop_map(Data::Boolean),
+        any::<i32>().prop_map(Data::Integer),
+        any::<isize>().prop_map(Data::Number),
+        any::<f32>().prop_map(Data::Float),
+        ".*".prop_map(Data::Text),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_csv_field_round_trip(data in data_strategy()) {
+        let kind = ColumnType::from(data.clone());
+        let parsed = Data::from_csv_field(&data.to_csv_field(), Some(kind));
+
+        prop_assert_eq!(parsed, data);
+    }
+}
+
+#[test]
+fn test_preview() {
+    let path = "./dummies/csv/air.csv";
+    let config = || {
+        Config::new(path)
+            .trim(true)
+            .types(TypesStrategy::Infer)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+
+    let full = Sheet::with_config(config()).unwrap();
+    let (preview, info) = Sheet::preview_with_config(config(), 3).unwrap();
+
+    assert_eq!(preview.height(), 3);
+    assert_eq!(preview.get_headers(), full.get_headers());
+    assert_eq!(preview.get_row_by_index(2), full.get_row_by_index(2));
+    assert!(info.truncated);
+    assert_eq!(info.file_size, std::fs::metadata(path).unwrap().len());
+
+    let (preview, info) = Sheet::preview_with_config(config(), full.height()).unwrap();
+    assert_eq!(preview.height(), full.height());
+    assert!(!info.truncated);
+
+    let limited = Sheet::with_config(config().max_rows(Some(0))).unwrap();
+    assert!(!limited.has_rows());
+
+    let (preview, info) = Sheet::preview(path, 5).unwrap();
+    assert_eq!(preview.height(), 5);
+    assert!(info.truncated);
+}
+
+#[test]
+fn test_drop_null_cols() {
+    let config = || {
+        Config::new("./dummies/csv/sparse.csv")
+            .primary(1)
+            .types(TypesStrategy::Infer)
+            .labels(HeaderStrategy::ReadLabels)
+    };
+
+    let mut sheet = Sheet::with_config(config()).unwrap();
+    assert_eq!(sheet.width(), 5);
+
+    assert_eq!(sheet.drop_null_cols(0.8), vec![3, 4]);
+    assert_eq!(sheet.width(), 3);
+    assert_eq!(sheet.get_headers().len(), 3);
+    assert_eq!(sheet.get_primary_key(), Some(1));
+    sheet.validate().unwrap();
+
+    assert_eq!(sheet.drop_null_cols(0.75), vec![2]);
+    assert_eq!(sheet.header(1).unwrap().label, "Score");
+
+    let (header, data) = sheet.remove_col(0).unwrap();
+    assert_eq!(header.label, "Name");
+    assert_eq!(data.len(), 4);
+    assert_eq!(sheet.get_primary_key(), Some(0));
+    assert_eq!(
+        sheet.get_row_by_index(0).unwrap().get_primary_cell(),
+        sheet.get_row_by_index(0).unwrap().get_cell_by_index(0)
+    );
+    assert!(sheet.remove_col(1).is_err());
+
+    let sheet = Sheet::with_config(config().drop_empty_cols(true)).unwrap();
+    assert_eq!(sheet.width(), 3);
+    assert_eq!(sheet.column_kind(2), Some(ColumnType::Text));
+}
+
+#[cfg(feature = "trace")]
+#[test]
+#[tracing_test::traced_test]
+fn test_load_span() {
+    let sheet = create_air_csv().unwrap();
+    assert_eq!((sheet.height(), sheet.width()), (12, 4));
+
+    assert!(logs_contain("sheet_load{"));
+    assert!(logs_contain("rows=12 cols=4"));
+}
+
+#[test]
+fn test_units() {
+    let mut sheet = create_air_csv().unwrap();
+    let thousands = Unit::Custom("k".into());
+
+    assert!(sheet.set_col_unit(9, Some(Unit::Percent)).is_err());
+    for col in 1..4 {
+        sheet.set_col_unit(col, Some(thousands.clone())).unwrap();
+    }
+    assert_eq!(sheet.header(1).unwrap().axis_label(), "1958 (k)");
+
+    let line_labels = |sheet: &Sheet| {
+        let line = sheet
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::Headers {
+                    y: "Passengers".into(),
+                },
+                LineLabelStrategy::FromCell(0),
+                HashSet::new(),
+                HashSet::new(),
+            )
+            .unwrap();
+
+        (line.x_label, line.y_label)
+    };
+    assert_eq!(
+        line_labels(&sheet),
+        ("Month".into(), "Passengers (k)".into())
+    );
+
+    let transposed = Sheet::transpose(&sheet, None).unwrap();
+    assert_eq!(transposed.header(0).unwrap().unit, None);
+    assert!(transposed
+        .get_headers()
+        .iter()
+        .skip(1)
+        .all(|header| header.unit == Some(thousands.clone())));
+
+    sheet.set_col_unit(2, Some(Unit::Percent)).unwrap();
+    assert_eq!(line_labels(&sheet), ("Month".into(), "Passengers".into()));
+
+    let bar = sheet
+        .create_bar_chart(
+            0,
+            2,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::Headers,
+            HashSet::new(),
+        )
+        .unwrap();
+    assert_eq!(bar.x_label, Some("Month".into()));
+    assert_eq!(bar.y_label, Some("1959 (%)".into()));
+}
+
+#[test]
+fn test_iter_as() {
+    #[derive(Debug, PartialEq)]
+    struct AirRow {
+        month: String,
+        y1958: i32,
+        y1959: i32,
+        y1960: i32,
+    }
+
+    impl FromRow for AirRow {
+        fn from_row(row: &Row, headers: &[ColumnHeader]) -> Result<Self> {
+            Ok(Self {
+                month: row.get_str(headers, "Month")?.to_owned(),
+                y1958: row.get_i32(headers, "1958")?,
+                y1959: row.get(headers, "1959")?,
+                y1960: row.get(headers, "1960")?,
+            })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Grade {
+        name: String,
+        score: Option<i32>,
+        passed: Option<bool>,
+    }
+
+    impl FromRow for Grade {
+        fn from_row(row: &Row, headers: &[ColumnHeader]) -> Result<Self> {
+            Ok(Self {
+                name: row.get(headers, "Name")?,
+                score: row.get(headers, "Score")?,
+                passed: row.get(headers, "Passed")?,
+            })
+        }
+    }
+
+    let sheet = create_air_csv().unwrap();
+    let rows = sheet
+        .iter_as::<AirRow>()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(rows.len(), 12);
+    assert_eq!(
+        rows[0],
+        AirRow {
+            month: "JAN".into(),
+            y1958: 340,
+            y1959: 360,
+            y1960: 417,
+        }
+    );
+
+    let config = Config::new("./dummies/csv/nulls.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .null_string("NA");
+    let sheet = Sheet::with_config(config).unwrap();
+    let grades = sheet
+        .iter_as::<Grade>()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        grades[1],
+        Grade {
+            name: "Bob".into(),
+            score: None,
+            passed: Some(false),
+        }
+    );
+    assert_eq!(grades[2].passed, None);
+
+    let row = sheet.get_row_by_index(0).unwrap();
+    let headers = sheet.get_headers();
+    assert!(matches!(
+        row.get::<i32>(headers, "Missing"),
+        Err(Error::ConversionError(_))
+    ));
+    assert!(matches!(
+        row.get::<i32>(headers, "Name"),
+        Err(Error::InvalidColumnType(_))
+    ));
+    assert!(row.get_str(headers, "Score").is_err());
+    assert_eq!(
+        row.get::<Option<String>>(headers, "Name").unwrap(),
+        Some("Ada".into())
+    );
+}
+
+#[test]
+fn test_suggest_chart() {
+    use crate::models::ChartKind;
+
+    let load = |path: &str| {
+        let config = Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+        Sheet::with_config(config).unwrap()
+    };
+
+    let sheet = load("./dummies/csv/infer.csv");
+    let suggestion = sheet.suggest_chart();
+    assert_eq!(suggestion.kind, Some(ChartKind::Bar));
+    assert_eq!(suggestion.x_col, Some(0));
+    assert_eq!(suggestion.value_cols, vec![1]);
+    assert!(matches!(sheet.auto_chart(), Ok(AutoChart::Bar(_))));
+
+    let sheet = load("./dummies/csv/stacked.csv");
+    let suggestion = sheet.suggest_chart();
+    assert_eq!(suggestion.kind, Some(ChartKind::StackedBar));
+    assert_eq!(suggestion.x_col, Some(0));
+    assert_eq!(suggestion.value_cols, vec![1, 2, 3, 4]);
+    match sheet.auto_chart().unwrap() {
+        AutoChart::StackedBar(chart) => assert_eq!(chart.bars.len(), sheet.height()),
+        chart => panic!("Expected a stacked bar chart, but had {:?}", chart.kind()),
+    }
+
+    let sheet = create_air_csv().unwrap();
+    let suggestion = sheet.suggest_chart();
+    assert_eq!(suggestion.kind, Some(ChartKind::StackedBar));
+    assert_eq!(suggestion.x_col, Some(0));
+    assert_eq!(suggestion.value_cols, vec![1, 2, 3]);
+
+    let suggestion = suggest_chart(sheet.get_headers(), sheet.height());
+    assert_eq!(suggestion.kind, Some(ChartKind::Line));
+    assert!(matches!(
+        sheet
+            .create_line_graph_labelled(
+                LineGraphAxisLabelStrategy::None,
+                LineLabelStrategy::FromCell(0),
+                RowSelector::default(),
+                HashSet::new()
+            )
+            .map(|graph| graph.lines.len()),
+        Ok(12)
+    ));
+
+    let suggestion = suggest_chart(sheet.get_headers(), 0);
+    assert_eq!(
+        suggestion,
+        ChartSuggestion {
+            kind: None,
+            x_col: None,
+            value_cols: Vec::new(),
+            reason: "The sheet has no rows".into(),
+        }
+    );
+
+    let headers = vec![
+        ColumnHeader::new("x".into(), ColumnType::Integer),
+        ColumnHeader::new("y".into(), ColumnType::Integer),
+    ];
+    let suggestion = suggest_chart(&headers, 3);
+    assert_eq!(suggestion.kind, Some(ChartKind::Bar));
+    assert_eq!(suggestion.x_col, Some(0));
+    assert_eq!(suggestion.value_cols, vec![1]);
+
+    let headers = vec![ColumnHeader::new("Name".into(), ColumnType::Text)];
+    assert_eq!(suggest_chart(&headers, 3).kind, None);
+}
+
+#[test]
+fn test_update_where() {
+    let mut sheet = create_air_csv().unwrap();
+
+    let count = sheet
+        .update_where(
+            1,
+            |data| matches!(data, Data::Integer(value) if *value < 340),
+            |_| Data::Integer(0),
+        )
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        sheet
+            .get_row_by_index(1)
+            .unwrap()
+            .get_cell_by_index(1)
+            .unwrap()
+            .get_data(),
+        &Data::Integer(0)
+    );
+    assert_eq!(
+        sheet
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(1)
+            .unwrap()
+            .get_data(),
+        &Data::Integer(340)
+    );
+
+    let count = sheet
+        .update_where(2, |data| data == &Data::Integer(360), |_| Data::None)
+        .unwrap();
+    assert_eq!(count, 1);
+    assert!(sheet.validate().is_ok());
+
+    assert_eq!(sheet.update_where(3, |_| false, |_| Data::None).unwrap(), 0);
+
+    let before = sheet.clone();
+    let res = sheet.update_where(
+        3,
+        |data| matches!(data, Data::Integer(value) if *value > 500),
+        |data| Data::Text(data.to_string()),
+    );
+    assert!(matches!(res, Err(Error::InvalidColumnType(_))));
+    assert_eq!(sheet, before);
+
+    assert!(sheet
+        .update_where(4, |_| true, |data| data.clone())
+        .is_err());
+}
+
+#[test]
+fn test_flexible_header_width() {
+    let load = |path: &str| {
+        let config = Config::new(path)
+            .trim(true)
+            .flexible(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+        Sheet::with_config(config).unwrap()
+    };
+
+    let sheet = load("./dummies/csv/wide_header.csv");
+    let labels = sheet
+        .get_headers()
+        .iter()
+        .map(|hdr| hdr.label.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(labels, vec!["Name", "Age", "City", "Country"]);
+    assert_eq!(sheet.width(), 4);
+    assert!(sheet.iter_rows().all(|row| row.iter_cells().count() == 4));
+    assert_eq!(sheet.column_kind(3), Some(ColumnType::None));
+    assert!(sheet.validate().is_ok());
+    assert_eq!(sheet.load_report().header_width_mismatch, Some((4, 3)));
+
+    let sheet = load("./dummies/csv/narrow_header.csv");
+    let labels = sheet
+        .get_headers()
+        .iter()
+        .map(|hdr| hdr.label.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(labels, vec!["Name", "Age", "", ""]);
+    assert!(sheet.iter_rows().all(|row| row.iter_cells().count() == 4));
+    assert_eq!(sheet.load_report().header_width_mismatch, Some((2, 4)));
+
+    let sheet = load("./dummies/csv/air.csv");
+    assert_eq!(sheet.load_report().header_width_mismatch, None);
+}
+
+#[test]
+fn test_write_csv_round_trip() {
+    let config = Config::new("./dummies/csv/infer.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = Sheet::with_config(config).unwrap();
+
+    let mut buffer = Vec::new();
+    sheet.write_csv(&mut buffer).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.starts_with("Month,Year,Percentage,Mixed,Expected,Test\n"));
+    assert!(written.contains("MAR,2000,1.0,419,false,Else\n"));
+
+    let options = WriteOptions::new()
+        .delimiter(b';')
+        .decimal_separator(',')
+        .quote_style(QuoteStyle::Always)
+        .terminator(LineTerminator::Crlf)
+        .null_string("NA");
+    let mut buffer = Vec::new();
+    sheet.write_csv_with(&mut buffer, &options).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.contains("\"JAN\";\"1998\";\"0,24\""));
+    assert!(written.ends_with("\r\n"));
+
+    let config = Config::new("infer.csv")
+        .delimiter(b';')
+        .decimal_separator(',')
+        .null_string("NA")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let read = Sheet::from_reader(written.as_bytes(), config).unwrap();
+    assert_eq!(read, sheet);
+
+    // A comma decimal is quoted when it collides with the delimiter
+    let options = WriteOptions::new()
+        .decimal_separator(',')
+        .quote_style(QuoteStyle::Never)
+        .headers(false);
+    let mut buffer = Vec::new();
+    sheet.write_csv_with(&mut buffer, &options).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.starts_with("JAN,1998,\"0,24\",417,true,"));
+
+    let config = Config::new("infer.csv")
+        .decimal_separator(',')
+        .labels(HeaderStrategy::NoLabels)
+        .types(TypesStrategy::Infer);
+    let read = Sheet::from_reader(written.as_bytes(), config).unwrap();
+    assert_eq!(read.column_kind(2), Some(ColumnType::Float));
+    assert_eq!(read.height(), 3);
+}
+
+#[test]
+fn test_boolean_charts() {
+    let load = || {
+        let config = Config::new("./dummies/csv/attendance.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer);
+        Sheet::with_config(config).unwrap()
+    };
+    assert_eq!(load().column_kind(2), Some(ColumnType::Boolean));
+
+    let res = load().create_bar_chart(
+        0,
+        2,
+        BarChartBarLabels::None,
+        BarChartAxisLabelStrategy::Headers,
+        RowSelector::default(),
+    );
+    match res {
+        Err(Error::ConversionError(msg)) => assert!(msg.contains("bool_mode")),
+        _ => panic!("Expected a boolean column to be rejected"),
+    }
+
+    let chart = load()
+        .create_bar_chart_with(
+            0,
+            2,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::Headers,
+            RowSelector::default(),
+            ScaleOptions::new().bool_mode(BoolMode::CountTrue),
+        )
+        .unwrap();
+    let bars = chart
+        .bars
+        .iter()
+        .map(|bar| (bar.point.x.clone(), bar.point.y.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bars,
+        vec![
+            (Data::Integer(1), Data::Integer(2)),
+            (Data::Integer(2), Data::Integer(3)),
+            (Data::Integer(3), Data::Integer(1)),
+        ]
+    );
+    assert_eq!(chart.y_scale.kind(), ScaleKind::Integer);
+
+    let week_one = RowSelector::Predicate(Box::new(|row: &Row| {
+        row.get_cell_by_index(0).unwrap().get_data() != &Data::Integer(1)
+    }));
+    let chart = load()
+        .create_bar_chart_with(
+            1,
+            2,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::Headers,
+            week_one,
+            ScaleOptions::new().bool_mode(BoolMode::AsBinary),
+        )
+        .unwrap();
+    let values = chart
+        .bars
+        .iter()
+        .map(|bar| bar.point.y.clone())
+        .collect::<Vec<Data>>();
+    assert_eq!(values, [1, 0, 1].map(Data::Integer).to_vec());
+    assert_eq!(chart.y_scale.kind(), ScaleKind::Integer);
+
+    assert!(load()
+        .create_stacked_bar_chart(
+            1,
+            [2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+        )
+        .is_err());
+    assert!(load()
+        .create_stacked_bar_chart_with(
+            1,
+            [2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+            ScaleOptions::new().bool_mode(BoolMode::CountTrue),
+        )
+        .is_err());
+
+    let chart = load()
+        .create_stacked_bar_chart_with(
+            1,
+            [2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+            ScaleOptions::new().bool_mode(BoolMode::AsBinary),
+        )
+        .unwrap();
+    assert_eq!(chart.y_scale.kind(), ScaleKind::Integer);
+    let total = |group: usize| chart.pair(group).0.map(|bar| bar.point.y.clone());
+    assert_eq!(total(0), Some(Data::Integer(2)));
+    assert_eq!(total(2), Some(Data::Integer(1)));
+    assert_eq!(total(8), Some(Data::Integer(1)));
+}
+
+#[test]
+fn test_exclude_junk_column() {
+    let config = Config::new("./dummies/csv/junk_column.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = Sheet::with_config(config).unwrap();
+    assert_eq!(sheet.column_kind(1), Some(ColumnType::None));
+
+    let exclude = HashSet::from([1]);
+
+    assert!(sheet
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::new(),
+        )
+        .is_err());
+
+    let line = sheet
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::Headers { y: "Units".into() },
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            exclude.clone(),
+        )
+        .unwrap();
+    assert_eq!(line.lines.len(), 4);
+    assert!(line.lines.iter().all(|ln| ln.points.len() == 2));
+    assert!(!line.x_scale.contains(&Data::Text("Notes".into())));
+    assert_eq!(line.y_scale.kind(), ScaleKind::Integer);
+
+    let res = sheet.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::FromCell(1),
+        RowSelector::default(),
+        exclude.clone(),
+    );
+    assert!(matches!(res, Err(Error::ConversionError(_))));
+
+    let context = ChartContext::new()
+        .exclude_cols(exclude)
+        .bar_axis_labels(BarChartAxisLabelStrategy::Headers)
+        .stacked_axis_labels(StackedBarChartAxisLabelStrategy::Header("Units".into()));
+    let charts = sheet.chart(&context).unwrap();
+
+    let bar = charts
+        .bar_chart(0, 2, BarChartBarLabels::FromColumn(0))
+        .unwrap();
+    assert_eq!(bar.bars.len(), 4);
+
+    let res = charts.bar_chart(0, 2, BarChartBarLabels::FromColumn(1));
+    assert!(matches!(res, Err(Error::ConversionError(_))));
+
+    let stacked = charts.stacked_bar_chart(0, [2, 3]).unwrap();
+    assert_eq!(stacked.bars.len(), 4);
+    assert_eq!(stacked.bars[0].point.y, Data::Integer(124));
+
+    let res = charts.stacked_bar_chart(0, [1, 2, 3]);
+    assert!(matches!(res, Err(Error::ConversionError(_))));
+}
+
+#[test]
+fn test_include_zero() {
+    let sheet = create_air_csv().unwrap();
+    let bar = |options: ScaleOptions| {
+        sheet
+            .clone()
+            .create_bar_chart_with(
+                0,
+                1,
+                BarChartBarLabels::None,
+                BarChartAxisLabelStrategy::None,
+                RowSelector::default(),
+                options,
+            )
+            .unwrap()
+    };
+
+    // Bar charts include zero by default
+    let chart = bar(ScaleOptions::default());
+    assert_eq!(chart.y_scale.points()[0], Data::Integer(0));
+
+    let chart = bar(ScaleOptions::new().include_zero(false));
+    assert_eq!(chart.y_scale.points()[0], Data::Integer(310));
+
+    // Line graphs do not
+    let line = |options: ScaleOptions| {
+        sheet
+            .create_line_graph_labelled_with(
+                LineGraphAxisLabelStrategy::None,
+                LineLabelStrategy::FromCell(0),
+                RowSelector::default(),
+                HashSet::new(),
+                options,
+            )
+            .unwrap()
+    };
+
+    let graph = line(ScaleOptions::default());
+    assert_ne!(graph.y_scale.points()[0], Data::Integer(0));
+
+    let graph = line(ScaleOptions::new().include_zero(true));
+    assert_eq!(graph.y_scale.points()[0], Data::Integer(0));
+}
+
+#[test]
+fn test_preserve_original() {
+    let path = "./dummies/csv/originals.csv";
+    let expected = std::fs::read_to_string(path).unwrap();
+    let config = |preserve| {
+        Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .preserve_original(preserve)
+    };
+    let write = |sheet: &Sheet, options: &WriteOptions| {
+        let mut buffer = Vec::new();
+        sheet.write_csv_with(&mut buffer, options).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    let sheet = Sheet::with_config(config(false)).unwrap();
+    assert_eq!(
+        sheet
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(0)
+            .unwrap()
+            .get_data(),
+        &Data::Integer(7)
+    );
+    assert_eq!(sheet.original_text(0, 0), None);
+    assert_ne!(write(&sheet, &WriteOptions::default()), expected);
+
+    let mut sheet = Sheet::with_config(config(true)).unwrap();
+    assert_eq!(
+        sheet
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(0)
+            .unwrap()
+            .get_data(),
+        &Data::Integer(7)
+    );
+    assert_eq!(
+        sheet
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(1)
+            .unwrap()
+            .get_data(),
+        &Data::Text("alice".into())
+    );
+    assert_eq!(sheet.original_text(0, 0), Some("007"));
+    assert_eq!(sheet.original_text(0, 1), Some("  alice "));
+    assert_eq!(sheet.original_text(1, 2), None);
+    assert_eq!(sheet.original_text(0, 3), Some("1.50"));
+    assert_eq!(sheet.original_text(2, 0), Some(" 12 "));
+    assert_eq!(sheet.original_text(5, 0), None);
+    assert_eq!(write(&sheet, &WriteOptions::default()), expected);
+
+    // Floats follow the decimal separator they are written with
+    let written = write(&sheet, &WriteOptions::new().decimal_separator(';'));
+    assert!(written.contains(",1;5\n"));
+    assert!(written.starts_with("Id,Name,Score,Ratio\n007,"));
+
+    // Changed cells lose their text, even after sorting
+    sheet.sort_rows_rev(0).unwrap();
+    assert_eq!(sheet.original_text(2, 0), Some("007"));
+    sheet
+        .update_where(0, |data| data == &Data::Integer(7), |_| Data::Integer(8))
+        .unwrap();
+    assert_eq!(sheet.original_text(2, 0), None);
+    assert_eq!(sheet.original_text(2, 2), Some("042"));
+
+    // Null tokens are written with the null string of the writer
+    let config = Config::new("notes.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .null_string("NA")
+        .preserve_original(true);
+    let sheet = Sheet::from_reader("Id,Note\n1,NA\n2,x\n".as_bytes(), config).unwrap();
+    assert_eq!(sheet.original_text(0, 1), None);
+    assert_eq!(
+        write(&sheet, &WriteOptions::new().null_string("NULL")),
+        "Id,Note\n1,NULL\n2,x\n"
+    );
+}
+
+#[test]
+fn test_nan_policy() {
+    let load = |policy| {
+        let config = Config::new("./dummies/csv/nan.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .nan_policy(policy);
+        let mut sheet = Sheet::with_config(config).unwrap();
+        sheet.sort_rows(1).unwrap();
+        sheet
+    };
+    let column = |sheet: &Sheet, col: usize| {
+        sheet
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(col).unwrap().get_data().clone())
+            .collect::<Vec<Data>>()
+    };
+    let stacked = |sheet: Sheet| {
+        sheet
+            .create_stacked_bar_chart(
+                0,
+                [1, 2],
+                StackedBarChartAxisLabelStrategy::None,
+                RowSelector::default(),
+            )
+            .unwrap()
+    };
+    let is_nan = |data: &Data| matches!(data, Data::Float(fl) if fl.is_nan());
+
+    let sheet = load(NanPolicy::Keep);
+    assert_eq!(ColumnType::Float, sheet.get_headers()[1].kind);
+    let values = column(&sheet, 1);
+    assert_eq!(values[..3], [-2.5, 1.5, f32::INFINITY].map(Data::Float));
+    assert!(is_nan(&values[3]));
+
+    let chart = sheet
+        .clone()
+        .create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+        )
+        .unwrap();
+    assert_eq!(4, chart.bars.len());
+    assert!(chart.y_scale.contains(&Data::Float(f32::NAN)));
+    assert!(chart.y_scale.contains(&Data::Float(f32::INFINITY)));
+    assert!(chart.y_scale.contains(&Data::Float(-2.5)));
+    assert!(!chart.y_scale.contains(&Data::Float(2.0)));
+
+    // Rows with a NaN or infinite section have no total, so only "d" and "a"
+    let chart = stacked(sheet);
+    let xs = chart
+        .bars
+        .iter()
+        .map(|bar| &bar.point.x)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![&Data::from("d"), &Data::from("a")], xs);
+    assert!(chart
+        .bars
+        .iter()
+        .flat_map(|bar| bar.fractions.values())
+        .all(|fraction| fraction.is_finite()));
+
+    let sheet = load(NanPolicy::Null);
+    assert_eq!(ColumnType::Float, sheet.get_headers()[1].kind);
+    assert_eq!(
+        vec![
+            Data::None,
+            Data::Float(-2.5),
+            Data::Float(1.5),
+            Data::Float(f32::INFINITY)
+        ],
+        column(&sheet, 1)
+    );
+    assert!(!column(&sheet, 2).iter().any(is_nan));
+    assert!(!column(&sheet, 0).iter().any(|data| data == &Data::None));
+
+    // Empty sections add nothing, so "b" keeps a bar of only its other value
+    let chart = stacked(sheet);
+    let xs = chart
+        .bars
+        .iter()
+        .map(|bar| &bar.point.x)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![&Data::from("b"), &Data::from("d"), &Data::from("a")],
+        xs
+    );
+    assert_eq!(Some(&1.0), chart.bars[0].fractions.get("Other"));
+    assert_eq!(1, chart.bars[0].fractions.len());
+}
+
+#[test]
+fn test_text_col_ops() {
+    let config = Config::new("./dummies/csv/text_ops.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .trim(true);
+    let mut sheet = Sheet::with_config(config).unwrap();
+    let column = |sheet: &Sheet, col: usize| {
+        sheet
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(col).unwrap().get_data().clone())
+            .collect::<Vec<Data>>()
+    };
+
+    // "2023" is read as an integer, so the column is mixed. Uneven splits are
+    // padded, and each part is typed separately
+    let cols = sheet.split_col(0, "-", &["Year", "Quarter"]).unwrap();
+    assert_eq!(vec![3, 4], cols);
+    assert_eq!(5, sheet.width());
+    assert_eq!("Year", sheet.get_headers()[3].label);
+    assert_eq!(ColumnType::Integer, sheet.get_headers()[3].kind);
+    assert_eq!(ColumnType::Text, sheet.get_headers()[4].kind);
+    assert_eq!(
+        vec![
+            Data::Integer(2021),
+            Data::Integer(2022),
+            Data::Integer(2023),
+            Data::None
+        ],
+        column(&sheet, 3)
+    );
+    assert_eq!(
+        vec![Data::from("Q3"), Data::from("Q1"), Data::None, Data::None],
+        column(&sheet, 4)
+    );
+    sheet.validate().unwrap();
+
+    // The last part keeps the remainder
+    let cols = sheet.split_col(2, " ", &["First", "Rest"]).unwrap();
+    assert_eq!(vec![5, 6], cols);
+    assert_eq!(
+        vec![
+            Data::from("big"),
+            Data::from("small-room"),
+            Data::None,
+            Data::from("nice")
+        ],
+        column(&sheet, 5)
+    );
+    assert_eq!(Data::from("room"), column(&sheet, 6)[0]);
+    assert_eq!(Data::None, column(&sheet, 6)[1]);
+
+    let col = sheet
+        .extract_col(1, SimplePattern::Digits, "Number")
+        .unwrap();
+    assert_eq!(7, col);
+    assert_eq!(ColumnType::Integer, sheet.get_headers()[col].kind);
+    assert_eq!(
+        vec![
+            Data::Integer(12),
+            Data::Integer(7),
+            Data::None,
+            Data::Integer(30)
+        ],
+        column(&sheet, col)
+    );
+
+    let prefix = SimplePattern::Prefix("Room ".into());
+    let col = sheet.extract_col(1, prefix, "Code").unwrap();
+    assert_eq!(ColumnType::Mixed, sheet.get_headers()[col].kind);
+    assert_eq!(Data::Integer(7), column(&sheet, col)[1]);
+    assert_eq!(Data::from("30A"), column(&sheet, col)[3]);
+
+    let delimited = SimplePattern::Delimited {
+        start: "Room ".into(),
+        end: "B".into(),
+    };
+    let col = sheet.extract_col(1, delimited, "Floor").unwrap();
+    assert_eq!(
+        vec![Data::Integer(12), Data::None, Data::None, Data::None],
+        column(&sheet, col)
+    );
+    sheet.validate().unwrap();
+
+    assert_eq!(3, sheet.replace_in_col(2, "room", "hall").unwrap());
+    assert_eq!(
+        vec![
+            Data::from("big hall"),
+            Data::from("small-hall"),
+            Data::None,
+            Data::from("nice hall")
+        ],
+        column(&sheet, 2)
+    );
+    assert_eq!(0, sheet.replace_in_col(2, "", "x").unwrap());
+
+    let width = sheet.width();
+    assert!(matches!(
+        sheet.split_col(3, "-", &["A"]),
+        Err(Error::InvalidColumnType(_))
+    ));
+    assert!(matches!(
+        sheet.extract_col(3, SimplePattern::Digits, "A"),
+        Err(Error::InvalidColumnType(_))
+    ));
+    assert!(matches!(
+        sheet.replace_in_col(width, "a", "b"),
+        Err(Error::InvalidColumnLength(_))
+    ));
+    assert!(sheet.split_col(1, "", &["A"]).is_err());
+    assert_eq!(width, sheet.width());
+}
+
+#[test]
+fn test_simple_pattern() {
+    assert_eq!(
+        Some("12B"),
+        SimplePattern::Prefix("Room ".into()).capture("Room 12B")
+    );
+    assert_eq!(None, SimplePattern::Prefix("Room ".into()).capture("Suite"));
+    assert_eq!(
+        Some("2021"),
+        SimplePattern::Suffix("-Q3".into()).capture("2021-Q3")
+    );
+    assert_eq!(
+        Some("b"),
+        SimplePattern::Delimited {
+            start: "(".into(),
+            end: ")".into()
+        }
+        .capture("a (b) (c)")
+    );
+    assert_eq!(Some("12"), SimplePattern::Digits.capture("Room 12B"));
+    assert_eq!(None, SimplePattern::Digits.capture("Suite"));
+}
+
+#[test]
+fn test_encoding() {
+    let config = |encoding| {
+        Config::new("./dummies/csv/latin1.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .encoding(encoding)
+    };
+    let cell = |sheet: &Sheet, row: usize, col: usize| {
+        sheet
+            .get_row_by_index(row)
+            .unwrap()
+            .get_cell_by_index(col)
+            .unwrap()
+            .get_data()
+            .clone()
+    };
+
+    let (sheet, info) = Sheet::preview_with_config(config(Encoding::Latin1), 10).unwrap();
+    let labels = sheet
+        .get_headers()
+        .iter()
+        .map(|header| header.label.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(vec!["Nom", "Ville", "Âge"], labels);
+    assert_eq!(Data::from("José"), cell(&sheet, 0, 0));
+    assert_eq!(Data::from("Montréal"), cell(&sheet, 0, 1));
+    assert_eq!(Data::from("Zürich"), cell(&sheet, 1, 1));
+    assert_eq!(Data::Integer(29), cell(&sheet, 1, 2));
+    assert_eq!(ColumnType::Integer, sheet.get_headers()[2].kind);
+    assert_eq!(0, info.replaced);
+
+    assert!(Sheet::with_config(config(Encoding::Utf8)).is_err());
+
+    // Every accented character is a lone invalid byte in UTF-8
+    let (sheet, info) = Sheet::preview_with_config(config(Encoding::Utf8Lossy), 10).unwrap();
+    assert_eq!(5, info.replaced);
+    assert_eq!(5, sheet.load_report().replaced);
+    assert_eq!("\u{FFFD}ge", sheet.get_headers()[2].label);
+    assert_eq!(Data::from("Jos\u{FFFD}"), cell(&sheet, 0, 0));
+
+    let sheet = Sheet::with_config(config(Encoding::Utf8Lossy)).unwrap();
+    assert_eq!(5, sheet.load_report().replaced);
+
+    let (_, info) = Sheet::preview_with_config(
+        Config::new("./dummies/csv/air.csv").encoding(Encoding::Utf8Lossy),
+        10,
+    )
+    .unwrap();
+    assert_eq!(0, info.replaced);
+}
+
+#[test]
+fn test_encoding_split_reads() {
+    /// Yields a single byte per read, splitting every multi-byte sequence.
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl std::io::Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((byte, rest)), Some(out)) => {
+                    *out = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let data = "Name,City\nJosé,Zürich\n".as_bytes();
+    let config = || Config::new("").labels(HeaderStrategy::ReadLabels);
+
+    let sheet =
+        Sheet::from_reader(ByteReader(data), config().encoding(Encoding::Utf8Lossy)).unwrap();
+    let row = sheet.get_row_by_index(0).unwrap();
+    assert_eq!(
+        &Data::from("José"),
+        row.get_cell_by_index(0).unwrap().get_data()
+    );
+    assert_eq!(
+        &Data::from("Zürich"),
+        row.get_cell_by_index(1).unwrap().get_data()
+    );
+
+    // A sequence cut off by the end of the input is replaced
+    let data = b"Name\nJos\xc3";
+    let sheet =
+        Sheet::from_reader(ByteReader(data), config().encoding(Encoding::Utf8Lossy)).unwrap();
+    let row = sheet.get_row_by_index(0).unwrap();
+    assert_eq!(
+        &Data::from("Jos\u{FFFD}"),
+        row.get_cell_by_index(0).unwrap().get_data()
+    );
+    assert_eq!(1, sheet.load_report().replaced);
+}
+
+#[test]
+fn test_bounds() {
+    let config = Config::new("./dummies/csv/bounds.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = Sheet::with_config(config).unwrap();
+
+    assert_eq!(
+        Some((Data::Integer(3), Data::Integer(7))),
+        sheet.col_bounds(1).unwrap()
+    );
+    assert_eq!(
+        Some((Data::Integer(-9), Data::Integer(-2))),
+        sheet.col_bounds(2).unwrap()
+    );
+    assert_eq!(None, sheet.col_bounds(3).unwrap());
+    assert_eq!(
+        Some((Data::Float(-4.5), Data::Float(1.5))),
+        sheet.col_bounds(4).unwrap()
+    );
+    assert_eq!(
+        Some((Data::Integer(42), Data::Integer(42))),
+        sheet.col_bounds(5).unwrap()
+    );
+
+    // Text is ordered after every number
+    assert_eq!(
+        Some((Data::Integer(-2), Data::from("a"))),
+        sheet.row_bounds(0).unwrap()
+    );
+    assert_eq!(
+        Some((Data::Float(-4.5), Data::Integer(7))),
+        sheet.bounds_of(&[1, 3, 4]).unwrap()
+    );
+    assert_eq!(
+        Some((Data::Integer(-9), Data::Integer(7))),
+        sheet.bounds_of(&[1, 2, 4]).unwrap()
+    );
+    assert_eq!(None, sheet.bounds_of(&[3]).unwrap());
+    assert_eq!(None, sheet.bounds_of(&[]).unwrap());
+
+    assert!(matches!(
+        sheet.col_bounds(6),
+        Err(Error::InvalidColumnLength(_))
+    ));
+    assert!(matches!(
+        sheet.bounds_of(&[1, 6]),
+        Err(Error::InvalidColumnLength(_))
+    ));
+    assert!(matches!(sheet.row_bounds(3), Err(Error::InvalidRow(3))));
+
+    // Non-finite floats are left out, as they are from scales
+    let config = Config::new("./dummies/csv/nan.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = Sheet::with_config(config).unwrap();
+    assert_eq!(
+        Some((Data::Float(-2.5), Data::Float(1.5))),
+        sheet.col_bounds(1).unwrap()
+    );
+}
+
+#[test]
+fn test_head_tail() {
+    let sheet = create_air_csv().unwrap();
+    let months = |sheet: &Sheet| {
+        sheet
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(0).unwrap().get_data().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let head = sheet.head(2);
+    assert_eq!(months(&head), vec!["JAN", "FEB"]);
+    assert_eq!(head.get_headers(), sheet.get_headers());
+    assert_eq!(head.get_primary_key(), sheet.get_primary_key());
+
+    let tail = sheet.tail(3);
+    assert_eq!(months(&tail), vec!["OCT", "NOV", "DEC"]);
+    assert!(tail.validate().is_ok());
+
+    assert_eq!(sheet.head(20), sheet);
+    assert_eq!(sheet.tail(20), sheet);
+    assert!(sheet.head(0).is_empty());
+    assert_eq!(sheet.head(0).width(), 4);
+}
+
+#[test]
+fn test_describe() {
+    let load = |types| {
+        let config = Config::new("./dummies/csv/infer.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .trim(true)
+            .types(types);
+        Sheet::with_config(config).unwrap().describe()
+    };
+    let cell = |sheet: &Sheet, row: usize, col: usize| {
+        sheet
+            .get_row_by_index(row)
+            .unwrap()
+            .get_cell_by_index(col)
+            .unwrap()
+            .get_data()
+            .clone()
+    };
+    let column = |sheet: &Sheet, col: usize| {
+        (0..sheet.height())
+            .map(|row| cell(sheet, row, col))
+            .collect::<Vec<_>>()
+    };
+
+    // Numeric summaries are float columns holding integer counts
+    let summary = load(TypesStrategy::Infer);
+    assert!(summary.validate().is_err());
+    assert_eq!((summary.width(), summary.height()), (7, 7));
+    assert_eq!(
+        column(&summary, 0),
+        ["count", "nulls", "distinct", "min", "max", "mean", "std"]
+            .into_iter()
+            .map(|stat| Data::Text(stat.into()))
+            .collect::<Vec<_>>()
+    );
+    let labels: Vec<_> = summary
+        .get_headers()
+        .iter()
+        .map(|hdr| (hdr.label.as_str(), hdr.kind))
+        .collect();
+    assert_eq!(
+        labels,
+        vec![
+            ("statistic", ColumnType::Text),
+            ("Month", ColumnType::Integer),
+            ("Year", ColumnType::Float),
+            ("Percentage", ColumnType::Float),
+            ("Mixed", ColumnType::Integer),
+            ("Expected", ColumnType::Integer),
+            ("Test", ColumnType::Integer),
+        ]
+    );
+
+    let text = |count, nulls, distinct| {
+        let mut column = vec![Data::None; 7];
+        column[..3].clone_from_slice(&[
+            Data::Integer(count),
+            Data::Integer(nulls),
+            Data::Integer(distinct),
+        ]);
+        column
+    };
+    assert_eq!(column(&summary, 1), text(3, 0, 3));
+    assert_eq!(column(&summary, 4), text(3, 0, 3));
+    assert_eq!(column(&summary, 5), text(3, 0, 2));
+    assert_eq!(column(&summary, 6), text(2, 1, 2));
+    assert_eq!(
+        column(&summary, 2),
+        vec![
+            Data::Integer(3),
+            Data::Integer(0),
+            Data::None,
+            Data::Float(1998.0),
+            Data::Float(2000.0),
+            Data::Float(1999.0),
+            Data::Float(1.0),
+        ]
+    );
+
+    let percentage = column(&summary, 3);
+    assert_eq!(
+        percentage[..5],
+        [
+            Data::Integer(3),
+            Data::Integer(0),
+            Data::None,
+            Data::Float(0.24),
+            Data::Float(1.0),
+        ]
+    );
+    let approx = |data: &Data, expected: f64| (data.as_f64().unwrap() - expected).abs() < 1e-5;
+    assert!(approx(&percentage[5], 2.03 / 3.0));
+    assert!(approx(&percentage[6], 0.392471));
+
+    // Without types, every column is of unknown type and only counted
+    let summary = load(TypesStrategy::None);
+    assert!(summary.validate().is_ok());
+    assert_eq!(summary.column_kind(2), Some(ColumnType::Integer));
+    assert_eq!(column(&summary, 2), text(3, 0, 3));
+    assert_eq!(column(&summary, 6), text(2, 1, 2));
+
+    // Integer bounds are written as floats
+    let config = Config::new("ids.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let summary = Sheet::from_reader("Id\n-4\n12\n".as_bytes(), config)
+        .unwrap()
+        .describe();
+    assert_eq!(summary.column_kind(1), Some(ColumnType::Float));
+    assert_eq!(cell(&summary, 0, 1), Data::Integer(2));
+    assert_eq!(cell(&summary, 3, 1), Data::Float(-4.0));
+    assert_eq!(cell(&summary, 4, 1), Data::Float(12.0));
+}
+
+#[test]
+fn test_data_parsing_agrees() {
+    use crate::repr::col_sheet::UnionValue;
+
+    let corpus = [
+        "",
+        "0",
+        "-7",
+        "42",
+        "2147483647",
+        "2147483648",
+        "-2147483649",
+        "3000000000",
+        "9223372036854775807",
+        "1.5",
+        "-0.25",
+        "1e3",
+        "1E-2",
+        ".5",
+        "5.",
+        "NaN",
+        "inf",
+        "-inf",
+        "true",
+        "false",
+        "True",
+        "FALSE",
+        "yes",
+        "0x10",
+        "1,000",
+        " 12",
+        "12 ",
+        "+3",
+        "--3",
+        "abc",
+        "12abc",
+        "é",
+        "null",
+    ];
+
+    for value in corpus {
+        let data = Data::from(value);
+        let row = Row::new([value], 0, 0);
+
+        assert_eq!(Data::from(value.to_string()), data, "{:?}", value);
+        assert_eq!(Data::from_csv_field(value, None), data, "{:?}", value);
+        assert_eq!(
+            row.get_cell_by_index(0).unwrap().get_data(),
+            &data,
+            "{:?}",
+            value
+        );
+
+        if !value.is_empty() {
+            let union = Data::try_from(UnionValue::parse(value)).unwrap();
+            assert_eq!(union, data, "{:?}", value);
+        }
+    }
+}
+
+#[test]
+fn test_weighted_bar_chart() {
+    let sheet = create_air_csv().unwrap();
+    let weight_of = |month: &str| {
+        let row = sheet
+            .iter_rows()
+            .find(|row| row.get_cell_by_index(0).unwrap().get_data().to_string() == month)
+            .unwrap();
+        row.get_cell_by_index(2).unwrap().get_data().clone()
+    };
+
+    let mut chart = sheet
+        .create_bar_chart_weighted(
+            0,
+            1,
+            2,
+            BarChartBarLabels::FromColumn(0),
+            BarChartAxisLabelStrategy::Headers,
+            RowSelector::Indices(HashSet::from([1])),
+        )
+        .unwrap();
+    let plain = sheet
+        .clone()
+        .create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::FromColumn(0),
+            BarChartAxisLabelStrategy::Headers,
+            RowSelector::Indices(HashSet::from([1])),
+        )
+        .unwrap();
+
+    assert_eq!(chart.bars.len(), 11);
+    assert_eq!(chart.x_scale, plain.x_scale);
+    assert_eq!(chart.y_scale, plain.y_scale);
+    assert_eq!(chart.bars[1].label.as_deref(), Some("MAR"));
+    assert_eq!(chart.bars[1].weight, Some(Data::Integer(406)));
+
+    let total: i32 = (0..sheet.height())
+        .filter(|row| *row != 1)
+        .map(
+            |row| match sheet.get_row_by_index(row).unwrap().get_cell_by_index(2) {
+                Some(cell) => cell.get_data().as_f64().unwrap() as i32,
+                None => 0,
+            },
+        )
+        .sum();
+    assert_eq!(chart.total_weight(), total as f64);
+
+    chart.bars.sort_by(|a, b| b.point.y.cmp(&a.point.y));
+    for bar in chart.bars.iter() {
+        assert_eq!(bar.weight, Some(weight_of(bar.label.as_deref().unwrap())));
+    }
+    let normalized = chart.normalize_weights();
+    let largest = weight_of("AUG").as_f64().unwrap();
+    for (bar, weight) in chart.bars.iter().zip(normalized) {
+        let expected = bar.weight.as_ref().unwrap().as_f64().unwrap() / largest;
+        assert_eq!(weight, Some(expected));
+    }
+    assert_eq!(chart.bars[0].label.as_deref(), Some("AUG"));
+    assert_eq!(chart.normalize_weights()[0], Some(1.0));
+
+    let weighted = |col| {
+        sheet.create_bar_chart_weighted(
+            1,
+            2,
+            col,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+        )
+    };
+    assert!(matches!(weighted(0), Err(Error::InvalidColumnType(_))));
+    assert!(matches!(weighted(4), Err(Error::InvalidColumnLength(_))));
+}
+
+#[test]
+fn test_sheet_view() {
+    let config = Config::new("./dummies/csv/junk_column.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let sheet = Sheet::with_config(config).unwrap();
+
+    let mut view = sheet.view();
+    view.hide_col(1).unwrap();
+    view.swap_cols(1, 2).unwrap();
+    assert_eq!(view.width(), 3);
+    assert_eq!(view.physical_col(1), Some(3));
+    assert_eq!(view.view_col(1), None);
+    assert!(view.swap_cols(0, 3).is_err());
+    assert!(view.reorder([0, 1, 1, 2]).is_err());
+
+    let row = view.iter_rows().next().unwrap();
+    let cells: Vec<&Data> = row.iter_cells().map(|cell| cell.get_data()).collect();
+    assert_eq!(
+        cells,
+        vec![
+            &Data::Text("JAN".into()),
+            &Data::Integer(4),
+            &Data::Integer(120)
+        ]
+    );
+    assert_eq!(
+        row.get_cell_by_index(2).unwrap().get_data(),
+        &Data::Integer(120)
+    );
+
+    let edited = {
+        let headers = view.get_headers().into_iter().cloned().collect();
+        let rows = view
+            .iter_rows()
+            .map(|row| {
+                row.iter_cells()
+                    .map(|cell| cell.get_data().clone())
+                    .collect()
+            })
+            .collect();
+        Sheet::from_rows(rows, headers, 0).unwrap()
+    };
+
+    let line = |view: &SheetView| {
+        view.create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::Headers { y: "Units".into() },
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::new(),
+        )
+        .unwrap()
+    };
+    let expected = edited
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::Headers { y: "Units".into() },
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::new(),
+        )
+        .unwrap();
+    assert_eq!(line(&view), expected);
+    assert_eq!(
+        line(&view).lines[0].points[0].x,
+        Data::Text("Returns".into())
+    );
+
+    let bar = view
+        .create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::FromColumn(2),
+            BarChartAxisLabelStrategy::Headers,
+            RowSelector::default(),
+        )
+        .unwrap();
+    let expected = edited
+        .clone()
+        .create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::FromColumn(2),
+            BarChartAxisLabelStrategy::Headers,
+            RowSelector::default(),
+        )
+        .unwrap();
+    assert_eq!(bar, expected);
+    assert_eq!(bar.y_label.as_deref(), Some("Returns"));
+
+    let stacked = view
+        .create_stacked_bar_chart(
+            0,
+            [1, 2],
+            StackedBarChartAxisLabelStrategy::Header("Units".into()),
+            RowSelector::default(),
+        )
+        .unwrap();
+    let expected = edited
+        .create_stacked_bar_chart(
+            0,
+            [1, 2],
+            StackedBarChartAxisLabelStrategy::Header("Units".into()),
+            RowSelector::default(),
+        )
+        .unwrap();
+    assert_eq!(stacked.bars.len(), expected.bars.len());
+    assert_eq!(stacked.x_scale, expected.x_scale);
+    assert_eq!(stacked.y_scale, expected.y_scale);
+    assert_eq!(stacked.x_axis, expected.x_axis);
+
+    view.show_col(1).unwrap();
+    assert_eq!(view.physical_col(1), Some(1));
+    assert!(view
+        .create_line_graph_labelled(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::new(),
+        )
+        .is_err());
+}
+
+#[test]
+fn test_logical_eq() {
+    let sheet = create_air_csv().unwrap();
+    let mut sorted = sheet.clone();
+    sorted.sort_rows(1).unwrap();
+
+    // Rows built in reverse are given different ids
+    let rows = sheet
+        .iter_rows()
+        .rev()
+        .map(|row| {
+            row.iter_cells()
+                .map(|cell| cell.get_data().clone())
+                .collect()
+        })
+        .collect();
+    let mut rebuilt = Sheet::from_rows(rows, sheet.get_headers().clone(), 0).unwrap();
+    rebuilt.sort_rows(1).unwrap();
+
+    assert_ne!(rebuilt, sorted);
+    assert!(rebuilt.logical_eq(&sorted));
+    assert!(sorted.logical_eq(&rebuilt));
+    assert!(!rebuilt.logical_eq(&sheet));
+    assert!(!sheet.head(11).logical_eq(&sheet));
+
+    let mut units = rebuilt.clone();
+    units.set_col_unit(1, Some(Unit::Percent)).unwrap();
+    assert!(units.logical_eq(&sorted));
+
+    let cell = rebuilt
+        .iter_rows_mut()
+        .nth(3)
+        .unwrap()
+        .iter_cells_mut()
+        .nth(2)
+        .unwrap();
+    cell.set_data(Data::Integer(0));
+    assert!(!rebuilt.logical_eq(&sorted));
+
+    let mut headers = sheet.get_headers().clone();
+    headers[3].set_label("1961".into());
+    let rows = sheet
+        .iter_rows()
+        .map(|row| {
+            row.iter_cells()
+                .map(|cell| cell.get_data().clone())
+                .collect()
+        })
+        .collect();
+    let renamed = Sheet::from_rows(rows, headers, 0).unwrap();
+    assert!(!renamed.logical_eq(&sheet));
+}
+
+#[test]
+fn test_select_columns() {
+    let config = Config::new("./dummies/csv/air.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer);
+    let full = Sheet::with_config(config.clone()).unwrap();
+
+    let select = SelectColumns::Names(vec!["Month".into(), "1959".into()]);
+    let slim = Sheet::with_config(config.clone().select_columns(select)).unwrap();
+    assert_eq!(slim.width(), 2);
+    assert_eq!(slim.height(), 12);
+    assert_eq!(slim.get_headers()[1].label, "1959");
+    assert_eq!(slim.column_kind(0), Some(ColumnType::Text));
+    assert_eq!(slim.column_kind(1), Some(ColumnType::Integer));
+
+    let chart = |sheet: Sheet, y_col: usize| {
+        sheet
+            .create_bar_chart(
+                0,
+                y_col,
+                BarChartBarLabels::None,
+                BarChartAxisLabelStrategy::Headers,
+                RowSelector::default(),
+            )
+            .unwrap()
+    };
+    assert_eq!(chart(slim, 1), chart(full, 2));
+
+    // Provided types and the primary column follow the selection
+    let select = SelectColumns::Indices(vec![3, 0]);
+    let reordered = Sheet::with_config(
+        config
+            .clone()
+            .types(TypesStrategy::Provided(vec![
+                ColumnType::Integer,
+                ColumnType::Text,
+            ]))
+            .select_columns(select),
+    )
+    .unwrap();
+    assert_eq!(reordered.get_primary_key(), Some(1));
+    assert_eq!(reordered.get_headers()[0].label, "1960");
+    let row = reordered.get_row_by_index(0).unwrap();
+    assert_eq!(
+        row.get_cell_by_index(0).unwrap().get_data(),
+        &Data::Integer(417)
+    );
+    assert_eq!(
+        row.get_cell_by_index(1).unwrap().get_data(),
+        &Data::Text("JAN".into())
+    );
+
+    // The default primary column falls back to the first selected column
+    let select = SelectColumns::Names(vec!["1958".into(), "1959".into()]);
+    let years = Sheet::with_config(config.clone().select_columns(select)).unwrap();
+    assert_eq!(years.width(), 2);
+    assert_eq!(years.get_primary_key(), Some(0));
+    assert_eq!(years.get_headers()[0].label, "1958");
+
+    // An explicit primary column must be selected
+    let res = Sheet::with_config(
+        config
+            .clone()
+            .primary(0)
+            .select_columns(SelectColumns::Indices(vec![1, 2])),
+    );
+    assert!(matches!(
+        res.as_ref().map_err(Error::inner),
+        Err(Error::InvalidPrimaryKey(_))
+    ));
+
+    let res = Sheet::with_config(
+        config
+            .clone()
+            .select_columns(SelectColumns::Names(vec!["Month".into(), "1961".into()])),
+    );
+    assert!(matches!(
+        res.as_ref().map_err(Error::inner),
+        Err(Error::SelectColumns(SelectColumnsError::UnknownLabel(label))) if label == "1961"
+    ));
+
+    let res = Sheet::with_config(
+        config
+            .clone()
+            .select_columns(SelectColumns::Indices(vec![0, 4])),
+    );
+    assert!(matches!(
+        res.as_ref().map_err(Error::inner),
+        Err(Error::SelectColumns(SelectColumnsError::OutOfRange(4)))
+    ));
+
+    let res = Sheet::with_config(
+        config
+            .clone()
+            .select_columns(SelectColumns::Indices(vec![0, 0])),
+    );
+    assert!(matches!(
+        res.as_ref().map_err(Error::inner),
+        Err(Error::SelectColumns(SelectColumnsError::Duplicate(0)))
+    ));
+
+    let res = Sheet::with_config(
+        config
+            .labels(HeaderStrategy::NoLabels)
+            .select_columns(SelectColumns::Names(vec!["Month".into()])),
+    );
+    assert!(matches!(
+        res.as_ref().map_err(Error::inner),
+        Err(Error::SelectColumns(SelectColumnsError::LabelsNotRead))
+    ));
+}
+
+#[test]
+fn test_type_mismatch_policy() {
+    let config = |policy: TypeMismatchPolicy| {
+        let ct = vec![
+            ColumnType::Text,
+            ColumnType::Float,
+            ColumnType::Integer,
+            ColumnType::Integer,
+        ];
+        Config::new("./dummies/csv/invalid1.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Provided(ct))
+            .type_mismatch(policy)
+    };
+    let cell = |sheet: &Sheet, row: usize, col: usize| {
+        sheet
+            .get_row_by_index(row)
+            .and_then(|row| row.get_cell_by_index(col))
+            .map(|cell| cell.get_data().clone())
+            .unwrap()
+    };
+
+    assert!(matches!(
+        Sheet::with_config(config(TypeMismatchPolicy::Fail))
+            .as_ref()
+            .map_err(Error::inner),
+        Err(Error::InvalidColumnType(_))
+    ));
+
+    let sheet = Sheet::with_config(config(TypeMismatchPolicy::Null)).unwrap();
+    assert_eq!(cell(&sheet, 0, 1), Data::None);
+    assert_eq!(cell(&sheet, 5, 2), Data::None);
+    assert_eq!(cell(&sheet, 2, 3), Data::None);
+    assert_eq!(cell(&sheet, 8, 3), Data::None);
+    assert_eq!(cell(&sheet, 7, 2), Data::Integer(559));
+    assert!(sheet.validate().is_ok());
+
+    let sheet = Sheet::with_config(config(TypeMismatchPolicy::Coerce)).unwrap();
+    assert_eq!(cell(&sheet, 0, 1), Data::Float(340.0));
+    assert_eq!(cell(&sheet, 5, 2), Data::None);
+    assert_eq!(cell(&sheet, 2, 3), Data::None);
+    assert_eq!(cell(&sheet, 8, 3), Data::None);
+    assert_eq!(cell(&sheet, 7, 2), Data::Integer(559));
+    assert!(sheet.validate().is_ok());
+
+    let data = "a,b\nx,2.0\n3,true\n";
+    let config = Config::new("")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(vec![
+            ColumnType::Text,
+            ColumnType::Integer,
+        ]))
+        .type_mismatch(TypeMismatchPolicy::Coerce);
+    let sheet = Sheet::from_reader(data.as_bytes(), config).unwrap();
+    assert_eq!(cell(&sheet, 0, 1), Data::Integer(2));
+    assert_eq!(cell(&sheet, 1, 0), Data::Text("3".into()));
+    assert_eq!(cell(&sheet, 1, 1), Data::None);
+}
+
+#[test]
+fn test_sheet_iter_cells() -> Result<()> {
+    let mut sheet = create_air_csv()?;
+
+    let cells: Vec<_> = sheet.iter_cells().collect();
+    assert_eq!(cells.len(), sheet.width() * sheet.height());
+    assert_eq!(cells[0], (0, 0, &Data::Text("JAN".into())));
+    assert_eq!(cells[7 * 4 + 2], (7, 2, &Data::Integer(559)));
+
+    sheet.remove_col(1)?;
+    for (row, col, data) in sheet.iter_cells() {
+        let cell = sheet
+            .get_row_by_index(row)
+            .and_then(|cells| cells.get_cell_by_index(col))
+            .unwrap();
+        assert_eq!(cell.get_data(), data);
+    }
+    assert!(sheet
+        .iter_cells()
+        .any(|cell| cell == (7, 1, &Data::Integer(559))));
+
+    let col: Vec<_> = sheet.enumerate_col(1).collect();
+    assert_eq!(col.len(), sheet.height());
+    assert_eq!(col[7], (7, &Data::Integer(559)));
+    assert_eq!(sheet.enumerate_col(sheet.width()).count(), 0);
+
+    sheet
+        .iter_cells_mut()
+        .filter(|(_, col, _)| *col == 1)
+        .for_each(|(_, _, data)| *data = Data::Integer(0));
+    assert!(sheet
+        .enumerate_col(1)
+        .all(|(_, data)| data == &Data::Integer(0)));
+    sheet.validate()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_chart_context() -> Result<()> {
+    let sheet = create_air_csv()?;
+    let excluded: HashSet<usize> = [0, 3, 11].into_iter().collect();
+    let context = ChartContext::new()
+        .exclude_rows(excluded.clone())
+        .exclude_cols([3])
+        .scale_options(ScaleOptions::new().descending(true))
+        .bar_axis_labels(BarChartAxisLabelStrategy::Headers);
+    let charts = sheet.chart(&context)?;
+
+    let graph = charts.line_graph(LineLabelStrategy::FromCell(0))?;
+    let chart = charts.bar_chart(0, 1, BarChartBarLabels::FromColumn(0))?;
+    let labels: Vec<String> = graph
+        .lines
+        .iter()
+        .filter_map(|line| line.label.clone())
+        .collect();
+    assert_eq!(labels.len(), 9);
+    assert_eq!(
+        labels,
+        chart
+            .bars
+            .iter()
+            .map(|bar| bar.label.clone().unwrap())
+            .collect::<Vec<String>>()
+    );
+    assert!(!labels.contains(&"JAN".to_string()));
+    assert!(graph.lines.iter().all(|line| line.points.len() == 2));
+    assert!(graph.y_scale.is_descending() && chart.y_scale.is_descending());
+    assert_eq!(chart.x_label.as_deref(), Some("Month"));
+
+    let old = sheet.clone().create_bar_chart_with(
+        0,
+        1,
+        BarChartBarLabels::FromColumn(0),
+        BarChartAxisLabelStrategy::Headers,
+        excluded,
+        ScaleOptions::new().descending(true),
+    )?;
+    assert_eq!(old, chart);
+
+    assert!(matches!(
+        charts.bar_chart(0, 3, BarChartBarLabels::None),
+        Err(Error::ConversionError(_))
+    ));
+    assert!(matches!(
+        sheet.chart(&ChartContext::new().exclude_cols([4])),
+        Err(Error::InvalidColumnLength(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_ids_match_positions() -> Result<()> {
+    fn assert_positional(sheet: &Sheet) {
+        for (idx, row) in sheet.iter_rows().enumerate() {
+            assert_eq!(row.id(), idx);
+            for (col, cell) in row.iter_cells().enumerate() {
+                assert_eq!(cell.id(), col);
+                assert_eq!(row.get_cell_by_id(col), row.get_cell_by_index(col));
+            }
+        }
+    }
+
+    let mut sheet = create_air_csv()?;
+    assert_positional(&sheet);
+    assert_positional(&Sheet::transpose(&sheet, None)?);
+    assert_positional(&sheet.tail(5));
+
+    sheet.sort_rows(1)?;
+    assert_eq!(sheet.get_row_by_index(0).unwrap().id(), 10);
+    sheet.renumber();
+    assert_positional(&sheet);
+
+    sheet.remove_col(1)?;
+    sheet.extract_col(0, SimplePattern::Prefix("".into()), "Copy")?;
+    assert_eq!(
+        sheet
+            .get_row_by_index(0)
+            .unwrap()
+            .get_cell_by_index(1)
+            .unwrap()
+            .id(),
+        2
+    );
+    sheet.renumber();
+    assert_positional(&sheet);
+
+    let config = Config::new("./dummies/csv/sparse.csv")
+        .primary(1)
+        .types(TypesStrategy::Infer)
+        .labels(HeaderStrategy::ReadLabels)
+        .drop_empty_cols(true);
+    assert_positional(&Sheet::with_config(config)?);
+
+    let config = Config::new("./dummies/csv/originals.csv")
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .preserve_original(true);
+    let sheet = Sheet::with_config(config)?.tail(2);
+    assert_positional(&sheet);
+    assert_eq!(sheet.original_text(0, 0), Some("010"));
+
+    Ok(())
+}
+
+#[test]
+fn test_no_primary_key() -> Result<()> {
+    let config = Config::new("./dummies/csv/air.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+        .primary(None);
+    let mut sheet = Sheet::with_config(config)?;
+    assert_eq!(sheet.get_primary_key(), None);
+    assert!(sheet
+        .iter_rows()
+        .all(|row| row.get_primary_cell().is_none()));
+    sheet.validate()?;
+
+    let keys = RowSelector::Keys(HashSet::from([Data::from("JAN")]));
+    let chart = sheet.clone().create_bar_chart(
+        0,
+        1,
+        BarChartBarLabels::None,
+        BarChartAxisLabelStrategy::None,
+        keys,
+    )?;
+    assert_eq!(chart.bars.len(), sheet.height());
+
+    sheet.set_primary_key(2)?;
+    assert_eq!(sheet.get_primary_key(), Some(2));
+    assert_eq!(
+        sheet.get_row_by_index(0).unwrap().get_primary_cell(),
+        sheet.get_row_by_index(0).unwrap().get_cell_by_index(2)
+    );
+    assert!(sheet.set_primary_key(4).is_err());
+    assert_eq!(sheet.get_primary_key(), Some(2));
+
+    sheet.clear_primary_key();
+    assert_eq!(sheet.get_primary_key(), None);
+    assert_eq!(sheet.head(3).get_primary_key(), None);
+    sheet.remove_col(0)?;
+    assert_eq!(sheet.get_primary_key(), None);
+
+    let mut sheet = Sheet::from_rows(vec![vec![1.into(), 2.into()]], vec![], 1)?;
+    assert_eq!(sheet.get_primary_key(), Some(1));
+    sheet.remove_col(1)?;
+    assert_eq!(sheet.get_primary_key(), Some(0));
+    sheet.remove_col(0)?;
+    assert_eq!(sheet.get_primary_key(), None);
+    assert_eq!(sheet.get_row_by_index(0).unwrap().get_primary_key(), None);
+    sheet.validate()?;
+
+    let empty = Sheet::from_rows(vec![], vec![], 0)?;
+    assert_eq!(empty.get_primary_key(), None);
+    assert!(Sheet::from_rows(vec![], vec![], 1).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_col_meta() -> Result<()> {
+    let config = |collect_meta: bool| {
+        Config::new("./dummies/csv/air.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .collect_meta(collect_meta)
+    };
+    let plain = Sheet::with_config(config(false))?;
+    let mut sheet = Sheet::with_config(config(true))?;
+    assert_eq!(plain.col_meta(1), None);
+
+    let meta = sheet.col_meta(1).unwrap();
+    assert_eq!(meta.min(), Some(&Data::Integer(310)));
+    assert_eq!(meta.max(), Some(&Data::Integer(505)));
+    assert_eq!(meta.null_count(), 0);
+    assert_eq!(meta.exact_distinct(), Some(12));
+    assert!(!meta.is_increasing());
+    assert_eq!(sheet.col_meta(sheet.width()), None);
+
+    assert_eq!(sheet, plain);
+    assert_eq!(sheet.describe(), plain.describe());
+    for col in 0..sheet.width() {
+        assert_eq!(sheet.col_bounds(col)?, plain.col_bounds(col)?);
+    }
+    for y_col in 1..sheet.width() {
+        let chart = |sheet: &Sheet| {
+            sheet.clone().create_bar_chart(
+                0,
+                y_col,
+                BarChartBarLabels::None,
+                BarChartAxisLabelStrategy::Headers,
+                RowSelector::default(),
+            )
+        };
+        assert_eq!(chart(&sheet)?, chart(&plain)?);
+    }
+
+    sheet.update_where(1, |data| *data == Data::Integer(310), |_| 600.into())?;
+    assert_eq!(sheet.col_meta(1), None);
+    assert!(sheet.col_meta(2).is_some());
+    assert_eq!(
+        sheet.col_bounds(1)?,
+        Some((Data::Integer(318), Data::Integer(600)))
+    );
+
+    sheet.sort_rows(2)?;
+    assert_eq!(sheet.col_meta(2), None);
+
+    let config = |collect_meta: bool| {
+        Config::new("./dummies/csv/nulls.csv")
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+            .null_string("NA")
+            .collect_meta(collect_meta)
+    };
+    let plain = Sheet::with_config(config(false))?;
+    let sheet = Sheet::with_config(config(true))?;
+    let meta = sheet.col_meta(1).unwrap();
+    assert_eq!(meta.null_count(), 1);
+    assert_eq!(meta.exact_distinct(), Some(2));
+    assert_eq!(sheet.describe(), plain.describe());
+
+    Ok(())
+}
+
+#[test]
+fn test_get_by_id() -> Result<()> {
+    let mut sheet = create_air_csv()?;
+    for idx in 0..sheet.height() {
+        assert_eq!(sheet.get_row_by_id(idx), sheet.get_row_by_index(idx));
+    }
+    assert_eq!(sheet.get_row_by_id(sheet.height()), None);
+
+    sheet.sort_rows(1)?;
+    assert_ne!(sheet.get_row_by_index(0).unwrap().id(), 0);
+    for row in sheet.iter_rows() {
+        assert_eq!(sheet.get_row_by_id(row.id()), Some(row));
+    }
+    assert_eq!(sheet.get_row_by_id(sheet.height()), None);
+
+    sheet.sort_rows_rev(2)?;
+    for row in sheet.iter_rows() {
+        assert_eq!(sheet.get_row_by_id(row.id()), Some(row));
+    }
+
+    let row = sheet.get_row_by_index(0).unwrap();
+    let cells = row.iter_cells().map(|cell| cell.get_data().clone());
+    sheet.push_row(cells.collect())?;
+    sheet.iter_rows_mut().count();
+    for row in sheet.iter_rows() {
+        assert_eq!(sheet.get_row_by_id(row.id()), Some(row));
+    }
+
+    sheet.remove_col(1)?;
+    for row in sheet.iter_rows() {
+        assert_eq!(row.get_cell_by_id(0), row.get_cell_by_index(0));
+        assert_eq!(row.get_cell_by_id(1), None);
+        assert_eq!(row.get_cell_by_id(2), row.get_cell_by_index(1));
+        assert_eq!(row.get_cell_by_id(3), row.get_cell_by_index(2));
+    }
+
+    sheet.renumber();
+    for idx in 0..sheet.height() {
+        assert_eq!(sheet.get_row_by_id(idx), sheet.get_row_by_index(idx));
+    }
+    let row = sheet.get_row_by_index(0).unwrap();
+    assert_eq!(row.get_cell_by_id(1), row.get_cell_by_index(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_bar_chart_provided_labels_excluded() -> Result<()> {
+    let sheet = create_air_csv()?;
+    let labels: Vec<String> = (0..sheet.height())
+        .map(|idx| format!("row {idx}"))
+        .collect();
+    let chart = |labels: Vec<String>, exclude: RowSelector| {
+        sheet.clone().create_bar_chart(
+            0,
+            1,
+            BarChartBarLabels::Provided(labels),
+            BarChartAxisLabelStrategy::None,
+            exclude,
+        )
+    };
+
+    let barchart = chart(labels.clone(), HashSet::from([1, 4]).into())?;
+    assert_eq!(barchart.bars.len(), sheet.height() - 2);
+    let expected: Vec<_> = (0..sheet.height())
+        .filter(|idx| *idx != 1 && *idx != 4)
+        .map(|idx| Some(format!("row {idx}")))
+        .collect();
+    let found: Vec<_> = barchart.bars.iter().map(|bar| bar.label.clone()).collect();
+    assert_eq!(found, expected);
+    for (bar, idx) in barchart.bars.iter().zip([0, 2, 3, 5]) {
+        let row = sheet.get_row_by_index(idx).unwrap();
+        assert_eq!(&bar.point.x, row.get_cell_by_index(0).unwrap().get_data());
+    }
+
+    let keys = RowSelector::Keys(HashSet::from([Data::from("JAN"), Data::from("MAR")]));
+    let barchart = chart(labels[..4].to_vec(), keys)?;
+    assert_eq!(barchart.bars[0].label.as_deref(), Some("row 1"));
+    assert_eq!(barchart.bars[1].label.as_deref(), Some("row 3"));
+    assert!(barchart.bars[2..].iter().all(|bar| bar.label.is_none()));
+
+    let mut too_many = labels;
+    too_many.push("extra".into());
+    assert!(matches!(
+        chart(too_many, RowSelector::default()),
+        Err(Error::ConversionError(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_selection_charts() -> Result<()> {
+    let sheet = create_air_csv()?;
+    let all = || RowSelector::Predicate(Box::new(|_: &Row| true));
+    let bar = |options: ScaleOptions| {
+        sheet.clone().create_bar_chart_with(
+            0,
+            1,
+            BarChartBarLabels::FromColumn(0),
+            BarChartAxisLabelStrategy::Headers,
+            all(),
+            options,
+        )
+    };
+
+    assert!(matches!(bar(ScaleOptions::new()), Err(Error::NoRows)));
+
+    let chart = bar(ScaleOptions::new().allow_empty(true))?;
+    assert!(chart.is_empty());
+    assert!(chart.x_scale.is_empty());
+    assert!(chart.y_scale.is_empty());
+    assert!(chart.y_scale.points().is_empty());
+    assert_eq!(chart.x_label.as_deref(), Some("Month"));
+
+    let stacked = |options: ScaleOptions| {
+        sheet.clone().create_stacked_bar_chart_with(
+            0,
+            [1, 2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            all(),
+            options,
+        )
+    };
+
+    assert!(matches!(stacked(ScaleOptions::new()), Err(Error::NoRows)));
+    let chart = stacked(ScaleOptions::new().allow_empty(true))?;
+    assert!(chart.is_empty());
+    assert!(chart.y_scale.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_line_graph() -> Result<()> {
+    let load = |path: &str| {
+        Sheet::with_config(
+            Config::new(path)
+                .primary(0)
+                .types(TypesStrategy::Infer)
+                .labels(HeaderStrategy::ReadLabels),
+        )
+    };
+    let before = load("./dummies/csv/diff_before.csv")?;
+    let after = load("./dummies/csv/diff_after.csv")?;
+
+    let (graph, summary) = before.diff_line_graph(&after, &DiffOptions::new())?;
+    assert_eq!(graph.lines.len(), 2);
+    assert_eq!(graph.lines[0].label.as_deref(), Some("north"));
+    assert_eq!(graph.lines[1].label.as_deref(), Some("south"));
+    let deltas: Vec<_> = graph.lines[1].points.iter().map(|pnt| &pnt.y).collect();
+    assert_eq!(deltas, [&Data::Integer(-3), &Data::Integer(5)]);
+    assert_eq!(graph.lines[1].points[1].x, Data::Text("Q2".into()));
+    assert_eq!(graph.y_scale.kind(), ScaleKind::Integer);
+    assert_eq!(graph.y_label, "Change");
+
+    assert_eq!(summary.base_only_rows, [Data::Text("east".into())]);
+    assert_eq!(summary.other_only_rows, [Data::Text("west".into())]);
+    assert!(summary.base_only_cols.is_empty());
+    assert_eq!(summary.other_only_cols, ["Q3"]);
+
+    let options = DiffOptions::new().mode(DiffMode::PercentChange);
+    let (graph, _) = before.diff_line_graph(&after, &options)?;
+    assert_eq!(graph.lines[0].points[0].y, Data::Float(50.0));
+    assert_eq!(graph.lines[1].points[1].y, Data::Float(100.0));
+
+    assert!(matches!(
+        before.diff_line_graph(&after, &options.strict(true)),
+        Err(Error::ConversionError(_))
+    ));
+
+    let unkeyed = Sheet::from_rows(vec![], vec![], None)?;
+    assert!(matches!(
+        before.diff_line_graph(&unkeyed, &DiffOptions::new()),
+        Err(Error::InvalidPrimaryKey(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_data_float_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |data: &Data| {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let zeros = HashSet::from([Data::Float(0.0), Data::Float(-0.0), Data::Float(0.0)]);
+    assert_eq!(zeros.len(), 2);
+    assert_eq!(hash(&Data::Float(0.0)), hash(&Data::Float(-0.0)));
+
+    // NaNs which display alike but differ in bits are unequal, yet hash alike
+    let payload = f32::from_bits(f32::NAN.to_bits() | 1);
+    assert_eq!(payload.to_string(), f32::NAN.to_string());
+    let nans = HashSet::from([
+        Data::Float(f32::NAN),
+        Data::Float(f32::NAN),
+        Data::Float(payload),
+    ]);
+    assert_eq!(nans.len(), 2);
+    assert!(nans.contains(&Data::Float(f32::NAN)));
+    assert_eq!(hash(&Data::Float(f32::NAN)), hash(&Data::Float(payload)));
+
+    let next = f32::from_bits(0.1_f32.to_bits() + 1);
+    let values = HashSet::from([Data::Float(0.1), Data::Float(next)]);
+    assert_eq!(values.len(), 2);
+    assert!(values.contains(&Data::Float(0.1)));
+    assert_ne!(hash(&Data::Float(0.1)), hash(&Data::Float(next)));
+}
+
+#[test]
+fn test_synthesized_cells() -> Result<()> {
+    let config = Config::new("./dummies/csv/flexible.csv")
+        .trim(true)
+        .labels(HeaderStrategy::NoLabels)
+        .flexible(true);
+    let mut sheet = Sheet::with_config(config)?;
+
+    let widths: Vec<usize> = sheet.iter_rows().map(Row::original_width).collect();
+    assert_eq!(widths, vec![4, 3, 4, 4, 4, 4, 3, 2, 4, 4, 4, 3]);
+    assert_eq!(
+        sheet.synthesized_cells(),
+        vec![(1, 3), (6, 3), (7, 2), (7, 3), (11, 3)]
+    );
+
+    let mut buffer = Vec::new();
+    sheet.write_csv_with(&mut buffer, &WriteOptions::new().headers(false))?;
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.contains("FEB,318,391,\nMAR"));
+    assert!(written.contains("AUG,606,,\n"));
+
+    let mut buffer = Vec::new();
+    let options = WriteOptions::new().headers(false).trim_padding(true);
+    sheet.write_csv_with(&mut buffer, &options)?;
+    let written = String::from_utf8(buffer).unwrap();
+    assert!(written.starts_with("JAN,340,360,417\nFEB,318,391\nMAR"));
+    assert!(written.contains("AUG,606\n"));
+    assert_eq!(written.lines().count(), 12);
+
+    sheet.remove_col(3)?;
+    assert_eq!(sheet.synthesized_cells(), vec![(7, 2)]);
+
+    let air = Sheet::with_config(Config::new("./dummies/csv/air.csv").trim(true))?;
+    assert!(air.synthesized_cells().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_error_context() {
+    let path = "./dummies/csv/invalid1.csv";
+    let ct = vec![
+        ColumnType::Text,
+        ColumnType::Integer,
+        ColumnType::Integer,
+        ColumnType::Integer,
+    ];
+    let config = Config::new(path)
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(ct));
+
+    let err = Sheet::with_config(config).unwrap_err();
+    assert!(matches!(err.inner(), Error::InvalidColumnType(_)));
+    assert_eq!(err.path(), Some(Path::new(path)));
+    assert_eq!(err.line(), Some(4));
+    assert!(err
+        .to_string()
+        .starts_with("./dummies/csv/invalid1.csv:4: Invalid Column type:"));
+
+    // Errors from the csv reader carry the line of the record
+    let config = Config::new("./dummies/csv/flexible.csv").flexible(false);
+    let err = Sheet::with_config(config).unwrap_err();
+    assert!(matches!(err.inner(), Error::CSVReaderError(_)));
+    assert_eq!(err.line(), Some(2));
+    assert!(err
+        .to_string()
+        .starts_with("./dummies/csv/flexible.csv:2: "));
+
+    let err = Sheet::new("./dummies/csv/missing.csv").unwrap_err();
+    assert_eq!(err.line(), None);
+    assert!(err.to_string().starts_with("./dummies/csv/missing.csv: "));
+
+    // Readers without a path only have lines
+    let config = Config::new("").labels(HeaderStrategy::NoLabels);
+    let err = Sheet::from_reader("1,2\n3\n".as_bytes(), config).unwrap_err();
+    assert_eq!(err.path(), None);
+    assert_eq!(err.line(), Some(2));
+}
+
+#[test]
+fn test_schema_drift() -> Result<()> {
+    let config = |path: &str| {
+        Config::new(path.to_string())
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    };
+
+    let base = Sheet::with_config(config("./dummies/csv/schema_base.csv"))?.schema();
+    assert_eq!(
+        base.columns(),
+        &[
+            SchemaColumn::new("Week", ColumnType::Integer, false),
+            SchemaColumn::new("Region", ColumnType::Text, false),
+            SchemaColumn::new("Units", ColumnType::Integer, false),
+            SchemaColumn::new("Price", ColumnType::Float, true),
+        ]
+    );
+
+    let renamed = Sheet::with_config(config("./dummies/csv/schema_renamed.csv"))?;
+    let diff = base.diff(&renamed.schema());
+    assert_eq!(diff.renamed, vec![("Region".into(), "Area".into())]);
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.retyped.is_empty());
+    assert!(!diff.is_compatible());
+
+    let retyped = Sheet::with_config(config("./dummies/csv/schema_retyped.csv"))?;
+    let diff = base.diff(&retyped.schema());
+    assert_eq!(
+        diff.retyped,
+        vec![("Units".into(), ColumnType::Integer, ColumnType::Float)]
+    );
+    assert!(diff.renamed.is_empty());
+    assert!(!diff.is_compatible());
+
+    let err =
+        Sheet::with_config(config("./dummies/csv/schema_retyped.csv").expect_schema(base.clone()))
+            .unwrap_err();
+    assert!(matches!(err.inner(), Error::SchemaMismatch(drift) if **drift == diff));
+    assert!(err
+        .to_string()
+        .ends_with("Schema Mismatch: retyped `Units` from integer to float"));
+
+    // Only nullability differs
+    let mut nullable = base.columns().to_vec();
+    nullable[3].nullable = false;
+    let sheet = Sheet::with_config(
+        config("./dummies/csv/schema_base.csv").expect_schema(Schema::new(nullable)),
+    )?;
+    assert_eq!(sheet.schema(), base);
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_policy() -> Result<()> {
+    let ct = vec![
+        ColumnType::Text,
+        ColumnType::Text,
+        ColumnType::Integer,
+        ColumnType::Integer,
+    ];
+    let config = Config::new("./dummies/csv/holes.csv")
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Provided(ct));
+    let sheet = Sheet::with_config(config)?;
+    let options = |policy| ScaleOptions::new().missing(policy);
+
+    let line_graph = |policy| {
+        sheet.create_line_graph_labelled_with(
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::from([1]),
+            options(policy),
+        )
+    };
+    let points = |lg: &crate::models::LineGraph| -> Vec<usize> {
+        lg.lines.iter().map(|line| line.points.len()).collect()
+    };
+
+    let keep = line_graph(MissingPolicy::Keep)?;
+    assert_eq!(points(&keep), vec![2, 2, 2, 2]);
+    assert_eq!(keep.lines[1].points[1].y, Data::None);
+    let skip = line_graph(MissingPolicy::Skip)?;
+    assert_eq!(points(&skip), vec![2, 1, 1, 2]);
+    let fill = line_graph(MissingPolicy::FillZero)?;
+    assert_eq!(points(&fill), vec![2, 2, 2, 2]);
+    assert_eq!(fill.lines[2].points[0].y, Data::Integer(0));
+    assert!(matches!(
+        line_graph(MissingPolicy::Error),
+        Err(Error::MissingValue { row: 1, col: 3 })
+    ));
+
+    let bar_chart = |policy| {
+        sheet.clone().create_bar_chart_with(
+            0,
+            2,
+            BarChartBarLabels::None,
+            BarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+            options(policy),
+        )
+    };
+
+    let keep = bar_chart(MissingPolicy::Keep)?;
+    assert_eq!(keep.bars.len(), 4);
+    assert_eq!(keep.bars[1].point.x, Data::None);
+    assert_eq!(keep.bars[2].point.y, Data::None);
+    let skip = bar_chart(MissingPolicy::Skip)?;
+    let months: Vec<Data> = skip.bars.iter().map(|bar| bar.point.x.clone()).collect();
+    assert_eq!(
+        months,
+        vec![Data::Text("JAN".into()), Data::Text("APR".into())]
+    );
+    let fill = bar_chart(MissingPolicy::FillZero)?;
+    assert_eq!(fill.bars.len(), 4);
+    // Text columns have no zero
+    assert_eq!(fill.bars[1].point.x, Data::None);
+    assert_eq!(fill.bars[2].point.y, Data::Integer(0));
+    assert!(matches!(
+        bar_chart(MissingPolicy::Error),
+        Err(Error::MissingValue { row: 1, col: 0 })
+    ));
+
+    let stacked = |policy| {
+        sheet.clone().create_stacked_bar_chart_with(
+            0,
+            [2, 3],
+            StackedBarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+            options(policy),
+        )
+    };
+
+    let keep = stacked(MissingPolicy::Keep)?;
+    assert_eq!(keep.bars.len(), 4);
+    assert_eq!(keep.bars[1].point.y, Data::Integer(12));
+    assert_eq!(keep.bars[1].section_exact("South"), None);
+    let skip = stacked(MissingPolicy::Skip)?;
+    let groups: Vec<usize> = skip.bars.iter().map(|bar| bar.group).collect();
+    assert_eq!(groups, vec![0, 2, 3]);
+    let fill = stacked(MissingPolicy::FillZero)?;
+    assert_eq!(fill.bars.len(), 4);
+    assert_eq!(fill.bars[1].section_exact("South"), Some(&Data::Integer(0)));
+    assert_eq!(fill.bars[1].fraction("South"), Some(0.0));
+    assert_eq!(fill.bars[2].section_exact("North"), Some(&Data::Integer(0)));
+    assert!(matches!(
+        stacked(MissingPolicy::Error),
+        Err(Error::MissingValue { row: 1, col: 0 })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_cache() -> Result<()> {
+    use super::INFERENCES;
+
+    let inferences = || INFERENCES.with(|count| count.get());
+    let path = std::env::temp_dir().join(format!("modav_schema_cache_{}.csv", std::process::id()));
+    std::fs::copy("./dummies/csv/air.csv", &path).unwrap();
+    let config = || {
+        Config::new(path.clone())
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    };
+
+    let before = inferences();
+    let sheet = Sheet::with_config(config())?;
+    assert_eq!(inferences(), before + 1);
+    let cache = sheet.schema_fingerprint(&path)?;
+    assert_eq!(cache.schema(), &sheet.schema());
+
+    // An unchanged file skips inference
+    let cached = Sheet::with_config(config().with_schema_cache(cache.clone()))?;
+    assert_eq!(inferences(), before + 1);
+    assert_eq!(cached, sheet);
+    assert_eq!(cached.schema(), sheet.schema());
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&cache).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::repr::SchemaCache>(&json).unwrap(),
+            cache
+        );
+    }
+
+    // A changed file is inferred again
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    contents.push_str("\"XXX\", 1.5, 2.5, 3.5\n");
+    std::fs::write(&path, contents).unwrap();
+    let changed = Sheet::with_config(config().with_schema_cache(cache.clone()))?;
+    assert_eq!(inferences(), before + 2);
+    assert_eq!(changed.column_kind(1), Some(ColumnType::Mixed));
+
+    // Cached types which no longer fit the cells fall back to inference
+    let stale = crate::repr::SchemaCache::new(
+        Schema::new(vec![
+            SchemaColumn::new("Month", ColumnType::Integer, false);
+            4
+        ]),
+        *sheet.schema_fingerprint(&path)?.fingerprint(),
+    );
+    let fallback = Sheet::with_config(config().with_schema_cache(stale))?;
+    assert_eq!(inferences(), before + 3);
+    assert_eq!(fallback, changed);
+
+    std::fs::remove_file(&path).unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_line_graph_duplicate_labels() -> Result<()> {
+    let sheet = Sheet::with_config(
+        Config::new("./dummies/csv/dup_headers.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer),
+    )?;
+
+    let graph = sheet.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::Headers { y: "Sales".into() },
+        LineLabelStrategy::FromCell(0),
+        RowSelector::default(),
+        HashSet::new(),
+    )?;
+
+    // Both `Sales` columns keep a position of their own, and the suffix
+    // steps over the label already taken by the last column
+    let expected = ["Sales", "Sales (3)", "Sales (2)"]
+        .map(|label| Data::Text(label.into()))
+        .to_vec();
+    assert_eq!(graph.x_scale.points(), expected);
+
+    let jan = &graph.lines[0];
+    let points = jan
+        .points
+        .iter()
+        .map(|point| (point.x.clone(), point.y.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        points,
+        vec![
+            (expected[0].clone(), Data::Integer(10)),
+            (expected[1].clone(), Data::Integer(12)),
+            (expected[2].clone(), Data::Integer(1)),
+        ]
+    );
+
+    // Excluding a repeat leaves the remaining labels alone
+    let graph = sheet.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::FromCell(0),
+        RowSelector::default(),
+        HashSet::from([1]),
+    )?;
+    assert_eq!(
+        graph.x_scale.points(),
+        vec![expected[0].clone(), expected[2].clone()]
+    );
+
+    // Columns without labels are told apart too
+    let headless = Sheet::from_rows(
+        vec![vec![Data::Integer(1), Data::Integer(2), Data::Integer(3)]],
+        vec![ColumnHeader::new(String::new(), ColumnType::Integer); 3],
+        0,
+    )?;
+    let graph = headless.create_line_graph_labelled(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::None,
+        RowSelector::default(),
+        HashSet::new(),
+    )?;
+    let expected = ["", "(2)", "(3)"]
+        .map(|label| Data::Text(label.into()))
+        .to_vec();
+    assert_eq!(graph.x_scale.points(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_auto_detect() -> Result<()> {
+    let rows = [
+        ["Name", "Team", "Score"],
+        ["Smith, John", "North", "42"],
+        ["Jane; Doe", "South", "37"],
+        ["Li", "East|West", "12"],
+    ];
+
+    for delimiter in [b',', b';', b'\t', b'|'] {
+        for has_header in [true, false] {
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(Vec::new());
+            for row in rows.iter().skip(usize::from(!has_header)) {
+                writer.write_record(row).unwrap();
+            }
+            let contents = writer.into_inner().unwrap();
+
+            let path = std::env::temp_dir().join(format!(
+                "modav_auto_detect_{}_{}_{}.csv",
+                std::process::id(),
+                delimiter,
+                has_header
+            ));
+            std::fs::write(&path, &contents).unwrap();
+
+            let sniffed = Config::new(&path).sniff(1024).unwrap();
+            assert_eq!(sniffed.delimiter, delimiter);
+            assert_eq!(sniffed.has_header, has_header);
+
+            let labels = if has_header {
+                HeaderStrategy::ReadLabels
+            } else {
+                HeaderStrategy::NoLabels
+            };
+            let expected = Sheet::from_reader(
+                contents.as_slice(),
+                Config::new("")
+                    .delimiter(delimiter)
+                    .labels(labels)
+                    .types(TypesStrategy::Infer),
+            )?;
+
+            let config = Config::new(&path)
+                .auto_detect(true)
+                .types(TypesStrategy::Infer);
+            assert_eq!(Sheet::with_config(config.clone())?, expected);
+            let columns = crate::repr::col_sheet::ColumnSheet::with_config(config).unwrap();
+            assert_eq!(
+                (columns.width(), columns.height()),
+                (expected.width(), expected.height())
+            );
+
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    // A sample cut short leaves out the last record
+    let path = std::env::temp_dir().join(format!("modav_sniff_cut_{}.csv", std::process::id()));
+    std::fs::write(&path, "a;b\n1;2\n3;4,5,6,7,8,9").unwrap();
+    let sniffed = Config::new(&path).sniff(12).unwrap();
+    assert_eq!(sniffed.delimiter, b';');
+    assert_eq!(sniffed.delimiter_confidence, 1.0);
+    std::fs::remove_file(&path).unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_stacked_bar_sections() -> Result<()> {
+    let sheet = Sheet::with_config(
+        Config::new("./dummies/csv/stacked_neg.csv")
+            .labels(HeaderStrategy::ReadLabels)
+            .trim(true)
+            .types(TypesStrategy::Infer),
+    )?;
+    let cols = [1, 2, 3, 4];
+    let stacked = sheet.clone().create_stacked_bar_chart(
+        0,
+        cols,
+        StackedBarChartAxisLabelStrategy::Header("Sales".into()),
+        HashSet::new(),
+    )?;
+
+    let column = |col: usize| {
+        sheet
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(col).unwrap().get_data().clone())
+            .collect::<Vec<Data>>()
+    };
+    let days = column(0);
+
+    let graph = stacked.to_line_graph().unwrap();
+    assert_eq!(graph.lines.len(), cols.len());
+    assert_eq!(graph.x_scale, stacked.x_scale);
+    assert_eq!(graph.x_label, "Day of Week");
+    assert_eq!(graph.y_label, "Sales");
+
+    for col in cols {
+        let label = sheet.header(col).unwrap().label.clone();
+        let line = graph
+            .lines
+            .iter()
+            .find(|line| line.label.as_ref() == Some(&label))
+            .unwrap();
+
+        // Positive and negative values alike follow the column
+        let ys = line
+            .points
+            .iter()
+            .map(|point| point.y.clone())
+            .collect::<Vec<_>>();
+        let xs = line
+            .points
+            .iter()
+            .map(|point| point.x.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ys, column(col));
+        assert_eq!(xs, days);
+        assert_eq!(line.points, stacked.section_series(&label));
+        assert!(ys.iter().all(|y| graph.y_scale.contains(y)));
+    }
+
+    let coffee = stacked.section_scale("Coffee").unwrap();
+    assert_eq!(coffee.kind(), ScaleKind::Integer);
+    assert!(coffee.contains(&Data::Integer(-10)) && coffee.contains(&Data::Integer(10)));
+    assert!(!coffee.contains(&Data::Integer(11)));
+    assert!(stacked.section_scale("Total").is_none());
+    assert!(stacked.section_series("Total").is_empty());
+
+    // Removing a section from the bars keeps its series
+    let mut removed = stacked.clone();
+    removed.remove_section_all("Soda");
+    assert_eq!(
+        removed.section_series("Soda"),
+        stacked.section_series("Soda")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_line_graph_transposed() -> Result<()> {
+    let mut sheet = create_air_csv()?;
+    for col in 1..sheet.width() {
+        sheet.set_col_unit(col, Some(Unit::Custom("k".into())))?;
+    }
+    let transposed = Sheet::transpose(&sheet, None)?;
+
+    let headers = || LineGraphAxisLabelStrategy::Headers {
+        y: "Passengers".into(),
+    };
+    let cases = vec![
+        (
+            headers(),
+            LineLabelStrategy::FromCell(0),
+            RowSelector::default(),
+            HashSet::new(),
+            ScaleOptions::default(),
+        ),
+        (
+            headers(),
+            LineLabelStrategy::FromCell(3),
+            RowSelector::Indices(HashSet::from([1])),
+            HashSet::from([0, 12]),
+            ScaleOptions::default().include_zero(true),
+        ),
+        (
+            LineGraphAxisLabelStrategy::Provided {
+                x: "Month".into(),
+                y: "Passengers".into(),
+            },
+            LineLabelStrategy::Provided(vec!["first".into(), "second".into()]),
+            RowSelector::Keys(HashSet::from([Data::Integer(1959)])),
+            HashSet::from([0]),
+            ScaleOptions::default().descending(true),
+        ),
+        (
+            LineGraphAxisLabelStrategy::None,
+            LineLabelStrategy::None,
+            RowSelector::Predicate(Box::new(|row: &Row| {
+                row.get_cell_by_index(1)
+                    .map_or(false, |cell| cell.get_data() == &Data::Integer(340))
+            })),
+            HashSet::from([0]),
+            ScaleOptions::default(),
+        ),
+    ];
+
+    for (axis_labels, label_strat, exclude_row, exclude_column, options) in cases {
+        // Predicates cannot be cloned, so each path gets its own
+        let exclude_row = match exclude_row {
+            RowSelector::Predicate(predicate) => {
+                let predicate = std::rc::Rc::new(predicate);
+                let copy = predicate.clone();
+                (
+                    RowSelector::Predicate(Box::new(move |row: &Row| predicate(row))),
+                    RowSelector::Predicate(Box::new(move |row: &Row| copy(row))),
+                )
+            }
+            RowSelector::Indices(indices) => (
+                RowSelector::Indices(indices.clone()),
+                RowSelector::Indices(indices),
+            ),
+            RowSelector::Keys(keys) => (RowSelector::Keys(keys.clone()), RowSelector::Keys(keys)),
+        };
+
+        let expected = transposed.create_line_graph_labelled_with(
+            axis_labels.clone(),
+            label_strat.clone(),
+            exclude_row.0,
+            exclude_column.clone(),
+            options.clone(),
+        )?;
+        let graph = sheet.create_line_graph_transposed(
+            axis_labels,
+            label_strat,
+            exclude_row.1,
+            exclude_column,
+            options,
+        )?;
+
+        assert_eq!(graph, expected);
+    }
+
+    // Errors follow the transposed sheet too
+    for label_strat in [
+        LineLabelStrategy::FromCell(13),
+        LineLabelStrategy::Provided(vec![String::new(); 4]),
+    ] {
+        let expected = transposed
+            .create_line_graph_labelled_with(
+                LineGraphAxisLabelStrategy::None,
+                label_strat.clone(),
+                RowSelector::default(),
+                HashSet::new(),
+                ScaleOptions::default(),
+            )
+            .unwrap_err();
+        let err = sheet
+            .create_line_graph_transposed(
+                LineGraphAxisLabelStrategy::None,
+                label_strat,
+                RowSelector::default(),
+                HashSet::new(),
+                ScaleOptions::default(),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), expected.to_string());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_categorical_order() -> Result<()> {
+    let config = Config::new(PathBuf::from("./dummies/csv/stacked.csv"))
+        .labels(HeaderStrategy::ReadLabels)
+        .trim(true)
+        .types(TypesStrategy::Infer);
+    let mut sheet = Sheet::with_config(config)?;
+    // The busiest days first, so the days are not in order
+    sheet.sort_rows_rev(5)?;
+
+    let chart = |options: ScaleOptions| {
+        sheet.clone().create_stacked_bar_chart_with(
+            0,
+            [1, 2, 3, 4],
+            StackedBarChartAxisLabelStrategy::None,
+            RowSelector::default(),
+            options,
+        )
+    };
+    let days = |days: &[&str]| {
+        days.iter()
+            .map(|day| Data::Text(day.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut stacked = chart(ScaleOptions::default())?;
+    stacked.x_scale.sort();
+    assert_eq!(
+        stacked.x_scale.points(),
+        days(&[
+            "Friday",
+            "Monday",
+            "Saturday",
+            "Sunday",
+            "Thursday",
+            "Tuesday",
+            "Wednesday"
+        ])
+    );
+
+    let week = days(&[
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ]);
+    let mut stacked = chart(ScaleOptions::default().categorical_order(CategoricalOrder::Weekdays))?;
+    assert_eq!(stacked.x_scale.points(), week);
+    stacked.x_scale.sort();
+    assert_eq!(stacked.x_scale.points(), week);
+
+    // Days are not months, so the scale is left as first seen
+    let stacked = chart(ScaleOptions::default().categorical_order(CategoricalOrder::Months))?;
+    assert_eq!(stacked.x_scale.points()[0], Data::from("Sunday"));
+    assert_eq!(stacked.x_scale.categorical_order(), &CategoricalOrder::None);
+
+    // Bar charts and line graphs order their categories alike
+    let bars = sheet.clone().create_bar_chart_with(
+        0,
+        1,
+        BarChartBarLabels::None,
+        BarChartAxisLabelStrategy::None,
+        RowSelector::default(),
+        ScaleOptions::default().categorical_order(CategoricalOrder::Weekdays),
+    )?;
+    assert_eq!(bars.x_scale.points(), week);
+
+    let air = create_air_csv()?;
+    let order = CategoricalOrder::Custom(vec!["1960".into(), "1959".into(), "1958".into()]);
+    let graph = air.create_line_graph_labelled_with(
+        LineGraphAxisLabelStrategy::None,
+        LineLabelStrategy::FromCell(0),
+        RowSelector::default(),
+        HashSet::new(),
+        ScaleOptions::default().categorical_order(order),
+    )?;
+    assert_eq!(graph.x_scale.points(), days(&["1960", "1959", "1958"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_row_constraints() -> Result<()> {
+    let config = Config::new("./dummies/csv/parts_total.csv")
+        .trim(true)
+        .types(TypesStrategy::Infer)
+        .labels(HeaderStrategy::ReadLabels);
+    let mut sheet = Sheet::with_config(config)?;
+
+    sheet.add_row_constraint(RowConstraint::ColumnLessEqual(1, 2))?;
+    sheet.add_row_constraint(RowConstraint::SumEquals {
+        parts: vec![3, 4, 5],
+        total: 6,
+        tolerance: Data::None,
+    })?;
+    sheet.add_row_constraint(RowConstraint::SumEquals {
+        parts: vec![7, 8],
+        total: 9,
+        tolerance: Data::Float(0.01),
+    })?;
+
+    let violations = sheet.check_constraints();
+    let rows = violations
+        .iter()
+        .map(|violation| violation.row)
+        .collect::<Vec<_>>();
+    // The empty end of the fourth row is not checked
+    assert_eq!(rows, vec![1, 2, 3]);
+    assert_eq!(
+        violations[0].description,
+        "`Start` (2024-02-14) is greater than `End` (2024-02-01)"
+    );
+    assert_eq!(
+        violations[1].description,
+        "`Parts A` + `Parts B` + `Parts C` sum to 12, not `Total` (13)"
+    );
+    assert!(violations[2]
+        .description
+        .starts_with("`Weight A` + `Weight B` sum to 0.3"));
+    assert_eq!(
+        violations[2].to_string(),
+        format!("Row id 3: {}", violations[2].description)
+    );
+
+    match sheet.add_row_constraint(RowConstraint::ColumnLessEqual(0, 10)) {
+        Err(Error::InvalidColumnLength(_)) => {}
+        res => panic!("Expected an invalid column error, got {:?}", res),
+    }
+
+    // Unenforced, rows breaking constraints are still accepted
+    let row = |start: &str, total: i32| {
+        let mut cells = vec![
+            Data::Integer(6),
+            Data::from(start),
+            Data::from("2024-07-01"),
+        ];
+        cells.extend([1, 2, 3, total].map(Data::Integer));
+        cells.extend([1.0, 2.0, 3.0].map(Data::Float));
+        cells
+    };
+    let mut enforced = sheet.clone();
+    sheet.push_row(row("2024-06-01", 7))?;
+    assert_eq!(sheet.check_constraints().len(), 4);
+
+    enforced.enforce_constraints(true);
+    match enforced.push_row(row("2024-08-01", 6)) {
+        Err(Error::ConstraintViolation(violation)) => {
+            assert_eq!(violation.row, 5);
+            assert!(violation.description.starts_with("`Start`"));
+        }
+        res => panic!("Expected a constraint violation, got {:?}", res),
+    }
+    assert_eq!(enforced.height(), 5);
+
+    enforced.push_row(row("2024-06-01", 6))?;
+    assert_eq!(enforced.height(), 6);
+    match enforced.set_cell(5, 6, Data::Integer(5)) {
+        Err(Error::ConstraintViolation(violation)) => assert_eq!(violation.row, 5),
+        res => panic!("Expected a constraint violation, got {:?}", res),
+    }
+    let cell = enforced
+        .get_row_by_index(5)
+        .and_then(|row| row.get_cell_by_index(6));
+    assert_eq!(cell.map(Cell::get_data), Some(&Data::Integer(6)));
+
+    // Fixing a row is allowed, even with its other violations left
+    assert_eq!(
+        enforced.set_cell(2, 6, Data::Integer(12))?,
+        Data::Integer(13)
+    );
+    assert_eq!(enforced.check_constraints().len(), 2);
+    assert!(enforced.set_cell(2, 6, Data::from("twelve")).is_err());
+    assert!(enforced.set_cell(9, 6, Data::Integer(12)).is_err());
+
+    // Removing a column drops its constraints and shifts the rest
+    enforced.remove_col(1)?;
+    let violations = enforced.check_constraints();
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].description.starts_with("`Weight A`"));
+
+    enforced.add_row_constraint(RowConstraint::Custom(Box::new(|row: &Row| {
+        row.get_cell_by_index(0)
+            .map_or(false, |cell| cell.get_data() != &Data::Integer(1))
+    })))?;
+    let violations = enforced.check_constraints();
+    assert_eq!(violations.len(), 2);
+    assert_eq!(violations[0].row, 0);
+    assert_eq!(violations[0].description, "breaks a custom constraint");
+
+    Ok(())
+}
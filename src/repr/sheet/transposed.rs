@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+
+use crate::models::{
+    line::{Line, LineGraph},
+    Point,
+};
+
+use super::{
+    charted_value, distinct_labels, distinct_types,
+    error::{Error, Result},
+    finish_line_graph, is_line_column, line_axis_labels, merge_kinds,
+    utils::*,
+    validate_line_columns, Cell, Row, Sheet,
+};
+
+/// The transpose of a [`Sheet`], read from the sheet without copying its
+/// cells. See [`Sheet::create_line_graph_transposed`].
+///
+/// Row `idx` of the transpose is column `idx + 1` of the sheet, led by the
+/// label of the column. Column `col + 1` of the transpose is row `col` of the
+/// sheet, labelled by its first cell. Headers are typed from their cells, as
+/// when transposing.
+#[derive(Debug)]
+pub(super) struct Transposed<'a> {
+    sheet: &'a Sheet,
+    headers: Vec<ColumnHeader>,
+    /// The first cell of each row, parsed from the label of its column
+    firsts: Vec<Data>,
+}
+
+impl<'a> Transposed<'a> {
+    pub(super) fn new(sheet: &'a Sheet) -> Self {
+        let firsts: Vec<Data> = sheet
+            .headers
+            .iter()
+            .skip(1)
+            .map(|header| Data::from_csv_field(&header.label, None))
+            .collect();
+
+        let mut headers = Vec::with_capacity(sheet.height() + 1);
+        headers.push(ColumnHeader::new(
+            sheet
+                .headers
+                .first()
+                .map(|header| header.label.clone())
+                .unwrap_or_default(),
+            firsts
+                .iter()
+                .map(ColumnType::from)
+                .fold(ColumnType::None, merge_kinds),
+        ));
+
+        // Each column holds a value from every column of the sheet, so only a
+        // unit shared by all of them still applies.
+        let unit = sheet.shared_unit(1..sheet.width()).cloned();
+        for row in sheet.iter_rows() {
+            let label = row
+                .cells
+                .first()
+                .map(|cell| cell.data.to_csv_field())
+                .unwrap_or_default();
+            let kind = row
+                .cells
+                .iter()
+                .skip(1)
+                .map(|cell| ColumnType::from(&cell.data))
+                .fold(ColumnType::None, merge_kinds);
+
+            let mut header = ColumnHeader::new(label, kind);
+            header.unit = unit.clone();
+            headers.push(header);
+        }
+
+        Self {
+            sheet,
+            headers,
+            firsts,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.headers.len()
+    }
+
+    fn height(&self) -> usize {
+        self.firsts.len()
+    }
+
+    /// Returns the data in the cell at `row` and `col` of the transpose.
+    /// Cells missing from the rows of the sheet are empty.
+    fn cell(&self, row: usize, col: usize) -> &Data {
+        match col.checked_sub(1) {
+            None => &self.firsts[row],
+            Some(col) => self.sheet.rows[col]
+                .cells
+                .get(row + 1)
+                .map_or(&Data::None, Cell::get_data),
+        }
+    }
+
+    /// Returns the cells of the row at `idx` of the transpose.
+    fn row_cells(&self, idx: usize) -> impl Iterator<Item = &Data> {
+        (0..self.width()).map(move |col| self.cell(idx, col))
+    }
+
+    /// Returns true if the row at `idx` of the transpose is selected by
+    /// `selector`. Only predicates are given a copy of the row.
+    fn selects(&self, selector: &RowSelector, idx: usize) -> bool {
+        match selector {
+            RowSelector::Indices(indices) => indices.contains(&idx),
+            RowSelector::Keys(keys) => keys.contains(&self.firsts[idx]),
+            RowSelector::Predicate(predicate) => {
+                let cells = self
+                    .row_cells(idx)
+                    .enumerate()
+                    .map(|(col, data)| Cell::new(col, data.clone()))
+                    .collect();
+                let row = Row {
+                    cells,
+                    primary: Some(0),
+                    id: idx,
+                    id_counter: self.width(),
+                    padding: 0,
+                };
+
+                predicate(&row)
+            }
+        }
+    }
+
+    /// Returns a new line graph with one line per row of the transpose, as
+    /// [`Sheet::create_line_graph_labelled_with`] would from the transpose.
+    pub(super) fn line_graph(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: &RowSelector,
+        exclude_column: &HashSet<usize>,
+        options: &ScaleOptions,
+    ) -> Result<LineGraph> {
+        let _span = trace_span!(
+            "create_line_graph_transposed",
+            rows = self.sheet.height(),
+            cols = self.sheet.width()
+        );
+
+        if !options.allows_empty() {
+            if self.height() == 0 {
+                return Err(Error::NoRows);
+            }
+
+            let has_data =
+                (0..self.height()).any(|idx| self.row_cells(idx).any(|data| data != &Data::None));
+            if !has_data {
+                return Err(Error::NoData);
+            }
+        }
+
+        if let Some(col) = exclude_column.iter().find(|col| **col >= self.width()) {
+            return Err(Error::InvalidColumnLength(format!(
+                "Tried to exclude out of range column {}",
+                col
+            )));
+        }
+
+        let columns = (0..self.width()).collect::<Vec<usize>>();
+        let (x_label, y_label) = line_axis_labels(
+            axis_labels,
+            &label_strat,
+            &self.headers,
+            exclude_column,
+            &columns,
+        );
+
+        let scale_kind = validate_line_columns(
+            &self.headers,
+            self.height(),
+            &label_strat,
+            exclude_column,
+            |col| distinct_types((0..self.height()).map(|row| self.cell(row, col))),
+        )?;
+
+        let line_columns = distinct_labels(
+            columns
+                .into_iter()
+                .filter(|col| is_line_column(&label_strat, exclude_column, *col))
+                .map(|col| (col, self.headers[col].label.as_str())),
+        );
+
+        let policy = options.get_missing();
+        let mut lines = Vec::new();
+        for idx in (0..self.height()).filter(|idx| !self.selects(exclude_row, *idx)) {
+            let mut points = Vec::with_capacity(line_columns.len());
+            for (col, x) in &line_columns {
+                let data = self.cell(idx, *col);
+                if let Some(y) = charted_value(data, &self.headers, idx, *col, policy)? {
+                    points.push(Point::new(Data::Text(x.clone()), y));
+                }
+            }
+
+            let line = Line::from_points(points);
+            let label = match &label_strat {
+                LineLabelStrategy::None => None,
+                LineLabelStrategy::Provided(labels) => labels.get(idx).cloned(),
+                LineLabelStrategy::FromCell(label_col)
+                | LineLabelStrategy::FromCellWith(label_col, _) => {
+                    Some(label_strat.cell_label(self.cell(idx, *label_col)))
+                }
+            };
+
+            lines.push(match label {
+                Some(label) => line.label(label),
+                None => line,
+            });
+        }
+
+        let x_values = line_columns.into_iter().map(|(_, x)| Data::Text(x));
+        finish_line_graph(lines, x_values, x_label, y_label, scale_kind, options)
+    }
+}
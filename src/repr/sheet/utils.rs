@@ -1,22 +1,40 @@
 use std::{
     cmp::{self, Ordering},
+    collections::HashSet,
     default, fmt, hash,
+    str::FromStr,
 };
 
-#[derive(Debug, Clone, Default, PartialEq)]
+use super::super::config::{parse_type_list, ParseTypeError};
+use super::Row;
+use crate::models::{bar::TopN, CategoricalOrder, LabelTransform};
+
+/// A single value within a [`Sheet`](super::Sheet).
+///
+/// Floats are compared bitwise, the same way as [`f32::total_cmp`], so that
+/// equality agrees with [`Ord`] and [`Hash`]. In particular, `NaN` equals
+/// itself while `0.0` and `-0.0` are not equal.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Data {
     /// A text
+    #[cfg_attr(feature = "serde", serde(rename = "Text"))]
     Text(String),
     /// A 32 bit signed integer
+    #[cfg_attr(feature = "serde", serde(rename = "Integer"))]
     Integer(i32),
     /// A 32 bit float
+    #[cfg_attr(feature = "serde", serde(rename = "Float"))]
     Float(f32),
     /// A signed integer
+    #[cfg_attr(feature = "serde", serde(rename = "Number"))]
     Number(isize),
     /// A boolean value
+    #[cfg_attr(feature = "serde", serde(rename = "Boolean"))]
     Boolean(bool),
     /// An empty cell
     #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "None"))]
     None,
 }
 
@@ -30,61 +48,289 @@ impl Data {
             _ => false,
         }
     }
+
+    /// The position of the variant within the total order of [`Data`].
+    fn order_rank(&self) -> u8 {
+        match self {
+            Data::None => 0,
+            Data::Boolean(_) => 1,
+            Data::Integer(_) => 2,
+            Data::Number(_) => 3,
+            Data::Float(_) => 4,
+            Data::Text(_) => 5,
+        }
+    }
+
+    pub(crate) fn as_i128(&self) -> Option<i128> {
+        match self {
+            Data::Integer(int) => Some(*int as i128),
+            Data::Number(num) => Some(*num as i128),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Data::Integer(int) => Some(*int as f64),
+            Data::Number(num) => Some(*num as f64),
+            Data::Float(float) => Some(*float as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the sum of two numbers in the wider of their types. Integers
+    /// overflowing `i32` become numbers, and a float makes the sum a float.
+    ///
+    /// Returns [`None`] if either is not a number or the sum overflows
+    /// `isize`.
+    pub(crate) fn checked_add(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (Data::Integer(x), Data::Integer(y)) => x
+                .checked_add(*y)
+                .map(Data::Integer)
+                .or_else(|| (*x as isize).checked_add(*y as isize).map(Data::Number)),
+            (Data::Float(_), _) | (_, Data::Float(_)) => {
+                Some(Data::Float((self.as_f64()? + other.as_f64()?) as f32))
+            }
+            _ => {
+                let sum = self.as_i128()?.checked_add(other.as_i128()?)?;
+                isize::try_from(sum).ok().map(Data::Number)
+            }
+        }
+    }
+
+    /// Clamps numeric data to the range `lower..=upper`, keeping its variant.
+    /// Integral variants are rounded to the nearest bound. Non-numeric data
+    /// is returned as-is.
+    pub(crate) fn clamp(self, lower: f64, upper: f64) -> Self {
+        match self.as_f64() {
+            Some(value) if value < lower => Self::from_f64(&self, lower),
+            Some(value) if value > upper => Self::from_f64(&self, upper),
+            _ => self,
+        }
+    }
+
+    /// Returns `self` as a CSV field, which [`Data::from_csv_field`] parses
+    /// back into the same value when given the [`ColumnType`] of `self`.
+    ///
+    /// Unlike [`Display`](fmt::Display), [`Data::None`] is an empty field and
+    /// floats always have a fractional part, so `Float(1.0)` is written as
+    /// `1.0` rather than `1`. The payloads of `NaN` are not preserved.
+    pub fn to_csv_field(&self) -> String {
+        match self {
+            Self::None => String::new(),
+            Self::Float(fl) if fl.is_finite() => {
+                let field = fl.to_string();
+                if field.contains('.') {
+                    field
+                } else {
+                    field + ".0"
+                }
+            }
+            data => data.to_string(),
+        }
+    }
+
+    /// Parses a CSV field into [`Data`].
+    ///
+    /// When `expected` is a concrete type, `field` is parsed as that type,
+    /// with a [`ColumnType::Text`] keeping `field` as-is, empty or not. If
+    /// `expected` is [`None`], [`ColumnType::None`] or [`ColumnType::Mixed`],
+    /// or parsing fails, the variant is inferred instead: an empty field is
+    /// [`Data::None`], followed by, in order, [`Data::Integer`],
+    /// [`Data::Boolean`], [`Data::Number`], [`Data::Float`] and
+    /// [`Data::Text`]. Inference is shared by every conversion of text into
+    /// [`Data`], including the cells of a [`Sheet`](super::Sheet) and
+    /// `From<&str>`.
+    pub fn from_csv_field(field: &str, expected: Option<ColumnType>) -> Self {
+        let parsed = match expected {
+            Some(ColumnType::Text) => Some(Data::Text(field.to_string())),
+            _ if field.is_empty() => Some(Data::None),
+            Some(ColumnType::Integer) => field.parse().ok().map(Data::Integer),
+            Some(ColumnType::Number) => field.parse().ok().map(Data::Number),
+            Some(ColumnType::Float) => f32::parse_field(field).map(Data::Float),
+            Some(ColumnType::Boolean) => field.parse().ok().map(Data::Boolean),
+            _ => None,
+        };
+
+        parsed.unwrap_or_else(|| Self::infer(field.to_string()))
+    }
+
+    /// Casts the data into a value of `kind`, if it can be done without
+    /// losing information. Data already matching `kind` is returned as is.
+    /// Text is parsed as `kind`, integers widen into floats and floats with no
+    /// fractional part narrow into integers. Any value can become text.
+    pub(crate) fn coerce(&self, kind: &ColumnType) -> Option<Self> {
+        if kind.crosscheck_type(self) {
+            return Some(self.clone());
+        }
+
+        let coerced = match (self, kind) {
+            (data, ColumnType::Text) => Data::Text(data.to_string()),
+            (Data::Text(text), kind) => Data::from_csv_field(text.trim(), Some(*kind)),
+            (Data::Integer(int), ColumnType::Number) => Data::Number(*int as isize),
+            (Data::Integer(int), ColumnType::Float) => Data::Float(*int as f32),
+            (Data::Number(num), ColumnType::Integer) => Data::Integer(i32::try_from(*num).ok()?),
+            (Data::Number(num), ColumnType::Float) => Data::Float(*num as f32),
+            (Data::Float(fl), ColumnType::Integer) if fl.fract() == 0.0 => {
+                let int = *fl as i32;
+                if int as f32 != *fl {
+                    return None;
+                }
+                Data::Integer(int)
+            }
+            (Data::Float(fl), ColumnType::Number) if fl.fract() == 0.0 => {
+                let num = *fl as isize;
+                if num as f32 != *fl {
+                    return None;
+                }
+                Data::Number(num)
+            }
+            _ => return None,
+        };
+
+        if kind.crosscheck_type(&coerced) {
+            Some(coerced)
+        } else {
+            None
+        }
+    }
+
+    fn infer(value: String) -> Self {
+        if value.is_empty() {
+            return Data::None;
+        }
+
+        if let Ok(parsed_i32) = value.parse::<i32>() {
+            return Data::Integer(parsed_i32);
+        };
+
+        if let Ok(parsed_bool) = value.parse::<bool>() {
+            return Data::Boolean(parsed_bool);
+        };
+
+        if let Ok(parsed_num) = value.parse::<isize>() {
+            return Data::Number(parsed_num);
+        };
+
+        if let Some(parsed_float) = f32::parse_field(&value) {
+            return Data::Float(parsed_float);
+        }
+
+        Data::Text(value)
+    }
+
+    /// Creates data of the same variant as `kind` from `value`.
+    fn from_f64(kind: &Self, value: f64) -> Self {
+        match kind {
+            Data::Integer(_) => Data::Integer(value.round() as i32),
+            Data::Number(_) => Data::Number(value.round() as isize),
+            _ => Data::Float(value as f32),
+        }
+    }
+}
+
+/// Values parsed from CSV fields, with one syntax shared by a
+/// [`Sheet`](super::Sheet) and a `ColumnSheet` so a file reads alike into
+/// either.
+///
+/// Numbers follow their [`FromStr`] syntax: integers may have a leading `+`,
+/// and floats may also use scientific notation, such as `1e5` or `2.5E-3`,
+/// or be one of `inf`, `infinity` and `nan` in any case and with an optional
+/// sign. A float literal too large for its type is not parsed, rather than
+/// read as an infinity.
+pub(crate) trait ParseField: FromStr {
+    fn parse_field(field: &str) -> Option<Self> {
+        field.parse().ok()
+    }
+}
+
+impl ParseField for i32 {}
+impl ParseField for u32 {}
+impl ParseField for isize {}
+impl ParseField for usize {}
+impl ParseField for bool {}
+impl ParseField for String {}
+
+macro_rules! parse_float {
+    ($($float:ty),+) => {
+        $(
+            impl ParseField for $float {
+                fn parse_field(field: &str) -> Option<Self> {
+                    let value = field.parse::<$float>().ok()?;
+                    if value.is_infinite() && !is_infinity(field) {
+                        return None;
+                    }
+
+                    Some(value)
+                }
+            }
+        )+
+    };
+}
+
+parse_float!(f32, f64);
+
+/// Returns true if `field` spells out an infinity, rather than a number
+/// too large to represent.
+fn is_infinity(field: &str) -> bool {
+    let unsigned = field.strip_prefix(&['+', '-'][..]).unwrap_or(field);
+    unsigned.eq_ignore_ascii_case("inf") || unsigned.eq_ignore_ascii_case("infinity")
 }
 
-#[allow(clippy::non_canonical_partial_ord_impl)]
 impl cmp::PartialOrd for Data {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Data {
+    fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Data::Text(x), Data::Text(y)) => x.partial_cmp(y),
-            (Data::Text(_), _) => Some(Ordering::Greater),
-            (Data::Number(x), Data::Number(y)) => x.partial_cmp(y),
-            (Data::Number(_), Data::Text(_)) => Some(Ordering::Less),
-            (Data::Number(_), _) => Some(Ordering::Greater),
-            (Data::Float(x), Data::Float(y)) => x.partial_cmp(y),
-            (Data::Float(_), Data::Text(_)) => Some(Ordering::Less),
-            (Data::Float(_), Data::Number(_)) => Some(Ordering::Less),
-            (Data::Float(_), _) => Some(Ordering::Greater),
-            (Data::Integer(x), Data::Integer(y)) => x.partial_cmp(y),
-            (Data::Integer(_), Data::Text(_)) => Some(Ordering::Less),
-            (Data::Integer(_), Data::Number(_)) => Some(Ordering::Less),
-            (Data::Integer(_), Data::Float(_)) => Some(Ordering::Less),
-            (Data::Integer(_), _) => Some(Ordering::Greater),
-            (Data::Boolean(x), Data::Boolean(y)) => x.partial_cmp(y),
-            (Data::Boolean(_), Data::Text(_)) => Some(Ordering::Less),
-            (Data::Boolean(_), Data::Number(_)) => Some(Ordering::Less),
-            (Data::Boolean(_), Data::Float(_)) => Some(Ordering::Less),
-            (Data::Boolean(_), Data::Integer(_)) => Some(Ordering::Less),
-            (Data::Boolean(_), _) => Some(Ordering::Greater),
-            (Data::None, Data::None) => Some(Ordering::Equal),
-            (Data::None, _) => Some(Ordering::Less),
+            (Data::Text(x), Data::Text(y)) => x == y,
+            (Data::Integer(x), Data::Integer(y)) => x == y,
+            (Data::Float(x), Data::Float(y)) => x.to_bits() == y.to_bits(),
+            (Data::Number(x), Data::Number(y)) => x == y,
+            (Data::Boolean(x), Data::Boolean(y)) => x == y,
+            (Data::None, Data::None) => true,
+            _ => false,
         }
     }
 }
 
 impl Eq for Data {}
 
+/// [`Data`] is totally ordered as follows:
+///
+/// `None` < `Boolean` < numeric values < `Text`
+///
+/// Numeric values (`Integer`, `Number` and `Float`) are compared by value with
+/// each other. Numerically equal values of different variants are ordered as
+/// `Integer` < `Number` < `Float`. Floats are compared using [`f32::total_cmp`].
+///
+/// This matches the ordering of `CellRef` used by `ColumnSheet`.
 impl cmp::Ord for Data {
     fn cmp(&self, other: &Self) -> Ordering {
-        if let Some(ord) = self.partial_cmp(other) {
-            ord
-        } else {
-            // Special case for NaN. Should only happend when both are f32
-            match self {
-                Data::Float(f) => {
-                    if f.is_nan() {
-                        Ordering::Less
-                    } else {
-                        Ordering::Greater
-                    }
-                }
+        let ord = match (self, other) {
+            (Data::Text(x), Data::Text(y)) => x.cmp(y),
+            (Data::Boolean(x), Data::Boolean(y)) => x.cmp(y),
+            (Data::Float(x), Data::Float(y)) => x.total_cmp(y),
+            (x, y) => match (x.as_i128(), y.as_i128()) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                _ => match (x.as_f64(), y.as_f64()) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    _ => Ordering::Equal,
+                },
+            },
+        };
 
-                _ => panic!("Partial_cmp for Data returned None. Only floats should do so"),
-            }
-        }
+        ord.then_with(|| self.order_rank().cmp(&other.order_rank()))
     }
 }
 
+/// Floats are hashed by their bits, without allocating. Both zeros hash
+/// alike, as do all `NaN`s, though they remain unequal unless their bits are
+/// the same. See [`PartialEq`] for [`Data`].
 impl hash::Hash for Data {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         match self {
@@ -92,7 +338,16 @@ impl hash::Hash for Data {
             Data::Integer(i) => i.hash(state),
             Data::Number(n) => n.hash(state),
             Data::Boolean(b) => b.hash(state),
-            Data::Float(f) => format!("{}", f).hash(state),
+            Data::Float(f) => {
+                let normalized = if *f == 0.0 {
+                    0.0
+                } else if f.is_nan() {
+                    f32::NAN
+                } else {
+                    *f
+                };
+                normalized.to_bits().hash(state)
+            }
             Data::None => "<None>".hash(state),
         }
     }
@@ -118,31 +373,9 @@ impl From<bool> for Data {
 }
 
 impl From<String> for Data {
+    /// Infers the variant from `value`. See [`Data::from_csv_field`].
     fn from(value: String) -> Self {
-        if value.is_empty() {
-            return Data::None;
-        }
-
-        if let Ok(parsed_i32) = value.parse::<i32>() {
-            return Data::Integer(parsed_i32);
-        };
-
-        if let Ok(parsed_bool) = value.parse::<bool>() {
-            return Data::Boolean(parsed_bool);
-        };
-
-        if let Ok(parsed_float) = value.parse::<f32>() {
-            return Data::Float(parsed_float);
-        }
-
-        if let Ok(parsed_num) = value.parse::<isize>() {
-            return Data::Number(parsed_num);
-        };
-
-        if value == Data::None.to_string() {
-            return Data::None;
-        }
-        Data::Text(value)
+        Self::infer(value)
     }
 }
 
@@ -177,6 +410,8 @@ impl From<Data> for String {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum ColumnType {
     /// A text column
     Text,
@@ -189,29 +424,54 @@ pub enum ColumnType {
     /// A boolean column
     Boolean,
     #[default]
-    /// A non-uniform type column
+    /// A column of unknown type, such as one made up of only empty cells or
+    /// one whose types were never inferred
     None,
+    /// A column whose non-empty cells have different types
+    Mixed,
 }
 
 impl Eq for ColumnType {}
 
 impl ColumnType {
     /// Returns true if data is equivalent to this column type.
-    /// For flexibility reasons, ColumnType::None always returns true
+    ///
+    /// Empty data matches every column type. Since their cells are not of a
+    /// single type, [`ColumnType::None`] and [`ColumnType::Mixed`] match any
+    /// data.
     pub fn crosscheck_type(&self, data: &Data) -> bool {
         if let Data::None = data {
             return true;
         };
         let conv: ColumnType = data.clone().into();
         match self {
-            ColumnType::None => true,
+            ColumnType::None | ColumnType::Mixed => true,
             _ => &conv == self,
         }
     }
+
+    /// Returns true if the cells of a column of this type can be ordered.
+    pub fn is_sortable(&self) -> bool {
+        !matches!(self, ColumnType::Mixed)
+    }
+
+    /// Returns true for numeric column types.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            ColumnType::Integer | ColumnType::Number | ColumnType::Float
+        )
+    }
 }
 
 impl From<Data> for ColumnType {
     fn from(value: Data) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&Data> for ColumnType {
+    fn from(value: &Data) -> Self {
         match value {
             Data::Text(_) => Self::Text,
             Data::Float(_) => Self::Float,
@@ -223,6 +483,21 @@ impl From<Data> for ColumnType {
     }
 }
 
+impl ColumnType {
+    /// The name of the type, as accepted by its [`FromStr`] implementation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Mixed => "mixed",
+            Self::Boolean => "boolean",
+            Self::Text => "text",
+            Self::Float => "float",
+            Self::Integer => "integer",
+            Self::Number => "number",
+        }
+    }
+}
+
 impl fmt::Display for ColumnType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -230,6 +505,7 @@ impl fmt::Display for ColumnType {
             "{}",
             match self {
                 Self::None => "No Column Type",
+                Self::Mixed => "Mixed Column Type",
                 Self::Boolean => "Boolean Column Type",
                 Self::Text => "Text Column Type",
                 Self::Float => "Float Column Type",
@@ -240,25 +516,97 @@ impl fmt::Display for ColumnType {
     }
 }
 
+impl FromStr for ColumnType {
+    type Err = ParseTypeError;
+
+    /// Parses a [`ColumnType`] from its name, ignoring case and surrounding
+    /// whitespace. The aliases `int`, `num`, `str` and `bool` are also accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" | "str" => Ok(Self::Text),
+            "integer" | "int" => Ok(Self::Integer),
+            "number" | "num" => Ok(Self::Number),
+            "float" => Ok(Self::Float),
+            "boolean" | "bool" => Ok(Self::Boolean),
+            "none" => Ok(Self::None),
+            "mixed" => Ok(Self::Mixed),
+            _ => Err(ParseTypeError::new(s.trim(), None)),
+        }
+    }
+}
+
+/// The unit of the values within a column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    Percent,
+    /// A currency, such as `$` or `EUR`
+    Currency(String),
+    Custom(String),
+}
+
+impl Unit {
+    /// Returns `label` followed by the unit in parentheses, such as
+    /// `Growth (%)`.
+    pub fn annotate(&self, label: &str) -> String {
+        if label.is_empty() {
+            format!("({})", self)
+        } else {
+            format!("{} ({})", label, self)
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Percent => write!(f, "%"),
+            Self::Currency(currency) => write!(f, "{}", currency),
+            Self::Custom(unit) => write!(f, "{}", unit),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnHeader {
     /// The label for the column
     pub label: String,
     /// The type of column
     pub kind: ColumnType,
+    /// The unit of the values within the column, if any
+    pub unit: Option<Unit>,
 }
 
 impl ColumnHeader {
     pub fn new(label: String, kind: ColumnType) -> Self {
-        Self { label, kind }
+        Self {
+            label,
+            kind,
+            unit: None,
+        }
+    }
+
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Returns the label annotated with the unit, if any. See
+    /// [`Unit::annotate`].
+    pub fn axis_label(&self) -> String {
+        match &self.unit {
+            Some(unit) => unit.annotate(&self.label),
+            None => self.label.clone(),
+        }
     }
 
     pub fn set_label(&mut self, label: String) {
         self.label = label;
     }
 
-    /// Returns true if data is equivalent to this column type.
-    /// For flexibility reasons, ColumnType::None always returns true
+    /// Returns true if data is equivalent to this column type. See
+    /// [`ColumnType::crosscheck_type`].
     pub fn crosscheck_type(&self, data: &Data) -> bool {
         self.kind.crosscheck_type(data)
     }
@@ -269,15 +617,128 @@ impl default::Default for ColumnHeader {
         Self {
             label: "".into(),
             kind: ColumnType::None,
+            unit: None,
         }
     }
 }
 
+/// Facts about the values of a column, gathered while it is loaded. See
+/// [`Config::collect_meta`](crate::repr::Config::collect_meta).
+///
+/// `T` is the owned value type of the sheet: [`Data`] for a
+/// [`Sheet`](super::Sheet) and
+/// [`CellValue`](crate::repr::col_sheet::CellValue) for a
+/// [`ColumnSheet`](crate::repr::col_sheet::ColumnSheet). The meta is dropped
+/// once the values of its column change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMeta<T = Data> {
+    bounds: Option<(T, T)>,
+    null_count: usize,
+    distinct: usize,
+    capped: bool,
+    increasing: bool,
+}
+
+impl<T> ColumnMeta<T> {
+    /// The number of distinct values past which they are no longer counted.
+    pub const DISTINCT_CAP: usize = 10_000;
+
+    pub(crate) fn new(
+        bounds: Option<(T, T)>,
+        null_count: usize,
+        distinct: (usize, bool),
+        increasing: bool,
+    ) -> Self {
+        Self {
+            bounds,
+            null_count,
+            distinct: distinct.0,
+            capped: distinct.1,
+            increasing,
+        }
+    }
+
+    /// Returns the smallest value of the column, as the column's `col_bounds`
+    /// would.
+    pub fn min(&self) -> Option<&T> {
+        self.bounds.as_ref().map(|(min, _)| min)
+    }
+
+    /// Returns the largest value of the column, as the column's `col_bounds`
+    /// would.
+    pub fn max(&self) -> Option<&T> {
+        self.bounds.as_ref().map(|(_, max)| max)
+    }
+
+    pub(crate) fn bounds(&self) -> Option<&(T, T)> {
+        self.bounds.as_ref()
+    }
+
+    /// Returns the number of empty cells within the column.
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+
+    /// Returns the number of distinct values within the column, ignoring
+    /// empty cells. Counting stops at [`ColumnMeta::DISTINCT_CAP`], so this
+    /// is a lower bound for columns with more distinct values.
+    pub fn distinct(&self) -> usize {
+        self.distinct
+    }
+
+    /// Returns the number of distinct values within the column if it is no
+    /// more than [`ColumnMeta::DISTINCT_CAP`].
+    pub fn exact_distinct(&self) -> Option<usize> {
+        (!self.capped).then_some(self.distinct)
+    }
+
+    /// Returns true if no value of the column is smaller than the one
+    /// before it, ignoring empty cells.
+    pub fn is_increasing(&self) -> bool {
+        self.increasing
+    }
+}
+
+/// Returns the number of distinct `keys`, and whether counting stopped at
+/// [`ColumnMeta::DISTINCT_CAP`].
+pub(crate) fn count_distinct<K: hash::Hash + Eq>(keys: impl Iterator<Item = K>) -> (usize, bool) {
+    let mut seen = HashSet::new();
+
+    for key in keys {
+        seen.insert(key);
+        if seen.len() > ColumnMeta::<Data>::DISTINCT_CAP {
+            return (ColumnMeta::<Data>::DISTINCT_CAP, true);
+        }
+    }
+
+    (seen.len(), false)
+}
+
+/// Returns true if none of `values` is smaller than the one before it.
+pub(crate) fn is_increasing<V: Ord>(values: impl Iterator<Item = V>) -> bool {
+    let mut values = values.peekable();
+
+    while let Some(value) = values.next() {
+        if values.peek().map_or(false, |next| *next < value) {
+            return false;
+        }
+    }
+
+    true
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 /// Determines how types for each column are generated.
 pub enum TypesStrategy {
     /// The types are infered from the csv
     Infer,
+    /// Like [`TypesStrategy::Infer`], but columns of values with differing
+    /// types are read into a
+    /// [`ArrayUnion`](crate::repr::col_sheet::ArrayUnion) by a
+    /// [`ColumnSheet`](crate::repr::col_sheet::ColumnSheet), keeping the type
+    /// of each value. A `Sheet` already keeps the type of each value, so
+    /// this is the same as [`TypesStrategy::Infer`] there.
+    InferWithUnion,
     /// The types are provided as a vector
     Provided(Vec<ColumnType>),
     /// All columns have a mixed type
@@ -285,6 +746,23 @@ pub enum TypesStrategy {
     None,
 }
 
+impl TypesStrategy {
+    /// The name of the strategy, as accepted by its [`FromStr`]
+    /// implementation. [`TypesStrategy::Provided`] is named by `provided:`
+    /// followed by the [`ColumnType::name`] of each type, separated by commas.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Infer => "infer".to_owned(),
+            Self::InferWithUnion => "infer-union".to_owned(),
+            Self::Provided(types) => {
+                let types = types.iter().map(ColumnType::name).collect::<Vec<_>>();
+                format!("provided:{}", types.join(","))
+            }
+            Self::None => "none".to_owned(),
+        }
+    }
+}
+
 impl fmt::Display for TypesStrategy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -292,6 +770,7 @@ impl fmt::Display for TypesStrategy {
             "{}",
             match self {
                 Self::Infer => "Infer types",
+                Self::InferWithUnion => "Infer types with unions",
                 Self::Provided(_) => "Provide types",
                 Self::None => "No types",
             },
@@ -299,14 +778,50 @@ impl fmt::Display for TypesStrategy {
     }
 }
 
+impl FromStr for TypesStrategy {
+    type Err = ParseTypeError;
+
+    /// Parses `infer`, `infer-union` and `none` as their respective strategies.
+    /// `provided:` followed by a comma separated list of [`ColumnType`]s, which
+    /// may be empty, is parsed as [`TypesStrategy::Provided`]; this is the form
+    /// returned by [`TypesStrategy::name`]. Any other input
+    /// is parsed as a list of types using [`parse_type_list`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+
+        if let Some(prefix) = trimmed.get(..9) {
+            if prefix.eq_ignore_ascii_case("provided:") {
+                let types = &trimmed[9..];
+                if types.trim().is_empty() {
+                    return Ok(Self::Provided(Vec::new()));
+                }
+                return parse_type_list(types).map(Self::Provided);
+            }
+        }
+
+        match trimmed.to_lowercase().as_str() {
+            "infer" => Ok(Self::Infer),
+            "infer-union" => Ok(Self::InferWithUnion),
+            "none" => Ok(Self::None),
+            _ => parse_type_list(s).map(Self::Provided),
+        }
+    }
+}
+
 /// Determines how the labels of the line graph created from a sheet are handled
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum LineLabelStrategy {
     /// Label is derived from a the cells of a column. The values are not used
     /// within the line graph
     FromCell(usize),
-    /// Labels for each line are provided. Excess labels are ignored. Lines with
-    /// no labels receive a [`LineLabelStrategy::None`]
+    /// Like [`LineLabelStrategy::FromCell`], with the transform applied to
+    /// each label before it is stored on its line
+    FromCellWith(usize, LabelTransform),
+    /// Labels for each line are provided. The label at index `i` belongs to
+    /// the line of the row at index `i` of the sheet, so excluding rows does
+    /// not shift labels onto other lines. Lines with no labels receive a
+    /// [`LineLabelStrategy::None`]. Providing more labels than rows is an
+    /// error
     Provided(Vec<String>),
     /// No labels
     #[default]
@@ -320,13 +835,61 @@ impl fmt::Display for LineLabelStrategy {
             "{}",
             match self {
                 Self::None => "No line labels",
-                Self::FromCell(_) => "Label using a cell",
+                Self::FromCell(_) | Self::FromCellWith(..) => "Label using a cell",
                 Self::Provided(_) => "Label provided",
             }
         )
     }
 }
 
+impl LineLabelStrategy {
+    /// Returns the column the labels are read from, if any.
+    pub fn label_col(&self) -> Option<usize> {
+        match self {
+            Self::FromCell(col) | Self::FromCellWith(col, _) => Some(*col),
+            Self::Provided(_) | Self::None => None,
+        }
+    }
+
+    /// Returns the label of a line read from the cell `value`, transformed
+    /// as the strategy asks.
+    pub(crate) fn cell_label(&self, value: &Data) -> String {
+        match self {
+            Self::FromCellWith(_, transform) => transform.apply(&value.to_string()),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Determines how the axis labels are generated for a line graph
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum LineGraphAxisLabelStrategy {
+    /// The y axis label is provided, while the x axis label is the header of
+    /// the column the line labels are read from. See
+    /// [`LineLabelStrategy::label_col`]. Other line label strategies produce
+    /// no x axis label
+    Headers { y: String },
+    /// The axis labels are provided.
+    Provided { x: String, y: String },
+    /// No labels are generated
+    #[default]
+    None,
+}
+
+impl fmt::Display for LineGraphAxisLabelStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::None => "No labels",
+                Self::Provided { .. } => "Labels provided",
+                Self::Headers { .. } => "Y axis provided",
+            }
+        )
+    }
+}
+
 /// Determines how the axis labels are generated for a bar chart
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum BarChartAxisLabelStrategy {
@@ -361,8 +924,13 @@ pub enum BarChartBarLabels {
     None,
     /// Values from corresponding column used as the labels
     FromColumn(usize),
-    /// Labels are provided Excess labels are ignored. Lines with
-    /// no labels receive a [`BarChartBarLabels::None`]
+    /// Like [`BarChartBarLabels::FromColumn`], with the transform applied to
+    /// each label before it is stored on its bar
+    FromColumnWith(usize, LabelTransform),
+    /// Labels are provided, one for each row of the sheet by position, so
+    /// the label of an excluded row is skipped along with it. Rows without a
+    /// label, or with an empty one, give unlabelled bars. Providing more
+    /// labels than rows is an error.
     Provided(Vec<String>),
 }
 
@@ -374,12 +942,368 @@ impl fmt::Display for BarChartBarLabels {
             match self {
                 Self::None => "No labels",
                 Self::Provided(_) => "Labels provided",
-                Self::FromColumn(_) => "Labels from a column",
+                Self::FromColumn(_) | Self::FromColumnWith(..) => "Labels from a column",
+            }
+        )
+    }
+}
+
+impl BarChartBarLabels {
+    /// Returns the column the labels are read from, if any.
+    pub fn label_col(&self) -> Option<usize> {
+        match self {
+            Self::FromColumn(col) | Self::FromColumnWith(col, _) => Some(*col),
+            Self::Provided(_) | Self::None => None,
+        }
+    }
+
+    /// Returns the label of a bar read from the cell `value`, transformed as
+    /// the labels ask.
+    pub(crate) fn cell_label(&self, value: &Data) -> String {
+        match self {
+            Self::FromColumnWith(_, transform) => transform.apply(&value.to_string()),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Determines how a boolean column is charted as values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoolMode {
+    /// `true` is charted as 1 and `false` as 0
+    AsBinary,
+    /// Each bar is the number of `true` values sharing its x value. Only
+    /// supported by bar charts
+    CountTrue,
+}
+
+impl fmt::Display for BoolMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::AsBinary => "As 1 and 0",
+                Self::CountTrue => "Count of true",
+            }
+        )
+    }
+}
+
+/// Determines how the empty cells of the charted columns are handled when
+/// converting a sheet to a chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MissingPolicy {
+    /// Empty cells are charted as they are. Line graphs keep points with an
+    /// empty y value, bar charts keep bars with an empty x or y value, and
+    /// stacked bar charts leave out empty sections but keep bars with an
+    /// empty x value
+    #[default]
+    Keep,
+    /// Points, bars and sections with an empty value are left out. A row
+    /// with an empty x value has no bars in a stacked bar chart
+    Skip,
+    /// Empty cells are charted as the zero of the type of their column, such
+    /// as `0` for integers or `false` for booleans. Cells of columns without
+    /// a zero, such as text columns, are kept empty
+    FillZero,
+    /// Empty cells are an [`Error::MissingValue`](super::Error::MissingValue)
+    Error,
+}
+
+impl fmt::Display for MissingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Keep => "Keep",
+                Self::Skip => "Skip",
+                Self::FillZero => "Fill with zero",
+                Self::Error => "Error",
+            }
+        )
+    }
+}
+
+/// Options applied to the scales of a chart during conversion.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScaleOptions {
+    clamp: Option<(f64, f64)>,
+    top_n: Option<TopN>,
+    descending: bool,
+    bool_mode: Option<BoolMode>,
+    include_zero: Option<bool>,
+    allow_empty: bool,
+    missing: MissingPolicy,
+    categorical_order: CategoricalOrder,
+}
+
+impl ScaleOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the value scale clamped to the values between the `lower` and
+    /// `upper` percentiles, both within `0.0..=100.0`, so outliers do not
+    /// stretch the scale. Values are charted as they are, so outliers lie off
+    /// the clamped scale, which is kept apart from the full value scale as
+    /// the `y_view` of line graphs and bar charts.
+    pub fn clamp_percentiles(self, lower: f64, upper: f64) -> Self {
+        Self {
+            clamp: Some((lower, upper)),
+            ..self
+        }
+    }
+
+    /// Keeps only the largest categories on the category scale, merging the
+    /// rest into a single category. Values must be numeric.
+    ///
+    /// Categories are bucketed before any clamping is applied.
+    pub fn top_n(self, top_n: TopN) -> Self {
+        Self {
+            top_n: Some(top_n),
+            ..self
+        }
+    }
+
+    /// Makes the value scale descending. See [`Scale::invert`].
+    ///
+    /// [`Scale::invert`]: crate::models::Scale::invert
+    pub fn descending(self, descending: bool) -> Self {
+        Self { descending, ..self }
+    }
+
+    /// Charts a boolean value column as integers, using `mode`. Boolean value
+    /// columns are rejected without a mode.
+    pub fn bool_mode(self, mode: BoolMode) -> Self {
+        Self {
+            bool_mode: Some(mode),
+            ..self
+        }
+    }
+
+    /// Extends the value scale to include zero. See
+    /// [`Scale::include_zero`].
+    ///
+    /// Bar and stacked bar charts include zero unless this is false, so bars
+    /// grow from a zero baseline. Line graphs do not unless this is true.
+    ///
+    /// [`Scale::include_zero`]: crate::models::Scale::include_zero
+    pub fn include_zero(self, include_zero: bool) -> Self {
+        Self {
+            include_zero: Some(include_zero),
+            ..self
+        }
+    }
+
+    /// Allows charts with nothing to chart, as when every row is excluded,
+    /// returning an empty chart with empty scales. Such charts are otherwise
+    /// an [`Error::NoRows`].
+    ///
+    /// [`Error::NoRows`]: super::Error::NoRows
+    pub fn allow_empty(self, allow_empty: bool) -> Self {
+        Self {
+            allow_empty,
+            ..self
+        }
+    }
+
+    /// How the empty cells of the charted columns are handled. Defaults to
+    /// [`MissingPolicy::Keep`].
+    pub fn missing(self, missing: MissingPolicy) -> Self {
+        Self { missing, ..self }
+    }
+
+    /// Orders the category scale by `order`, if every category is part of
+    /// it, such as months from January to December rather than in the order
+    /// first seen. See [`Scale::set_order`].
+    ///
+    /// [`Scale::set_order`]: crate::models::Scale::set_order
+    pub fn categorical_order(self, categorical_order: CategoricalOrder) -> Self {
+        Self {
+            categorical_order,
+            ..self
+        }
+    }
+
+    /// Returns the percentiles the scale is clamped to, if any.
+    pub fn get_clamp(&self) -> Option<(f64, f64)> {
+        self.clamp
+    }
+
+    /// Returns the bucketing applied to the category scale, if any.
+    pub fn get_top_n(&self) -> Option<&TopN> {
+        self.top_n.as_ref()
+    }
+
+    /// Returns true if the value scale is descending.
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Returns how boolean value columns are charted, if at all.
+    pub fn get_bool_mode(&self) -> Option<BoolMode> {
+        self.bool_mode
+    }
+
+    /// Returns whether the value scale includes zero, if set.
+    pub fn get_include_zero(&self) -> Option<bool> {
+        self.include_zero
+    }
+
+    /// Returns true if charts with nothing to chart are allowed.
+    pub fn allows_empty(&self) -> bool {
+        self.allow_empty
+    }
+
+    /// Returns how the empty cells of the charted columns are handled.
+    pub fn get_missing(&self) -> MissingPolicy {
+        self.missing
+    }
+
+    /// Returns the order of the category scale.
+    pub fn get_categorical_order(&self) -> &CategoricalOrder {
+        &self.categorical_order
+    }
+}
+
+/// How the values of two sheets are compared by
+/// [`Sheet::diff_line_graph`](super::Sheet::diff_line_graph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DiffMode {
+    /// The value of the other sheet less that of the base sheet
+    #[default]
+    Delta,
+    /// The difference as a percentage of the value of the base sheet. Values
+    /// changing from zero have no percent change
+    PercentChange,
+}
+
+impl fmt::Display for DiffMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Delta => "Change",
+                Self::PercentChange => "Change (%)",
             }
         )
     }
 }
 
+/// Options for comparing two sheets with
+/// [`Sheet::diff_line_graph`](super::Sheet::diff_line_graph).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DiffOptions {
+    mode: DiffMode,
+    strict: bool,
+}
+
+impl DiffOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How the values of the two sheets are compared.
+    pub fn mode(self, mode: DiffMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Rejects sheets whose keys or columns do not all match, rather than
+    /// reporting them in the [`DiffSummary`].
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Returns how the values of the two sheets are compared.
+    pub fn get_mode(&self) -> DiffMode {
+        self.mode
+    }
+
+    /// Returns true if mismatched keys or columns are rejected.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
+
+/// The rows and columns of two sheets left out of a comparison because only
+/// one of the sheets has them. Returned by
+/// [`Sheet::diff_line_graph`](super::Sheet::diff_line_graph).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    /// The keys of the rows only in the base sheet, in order.
+    pub base_only_rows: Vec<Data>,
+    /// The keys of the rows only in the other sheet, in order.
+    pub other_only_rows: Vec<Data>,
+    /// The labels of the numeric columns only in the base sheet, in order.
+    pub base_only_cols: Vec<String>,
+    /// The labels of the numeric columns only in the other sheet, in order.
+    pub other_only_cols: Vec<String>,
+}
+
+impl DiffSummary {
+    /// Returns true if every row and column was compared.
+    pub fn is_empty(&self) -> bool {
+        self.base_only_rows.is_empty()
+            && self.other_only_rows.is_empty()
+            && self.base_only_cols.is_empty()
+            && self.other_only_cols.is_empty()
+    }
+}
+
+/// Selects the rows of a [`Sheet`](super::Sheet) to exclude from a
+/// conversion.
+///
+/// [`RowSelector::Indices`] refers to the current position of each row, so
+/// the rows it selects change when the sheet is sorted. [`RowSelector::Keys`]
+/// and [`RowSelector::Predicate`] depend only on the contents of each row and
+/// select the same rows regardless of order.
+pub enum RowSelector {
+    /// The positions of the rows
+    Indices(HashSet<usize>),
+    /// The values of the primary column of the rows
+    Keys(HashSet<Data>),
+    /// The rows for which the predicate returns true
+    Predicate(Box<dyn Fn(&Row) -> bool>),
+}
+
+impl RowSelector {
+    /// Returns true if `row`, at position `idx`, is selected.
+    pub(crate) fn selects(&self, idx: usize, row: &Row) -> bool {
+        match self {
+            Self::Indices(indices) => indices.contains(&idx),
+            Self::Keys(keys) => row
+                .get_primary_cell()
+                .map_or(false, |cell| keys.contains(&cell.data)),
+            Self::Predicate(predicate) => predicate(row),
+        }
+    }
+}
+
+impl Default for RowSelector {
+    fn default() -> Self {
+        Self::Indices(HashSet::new())
+    }
+}
+
+impl From<HashSet<usize>> for RowSelector {
+    fn from(value: HashSet<usize>) -> Self {
+        Self::Indices(value)
+    }
+}
+
+impl fmt::Debug for RowSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Indices(indices) => f.debug_tuple("Indices").field(indices).finish(),
+            Self::Keys(keys) => f.debug_tuple("Keys").field(keys).finish(),
+            Self::Predicate(_) => f.debug_tuple("Predicate").finish(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum StackedBarChartAxisLabelStrategy {
     /// The y axis label is provided, while the header for the x column is used
@@ -405,3 +1329,39 @@ impl fmt::Display for StackedBarChartAxisLabelStrategy {
         )
     }
 }
+
+/// A pattern capturing part of a text value, used by
+/// [`Sheet::extract_col`](super::Sheet::extract_col).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimplePattern {
+    /// The text after the prefix, for values starting with it
+    Prefix(String),
+    /// The text before the suffix, for values ending with it
+    Suffix(String),
+    /// The text between the first `start` and the first `end` after it
+    Delimited { start: String, end: String },
+    /// The first run of ASCII digits
+    Digits,
+}
+
+impl SimplePattern {
+    /// Returns the part of `value` captured by the pattern, if it matches.
+    pub fn capture<'a>(&self, value: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Prefix(prefix) => value.strip_prefix(prefix.as_str()),
+            Self::Suffix(suffix) => value.strip_suffix(suffix.as_str()),
+            Self::Delimited { start, end } => {
+                let (_, rest) = value.split_once(start.as_str())?;
+                rest.split_once(end.as_str()).map(|(captured, _)| captured)
+            }
+            Self::Digits => {
+                let start = value.find(|c: char| c.is_ascii_digit())?;
+                let rest = &value[start..];
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                Some(&rest[..end])
+            }
+        }
+    }
+}
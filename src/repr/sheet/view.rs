@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use crate::models::{bar::BarChart, line::LineGraph, stacked_bar::StackedBarChart, LabelTransform};
+
+use super::{
+    error::{Error, Result},
+    utils::*,
+    Cell, Row, Sheet,
+};
+
+/// A borrowed view of a [`Sheet`] with its columns reordered and hidden,
+/// without copying the sheet.
+///
+/// Columns are addressed in two ways. A *physical* index is the position of
+/// a column within the sheet, while a *view* index is its position among the
+/// visible columns of the view. Reordering uses physical indices. Reads and
+/// conversions use view indices.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::repr::sheet::{Sheet, SheetView};
+/// use modav_core::repr::{Config, HeaderStrategy, TypesStrategy};
+///
+/// let config = Config::new("./dummies/csv/junk_column.csv")
+///     .labels(HeaderStrategy::ReadLabels)
+///     .types(TypesStrategy::Infer);
+/// let sheet = Sheet::with_config(config).unwrap();
+///
+/// let mut view = sheet.view();
+/// view.hide_col(1).unwrap();
+/// view.swap_cols(1, 2).unwrap();
+///
+/// let headers: Vec<_> = view.get_headers().iter().map(|hdr| hdr.label.as_str()).collect();
+/// assert_eq!(headers, vec!["Month", "Returns", "Sales"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SheetView<'a> {
+    sheet: &'a Sheet,
+    /// The physical indices of all columns, in view order
+    order: Vec<usize>,
+    hidden: HashSet<usize>,
+    /// The physical indices of the visible columns, in view order
+    visible: Vec<usize>,
+}
+
+impl<'a> SheetView<'a> {
+    pub(super) fn new(sheet: &'a Sheet) -> Self {
+        let order: Vec<usize> = (0..sheet.width()).collect();
+
+        Self {
+            sheet,
+            visible: order.clone(),
+            order,
+            hidden: HashSet::new(),
+        }
+    }
+
+    fn update_visible(&mut self) {
+        self.visible = self
+            .order
+            .iter()
+            .copied()
+            .filter(|col| !self.hidden.contains(col))
+            .collect();
+    }
+
+    fn out_of_range() -> Error {
+        Error::InvalidColumnLength("Tried to access out of range column".to_string())
+    }
+
+    /// Returns the sheet being viewed.
+    pub fn sheet(&self) -> &'a Sheet {
+        self.sheet
+    }
+
+    /// Orders all columns, hidden or not, by `order`, a permutation of their
+    /// physical indices.
+    ///
+    /// Returns an error if `order` is not a permutation of the columns of the
+    /// sheet.
+    pub fn reorder(&mut self, order: impl IntoIterator<Item = usize>) -> Result<()> {
+        let order: Vec<usize> = order.into_iter().collect();
+        let width = self.sheet.width();
+
+        let distinct = order.iter().collect::<HashSet<&usize>>().len();
+        if order.len() != width || distinct != width || order.iter().any(|col| *col >= width) {
+            return Err(Error::InvalidColumnLength(format!(
+                "Expected a permutation of {} columns",
+                width
+            )));
+        }
+
+        self.order = order;
+        self.update_visible();
+
+        Ok(())
+    }
+
+    /// Swaps the visible columns at view indices `x` and `y`.
+    pub fn swap_cols(&mut self, x: usize, y: usize) -> Result<()> {
+        let x = self.physical_col(x).ok_or_else(Self::out_of_range)?;
+        let y = self.physical_col(y).ok_or_else(Self::out_of_range)?;
+
+        let x = self.order.iter().position(|col| *col == x).unwrap();
+        let y = self.order.iter().position(|col| *col == y).unwrap();
+        self.order.swap(x, y);
+        self.update_visible();
+
+        Ok(())
+    }
+
+    /// Hides the column at physical index `col`. Hiding a hidden column does
+    /// nothing.
+    pub fn hide_col(&mut self, col: usize) -> Result<()> {
+        if col >= self.sheet.width() {
+            return Err(Self::out_of_range());
+        }
+
+        self.hidden.insert(col);
+        self.update_visible();
+
+        Ok(())
+    }
+
+    /// Shows the column at physical index `col` in its place within the
+    /// view order.
+    pub fn show_col(&mut self, col: usize) -> Result<()> {
+        if col >= self.sheet.width() {
+            return Err(Self::out_of_range());
+        }
+
+        self.hidden.remove(&col);
+        self.update_visible();
+
+        Ok(())
+    }
+
+    /// Returns true if the column at physical index `col` is hidden.
+    pub fn is_hidden(&self, col: usize) -> bool {
+        self.hidden.contains(&col)
+    }
+
+    /// Returns the physical index of the column at view index `col`.
+    pub fn physical_col(&self, col: usize) -> Option<usize> {
+        self.visible.get(col).copied()
+    }
+
+    /// Returns the view index of the column at physical index `col`, if it
+    /// is visible.
+    pub fn view_col(&self, col: usize) -> Option<usize> {
+        self.visible.iter().position(|visible| *visible == col)
+    }
+
+    fn physical(&self, col: usize) -> Result<usize> {
+        self.physical_col(col).ok_or_else(Self::out_of_range)
+    }
+
+    /// Returns the number of visible columns.
+    pub fn width(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.sheet.height()
+    }
+
+    /// Returns the headers of the visible columns, in view order.
+    pub fn get_headers(&self) -> Vec<&'a ColumnHeader> {
+        let headers = self.sheet.get_headers();
+        self.visible.iter().map(|col| &headers[*col]).collect()
+    }
+
+    /// Returns the header of the column at view index `col`.
+    pub fn get_header(&self, col: usize) -> Option<&'a ColumnHeader> {
+        let col = self.physical_col(col)?;
+        self.sheet.get_headers().get(col)
+    }
+
+    /// Returns the type of the column at view index `col`.
+    pub fn column_kind(&self, col: usize) -> Option<ColumnType> {
+        self.sheet.column_kind(self.physical_col(col)?)
+    }
+
+    /// Returns an iterator over the rows of the sheet, each showing only the
+    /// visible cells in view order.
+    pub fn iter_rows(&self) -> impl Iterator<Item = RowView<'_>> {
+        self.sheet.iter_rows().map(move |row| RowView {
+            row,
+            columns: &self.visible,
+        })
+    }
+
+    /// Returns a new line graph of the visible columns, in view order. Column
+    /// indices within `label_strat` and `exclude_column` are view indices,
+    /// while rows are selected as in the sheet.
+    ///
+    /// See [`Sheet::create_line_graph_labelled`].
+    pub fn create_line_graph_labelled(
+        &self,
+        axis_labels: LineGraphAxisLabelStrategy,
+        label_strat: LineLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+        exclude_column: HashSet<usize>,
+    ) -> Result<LineGraph> {
+        let label_strat = match label_strat {
+            LineLabelStrategy::FromCell(col) => LineLabelStrategy::FromCell(self.physical(col)?),
+            LineLabelStrategy::FromCellWith(col, transform) => {
+                LineLabelStrategy::FromCellWith(self.physical(col)?, transform)
+            }
+            strat => strat,
+        };
+
+        let mut exclude = self.hidden.clone();
+        for col in exclude_column {
+            exclude.insert(self.physical(col)?);
+        }
+
+        self.sheet.validate_for_chart()?;
+        self.sheet.line_graph_labelled(
+            axis_labels,
+            label_strat,
+            &exclude_row.into(),
+            &exclude,
+            &ScaleOptions::default(),
+            &self.visible,
+        )
+    }
+
+    /// Returns a new bar chart of the values in `y_col` against those in
+    /// `x_col`, both view indices. A [`BarChartBarLabels::FromColumn`] is
+    /// also a view index.
+    ///
+    /// See [`Sheet::create_bar_chart`].
+    pub fn create_bar_chart(
+        &self,
+        x_col: usize,
+        y_col: usize,
+        bar_label: BarChartBarLabels,
+        axis_labels: BarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+    ) -> Result<BarChart> {
+        let x_col = self.physical(x_col)?;
+        let y_col = self.physical(y_col)?;
+        let bar_label = match bar_label {
+            BarChartBarLabels::FromColumn(col) => {
+                BarChartBarLabels::FromColumn(self.physical(col)?)
+            }
+            BarChartBarLabels::FromColumnWith(col, transform) => {
+                BarChartBarLabels::FromColumnWith(self.physical(col)?, transform)
+            }
+            labels => labels,
+        };
+
+        self.sheet.assert_convertible()?;
+        self.sheet.check_booleans(&[y_col], None)?;
+
+        self.sheet.bar_chart(
+            x_col,
+            y_col,
+            bar_label,
+            axis_labels,
+            &exclude_row.into(),
+            &HashSet::new(),
+            &ScaleOptions::default(),
+            false,
+        )
+    }
+
+    /// Returns a new stacked bar chart of the columns in `cols` against
+    /// those in `x_col`, all view indices.
+    ///
+    /// See [`Sheet::create_stacked_bar_chart`].
+    pub fn create_stacked_bar_chart(
+        &self,
+        x_col: usize,
+        cols: impl IntoIterator<Item = usize>,
+        axis_labels: StackedBarChartAxisLabelStrategy,
+        exclude_row: impl Into<RowSelector>,
+    ) -> Result<StackedBarChart> {
+        let x_col = self.physical(x_col)?;
+        let cols = cols
+            .into_iter()
+            .collect::<HashSet<usize>>()
+            .into_iter()
+            .map(|col| self.physical(col))
+            .collect::<Result<Vec<usize>>>()?;
+
+        self.sheet.assert_convertible()?;
+        self.sheet.check_booleans(&cols, None)?;
+
+        self.sheet.stacked_bar_chart(
+            x_col,
+            cols,
+            axis_labels,
+            &exclude_row.into(),
+            &HashSet::new(),
+            &ScaleOptions::default(),
+            &LabelTransform::None,
+        )
+    }
+}
+
+/// A borrowed row of a [`SheetView`], showing only the visible cells in view
+/// order.
+#[derive(Debug, Clone, Copy)]
+pub struct RowView<'a> {
+    row: &'a Row,
+    columns: &'a [usize],
+}
+
+impl<'a> RowView<'a> {
+    /// Returns the row being viewed.
+    pub fn row(&self) -> &'a Row {
+        self.row
+    }
+
+    /// Returns the number of visible cells.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns true if the row has no visible cells.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the cell at view index `index`.
+    pub fn get_cell_by_index(&self, index: usize) -> Option<&'a Cell> {
+        let col = self.columns.get(index)?;
+        self.row.get_cell_by_index(*col)
+    }
+
+    /// Returns an iterator over the visible cells, in view order.
+    pub fn iter_cells(&self) -> impl Iterator<Item = &'a Cell> + 'a {
+        let row = self.row;
+        self.columns
+            .iter()
+            .filter_map(move |col| row.get_cell_by_index(*col))
+    }
+}
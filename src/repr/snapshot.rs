@@ -0,0 +1,350 @@
+//! A compact binary format for caching sheets, enabled by the `serde`
+//! feature.
+//!
+//! A snapshot is laid out as:
+//!
+//! ```text
+//! [version: u8] [kind: u8] [payload] [checksum: u32 LE]
+//! ```
+//!
+//! The payload is written with `bincode`, and the checksum is the CRC-32 of
+//! every byte before it. Snapshots are meant for caches rather than
+//! interchange, so only snapshots of the current version can be read and a
+//! stale cache should be rebuilt from its source.
+
+use std::{error, fmt};
+
+use bincode::Options;
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The version of the snapshot format written by this crate.
+pub const SNAPSHOT_VERSION: u8 = 2;
+
+/// The kinds of sheets a snapshot can hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum SnapshotKind {
+    Sheet = 0,
+    ColumnSheet = 1,
+    Workbook = 2,
+}
+
+/// The bytes of the header and checksum around the payload.
+const FRAME_LEN: usize = 6;
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().reject_trailing_bytes()
+}
+
+/// Writes `value` as a snapshot of the given kind.
+pub(crate) fn encode<T: Serialize>(
+    kind: SnapshotKind,
+    value: &T,
+) -> Result<Vec<u8>, SnapshotError> {
+    let mut bytes = vec![SNAPSHOT_VERSION, kind as u8];
+    options()
+        .serialize_into(&mut bytes, value)
+        .map_err(|err| SnapshotError::Malformed(err.to_string()))?;
+
+    let checksum = crc32(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+
+    Ok(bytes)
+}
+
+/// Reads a snapshot of the given kind written by [`encode`].
+pub(crate) fn decode<T: DeserializeOwned>(
+    kind: SnapshotKind,
+    bytes: &[u8],
+) -> Result<T, SnapshotError> {
+    if bytes.len() < FRAME_LEN {
+        return Err(SnapshotError::Truncated);
+    }
+
+    if bytes[0] != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(bytes[0]));
+    }
+
+    let (body, checksum) = bytes.split_at(bytes.len() - 4);
+    let checksum = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+    if crc32(body) != checksum {
+        return Err(SnapshotError::ChecksumMismatch);
+    }
+
+    if body[1] != kind as u8 {
+        return Err(SnapshotError::WrongKind);
+    }
+
+    options()
+        .deserialize(&body[2..])
+        .map_err(|err| SnapshotError::Malformed(err.to_string()))
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut idx = 0;
+
+    while idx < 256 {
+        let mut crc = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+
+    table
+}
+
+/// The CRC-32 (IEEE) checksum of `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, byte| {
+        CRC_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// A buffer written as a single run of bytes rather than byte by byte.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Bytes(pub(crate) Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a byte buffer")
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Bytes, E> {
+                Ok(Bytes(value.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Bytes, E> {
+                Ok(Bytes(value))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Bytes, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+
+                Ok(Bytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot is too short to hold its header and checksum
+    Truncated,
+    /// The snapshot was written by an unsupported version of the format
+    UnsupportedVersion(u8),
+    /// The checksum does not match the contents of the snapshot
+    ChecksumMismatch,
+    /// The snapshot holds a different kind of sheet
+    WrongKind,
+    /// The contents of the snapshot do not describe a valid sheet
+    Malformed(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "The snapshot is truncated"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported snapshot version {}", version)
+            }
+            Self::ChecksumMismatch => write!(f, "The snapshot is corrupt: checksum mismatch"),
+            Self::WrongKind => write!(f, "The snapshot holds a different kind of sheet"),
+            Self::Malformed(reason) => write!(f, "Malformed snapshot: {}", reason),
+        }
+    }
+}
+
+impl error::Error for SnapshotError {}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+    use crate::repr::{
+        col_sheet::{ColumnSheet, DataType, Error as ColumnSheetError},
+        Config, Error, HeaderStrategy, Sheet, TypesStrategy, Unit,
+    };
+
+    fn config(path: &str) -> Config<&str> {
+        Config::new(path)
+            .trim(true)
+            .labels(HeaderStrategy::ReadLabels)
+            .types(TypesStrategy::Infer)
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_sheet_round_trip() {
+        for path in [
+            "./dummies/csv/air.csv",
+            "./dummies/csv/nulls.csv",
+            "./dummies/csv/mixed.csv",
+        ] {
+            let sheet = Sheet::with_config(config(path)).unwrap();
+            let bytes = sheet.to_bytes().unwrap();
+
+            assert_eq!(Sheet::from_bytes(&bytes).unwrap(), sheet, "{}", path);
+        }
+
+        let sheet = Sheet::with_config(config("./dummies/csv/air.csv").primary(None)).unwrap();
+        let read = Sheet::from_bytes(&sheet.to_bytes().unwrap()).unwrap();
+        assert_eq!(read.get_primary_key(), None);
+        assert_eq!(read, sheet);
+
+        let sheet =
+            Sheet::with_config(config("./dummies/csv/originals.csv").preserve_original(true))
+                .unwrap();
+        let bytes = sheet.to_bytes().unwrap();
+        assert_eq!(sheet.to_bytes().unwrap(), bytes);
+        assert_eq!(Sheet::from_bytes(&bytes).unwrap(), sheet);
+    }
+
+    #[test]
+    fn test_column_sheet_round_trip() {
+        let strategies = [TypesStrategy::Infer, TypesStrategy::InferWithUnion];
+        for path in [
+            "./dummies/csv/air.csv",
+            "./dummies/csv/nulls.csv",
+            "./dummies/csv/mixed.csv",
+            "./dummies/csv/originals.csv",
+        ] {
+            for types in strategies.clone() {
+                let config = config(path).types(types).preserve_original(true);
+                let sheet = ColumnSheet::with_config(config).unwrap();
+                let read = ColumnSheet::from_bytes(&sheet.to_bytes().unwrap()).unwrap();
+
+                assert_eq!(read.width(), sheet.width(), "{}", path);
+                assert_eq!(read.height(), sheet.height(), "{}", path);
+                assert_eq!(read.get_primary(), sheet.get_primary());
+                assert_eq!(read.get_null_string(), sheet.get_null_string());
+                assert!(read.headers().eq(sheet.headers()), "{}", path);
+                assert!(read.iter_rows().eq(sheet.iter_rows()), "{}", path);
+                for col in 0..sheet.width() {
+                    assert_eq!(
+                        read.get_col(col).unwrap().kind(),
+                        sheet.get_col(col).unwrap().kind()
+                    );
+                    for row in 0..sheet.height() {
+                        assert_eq!(read.original_text(col, row), sheet.original_text(col, row));
+                    }
+                }
+            }
+        }
+
+        let mut sheet = ColumnSheet::with_config(config("./dummies/csv/air.csv")).unwrap();
+        sheet.convert_col(1, DataType::F64).unwrap();
+        sheet.convert_col(2, DataType::ISize).unwrap();
+        sheet.convert_col_unchecked(3, DataType::Bool).unwrap();
+        sheet.set_col_unit(1, Some(Unit::Percent)).unwrap();
+        sheet.clear_cell(1, 3).unwrap();
+        sheet.clear_cell(2, 0).unwrap();
+        let read = ColumnSheet::from_bytes(&sheet.to_bytes().unwrap()).unwrap();
+        assert!(read.iter_rows().eq(sheet.iter_rows()));
+        assert_eq!(read.get_col(1).unwrap().unit(), Some(&Unit::Percent));
+        assert_eq!(read.get_col(2).unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_snapshot() {
+        let sheet = Sheet::with_config(config("./dummies/csv/air.csv")).unwrap();
+        let bytes = sheet.to_bytes().unwrap();
+
+        let mut flipped = bytes.clone();
+        flipped[bytes.len() / 2] ^= 0x10;
+        assert!(matches!(
+            Sheet::from_bytes(&flipped),
+            Err(Error::Snapshot(SnapshotError::ChecksumMismatch))
+        ));
+
+        assert!(matches!(
+            Sheet::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::Snapshot(SnapshotError::ChecksumMismatch))
+        ));
+        assert!(matches!(
+            Sheet::from_bytes(&bytes[..3]),
+            Err(Error::Snapshot(SnapshotError::Truncated))
+        ));
+
+        let mut stale = bytes.clone();
+        stale[0] = SNAPSHOT_VERSION + 1;
+        assert!(matches!(
+            Sheet::from_bytes(&stale),
+            Err(Error::Snapshot(SnapshotError::UnsupportedVersion(version)))
+                if version == SNAPSHOT_VERSION + 1
+        ));
+
+        assert!(matches!(
+            ColumnSheet::from_bytes(&bytes),
+            Err(ColumnSheetError::Snapshot(SnapshotError::WrongKind))
+        ));
+
+        let sheet = ColumnSheet::with_config(config("./dummies/csv/air.csv")).unwrap();
+        let mut bytes = sheet.to_bytes().unwrap();
+        let last = bytes.len() - 5;
+        bytes[last] ^= 0x01;
+        assert!(matches!(
+            ColumnSheet::from_bytes(&bytes),
+            Err(ColumnSheetError::Snapshot(SnapshotError::ChecksumMismatch))
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_size() {
+        let sheet = Sheet::with_config(config("./dummies/csv/air.csv")).unwrap();
+        let json = serde_json::to_vec(&sheet.iter_rows().collect::<Vec<_>>()).unwrap();
+
+        let bytes = sheet.to_bytes().unwrap();
+        assert!(
+            bytes.len() * 4 < json.len(),
+            "{} against {}",
+            bytes.len(),
+            json.len()
+        );
+
+        let bytes = ColumnSheet::with_config(config("./dummies/csv/air.csv"))
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(
+            bytes.len() * 4 < json.len(),
+            "{} against {}",
+            bytes.len(),
+            json.len()
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Detection of the dialect of a CSV file from a sample of it: its
+//! delimiter, quote character and whether its first record is a header.
+
+/// The delimiters tried, in order of preference when equally likely.
+const DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// The quote characters tried, in order of preference when equally likely.
+const QUOTES: [u8; 2] = [b'"', b'\''];
+
+/// The dialect detected from a sample of a CSV file. See [`sniff_sample`].
+///
+/// Each confidence is between `0.0` and `1.0`, with `0.0` when the sample
+/// gave no evidence either way and the default was kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SniffResult {
+    /// The most likely field delimiter, one of `,`, `;`, `\t` or `|`
+    pub delimiter: u8,
+    pub delimiter_confidence: f64,
+    /// The most likely quote character, either `"` or `'`
+    pub quote: u8,
+    pub quote_confidence: f64,
+    /// Whether the first record is likely a header
+    pub has_header: bool,
+    pub header_confidence: f64,
+}
+
+impl Default for SniffResult {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            delimiter_confidence: 0.0,
+            quote: b'"',
+            quote_confidence: 0.0,
+            has_header: false,
+            header_confidence: 0.0,
+        }
+    }
+}
+
+/// Detects the dialect of the CSV data in `sample`, such as the start of a
+/// file. A last record cut short by the end of the sample should be left
+/// out.
+///
+/// The quote character is the one most often found at the edges of fields,
+/// and the delimiter the one splitting the most records into the same
+/// number of fields, of at least two. The first record is taken as a header
+/// if its fields look unlike the fields below them: text above a numeric or
+/// boolean column, or of another length than a column of fixed length text.
+/// A header of numbers above numeric columns is only detected through its
+/// other columns.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::repr::sniff_sample;
+///
+/// let sniffed = sniff_sample("Name;Age\n\"Smith; John\";42\nJane;37\n");
+///
+/// assert_eq!(sniffed.delimiter, b';');
+/// assert_eq!(sniffed.quote, b'"');
+/// assert!(sniffed.has_header);
+/// ```
+pub fn sniff_sample(sample: &str) -> SniffResult {
+    let mut sniffed = SniffResult::default();
+
+    let quotes = QUOTES.map(|quote| quote_edges(sample, quote));
+    let total = quotes.iter().sum::<usize>();
+    if total != 0 {
+        let best = (0..QUOTES.len()).fold(0, |best, idx| {
+            if quotes[idx] > quotes[best] {
+                idx
+            } else {
+                best
+            }
+        });
+        sniffed.quote = QUOTES[best];
+        sniffed.quote_confidence = quotes[best] as f64 / total as f64;
+    }
+
+    let mut scores = DELIMITERS
+        .iter()
+        .map(|delimiter| {
+            let records = split_records(sample, *delimiter, sniffed.quote);
+            let (width, consistency) = modal_width(&records);
+            (*delimiter, width, consistency, records)
+        })
+        .collect::<Vec<_>>();
+    // Stable, so equally likely delimiters keep their order of preference
+    scores.sort_by(|x, y| y.2.total_cmp(&x.2).then(y.1.cmp(&x.1)));
+
+    let mut scores = scores.into_iter();
+    let Some((delimiter, _, consistency, records)) = scores.next() else {
+        return sniffed;
+    };
+    if consistency == 0.0 {
+        return sniffed;
+    }
+    let runner_up = scores.next().map_or(0.0, |score| score.2);
+
+    sniffed.delimiter = delimiter;
+    sniffed.delimiter_confidence = consistency - runner_up;
+
+    let (votes_for, votes_against) = header_votes(&records);
+    let votes = votes_for + votes_against;
+    if votes != 0 {
+        sniffed.has_header = votes_for > votes_against;
+        sniffed.header_confidence = votes_for.abs_diff(votes_against) as f64 / votes as f64;
+    }
+
+    sniffed
+}
+
+/// Counts the occurrences of `quote` at the start or end of a field, next
+/// to a line break or any of the delimiters tried.
+fn quote_edges(sample: &str, quote: u8) -> usize {
+    let bytes = sample.as_bytes();
+    let is_edge = |byte: Option<&u8>| match byte {
+        None => true,
+        Some(byte) => matches!(byte, b'\n' | b'\r') || DELIMITERS.contains(byte),
+    };
+
+    (0..bytes.len())
+        .filter(|idx| bytes[*idx] == quote)
+        .filter(|idx| {
+            let before = idx.checked_sub(1).and_then(|idx| bytes.get(idx));
+            is_edge(before) || is_edge(bytes.get(idx + 1))
+        })
+        .count()
+}
+
+/// Splits `sample` into records of fields as the `csv` crate would: quoted
+/// fields may hold delimiters, line breaks and doubled quotes, and empty
+/// lines are skipped.
+fn split_records(sample: &str, delimiter: u8, quote: u8) -> Vec<Vec<String>> {
+    let (delimiter, quote) = (char::from(delimiter), char::from(quote));
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = sample.chars().peekable();
+
+    while let Some(next) = chars.next() {
+        match next {
+            _ if quoted && next == quote => {
+                if chars.peek() == Some(&quote) {
+                    field.push(quote);
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            }
+            _ if quoted => field.push(next),
+            _ if next == quote && field.is_empty() => quoted = true,
+            _ if next == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' | '\n' => {
+                if !record.is_empty() || !field.is_empty() {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ => field.push(next),
+        }
+    }
+
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+/// Returns the most common number of fields of `records`, the widest among
+/// equally common ones, and the share of records with it. The share is
+/// `0.0` if the number is less than two, as nothing was delimited.
+fn modal_width(records: &[Vec<String>]) -> (usize, f64) {
+    let mut counts = std::collections::BTreeMap::new();
+    for record in records {
+        *counts.entry(record.len()).or_insert(0usize) += 1;
+    }
+
+    let Some((width, count)) = counts
+        .into_iter()
+        .max_by(|x, y| x.1.cmp(&y.1).then(x.0.cmp(&y.0)))
+    else {
+        return (0, 0.0);
+    };
+
+    if width < 2 {
+        return (width, 0.0);
+    }
+
+    (width, count as f64 / records.len() as f64)
+}
+
+/// The kind of value a field looks like.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldClass {
+    Empty,
+    Bool,
+    Number,
+    Text,
+}
+
+impl FieldClass {
+    fn of(field: &str) -> Self {
+        let field = field.trim();
+
+        if field.is_empty() {
+            Self::Empty
+        } else if field.eq_ignore_ascii_case("true") || field.eq_ignore_ascii_case("false") {
+            Self::Bool
+        } else if field.parse::<f64>().map_or(false, f64::is_finite) {
+            Self::Number
+        } else {
+            Self::Text
+        }
+    }
+}
+
+/// Returns the number of columns suggesting the first record is a header,
+/// and the number suggesting it is not.
+fn header_votes(records: &[Vec<String>]) -> (usize, usize) {
+    let Some((first, rest)) = records.split_first() else {
+        return (0, 0);
+    };
+    let (mut votes_for, mut votes_against) = (0, 0);
+
+    for (col, label) in first.iter().enumerate() {
+        let values = rest
+            .iter()
+            .filter_map(|record| record.get(col))
+            .filter(|value| FieldClass::of(value) != FieldClass::Empty)
+            .collect::<Vec<_>>();
+        let Some(kind) = values.first().map(|value| FieldClass::of(value)) else {
+            continue;
+        };
+        if values.iter().any(|value| FieldClass::of(value) != kind) {
+            continue;
+        }
+
+        let label_kind = FieldClass::of(label);
+        if kind != FieldClass::Text {
+            // A number above numbers could be either
+            if label_kind == FieldClass::Text {
+                votes_for += 1;
+            }
+            continue;
+        }
+
+        let length = values[0].chars().count();
+        if values.len() < 2 || values.iter().any(|value| value.chars().count() != length) {
+            continue;
+        }
+        if label.chars().count() == length {
+            votes_against += 1;
+        } else {
+            votes_for += 1;
+        }
+    }
+
+    (votes_for, votes_against)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `rows` as CSV with `delimiter`, quoting fields which hold it.
+    fn generate(rows: &[Vec<&str>], delimiter: char) -> String {
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|field| match field.contains(delimiter) {
+                        true => format!("\"{field}\""),
+                        false => field.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(&delimiter.to_string())
+            })
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_sniff_matrix() {
+        let header = vec!["Name", "Team", "Score", "Active"];
+        let body = vec![
+            vec!["Smith, John", "A;B", "42", "true"],
+            vec!["Jane Doe", "C|D", "37.5", "false"],
+            vec!["Ade\tKofi", "E", "12", "true"],
+            vec!["Li", "F", "", "false"],
+        ];
+
+        for delimiter in [',', ';', '\t', '|'] {
+            for has_header in [true, false] {
+                let mut rows = body.clone();
+                if has_header {
+                    rows.insert(0, header.clone());
+                }
+
+                let sniffed = sniff_sample(&generate(&rows, delimiter));
+                let case = format!("{delimiter:?} with header: {has_header}");
+                assert_eq!(char::from(sniffed.delimiter), delimiter, "{case}");
+                assert!(sniffed.delimiter_confidence > 0.0, "{case}");
+                assert_eq!(sniffed.has_header, has_header, "{case}");
+                assert_eq!(sniffed.quote, b'"', "{case}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sniff_tricky_samples() {
+        // Numeric looking headers are found through the text column
+        let sniffed = sniff_sample("Month,1958,1959\nJAN,340,360\nFEB,318,342\nMAR,362,406\n");
+        assert!(sniffed.has_header);
+        assert_eq!(sniffed.header_confidence, 1.0);
+
+        // Only numbers gives no evidence of a header
+        let sniffed = sniff_sample("1,2,3\n4,5,6\n7,8,9\n");
+        assert!(!sniffed.has_header);
+        assert_eq!(sniffed.header_confidence, 0.0);
+        assert_eq!(sniffed.delimiter, b',');
+
+        // Delimiters within quotes are not counted
+        let sample = "'a;b';'c;d';e\n'f;g';h;i\n'j;k';l;m\n";
+        let sniffed = sniff_sample(sample);
+        assert_eq!(sniffed.quote, b'\'');
+        assert_eq!(sniffed.delimiter, b';');
+        assert_eq!(sniffed.delimiter_confidence, 1.0);
+
+        // Quoted line breaks stay within their record
+        let sample = "id|note\n1|\"first\nline\"\n2|\"second\nline\"\n";
+        let sniffed = sniff_sample(sample);
+        assert_eq!(sniffed.delimiter, b'|');
+        assert!(sniffed.has_header);
+
+        // A single column has nothing to delimit
+        let sniffed = sniff_sample("alpha\nbeta\ngamma\n");
+        assert_eq!(sniffed, SniffResult::default());
+        assert_eq!(sniff_sample(""), SniffResult::default());
+    }
+}
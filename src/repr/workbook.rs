@@ -0,0 +1,470 @@
+//! Several named sheets held together, such as raw data alongside the lookup
+//! tables and summaries derived from it.
+
+use std::{error, fmt};
+
+#[cfg(feature = "serde")]
+use super::snapshot::{self, Bytes, SnapshotError, SnapshotKind};
+use super::{col_sheet, Sheet};
+#[cfg(feature = "serde")]
+use col_sheet::ColumnSheet;
+
+/// Named sheets kept in the order they were inserted.
+///
+/// A workbook holds [`Sheet`]s by default, or [`ColumnSheet`](col_sheet::ColumnSheet)s as a
+/// `Workbook<ColumnSheet>`. Names are unique within a workbook.
+///
+/// # Example
+///
+/// ```
+/// use modav_core::repr::{Sheet, Workbook};
+///
+/// let mut workbook = Workbook::new();
+/// workbook.insert("raw", Sheet::from_rows(vec![], vec![], 0).unwrap()).unwrap();
+/// workbook.insert("summary", Sheet::from_rows(vec![], vec![], 0).unwrap()).unwrap();
+///
+/// assert!(workbook.insert("raw", Sheet::from_rows(vec![], vec![], 0).unwrap()).is_err());
+/// assert_eq!(workbook.names().collect::<Vec<_>>(), vec!["raw", "summary"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workbook<S = Sheet> {
+    sheets: Vec<(String, S)>,
+}
+
+impl<S> Default for Workbook<S> {
+    fn default() -> Self {
+        Self { sheets: Vec::new() }
+    }
+}
+
+impl<S> Workbook<S> {
+    /// Returns an empty workbook.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `sheet` as `name` after the existing sheets.
+    ///
+    /// Returns an error, leaving the workbook unchanged, if another sheet is
+    /// already named `name`.
+    pub fn insert(&mut self, name: impl Into<String>, sheet: S) -> Result<(), WorkbookError> {
+        let name = name.into();
+        if self.contains(&name) {
+            return Err(WorkbookError::DuplicateName(name));
+        }
+
+        self.sheets.push((name, sheet));
+
+        Ok(())
+    }
+
+    /// Returns the sheet named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&S> {
+        self.sheets
+            .iter()
+            .find(|(own, _)| own == name)
+            .map(|(_, sheet)| sheet)
+    }
+
+    /// Returns a mutable reference to the sheet named `name`, if any.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut S> {
+        self.sheets
+            .iter_mut()
+            .find(|(own, _)| own == name)
+            .map(|(_, sheet)| sheet)
+    }
+
+    /// Removes and returns the sheet named `name`, if any. The other sheets
+    /// keep their order.
+    pub fn remove(&mut self, name: &str) -> Option<S> {
+        let pos = self.position(name)?;
+
+        Some(self.sheets.remove(pos).1)
+    }
+
+    /// Renames the sheet named `from` to `to`, keeping its position.
+    ///
+    /// Returns an error if no sheet is named `from`, or if another sheet is
+    /// already named `to`.
+    pub fn rename(&mut self, from: &str, to: impl Into<String>) -> Result<(), WorkbookError> {
+        let to = to.into();
+        let pos = self
+            .position(from)
+            .ok_or_else(|| WorkbookError::UnknownSheet(from.to_string()))?;
+
+        if from != to && self.contains(&to) {
+            return Err(WorkbookError::DuplicateName(to));
+        }
+
+        self.sheets[pos].0 = to;
+
+        Ok(())
+    }
+
+    /// Returns true if a sheet is named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Returns the number of sheets.
+    pub fn len(&self) -> usize {
+        self.sheets.len()
+    }
+
+    /// Returns true if the workbook has no sheets.
+    pub fn is_empty(&self) -> bool {
+        self.sheets.is_empty()
+    }
+
+    /// Returns an iterator over the names of the sheets, in insertion order.
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.sheets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns an iterator over the sheets with their names, in insertion
+    /// order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &S)> {
+        self.sheets
+            .iter()
+            .map(|(name, sheet)| (name.as_str(), sheet))
+    }
+
+    /// Returns an iterator over mutable references to the sheets with their
+    /// names, in insertion order.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = (&str, &mut S)> {
+        self.sheets
+            .iter_mut()
+            .map(|(name, sheet)| (name.as_str(), sheet))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.sheets.iter().position(|(own, _)| own == name)
+    }
+
+    /// Returns the sheet named `name`, or an error if there is none.
+    fn sheet(&self, name: &str) -> Result<&S, WorkbookError> {
+        self.get(name)
+            .ok_or_else(|| WorkbookError::UnknownSheet(name.to_string()))
+    }
+}
+
+impl Workbook<Sheet> {
+    /// Returns the sheet named `left` joined with the sheet named `right` on
+    /// their columns at `left_col` and `right_col`. See [`Sheet::join`].
+    ///
+    /// Returns an error if either sheet does not exist or cannot be joined.
+    pub fn join(
+        &self,
+        left: &str,
+        right: &str,
+        left_col: usize,
+        right_col: usize,
+    ) -> Result<Sheet, WorkbookError> {
+        let sheet = self
+            .sheet(left)?
+            .join(self.sheet(right)?, left_col, right_col)?;
+
+        Ok(sheet)
+    }
+
+    /// Returns the sheets named by `names` concatenated in that order. See
+    /// [`Sheet::concat`].
+    ///
+    /// Returns an error if `names` is empty, or if any sheet does not exist
+    /// or cannot be concatenated.
+    pub fn concat(&self, names: &[&str]) -> Result<Sheet, WorkbookError> {
+        let (first, rest) = names.split_first().ok_or(WorkbookError::NoSheets)?;
+
+        let mut sheet = self.sheet(first)?.clone();
+        for name in rest {
+            sheet = sheet.concat(self.sheet(name)?)?;
+        }
+
+        Ok(sheet)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S> Workbook<S> {
+    /// Writes every sheet with `encode` into a single snapshot.
+    fn encode<E>(&self, encode: impl Fn(&S) -> Result<Vec<u8>, E>) -> Result<Vec<u8>, WorkbookError>
+    where
+        WorkbookError: From<E>,
+    {
+        let sheets = self
+            .sheets
+            .iter()
+            .map(|(name, sheet)| Ok((name.as_str(), Bytes(encode(sheet)?))))
+            .collect::<Result<Vec<_>, WorkbookError>>()?;
+
+        Ok(snapshot::encode(SnapshotKind::Workbook, &sheets)?)
+    }
+
+    /// Reads a snapshot written by [`Workbook::encode`], reading each sheet
+    /// with `decode`.
+    fn decode<E>(
+        bytes: &[u8],
+        decode: impl Fn(&[u8]) -> Result<S, E>,
+    ) -> Result<Self, WorkbookError>
+    where
+        WorkbookError: From<E>,
+    {
+        let sheets: Vec<(String, Bytes)> = snapshot::decode(SnapshotKind::Workbook, bytes)?;
+
+        let mut workbook = Self::new();
+        for (name, Bytes(bytes)) in sheets {
+            workbook.insert(name, decode(&bytes)?)?;
+        }
+
+        Ok(workbook)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Workbook<Sheet> {
+    /// Returns a binary snapshot of every sheet of the workbook with its
+    /// name. See [`Sheet::to_bytes`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WorkbookError> {
+        self.encode(Sheet::to_bytes)
+    }
+
+    /// Reads a workbook from a snapshot written by [`Workbook::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WorkbookError> {
+        Self::decode(bytes, Sheet::from_bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Workbook<ColumnSheet> {
+    /// Returns a binary snapshot of every sheet of the workbook with its
+    /// name. See [`ColumnSheet::to_bytes`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WorkbookError> {
+        self.encode(ColumnSheet::to_bytes)
+    }
+
+    /// Reads a workbook from a snapshot written by [`Workbook::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WorkbookError> {
+        Self::decode(bytes, ColumnSheet::from_bytes)
+    }
+}
+
+impl<S> IntoIterator for Workbook<S> {
+    type Item = (String, S);
+    type IntoIter = std::vec::IntoIter<(String, S)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sheets.into_iter()
+    }
+}
+
+/// Error from working with the sheets of a [`Workbook`].
+#[derive(Debug)]
+pub enum WorkbookError {
+    /// Another sheet already has the name
+    DuplicateName(String),
+    /// No sheet has the name
+    UnknownSheet(String),
+    /// No sheets were named
+    NoSheets,
+    /// Error from a [`Sheet`] of the workbook
+    Sheet(super::Error),
+    /// Error from a [`ColumnSheet`](col_sheet::ColumnSheet) of the workbook
+    ColumnSheet(col_sheet::Error),
+    /// Error from reading or writing a binary snapshot of the workbook
+    #[cfg(feature = "serde")]
+    Snapshot(SnapshotError),
+}
+
+impl From<super::Error> for WorkbookError {
+    fn from(value: super::Error) -> Self {
+        Self::Sheet(value)
+    }
+}
+
+impl From<col_sheet::Error> for WorkbookError {
+    fn from(value: col_sheet::Error) -> Self {
+        Self::ColumnSheet(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SnapshotError> for WorkbookError {
+    fn from(value: SnapshotError) -> Self {
+        Self::Snapshot(value)
+    }
+}
+
+impl fmt::Display for WorkbookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateName(name) => write!(f, "A sheet named `{name}` already exists"),
+            Self::UnknownSheet(name) => write!(f, "No sheet is named `{name}`"),
+            Self::NoSheets => write!(f, "No sheets were named"),
+            Self::Sheet(error) => error.fmt(f),
+            Self::ColumnSheet(error) => error.fmt(f),
+            #[cfg(feature = "serde")]
+            Self::Snapshot(error) => error.fmt(f),
+        }
+    }
+}
+
+impl error::Error for WorkbookError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Sheet(error) => Some(error),
+            Self::ColumnSheet(error) => Some(error),
+            #[cfg(feature = "serde")]
+            Self::Snapshot(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repr::{ColumnHeader, ColumnType, Data};
+
+    fn sheet(headers: [(&str, ColumnType); 2], rows: Vec<[Data; 2]>) -> Sheet {
+        let headers = headers
+            .iter()
+            .map(|(label, kind)| ColumnHeader::new(label.to_string(), *kind))
+            .collect();
+        let rows = rows.into_iter().map(Vec::from).collect();
+
+        Sheet::from_rows(rows, headers, 0).unwrap()
+    }
+
+    fn sales() -> Sheet {
+        sheet(
+            [("Region", ColumnType::Text), ("Sales", ColumnType::Integer)],
+            vec![
+                ["north".into(), 12.into()],
+                ["south".into(), 7.into()],
+                ["east".into(), 3.into()],
+                ["north".into(), 5.into()],
+            ],
+        )
+    }
+
+    fn regions() -> Sheet {
+        sheet(
+            [("Region", ColumnType::Text), ("Manager", ColumnType::Text)],
+            vec![
+                ["south".into(), "Ama".into()],
+                ["north".into(), "Kofi".into()],
+                ["west".into(), "Esi".into()],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut workbook = Workbook::new();
+        for name in ["sales", "regions", "summary", "lookup"] {
+            workbook.insert(name, sales()).unwrap();
+        }
+        assert_eq!(workbook.len(), 4);
+
+        assert!(matches!(
+            workbook.insert("regions", regions()),
+            Err(WorkbookError::DuplicateName(name)) if name == "regions"
+        ));
+        assert_eq!(workbook.get("regions"), Some(&sales()));
+
+        assert_eq!(workbook.remove("regions"), Some(sales()));
+        assert_eq!(workbook.remove("regions"), None);
+        workbook.insert("regions", regions()).unwrap();
+        assert_eq!(
+            workbook.names().collect::<Vec<_>>(),
+            vec!["sales", "summary", "lookup", "regions"]
+        );
+
+        workbook.rename("summary", "totals").unwrap();
+        workbook.rename("lookup", "lookup").unwrap();
+        assert!(matches!(
+            workbook.rename("totals", "sales"),
+            Err(WorkbookError::DuplicateName(_))
+        ));
+        assert!(matches!(
+            workbook.rename("summary", "other"),
+            Err(WorkbookError::UnknownSheet(_))
+        ));
+        assert_eq!(
+            workbook.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["sales", "totals", "lookup", "regions"]
+        );
+
+        workbook.get_mut("totals").unwrap().remove_col(1).unwrap();
+        assert_eq!(workbook.get("totals").unwrap().width(), 1);
+        assert!(!workbook.contains("summary"));
+
+        let names: Vec<_> = workbook.into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["sales", "totals", "lookup", "regions"]);
+    }
+
+    #[test]
+    fn test_join() {
+        let mut workbook = Workbook::new();
+        workbook.insert("sales", sales()).unwrap();
+        workbook.insert("regions", regions()).unwrap();
+
+        let joined = workbook.join("sales", "regions", 0, 0).unwrap();
+        assert_eq!(joined, sales().join(&regions(), 0, 0).unwrap());
+        assert_eq!(joined.width(), 3);
+        assert_eq!(joined.get_headers()[2].label, "Manager");
+        assert_eq!(joined.column_kind(1), Some(ColumnType::Integer));
+
+        let managers = joined
+            .iter_rows()
+            .map(|row| row.get_cell_by_index(2).unwrap().get_data().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            managers,
+            vec![Data::from("Kofi"), Data::from("Ama"), Data::from("Kofi")]
+        );
+
+        assert!(matches!(
+            workbook.join("sales", "missing", 0, 0),
+            Err(WorkbookError::UnknownSheet(name)) if name == "missing"
+        ));
+        assert!(matches!(
+            workbook.join("sales", "regions", 0, 2),
+            Err(WorkbookError::Sheet(_))
+        ));
+    }
+
+    #[test]
+    fn test_concat() {
+        let mut workbook = Workbook::new();
+        workbook.insert("sales", sales()).unwrap();
+        workbook.insert("regions", regions()).unwrap();
+
+        let sheet = workbook.concat(&["sales", "sales"]).unwrap();
+        assert_eq!(sheet.height(), 8);
+        assert_eq!(sheet.column_kind(1), Some(ColumnType::Integer));
+
+        let sheet = workbook.concat(&["sales", "regions"]).unwrap();
+        assert_eq!(sheet.height(), 7);
+        assert_eq!(sheet.column_kind(1), Some(ColumnType::Mixed));
+        sheet.validate().unwrap();
+
+        assert!(matches!(workbook.concat(&[]), Err(WorkbookError::NoSheets)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot() {
+        let mut workbook = Workbook::new();
+        workbook.insert("sales", sales()).unwrap();
+        workbook.insert("regions", regions()).unwrap();
+
+        let bytes = workbook.to_bytes().unwrap();
+        assert_eq!(Workbook::<Sheet>::from_bytes(&bytes).unwrap(), workbook);
+        assert!(Workbook::<ColumnSheet>::from_bytes(&bytes).is_err());
+        assert!(
+            Workbook::<Sheet>::from_bytes(&workbook.get("sales").unwrap().to_bytes().unwrap())
+                .is_err()
+        );
+    }
+}
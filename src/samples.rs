@@ -0,0 +1,82 @@
+//! Small datasets bundled with the crate, for examples and doctests.
+//!
+//! Enabled by the `samples` feature. Each dataset is embedded in the binary,
+//! so loading one does not depend on the working directory.
+//!
+//! ```
+//! use modav_core::samples;
+//!
+//! for name in samples::NAMES {
+//!     assert!(samples::csv(name).is_some());
+//! }
+//!
+//! let sheet = samples::air();
+//! assert_eq!(sheet.height(), 12);
+//! assert_eq!(sheet.width(), 4);
+//! ```
+
+use crate::repr::{col_sheet::ColumnSheet, Config, HeaderStrategy, Sheet, TypesStrategy};
+
+const AIR: &str = include_str!("../dummies/csv/air.csv");
+const STACKED: &str = include_str!("../dummies/csv/stacked.csv");
+const INFER: &str = include_str!("../dummies/csv/infer.csv");
+
+/// The names of the bundled datasets.
+pub const NAMES: [&str; 3] = ["air", "stacked", "infer"];
+
+/// Returns the raw CSV of the dataset called `name`, if any. See [`NAMES`].
+pub fn csv(name: &str) -> Option<&'static str> {
+    match name {
+        "air" => Some(AIR),
+        "stacked" => Some(STACKED),
+        "infer" => Some(INFER),
+        _ => None,
+    }
+}
+
+/// The configuration used for every dataset: labels are read, fields are
+/// trimmed and types are inferred.
+fn config(name: &'static str) -> Config<&'static str> {
+    Config::new(name)
+        .trim(true)
+        .labels(HeaderStrategy::ReadLabels)
+        .types(TypesStrategy::Infer)
+}
+
+fn sheet(name: &'static str) -> Sheet {
+    let data = csv(name).expect("Bundled sample should exist");
+    Sheet::from_reader(data.as_bytes(), config(name)).expect("Bundled sample should load")
+}
+
+fn column_sheet(name: &'static str) -> ColumnSheet {
+    let data = csv(name).expect("Bundled sample should exist");
+    ColumnSheet::from_reader(data.as_bytes(), config(name)).expect("Bundled sample should load")
+}
+
+/// Monthly air passenger numbers for 1958 to 1960. A text `Month` column
+/// followed by three integer columns, one for each year.
+pub fn air() -> Sheet {
+    sheet("air")
+}
+
+/// [`air`] as a [`ColumnSheet`].
+pub fn air_columns() -> ColumnSheet {
+    column_sheet("air")
+}
+
+/// Daily sales of four products. A text `Day of Week` column followed by an
+/// integer column for each product and a `Total` column.
+pub fn stacked() -> Sheet {
+    sheet("stacked")
+}
+
+/// [`stacked`] as a [`ColumnSheet`].
+pub fn stacked_columns() -> ColumnSheet {
+    column_sheet("stacked")
+}
+
+/// A few rows with columns of every type, including a mixed column and a
+/// column with an empty cell.
+pub fn infer() -> Sheet {
+    sheet("infer")
+}
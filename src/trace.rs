@@ -0,0 +1,78 @@
+//! Optional [`tracing`](https://docs.rs/tracing) instrumentation, enabled by
+//! the `trace` feature.
+//!
+//! Without the feature the macros here expand to nothing and [`Timed`] is a
+//! zero sized no-op.
+
+/// Enters a debug span which records its duration in microseconds as
+/// `elapsed_us` when dropped. Fields follow the syntax of `tracing::span!`.
+#[cfg(feature = "trace")]
+macro_rules! trace_span {
+    ($name:literal $(, $($fields:tt)+)?) => {
+        $crate::trace::Timed::new(tracing::debug_span!(
+            $name,
+            elapsed_us = tracing::field::Empty
+            $(, $($fields)+)?
+        ))
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        $crate::trace::Timed
+    };
+}
+
+/// Emits a debug event. Arguments follow the syntax of `tracing::debug!`.
+#[cfg(feature = "trace")]
+macro_rules! trace_debug {
+    ($($args:tt)+) => {
+        tracing::debug!($($args)+)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_debug {
+    ($($args:tt)+) => {};
+}
+
+/// An entered span, created by `trace_span!`.
+#[cfg(feature = "trace")]
+pub(crate) struct Timed {
+    span: tracing::span::EnteredSpan,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "trace")]
+impl Timed {
+    pub(crate) fn new(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Records `value` for a field declared when the span was created.
+    #[cfg_attr(not(feature = "csv"), allow(dead_code))]
+    pub(crate) fn record<V: tracing::Value>(&self, field: &str, value: V) {
+        self.span.record(field, value);
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for Timed {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed().as_micros() as u64;
+        self.span.record("elapsed_us", elapsed);
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+pub(crate) struct Timed;
+
+#[cfg(not(feature = "trace"))]
+impl Timed {
+    #[cfg_attr(not(feature = "csv"), allow(dead_code))]
+    pub(crate) fn record<V>(&self, _field: &str, _value: V) {}
+}